@@ -0,0 +1,208 @@
+//! Account lists for the instructions `send` submits, mirroring what the program looks up in
+//! remaining accounts for the vault's configuration.
+
+use anchor_lang::{system_program, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+use strike_client::bulk_account_indexes;
+use strike_client::pda::{
+    admin_nonce_address, allowance_address, blocklist_address, event_authority_address,
+    fee_treasury_address, named_treasury_address, nonce_address, recipient_allowlist_address,
+    treasury_address, usd_cap_address,
+};
+use strike_example::instructions::{
+    Asset, BulkAccountIndexes, BulkWithdrawalTicket, Vault, WithdrawalTicket,
+};
+
+pub fn withdraw(
+    address: &Pubkey,
+    vault: &Vault,
+    payer: &Pubkey,
+    ticket: &WithdrawalTicket,
+) -> Vec<AccountMeta> {
+    let mut accounts = strike_example::accounts::Withdraw {
+        vault: *address,
+        treasury: treasury_address(address),
+        fee_treasury: fee_treasury_address(address),
+        recipient: ticket.recipient,
+        nonce_account: Some(nonce_address(address, ticket.request_id)),
+        receipt: None,
+        payer: *payer,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        event_authority: event_authority_address(),
+        program: strike_example::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(allowances(address, std::slice::from_ref(ticket)));
+    if let Some(name) = &ticket.treasury {
+        accounts.push(AccountMeta::new(
+            named_treasury_address(address, name),
+            false,
+        ));
+    }
+    accounts.extend(gating(address, vault, std::slice::from_ref(ticket)));
+    accounts.extend(spl(address, vault, payer, std::slice::from_ref(ticket)));
+    accounts
+}
+
+/// The accounts, and where in the remaining ones each ticket's recipient, mints and token
+/// accounts sit.
+pub fn bulk_withdraw(
+    address: &Pubkey,
+    vault: &Vault,
+    payer: &Pubkey,
+    bulk_ticket: &BulkWithdrawalTicket,
+) -> (Vec<AccountMeta>, Vec<BulkAccountIndexes>) {
+    let mut accounts = strike_example::accounts::BulkWithdraw {
+        vault: *address,
+        treasury: treasury_address(address),
+        fee_treasury: fee_treasury_address(address),
+        payer: *payer,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        event_authority: event_authority_address(),
+        program: strike_example::ID,
+    }
+    .to_account_metas(None);
+    let named = accounts.len();
+    accounts.extend(
+        bulk_ticket
+            .tickets
+            .iter()
+            .map(|ticket| AccountMeta::new(nonce_address(address, ticket.request_id), false)),
+    );
+    for ticket in &bulk_ticket.tickets {
+        let recipient = AccountMeta::new(ticket.recipient, false);
+        if !accounts.contains(&recipient) {
+            accounts.push(recipient);
+        }
+    }
+    accounts.extend(allowances(address, &bulk_ticket.tickets));
+    accounts.extend(gating(address, vault, &bulk_ticket.tickets));
+    accounts.extend(spl(address, vault, payer, &bulk_ticket.tickets));
+    let account_indexes =
+        bulk_account_indexes(bulk_ticket, address, &spl_token::ID, &accounts[named..]);
+    (accounts, account_indexes)
+}
+
+pub fn rotate_validators(address: &Pubkey, payer: &Pubkey, request_id: u64) -> Vec<AccountMeta> {
+    strike_example::accounts::RotateValidator {
+        vault: *address,
+        nonce_account: admin_nonce_address(address, request_id),
+        payer: *payer,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None)
+}
+
+/// Allowance PDAs, one per (recipient, asset) line.
+fn allowances(address: &Pubkey, tickets: &[WithdrawalTicket]) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = Vec::new();
+    for ticket in tickets {
+        for withdrawal in &ticket.withdrawals {
+            let meta = AccountMeta::new(
+                allowance_address(address, &ticket.recipient, &withdrawal.asset),
+                false,
+            );
+            if !accounts.contains(&meta) {
+                accounts.push(meta);
+            }
+        }
+    }
+    accounts
+}
+
+/// Recipient allowlist, blocklist and USD cap PDAs the vault's flags make withdrawals check.
+fn gating(address: &Pubkey, vault: &Vault, tickets: &[WithdrawalTicket]) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+    if vault.recipient_allowlist {
+        accounts.push(AccountMeta::new_readonly(
+            recipient_allowlist_address(address),
+            false,
+        ));
+    }
+    if vault.has_blocklist {
+        accounts.push(AccountMeta::new_readonly(blocklist_address(address), false));
+    }
+    if vault.has_usd_caps {
+        for ticket in tickets {
+            for withdrawal in &ticket.withdrawals {
+                let meta =
+                    AccountMeta::new_readonly(usd_cap_address(address, &withdrawal.asset), false);
+                if !accounts.contains(&meta) {
+                    accounts.push(meta);
+                }
+            }
+        }
+        // Fees paid in the fee mint are priced by its USD cap.
+        if vault.fee_mint != Pubkey::default() {
+            let fee_asset = Asset::SplToken {
+                mint: vault.fee_mint,
+            };
+            let meta = AccountMeta::new_readonly(usd_cap_address(address, &fee_asset), false);
+            if !accounts.contains(&meta) {
+                accounts.push(meta);
+            }
+        }
+    }
+    accounts
+}
+
+/// Mint, recipient ATA and vault ATA per SPL or NFT line, plus the fee treasury ATA while the
+/// vault charges a fee. Lines paying their fee in the vault's fee mint need that mint and the
+/// payer's and fee treasury's ATAs of it instead.
+fn spl(
+    address: &Pubkey,
+    vault: &Vault,
+    payer: &Pubkey,
+    tickets: &[WithdrawalTicket],
+) -> Vec<AccountMeta> {
+    let fee_treasury = fee_treasury_address(address);
+    let mut accounts: Vec<AccountMeta> = Vec::new();
+    let mut pays_in_fee_mint = false;
+    for ticket in tickets {
+        for withdrawal in &ticket.withdrawals {
+            let fee_in_kind = vault.pays_fee_in_kind(&withdrawal.asset);
+            pays_in_fee_mint |= vault.fee_bps > 0 && !fee_in_kind;
+            let mint = match withdrawal.asset {
+                Asset::Sol => continue,
+                Asset::SplToken { mint } | Asset::Nft { mint } => mint,
+            };
+            let mut metas = vec![
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(
+                    get_associated_token_address(&ticket.recipient, &mint),
+                    false,
+                ),
+                AccountMeta::new(get_associated_token_address(address, &mint), false),
+            ];
+            if vault.fee_bps > 0 && fee_in_kind {
+                metas.push(AccountMeta::new(
+                    get_associated_token_address(&fee_treasury, &mint),
+                    false,
+                ));
+            }
+            for meta in metas {
+                if !accounts.contains(&meta) {
+                    accounts.push(meta);
+                }
+            }
+        }
+    }
+    if pays_in_fee_mint {
+        let mint = vault.fee_mint;
+        for meta in [
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(get_associated_token_address(payer, &mint), false),
+            AccountMeta::new(get_associated_token_address(&fee_treasury, &mint), false),
+        ] {
+            if !accounts.contains(&meta) {
+                accounts.push(meta);
+            }
+        }
+    }
+    accounts
+}
@@ -0,0 +1,25 @@
+//! Hex encoding for ticket files, signer addresses, keys and signatures.
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return Err(format!("odd-length hex {:?}", hex));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| format!("invalid hex {:?}", hex))
+        })
+        .collect()
+}
+
+/// 20-byte Ethereum-style signer address.
+pub fn parse_address(hex: &str) -> Result<[u8; 20], String> {
+    from_hex(hex)?
+        .try_into()
+        .map_err(|_| format!("signer address {:?} must be 20 bytes", hex))
+}
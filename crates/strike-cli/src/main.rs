@@ -0,0 +1,552 @@
+//! Operator CLI for strike-example vaults.
+//!
+//! Usage: strike-cli <RPC_URL> <KEYPAIR> <COMMAND> [ARGS...]
+//!
+//! Tickets travel between operators as files holding the hex of the Borsh-encoded `AnyTicket`,
+//! so one operator builds a ticket, every validator hashes and signs the same bytes, and anyone
+//! holding the ticket and enough signature lines can send it. `KEYPAIR` pays for and signs the
+//! transactions `init` and `send` submit; the other commands never load it.
+
+mod accounts;
+mod encoding;
+
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize, InstructionData};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use solana_sdk::transaction::Transaction;
+use strike_client::pda::{
+    admin_nonce_address, nonce_address, treasury_address, vault_address, withdrawal_receipt_address,
+};
+use strike_client::{EthSigner, TicketBuilder};
+use strike_example::instructions::constant::TICKET_VERSION;
+use strike_example::instructions::util::genesis_network_id;
+use strike_example::instructions::{
+    AnyTicket, Asset, AssetAmount, AuthScheme, BulkWithdrawalTicket, NonceAccount,
+    SignerWithSignature, Vault, WeightedSigner, WithdrawalReceipt,
+};
+
+use encoding::{from_hex, parse_address, to_hex};
+
+const USAGE: &str = "usage: strike-cli <RPC_URL> <KEYPAIR> <COMMAND> [ARGS...]
+
+commands:
+  init <VAULT_SEED> <NETWORK_ID> <M> <ADMIN_M> <DOMAIN_SUFFIX> <SIGNER_ADDRESS>...
+  vault <VAULT_SEED>
+  nonce <VAULT_SEED> <REQUEST_ID> [admin]
+  receipt <VAULT_SEED> <REQUEST_ID>
+  withdrawal-ticket <VAULT_SEED> <REQUEST_ID> <EXPIRY> <RECIPIENT> <ASSET=AMOUNT>...
+  bulk-ticket <TICKET_FILE>...
+  rotation-ticket <VAULT_SEED> <REQUEST_ID> <EXPIRY> <M> <ADMIN_M> <SIGNER_ADDRESS>...
+  hash <VAULT_SEED> <TICKET_FILE>
+  sign <VAULT_SEED> <TICKET_FILE> <SECRET_KEY_FILE>
+  send <VAULT_SEED> <TICKET_FILE> <SIGNATURE_FILE>
+
+ASSET is `sol`, `spl:<MINT>` or `nft:<MINT>`. Signer addresses and keys are hex. `sign` prints
+one `<ADDRESS> <SIGNATURE>` line; concatenate the validators' lines into SIGNATURE_FILE.
+NETWORK_ID `genesis` derives the network id from the cluster's genesis hash and binds the vault
+to that cluster.";
+
+struct Cli {
+    client: RpcClient,
+    keypair_path: String,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 4 {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    }
+
+    let cli = Cli {
+        client: RpcClient::new_with_commitment(args[1].clone(), CommitmentConfig::confirmed()),
+        keypair_path: args[2].clone(),
+    };
+    match cli.run(&args[3], &args[4..]) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Cli {
+    fn run(&self, command: &str, args: &[String]) -> Result<(), String> {
+        match (command, args) {
+            ("init", [seed, network_id, m, admin_m, suffix, signers @ ..]) => {
+                self.init(seed, network_id, m, admin_m, suffix, signers)
+            }
+            ("vault", [seed]) => self.show_vault(seed),
+            ("nonce", [seed, request_id]) => self.show_nonce(seed, request_id, false),
+            ("nonce", [seed, request_id, admin]) if admin == "admin" => {
+                self.show_nonce(seed, request_id, true)
+            }
+            ("receipt", [seed, request_id]) => self.show_receipt(seed, request_id),
+            ("withdrawal-ticket", [seed, request_id, expiry, recipient, lines @ ..]) => {
+                self.withdrawal_ticket(seed, request_id, expiry, recipient, lines)
+            }
+            ("bulk-ticket", files) if !files.is_empty() => bulk_ticket(files),
+            ("rotation-ticket", [seed, request_id, expiry, m, admin_m, signers @ ..]) => {
+                self.rotation_ticket(seed, request_id, expiry, m, admin_m, signers)
+            }
+            ("hash", [seed, ticket_file]) => self.hash(seed, ticket_file),
+            ("sign", [seed, ticket_file, key_file]) => self.sign(seed, ticket_file, key_file),
+            ("send", [seed, ticket_file, signature_file]) => {
+                self.send(seed, ticket_file, signature_file)
+            }
+            _ => Err(format!("unknown command or arguments\n{}", USAGE)),
+        }
+    }
+
+    fn init(
+        &self,
+        seed: &str,
+        network_id: &str,
+        m: &str,
+        admin_m: &str,
+        suffix: &str,
+        signers: &[String],
+    ) -> Result<(), String> {
+        let payer = self.payer()?;
+        let vault = vault_address(seed);
+        let (network_id, genesis_hash) = if network_id == "genesis" {
+            let genesis_hash = self
+                .client
+                .get_genesis_hash()
+                .map_err(|e| format!("get genesis hash: {}", e))?
+                .to_bytes();
+            (genesis_network_id(&genesis_hash), Some(genesis_hash))
+        } else {
+            (parse(network_id, "network id")?, None)
+        };
+        let ix = Instruction {
+            program_id: strike_example::ID,
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(
+                &strike_example::accounts::Initialize {
+                    vault,
+                    treasury: treasury_address(&vault),
+                    authority: payer.pubkey(),
+                    system_program: anchor_lang::system_program::ID,
+                },
+                None,
+            ),
+            data: strike_example::instruction::Initialize {
+                vault_seed: seed.to_string(),
+                network_id,
+                m_threshold: parse(m, "threshold")?,
+                admin_threshold: parse(admin_m, "admin threshold")?,
+                signers: parse_signer_set(signers)?,
+                admin_signers: vec![],
+                domain_suffix: suffix.to_string(),
+                // The CLI signs tickets with Ethereum keys.
+                auth_scheme: AuthScheme::Secp256k1,
+                genesis_hash,
+            }
+            .data(),
+        };
+
+        let signature = self.submit(ix)?;
+        println!("initialized vault {} in {}", vault, signature);
+        Ok(())
+    }
+
+    fn show_vault(&self, seed: &str) -> Result<(), String> {
+        let address = vault_address(seed);
+        let vault = self.vault(seed)?;
+        let treasury = treasury_address(&address);
+        let treasury_lamports = self
+            .client
+            .get_balance(&treasury)
+            .map_err(|e| format!("get treasury balance: {}", e))?;
+
+        println!("vault:            {}", address);
+        println!("seed:             {}", vault.vault_seed);
+        println!("network id:       {}", vault.network_id);
+        if vault.genesis_hash != [0; 32] {
+            println!("genesis hash:     {}", to_hex(&vault.genesis_hash));
+        }
+        println!("domain suffix:    {:?}", vault.domain_suffix);
+        println!("threshold:        {}", vault.m_threshold);
+        println!("admin threshold:  {}", vault.admin_threshold);
+        print_signers("signers", &vault.signers);
+        print_signers("admin signers", &vault.admin_signers);
+        println!("assets:");
+        for config in vault.whitelisted_assets.iter() {
+            println!(
+                "  {} decimals={} paused={}",
+                format_asset(&config.asset),
+                config.decimals,
+                config.paused
+            );
+        }
+        println!("processed count:  {}", vault.processed_count);
+        println!("processed root:   {}", to_hex(&vault.processed_root));
+        println!("sequence:         {}", vault.sequence);
+        println!("fee bps:          {}", vault.fee_bps);
+        println!("guardian:         {}", vault.guardian);
+        println!(
+            "treasury:         {} ({} lamports)",
+            treasury, treasury_lamports
+        );
+        Ok(())
+    }
+
+    fn show_nonce(&self, seed: &str, request_id: &str, admin: bool) -> Result<(), String> {
+        let vault = vault_address(seed);
+        let request_id = parse(request_id, "request id")?;
+        let address = if admin {
+            admin_nonce_address(&vault, request_id)
+        } else {
+            nonce_address(&vault, request_id)
+        };
+
+        let account = self
+            .client
+            .get_account_with_commitment(&address, CommitmentConfig::confirmed())
+            .map_err(|e| format!("get nonce account: {}", e))?
+            .value;
+        let nonce = match account {
+            Some(account) => Some(
+                NonceAccount::try_deserialize(&mut account.data.as_slice())
+                    .map_err(|e| format!("deserialize nonce account: {}", e))?,
+            ),
+            None => None,
+        };
+
+        println!("nonce: {}", address);
+        match nonce {
+            Some(nonce) if nonce.used && nonce.ticket_hash == [0; 32] => println!("vetoed"),
+            Some(nonce) if nonce.used => {
+                println!("used by ticket {}", to_hex(&nonce.ticket_hash))
+            }
+            _ => println!("unused"),
+        }
+        Ok(())
+    }
+
+    fn show_receipt(&self, seed: &str, request_id: &str) -> Result<(), String> {
+        let vault = vault_address(seed);
+        let request_id = parse(request_id, "request id")?;
+        let address = withdrawal_receipt_address(&vault, request_id);
+
+        let account = self
+            .client
+            .get_account_with_commitment(&address, CommitmentConfig::confirmed())
+            .map_err(|e| format!("get receipt account: {}", e))?
+            .value;
+
+        println!("receipt: {}", address);
+        let Some(account) = account else {
+            println!("none (not paid, or paid without a receipt)");
+            return Ok(());
+        };
+        let receipt = WithdrawalReceipt::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| format!("deserialize receipt: {}", e))?;
+        println!("recipient: {}", receipt.recipient);
+        println!("slot:      {}", receipt.slot);
+        if receipt.withdrawals.is_empty() {
+            println!("blocked, nothing paid");
+        }
+        for withdrawal in receipt.withdrawals.iter() {
+            println!(
+                "  {}={}",
+                format_asset(&withdrawal.asset),
+                withdrawal.amount
+            );
+        }
+        Ok(())
+    }
+
+    fn withdrawal_ticket(
+        &self,
+        seed: &str,
+        request_id: &str,
+        expiry: &str,
+        recipient: &str,
+        lines: &[String],
+    ) -> Result<(), String> {
+        let builder = self.ticket_builder(seed, expiry)?;
+        let withdrawals = lines
+            .iter()
+            .map(|line| parse_asset_amount(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ticket = builder.withdrawal(
+            parse(request_id, "request id")?,
+            parse_pubkey(recipient)?,
+            withdrawals,
+        );
+        print_ticket(&AnyTicket::Withdrawal { ticket })
+    }
+
+    fn rotation_ticket(
+        &self,
+        seed: &str,
+        request_id: &str,
+        expiry: &str,
+        m: &str,
+        admin_m: &str,
+        signers: &[String],
+    ) -> Result<(), String> {
+        let builder = self.ticket_builder(seed, expiry)?;
+        let ticket = builder.rotate_validator(
+            parse(request_id, "request id")?,
+            parse_signer_set(signers)?,
+            parse(m, "threshold")?,
+            parse(admin_m, "admin threshold")?,
+        );
+        print_ticket(&AnyTicket::RotateValidator { ticket })
+    }
+
+    fn hash(&self, seed: &str, ticket_file: &str) -> Result<(), String> {
+        let vault = self.vault(seed)?;
+        let ticket = read_ticket(ticket_file)?;
+        println!("{}", to_hex(&ticket.ticket().hash(&vault.domain_suffix)));
+        Ok(())
+    }
+
+    fn sign(&self, seed: &str, ticket_file: &str, key_file: &str) -> Result<(), String> {
+        let vault = self.vault(seed)?;
+        let ticket = read_ticket(ticket_file)?;
+        let secret =
+            std::fs::read_to_string(key_file).map_err(|e| format!("read {}: {}", key_file, e))?;
+        let secret: [u8; 32] = from_hex(secret.trim())?
+            .try_into()
+            .map_err(|_| "secret key must be 32 bytes".to_string())?;
+        let signer =
+            EthSigner::from_secret(&secret).map_err(|e| format!("invalid secret key: {}", e))?;
+
+        let signed = signer.sign(ticket.ticket(), &vault.domain_suffix);
+        let mut signature = signed.signature.to_vec();
+        signature.push(signed.recovery_id);
+        println!("{} {}", to_hex(&signer.address), to_hex(&signature));
+        Ok(())
+    }
+
+    fn send(&self, seed: &str, ticket_file: &str, signature_file: &str) -> Result<(), String> {
+        let payer = self.payer()?.pubkey();
+        let vault = self.vault(seed)?;
+        let address = vault_address(seed);
+        let ticket = read_ticket(ticket_file)?;
+        let signers_with_sigs = read_signatures(signature_file)?;
+
+        let ix = match ticket {
+            AnyTicket::Withdrawal { ticket } => Instruction {
+                program_id: strike_example::ID,
+                accounts: accounts::withdraw(&address, &vault, &payer, &ticket),
+                data: strike_example::instruction::Withdraw {
+                    ticket,
+                    signers_with_sigs,
+                    metadata: None,
+                }
+                .data(),
+            },
+            AnyTicket::BulkWithdrawal { ticket } => {
+                let (accounts, account_indexes) =
+                    accounts::bulk_withdraw(&address, &vault, &payer, &ticket);
+                Instruction {
+                    program_id: strike_example::ID,
+                    accounts,
+                    data: strike_example::instruction::BulkWithdraw {
+                        bulk_ticket: ticket,
+                        signers_with_sigs,
+                        account_indexes,
+                        metadata: None,
+                    }
+                    .data(),
+                }
+            }
+            AnyTicket::RotateValidator { ticket } => Instruction {
+                program_id: strike_example::ID,
+                accounts: accounts::rotate_validators(&address, &payer, ticket.request_id),
+                data: strike_example::instruction::RotateValidators {
+                    ticket,
+                    signers_with_sigs,
+                }
+                .data(),
+            },
+            _ => return Err("send supports withdrawal, bulk and rotation tickets".to_string()),
+        };
+
+        let signature = self.submit(ix)?;
+        println!("sent {}", signature);
+        Ok(())
+    }
+
+    fn vault(&self, seed: &str) -> Result<Vault, String> {
+        let account = self
+            .client
+            .get_account(&vault_address(seed))
+            .map_err(|e| format!("get vault account: {}", e))?;
+        Vault::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| format!("deserialize vault account: {}", e))
+    }
+
+    /// Builder for the vault's tickets, with its network id and `expiry` (Unix timestamp).
+    fn ticket_builder(&self, seed: &str, expiry: &str) -> Result<TicketBuilder, String> {
+        let vault = self.vault(seed)?;
+        Ok(TicketBuilder::new(
+            vault_address(seed),
+            vault.network_id,
+            parse(expiry, "expiry")?,
+        ))
+    }
+
+    fn payer(&self) -> Result<solana_sdk::signature::Keypair, String> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|e| format!("read keypair {}: {}", self.keypair_path, e))
+    }
+
+    fn submit(&self, ix: Instruction) -> Result<solana_sdk::signature::Signature, String> {
+        let payer = self.payer()?;
+        let blockhash = self
+            .client
+            .get_latest_blockhash()
+            .map_err(|e| format!("get blockhash: {}", e))?;
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+        self.client
+            .send_and_confirm_transaction(&tx)
+            .map_err(|e| format!("send transaction: {}", e))
+    }
+}
+
+fn bulk_ticket(files: &[String]) -> Result<(), String> {
+    let tickets = files
+        .iter()
+        .map(|file| match read_ticket(file)? {
+            AnyTicket::Withdrawal { ticket } => Ok(ticket),
+            _ => Err(format!("{} is not a withdrawal ticket", file)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    print_ticket(&AnyTicket::BulkWithdrawal {
+        ticket: BulkWithdrawalTicket {
+            version: TICKET_VERSION,
+            tickets,
+        },
+    })
+}
+
+fn print_ticket(ticket: &AnyTicket) -> Result<(), String> {
+    let bytes = ticket
+        .try_to_vec()
+        .map_err(|e| format!("serialize ticket: {}", e))?;
+    println!("{}", to_hex(&bytes));
+    Ok(())
+}
+
+fn read_ticket(file: &str) -> Result<AnyTicket, String> {
+    let hex = std::fs::read_to_string(file).map_err(|e| format!("read {}: {}", file, e))?;
+    AnyTicket::try_from_slice(&from_hex(hex.trim())?)
+        .map_err(|e| format!("{} is not a ticket: {}", file, e))
+}
+
+/// Signature lines (`<ADDRESS> <SIGNATURE>`), deduplicated and sorted by signer address as the
+/// program requires.
+fn read_signatures(file: &str) -> Result<Vec<SignerWithSignature>, String> {
+    let text = std::fs::read_to_string(file).map_err(|e| format!("read {}: {}", file, e))?;
+    let mut lines: Vec<([u8; 20], SignerWithSignature)> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (address, signature) = line
+            .split_once(' ')
+            .ok_or_else(|| format!("bad signature line {:?}", line))?;
+        let address = parse_address(address)?;
+        let signature = from_hex(signature.trim())?;
+        if signature.len() != 65 {
+            return Err(format!(
+                "signature of {} must be 65 bytes",
+                to_hex(&address)
+            ));
+        }
+        if lines.iter().any(|(seen, _)| *seen == address) {
+            continue;
+        }
+
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&signature[..64]);
+        lines.push((
+            address,
+            SignerWithSignature {
+                signature: bytes,
+                recovery_id: signature[64],
+            },
+        ));
+    }
+
+    lines.sort_by_key(|(address, _)| *address);
+    Ok(lines.into_iter().map(|(_, sig)| sig).collect())
+}
+
+fn parse<T: FromStr>(value: &str, what: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} {:?}", what, value))
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|e| format!("invalid address {}: {}", value, e))
+}
+
+/// Weight-1 signer set entries for hex signer addresses.
+fn parse_signer_set(addresses: &[String]) -> Result<Vec<WeightedSigner>, String> {
+    addresses
+        .iter()
+        .map(|address| {
+            Ok(WeightedSigner {
+                address: parse_address(address)?,
+                weight: 1,
+                valid_until: None,
+            })
+        })
+        .collect()
+}
+
+/// `sol=<AMOUNT>`, `spl:<MINT>=<AMOUNT>` or `nft:<MINT>=1`.
+fn parse_asset_amount(line: &str) -> Result<AssetAmount, String> {
+    let (asset, amount) = line
+        .split_once('=')
+        .ok_or_else(|| format!("bad withdrawal line {:?}, expected ASSET=AMOUNT", line))?;
+    let asset = match asset.split_once(':') {
+        None if asset == "sol" => Asset::Sol,
+        Some(("spl", mint)) => Asset::SplToken {
+            mint: parse_pubkey(mint)?,
+        },
+        Some(("nft", mint)) => Asset::Nft {
+            mint: parse_pubkey(mint)?,
+        },
+        _ => return Err(format!("bad asset {:?}", asset)),
+    };
+    Ok(AssetAmount {
+        asset,
+        amount: parse(amount, "amount")?,
+    })
+}
+
+fn format_asset(asset: &Asset) -> String {
+    match asset {
+        Asset::Sol => "sol".to_string(),
+        Asset::SplToken { mint } => format!("spl:{}", mint),
+        Asset::Nft { mint } => format!("nft:{}", mint),
+    }
+}
+
+fn print_signers(label: &str, signers: &[WeightedSigner]) {
+    println!("{}:", label);
+    for signer in signers {
+        match signer.valid_until {
+            Some(valid_until) => println!(
+                "  {} weight={} valid_until={}",
+                to_hex(&signer.address),
+                signer.weight,
+                valid_until
+            ),
+            None => println!("  {} weight={}", to_hex(&signer.address), signer.weight),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Account indexes for `bulk_withdraw`, which reads each ticket's recipient, mints and token
+//! accounts at the positions the caller gives rather than searching its remaining accounts.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use strike_example::instructions::util::recipient_token_address;
+use strike_example::instructions::{
+    Asset, BulkAccountIndexes, BulkWithdrawalTicket, WithdrawalAccountIndexes,
+};
+
+/// Indexes of every ticket's accounts in `remaining_accounts`, the accounts following
+/// `BulkWithdraw`'s own. An account missing from the list gets index `u8::MAX`, which the
+/// program rejects like any account it can't find.
+pub fn bulk_account_indexes(
+    bulk_ticket: &BulkWithdrawalTicket,
+    vault: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Vec<BulkAccountIndexes> {
+    let index_of = |address: &Pubkey| -> u8 {
+        remaining_accounts
+            .iter()
+            .position(|meta| meta.pubkey == *address)
+            .and_then(|index| u8::try_from(index).ok())
+            .unwrap_or(u8::MAX)
+    };
+
+    bulk_ticket
+        .tickets
+        .iter()
+        .map(|ticket| BulkAccountIndexes {
+            recipient: index_of(&ticket.recipient),
+            withdrawals: ticket
+                .withdrawals
+                .iter()
+                .enumerate()
+                .map(|(idx, withdrawal)| match withdrawal.asset {
+                    Asset::Sol => WithdrawalAccountIndexes::default(),
+                    Asset::SplToken { mint } | Asset::Nft { mint } => WithdrawalAccountIndexes {
+                        mint: index_of(&mint),
+                        vault_token: index_of(&get_associated_token_address_with_program_id(
+                            vault,
+                            &mint,
+                            token_program,
+                        )),
+                        recipient_token: index_of(&recipient_token_address(
+                            ticket,
+                            idx,
+                            &mint,
+                            token_program,
+                        )),
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,25 @@
+//! Off-chain client SDK for `strike-example`: ticket builders, ticket hashing, secp256k1
+//! signing, PDA derivation and `bulk_withdraw` account indexes.
+//!
+//! Tickets are the program's own types and hash through the program's own `Ticket::preimage`,
+//! so relayers and signing services built on this crate can't drift from on-chain hashing.
+
+pub mod bulk;
+pub mod pda;
+pub mod signer;
+pub mod tickets;
+
+pub use bulk::bulk_account_indexes;
+pub use signer::EthSigner;
+pub use strike_example::instructions::Ticket;
+pub use tickets::TicketBuilder;
+
+/// The bytes validators sign the keccak256 of, built exactly as the program builds them.
+pub fn ticket_preimage(ticket: &dyn Ticket, domain_suffix: &str) -> Vec<u8> {
+    ticket.preimage(domain_suffix)
+}
+
+/// The hash validators sign for `ticket` under the vault's `domain_suffix`.
+pub fn ticket_hash(ticket: &dyn Ticket, domain_suffix: &str) -> [u8; 32] {
+    ticket.hash(domain_suffix)
+}
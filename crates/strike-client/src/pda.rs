@@ -0,0 +1,214 @@
+//! Addresses of the program's PDAs, derived with the seeds its account constraints use.
+
+use anchor_lang::prelude::Pubkey;
+use strike_example::instructions::constant::{
+    ADMIN_NONCE_SEED, NONCE_SEED, SUB_VAULT_SEED, TREASURY_SEED, VAULT_SEED,
+};
+use strike_example::instructions::Asset;
+
+pub fn vault_address(vault_seed: &str) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_SEED, vault_seed.as_bytes()], &strike_example::ID).0
+}
+
+pub fn sub_vault_address(parent: &Pubkey, sub_vault_seed: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SUB_VAULT_SEED, parent.as_ref(), sub_vault_seed.as_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn treasury_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TREASURY_SEED, vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn fee_treasury_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_treasury", vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn nonce_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[NONCE_SEED, vault.as_ref(), &request_id.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn admin_nonce_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ADMIN_NONCE_SEED, vault.as_ref(), &request_id.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn deposit_record_address(vault: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"deposit_record", vault.as_ref(), user.as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn stake_address(vault: &Pubkey, vote_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"stake", vault.as_ref(), vote_account.as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn lst_funding_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lst_funding", vault.as_ref()], &strike_example::ID).0
+}
+
+/// Temporary wSOL account `deposit_wsol` and `withdraw_wsol` wrap and unwrap through.
+pub fn wsol_temp_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"wsol", vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn named_treasury_address(vault: &Pubkey, name: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TREASURY_SEED, vault.as_ref(), name.as_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn allowance_address(vault: &Pubkey, recipient: &Pubkey, asset: &Asset) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"allowance",
+            vault.as_ref(),
+            recipient.as_ref(),
+            asset.seed_key().as_ref(),
+        ],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn receipt_mint_address(vault: &Pubkey, asset: &Asset) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"receipt_mint", vault.as_ref(), asset.seed_key().as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn usd_cap_address(vault: &Pubkey, asset: &Asset) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"usd_cap", vault.as_ref(), asset.seed_key().as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn recipient_allowlist_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"recipient_allowlist", vault.as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn strategy_allowlist_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"strategy_allowlist", vault.as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn blocklist_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"blocklist", vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn crank_registry_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"crank_registry", vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn signer_set_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"signer_set", vault.as_ref()], &strike_example::ID).0
+}
+
+pub fn claim_root_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"claim_root", vault.as_ref(), &request_id.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn withdrawal_receipt_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"withdrawal_receipt",
+            vault.as_ref(),
+            &request_id.to_le_bytes(),
+        ],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn stream_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"stream", vault.as_ref(), &request_id.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn schedule_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"schedule", vault.as_ref(), &request_id.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn claimed_leaf_address(claim_root: &Pubkey, index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"claimed", claim_root.as_ref(), &index.to_le_bytes()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+pub fn wormhole_emitter_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"wormhole_emitter", vault.as_ref()], &strike_example::ID).0
+}
+
+/// PDA the program signs its hook program calls with.
+pub fn hook_authority_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"hook_authority", vault.as_ref()], &strike_example::ID).0
+}
+
+/// PDA holding the program's upgrade authority once `accept_upgrade_authority` handed it over.
+pub fn upgrade_authority_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"upgrade_authority", vault.as_ref()], &strike_example::ID).0
+}
+
+/// Authority the vault's nonce tree is initialized with; signs its `replace_leaf` calls.
+pub fn nonce_tree_authority_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"nonce_tree_authority", vault.as_ref()],
+        &strike_example::ID,
+    )
+    .0
+}
+
+/// Program data account of the strike program, as the upgradeable loader derives it.
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(
+        &[strike_example::ID.as_ref()],
+        &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )
+    .0
+}
+
+/// PDA the program signs its `emit_cpi!` self-invocations with.
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &strike_example::ID).0
+}
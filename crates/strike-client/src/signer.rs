@@ -0,0 +1,129 @@
+//! Ethereum-style secp256k1 signing of tickets, as validators approve them.
+
+use anchor_lang::solana_program::instruction::Instruction;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use solana_program::{keccak, secp256k1_program};
+use strike_example::instructions::{SignerWithSignature, Ticket, WeightedSigner};
+
+/// Ethereum address of a secp256k1 key: the last 20 bytes of the keccak256 of its
+/// uncompressed public key.
+pub fn eth_address(key: &VerifyingKey) -> [u8; 20] {
+    let encoded = key.to_encoded_point(false);
+    let hash = keccak::hash(&encoded.as_bytes()[1..]).to_bytes();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..32]);
+    address
+}
+
+/// Ethereum-style secp256k1 signer, with the weight and expiry it holds in a signer set.
+pub struct EthSigner {
+    key: SigningKey,
+    pub address: [u8; 20],
+    pub weight: u8,
+    pub valid_until: Option<i64>,
+}
+
+impl EthSigner {
+    pub fn new(key: SigningKey) -> Self {
+        let address = eth_address(key.verifying_key());
+        Self {
+            key,
+            address,
+            weight: 1,
+            valid_until: None,
+        }
+    }
+
+    pub fn from_secret(secret: &[u8; 32]) -> Result<Self, k256::ecdsa::Error> {
+        Ok(Self::new(SigningKey::from_bytes(secret.into())?))
+    }
+
+    /// Deterministic signer for `seed`, for tests and local networks only.
+    pub fn from_seed(seed: u64) -> Self {
+        let secret = keccak::hashv(&[b"strike-test-signer".as_slice(), &seed.to_le_bytes()]);
+        Self::from_secret(&secret.to_bytes()).expect("valid secret key")
+    }
+
+    pub fn with_weight(mut self, weight: u8) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_valid_until(mut self, valid_until: i64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Signer-set entry for this signer, for `initialize` and rotation tickets.
+    pub fn weighted(&self) -> WeightedSigner {
+        WeightedSigner {
+            address: self.address,
+            weight: self.weight,
+            valid_until: self.valid_until,
+        }
+    }
+
+    pub fn sign_hash(&self, message_hash: &[u8; 32]) -> SignerWithSignature {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(message_hash)
+            .expect("signing succeeds");
+
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&signature.to_bytes());
+
+        SignerWithSignature {
+            signature: bytes,
+            recovery_id: recovery_id.to_byte(),
+        }
+    }
+
+    pub fn sign(&self, ticket: &dyn Ticket, domain_suffix: &str) -> SignerWithSignature {
+        self.sign_hash(&ticket.hash(domain_suffix))
+    }
+
+    /// Secp256k1 precompile instruction verifying this signer's signature over the ticket, for
+    /// a transaction that places it at instruction `index`. The program instruction needs the
+    /// instructions sysvar in its remaining accounts.
+    pub fn secp256k1_ix(&self, ticket: &dyn Ticket, domain_suffix: &str, index: u8) -> Instruction {
+        const ADDRESS_OFFSET: u16 = 12;
+        const SIGNATURE_OFFSET: u16 = ADDRESS_OFFSET + 20;
+        const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 65;
+
+        let message = ticket.preimage(domain_suffix);
+        let sig = self.sign(ticket, domain_suffix);
+
+        let mut data = vec![1u8];
+        data.extend_from_slice(&SIGNATURE_OFFSET.to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&ADDRESS_OFFSET.to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&MESSAGE_OFFSET.to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&self.address);
+        data.extend_from_slice(&sig.signature);
+        data.push(sig.recovery_id);
+        data.extend_from_slice(&message);
+
+        Instruction {
+            program_id: secp256k1_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+}
+
+/// Sign `ticket` with `signers` in ascending address order, as the program requires.
+pub fn sign_sorted(
+    signers: &[&EthSigner],
+    ticket: &dyn Ticket,
+    domain_suffix: &str,
+) -> Vec<SignerWithSignature> {
+    let mut signers = signers.to_vec();
+    signers.sort_by_key(|signer| signer.address);
+    signers
+        .iter()
+        .map(|signer| signer.sign(ticket, domain_suffix))
+        .collect()
+}
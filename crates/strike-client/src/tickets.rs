@@ -0,0 +1,232 @@
+//! Builders filling the fields every ticket shares: version, vault, expiry, network and
+//! activation time.
+
+use anchor_lang::prelude::Pubkey;
+use strike_example::instructions::constant::TICKET_VERSION;
+use strike_example::instructions::{
+    AddAssetTicket, AdminDepositTicket, AdminWithdrawalTicket, Asset, AssetAmount, AssetSettings,
+    BulkWithdrawalTicket, RemoveAssetTicket, RotateAdminSignersTicket, RotateValidatorTicket,
+    SetFeeTicket, SetGuardianTicket, SetMinWithdrawalTicket, UpdateAssetTicket, WeightedSigner,
+    WithdrawalTicket,
+};
+
+/// Builds tickets for one vault. Optional ticket fields (memo, named treasury, token accounts,
+/// source ref) start unset and can be filled on the returned ticket.
+#[derive(Clone, Debug)]
+pub struct TicketBuilder {
+    pub vault: Pubkey,
+    pub network_id: u64,
+    pub version: u8,
+    pub expiry: i64,
+    pub not_before: Option<i64>,
+}
+
+impl TicketBuilder {
+    pub fn new(vault: Pubkey, network_id: u64, expiry: i64) -> Self {
+        Self {
+            vault,
+            network_id,
+            version: TICKET_VERSION,
+            expiry,
+            not_before: None,
+        }
+    }
+
+    /// Tickets built from now on only become valid at `not_before`.
+    pub fn with_not_before(mut self, not_before: i64) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    pub fn withdrawal(
+        &self,
+        request_id: u64,
+        recipient: Pubkey,
+        withdrawals: Vec<AssetAmount>,
+    ) -> WithdrawalTicket {
+        WithdrawalTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            recipient,
+            withdrawals,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            memo: None,
+            treasury: None,
+            not_before: self.not_before,
+            token_accounts: None,
+            source_ref: None,
+            balance_bps: None,
+        }
+    }
+
+    pub fn bulk_withdrawal(&self, tickets: Vec<WithdrawalTicket>) -> BulkWithdrawalTicket {
+        BulkWithdrawalTicket {
+            version: self.version,
+            tickets,
+        }
+    }
+
+    pub fn admin_withdrawal(
+        &self,
+        request_id: u64,
+        recipient: Pubkey,
+        withdrawals: Vec<AssetAmount>,
+    ) -> AdminWithdrawalTicket {
+        AdminWithdrawalTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            recipient,
+            withdrawals,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+            source_ref: None,
+        }
+    }
+
+    pub fn admin_deposit(&self, request_id: u64, deposits: Vec<AssetAmount>) -> AdminDepositTicket {
+        AdminDepositTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            deposits,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+            source_ref: None,
+            bypass_whitelist: false,
+        }
+    }
+
+    pub fn add_asset(
+        &self,
+        request_id: u64,
+        asset: Asset,
+        settings: AssetSettings,
+    ) -> AddAssetTicket {
+        AddAssetTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            asset,
+            settings,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn update_asset(
+        &self,
+        request_id: u64,
+        asset: Asset,
+        settings: AssetSettings,
+    ) -> UpdateAssetTicket {
+        UpdateAssetTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            asset,
+            settings,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn remove_asset(&self, request_id: u64, asset: Asset) -> RemoveAssetTicket {
+        RemoveAssetTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            asset,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn set_min_withdrawal(
+        &self,
+        request_id: u64,
+        asset: Asset,
+        min_amount: u64,
+    ) -> SetMinWithdrawalTicket {
+        SetMinWithdrawalTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            asset,
+            min_amount,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn rotate_validator(
+        &self,
+        request_id: u64,
+        signers: Vec<WeightedSigner>,
+        m_threshold: u8,
+        admin_threshold: u8,
+    ) -> RotateValidatorTicket {
+        RotateValidatorTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            signers,
+            m_threshold,
+            admin_threshold,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn rotate_admin_signers(
+        &self,
+        request_id: u64,
+        signers: Vec<WeightedSigner>,
+        admin_threshold: u8,
+    ) -> RotateAdminSignersTicket {
+        RotateAdminSignersTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            signers,
+            admin_threshold,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn set_fee(&self, request_id: u64, fee_bps: u16, fee_mint: Pubkey) -> SetFeeTicket {
+        SetFeeTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            fee_bps,
+            fee_mint,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+
+    pub fn set_guardian(&self, request_id: u64, guardian: Pubkey) -> SetGuardianTicket {
+        SetGuardianTicket {
+            version: self.version,
+            request_id,
+            vault: self.vault,
+            guardian,
+            expiry: self.expiry,
+            network_id: self.network_id,
+            not_before: self.not_before,
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Indexes the events the program emits (`emit_cpi!`) into SQLite or Postgres, giving operators
+//! an audit trail of deposits, withdrawals, rotations and every other vault event.
+//!
+//! Usage: strike-indexer <RPC_URL> <WS_URL> <DATABASE>
+//!
+//! `DATABASE` is a `postgres://` connection string or an SQLite file path. On start the indexer
+//! backfills every program transaction newer than the last one it indexed, then follows new
+//! ones through a logs subscription. Transactions are indexed at most once, keyed by signature.
+
+mod records;
+mod store;
+
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::{
+    RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding, UiTransactionStatusMeta,
+};
+
+use store::Store;
+
+/// RPC page size for `getSignaturesForAddress`.
+const SIGNATURE_PAGE: usize = 1000;
+/// Attempts at fetching a transaction a log notification announced before it is served.
+const FETCH_ATTEMPTS: u32 = 10;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        eprintln!("usage: strike-indexer <RPC_URL> <WS_URL> <DATABASE>");
+        return ExitCode::from(2);
+    }
+
+    let client = RpcClient::new_with_commitment(args[1].clone(), CommitmentConfig::confirmed());
+    let mut store = match Store::open(&args[3]) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("open database: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&client, &args[2], &mut store) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("indexer failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(client: &RpcClient, ws_url: &str, store: &mut Store) -> Result<(), String> {
+    store.migrate()?;
+
+    // Subscribe before backfilling so nothing lands between the two, the overlap is skipped.
+    let (_subscription, notifications) = PubsubClient::logs_subscribe(
+        ws_url,
+        RpcTransactionLogsFilter::Mentions(vec![strike_example::ID.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )
+    .map_err(|e| format!("subscribe to program logs: {}", e))?;
+
+    let backlog = fetch_signatures(client, store.latest_signature()?)?;
+    println!("backfilling {} transaction(s)", backlog.len());
+    for signature in backlog.iter() {
+        index(client, store, signature)?;
+    }
+
+    println!("following program logs");
+    for notification in notifications.iter() {
+        let logs = notification.value;
+        // Failed transactions emit no events.
+        if logs.err.is_some() {
+            continue;
+        }
+        let signature = Signature::from_str(&logs.signature)
+            .map_err(|e| format!("parse signature {}: {}", logs.signature, e))?;
+        index(client, store, &signature)?;
+    }
+
+    Err("logs subscription closed".to_string())
+}
+
+/// Program transactions newer than `until`, oldest first.
+fn fetch_signatures(
+    client: &RpcClient,
+    until: Option<Signature>,
+) -> Result<Vec<Signature>, String> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = client
+            .get_signatures_for_address_with_config(
+                &strike_example::ID,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .map_err(|e| format!("get signatures: {}", e))?;
+
+        let page_len = page.len();
+        for status in page {
+            if status.err.is_some() {
+                continue;
+            }
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| format!("parse signature {}: {}", status.signature, e))?;
+            signatures.push(signature);
+        }
+        if page_len < SIGNATURE_PAGE {
+            break;
+        }
+        before = signatures.last().copied();
+    }
+
+    signatures.reverse();
+    Ok(signatures)
+}
+
+/// Decode the events of one transaction and store them with it.
+fn index(client: &RpcClient, store: &mut Store, signature: &Signature) -> Result<(), String> {
+    if store.has_transaction(signature)? {
+        return Ok(());
+    }
+
+    let tx = fetch_transaction(client, signature)?;
+    let Some(meta) = tx.transaction.meta else {
+        return Err(format!("{}: transaction has no status meta", signature));
+    };
+    if meta.err.is_some() {
+        return Ok(());
+    }
+    let Some(versioned) = tx.transaction.transaction.decode() else {
+        return Err(format!("{}: cannot decode transaction", signature));
+    };
+
+    let account_keys = full_account_keys(versioned.message.static_account_keys(), &meta)?;
+    let events = records::decode_events(&account_keys, &meta)
+        .map_err(|e| format!("{}: {}", signature, e))?;
+    store.insert(signature, tx.slot, tx.block_time, &events)?;
+
+    if !events.is_empty() {
+        println!("{}: {} event(s)", signature, events.len());
+    }
+    Ok(())
+}
+
+fn fetch_transaction(
+    client: &RpcClient,
+    signature: &Signature,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta, String> {
+    let mut attempt = 1;
+    loop {
+        let result = client.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        );
+        match result {
+            Ok(tx) => return Ok(tx),
+            Err(_) if attempt < FETCH_ATTEMPTS => {
+                attempt += 1;
+                thread::sleep(Duration::from_millis(500));
+            }
+            Err(e) => return Err(format!("get transaction {}: {}", signature, e)),
+        }
+    }
+}
+
+/// Static keys followed by the addresses loaded from lookup tables, in message index order.
+fn full_account_keys(
+    static_keys: &[Pubkey],
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<Pubkey>, String> {
+    let mut keys = static_keys.to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            keys.push(Pubkey::from_str(key).map_err(|e| format!("parse loaded address: {}", e))?);
+        }
+    }
+    Ok(keys)
+}
@@ -0,0 +1,130 @@
+//! Decoding `emit_cpi!` event records out of a transaction's inner instructions.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiInstruction, UiTransactionStatusMeta};
+use strike_example::instructions::{
+    AdminOpEvent, BatchSummaryEvent, BlockedRecipientEvent, ClaimEvent, ClaimRootEvent,
+    CrankIncentiveEvent, DepositCreditedEvent, DepositEvent, ExecuteEvent, RelayerRebateEvent,
+    RotateEvent, ScheduleCreatedEvent, ScheduleExecutedEvent, SourceRefEvent, StreamClaimEvent,
+    StreamCreatedEvent, VaaWithdrawalEvent, VaultInitializedEvent, VaultMigratedEvent,
+    WithdrawEvent, WithdrawalVetoedEvent,
+};
+
+/// Every event the program emits, by name.
+const EVENTS: &[(&str, &[u8])] = &[
+    (
+        "VaultInitializedEvent",
+        VaultInitializedEvent::DISCRIMINATOR,
+    ),
+    ("VaultMigratedEvent", VaultMigratedEvent::DISCRIMINATOR),
+    ("DepositEvent", DepositEvent::DISCRIMINATOR),
+    ("WithdrawEvent", WithdrawEvent::DISCRIMINATOR),
+    ("RelayerRebateEvent", RelayerRebateEvent::DISCRIMINATOR),
+    (
+        "BlockedRecipientEvent",
+        BlockedRecipientEvent::DISCRIMINATOR,
+    ),
+    ("SourceRefEvent", SourceRefEvent::DISCRIMINATOR),
+    ("VaaWithdrawalEvent", VaaWithdrawalEvent::DISCRIMINATOR),
+    (
+        "WithdrawalVetoedEvent",
+        WithdrawalVetoedEvent::DISCRIMINATOR,
+    ),
+    ("ExecuteEvent", ExecuteEvent::DISCRIMINATOR),
+    ("ClaimRootEvent", ClaimRootEvent::DISCRIMINATOR),
+    ("ClaimEvent", ClaimEvent::DISCRIMINATOR),
+    ("DepositCreditedEvent", DepositCreditedEvent::DISCRIMINATOR),
+    ("StreamCreatedEvent", StreamCreatedEvent::DISCRIMINATOR),
+    ("StreamClaimEvent", StreamClaimEvent::DISCRIMINATOR),
+    ("ScheduleCreatedEvent", ScheduleCreatedEvent::DISCRIMINATOR),
+    (
+        "ScheduleExecutedEvent",
+        ScheduleExecutedEvent::DISCRIMINATOR,
+    ),
+    ("CrankIncentiveEvent", CrankIncentiveEvent::DISCRIMINATOR),
+    ("BatchSummaryEvent", BatchSummaryEvent::DISCRIMINATOR),
+    ("AdminOpEvent", AdminOpEvent::DISCRIMINATOR),
+    ("RotateEvent", RotateEvent::DISCRIMINATOR),
+];
+
+/// Events with a table of their own, on top of the row every event gets in `events`.
+pub enum Record {
+    Deposit(DepositEvent),
+    Withdrawal(WithdrawEvent),
+    Rotation(RotateEvent),
+}
+
+pub struct IndexedEvent {
+    pub name: &'static str,
+    pub vault: Pubkey, // every event starts with its vault
+    pub sequence: u64, // and the vault's sequence number, see `Vault::sequence`
+    pub data: Vec<u8>, // Borsh-encoded event, discriminator included
+    pub record: Option<Record>,
+}
+
+/// Events the program emitted in the transaction, in emission order. Records of events this
+/// build doesn't know are skipped with a warning.
+pub fn decode_events(
+    account_keys: &[Pubkey],
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<IndexedEvent>, String> {
+    let OptionSerializer::Some(inner) = &meta.inner_instructions else {
+        return Ok(Vec::new());
+    };
+
+    let mut events = Vec::new();
+    for instruction in inner.iter().flat_map(|inner| inner.instructions.iter()) {
+        let UiInstruction::Compiled(instruction) = instruction else {
+            continue;
+        };
+        if account_keys.get(instruction.program_id_index as usize) != Some(&strike_example::ID) {
+            continue;
+        }
+        let data = bs58::decode(&instruction.data)
+            .into_vec()
+            .map_err(|e| format!("inner instruction data: {}", e))?;
+        let Some(data) = data.strip_prefix(EVENT_IX_TAG_LE) else {
+            continue;
+        };
+
+        let Some((name, discriminator)) = EVENTS.iter().find(|(_, d)| data.starts_with(d)) else {
+            eprintln!(
+                "skipping event with unknown discriminator {:?}",
+                &data[..8.min(data.len())]
+            );
+            continue;
+        };
+        let body = &data[discriminator.len()..];
+        let vault = body
+            .get(..32)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or_else(|| format!("{} has no vault", name))?;
+        let sequence = body
+            .get(32..40)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or_else(|| format!("{} has no sequence", name))?;
+
+        let record = match *name {
+            "DepositEvent" => Some(Record::Deposit(decode(name, body)?)),
+            "WithdrawEvent" => Some(Record::Withdrawal(decode(name, body)?)),
+            "RotateEvent" => Some(Record::Rotation(decode(name, body)?)),
+            _ => None,
+        };
+        events.push(IndexedEvent {
+            name,
+            vault,
+            sequence,
+            data: data.to_vec(),
+            record,
+        });
+    }
+    Ok(events)
+}
+
+fn decode<T: AnchorDeserialize>(name: &str, body: &[u8]) -> Result<T, String> {
+    T::try_from_slice(body).map_err(|e| format!("cannot decode {}: {}", name, e))
+}
@@ -0,0 +1,363 @@
+//! SQLite and Postgres storage. Both run the same SQL: every integer column is a BIGINT and
+//! parameters are written `$1`, `$2`, ... in the order they are bound.
+
+use std::str::FromStr;
+
+use solana_sdk::signature::Signature;
+use strike_example::instructions::{Asset, DepositEvent, RotateEvent, WithdrawEvent};
+
+use crate::records::{IndexedEvent, Record};
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS transactions (
+        signature TEXT PRIMARY KEY,
+        slot BIGINT NOT NULL,
+        block_time BIGINT
+    )",
+    "CREATE TABLE IF NOT EXISTS events (
+        signature TEXT NOT NULL,
+        event_index BIGINT NOT NULL,
+        vault TEXT NOT NULL,
+        sequence BIGINT NOT NULL,
+        name TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (signature, event_index)
+    )",
+    "CREATE TABLE IF NOT EXISTS deposits (
+        signature TEXT NOT NULL,
+        event_index BIGINT NOT NULL,
+        vault TEXT NOT NULL,
+        request_id BIGINT NOT NULL,
+        depositor TEXT NOT NULL,
+        asset TEXT NOT NULL,
+        amount BIGINT NOT NULL,
+        net_amount BIGINT NOT NULL,
+        admin BOOLEAN NOT NULL,
+        valid_signers BIGINT NOT NULL,
+        metadata TEXT,
+        PRIMARY KEY (signature, event_index)
+    )",
+    "CREATE TABLE IF NOT EXISTS withdrawals (
+        signature TEXT NOT NULL,
+        event_index BIGINT NOT NULL,
+        vault TEXT NOT NULL,
+        request_id BIGINT NOT NULL,
+        recipient TEXT NOT NULL,
+        asset TEXT NOT NULL,
+        amount BIGINT NOT NULL,
+        gross_amount BIGINT NOT NULL,
+        fee BIGINT NOT NULL,
+        admin BOOLEAN NOT NULL,
+        valid_signers BIGINT NOT NULL,
+        metadata TEXT,
+        PRIMARY KEY (signature, event_index)
+    )",
+    "CREATE TABLE IF NOT EXISTS rotations (
+        signature TEXT NOT NULL,
+        event_index BIGINT NOT NULL,
+        vault TEXT NOT NULL,
+        request_id BIGINT NOT NULL,
+        signers TEXT NOT NULL,
+        m_threshold BIGINT NOT NULL,
+        admin_threshold BIGINT NOT NULL,
+        valid_signers BIGINT NOT NULL,
+        PRIMARY KEY (signature, event_index)
+    )",
+];
+
+#[derive(Clone)]
+enum Value {
+    Int(i64),
+    NullableInt(Option<i64>),
+    Text(Option<String>),
+    Bool(bool),
+}
+
+pub enum Store {
+    Sqlite(rusqlite::Connection),
+    Postgres(Box<postgres::Client>),
+}
+
+impl Store {
+    /// Connect to Postgres for a `postgres://` or `postgresql://` URL, else open the SQLite file.
+    pub fn open(database: &str) -> Result<Self, String> {
+        if database.starts_with("postgres://") || database.starts_with("postgresql://") {
+            let client = postgres::Client::connect(database, postgres::NoTls)
+                .map_err(|e| format!("connect to postgres: {}", e))?;
+            Ok(Store::Postgres(Box::new(client)))
+        } else {
+            let connection = rusqlite::Connection::open(database)
+                .map_err(|e| format!("open {}: {}", database, e))?;
+            Ok(Store::Sqlite(connection))
+        }
+    }
+
+    pub fn migrate(&mut self) -> Result<(), String> {
+        for statement in SCHEMA {
+            self.execute(statement, &[])?;
+        }
+        Ok(())
+    }
+
+    /// Newest indexed transaction, where backfilling resumes.
+    pub fn latest_signature(&mut self) -> Result<Option<Signature>, String> {
+        let signature = self.query_text(
+            "SELECT signature FROM transactions ORDER BY slot DESC LIMIT 1",
+            &[],
+        )?;
+        signature
+            .map(|signature| {
+                Signature::from_str(&signature)
+                    .map_err(|e| format!("stored signature {}: {}", signature, e))
+            })
+            .transpose()
+    }
+
+    pub fn has_transaction(&mut self, signature: &Signature) -> Result<bool, String> {
+        let found = self.query_text(
+            "SELECT signature FROM transactions WHERE signature = $1",
+            &[Value::Text(Some(signature.to_string()))],
+        )?;
+        Ok(found.is_some())
+    }
+
+    /// Store a transaction and its events atomically.
+    pub fn insert(
+        &mut self,
+        signature: &Signature,
+        slot: u64,
+        block_time: Option<i64>,
+        events: &[IndexedEvent],
+    ) -> Result<(), String> {
+        self.execute("BEGIN", &[])?;
+        match self.insert_rows(&signature.to_string(), slot, block_time, events) {
+            Ok(()) => self.execute("COMMIT", &[]),
+            Err(e) => {
+                // The insert error is the one worth reporting.
+                let _ = self.execute("ROLLBACK", &[]);
+                Err(e)
+            }
+        }
+    }
+
+    fn insert_rows(
+        &mut self,
+        signature: &str,
+        slot: u64,
+        block_time: Option<i64>,
+        events: &[IndexedEvent],
+    ) -> Result<(), String> {
+        self.execute(
+            "INSERT INTO transactions (signature, slot, block_time) VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING",
+            &[text(signature), int(slot)?, Value::NullableInt(block_time)],
+        )?;
+
+        for (index, event) in events.iter().enumerate() {
+            let index = Value::Int(index as i64);
+            self.execute(
+                "INSERT INTO events (signature, event_index, vault, sequence, name, data)
+                 VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT DO NOTHING",
+                &[
+                    text(signature),
+                    index.clone(),
+                    text(&event.vault.to_string()),
+                    int(event.sequence)?,
+                    text(event.name),
+                    text(&hex(&event.data)),
+                ],
+            )?;
+
+            match &event.record {
+                Some(Record::Deposit(deposit)) => self.insert_deposit(signature, index, deposit)?,
+                Some(Record::Withdrawal(withdrawal)) => {
+                    self.insert_withdrawal(signature, index, withdrawal)?
+                }
+                Some(Record::Rotation(rotation)) => {
+                    self.insert_rotation(signature, index, rotation)?
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_deposit(
+        &mut self,
+        signature: &str,
+        index: Value,
+        event: &DepositEvent,
+    ) -> Result<(), String> {
+        self.execute(
+            "INSERT INTO deposits (signature, event_index, vault, request_id, depositor, asset,
+                amount, net_amount, admin, valid_signers, metadata)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT DO NOTHING",
+            &[
+                text(signature),
+                index,
+                text(&event.vault.to_string()),
+                int(event.request_id)?,
+                text(&event.depositor.to_string()),
+                text(&asset(&event.asset)),
+                int(event.amount)?,
+                int(event.net_amount)?,
+                Value::Bool(event.admin),
+                Value::Int(event.valid_signers as i64),
+                Value::Text(event.metadata.clone()),
+            ],
+        )
+    }
+
+    fn insert_withdrawal(
+        &mut self,
+        signature: &str,
+        index: Value,
+        event: &WithdrawEvent,
+    ) -> Result<(), String> {
+        self.execute(
+            "INSERT INTO withdrawals (signature, event_index, vault, request_id, recipient, asset,
+                amount, gross_amount, fee, admin, valid_signers, metadata)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) ON CONFLICT DO NOTHING",
+            &[
+                text(signature),
+                index,
+                text(&event.vault.to_string()),
+                int(event.request_id)?,
+                text(&event.recipient.to_string()),
+                text(&asset(&event.asset)),
+                int(event.amount)?,
+                int(event.gross_amount)?,
+                int(event.fee)?,
+                Value::Bool(event.admin),
+                Value::Int(event.valid_signers as i64),
+                Value::Text(event.metadata.clone()),
+            ],
+        )
+    }
+
+    fn insert_rotation(
+        &mut self,
+        signature: &str,
+        index: Value,
+        event: &RotateEvent,
+    ) -> Result<(), String> {
+        // `<address hex>:<weight>` per signer, comma separated.
+        let signers = event
+            .signers
+            .iter()
+            .map(|signer| format!("{}:{}", hex(&signer.address), signer.weight))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.execute(
+            "INSERT INTO rotations (signature, event_index, vault, request_id, signers,
+                m_threshold, admin_threshold, valid_signers)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT DO NOTHING",
+            &[
+                text(signature),
+                index,
+                text(&event.vault.to_string()),
+                int(event.request_id)?,
+                text(&signers),
+                Value::Int(event.m_threshold as i64),
+                Value::Int(event.admin_threshold as i64),
+                Value::Int(event.valid_signers as i64),
+            ],
+        )
+    }
+
+    fn execute(&mut self, sql: &str, params: &[Value]) -> Result<(), String> {
+        match self {
+            Store::Sqlite(connection) => connection
+                .execute(
+                    sql,
+                    rusqlite::params_from_iter(params.iter().map(sqlite_value)),
+                )
+                .map(|_| ())
+                .map_err(|e| format!("database: {}", e)),
+            Store::Postgres(client) => client
+                .execute(
+                    sql,
+                    &postgres_params(params)
+                        .iter()
+                        .map(|p| p.as_ref())
+                        .collect::<Vec<_>>(),
+                )
+                .map(|_| ())
+                .map_err(|e| format!("database: {}", e)),
+        }
+    }
+
+    /// First column of the first row, if any.
+    fn query_text(&mut self, sql: &str, params: &[Value]) -> Result<Option<String>, String> {
+        match self {
+            Store::Sqlite(connection) => {
+                let mut statement = connection
+                    .prepare(sql)
+                    .map_err(|e| format!("database: {}", e))?;
+                let mut rows = statement
+                    .query(rusqlite::params_from_iter(params.iter().map(sqlite_value)))
+                    .map_err(|e| format!("database: {}", e))?;
+                match rows.next().map_err(|e| format!("database: {}", e))? {
+                    Some(row) => row.get(0).map(Some).map_err(|e| format!("database: {}", e)),
+                    None => Ok(None),
+                }
+            }
+            Store::Postgres(client) => {
+                let params = postgres_params(params);
+                let row = client
+                    .query_opt(sql, &params.iter().map(|p| p.as_ref()).collect::<Vec<_>>())
+                    .map_err(|e| format!("database: {}", e))?;
+                Ok(row.map(|row| row.get(0)))
+            }
+        }
+    }
+}
+
+fn sqlite_value(value: &Value) -> rusqlite::types::Value {
+    match value {
+        Value::Int(value) | Value::NullableInt(Some(value)) => {
+            rusqlite::types::Value::Integer(*value)
+        }
+        Value::NullableInt(None) => rusqlite::types::Value::Null,
+        Value::Text(Some(value)) => rusqlite::types::Value::Text(value.clone()),
+        Value::Text(None) => rusqlite::types::Value::Null,
+        Value::Bool(value) => rusqlite::types::Value::Integer(*value as i64),
+    }
+}
+
+fn postgres_params(params: &[Value]) -> Vec<Box<dyn postgres::types::ToSql + Sync>> {
+    params
+        .iter()
+        .map(|value| -> Box<dyn postgres::types::ToSql + Sync> {
+            match value {
+                Value::Int(value) => Box::new(*value),
+                Value::NullableInt(value) => Box::new(*value),
+                Value::Text(value) => Box::new(value.clone()),
+                Value::Bool(value) => Box::new(*value),
+            }
+        })
+        .collect()
+}
+
+fn text(value: &str) -> Value {
+    Value::Text(Some(value.to_string()))
+}
+
+/// Amounts and ids are u64 on chain but BIGINT columns are signed.
+fn int(value: u64) -> Result<Value, String> {
+    i64::try_from(value)
+        .map(Value::Int)
+        .map_err(|_| format!("{} exceeds the BIGINT range", value))
+}
+
+fn asset(asset: &Asset) -> String {
+    match asset {
+        Asset::Sol => "sol".to_string(),
+        Asset::SplToken { mint } => format!("spl:{}", mint),
+        Asset::Nft { mint } => format!("nft:{}", mint),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,4466 @@
+//! solana-program-test harness for `strike-example`.
+//!
+//! Runs the program natively in-process and exposes reusable builders for signers,
+//! tickets and instructions, so forks of the program inherit the integration suite.
+//! Ticket hashes come from the program's own `Ticket::hash`, so the harness can't
+//! drift from on-chain hashing. Signers and PDA derivation come from `strike-client`.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, system_instruction, sysvar};
+use anchor_lang::{
+    system_program, AccountDeserialize, AnchorDeserialize, AnchorSerialize, Discriminator,
+    InstructionData, Space, ToAccountMetas,
+};
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use k256::ecdsa::Signature;
+use solana_program::{ed25519_program, keccak, stake};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::clock::Clock;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub use strike_client::pda::*;
+pub use strike_client::{bulk_account_indexes, EthSigner};
+use strike_example::instructions::constant::{
+    CHAINLINK_HEADER_SIZE, CHAINLINK_STORE_PROGRAM_ID, CHAINLINK_TRANSMISSIONS_DISCRIMINATOR,
+    CIVIC_GATEWAY_PROGRAM_ID, HOOK_IX_DISCRIMINATOR, INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR,
+    JUPITER_PROGRAM_ID, PRICE_UPDATE_V2_DISCRIMINATOR, PYTH_RECEIVER_PROGRAM_ID,
+    REPLACE_LEAF_DISCRIMINATOR, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID,
+    SPL_STAKE_POOL_PROGRAM_ID, TICKET_VERSION,
+};
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::signer_set_hash;
+use strike_example::instructions::util::{
+    attestation_address, claim_node_hash, ed25519_signer_address,
+};
+use strike_example::instructions::{
+    AddAssetTicket, AddCrankerTicket, AddRecipientTicket, AddStrategyTicket, AdminDepositTicket,
+    AdminWithdrawalTicket, Allowance, AnyTicket, Asset, AssetAmount, AssetSettings, AuthScheme,
+    BlockRecipientTicket, Blocklist, BulkWithdrawalTicket, ChainlinkFeedHeader,
+    ChainlinkTransmission, ClaimLeaf, ClaimRoot, ClaimRootTicket, CloseVaultTokenAccountTicket,
+    CollectFeesTicket, CpiAccountMeta, CrankRegistry, CreateReceiptMintTicket,
+    CreateScheduleTicket, CreateStreamTicket, CreateSubVaultTicket, CreditTicket,
+    DeactivateStakeTicket, DelegateStakeTicket, DepositAttestation, DepositRecord, ExecuteTicket,
+    GatewayToken, GatewayTokenState, HookPayload, LiquidStakeTicket, LiquidUnstakeTicket,
+    LogVerbosity, NonceAccount, PostedVaaData, PriceFeedMessage, PriceOracle, PriceUpdateV2,
+    ProposeSignerSetTicket, RebalanceTicket, RecipientAllowlist, ReclaimSubVaultTicket,
+    RemoveAssetTicket, RemoveCrankerTicket, RemoveRecipientTicket, RemoveStrategyTicket,
+    RotateAdminSignersTicket, RotateValidatorTicket, Schedule, SetAllowanceTicket,
+    SetAuthSchemeTicket, SetCrankIncentiveTicket, SetDepositRegistryTicket, SetExpiryGraceTicket,
+    SetExtraNetworkIdsTicket, SetFeeTicket, SetGuardianTicket, SetHookProgramTicket,
+    SetIdentityNetworkTicket, SetLogVerbosityTicket, SetMaxTicketLifetimeTicket,
+    SetMaxTicketUsdTicket, SetMinWithdrawalTicket, SetNonceTreeTicket, SetRecipientAllowlistTicket,
+    SetRelayerRebateTicket, SetReplayModeTicket, SetStrictSignaturesTicket,
+    SetTreasuryReserveTicket, SetTreasuryTicket, SetUsdCapTicket, SetWormholeEmitterTicket,
+    SignerWithSignature, StrategyAllowlist, StrategyTicket, Stream, SwapTicket, SweepAssetTicket,
+    SweepDustTicket, Ticket, UnblockRecipientTicket, UpdateAssetTicket, UpgradeProgramTicket,
+    UsdCap, Vault, VaultV1, VerificationLevel, WeightedSigner, WithdrawStakeTicket,
+    WithdrawalReceipt, WithdrawalTicket,
+};
+
+pub const NETWORK_ID: u64 = 102;
+pub const DEFAULT_EXPIRY_SECS: i64 = 3600;
+pub const STAKE_CONFIG_ID: Pubkey =
+    solana_sdk::pubkey!("StakeConfig11111111111111111111111111111111");
+/// Program id of the test hook loaded next to `strike-example`, see `process_hook`.
+pub const TEST_HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x4b; 32]);
+/// Request id the test hook refuses, to show a hook can revert the whole instruction.
+pub const HOOK_REJECTED_REQUEST_ID: u64 = 666;
+/// Attestor program for `AuthScheme::Attestor` vaults; `attest` creates its attestations.
+pub const TEST_ATTESTOR_ID: Pubkey = Pubkey::new_from_array([0x41; 32]);
+/// Stands in for the Wormhole core bridge as the owner of `post_vaa` accounts.
+pub const TEST_CORE_BRIDGE_ID: Pubkey = Pubkey::new_from_array([0x57; 32]);
+pub const TEST_EMITTER_CHAIN: u16 = 2;
+pub const TEST_EMITTER_ADDRESS: [u8; 32] = [0xee; 32];
+
+/// Anchor's `entry` wants `&'info [AccountInfo<'info>]`; program-test hands out shorter borrows.
+fn process_instruction<'a, 'b, 'c, 'info>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'info>],
+    data: &'c [u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    strike_example::entry(program_id, accounts, data)
+}
+
+/// Test hook program: accepts `on_vault_event` calls signed by the vault's hook authority, except
+/// for `HOOK_REJECTED_REQUEST_ID`.
+fn process_hook(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let Some(mut payload) = data.strip_prefix(&HOOK_IX_DISCRIMINATOR[..]) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let payload =
+        HookPayload::deserialize(&mut payload).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let authority = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if !authority.is_signer || *authority.key != hook_authority_address(&payload.vault) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if payload.request_id == HOOK_REJECTED_REQUEST_ID {
+        return Err(ProgramError::Custom(0));
+    }
+    Ok(())
+}
+
+/// Stands in for Jupiter: swaps `amount_in` of the user's tokens into the pool for `amount_out`
+/// of the pool's, as `swap_route` lays out the accounts and data.
+fn process_swap(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [token_program, user, user_source, pool_input, pool_output, user_destination, pool_authority, ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (amount_in, amount_out) = <(u64, u64)>::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let pay_in = spl_token::instruction::transfer(
+        token_program.key,
+        user_source.key,
+        pool_input.key,
+        user.key,
+        &[],
+        amount_in,
+    )?;
+    invoke(
+        &pay_in,
+        &[user_source.clone(), pool_input.clone(), user.clone()],
+    )?;
+
+    let (_, bump) = Pubkey::find_program_address(&[b"pool"], program_id);
+    let pay_out = spl_token::instruction::transfer(
+        token_program.key,
+        pool_output.key,
+        user_destination.key,
+        pool_authority.key,
+        &[],
+        amount_out,
+    )?;
+    invoke_signed(
+        &pay_out,
+        &[
+            pool_output.clone(),
+            user_destination.clone(),
+            pool_authority.clone(),
+        ],
+        &[&[b"pool", &[bump]]],
+    )
+}
+
+/// Byte offset of the first leaf in a test nonce tree, after the authority and depth.
+const NONCE_TREE_HEADER: usize = 36;
+
+/// Stands in for the SPL account compression program. `init_empty_merkle_tree` stores the
+/// authority and depth at the head of the tree account; `replace_leaf` keeps each leaf in a
+/// 32-byte slot after them and, like the real program, needs the authority's signature, the
+/// current leaf as the previous one and a proof node per level. Roots aren't tracked.
+fn process_compression(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [tree, authority, _noop, proof @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if tree.owner != program_id || !tree.is_writable {
+        return Err(ProgramError::IllegalOwner);
+    }
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let mut tree_data = tree.try_borrow_mut_data()?;
+    let (discriminator, args) = data.split_at(8);
+
+    if discriminator == INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR {
+        if tree_data[..NONCE_TREE_HEADER].iter().any(|&byte| byte != 0) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        let max_depth = u32::from_le_bytes(args[..4].try_into().unwrap());
+        if tree_data.len() < NONCE_TREE_HEADER + (32 << max_depth) {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        tree_data[..32].copy_from_slice(authority.key.as_ref());
+        tree_data[32..NONCE_TREE_HEADER].copy_from_slice(&max_depth.to_le_bytes());
+        return Ok(());
+    }
+    if discriminator != REPLACE_LEAF_DISCRIMINATOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let max_depth = u32::from_le_bytes(tree_data[32..NONCE_TREE_HEADER].try_into().unwrap());
+    if tree_data[..32] != authority.key.as_ref()[..] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if proof.len() != max_depth as usize {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let previous_leaf = &args[32..64];
+    let new_leaf = &args[64..96];
+    let index = u32::from_le_bytes(args[96..100].try_into().unwrap());
+    if index >> max_depth != 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let slot = NONCE_TREE_HEADER + 32 * index as usize;
+    if tree_data[slot..slot + 32] != *previous_leaf {
+        return Err(ProgramError::Custom(1)); // the leaf was already replaced
+    }
+    tree_data[slot..slot + 32].copy_from_slice(new_leaf);
+    Ok(())
+}
+
+/// Stands in for the SPL noop program the compression program logs through.
+fn process_noop(_program_id: &Pubkey, _accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    Ok(())
+}
+
+/// Size of the upgradeable loader's `ProgramData` header preceding the program's code.
+const PROGRAM_DATA_HEADER: usize = 45;
+
+/// The vault program runs from its SBF build when `SBF_OUT_DIR` is set, deployed behind the
+/// upgradeable loader so it can be upgraded, and natively otherwise. The hook, swap,
+/// compression and noop test programs only exist natively.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = if std::env::var("SBF_OUT_DIR").is_ok() {
+        let mut program_test = ProgramTest::default();
+        program_test.add_upgradeable_program_to_genesis("strike_example", &strike_example::ID);
+        program_test
+    } else {
+        ProgramTest::new(
+            "strike_example",
+            strike_example::ID,
+            processor!(process_instruction),
+        )
+    };
+    program_test.prefer_bpf(false);
+    program_test.add_program("test_hook", TEST_HOOK_PROGRAM_ID, processor!(process_hook));
+    program_test.add_program("test_swap", JUPITER_PROGRAM_ID, processor!(process_swap));
+    program_test.add_program(
+        "spl_account_compression",
+        SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        processor!(process_compression),
+    );
+    program_test.add_program("spl_noop", SPL_NOOP_PROGRAM_ID, processor!(process_noop));
+    program_test
+}
+
+/// The high-s twin of a signature: (r, n - s) with the recovery id flipped,
+/// which recovers the same address.
+pub fn high_s(sig: &SignerWithSignature) -> SignerWithSignature {
+    let signature = Signature::from_slice(&sig.signature).expect("valid signature");
+    let (r, s) = signature.split_scalars();
+    let flipped = Signature::from_scalars(r.to_bytes(), (-*s).to_bytes()).expect("valid scalars");
+
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&flipped.to_bytes());
+
+    SignerWithSignature {
+        signature: bytes,
+        recovery_id: sig.recovery_id ^ 1,
+    }
+}
+
+/// Signer-set entry for an Ed25519 key, with weight 1.
+pub fn ed25519_signer(keypair: &Keypair) -> WeightedSigner {
+    WeightedSigner {
+        address: ed25519_signer_address(&keypair.pubkey()),
+        weight: 1,
+        valid_until: None,
+    }
+}
+
+/// Ed25519 precompile instruction carrying `keypair`'s signature over the ticket hash. It goes
+/// before the program instruction, which needs `instructions_sysvar()` in its remaining accounts.
+pub fn ed25519_ix(keypair: &Keypair, ticket: &dyn Ticket, domain_suffix: &str) -> Instruction {
+    const PUBKEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+
+    let message = ticket.hash(domain_suffix);
+    let signature = keypair.sign_message(&message);
+
+    let mut data = vec![1u8, 0];
+    for field in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBKEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(keypair.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(&message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+pub fn instructions_sysvar() -> AccountMeta {
+    readonly(sysvar::instructions::ID)
+}
+
+/// Merkle tree over claim leaves, hashed the way `claim` verifies it. An odd node is carried up
+/// to the next level unchanged.
+pub struct ClaimTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl ClaimTree {
+    pub fn new(leaves: &[ClaimLeaf]) -> Self {
+        let mut levels = vec![leaves.iter().map(ClaimLeaf::hash).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => claim_node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        proof
+    }
+}
+
+/// PDA owning the mock Jupiter program's pool token accounts.
+pub fn swap_pool_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool"], &JUPITER_PROGRAM_ID).0
+}
+
+pub fn sol(amount: u64) -> AssetAmount {
+    AssetAmount {
+        asset: Asset::Sol,
+        amount,
+    }
+}
+
+pub fn spl(mint: Pubkey, amount: u64) -> AssetAmount {
+    AssetAmount {
+        asset: Asset::SplToken { mint },
+        amount,
+    }
+}
+
+pub fn readonly(pubkey: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(pubkey, false)
+}
+
+pub fn writable(pubkey: Pubkey) -> AccountMeta {
+    AccountMeta::new(pubkey, false)
+}
+
+/// Assert that a transaction failed with the given program error.
+pub fn assert_error(result: Result<(), TransactionError>, expected: ErrorCode) {
+    let code: u32 = expected.into();
+    match result {
+        Err(TransactionError::InstructionError(
+            _,
+            solana_sdk::instruction::InstructionError::Custom(actual),
+        )) => assert_eq!(actual, code, "expected {:?}", expected),
+        other => panic!("expected {:?} ({}), got {:?}", expected, code, other),
+    }
+}
+
+/// Assert that a transaction failed for any reason.
+pub fn assert_failed(result: Result<(), TransactionError>) {
+    assert!(result.is_err(), "transaction unexpectedly succeeded");
+}
+
+pub struct VaultConfig {
+    pub signers: usize,
+    pub weights: Vec<u8>, // per signer, empty for weight 1 each
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub domain_suffix: String,
+    pub auth_scheme: AuthScheme,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            signers: 3,
+            weights: vec![],
+            m_threshold: 2,
+            admin_threshold: 3,
+            domain_suffix: String::new(),
+            auth_scheme: AuthScheme::Secp256k1,
+        }
+    }
+}
+
+/// Accounts of an SPL stake pool the vault can deposit into.
+pub struct TestStakePool {
+    pub stake_pool: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub pool_mint: Pubkey,
+}
+
+/// Token accounts of a pool the mock Jupiter program swaps against.
+pub struct TestSwapPool {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub pool_input: Pubkey,
+    pub pool_output: Pubkey,
+}
+
+/// An initialized vault running under program-test.
+pub struct TestVault {
+    pub context: ProgramTestContext,
+    pub vault_seed: String,
+    pub domain_suffix: String,
+    pub signers: Vec<EthSigner>,
+    pub vault: Pubkey,
+    pub treasury: Pubkey,
+    next_request_id: u64,
+}
+
+impl TestVault {
+    pub async fn new(config: VaultConfig) -> Self {
+        let context = program_test().start_with_context().await;
+        let signers: Vec<EthSigner> = (0..config.signers as u64)
+            .map(|i| {
+                let signer = EthSigner::from_seed(i);
+                match config.weights.get(i as usize) {
+                    Some(&weight) => signer.with_weight(weight),
+                    None => signer,
+                }
+            })
+            .collect();
+
+        let vault_seed = format!("test-vault-{}", config.signers);
+        let vault = vault_address(&vault_seed);
+        let treasury = treasury_address(&vault);
+
+        let mut test_vault = Self {
+            context,
+            vault_seed: vault_seed.clone(),
+            domain_suffix: config.domain_suffix.clone(),
+            signers,
+            vault,
+            treasury,
+            next_request_id: 1,
+        };
+
+        let ix = test_vault.initialize_ix_with_scheme(
+            vault_seed,
+            config.m_threshold,
+            config.admin_threshold,
+            test_vault.signer_set(),
+            vec![],
+            config.domain_suffix,
+            config.auth_scheme,
+        );
+        test_vault
+            .process(&[ix], &[])
+            .await
+            .expect("vault initializes");
+
+        test_vault
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn signer_set(&self) -> Vec<WeightedSigner> {
+        self.signers.iter().map(EthSigner::weighted).collect()
+    }
+
+    pub fn request_id(&mut self) -> u64 {
+        self.next_request_id += 1;
+        self.next_request_id
+    }
+
+    /// Sign `ticket` with the signers at `indexes`, ordered by signer address as the program
+    /// requires.
+    pub fn sign(&self, ticket: &dyn Ticket, indexes: &[usize]) -> Vec<SignerWithSignature> {
+        let mut indexes = indexes.to_vec();
+        indexes.sort_by_key(|&i| self.signers[i].address);
+        indexes
+            .iter()
+            .map(|&i| self.signers[i].sign(ticket, &self.domain_suffix))
+            .collect()
+    }
+
+    pub fn sign_all(&self, ticket: &dyn Ticket) -> Vec<SignerWithSignature> {
+        let indexes: Vec<usize> = (0..self.signers.len()).collect();
+        self.sign(ticket, &indexes)
+    }
+
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        extra_signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .expect("blockhash");
+
+        let mut signers: Vec<&Keypair> = vec![&self.context.payer];
+        signers.extend_from_slice(extra_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &signers,
+            blockhash,
+        );
+
+        self.context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map_err(|e: BanksClientError| e.unwrap())
+    }
+
+    /// Simulate a view instruction and decode its return data.
+    pub async fn view<T: AnchorDeserialize>(&mut self, instruction: Instruction) -> T {
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .expect("blockhash");
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer],
+            blockhash,
+        );
+
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .expect("simulation");
+        simulation
+            .result
+            .expect("simulation result")
+            .expect("view succeeds");
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("return data");
+        T::try_from_slice(&return_data.data).expect("return data deserializes")
+    }
+
+    /// Simulate `instructions` and return the logs, whether or not they succeed.
+    pub async fn simulate_logs(&mut self, instructions: &[Instruction]) -> Vec<String> {
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .expect("blockhash");
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer],
+            blockhash,
+        );
+
+        self.context
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .expect("simulation")
+            .simulation_details
+            .expect("simulation details")
+            .logs
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .expect("clock")
+    }
+
+    pub async fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn expiry(&mut self) -> i64 {
+        self.now().await + DEFAULT_EXPIRY_SECS
+    }
+
+    pub async fn lamports(&mut self, pubkey: &Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(*pubkey)
+            .await
+            .expect("balance")
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = system_instruction::transfer(&self.payer(), to, lamports);
+        self.process(&[ix], &[]).await.expect("funding succeeds");
+    }
+
+    pub async fn new_funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        self.fund(&keypair.pubkey(), lamports).await;
+        keypair
+    }
+
+    async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .expect("account fetch")?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).expect("account deserializes"))
+    }
+
+    pub async fn vault_state(&mut self) -> Vault {
+        let vault = self.vault;
+        self.account::<Vault>(&vault).await.expect("vault exists")
+    }
+
+    /// Assets on the vault's whitelist, in whitelisting order.
+    pub async fn whitelisted_assets(&mut self) -> Vec<Asset> {
+        let vault = self.vault_state().await;
+        vault
+            .whitelisted_assets
+            .into_iter()
+            .map(|config| config.asset)
+            .collect()
+    }
+
+    /// State of another vault created in the same program-test context.
+    pub async fn vault_state_of(&mut self, vault: &Pubkey) -> Vault {
+        self.account::<Vault>(vault).await.expect("vault exists")
+    }
+
+    pub async fn deposit_record(&mut self, user: &Pubkey) -> Option<DepositRecord> {
+        let address = deposit_record_address(&self.vault, user);
+        self.account::<DepositRecord>(&address).await
+    }
+
+    pub async fn allowance(&mut self, recipient: &Pubkey, asset: &Asset) -> Option<Allowance> {
+        let address = allowance_address(&self.vault, recipient, asset);
+        self.account::<Allowance>(&address).await
+    }
+
+    pub async fn usd_cap(&mut self, asset: &Asset) -> Option<UsdCap> {
+        let address = usd_cap_address(&self.vault, asset);
+        self.account::<UsdCap>(&address).await
+    }
+
+    pub async fn recipient_allowlist(&mut self) -> Option<RecipientAllowlist> {
+        let address = recipient_allowlist_address(&self.vault);
+        self.account::<RecipientAllowlist>(&address).await
+    }
+
+    pub async fn strategy_allowlist(&mut self) -> Option<StrategyAllowlist> {
+        let address = strategy_allowlist_address(&self.vault);
+        self.account::<StrategyAllowlist>(&address).await
+    }
+
+    pub async fn blocklist(&mut self) -> Option<Blocklist> {
+        let address = blocklist_address(&self.vault);
+        self.account::<Blocklist>(&address).await
+    }
+
+    pub async fn crank_registry(&mut self) -> Option<CrankRegistry> {
+        let address = crank_registry_address(&self.vault);
+        self.account::<CrankRegistry>(&address).await
+    }
+
+    pub async fn claim_root(&mut self, request_id: u64) -> Option<ClaimRoot> {
+        let address = claim_root_address(&self.vault, request_id);
+        self.account::<ClaimRoot>(&address).await
+    }
+
+    pub async fn withdrawal_receipt(&mut self, request_id: u64) -> Option<WithdrawalReceipt> {
+        let address = withdrawal_receipt_address(&self.vault, request_id);
+        self.account::<WithdrawalReceipt>(&address).await
+    }
+
+    pub async fn stream(&mut self, request_id: u64) -> Option<Stream> {
+        let address = stream_address(&self.vault, request_id);
+        self.account::<Stream>(&address).await
+    }
+
+    pub async fn schedule(&mut self, request_id: u64) -> Option<Schedule> {
+        let address = schedule_address(&self.vault, request_id);
+        self.account::<Schedule>(&address).await
+    }
+
+    pub async fn nonce_used(&mut self, nonce: &Pubkey) -> bool {
+        self.account::<NonceAccount>(nonce)
+            .await
+            .map(|n| n.used)
+            .unwrap_or(false)
+    }
+
+    pub async fn nonce_account(&mut self, nonce: &Pubkey) -> Option<NonceAccount> {
+        self.account::<NonceAccount>(nonce).await
+    }
+
+    // ---------------------------------------------------------------------
+    // SPL helpers
+    // ---------------------------------------------------------------------
+
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let space = spl_token::state::Mint::LEN;
+
+        let ixs = [
+            system_instruction::create_account(
+                &self.payer(),
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.payer(),
+                None,
+                decimals,
+            )
+            .expect("mint ix"),
+        ];
+        self.process(&ixs, &[&mint]).await.expect("mint created");
+
+        mint.pubkey()
+    }
+
+    pub async fn create_ata(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &self.payer(),
+            owner,
+            mint,
+            &spl_token::ID,
+        );
+        self.process(&[ix], &[]).await.expect("ata created");
+
+        get_associated_token_address(owner, mint)
+    }
+
+    /// A token account of `owner` for `mint` at a fresh address, not the owner's ATA.
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let space = spl_token::state::Account::LEN;
+
+        let ixs = [
+            system_instruction::create_account(
+                &self.payer(),
+                &account.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::ID,
+                &account.pubkey(),
+                mint,
+                owner,
+            )
+            .expect("token account ix"),
+        ];
+        self.process(&ixs, &[&account])
+            .await
+            .expect("token account created");
+
+        account.pubkey()
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let ix = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint,
+            account,
+            &self.payer(),
+            &[],
+            amount,
+        )
+        .expect("mint_to ix");
+        self.process(&[ix], &[]).await.expect("minted");
+    }
+
+    /// Have the test attestor approve `ticket`, returning the attestation as a remaining account.
+    pub fn attest(&mut self, ticket: &dyn Ticket) -> AccountMeta {
+        let attestation = attestation_address(
+            &self.vault,
+            &TEST_ATTESTOR_ID,
+            &ticket.hash(&self.domain_suffix),
+        );
+        let mut account = AccountSharedData::new(1_000_000_000, 1, &TEST_ATTESTOR_ID);
+        account.set_data_from_slice(&[1]);
+        self.context.set_account(&attestation, &account);
+        readonly(attestation)
+    }
+
+    /// Rewrite the vault as version 1 of the program left it: bare signer addresses and assets,
+    /// nothing past `reserve`, and rent for that size only.
+    pub async fn rewrite_as_v1(&mut self, whitelisted_assets: Vec<Asset>) {
+        let state = self.vault_state().await;
+        let legacy = VaultV1 {
+            version: 1,
+            authority: state.authority,
+            vault_seed: state.vault_seed,
+            m_threshold: state.m_threshold,
+            admin_threshold: state.admin_threshold,
+            network_id: state.network_id,
+            signers: state.signers.iter().map(|signer| signer.address).collect(),
+            whitelisted_assets,
+            bump: state.bump,
+            treasury_bump: state.treasury_bump,
+            reserve: state.reserve,
+        };
+        let mut data = Vault::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).expect("vault serializes");
+        data.resize(8 + VaultV1::INIT_SPACE, 0);
+
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let mut account = AccountSharedData::new(
+            rent.minimum_balance(data.len()),
+            data.len(),
+            &strike_example::ID,
+        );
+        account.set_data_from_slice(&data);
+        let vault = self.vault;
+        self.context.set_account(&vault, &account);
+    }
+
+    /// A `PostedVAA` account as the core bridge leaves it once the guardian signatures over the
+    /// VAA have been verified, carrying `payload`.
+    pub fn post_vaa(
+        &mut self,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        payload: Vec<u8>,
+    ) -> Pubkey {
+        let vaa = PostedVaaData {
+            vaa_version: 1,
+            consistency_level: 1,
+            vaa_time: 0,
+            vaa_signature_account: Pubkey::default(),
+            submission_time: 0,
+            nonce: 0,
+            sequence: 0,
+            emitter_chain,
+            emitter_address,
+            payload,
+        };
+        let mut data = b"vaa".to_vec();
+        vaa.serialize(&mut data).expect("vaa serializes");
+
+        let posted_vaa = Keypair::new().pubkey();
+        let mut account = AccountSharedData::new(1_000_000_000, data.len(), &TEST_CORE_BRIDGE_ID);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&posted_vaa, &account);
+        posted_vaa
+    }
+
+    /// Hand this program's upgrade authority to `authority`, as a deploy from that key leaves it.
+    /// The SBF build keeps its code; natively only the program data header exists, so the
+    /// loader can move the authority but has nothing to upgrade.
+    pub async fn deploy_program_data(&mut self, authority: Pubkey) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(program_data_address())
+            .await
+            .expect("account")
+            .unwrap_or_else(|| Account {
+                lamports: 1_000_000_000,
+                data: vec![0; PROGRAM_DATA_HEADER],
+                owner: bpf_loader_upgradeable::ID,
+                ..Account::default()
+            });
+        account.data[..4].copy_from_slice(&3u32.to_le_bytes()); // UpgradeableLoaderState::ProgramData
+        account.data[12] = 1;
+        account.data[13..PROGRAM_DATA_HEADER].copy_from_slice(authority.as_ref());
+        self.context
+            .set_account(&program_data_address(), &account.into());
+    }
+
+    /// A buffer holding this program's current build, already handed to the vault's upgrade
+    /// authority as `upgrade_program` requires.
+    pub async fn upgrade_buffer(&mut self) -> Pubkey {
+        let program_data = self
+            .context
+            .banks_client
+            .get_account(program_data_address())
+            .await
+            .expect("account")
+            .expect("program data exists");
+        let mut data = 1u32.to_le_bytes().to_vec(); // UpgradeableLoaderState::Buffer
+        data.push(1);
+        data.extend_from_slice(upgrade_authority_address(&self.vault).as_ref());
+        data.extend_from_slice(&program_data.data[PROGRAM_DATA_HEADER..]);
+        let buffer = Keypair::new().pubkey();
+        let account = Account {
+            lamports: 10_000_000_000,
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            ..Account::default()
+        };
+        self.context.set_account(&buffer, &account.into());
+        buffer
+    }
+
+    /// Upgrade authority recorded in this program's program data account.
+    pub async fn program_upgrade_authority(&mut self) -> Option<Pubkey> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(program_data_address())
+            .await
+            .expect("account")
+            .expect("program data exists");
+        (account.data[12] == 1)
+            .then(|| Pubkey::try_from(&account.data[13..PROGRAM_DATA_HEADER]).unwrap())
+    }
+
+    /// An unset nonce tree account of `max_depth`, owned by the test compression program.
+    pub fn create_nonce_tree(&mut self, max_depth: u32) -> Pubkey {
+        let nonce_tree = Keypair::new().pubkey();
+        let account = AccountSharedData::new(
+            1_000_000_000,
+            NONCE_TREE_HEADER + (32 << max_depth),
+            &SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        );
+        self.context.set_account(&nonce_tree, &account);
+        nonce_tree
+    }
+
+    /// Remaining accounts recording a withdrawal in `nonce_tree`: the programs, the authority
+    /// PDA and the tree, then a root and `proof_len` proof nodes. The test compression program
+    /// checks neither, only the proof length.
+    pub fn nonce_tree_accounts(&self, nonce_tree: Pubkey, proof_len: u32) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            readonly(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID),
+            readonly(SPL_NOOP_PROGRAM_ID),
+            readonly(nonce_tree_authority_address(&self.vault)),
+            writable(nonce_tree),
+            readonly(Pubkey::new_unique()),
+        ];
+        accounts.extend((0..proof_len).map(|_| readonly(Pubkey::new_unique())));
+        accounts
+    }
+
+    /// A fully verified Pyth `PriceUpdateV2` account for `feed_id`, published at `publish_time`.
+    pub fn post_pyth_price(
+        &mut self,
+        feed_id: [u8; 32],
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        publish_time: i64,
+    ) -> Pubkey {
+        let update = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price,
+                conf,
+                exponent,
+                publish_time,
+                prev_publish_time: publish_time,
+                ema_price: price,
+                ema_conf: conf,
+            },
+            posted_slot: 0,
+        };
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        update
+            .serialize(&mut data)
+            .expect("price update serializes");
+
+        let price_update = Keypair::new().pubkey();
+        let mut account =
+            AccountSharedData::new(1_000_000_000, data.len(), &PYTH_RECEIVER_PROGRAM_ID);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&price_update, &account);
+        price_update
+    }
+
+    /// An attestation of `subject` under `schema`, owned by the `registry` program.
+    pub fn post_deposit_attestation(
+        &mut self,
+        registry: &Pubkey,
+        schema: Pubkey,
+        subject: Pubkey,
+        expiry: i64,
+        revoked: bool,
+    ) -> Pubkey {
+        let mut data = vec![0u8; 8];
+        DepositAttestation {
+            schema,
+            subject,
+            expiry,
+            revoked,
+        }
+        .serialize(&mut data)
+        .expect("attestation serializes");
+
+        let attestation = Keypair::new().pubkey();
+        let mut account = AccountSharedData::new(1_000_000_000, data.len(), registry);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&attestation, &account);
+        attestation
+    }
+
+    /// A Civic gateway token of `wallet` from the gatekeeper `network`.
+    pub fn post_identity_pass(
+        &mut self,
+        wallet: Pubkey,
+        network: Pubkey,
+        state: GatewayTokenState,
+        expire_time: Option<i64>,
+    ) -> Pubkey {
+        let mut data = Vec::new();
+        GatewayToken {
+            features: 0,
+            parent_gateway_token: None,
+            owner_wallet: wallet,
+            owner_identity: None,
+            gatekeeper_network: network,
+            issuing_gatekeeper: Pubkey::new_unique(),
+            state,
+            expire_time,
+        }
+        .serialize(&mut data)
+        .expect("gateway token serializes");
+
+        let pass = Keypair::new().pubkey();
+        let mut account =
+            AccountSharedData::new(1_000_000_000, data.len(), &CIVIC_GATEWAY_PROGRAM_ID);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&pass, &account);
+        pass
+    }
+
+    /// A Chainlink store feed whose latest round reports `answer` with `decimals`, at `timestamp`.
+    /// The round sits in the second of three ring buffer slots, behind an older round.
+    pub fn post_chainlink_round(&mut self, answer: i128, decimals: u8, timestamp: u32) -> Pubkey {
+        let header = ChainlinkFeedHeader {
+            version: 2,
+            state: 0,
+            owner: Pubkey::default(),
+            proposed_owner: Pubkey::default(),
+            writer: Pubkey::default(),
+            description: [0; 32],
+            decimals,
+            flagging_threshold: 0,
+            latest_round_id: 2,
+            granularity: 30,
+            live_length: 3,
+            live_cursor: 2,
+            historical_cursor: 0,
+        };
+        let mut data = CHAINLINK_TRANSMISSIONS_DISCRIMINATOR.to_vec();
+        header.serialize(&mut data).expect("header serializes");
+        data.resize(8 + CHAINLINK_HEADER_SIZE, 0);
+        let rounds = [
+            (answer / 2, timestamp.saturating_sub(60)),
+            (answer, timestamp),
+            (0, 0),
+        ];
+        for (answer, timestamp) in rounds {
+            let round = ChainlinkTransmission {
+                slot: 0,
+                timestamp,
+                padding0: 0,
+                answer,
+                padding1: 0,
+                padding2: 0,
+            };
+            round.serialize(&mut data).expect("round serializes");
+        }
+
+        let feed = Keypair::new().pubkey();
+        let mut account =
+            AccountSharedData::new(1_000_000_000, data.len(), &CHAINLINK_STORE_PROGRAM_ID);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&feed, &account);
+        feed
+    }
+
+    /// A mock Jupiter pool trading `input_mint` for `output_mint`, holding `liquidity` of the
+    /// output mint.
+    pub async fn create_swap_pool(
+        &mut self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        liquidity: u64,
+    ) -> TestSwapPool {
+        let authority = swap_pool_authority();
+        let pool_input = self.create_ata(&authority, &input_mint).await;
+        let pool_output = self.create_ata(&authority, &output_mint).await;
+        self.mint_to(&output_mint, &pool_output, liquidity).await;
+
+        TestSwapPool {
+            input_mint,
+            output_mint,
+            pool_input,
+            pool_output,
+        }
+    }
+
+    /// Route data and accounts for the mock Jupiter program, swapping `amount_in` of the vault's
+    /// input tokens for `amount_out` from `pool`.
+    pub fn swap_route(
+        &self,
+        pool: &TestSwapPool,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> (Vec<u8>, Vec<AccountMeta>) {
+        let mut data = Vec::new();
+        (amount_in, amount_out)
+            .serialize(&mut data)
+            .expect("route serializes");
+        let accounts = vec![
+            readonly(spl_token::ID),
+            readonly(self.vault),
+            writable(get_associated_token_address(&self.vault, &pool.input_mint)),
+            writable(pool.pool_input),
+            writable(pool.pool_output),
+            writable(get_associated_token_address(&self.vault, &pool.output_mint)),
+            readonly(swap_pool_authority()),
+        ];
+        (data, accounts)
+    }
+
+    /// A stake pool account owned by the stake pool program, with a real pool mint. The stake
+    /// pool program itself isn't loaded, so only the vault's own checks can run against it.
+    pub async fn create_stake_pool(&mut self) -> TestStakePool {
+        let stake_pool = Keypair::new().pubkey();
+        self.context.set_account(
+            &stake_pool,
+            &AccountSharedData::new(1_000_000_000, 0, &SPL_STAKE_POOL_PROGRAM_ID),
+        );
+        let pool_mint = self.create_mint(9).await;
+        let manager = Keypair::new().pubkey();
+        let manager_fee_account = self.create_ata(&manager, &pool_mint).await;
+
+        TestStakePool {
+            stake_pool,
+            withdraw_authority: Pubkey::find_program_address(
+                &[stake_pool.as_ref(), b"withdraw"],
+                &SPL_STAKE_POOL_PROGRAM_ID,
+            )
+            .0,
+            reserve_stake: Keypair::new().pubkey(),
+            manager_fee_account,
+            pool_mint,
+        }
+    }
+
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*account)
+            .await
+            .expect("account fetch")
+            .expect("token account exists");
+        spl_token::state::Account::unpack(&account.data)
+            .expect("token account")
+            .amount
+    }
+
+    // ---------------------------------------------------------------------
+    // Ticket builders
+    // ---------------------------------------------------------------------
+
+    pub async fn withdrawal_ticket(
+        &mut self,
+        recipient: Pubkey,
+        withdrawals: Vec<AssetAmount>,
+    ) -> WithdrawalTicket {
+        WithdrawalTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            withdrawals,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+            memo: None,
+            treasury: None,
+            token_accounts: None,
+            source_ref: None,
+            balance_bps: None,
+        }
+    }
+
+    pub async fn admin_withdrawal_ticket(
+        &mut self,
+        recipient: Pubkey,
+        withdrawals: Vec<AssetAmount>,
+    ) -> AdminWithdrawalTicket {
+        AdminWithdrawalTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            withdrawals,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+            source_ref: None,
+        }
+    }
+
+    pub async fn admin_deposit_ticket(&mut self, deposits: Vec<AssetAmount>) -> AdminDepositTicket {
+        AdminDepositTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            deposits,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+            source_ref: None,
+            bypass_whitelist: false,
+        }
+    }
+
+    pub async fn add_asset_ticket(&mut self, asset: Asset) -> AddAssetTicket {
+        AddAssetTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            settings: AssetSettings::default(),
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn update_asset_ticket(
+        &mut self,
+        asset: Asset,
+        settings: AssetSettings,
+    ) -> UpdateAssetTicket {
+        UpdateAssetTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            settings,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn remove_asset_ticket(&mut self, asset: Asset) -> RemoveAssetTicket {
+        RemoveAssetTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_min_withdrawal_ticket(
+        &mut self,
+        asset: Asset,
+        min_amount: u64,
+    ) -> SetMinWithdrawalTicket {
+        SetMinWithdrawalTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            min_amount,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_usd_cap_ticket(
+        &mut self,
+        asset: Asset,
+        oracle: PriceOracle,
+        max_usd: u64,
+        max_age: u64,
+        max_conf_bps: u16,
+    ) -> SetUsdCapTicket {
+        SetUsdCapTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            oracle,
+            max_usd,
+            max_age,
+            max_conf_bps,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_max_ticket_usd_ticket(
+        &mut self,
+        max_ticket_usd: u64,
+    ) -> SetMaxTicketUsdTicket {
+        SetMaxTicketUsdTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            max_ticket_usd,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_log_verbosity_ticket(
+        &mut self,
+        log_verbosity: LogVerbosity,
+    ) -> SetLogVerbosityTicket {
+        SetLogVerbosityTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            log_verbosity,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn add_recipient_ticket(&mut self, recipient: Pubkey) -> AddRecipientTicket {
+        AddRecipientTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn add_strategy_ticket(&mut self, program_id: Pubkey) -> AddStrategyTicket {
+        AddStrategyTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            program_id,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn remove_strategy_ticket(&mut self, program_id: Pubkey) -> RemoveStrategyTicket {
+        RemoveStrategyTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            program_id,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn add_cranker_ticket(&mut self, cranker: Pubkey) -> AddCrankerTicket {
+        AddCrankerTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            cranker,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn remove_cranker_ticket(&mut self, cranker: Pubkey) -> RemoveCrankerTicket {
+        RemoveCrankerTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            cranker,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_crank_incentive_ticket(&mut self, incentive: u64) -> SetCrankIncentiveTicket {
+        SetCrankIncentiveTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            incentive,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    /// Ticket for `instruction`, which `execute_strategy` performs with the vault PDA as signer.
+    pub async fn strategy_ticket(&mut self, instruction: &Instruction) -> StrategyTicket {
+        StrategyTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| CpiAccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data_hash: keccak::hash(&instruction.data).to_bytes(),
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    /// Ticket for `instruction`, which `execute` performs with the vault PDA as signer.
+    pub async fn execute_ticket(&mut self, instruction: &Instruction) -> ExecuteTicket {
+        ExecuteTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| CpiAccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn remove_recipient_ticket(&mut self, recipient: Pubkey) -> RemoveRecipientTicket {
+        RemoveRecipientTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_recipient_allowlist_ticket(
+        &mut self,
+        enabled: bool,
+    ) -> SetRecipientAllowlistTicket {
+        SetRecipientAllowlistTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            enabled,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn block_recipient_ticket(&mut self, recipient: Pubkey) -> BlockRecipientTicket {
+        BlockRecipientTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn unblock_recipient_ticket(&mut self, recipient: Pubkey) -> UnblockRecipientTicket {
+        UnblockRecipientTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_fee_ticket(&mut self, fee_bps: u16) -> SetFeeTicket {
+        SetFeeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            fee_bps,
+            fee_mint: Pubkey::default(),
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_relayer_rebate_ticket(&mut self, max_rebate: u64) -> SetRelayerRebateTicket {
+        SetRelayerRebateTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            max_rebate,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_max_ticket_lifetime_ticket(
+        &mut self,
+        max_lifetime: u64,
+    ) -> SetMaxTicketLifetimeTicket {
+        SetMaxTicketLifetimeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            max_lifetime,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_expiry_grace_ticket(&mut self, grace: u64) -> SetExpiryGraceTicket {
+        SetExpiryGraceTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            grace,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_nonce_tree_ticket(
+        &mut self,
+        nonce_tree: Pubkey,
+        base_request_id: u64,
+        max_depth: u32,
+    ) -> SetNonceTreeTicket {
+        SetNonceTreeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            nonce_tree,
+            base_request_id,
+            max_depth,
+            max_buffer_size: 8,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_extra_network_ids_ticket(
+        &mut self,
+        extra_network_ids: Vec<u64>,
+    ) -> SetExtraNetworkIdsTicket {
+        SetExtraNetworkIdsTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            extra_network_ids,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_guardian_ticket(&mut self, guardian: Pubkey) -> SetGuardianTicket {
+        SetGuardianTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            guardian,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_auth_scheme_ticket(&mut self, auth_scheme: AuthScheme) -> SetAuthSchemeTicket {
+        SetAuthSchemeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            auth_scheme,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_hook_program_ticket(&mut self, hook_program: Pubkey) -> SetHookProgramTicket {
+        SetHookProgramTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            hook_program,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_deposit_registry_ticket(
+        &mut self,
+        registry: Pubkey,
+        schema: Pubkey,
+    ) -> SetDepositRegistryTicket {
+        SetDepositRegistryTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            registry,
+            schema,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_identity_network_ticket(
+        &mut self,
+        network: Pubkey,
+    ) -> SetIdentityNetworkTicket {
+        SetIdentityNetworkTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            network,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_strict_signatures_ticket(
+        &mut self,
+        enabled: bool,
+    ) -> SetStrictSignaturesTicket {
+        SetStrictSignaturesTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            enabled,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_treasury_reserve_ticket(&mut self, reserve: u64) -> SetTreasuryReserveTicket {
+        SetTreasuryReserveTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            reserve,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_replay_mode_ticket(
+        &mut self,
+        watermark_replay: bool,
+        watermark: u64,
+    ) -> SetReplayModeTicket {
+        SetReplayModeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            watermark_replay,
+            watermark,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn propose_signer_set_ticket(
+        &mut self,
+        signers: &[WeightedSigner],
+        m_threshold: u8,
+        admin_threshold: u8,
+    ) -> ProposeSignerSetTicket {
+        ProposeSignerSetTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            signers_hash: signer_set_hash(signers),
+            signers_len: signers.len() as u8,
+            m_threshold,
+            admin_threshold,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn delegate_stake_ticket(
+        &mut self,
+        vote_account: Pubkey,
+        amount: u64,
+    ) -> DelegateStakeTicket {
+        DelegateStakeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            vote_account,
+            amount,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn deactivate_stake_ticket(&mut self, vote_account: Pubkey) -> DeactivateStakeTicket {
+        DeactivateStakeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            vote_account,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn withdraw_stake_ticket(
+        &mut self,
+        vote_account: Pubkey,
+        amount: u64,
+    ) -> WithdrawStakeTicket {
+        WithdrawStakeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            vote_account,
+            amount,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn liquid_stake_ticket(
+        &mut self,
+        pool: &TestStakePool,
+        lamports: u64,
+        min_pool_tokens: u64,
+    ) -> LiquidStakeTicket {
+        LiquidStakeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            stake_pool: pool.stake_pool,
+            pool_mint: pool.pool_mint,
+            lamports,
+            min_pool_tokens,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn liquid_unstake_ticket(
+        &mut self,
+        pool: &TestStakePool,
+        pool_tokens: u64,
+        min_lamports: u64,
+    ) -> LiquidUnstakeTicket {
+        LiquidUnstakeTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            stake_pool: pool.stake_pool,
+            pool_mint: pool.pool_mint,
+            pool_tokens,
+            min_lamports,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn rebalance_ticket(
+        &mut self,
+        asset: Asset,
+        amount: u64,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> RebalanceTicket {
+        RebalanceTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            amount,
+            source,
+            destination,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn swap_ticket(
+        &mut self,
+        pool: &TestSwapPool,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> SwapTicket {
+        SwapTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            input_mint: pool.input_mint,
+            output_mint: pool.output_mint,
+            amount_in,
+            min_amount_out,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn collect_fees_ticket(
+        &mut self,
+        recipient: Pubkey,
+        fees: Vec<AssetAmount>,
+    ) -> CollectFeesTicket {
+        CollectFeesTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            fees,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn sweep_dust_ticket(
+        &mut self,
+        destination: Pubkey,
+        mints: Vec<Pubkey>,
+    ) -> SweepDustTicket {
+        SweepDustTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            destination,
+            mints,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn close_vault_token_account_ticket(
+        &mut self,
+        mint: Pubkey,
+        rent_recipient: Pubkey,
+    ) -> CloseVaultTokenAccountTicket {
+        CloseVaultTokenAccountTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            mint,
+            rent_recipient,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn sweep_asset_ticket(
+        &mut self,
+        mint: Pubkey,
+        destination: Pubkey,
+        close_account: bool,
+    ) -> SweepAssetTicket {
+        SweepAssetTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            mint,
+            destination,
+            close_account,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn upgrade_program_ticket(
+        &mut self,
+        buffer: Pubkey,
+        spill: Pubkey,
+    ) -> UpgradeProgramTicket {
+        UpgradeProgramTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            buffer,
+            spill,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_allowance_ticket(
+        &mut self,
+        recipient: Pubkey,
+        asset: Asset,
+        cap: u64,
+    ) -> SetAllowanceTicket {
+        SetAllowanceTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            asset,
+            cap,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_treasury_ticket(&mut self, name: &str, m_threshold: u8) -> SetTreasuryTicket {
+        SetTreasuryTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            name: name.to_string(),
+            m_threshold,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn set_wormhole_emitter_ticket(
+        &mut self,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    ) -> SetWormholeEmitterTicket {
+        SetWormholeEmitterTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            core_bridge: TEST_CORE_BRIDGE_ID,
+            emitter_chain,
+            emitter_address,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn create_sub_vault_ticket(&mut self, sub_vault_seed: &str) -> CreateSubVaultTicket {
+        CreateSubVaultTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            sub_vault_seed: sub_vault_seed.to_string(),
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn reclaim_sub_vault_ticket(
+        &mut self,
+        sub_vault: Pubkey,
+        withdrawals: Vec<AssetAmount>,
+    ) -> ReclaimSubVaultTicket {
+        ReclaimSubVaultTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            sub_vault,
+            withdrawals,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn claim_root_ticket(&mut self, root: [u8; 32]) -> ClaimRootTicket {
+        let expiry = self.expiry().await;
+        ClaimRootTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            root,
+            claim_expiry: expiry,
+            expiry,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    /// Credit of `credits` to `user`'s deposit record, for funds that arrived on another chain in
+    /// `source_ref`.
+    pub async fn credit_ticket(
+        &mut self,
+        user: Pubkey,
+        credits: Vec<AssetAmount>,
+        source_ref: [u8; 32],
+    ) -> CreditTicket {
+        CreditTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            user,
+            credits,
+            source_ref,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    /// Stream of `amount` to `recipient` starting now, vesting `rate` per second after `cliff`
+    /// seconds.
+    pub async fn create_stream_ticket(
+        &mut self,
+        recipient: Pubkey,
+        asset: Asset,
+        amount: u64,
+        cliff: i64,
+        rate: u64,
+    ) -> CreateStreamTicket {
+        let now = self.now().await;
+        CreateStreamTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            asset,
+            amount,
+            start_time: now,
+            cliff_time: now + cliff,
+            rate,
+            expiry: now + DEFAULT_EXPIRY_SECS,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    /// `payments` payments of `amount` to `recipient`, one every `interval` seconds, the first
+    /// due now.
+    pub async fn create_schedule_ticket(
+        &mut self,
+        recipient: Pubkey,
+        asset: Asset,
+        amount: u64,
+        interval: i64,
+        payments: u32,
+    ) -> CreateScheduleTicket {
+        let now = self.now().await;
+        CreateScheduleTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            recipient,
+            asset,
+            amount,
+            interval,
+            first_execution: now,
+            payments,
+            expiry: now + DEFAULT_EXPIRY_SECS,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn create_receipt_mint_ticket(
+        &mut self,
+        asset: Asset,
+        decimals: u8,
+    ) -> CreateReceiptMintTicket {
+        CreateReceiptMintTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            asset,
+            decimals,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn rotate_admin_signers_ticket(
+        &mut self,
+        signers: Vec<WeightedSigner>,
+        admin_threshold: u8,
+    ) -> RotateAdminSignersTicket {
+        RotateAdminSignersTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            signers,
+            admin_threshold,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    pub async fn rotate_ticket(
+        &mut self,
+        signers: Vec<WeightedSigner>,
+        m_threshold: u8,
+        admin_threshold: u8,
+    ) -> RotateValidatorTicket {
+        RotateValidatorTicket {
+            version: TICKET_VERSION,
+            request_id: self.request_id(),
+            vault: self.vault,
+            signers,
+            m_threshold,
+            admin_threshold,
+            expiry: self.expiry().await,
+            network_id: NETWORK_ID,
+            not_before: None,
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Instruction builders
+    // ---------------------------------------------------------------------
+
+    pub fn initialize_ix(
+        &self,
+        vault_seed: String,
+        m_threshold: u8,
+        admin_threshold: u8,
+        signers: Vec<WeightedSigner>,
+        admin_signers: Vec<WeightedSigner>,
+        domain_suffix: String,
+    ) -> Instruction {
+        self.initialize_ix_with_scheme(
+            vault_seed,
+            m_threshold,
+            admin_threshold,
+            signers,
+            admin_signers,
+            domain_suffix,
+            AuthScheme::Secp256k1,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_ix_with_scheme(
+        &self,
+        vault_seed: String,
+        m_threshold: u8,
+        admin_threshold: u8,
+        signers: Vec<WeightedSigner>,
+        admin_signers: Vec<WeightedSigner>,
+        domain_suffix: String,
+        auth_scheme: AuthScheme,
+    ) -> Instruction {
+        let vault = vault_address(&vault_seed);
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::Initialize {
+                vault,
+                treasury: treasury_address(&vault),
+                authority: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::Initialize {
+                vault_seed,
+                network_id: NETWORK_ID,
+                m_threshold,
+                admin_threshold,
+                signers,
+                admin_signers,
+                domain_suffix,
+                auth_scheme,
+                genesis_hash: None,
+            }
+            .data(),
+        }
+    }
+
+    /// `initialize` with the default thresholds and signers, binding `network_id` to the cluster
+    /// with `genesis_hash`.
+    pub fn initialize_ix_with_genesis(
+        &self,
+        vault_seed: String,
+        network_id: u64,
+        genesis_hash: [u8; 32],
+    ) -> Instruction {
+        let vault = vault_address(&vault_seed);
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::Initialize {
+                vault,
+                treasury: treasury_address(&vault),
+                authority: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::Initialize {
+                vault_seed,
+                network_id,
+                m_threshold: 2,
+                admin_threshold: 3,
+                signers: self.signer_set(),
+                admin_signers: vec![],
+                domain_suffix: String::new(),
+                auth_scheme: AuthScheme::Secp256k1,
+                genesis_hash: Some(genesis_hash),
+            }
+            .data(),
+        }
+    }
+
+    /// Genesis hash of the program-test cluster.
+    pub fn genesis_hash(&self) -> [u8; 32] {
+        self.context.genesis_config().hash().to_bytes()
+    }
+
+    /// `initialize_full` for `assets`, with the mint and vault ATA of every token asset as
+    /// remaining accounts.
+    pub fn initialize_full_ix(
+        &self,
+        vault_seed: String,
+        signers: Vec<WeightedSigner>,
+        assets: Vec<Asset>,
+    ) -> Instruction {
+        let vault = vault_address(&vault_seed);
+        let mut accounts = strike_example::accounts::InitializeFull {
+            base: strike_example::accounts::Initialize {
+                vault,
+                treasury: treasury_address(&vault),
+                authority: self.payer(),
+                system_program: system_program::ID,
+            },
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None);
+        for asset in &assets {
+            if let Asset::SplToken { mint } | Asset::Nft { mint } = asset {
+                accounts.push(readonly(*mint));
+                accounts.push(writable(get_associated_token_address(&vault, mint)));
+            }
+        }
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::InitializeFull {
+                vault_seed,
+                network_id: NETWORK_ID,
+                m_threshold: 2,
+                admin_threshold: 3,
+                signers,
+                admin_signers: vec![],
+                domain_suffix: String::new(),
+                auth_scheme: AuthScheme::Secp256k1,
+                genesis_hash: None,
+                assets,
+            }
+            .data(),
+        }
+    }
+
+    /// Remaining accounts for SPL deposits: mint, depositor ATA and vault ATA per mint.
+    pub fn spl_deposit_accounts(&self, owner: &Pubkey, mints: &[Pubkey]) -> Vec<AccountMeta> {
+        mints
+            .iter()
+            .flat_map(|mint| {
+                [
+                    readonly(*mint),
+                    writable(get_associated_token_address(owner, mint)),
+                    writable(get_associated_token_address(&self.vault, mint)),
+                ]
+            })
+            .collect()
+    }
+
+    /// Allowance PDAs `withdraw` and `bulk_withdraw` charge, one per (recipient, asset) line.
+    pub fn allowance_accounts(&self, tickets: &[WithdrawalTicket]) -> Vec<AccountMeta> {
+        let mut accounts: Vec<AccountMeta> = Vec::new();
+        for ticket in tickets {
+            for withdrawal in &ticket.withdrawals {
+                let meta = writable(allowance_address(
+                    &self.vault,
+                    &ticket.recipient,
+                    &withdrawal.asset,
+                ));
+                if !accounts.contains(&meta) {
+                    accounts.push(meta);
+                }
+            }
+        }
+        accounts
+    }
+
+    /// Remaining accounts for SPL withdrawals: mint, recipient ATA and vault ATA per mint.
+    pub fn spl_withdraw_accounts(&self, recipient: &Pubkey, mints: &[Pubkey]) -> Vec<AccountMeta> {
+        self.spl_deposit_accounts(recipient, mints)
+    }
+
+    pub fn deposit_ix(
+        &self,
+        user: &Pubkey,
+        deposits: Vec<AssetAmount>,
+        request_id: u64,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        self.deposit_ix_with_metadata(user, deposits, request_id, None, remaining_accounts)
+    }
+
+    pub fn deposit_ix_with_metadata(
+        &self,
+        user: &Pubkey,
+        deposits: Vec<AssetAmount>,
+        request_id: u64,
+        metadata: Option<String>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::Deposit {
+            vault: self.vault,
+            treasury: self.treasury,
+            deposit_record: deposit_record_address(&self.vault, user),
+            user: *user,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Deposit {
+                deposits,
+                request_id,
+                metadata,
+            }
+            .data(),
+        }
+    }
+
+    pub fn withdraw_with_vaa_ix(
+        &self,
+        ticket: &WithdrawalTicket,
+        posted_vaa: Pubkey,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::WithdrawWithVaa {
+            vault: self.vault,
+            treasury: self.treasury,
+            fee_treasury: fee_treasury_address(&self.vault),
+            wormhole_emitter: wormhole_emitter_address(&self.vault),
+            posted_vaa,
+            recipient: ticket.recipient,
+            nonce_account: nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(self.allowance_accounts(std::slice::from_ref(ticket)));
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::WithdrawWithVaa {
+                request_id: ticket.request_id,
+                metadata: None,
+            }
+            .data(),
+        }
+    }
+
+    pub fn withdraw_ix(
+        &self,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let nonce = nonce_address(&self.vault, ticket.request_id);
+        self.withdraw_ix_with_nonce(
+            ticket,
+            signers_with_sigs,
+            remaining_accounts,
+            Some(nonce),
+            None,
+        )
+    }
+
+    /// `withdraw_ix` that also creates the ticket's withdrawal receipt.
+    pub fn withdraw_with_receipt_ix(
+        &self,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let nonce = nonce_address(&self.vault, ticket.request_id);
+        let receipt = withdrawal_receipt_address(&self.vault, ticket.request_id);
+        self.withdraw_ix_with_nonce(
+            ticket,
+            signers_with_sigs,
+            remaining_accounts,
+            Some(nonce),
+            Some(receipt),
+        )
+    }
+
+    /// `withdraw_ix` without the nonce account, as watermark replay allows.
+    pub fn withdraw_without_nonce_ix(
+        &self,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        self.withdraw_ix_with_nonce(ticket, signers_with_sigs, remaining_accounts, None, None)
+    }
+
+    fn withdraw_ix_with_nonce(
+        &self,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+        nonce_account: Option<Pubkey>,
+        receipt: Option<Pubkey>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::Withdraw {
+            vault: self.vault,
+            treasury: self.treasury,
+            fee_treasury: fee_treasury_address(&self.vault),
+            recipient: ticket.recipient,
+            nonce_account,
+            receipt,
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(self.allowance_accounts(std::slice::from_ref(&ticket)));
+        if let Some(name) = &ticket.treasury {
+            accounts.push(writable(named_treasury_address(&self.vault, name)));
+        }
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Withdraw {
+                ticket,
+                signers_with_sigs,
+                metadata: None,
+            }
+            .data(),
+        }
+    }
+
+    /// Takes the same remaining accounts as `withdraw_ix` for the ticket.
+    pub fn validate_ticket_ix(
+        &self,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::ValidateTicket {
+            vault: self.vault,
+            treasury: self.treasury,
+            nonce_account: nonce_address(&self.vault, ticket.request_id),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(self.allowance_accounts(std::slice::from_ref(&ticket)));
+        if let Some(name) = &ticket.treasury {
+            accounts.push(readonly(named_treasury_address(&self.vault, name)));
+        }
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::ValidateTicket {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// Builds the nonce accounts for every ticket and their allowance accounts, followed by
+    /// `remaining_accounts`.
+    pub fn bulk_withdraw_ix(
+        &self,
+        bulk_ticket: BulkWithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::BulkWithdraw {
+            vault: self.vault,
+            treasury: self.treasury,
+            fee_treasury: fee_treasury_address(&self.vault),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        let named = accounts.len();
+        accounts.extend(
+            bulk_ticket
+                .tickets
+                .iter()
+                .map(|t| writable(nonce_address(&self.vault, t.request_id))),
+        );
+        accounts.extend(self.allowance_accounts(&bulk_ticket.tickets));
+        accounts.extend(remaining_accounts);
+        let account_indexes = bulk_account_indexes(
+            &bulk_ticket,
+            &self.vault,
+            &spl_token::ID,
+            &accounts[named..],
+        );
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::BulkWithdraw {
+                bulk_ticket,
+                signers_with_sigs,
+                account_indexes,
+                metadata: None,
+            }
+            .data(),
+        }
+    }
+
+    pub fn publish_claim_root_ix(
+        &self,
+        ticket: ClaimRootTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::PublishClaimRoot {
+                vault: self.vault,
+                nonce_account: nonce_address(&self.vault, ticket.request_id),
+                claim_root: claim_root_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+                event_authority: event_authority_address(),
+                program: strike_example::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::PublishClaimRoot {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// `claim_credited_deposit` submitted by the ticket's user, who must sign the transaction.
+    pub fn claim_credited_deposit_ix(
+        &self,
+        ticket: CreditTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::ClaimCreditedDeposit {
+                vault: self.vault,
+                nonce_account: nonce_address(&self.vault, ticket.request_id),
+                deposit_record: deposit_record_address(&self.vault, &ticket.user),
+                user: ticket.user,
+                system_program: system_program::ID,
+                event_authority: event_authority_address(),
+                program: strike_example::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::ClaimCreditedDeposit {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn create_stream_ix(
+        &self,
+        ticket: CreateStreamTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::CreateStream {
+            vault: self.vault,
+            treasury: self.treasury,
+            stream: stream_address(&self.vault, ticket.request_id),
+            recipient: ticket.recipient,
+            nonce_account: nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::CreateStream {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// The payer cranks.
+    pub fn claim_stream_ix(
+        &self,
+        request_id: u64,
+        recipient: Pubkey,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::ClaimStream {
+            vault: self.vault,
+            treasury: self.treasury,
+            stream: stream_address(&self.vault, request_id),
+            recipient,
+            cranker: self.payer(),
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::ClaimStream {}.data(),
+        }
+    }
+
+    pub fn create_schedule_ix(
+        &self,
+        ticket: CreateScheduleTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::CreateSchedule {
+                vault: self.vault,
+                nonce_account: nonce_address(&self.vault, ticket.request_id),
+                schedule: schedule_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+                event_authority: event_authority_address(),
+                program: strike_example::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::CreateSchedule {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// The payer cranks. Appends the recipient's allowance PDA for `asset`, followed by
+    /// `remaining_accounts`.
+    pub fn execute_schedule_ix(
+        &self,
+        request_id: u64,
+        recipient: Pubkey,
+        asset: &Asset,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::ExecuteSchedule {
+            vault: self.vault,
+            treasury: self.treasury,
+            schedule: schedule_address(&self.vault, request_id),
+            recipient,
+            cranker: self.payer(),
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.push(writable(allowance_address(&self.vault, &recipient, asset)));
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::ExecuteSchedule {}.data(),
+        }
+    }
+
+    /// Appends the recipient's allowance PDA for the leaf, followed by `remaining_accounts`.
+    pub fn claim_ix(
+        &self,
+        request_id: u64,
+        leaf: ClaimLeaf,
+        proof: Vec<[u8; 32]>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let claim_root = claim_root_address(&self.vault, request_id);
+        let mut accounts = strike_example::accounts::Claim {
+            vault: self.vault,
+            treasury: self.treasury,
+            fee_treasury: fee_treasury_address(&self.vault),
+            claim_root,
+            claimed_leaf: claimed_leaf_address(&claim_root, leaf.index),
+            recipient: leaf.recipient,
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.push(writable(allowance_address(
+            &self.vault,
+            &leaf.recipient,
+            &leaf.asset_amount.asset,
+        )));
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Claim { leaf, proof }.data(),
+        }
+    }
+
+    pub fn admin_deposit_ix(
+        &self,
+        ticket: AdminDepositTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::AdminDeposit {
+            vault: self.vault,
+            treasury: self.treasury,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            deposit_record: deposit_record_address(&self.vault, &self.payer()),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::AdminDeposit {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn admin_withdraw_ix(
+        &self,
+        ticket: AdminWithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::AdminWithdraw {
+            vault: self.vault,
+            treasury: self.treasury,
+            recipient: ticket.recipient,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            event_authority: event_authority_address(),
+            program: strike_example::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::AdminWithdraw {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn add_asset_ix(
+        &self,
+        ticket: AddAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::AddAsset {
+            vault: self.vault,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        if let Asset::SplToken { mint } = &ticket.asset {
+            accounts.push(readonly(*mint));
+        }
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::AddAsset {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn update_asset_ix(
+        &self,
+        ticket: UpdateAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::UpdateAsset {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::UpdateAsset {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn remove_asset_ix(
+        &self,
+        ticket: RemoveAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::RemoveAsset {
+            vault: self.vault,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        if let Asset::SplToken { mint } | Asset::Nft { mint } = &ticket.asset {
+            accounts.push(readonly(*mint));
+            accounts.push(readonly(get_associated_token_address(&self.vault, mint)));
+        }
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::RemoveAsset {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_min_withdrawal_ix(
+        &self,
+        ticket: SetMinWithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetMinWithdrawal {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetMinWithdrawal {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_usd_cap_ix(
+        &self,
+        ticket: SetUsdCapTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::SetUsdCap {
+            vault: self.vault,
+            usd_cap: usd_cap_address(&self.vault, &ticket.asset),
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::SetUsdCap {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_max_ticket_usd_ix(
+        &self,
+        ticket: SetMaxTicketUsdTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetMaxTicketUsd {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetMaxTicketUsd {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn rotate_validators_ix(
+        &self,
+        ticket: RotateValidatorTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::RotateValidator {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::RotateValidators {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn rotate_admin_signers_ix(
+        &self,
+        ticket: RotateAdminSignersTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::RotateAdminSigners {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::RotateAdminSigners {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_log_verbosity_ix(
+        &self,
+        ticket: SetLogVerbosityTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetLogVerbosity {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetLogVerbosity {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn add_recipient_ix(
+        &self,
+        ticket: AddRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::AddRecipient {
+                vault: self.vault,
+                recipient_allowlist: recipient_allowlist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::AddRecipient {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn add_strategy_ix(
+        &self,
+        ticket: AddStrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::AddStrategy {
+                vault: self.vault,
+                strategy_allowlist: strategy_allowlist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::AddStrategy {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn remove_strategy_ix(
+        &self,
+        ticket: RemoveStrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::RemoveStrategy {
+                vault: self.vault,
+                strategy_allowlist: strategy_allowlist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::RemoveStrategy {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn add_cranker_ix(
+        &self,
+        ticket: AddCrankerTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::AddCranker {
+                vault: self.vault,
+                crank_registry: crank_registry_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::AddCranker {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn remove_cranker_ix(
+        &self,
+        ticket: RemoveCrankerTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::RemoveCranker {
+                vault: self.vault,
+                crank_registry: crank_registry_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::RemoveCranker {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_crank_incentive_ix(
+        &self,
+        ticket: SetCrankIncentiveTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetCrankIncentive {
+                vault: self.vault,
+                crank_registry: crank_registry_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetCrankIncentive {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// `execute_strategy` for `instruction`. The vault PDA is passed as a plain account; the
+    /// program signs for it.
+    pub fn execute_strategy_ix(
+        &self,
+        ticket: StrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        instruction: Instruction,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::ExecuteStrategy {
+            vault: self.vault,
+            strategy_allowlist: strategy_allowlist_address(&self.vault),
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.push(readonly(instruction.program_id));
+        accounts.extend(instruction.accounts.iter().map(|meta| AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer && meta.pubkey != self.vault,
+            is_writable: meta.is_writable,
+        }));
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::ExecuteStrategy {
+                ticket,
+                signers_with_sigs,
+                data: instruction.data,
+            }
+            .data(),
+        }
+    }
+
+    /// `execute` for the ticket's instruction, passing `accounts` after the target program. The
+    /// vault PDA is passed as a plain account; the program signs for it.
+    pub fn execute_ix(
+        &self,
+        ticket: ExecuteTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        accounts: &[AccountMeta],
+    ) -> Instruction {
+        let mut metas = strike_example::accounts::Execute {
+            vault: self.vault,
+            nonce_account: nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        metas.push(readonly(ticket.program_id));
+        metas.extend(accounts.iter().map(|meta| AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer && meta.pubkey != self.vault,
+            is_writable: meta.is_writable,
+        }));
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: metas,
+            data: strike_example::instruction::Execute {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn remove_recipient_ix(
+        &self,
+        ticket: RemoveRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::RemoveRecipient {
+                vault: self.vault,
+                recipient_allowlist: recipient_allowlist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::RemoveRecipient {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_recipient_allowlist_ix(
+        &self,
+        ticket: SetRecipientAllowlistTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetRecipientAllowlist {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetRecipientAllowlist {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn block_recipient_ix(
+        &self,
+        ticket: BlockRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::BlockRecipient {
+                vault: self.vault,
+                blocklist: blocklist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::BlockRecipient {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn unblock_recipient_ix(
+        &self,
+        ticket: UnblockRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::UnblockRecipient {
+                vault: self.vault,
+                blocklist: blocklist_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::UnblockRecipient {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_fee_ix(
+        &self,
+        ticket: SetFeeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetFee {
+                vault: self.vault,
+                fee_treasury: fee_treasury_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetFee {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_relayer_rebate_ix(
+        &self,
+        ticket: SetRelayerRebateTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetRelayerRebate {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetRelayerRebate {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_max_ticket_lifetime_ix(
+        &self,
+        ticket: SetMaxTicketLifetimeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetMaxTicketLifetime {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetMaxTicketLifetime {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_expiry_grace_ix(
+        &self,
+        ticket: SetExpiryGraceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetExpiryGrace {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetExpiryGrace {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_nonce_tree_ix(
+        &self,
+        ticket: SetNonceTreeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetNonceTree {
+                vault: self.vault,
+                nonce_tree: ticket.nonce_tree,
+                nonce_tree_authority: nonce_tree_authority_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                compression_program: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+                noop_program: SPL_NOOP_PROGRAM_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetNonceTree {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_extra_network_ids_ix(
+        &self,
+        ticket: SetExtraNetworkIdsTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetExtraNetworkIds {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetExtraNetworkIds {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_guardian_ix(
+        &self,
+        ticket: SetGuardianTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetGuardian {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetGuardian {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_auth_scheme_ix(
+        &self,
+        ticket: SetAuthSchemeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetAuthScheme {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetAuthScheme {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_hook_program_ix(
+        &self,
+        ticket: SetHookProgramTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetHookProgram {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetHookProgram {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_deposit_registry_ix(
+        &self,
+        ticket: SetDepositRegistryTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetDepositRegistry {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetDepositRegistry {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_identity_network_ix(
+        &self,
+        ticket: SetIdentityNetworkTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetIdentityNetwork {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetIdentityNetwork {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_strict_signatures_ix(
+        &self,
+        ticket: SetStrictSignaturesTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetStrictSignatures {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetStrictSignatures {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_treasury_reserve_ix(
+        &self,
+        ticket: SetTreasuryReserveTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetTreasuryReserve {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetTreasuryReserve {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_replay_mode_ix(
+        &self,
+        ticket: SetReplayModeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetReplayMode {
+                vault: self.vault,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetReplayMode {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn propose_signer_set_ix(
+        &self,
+        ticket: ProposeSignerSetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::ProposeSignerSet {
+                vault: self.vault,
+                signer_set: signer_set_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::ProposeSignerSet {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn upload_signers_ix(&self, uploader: Pubkey, signers: Vec<WeightedSigner>) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::UploadSigners {
+                vault: self.vault,
+                signer_set: signer_set_address(&self.vault),
+                uploader,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::UploadSigners { signers }.data(),
+        }
+    }
+
+    pub fn activate_signer_set_ix(&self, uploader: Pubkey) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::ActivateSignerSet {
+                vault: self.vault,
+                signer_set: signer_set_address(&self.vault),
+                uploader,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::ActivateSignerSet {}.data(),
+        }
+    }
+
+    /// Remaining accounts deposits and withdrawals need while the test hook is set.
+    pub fn hook_accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            readonly(TEST_HOOK_PROGRAM_ID),
+            readonly(hook_authority_address(&self.vault)),
+        ]
+    }
+
+    pub fn migrate_ix(&self, authority: &Pubkey, mints: &[Pubkey]) -> Instruction {
+        let mut accounts = strike_example::accounts::Migrate {
+            vault: self.vault,
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(mints.iter().copied().map(readonly));
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Migrate {}.data(),
+        }
+    }
+
+    pub fn veto_withdrawal_ix(&self, guardian: &Pubkey, request_id: u64) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::VetoWithdrawal {
+                vault: self.vault,
+                nonce_account: nonce_address(&self.vault, request_id),
+                guardian: *guardian,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::VetoWithdrawal { request_id }.data(),
+        }
+    }
+
+    pub fn close_expired_nonce_ix(&self, request_id: u64, admin: bool) -> Instruction {
+        let nonce_account = if admin {
+            admin_nonce_address(&self.vault, request_id)
+        } else {
+            nonce_address(&self.vault, request_id)
+        };
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::CloseExpiredNonce {
+                vault: self.vault,
+                treasury: self.treasury,
+                nonce_account,
+                cranker: self.payer(),
+                event_authority: event_authority_address(),
+                program: strike_example::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::CloseExpiredNonce { request_id, admin }.data(),
+        }
+    }
+
+    pub fn compute_ticket_hash_ix(&self, ticket: AnyTicket, domain_suffix: &str) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::ComputeTicketHash {}.to_account_metas(None),
+            data: strike_example::instruction::ComputeTicketHash {
+                ticket,
+                domain_suffix: domain_suffix.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn get_vault_info_ix(&self) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::GetVaultInfo {
+                vault: self.vault,
+                treasury: self.treasury,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::GetVaultInfo {}.data(),
+        }
+    }
+
+    pub fn get_nonce_status_ix(&self, request_id: u64, admin: bool) -> Instruction {
+        let nonce_account = if admin {
+            admin_nonce_address(&self.vault, request_id)
+        } else {
+            nonce_address(&self.vault, request_id)
+        };
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::GetNonceStatus {
+                vault: self.vault,
+                nonce_account,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::GetNonceStatus { request_id, admin }.data(),
+        }
+    }
+
+    pub fn get_asset_config_ix(&self, asset: Asset) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::GetAssetConfig { vault: self.vault }
+                .to_account_metas(None),
+            data: strike_example::instruction::GetAssetConfig { asset }.data(),
+        }
+    }
+
+    pub fn delegate_stake_ix(
+        &self,
+        ticket: DelegateStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::DelegateStake {
+                vault: self.vault,
+                treasury: self.treasury,
+                stake_account: stake_address(&self.vault, &ticket.vote_account),
+                vote_account: ticket.vote_account,
+                stake_config: STAKE_CONFIG_ID,
+                stake_history: sysvar::stake_history::ID,
+                clock: sysvar::clock::ID,
+                rent: sysvar::rent::ID,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                stake_program: stake::program::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::DelegateStake {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn deactivate_stake_ix(
+        &self,
+        ticket: DeactivateStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::DeactivateStake {
+                vault: self.vault,
+                stake_account: stake_address(&self.vault, &ticket.vote_account),
+                clock: sysvar::clock::ID,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                stake_program: stake::program::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::DeactivateStake {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn withdraw_stake_ix(
+        &self,
+        ticket: WithdrawStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::WithdrawStake {
+                vault: self.vault,
+                treasury: self.treasury,
+                stake_account: stake_address(&self.vault, &ticket.vote_account),
+                stake_history: sysvar::stake_history::ID,
+                clock: sysvar::clock::ID,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                stake_program: stake::program::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::WithdrawStake {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn liquid_stake_ix(
+        &self,
+        pool: &TestStakePool,
+        ticket: LiquidStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::LiquidStake {
+                vault: self.vault,
+                treasury: self.treasury,
+                lst_funding: lst_funding_address(&self.vault),
+                stake_pool: pool.stake_pool,
+                pool_withdraw_authority: pool.withdraw_authority,
+                reserve_stake: pool.reserve_stake,
+                manager_fee_account: pool.manager_fee_account,
+                pool_mint: pool.pool_mint,
+                vault_pool_token_account: get_associated_token_address(
+                    &self.vault,
+                    &pool.pool_mint,
+                ),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                stake_pool_program: SPL_STAKE_POOL_PROGRAM_ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::LiquidStake {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn liquid_unstake_ix(
+        &self,
+        pool: &TestStakePool,
+        ticket: LiquidUnstakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::LiquidUnstake {
+                vault: self.vault,
+                treasury: self.treasury,
+                stake_pool: pool.stake_pool,
+                pool_withdraw_authority: pool.withdraw_authority,
+                reserve_stake: pool.reserve_stake,
+                manager_fee_account: pool.manager_fee_account,
+                pool_mint: pool.pool_mint,
+                vault_pool_token_account: get_associated_token_address(
+                    &self.vault,
+                    &pool.pool_mint,
+                ),
+                clock: sysvar::clock::ID,
+                stake_history: sysvar::stake_history::ID,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                stake_pool_program: SPL_STAKE_POOL_PROGRAM_ID,
+                stake_program: stake::program::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::LiquidUnstake {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn rebalance_ix(
+        &self,
+        ticket: RebalanceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::Rebalance {
+            vault: self.vault,
+            treasury: self.treasury,
+            source: ticket.source,
+            destination: ticket.destination,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Rebalance {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn swap_ix(
+        &self,
+        ticket: SwapTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        route: (Vec<u8>, Vec<AccountMeta>),
+    ) -> Instruction {
+        let (data, route_accounts) = route;
+        let mut accounts = strike_example::accounts::Swap {
+            vault: self.vault,
+            input_mint: ticket.input_mint,
+            output_mint: ticket.output_mint,
+            vault_input_token_account: get_associated_token_address(
+                &self.vault,
+                &ticket.input_mint,
+            ),
+            vault_output_token_account: get_associated_token_address(
+                &self.vault,
+                &ticket.output_mint,
+            ),
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            jupiter_program: JUPITER_PROGRAM_ID,
+            input_token_program: spl_token::ID,
+            output_token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(route_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::Swap {
+                ticket,
+                signers_with_sigs,
+                data,
+            }
+            .data(),
+        }
+    }
+
+    pub fn collect_fees_ix(
+        &self,
+        ticket: CollectFeesTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::CollectFees {
+            vault: self.vault,
+            fee_treasury: fee_treasury_address(&self.vault),
+            recipient: ticket.recipient,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::CollectFees {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    /// `create_vault_token_accounts` for `mints`, with the mint and vault ATA of each as
+    /// remaining accounts.
+    pub fn create_vault_token_accounts_ix(&self, mints: Vec<Pubkey>) -> Instruction {
+        let mut accounts = strike_example::accounts::CreateVaultTokenAccounts {
+            vault: self.vault,
+            payer: self.payer(),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        for mint in &mints {
+            accounts.push(readonly(*mint));
+            accounts.push(writable(get_associated_token_address(&self.vault, mint)));
+        }
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::CreateVaultTokenAccounts { mints }.data(),
+        }
+    }
+
+    pub fn sweep_dust_ix(
+        &self,
+        ticket: SweepDustTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::SweepDust {
+            vault: self.vault,
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::SweepDust {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn close_vault_token_account_ix(
+        &self,
+        ticket: CloseVaultTokenAccountTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::CloseVaultTokenAccount {
+                vault: self.vault,
+                mint: ticket.mint,
+                vault_token_account: get_associated_token_address(&self.vault, &ticket.mint),
+                rent_recipient: ticket.rent_recipient,
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::CloseVaultTokenAccount {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn sweep_asset_ix(
+        &self,
+        ticket: SweepAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SweepAsset {
+                vault: self.vault,
+                mint: ticket.mint,
+                vault_token_account: get_associated_token_address(&self.vault, &ticket.mint),
+                destination: ticket.destination,
+                destination_token_account: get_associated_token_address(
+                    &ticket.destination,
+                    &ticket.mint,
+                ),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SweepAsset {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn accept_upgrade_authority_ix(&self, current_authority: Pubkey) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::AcceptUpgradeAuthority {
+                vault: self.vault,
+                program_data: program_data_address(),
+                current_authority,
+                upgrade_authority: upgrade_authority_address(&self.vault),
+                bpf_loader_program: bpf_loader_upgradeable::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::AcceptUpgradeAuthority {}.data(),
+        }
+    }
+
+    pub fn upgrade_program_ix(
+        &self,
+        ticket: UpgradeProgramTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::UpgradeProgram {
+                vault: self.vault,
+                program: strike_example::ID,
+                program_data: program_data_address(),
+                buffer: ticket.buffer,
+                spill: ticket.spill,
+                upgrade_authority: upgrade_authority_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                rent: sysvar::rent::ID,
+                clock: sysvar::clock::ID,
+                bpf_loader_program: bpf_loader_upgradeable::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::UpgradeProgram {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_allowance_ix(
+        &self,
+        ticket: SetAllowanceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetAllowance {
+                vault: self.vault,
+                allowance: allowance_address(&self.vault, &ticket.recipient, &ticket.asset),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetAllowance {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_treasury_ix(
+        &self,
+        ticket: SetTreasuryTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetTreasury {
+                vault: self.vault,
+                treasury: named_treasury_address(&self.vault, &ticket.name),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetTreasury {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_wormhole_emitter_ix(
+        &self,
+        ticket: SetWormholeEmitterTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::SetWormholeEmitter {
+                vault: self.vault,
+                wormhole_emitter: wormhole_emitter_address(&self.vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::SetWormholeEmitter {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn create_sub_vault_ix(
+        &self,
+        ticket: CreateSubVaultTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Instruction {
+        let sub_vault = sub_vault_address(&self.vault, &ticket.sub_vault_seed);
+        Instruction {
+            program_id: strike_example::ID,
+            accounts: strike_example::accounts::CreateSubVault {
+                vault: self.vault,
+                sub_vault,
+                sub_treasury: treasury_address(&sub_vault),
+                nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+                payer: self.payer(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: strike_example::instruction::CreateSubVault {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn reclaim_sub_vault_ix(
+        &self,
+        ticket: ReclaimSubVaultTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::ReclaimSubVault {
+            vault: self.vault,
+            treasury: self.treasury,
+            sub_vault: ticket.sub_vault,
+            sub_treasury: treasury_address(&ticket.sub_vault),
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::ReclaimSubVault {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    pub fn create_receipt_mint_ix(
+        &self,
+        ticket: CreateReceiptMintTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = strike_example::accounts::CreateReceiptMint {
+            vault: self.vault,
+            receipt_mint: receipt_mint_address(&self.vault, &ticket.asset),
+            nonce_account: admin_nonce_address(&self.vault, ticket.request_id),
+            payer: self.payer(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: strike_example::ID,
+            accounts,
+            data: strike_example::instruction::CreateReceiptMint {
+                ticket,
+                signers_with_sigs,
+            }
+            .data(),
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Shortcuts
+    // ---------------------------------------------------------------------
+
+    pub async fn whitelist(&mut self, asset: Asset) {
+        let ticket = self.add_asset_ticket(asset).await;
+        let sigs = self.sign_all(&ticket);
+        let ix = self.add_asset_ix(ticket, sigs);
+        self.process(&[ix], &[]).await.expect("asset whitelisted");
+    }
+
+    pub async fn update_asset(&mut self, asset: Asset, settings: AssetSettings) {
+        let ticket = self.update_asset_ticket(asset, settings).await;
+        let sigs = self.sign_all(&ticket);
+        let ix = self.update_asset_ix(ticket, sigs);
+        self.process(&[ix], &[]).await.expect("asset updated");
+    }
+
+    pub async fn deposit_sol(&mut self, user: &Keypair, amount: u64) {
+        let request_id = self.request_id();
+        let ix = self.deposit_ix(&user.pubkey(), vec![sol(amount)], request_id, vec![]);
+        self.process(&[ix], &[user]).await.expect("sol deposited");
+    }
+
+    /// Create a whitelisted mint with a funded vault ATA.
+    pub async fn funded_mint(&mut self, decimals: u8, vault_amount: u64) -> Pubkey {
+        let mint = self.create_mint(decimals).await;
+        let vault = self.vault;
+        let vault_ata = self.create_ata(&vault, &mint).await;
+        self.mint_to(&mint, &vault_ata, vault_amount).await;
+        self.whitelist(Asset::SplToken { mint }).await;
+        mint
+    }
+}
@@ -0,0 +1,1216 @@
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::signature::{Keypair, Signer};
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::{
+    AnyTicket, Asset, AssetConfig, AssetSettings, LogVerbosity, NonceStatus, Ticket, VaultInfo,
+    WeightedSigner,
+};
+use strike_program_test::*;
+
+const LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn add_and_remove_asset() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.create_mint(6).await;
+
+    tv.whitelist(Asset::Sol).await;
+    tv.whitelist(Asset::SplToken { mint }).await;
+    // Adding an existing asset is a no-op.
+    tv.whitelist(Asset::Sol).await;
+    assert_eq!(
+        tv.whitelisted_assets().await,
+        vec![Asset::Sol, Asset::SplToken { mint }]
+    );
+    // Decimals come from the mint.
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.asset_config(&Asset::Sol).unwrap().decimals, 9);
+    assert_eq!(
+        vault
+            .asset_config(&Asset::SplToken { mint })
+            .unwrap()
+            .decimals,
+        6
+    );
+
+    let ticket = tv.remove_asset_ticket(Asset::Sol).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.whitelisted_assets().await,
+        vec![Asset::SplToken { mint }]
+    );
+}
+
+#[tokio::test]
+async fn update_asset_applies_deposit_and_withdrawal_limits() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+
+    let settings = AssetSettings {
+        min_deposit: LAMPORTS / 10,
+        deposit_cap: LAMPORTS,
+        withdrawal_limit: LAMPORTS / 2,
+        tvl_cap: 0,
+        user_cap: 0,
+        paused: false,
+    };
+    let ticket = tv.update_asset_ticket(Asset::Sol, settings).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.update_asset_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.update_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let vault = tv.vault_state().await;
+    let config = vault.asset_config(&Asset::Sol).unwrap();
+    assert_eq!(config.withdrawal_limit, LAMPORTS / 2);
+    assert_eq!(config.decimals, 9);
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(LAMPORTS / 20)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::BelowMinDeposit,
+    );
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(2 * LAMPORTS)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::DepositCapExceeded,
+    );
+    tv.deposit_sol(&user, LAMPORTS).await;
+
+    let recipient = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::AboveWithdrawalLimit,
+    );
+
+    // Pausing stops deposits but leaves withdrawals alone.
+    let paused = AssetSettings {
+        paused: true,
+        ..settings
+    };
+    let ticket = tv.update_asset_ticket(Asset::Sol, paused).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.update_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(LAMPORTS)], request_id, vec![]);
+    assert_error(tv.process(&[ix], &[&user]).await, ErrorCode::AssetPaused);
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS / 2)])
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS / 2);
+
+    let ticket = tv
+        .update_asset_ticket(
+            Asset::Nft {
+                mint: Pubkey::new_unique(),
+            },
+            settings,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.update_asset_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AssetNotWhitelisted);
+}
+
+#[tokio::test]
+async fn admin_updates_require_admin_threshold() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+
+    let ticket = tv.add_asset_ticket(Asset::Sol).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.add_asset_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign(&ticket, &[0, 1, 1]);
+    let ix = tv.add_asset_ix(ticket.clone(), sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateSignature);
+
+    let sigs = tv.sign_all(&ticket);
+    tv.warp_to_timestamp(ticket.expiry + 1).await;
+    let ix = tv.add_asset_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TicketExpired);
+}
+
+#[tokio::test]
+async fn rotated_signer_set_takes_over() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    // Signed by the old set before the rotation lands.
+    let stale = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let stale_sigs = tv.sign(&stale, &[0, 1]);
+
+    let new_signers: Vec<EthSigner> = (10..12).map(EthSigner::from_seed).collect();
+    let rotate = tv
+        .rotate_ticket(new_signers.iter().map(EthSigner::weighted).collect(), 1, 2)
+        .await;
+    let sigs = tv.sign_all(&rotate);
+    let ix = tv.rotate_validators_ix(rotate, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.signers = new_signers;
+
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.signers, tv.signer_set());
+    assert_eq!(vault.m_threshold, 1);
+    assert_eq!(vault.admin_threshold, 2);
+
+    let ix = tv.withdraw_ix(stale, stale_sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn rotate_rejects_invalid_sets() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let duplicate = EthSigner::from_seed(20).weighted();
+
+    let ticket = tv.rotate_ticket(vec![duplicate, duplicate], 1, 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rotate_validators_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateSigner);
+
+    let ticket = tv.rotate_ticket(vec![duplicate], 2, 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rotate_validators_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidThreshold);
+
+    let ticket = tv.rotate_ticket(vec![], 1, 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rotate_validators_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignersCount);
+}
+
+#[tokio::test]
+async fn signer_set_account_takes_over_past_max_signers() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+    let uploader = tv.payer();
+
+    let new_signers: Vec<EthSigner> = (100..124).map(EthSigner::from_seed).collect();
+    let set: Vec<WeightedSigner> = new_signers.iter().map(EthSigner::weighted).collect();
+
+    // Uploaded out of order, the set doesn't hash to the proposal.
+    let propose = tv.propose_signer_set_ticket(&set, 3, 4).await;
+    let sigs = tv.sign_all(&propose);
+    let ix = tv.propose_signer_set_ix(propose, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let reversed: Vec<WeightedSigner> = set.iter().rev().copied().collect();
+    let upload_0 = tv.upload_signers_ix(uploader, reversed[..12].to_vec());
+    let upload_1 = tv.upload_signers_ix(uploader, reversed[12..].to_vec());
+    tv.process(&[upload_0, upload_1], &[]).await.unwrap();
+    let ix = tv.activate_signer_set_ix(uploader);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::SignerSetHashMismatch,
+    );
+
+    // A new proposal starts the upload over; only its payer may upload.
+    let propose = tv.propose_signer_set_ticket(&set, 3, 4).await;
+    let sigs = tv.sign_all(&propose);
+    let ix = tv.propose_signer_set_ix(propose, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let stranger = tv.new_funded_keypair(LAMPORTS).await;
+    let ix = tv.upload_signers_ix(stranger.pubkey(), set[..12].to_vec());
+    assert_error(
+        tv.process(&[ix], &[&stranger]).await,
+        ErrorCode::NotSignerSetUploader,
+    );
+    let ix = tv.upload_signers_ix(uploader, set[..12].to_vec());
+    tv.process(&[ix], &[]).await.unwrap();
+    let ix = tv.activate_signer_set_ix(uploader);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignersCount);
+    let ix = tv.upload_signers_ix(uploader, set[12..].to_vec());
+    tv.process(&[ix], &[]).await.unwrap();
+    let ix = tv.activate_signer_set_ix(uploader);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.signers = new_signers;
+
+    let vault = tv.vault_state().await;
+    assert!(vault.signers.is_empty());
+    assert!(vault.has_signer_set);
+    assert_eq!(vault.m_threshold, 3);
+    assert_eq!(vault.admin_threshold, 4);
+
+    // Signers past MAX_SIGNERS approve, recovered in the program or verified by the precompile.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[11, 17]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::SignerSetAccountNotFound,
+    );
+    let verify_23 = tv.signers[23].secp256k1_ix(&ticket, &tv.domain_suffix, 0);
+    let remaining = vec![
+        readonly(signer_set_address(&tv.vault)),
+        instructions_sysvar(),
+    ];
+    let ix = tv.withdraw_ix(ticket, sigs, remaining);
+    tv.process(&[verify_23, ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // The old signers no longer count.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let old = EthSigner::from_seed(0);
+    let sigs = vec![old.sign(&ticket, &tv.domain_suffix)];
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(signer_set_address(&tv.vault))]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+}
+
+#[tokio::test]
+async fn expired_signer_signatures_are_ignored() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    let now = tv.now().await;
+    let expired = EthSigner::from_seed(10).with_valid_until(now);
+    let ticket = tv.rotate_ticket(vec![expired.weighted()], 1, 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rotate_validators_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignerExpiry);
+
+    let new_signers = vec![
+        EthSigner::from_seed(10).with_valid_until(now + 100),
+        EthSigner::from_seed(11),
+        EthSigner::from_seed(12),
+    ];
+    let rotate = tv
+        .rotate_ticket(new_signers.iter().map(EthSigner::weighted).collect(), 2, 2)
+        .await;
+    let sigs = tv.sign_all(&rotate);
+    let ix = tv.rotate_validators_ix(rotate, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.signers = new_signers;
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    tv.warp_to_timestamp(now + 101).await;
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+}
+
+#[tokio::test]
+async fn admin_signers_rotate_independently() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    let cold: Vec<EthSigner> = (30..32).map(EthSigner::from_seed).collect();
+    let cold_set: Vec<_> = cold.iter().map(EthSigner::weighted).collect();
+    let sign_cold = |tv: &TestVault, ticket: &dyn Ticket| -> Vec<_> {
+        cold.iter()
+            .map(|s| s.sign(ticket, &tv.domain_suffix))
+            .collect()
+    };
+
+    let ticket = tv.rotate_admin_signers_ticket(cold_set.clone(), 2).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rotate_admin_signers_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.admin_signers, cold_set);
+    assert_eq!(vault.admin_threshold, 2);
+
+    // Admin ops now need the cold set.
+    let ticket = tv.set_log_verbosity_ticket(LogVerbosity::Compact).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_log_verbosity_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+    let sigs = sign_cold(&tv, &ticket);
+    let ix = tv.set_log_verbosity_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // Withdrawals stay with the operational set.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // Rotating the operational set leaves the cold set in place.
+    let new_signers: Vec<EthSigner> = (40..42).map(EthSigner::from_seed).collect();
+    let rotate = tv
+        .rotate_ticket(new_signers.iter().map(EthSigner::weighted).collect(), 1, 2)
+        .await;
+    let sigs = sign_cold(&tv, &rotate);
+    let ix = tv.rotate_validators_ix(rotate, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.signers = new_signers;
+
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.signers, tv.signer_set());
+    assert_eq!(vault.admin_signers, cold_set);
+}
+
+#[tokio::test]
+async fn admin_deposit_needs_one_signer() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    tv.whitelist(Asset::Sol).await;
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+
+    let ticket = tv.admin_deposit_ticket(vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[2]);
+    let ix = tv.admin_deposit_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&treasury).await - before, LAMPORTS);
+
+    let ticket = tv.admin_deposit_ticket(vec![sol(LAMPORTS)]).await;
+    let sigs = vec![EthSigner::from_seed(99).sign(&ticket, &tv.domain_suffix)];
+    let ix = tv.admin_deposit_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+}
+
+#[tokio::test]
+async fn admin_deposit_may_bypass_the_whitelist() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+
+    let ticket = tv.admin_deposit_ticket(vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.admin_deposit_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AssetNotWhitelisted);
+
+    let mut ticket = tv.admin_deposit_ticket(vec![sol(LAMPORTS)]).await;
+    ticket.bypass_whitelist = true;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.admin_deposit_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&treasury).await - before, LAMPORTS);
+}
+
+#[tokio::test]
+async fn admin_withdraw_needs_all_signers() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.funded_mint(0, 50).await;
+    let recipient = Keypair::new().pubkey();
+    let recipient_ata = tv.create_ata(&recipient, &mint).await;
+
+    let ticket = tv
+        .admin_withdrawal_ticket(recipient, vec![spl(mint, 20)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let accounts = tv.spl_withdraw_accounts(&recipient, &[mint]);
+    let ix = tv.admin_withdraw_ix(ticket.clone(), sigs, accounts.clone());
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.admin_withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let vault_ata = get_associated_token_address(&tv.vault, &mint);
+    assert_eq!(tv.token_balance(&recipient_ata).await, 20);
+    assert_eq!(tv.token_balance(&vault_ata).await, 30);
+}
+
+#[tokio::test]
+async fn collect_fees_sweeps_fee_treasury() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let mint = tv.funded_mint(6, 1_000_000).await;
+    let fee_treasury = fee_treasury_address(&tv.vault);
+    let fee_ata = tv.create_ata(&fee_treasury, &mint).await;
+
+    // 1% of every withdrawal line stays in the fee treasury.
+    let ticket = tv.set_fee_ticket(100).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    tv.create_ata(&recipient, &mint).await;
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), spl(mint, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let mut accounts = tv.spl_withdraw_accounts(&recipient, &[mint]);
+    accounts.push(writable(fee_ata));
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let collector = Keypair::new().pubkey();
+    let collector_ata = tv.create_ata(&collector, &mint).await;
+    let accounts = vec![readonly(mint), writable(fee_ata), writable(collector_ata)];
+    let ticket = tv
+        .collect_fees_ticket(collector, vec![sol(LAMPORTS / 100), spl(mint, 100)])
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.collect_fees_ix(ticket, sigs, accounts.clone());
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.lamports(&collector).await, LAMPORTS / 100);
+    assert_eq!(tv.token_balance(&collector_ata).await, 100);
+    assert_eq!(tv.token_balance(&fee_ata).await, 0);
+
+    // The fee treasury keeps its rent-exempt minimum.
+    let ticket = tv.collect_fees_ticket(collector, vec![sol(1)]).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.collect_fees_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn sweep_dust_moves_balances_below_the_minimum() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let dust_mint = tv.funded_mint(6, 50).await;
+    let funded_mint = tv.funded_mint(6, 1_000_000).await;
+    let vault_dust_ata = get_associated_token_address(&tv.vault, &dust_mint);
+    let vault_funded_ata = get_associated_token_address(&tv.vault, &funded_mint);
+    for mint in [dust_mint, funded_mint] {
+        let ticket = tv
+            .set_min_withdrawal_ticket(Asset::SplToken { mint }, 100)
+            .await;
+        let sigs = tv.sign_all(&ticket);
+        let ix = tv.set_min_withdrawal_ix(ticket, sigs);
+        tv.process(&[ix], &[]).await.unwrap();
+    }
+
+    let destination = Keypair::new().pubkey();
+    let dust_ata = tv.create_ata(&destination, &dust_mint).await;
+    let funded_ata = tv.create_ata(&destination, &funded_mint).await;
+    let accounts = tv.spl_withdraw_accounts(&destination, &[dust_mint, funded_mint]);
+    let ticket = tv
+        .sweep_dust_ticket(destination, vec![dust_mint, funded_mint])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.sweep_dust_ix(ticket.clone(), sigs, accounts.clone());
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.sweep_dust_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.token_balance(&dust_ata).await, 50);
+    assert_eq!(tv.token_balance(&vault_dust_ata).await, 0);
+    assert_eq!(tv.token_balance(&funded_ata).await, 0);
+    assert_eq!(tv.token_balance(&vault_funded_ata).await, 1_000_000);
+}
+
+#[tokio::test]
+async fn remove_asset_needs_an_empty_vault_token_account() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let held_mint = tv.funded_mint(6, 10).await;
+    let unopened_mint = tv.create_mint(6).await;
+    tv.whitelist(Asset::SplToken {
+        mint: unopened_mint,
+    })
+    .await;
+
+    let ticket = tv
+        .remove_asset_ticket(Asset::SplToken { mint: held_mint })
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_asset_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotEmpty,
+    );
+
+    // The account has to be passed in.
+    let ticket = tv
+        .remove_asset_ticket(Asset::SplToken { mint: held_mint })
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let mut ix = tv.remove_asset_ix(ticket, sigs);
+    ix.accounts.pop();
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+
+    // A vault token account that was never created holds nothing.
+    let ticket = tv
+        .remove_asset_ticket(Asset::SplToken {
+            mint: unopened_mint,
+        })
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.whitelisted_assets().await,
+        vec![Asset::SplToken { mint: held_mint }]
+    );
+}
+
+#[tokio::test]
+async fn close_vault_token_account_of_a_delisted_drained_mint() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let drained_mint = tv.funded_mint(6, 0).await;
+    let refilled_mint = tv.funded_mint(6, 0).await;
+    let vault_ata = get_associated_token_address(&tv.vault, &drained_mint);
+    let refilled_ata = get_associated_token_address(&tv.vault, &refilled_mint);
+    let rent = tv.lamports(&vault_ata).await;
+    let rent_recipient = Keypair::new().pubkey();
+
+    let ticket = tv
+        .close_vault_token_account_ticket(drained_mint, rent_recipient)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.close_vault_token_account_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::AssetStillWhitelisted,
+    );
+
+    for mint in [drained_mint, refilled_mint] {
+        let ticket = tv.remove_asset_ticket(Asset::SplToken { mint }).await;
+        let sigs = tv.sign_all(&ticket);
+        let ix = tv.remove_asset_ix(ticket, sigs);
+        tv.process(&[ix], &[]).await.unwrap();
+    }
+    // Anyone can still send tokens to a delisted mint's account.
+    tv.mint_to(&refilled_mint, &refilled_ata, 10).await;
+
+    let ticket = tv
+        .close_vault_token_account_ticket(refilled_mint, rent_recipient)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.close_vault_token_account_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotEmpty,
+    );
+
+    let ticket = tv
+        .close_vault_token_account_ticket(drained_mint, rent_recipient)
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.close_vault_token_account_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.close_vault_token_account_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&vault_ata).await, 0);
+    assert_eq!(tv.lamports(&rent_recipient).await, rent);
+}
+
+#[tokio::test]
+async fn sweep_asset_empties_and_closes_a_delisted_mint() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.funded_mint(6, 1_000_000).await;
+    let vault_ata = get_associated_token_address(&tv.vault, &mint);
+    let rent = tv.lamports(&vault_ata).await;
+    let destination = Keypair::new().pubkey();
+    let destination_ata = tv.create_ata(&destination, &mint).await;
+
+    let ticket = tv.sweep_asset_ticket(mint, destination, true).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.sweep_asset_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::AssetStillWhitelisted,
+    );
+
+    // Sweeping alone leaves the account open and the asset listed.
+    let ticket = tv.sweep_asset_ticket(mint, destination, false).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.sweep_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&destination_ata).await, 1_000_000);
+    assert_eq!(tv.token_balance(&vault_ata).await, 0);
+
+    let ticket = tv.remove_asset_ticket(Asset::SplToken { mint }).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.mint_to(&mint, &vault_ata, 10).await;
+
+    let ticket = tv.sweep_asset_ticket(mint, destination, true).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.sweep_asset_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.sweep_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&destination_ata).await, 1_000_010);
+    assert_eq!(tv.lamports(&vault_ata).await, 0);
+    assert_eq!(tv.lamports(&destination).await, rent);
+}
+
+#[tokio::test]
+async fn set_log_verbosity_needs_admin_threshold() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    assert_eq!(tv.vault_state().await.log_verbosity, LogVerbosity::Itemized);
+
+    let ticket = tv.set_log_verbosity_ticket(LogVerbosity::Compact).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.set_log_verbosity_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_log_verbosity_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.log_verbosity, LogVerbosity::Compact);
+
+    // Compact logging doesn't change what the instructions do.
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 2 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn sub_vault_is_reclaimed_by_parent() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    tv.whitelist(Asset::Sol).await;
+
+    // Sub-vaults are derived under their parent, so taking the seed as a top-level vault first
+    // doesn't block the sub-vault.
+    let signers = tv.signer_set();
+    let ix = tv.initialize_ix("client-a".to_string(), 2, 3, signers, vec![], String::new());
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.create_sub_vault_ticket("client-a").await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.create_sub_vault_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.create_sub_vault_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // Signers are inherited, the whitelist is not.
+    let sub_vault = sub_vault_address(&tv.vault, "client-a");
+    let parent = tv.vault_state().await;
+    let child = tv.vault_state_of(&sub_vault).await;
+    assert_eq!(child.parent, tv.vault);
+    assert_eq!(child.signers, parent.signers);
+    assert_eq!(child.m_threshold, parent.m_threshold);
+    assert_eq!(child.admin_threshold, parent.admin_threshold);
+    assert!(child.whitelisted_assets.is_empty());
+
+    let sub_treasury = treasury_address(&sub_vault);
+    tv.fund(&sub_treasury, 2 * LAMPORTS).await;
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+
+    let ticket = tv
+        .reclaim_sub_vault_ticket(sub_vault, vec![sol(LAMPORTS)])
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.reclaim_sub_vault_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&treasury).await - before, LAMPORTS);
+
+    // A vault the parent didn't create can't be reclaimed.
+    let vault = tv.vault;
+    let ticket = tv.reclaim_sub_vault_ticket(vault, vec![sol(1)]).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.reclaim_sub_vault_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NotASubVault);
+}
+
+#[tokio::test]
+async fn accept_upgrade_authority_hands_the_program_to_the_vault() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let deploy_key = Keypair::new();
+    tv.deploy_program_data(deploy_key.pubkey()).await;
+
+    let other = Keypair::new();
+    let ix = tv.accept_upgrade_authority_ix(other.pubkey());
+    assert_failed(tv.process(&[ix], &[&other]).await);
+    assert_eq!(
+        tv.program_upgrade_authority().await,
+        Some(deploy_key.pubkey())
+    );
+
+    let ix = tv.accept_upgrade_authority_ix(deploy_key.pubkey());
+    tv.process(std::slice::from_ref(&ix), &[&deploy_key])
+        .await
+        .unwrap();
+    let upgrade_authority = upgrade_authority_address(&tv.vault);
+    assert_eq!(
+        tv.program_upgrade_authority().await,
+        Some(upgrade_authority)
+    );
+
+    // The deploy key has nothing left to hand over.
+    assert_failed(tv.process(&[ix], &[&deploy_key]).await);
+}
+
+#[tokio::test]
+#[ignore = "only the SBF build sits behind the upgradeable loader: run with SBF_OUT_DIR set"]
+async fn upgrade_program_needs_admin_threshold_and_a_fresh_nonce() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let deploy_key = Keypair::new();
+    tv.deploy_program_data(deploy_key.pubkey()).await;
+    let ix = tv.accept_upgrade_authority_ix(deploy_key.pubkey());
+    tv.process(&[ix], &[&deploy_key]).await.unwrap();
+
+    let buffer = tv.upgrade_buffer().await;
+    let spill = Keypair::new().pubkey();
+    let ticket = tv.upgrade_program_ticket(buffer, spill).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.upgrade_program_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let ticket = tv.upgrade_program_ticket(buffer, spill).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.upgrade_program_ix(ticket, sigs);
+    tv.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert!(tv
+        .context
+        .banks_client
+        .get_account(buffer)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(tv.lamports(&spill).await > 0);
+
+    // The upgraded program is live from the next slot.
+    let slot = tv.context.banks_client.get_root_slot().await.unwrap();
+    tv.context.warp_to_slot(slot + 2).unwrap();
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+}
+
+#[tokio::test]
+async fn delegate_stake_needs_admin_threshold_and_funds() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let vote_account = Keypair::new().pubkey();
+
+    let ticket = tv.delegate_stake_ticket(vote_account, LAMPORTS).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.delegate_stake_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // The treasury only holds its rent-exempt minimum.
+    let ticket = tv.delegate_stake_ticket(vote_account, LAMPORTS).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.delegate_stake_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn liquid_stake_needs_admin_threshold_and_funds() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let pool = tv.create_stake_pool().await;
+
+    let ticket = tv.liquid_stake_ticket(&pool, LAMPORTS, 0).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.liquid_stake_ix(&pool, ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // The treasury only holds its rent-exempt minimum.
+    let ticket = tv.liquid_stake_ticket(&pool, LAMPORTS, 0).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.liquid_stake_ix(&pool, ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn rebalance_moves_funds_between_vault_accounts() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let vault = tv.vault;
+    let treasury = tv.treasury;
+    let cold = named_treasury_address(&vault, "cold");
+    tv.fund(&treasury, 2 * LAMPORTS).await;
+
+    let ticket = tv.set_treasury_ticket("cold", 3).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_treasury_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let cold_before = tv.lamports(&cold).await;
+
+    let ticket = tv
+        .rebalance_ticket(Asset::Sol, LAMPORTS, treasury, cold)
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let ticket = tv
+        .rebalance_ticket(Asset::Sol, LAMPORTS, treasury, cold)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&cold).await, cold_before + LAMPORTS);
+
+    // Funds can't leave the vault this way.
+    let outsider = tv.new_funded_keypair(LAMPORTS).await.pubkey();
+    let ticket = tv
+        .rebalance_ticket(Asset::Sol, LAMPORTS / 2, cold, outsider)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidRebalanceAccount,
+    );
+
+    // Tokens move between token accounts the vault owns.
+    let mint = tv.create_mint(6).await;
+    let vault_ata = tv.create_ata(&vault, &mint).await;
+    let vault_spare = tv.create_token_account(&vault, &mint).await;
+    let outsider_ata = tv.create_ata(&outsider, &mint).await;
+    tv.mint_to(&mint, &vault_ata, 1_000).await;
+
+    let asset = Asset::SplToken { mint };
+    let ticket = tv
+        .rebalance_ticket(asset.clone(), 400, vault_ata, vault_spare)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![readonly(mint)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&vault_ata).await, 600);
+    assert_eq!(tv.token_balance(&vault_spare).await, 400);
+
+    let ticket = tv
+        .rebalance_ticket(asset.clone(), 400, vault_ata, outsider_ata)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![readonly(mint)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidRebalanceAccount,
+    );
+
+    let ticket = tv.rebalance_ticket(asset, 400, vault_ata, vault_ata).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.rebalance_ix(ticket, sigs, vec![readonly(mint)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidRebalance);
+}
+
+#[tokio::test]
+async fn swap_holds_the_route_to_the_ticket() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let usdc = tv.create_mint(6).await;
+    let usdt = tv.create_mint(6).await;
+    tv.whitelist(Asset::SplToken { mint: usdc }).await;
+    let vault = tv.vault;
+    let vault_usdc = tv.create_ata(&vault, &usdc).await;
+    tv.mint_to(&usdc, &vault_usdc, 1_000).await;
+    let pool = tv.create_swap_pool(usdc, usdt, 1_000).await;
+
+    // Both sides must be whitelisted.
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.swap_ix(ticket, sigs, tv.swap_route(&pool, 100, 99));
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AssetNotWhitelisted);
+    tv.whitelist(Asset::SplToken { mint: usdt }).await;
+
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.swap_ix(ticket, sigs, tv.swap_route(&pool, 100, 99));
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // The route is quoted at submission, but can't spend more or return less than signed for.
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.swap_ix(ticket, sigs, tv.swap_route(&pool, 101, 99));
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::SwapInputExceeded);
+
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.swap_ix(ticket, sigs, tv.swap_route(&pool, 100, 94));
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::SwapSlippage);
+
+    // The vault signs the route, so it can't reach the vault's other token accounts.
+    let other = tv.create_mint(6).await;
+    let vault_other = tv.create_ata(&vault, &other).await;
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign_all(&ticket);
+    let (data, mut accounts) = tv.swap_route(&pool, 100, 99);
+    accounts.push(writable(vault_other));
+    let ix = tv.swap_ix(ticket, sigs, (data, accounts));
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::SwapAccountNotAllowed,
+    );
+
+    let ticket = tv.swap_ticket(&pool, 100, 95).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.swap_ix(ticket, sigs, tv.swap_route(&pool, 100, 99));
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&vault_usdc).await, 900);
+    assert_eq!(
+        tv.token_balance(&get_associated_token_address(&vault, &usdt))
+            .await,
+        99
+    );
+}
+
+#[tokio::test]
+async fn execute_strategy_only_calls_approved_programs() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let memo = spl_memo_ix(&tv.vault, b"strategy");
+
+    let ticket = tv.add_strategy_ticket(anchor_spl::memo::ID).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.add_strategy_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.strategy_allowlist().await.unwrap().programs,
+        vec![anchor_spl::memo::ID]
+    );
+
+    // The vault PDA signs the memo.
+    let ticket = tv.strategy_ticket(&memo).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.execute_strategy_ix(ticket, sigs, memo.clone());
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // The data is part of the signed hash.
+    let ticket = tv.strategy_ticket(&memo).await;
+    let sigs = tv.sign_all(&ticket);
+    let tampered = spl_memo_ix(&tv.vault, b"tampered");
+    let ix = tv.execute_strategy_ix(ticket, sigs, tampered);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::CpiDataMismatch);
+
+    let ticket = tv.remove_strategy_ticket(anchor_spl::memo::ID).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_strategy_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.strategy_ticket(&memo).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.execute_strategy_ix(ticket, sigs, memo);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::StrategyNotApproved);
+}
+
+fn spl_memo_ix(signer: &Pubkey, memo: &[u8]) -> Instruction {
+    Instruction {
+        program_id: anchor_spl::memo::ID,
+        accounts: vec![AccountMeta::new_readonly(*signer, true)],
+        data: memo.to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn crank_registry_restricts_and_pays_crankers() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+    let treasury = tv.treasury;
+    let registry = readonly(crank_registry_address(&tv.vault));
+
+    let ticket = tv
+        .create_schedule_ticket(recipient, Asset::Sol, LAMPORTS / 10, 60, 3)
+        .await;
+    let request_id = ticket.request_id;
+    let first = ticket.first_execution;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.create_schedule_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.set_crank_incentive_ticket(10_000).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_crank_incentive_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.vault_state().await.has_crank_registry);
+
+    // The registry is empty, so nobody may crank.
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![registry.clone()]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::CrankerNotRegistered,
+    );
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::CrankRegistryNotFound,
+    );
+
+    let payer = tv.payer();
+    let ticket = tv.add_cranker_ticket(payer).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.add_cranker_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.crank_registry().await.unwrap().crankers, vec![payer]);
+
+    let before = tv.lamports(&treasury).await;
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![registry.clone()]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.lamports(&treasury).await,
+        before - LAMPORTS / 10 - 10_000
+    );
+
+    let ticket = tv.remove_cranker_ticket(payer).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_cranker_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    tv.warp_to_timestamp(first + 60).await;
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![registry]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::CrankerNotRegistered,
+    );
+}
+
+#[tokio::test]
+async fn views_return_vault_nonce_and_asset_state() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+
+    let ticket = tv.add_asset_ticket(Asset::Sol).await;
+    let request_id = ticket.request_id;
+    let hash = ticket.hash(&tv.vault_state().await.domain_suffix);
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.add_asset_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.deposit_sol(&user, LAMPORTS).await;
+
+    let info: VaultInfo = tv.view(tv.get_vault_info_ix()).await;
+    let vault = tv.vault_state().await;
+    assert_eq!(info.vault_seed, vault.vault_seed);
+    assert_eq!(info.m_threshold, vault.m_threshold);
+    assert_eq!(info.signers, vault.signers);
+    assert_eq!(info.processed_count, vault.processed_count);
+    assert_eq!(info.sequence, 3); // initialize, add_asset, deposit, not the view
+    assert_eq!(info.asset_count, 1);
+    assert_eq!(info.treasury, tv.treasury);
+    assert_eq!(info.treasury_lamports, LAMPORTS);
+
+    let status: NonceStatus = tv.view(tv.get_nonce_status_ix(request_id, true)).await;
+    assert_eq!(status.nonce, admin_nonce_address(&tv.vault, request_id));
+    assert!(status.used && !status.vetoed);
+    assert_eq!(status.ticket_hash, hash);
+    // The withdrawal nonce of the same request id is untouched and doesn't exist.
+    let status: NonceStatus = tv.view(tv.get_nonce_status_ix(request_id, false)).await;
+    assert!(!status.used);
+    assert_eq!(status.ticket_hash, [0; 32]);
+
+    let config: Option<AssetConfig> = tv.view(tv.get_asset_config_ix(Asset::Sol)).await;
+    assert_eq!(config.as_ref(), vault.asset_config(&Asset::Sol));
+    let mint = tv.create_mint(6).await;
+    let config: Option<AssetConfig> = tv
+        .view(tv.get_asset_config_ix(Asset::SplToken { mint }))
+        .await;
+    assert_eq!(config, None);
+}
+
+#[tokio::test]
+async fn compute_ticket_hash_matches_ticket_hashing() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let suffix = tv.vault_state().await.domain_suffix;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let hash = ticket.hash(&suffix);
+    let ix = tv.compute_ticket_hash_ix(AnyTicket::Withdrawal { ticket }, &suffix);
+    assert_eq!(tv.view::<[u8; 32]>(ix).await, hash);
+
+    let ticket = tv.add_asset_ticket(Asset::Sol).await;
+    let hash = ticket.hash(&suffix);
+    let ix = tv.compute_ticket_hash_ix(
+        AnyTicket::AddAsset {
+            ticket: ticket.clone(),
+        },
+        &suffix,
+    );
+    assert_eq!(tv.view::<[u8; 32]>(ix).await, hash);
+
+    // The domain suffix is part of the hash.
+    let ix = tv.compute_ticket_hash_ix(AnyTicket::AddAsset { ticket }, "other-deployment");
+    assert_ne!(tv.view::<[u8; 32]>(ix).await, hash);
+}
@@ -0,0 +1,296 @@
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use strike_example::instructions::constant::TICKET_VERSION;
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::{
+    Asset, BulkAccountIndexes, BulkWithdrawalTicket, GatewayTokenState,
+};
+use strike_program_test::*;
+
+const LAMPORTS: u64 = 1_000_000_000;
+
+/// `ix` with the account indexes `bulk_withdraw_ix` computed passed through `edit`.
+fn edit_account_indexes(
+    mut ix: Instruction,
+    edit: impl FnOnce(&mut Vec<BulkAccountIndexes>),
+) -> Instruction {
+    let mut args = strike_example::instruction::BulkWithdraw::try_from_slice(&ix.data[8..])
+        .expect("bulk_withdraw data");
+    edit(&mut args.account_indexes);
+    ix.data = args.data();
+    ix
+}
+
+async fn funded_vault() -> TestVault {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    tv
+}
+
+#[tokio::test]
+async fn bulk_withdraw_sol_and_spl() {
+    let mut tv = funded_vault().await;
+    let mint = tv.funded_mint(6, 1_000).await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+    let bob_ata = tv.create_ata(&bob, &mint).await;
+
+    let tickets = vec![
+        tv.withdrawal_ticket(alice, vec![sol(LAMPORTS)]).await,
+        tv.withdrawal_ticket(bob, vec![sol(LAMPORTS / 2), spl(mint, 250)])
+            .await,
+    ];
+    let nonces: Vec<_> = tickets
+        .iter()
+        .map(|t| nonce_address(&tv.vault, t.request_id))
+        .collect();
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets,
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+
+    let mut accounts = vec![writable(alice), writable(bob)];
+    accounts.extend(tv.spl_withdraw_accounts(&bob, &[mint]));
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.lamports(&alice).await, LAMPORTS);
+    assert_eq!(tv.lamports(&bob).await, LAMPORTS / 2);
+    assert_eq!(tv.token_balance(&bob_ata).await, 250);
+    for nonce in nonces {
+        assert!(tv.nonce_used(&nonce).await);
+    }
+}
+
+#[tokio::test]
+async fn bulk_withdraw_account_permutations() {
+    let mut tv = funded_vault().await;
+    let mint = tv.funded_mint(6, 1_000).await;
+    let alice = Keypair::new().pubkey();
+    let alice_ata = tv.create_ata(&alice, &mint).await;
+    let vault_ata = get_associated_token_address(&tv.vault, &mint);
+
+    // Recipient and token accounts may appear in any order after the nonces.
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![
+            tv.withdrawal_ticket(alice, vec![spl(mint, 100), sol(LAMPORTS)])
+                .await,
+        ],
+    };
+    let sigs = tv.sign(&bulk, &[1, 2]);
+    let accounts = vec![
+        writable(vault_ata),
+        readonly(mint),
+        writable(alice_ata),
+        writable(alice),
+    ];
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&alice_ata).await, 100);
+
+    // Missing recipient account.
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![sol(1)]).await],
+    };
+    let sigs = tv.sign(&bulk, &[1, 2]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidRecipient);
+
+    // A recipient index pointing at another account, here the ticket's nonce.
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![sol(1)]).await],
+    };
+    let sigs = tv.sign(&bulk, &[1, 2]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    let ix = edit_account_indexes(ix, |indexes| indexes[0].recipient = 0);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidRecipient);
+
+    // Indexes must cover every ticket and every withdrawal line.
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![spl(mint, 1)]).await],
+    };
+    let sigs = tv.sign(&bulk, &[1, 2]);
+    let mut accounts = vec![writable(alice)];
+    accounts.extend(tv.spl_withdraw_accounts(&alice, &[mint]));
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, accounts);
+    let no_tickets = edit_account_indexes(ix.clone(), |indexes| indexes.clear());
+    assert_error(
+        tv.process(&[no_tickets], &[]).await,
+        ErrorCode::InvalidAccountIndexes,
+    );
+    let no_lines = edit_account_indexes(ix.clone(), |indexes| indexes[0].withdrawals.clear());
+    assert_error(
+        tv.process(&[no_lines], &[]).await,
+        ErrorCode::InvalidAccountIndexes,
+    );
+
+    // A vault token index pointing at the recipient's token account.
+    let ix = edit_account_indexes(ix, |indexes| {
+        indexes[0].withdrawals[0].vault_token = indexes[0].withdrawals[0].recipient_token
+    });
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+
+    // Missing token accounts for an SPL withdrawal.
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![spl(mint, 1)]).await],
+    };
+    let sigs = tv.sign(&bulk, &[1, 2]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice), readonly(mint)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+}
+
+#[tokio::test]
+async fn bulk_withdraw_rejects_bad_batches() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(alice, vec![sol(1)]).await;
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![ticket.clone(), ticket],
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateRequestId);
+
+    let mut tickets = Vec::new();
+    for _ in 0..5 {
+        tickets.push(tv.withdrawal_ticket(alice, vec![sol(1)]).await);
+    }
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets,
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TooManyTickets);
+
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![sol(1)]).await],
+    };
+    let sigs = tv.sign(&bulk, &[0, 0]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateSignature);
+
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![tv.withdrawal_ticket(alice, vec![sol(1)]).await],
+    };
+    let expiry = bulk.tickets[0].expiry;
+    let sigs = tv.sign(&bulk, &[0, 1]);
+    tv.warp_to_timestamp(expiry + 1).await;
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TicketExpired);
+}
+
+#[tokio::test]
+async fn bulk_withdraw_logs_the_failing_ticket_and_withdrawal() {
+    let mut tv = funded_vault().await;
+    let mint = tv.funded_mint(6, 1_000).await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+
+    let tickets = vec![
+        tv.withdrawal_ticket(alice, vec![sol(1)]).await,
+        tv.withdrawal_ticket(bob, vec![sol(1), spl(mint, 0)]).await,
+    ];
+    let request_id = tickets[1].request_id;
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets,
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice), writable(bob)]);
+
+    let logs = tv.simulate_logs(std::slice::from_ref(&ix)).await;
+    let expected = format!(
+        "Bulk ticket 1 (request id {}) withdrawal 1 failed",
+        request_id
+    );
+    assert!(
+        logs.iter().any(|log| log.contains(&expected)),
+        "no {:?} in {:?}",
+        expected,
+        logs
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidAmount);
+}
+
+#[tokio::test]
+async fn bulk_withdraw_rejects_used_nonce() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(alice, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets: vec![ticket],
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, vec![writable(alice)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+}
+
+#[tokio::test]
+async fn bulk_withdraw_checks_every_recipients_identity_pass() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+    let network = Pubkey::new_unique();
+    let set = tv.set_identity_network_ticket(network).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_identity_network_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let alice_pass = tv.post_identity_pass(alice, network, GatewayTokenState::Active, None);
+    let bob_pass = tv.post_identity_pass(bob, network, GatewayTokenState::Active, None);
+
+    let tickets = vec![
+        tv.withdrawal_ticket(alice, vec![sol(LAMPORTS)]).await,
+        tv.withdrawal_ticket(bob, vec![sol(LAMPORTS)]).await,
+    ];
+    let bulk = BulkWithdrawalTicket {
+        version: TICKET_VERSION,
+        tickets,
+    };
+    let sigs = tv.sign(&bulk, &[0, 1]);
+
+    let accounts = vec![writable(alice), writable(bob), readonly(alice_pass)];
+    let ix = tv.bulk_withdraw_ix(bulk.clone(), sigs.clone(), accounts);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::IdentityPassRequired,
+    );
+
+    let accounts = vec![
+        writable(alice),
+        writable(bob),
+        readonly(alice_pass),
+        readonly(bob_pass),
+    ];
+    let ix = tv.bulk_withdraw_ix(bulk, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&alice).await, LAMPORTS);
+    assert_eq!(tv.lamports(&bob).await, LAMPORTS);
+}
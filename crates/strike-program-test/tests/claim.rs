@@ -0,0 +1,313 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::{Asset, ClaimLeaf, GatewayTokenState, PriceOracle};
+use strike_program_test::*;
+
+const LAMPORTS: u64 = 1_000_000_000;
+const SOL_USD_FEED: [u8; 32] = [0x50; 32];
+
+async fn funded_vault() -> TestVault {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    tv
+}
+
+fn leaf(index: u64, recipient: Pubkey, amount: u64) -> ClaimLeaf {
+    ClaimLeaf {
+        index,
+        recipient,
+        asset_amount: sol(amount),
+    }
+}
+
+/// Publish the tree's root with M of N and return its request id.
+async fn publish(tv: &mut TestVault, tree: &ClaimTree) -> u64 {
+    let ticket = tv.claim_root_ticket(tree.root()).await;
+    let request_id = ticket.request_id;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.publish_claim_root_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    request_id
+}
+
+#[tokio::test]
+async fn claim_pays_each_leaf_once() {
+    let mut tv = funded_vault().await;
+    let recipients: Vec<_> = (0..5).map(|_| Keypair::new().pubkey()).collect();
+    let leaves: Vec<_> = recipients
+        .iter()
+        .enumerate()
+        .map(|(i, r)| leaf(i as u64, *r, (i as u64 + 1) * LAMPORTS / 10))
+        .collect();
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+    assert!(tv.nonce_used(&nonce_address(&tv.vault, request_id)).await);
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let ix = tv.claim_ix(request_id, leaf.clone(), tree.proof(i), vec![]);
+        tv.process(&[ix], &[]).await.unwrap();
+        assert_eq!(tv.lamports(&leaf.recipient).await, leaf.asset_amount.amount);
+    }
+    assert_eq!(tv.claim_root(request_id).await.unwrap().claimed, 5);
+
+    let ix = tv.claim_ix(request_id, leaves[2].clone(), tree.proof(2), vec![]);
+    assert_failed(tv.process(&[ix], &[]).await);
+    assert_eq!(tv.lamports(&recipients[2]).await, 3 * LAMPORTS / 10);
+}
+
+#[tokio::test]
+async fn claim_rejects_forged_leaves() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+    let leaves = vec![leaf(0, alice, LAMPORTS), leaf(1, bob, LAMPORTS)];
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+
+    // Inflated amount.
+    let ix = tv.claim_ix(
+        request_id,
+        leaf(0, alice, 2 * LAMPORTS),
+        tree.proof(0),
+        vec![],
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidClaimProof);
+
+    // Someone else's proof.
+    let ix = tv.claim_ix(request_id, leaf(0, bob, LAMPORTS), tree.proof(0), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidClaimProof);
+
+    // The root itself needs M of N.
+    let ticket = tv.claim_root_ticket(tree.root()).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.publish_claim_root_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // Claims close with the claim window.
+    let claim_expiry = tv.claim_root(request_id).await.unwrap().claim_expiry;
+    tv.warp_to_timestamp(claim_expiry + 1).await;
+    let ix = tv.claim_ix(request_id, leaves[1].clone(), tree.proof(1), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::ClaimWindowClosed);
+}
+
+#[tokio::test]
+async fn claim_needs_an_identity_pass_once_a_network_is_set() {
+    let mut tv = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let network = Pubkey::new_unique();
+    let leaves = vec![leaf(0, recipient, LAMPORTS)];
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+
+    let set = tv.set_identity_network_ticket(network).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_identity_network_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ix = tv.claim_ix(request_id, leaves[0].clone(), tree.proof(0), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::IdentityPassRequired,
+    );
+
+    let pass = tv.post_identity_pass(recipient, network, GatewayTokenState::Revoked, None);
+    let ix = tv.claim_ix(
+        request_id,
+        leaves[0].clone(),
+        tree.proof(0),
+        vec![readonly(pass)],
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::IdentityPassInvalid);
+
+    let pass = tv.post_identity_pass(recipient, network, GatewayTokenState::Active, None);
+    let ix = tv.claim_ix(
+        request_id,
+        leaves[0].clone(),
+        tree.proof(0),
+        vec![readonly(pass)],
+    );
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn claim_checks_withdrawal_limits() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+    let leaves = vec![
+        leaf(0, alice, LAMPORTS / 10 - 1),
+        leaf(1, bob, LAMPORTS / 10),
+    ];
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+
+    let ticket = tv
+        .set_min_withdrawal_ticket(Asset::Sol, LAMPORTS / 10)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_min_withdrawal_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ix = tv.claim_ix(request_id, leaves[0].clone(), tree.proof(0), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::BelowMinWithdrawal);
+
+    let ix = tv.claim_ix(request_id, leaves[1].clone(), tree.proof(1), vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&bob).await, LAMPORTS / 10);
+}
+
+#[tokio::test]
+async fn claim_checks_usd_caps() {
+    let mut tv = funded_vault().await;
+    let alice = Keypair::new().pubkey();
+    let bob = Keypair::new().pubkey();
+    let leaves = vec![leaf(0, alice, 3 * LAMPORTS), leaf(1, bob, LAMPORTS)];
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+
+    // $300 per line.
+    let ticket = tv
+        .set_usd_cap_ticket(
+            Asset::Sol,
+            PriceOracle::Pyth {
+                feed_id: SOL_USD_FEED,
+            },
+            300_000_000,
+            60,
+            100,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_usd_cap_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    let now = tv.now().await;
+    let price = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 1_000_000, -8, now);
+    let usd_cap = readonly(usd_cap_address(&tv.vault, &Asset::Sol));
+
+    let ix = tv.claim_ix(
+        request_id,
+        leaves[1].clone(),
+        tree.proof(1),
+        vec![readonly(price)],
+    );
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::UsdCapAccountNotFound,
+    );
+
+    // 3 SOL at $150 is over the cap.
+    let ix = tv.claim_ix(
+        request_id,
+        leaves[0].clone(),
+        tree.proof(0),
+        vec![usd_cap.clone(), readonly(price)],
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdCapExceeded);
+
+    let ix = tv.claim_ix(
+        request_id,
+        leaves[1].clone(),
+        tree.proof(1),
+        vec![usd_cap, readonly(price)],
+    );
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&bob).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn claim_pays_the_protocol_fee() {
+    let mut tv = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let fee_treasury = fee_treasury_address(&tv.vault);
+    let treasury = tv.treasury;
+    let leaves = vec![leaf(0, recipient, LAMPORTS)];
+    let tree = ClaimTree::new(&leaves);
+    let request_id = publish(&mut tv, &tree).await;
+
+    // 1%
+    let ticket = tv.set_fee_ticket(100).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let treasury_before = tv.lamports(&treasury).await;
+    let fee_treasury_before = tv.lamports(&fee_treasury).await;
+
+    let ix = tv.claim_ix(request_id, leaves[0].clone(), tree.proof(0), vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS - LAMPORTS / 100);
+    assert_eq!(
+        tv.lamports(&fee_treasury).await - fee_treasury_before,
+        LAMPORTS / 100
+    );
+    assert_eq!(treasury_before - tv.lamports(&treasury).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn stream_vests_after_cliff_at_rate() {
+    let mut tv = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let treasury = tv.treasury;
+    let treasury_before = tv.lamports(&treasury).await;
+
+    let ticket = tv
+        .create_stream_ticket(recipient, Asset::Sol, LAMPORTS, 100, LAMPORTS / 1000)
+        .await;
+    let request_id = ticket.request_id;
+    let start = ticket.start_time;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.create_stream_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.nonce_used(&nonce_address(&tv.vault, request_id)).await);
+    assert_eq!(tv.lamports(&treasury).await, treasury_before - LAMPORTS);
+
+    let ix = tv.claim_stream_ix(request_id, recipient, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NothingToClaim);
+
+    tv.warp_to_timestamp(start + 100).await;
+    let ix = tv.claim_stream_ix(request_id, recipient, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS / 10);
+
+    // Past the end only the remainder is paid, once.
+    tv.warp_to_timestamp(start + 5000).await;
+    let ix = tv.claim_stream_ix(request_id, recipient, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    assert_eq!(tv.stream(request_id).await.unwrap().claimed, LAMPORTS);
+
+    let ix = tv.claim_stream_ix(request_id, recipient, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NothingToClaim);
+}
+
+#[tokio::test]
+async fn stream_needs_m_of_n_and_a_valid_schedule() {
+    let mut tv = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv
+        .create_stream_ticket(recipient, Asset::Sol, LAMPORTS, 100, LAMPORTS / 1000)
+        .await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.create_stream_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let ticket = tv
+        .create_stream_ticket(recipient, Asset::Sol, LAMPORTS, -1, LAMPORTS / 1000)
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.create_stream_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidStreamSchedule,
+    );
+}
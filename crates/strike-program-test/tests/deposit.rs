@@ -0,0 +1,475 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use strike_example::instructions::constant::MAX_METADATA_LEN;
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::{Asset, AssetSettings};
+use strike_program_test::*;
+
+#[tokio::test]
+async fn deposit_sol_credits_treasury() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+    tv.deposit_sol(&user, 1_000_000_000).await;
+    assert_eq!(tv.lamports(&treasury).await - before, 1_000_000_000);
+}
+
+#[tokio::test]
+async fn deposit_rejects_non_whitelisted_and_zero_amounts() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::AssetNotWhitelisted,
+    );
+
+    tv.whitelist(Asset::Sol).await;
+
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(0)], request_id, vec![]);
+    assert_error(tv.process(&[ix], &[&user]).await, ErrorCode::InvalidAmount);
+
+    let ix = tv.deposit_ix(&user.pubkey(), vec![], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::NoDepositsProvided,
+    );
+}
+
+#[tokio::test]
+async fn deposit_rejects_oversized_or_control_character_metadata() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+
+    let request_id = tv.request_id();
+    let long = "x".repeat(MAX_METADATA_LEN + 1);
+    let ix = tv.deposit_ix_with_metadata(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        Some(long),
+        vec![],
+    );
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::MetadataTooLong,
+    );
+
+    let ix = tv.deposit_ix_with_metadata(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        Some("order-1\nforged-line".to_string()),
+        vec![],
+    );
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::InvalidMetadata,
+    );
+
+    // Surrounding whitespace is trimmed, not rejected.
+    let ix = tv.deposit_ix_with_metadata(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        Some("  order-1  ".to_string()),
+        vec![],
+    );
+    tv.process(&[ix], &[&user]).await.unwrap();
+}
+
+#[tokio::test]
+async fn deposit_spl_moves_tokens_to_vault_ata() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(1_000_000_000).await;
+    let mint = tv.funded_mint(6, 0).await;
+    let user_ata = tv.create_ata(&user.pubkey(), &mint).await;
+    tv.mint_to(&mint, &user_ata, 500).await;
+
+    let request_id = tv.request_id();
+    let accounts = tv.spl_deposit_accounts(&user.pubkey(), &[mint]);
+    let ix = tv.deposit_ix(&user.pubkey(), vec![spl(mint, 200)], request_id, accounts);
+    tv.process(&[ix], &[&user]).await.unwrap();
+
+    let vault_ata = anchor_spl::associated_token::get_associated_token_address(&tv.vault, &mint);
+    assert_eq!(tv.token_balance(&vault_ata).await, 200);
+    assert_eq!(tv.token_balance(&user_ata).await, 300);
+}
+
+#[tokio::test]
+async fn deposit_spl_requires_token_accounts() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(1_000_000_000).await;
+    let mint = tv.funded_mint(6, 0).await;
+    let user_ata = tv.create_ata(&user.pubkey(), &mint).await;
+    tv.mint_to(&mint, &user_ata, 500).await;
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![spl(mint, 200)],
+        request_id,
+        vec![readonly(mint)],
+    );
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+
+    let ix = tv.deposit_ix(&user.pubkey(), vec![spl(mint, 200)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::MintAccountNotFound,
+    );
+}
+
+#[tokio::test]
+async fn deposit_record_accumulates_per_asset() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+    let mint = tv.funded_mint(6, 0).await;
+    let user_ata = tv.create_ata(&user.pubkey(), &mint).await;
+    tv.mint_to(&mint, &user_ata, 500).await;
+
+    assert!(tv.deposit_record(&user.pubkey()).await.is_none());
+
+    tv.deposit_sol(&user, 1_000_000_000).await;
+    let request_id = tv.request_id();
+    let accounts = tv.spl_deposit_accounts(&user.pubkey(), &[mint]);
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![spl(mint, 200), sol(500_000_000)],
+        request_id,
+        accounts,
+    );
+    tv.process(&[ix], &[&user]).await.unwrap();
+
+    let record = tv.deposit_record(&user.pubkey()).await.unwrap();
+    assert_eq!(record.vault, tv.vault);
+    assert_eq!(record.user, user.pubkey());
+    let totals: Vec<(Asset, u64)> = record
+        .deposits
+        .into_iter()
+        .map(|d| (d.asset, d.amount))
+        .collect();
+    assert_eq!(
+        totals,
+        vec![(Asset::Sol, 1_500_000_000), (Asset::SplToken { mint }, 200)]
+    );
+}
+
+#[tokio::test]
+async fn credited_deposits_are_claimed_into_the_ledger() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+    let mint = tv.create_mint(6).await;
+    tv.deposit_sol(&user, 1_000_000_000).await;
+    let treasury = tv.treasury;
+    let treasury_before = tv.lamports(&treasury).await;
+
+    let ticket = tv
+        .credit_ticket(user.pubkey(), vec![sol(2_000_000_000)], [7; 32])
+        .await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.claim_credited_deposit_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.claim_credited_deposit_ix(ticket.clone(), sigs.clone());
+    tv.process(&[ix], &[&user]).await.unwrap();
+    assert!(
+        tv.nonce_used(&nonce_address(&tv.vault, ticket.request_id))
+            .await
+    );
+
+    // The credit adds to what the user deposited here, without moving funds.
+    let record = tv.deposit_record(&user.pubkey()).await.unwrap();
+    assert_eq!(record.deposits[0].amount, 3_000_000_000);
+    assert_eq!(tv.lamports(&treasury).await, treasury_before);
+
+    let ix = tv.claim_credited_deposit_ix(ticket, sigs);
+    assert_failed(tv.process(&[ix], &[&user]).await);
+
+    let ticket = tv
+        .credit_ticket(user.pubkey(), vec![spl(mint, 100)], [8; 32])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.claim_credited_deposit_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::AssetNotWhitelisted,
+    );
+}
+
+#[tokio::test]
+async fn deposit_mints_receipts_when_enabled() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+
+    // Receipts must match the asset's decimals.
+    let ticket = tv.create_receipt_mint_ticket(Asset::Sol, 6).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.create_receipt_mint_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidReceiptDecimals,
+    );
+
+    let ticket = tv.create_receipt_mint_ticket(Asset::Sol, 9).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.create_receipt_mint_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let receipt_mint = receipt_mint_address(&tv.vault, &Asset::Sol);
+    let user_receipts = tv.create_ata(&user.pubkey(), &receipt_mint).await;
+
+    // Without the receipt mint in remaining accounts the deposit goes through unreceipted.
+    tv.deposit_sol(&user, 1_000_000_000).await;
+    assert_eq!(tv.token_balance(&user_receipts).await, 0);
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![sol(400_000_000)],
+        request_id,
+        vec![writable(receipt_mint), writable(user_receipts)],
+    );
+    tv.process(&[ix], &[&user]).await.unwrap();
+    assert_eq!(tv.token_balance(&user_receipts).await, 400_000_000);
+}
+
+#[tokio::test]
+async fn deposit_notifies_the_hook_program() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+
+    let ticket = tv.set_hook_program_ticket(strike_example::ID).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_hook_program_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidHookProgram);
+
+    let ticket = tv.set_hook_program_ticket(TEST_HOOK_PROGRAM_ID).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_hook_program_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.hook_program, TEST_HOOK_PROGRAM_ID);
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::HookProgramNotFound,
+    );
+
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+    let accounts = tv.hook_accounts();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000)], request_id, accounts);
+    tv.process(&[ix], &[&user]).await.unwrap();
+    assert_eq!(tv.lamports(&treasury).await - before, 1_000);
+
+    // A hook that fails reverts the deposit with it.
+    let accounts = tv.hook_accounts();
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        HOOK_REJECTED_REQUEST_ID,
+        accounts,
+    );
+    assert_failed(tv.process(&[ix], &[&user]).await);
+    assert_eq!(tv.lamports(&treasury).await - before, 1_000);
+}
+
+#[tokio::test]
+async fn deposits_stop_at_the_tvl_cap() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    let mint = tv.funded_mint(6, 0).await;
+    let user_ata = tv.create_ata(&user.pubkey(), &mint).await;
+    tv.mint_to(&mint, &user_ata, 1_000).await;
+    let payer = tv.payer();
+    let payer_ata = tv.create_ata(&payer, &mint).await;
+    tv.mint_to(&mint, &payer_ata, 1_000).await;
+    let asset = Asset::SplToken { mint };
+    tv.update_asset(
+        asset.clone(),
+        AssetSettings {
+            tvl_cap: 500,
+            ..AssetSettings::default()
+        },
+    )
+    .await;
+
+    let request_id = tv.request_id();
+    let accounts = tv.spl_deposit_accounts(&user.pubkey(), &[mint]);
+    let ix = tv.deposit_ix(&user.pubkey(), vec![spl(mint, 300)], request_id, accounts);
+    tv.process(&[ix], &[&user]).await.unwrap();
+
+    let accounts = tv.spl_deposit_accounts(&user.pubkey(), &[mint]);
+    let ix = tv.deposit_ix(&user.pubkey(), vec![spl(mint, 300)], request_id, accounts);
+    assert_error(tv.process(&[ix], &[&user]).await, ErrorCode::TvlCapExceeded);
+
+    // Admin deposits count towards the same cap.
+    let ticket = tv.admin_deposit_ticket(vec![spl(mint, 201)]).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let accounts = tv.spl_deposit_accounts(&payer, &[mint]);
+    let ix = tv.admin_deposit_ix(ticket, sigs, accounts);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TvlCapExceeded);
+
+    let ticket = tv.admin_deposit_ticket(vec![spl(mint, 200)]).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let accounts = tv.spl_deposit_accounts(&payer, &[mint]);
+    let ix = tv.admin_deposit_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let vault_ata = anchor_spl::associated_token::get_associated_token_address(&tv.vault, &mint);
+    assert_eq!(tv.token_balance(&vault_ata).await, 500);
+
+    // SOL counts the treasury's whole balance.
+    tv.whitelist(Asset::Sol).await;
+    let treasury = tv.treasury;
+    let held = tv.lamports(&treasury).await;
+    tv.update_asset(
+        Asset::Sol,
+        AssetSettings {
+            tvl_cap: held + 1_000,
+            ..AssetSettings::default()
+        },
+    )
+    .await;
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_001)], request_id, vec![]);
+    assert_error(tv.process(&[ix], &[&user]).await, ErrorCode::TvlCapExceeded);
+    tv.deposit_sol(&user, 1_000).await;
+}
+
+#[tokio::test]
+async fn deposits_stop_at_the_user_cap() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let alice = tv.new_funded_keypair(5_000_000_000).await;
+    let bob = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.update_asset(
+        Asset::Sol,
+        AssetSettings {
+            user_cap: 1_000,
+            ..AssetSettings::default()
+        },
+    )
+    .await;
+
+    tv.deposit_sol(&alice, 600).await;
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&alice.pubkey(), vec![sol(401)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&alice]).await,
+        ErrorCode::UserCapExceeded,
+    );
+    tv.deposit_sol(&alice, 400).await;
+
+    // The cap is per depositor.
+    tv.deposit_sol(&bob, 1_000).await;
+    let record = tv.deposit_record(&alice.pubkey()).await.unwrap();
+    assert_eq!(record.deposits[0].amount, 1_000);
+}
+
+#[tokio::test]
+async fn deposits_need_an_attestation_once_a_registry_is_set() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    tv.whitelist(Asset::Sol).await;
+    let registry = Pubkey::new_unique();
+    let schema = Pubkey::new_unique();
+
+    let ticket = tv
+        .set_deposit_registry_ticket(registry, Pubkey::default())
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_deposit_registry_ix(ticket, sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidDepositRegistry,
+    );
+    let ticket = tv.set_deposit_registry_ticket(registry, schema).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_deposit_registry_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000)], request_id, vec![]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::DepositorNotAttested,
+    );
+
+    // Someone else's attestation, another schema or another owner don't count.
+    let other = tv.post_deposit_attestation(&registry, schema, Pubkey::new_unique(), 0, false);
+    let wrong_schema =
+        tv.post_deposit_attestation(&registry, Pubkey::new_unique(), user.pubkey(), 0, false);
+    let forged =
+        tv.post_deposit_attestation(&Pubkey::new_unique(), schema, user.pubkey(), 0, false);
+    let accounts = vec![readonly(other), readonly(wrong_schema), readonly(forged)];
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000)], request_id, accounts);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::DepositorNotAttested,
+    );
+
+    let now = tv.now().await;
+    let expired = tv.post_deposit_attestation(&registry, schema, user.pubkey(), now, false);
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        vec![readonly(expired)],
+    );
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::DepositorAttestationInvalid,
+    );
+    let revoked = tv.post_deposit_attestation(&registry, schema, user.pubkey(), 0, true);
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        vec![readonly(revoked)],
+    );
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::DepositorAttestationInvalid,
+    );
+
+    let treasury = tv.treasury;
+    let before = tv.lamports(&treasury).await;
+    let valid = tv.post_deposit_attestation(&registry, schema, user.pubkey(), now + 3600, false);
+    let ix = tv.deposit_ix(
+        &user.pubkey(),
+        vec![sol(1_000)],
+        request_id,
+        vec![readonly(valid)],
+    );
+    tv.process(&[ix], &[&user]).await.unwrap();
+    assert_eq!(tv.lamports(&treasury).await - before, 1_000);
+
+    // The default registry opens deposits again.
+    let ticket = tv
+        .set_deposit_registry_ticket(Pubkey::default(), Pubkey::default())
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_deposit_registry_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.deposit_sol(&user, 1_000).await;
+}
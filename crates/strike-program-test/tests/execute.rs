@@ -0,0 +1,76 @@
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use strike_example::instructions::errors::ErrorCode;
+use strike_program_test::*;
+
+fn spl_memo_ix(signer: &Pubkey, memo: &[u8]) -> Instruction {
+    Instruction {
+        program_id: anchor_spl::memo::ID,
+        accounts: vec![AccountMeta::new_readonly(*signer, true)],
+        data: memo.to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn execute_needs_m_of_n() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let memo = spl_memo_ix(&tv.vault, b"execute");
+
+    let ticket = tv.execute_ticket(&memo).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.execute_ix(ticket, sigs, &memo.accounts);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // The vault PDA signs the memo.
+    let ticket = tv.execute_ticket(&memo).await;
+    let request_id = ticket.request_id;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.execute_ix(ticket, sigs, &memo.accounts);
+    tv.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+    let nonce = nonce_address(&tv.vault, request_id);
+    assert!(tv.nonce_account(&nonce).await.unwrap().used);
+
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+}
+
+#[tokio::test]
+async fn execute_passes_exactly_the_ticket_accounts() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let memo = spl_memo_ix(&tv.vault, b"execute");
+
+    let ticket = tv.execute_ticket(&memo).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.execute_ix(ticket, sigs, &[readonly(Keypair::new().pubkey())]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::CpiAccountMismatch);
+
+    let ticket = tv.execute_ticket(&memo).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.execute_ix(ticket, sigs, &[]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::CpiAccountMismatch);
+
+    // The ticket can't name a signer the transaction doesn't provide.
+    let stranger = Keypair::new().pubkey();
+    let memo = Instruction {
+        accounts: vec![AccountMeta::new_readonly(stranger, true)],
+        ..memo
+    };
+    let ticket = tv.execute_ticket(&memo).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.execute_ix(ticket, sigs, &[readonly(stranger)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::CpiAccountMismatch);
+
+    // Nor a writable account passed read-only.
+    let other = Keypair::new().pubkey();
+    let memo = Instruction {
+        accounts: vec![AccountMeta::new(other, false)],
+        ..memo
+    };
+    let ticket = tv.execute_ticket(&memo).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.execute_ix(ticket, sigs, &[readonly(other)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::CpiAccountMismatch);
+}
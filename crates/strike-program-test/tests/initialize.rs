@@ -0,0 +1,294 @@
+use solana_sdk::signature::Signer;
+use strike_example::instructions::constant::CURRENT_VERSION;
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::util::genesis_network_id;
+use strike_example::instructions::{Asset, AuthScheme, WeightedSigner};
+use strike_program_test::*;
+
+#[tokio::test]
+async fn initialize_stores_configuration() {
+    let mut tv = TestVault::new(VaultConfig {
+        domain_suffix: "desk-a".to_string(),
+        ..VaultConfig::default()
+    })
+    .await;
+
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.m_threshold, 2);
+    assert_eq!(vault.admin_threshold, 3);
+    assert_eq!(vault.network_id, NETWORK_ID);
+    assert_eq!(vault.signers, tv.signer_set());
+    assert_eq!(vault.domain_suffix, "desk-a");
+    assert!(vault.whitelisted_assets.is_empty());
+}
+
+#[tokio::test]
+async fn initialize_sets_the_auth_scheme() {
+    let mut tv = TestVault::new(VaultConfig {
+        auth_scheme: AuthScheme::Secp256r1,
+        ..VaultConfig::default()
+    })
+    .await;
+    assert_eq!(tv.vault_state().await.auth_scheme, AuthScheme::Secp256r1);
+
+    let ix = tv.initialize_ix_with_scheme(
+        "self-attestor".to_string(),
+        2,
+        3,
+        tv.signer_set(),
+        vec![],
+        String::new(),
+        AuthScheme::Attestor {
+            program: strike_example::ID,
+        },
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidAttestor);
+}
+
+#[tokio::test]
+async fn initialize_binds_network_id_to_the_genesis_hash() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    assert_eq!(tv.vault_state().await.genesis_hash, [0; 32]);
+
+    let genesis_hash = tv.genesis_hash();
+    for (network_id, genesis_hash) in [
+        (NETWORK_ID, genesis_hash),
+        (genesis_network_id(&[0; 32]), [0; 32]),
+    ] {
+        let ix = tv.initialize_ix_with_genesis("bound".to_string(), network_id, genesis_hash);
+        assert_error(
+            tv.process(&[ix], &[]).await,
+            ErrorCode::GenesisNetworkMismatch,
+        );
+    }
+
+    let network_id = genesis_network_id(&genesis_hash);
+    let ix = tv.initialize_ix_with_genesis("bound".to_string(), network_id, genesis_hash);
+    tv.process(&[ix], &[]).await.unwrap();
+    let vault = tv.vault_state_of(&vault_address("bound")).await;
+    assert_eq!(vault.network_id, network_id);
+    assert_eq!(vault.genesis_hash, genesis_hash);
+}
+
+#[tokio::test]
+async fn sequence_counts_state_changing_instructions() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    assert_eq!(tv.vault_state().await.sequence, 1);
+
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 1_000_000).await;
+    assert_eq!(tv.vault_state().await.sequence, 3);
+
+    // A failed instruction reverts its bump.
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(0)], request_id, vec![]);
+    assert_error(tv.process(&[ix], &[&user]).await, ErrorCode::InvalidAmount);
+    assert_eq!(tv.vault_state().await.sequence, 3);
+}
+
+#[tokio::test]
+async fn migrate_converts_a_version_1_vault() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(5_000_000_000).await;
+    let mint = tv.create_mint(6).await;
+    let assets = vec![Asset::Sol, Asset::SplToken { mint }];
+    tv.rewrite_as_v1(assets.clone()).await;
+
+    // Until it's migrated, nothing can load the vault.
+    let request_id = tv.request_id();
+    let ix = tv.deposit_ix(&user.pubkey(), vec![sol(1_000_000)], request_id, vec![]);
+    assert_failed(tv.process(&[ix], &[&user]).await);
+
+    let ix = tv.migrate_ix(&user.pubkey(), &[mint]);
+    assert_error(
+        tv.process(&[ix], &[&user]).await,
+        ErrorCode::NotVaultAuthority,
+    );
+    // The SPL mint is needed for its decimals.
+    let ix = tv.migrate_ix(&tv.payer(), &[]);
+    assert_failed(tv.process(&[ix], &[]).await);
+
+    let ix = tv.migrate_ix(&tv.payer(), &[mint]);
+    tv.process(&[ix], &[]).await.unwrap();
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.version, CURRENT_VERSION);
+    assert_eq!(vault.authority, tv.payer());
+    assert_eq!(vault.vault_seed, tv.vault_seed);
+    assert_eq!((vault.m_threshold, vault.admin_threshold), (2, 3));
+    assert_eq!(vault.network_id, NETWORK_ID);
+    assert_eq!(vault.signers, tv.signer_set());
+    assert!(vault.admin_signers.is_empty());
+    let whitelisted: Vec<Asset> = vault
+        .whitelisted_assets
+        .iter()
+        .map(|config| config.asset.clone())
+        .collect();
+    assert_eq!(whitelisted, assets);
+    assert_eq!(vault.whitelisted_assets[1].decimals, 6);
+    assert_eq!(vault.sequence, 1);
+
+    // Migrating again changes nothing.
+    let ix = tv.migrate_ix(&tv.payer(), &[mint]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.sequence, 1);
+
+    tv.deposit_sol(&user, 1_000_000).await;
+    assert_eq!(tv.vault_state().await.sequence, 2);
+}
+
+#[tokio::test]
+async fn initialize_rejects_bad_thresholds() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let signers = tv.signer_set();
+
+    let ix = tv.initialize_ix(
+        "zero-m".to_string(),
+        0,
+        1,
+        signers.clone(),
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidThreshold);
+
+    let ix = tv.initialize_ix(
+        "big-m".to_string(),
+        4,
+        1,
+        signers.clone(),
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidThreshold);
+
+    let ix = tv.initialize_ix(
+        "big-admin".to_string(),
+        1,
+        4,
+        signers,
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidThreshold);
+}
+
+#[tokio::test]
+async fn initialize_rejects_bad_signer_sets() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+
+    let ix = tv.initialize_ix("empty".to_string(), 1, 1, vec![], vec![], String::new());
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignersCount);
+
+    let too_many: Vec<WeightedSigner> = (0..11)
+        .map(|i| EthSigner::from_seed(i).weighted())
+        .collect();
+    let ix = tv.initialize_ix(
+        "too-many".to_string(),
+        1,
+        1,
+        too_many,
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignersCount);
+
+    let duplicate = EthSigner::from_seed(7).weighted();
+    let ix = tv.initialize_ix(
+        "duplicate".to_string(),
+        1,
+        1,
+        vec![duplicate, duplicate],
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateSigner);
+
+    let ix = tv.initialize_ix(
+        "long-suffix".to_string(),
+        1,
+        1,
+        vec![duplicate],
+        vec![],
+        "x".repeat(33),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DomainSuffixTooLong);
+
+    let zero_weight = EthSigner::from_seed(8).with_weight(0).weighted();
+    let ix = tv.initialize_ix(
+        "zero-weight".to_string(),
+        1,
+        1,
+        vec![duplicate, zero_weight],
+        vec![],
+        String::new(),
+    );
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidSignerWeight);
+}
+
+#[tokio::test]
+async fn initialize_twice_fails() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let ix = tv.initialize_ix(
+        tv.vault_seed.clone(),
+        2,
+        3,
+        tv.signer_set(),
+        vec![],
+        String::new(),
+    );
+    assert_failed(tv.process(&[ix], &[]).await);
+}
+
+#[tokio::test]
+async fn initialize_full_creates_token_accounts_and_whitelist() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.create_mint(6).await;
+    let assets = vec![Asset::Sol, Asset::SplToken { mint }];
+
+    let duplicated = vec![Asset::Sol, Asset::Sol];
+    let ix = tv.initialize_full_ix("dup".to_string(), tv.signer_set(), duplicated);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateAsset);
+
+    let ix = tv.initialize_full_ix("full".to_string(), tv.signer_set(), assets.clone());
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let vault = vault_address("full");
+    let state = tv.vault_state_of(&vault).await;
+    let whitelisted: Vec<Asset> = state
+        .whitelisted_assets
+        .iter()
+        .map(|config| config.asset.clone())
+        .collect();
+    assert_eq!(whitelisted, assets);
+    assert_eq!(state.whitelisted_assets[1].decimals, 6);
+    assert_eq!(state.signers, tv.signer_set());
+    let vault_ata = anchor_spl::associated_token::get_associated_token_address(&vault, &mint);
+    assert_eq!(tv.token_balance(&vault_ata).await, 0);
+}
+
+#[tokio::test]
+async fn create_vault_token_accounts_in_one_transaction() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let vault = tv.vault;
+    let first = tv.create_mint(6).await;
+    let second = tv.create_mint(0).await;
+    // An existing account is left as it is.
+    let existing = tv.create_ata(&vault, &first).await;
+    tv.mint_to(&first, &existing, 500).await;
+
+    let ix = tv.create_vault_token_accounts_ix(vec![first, second]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AssetNotWhitelisted);
+
+    tv.whitelist(Asset::SplToken { mint: first }).await;
+    tv.whitelist(Asset::Nft { mint: second }).await;
+    let ix = tv.create_vault_token_accounts_ix(vec![first, first]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateAsset);
+
+    let ix = tv.create_vault_token_accounts_ix(vec![first, second]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.token_balance(&existing).await, 500);
+    let vault_ata = anchor_spl::associated_token::get_associated_token_address(&vault, &second);
+    assert_eq!(tv.token_balance(&vault_ata).await, 0);
+}
@@ -0,0 +1,107 @@
+use solana_sdk::keccak;
+use solana_sdk::pubkey::Pubkey;
+use strike_example::instructions::constant::TICKET_VERSION;
+use strike_example::instructions::{
+    Asset, AssetAmount, CpiAccountMeta, ExecuteTicket, StrategyTicket, Ticket, WithdrawalTicket,
+};
+
+#[test]
+fn domain_suffix_cant_extend_a_separator() {
+    let accounts = vec![CpiAccountMeta {
+        pubkey: Pubkey::new_unique(),
+        is_signer: true,
+        is_writable: false,
+    }];
+    let data = b"execute".to_vec();
+    let execute = ExecuteTicket {
+        version: TICKET_VERSION,
+        request_id: 7,
+        vault: Pubkey::new_unique(),
+        program_id: Pubkey::new_unique(),
+        accounts: accounts.clone(),
+        data: data.clone(),
+        expiry: 1_700_000_000,
+        network_id: 101,
+        not_before: None,
+    };
+    let strategy = StrategyTicket {
+        version: execute.version,
+        request_id: execute.request_id,
+        vault: execute.vault,
+        program_id: execute.program_id,
+        accounts,
+        data_hash: keccak::hash(&data).to_bytes(),
+        expiry: execute.expiry,
+        network_id: execute.network_id,
+        not_before: None,
+    };
+
+    // `Execute` under the suffix "Strategy" would otherwise spell out `ExecuteStrategy`.
+    assert_ne!(execute.hash("Strategy"), strategy.hash(""));
+}
+
+fn withdrawal_ticket() -> WithdrawalTicket {
+    WithdrawalTicket {
+        version: TICKET_VERSION,
+        request_id: 7,
+        vault: Pubkey::new_unique(),
+        recipient: Pubkey::new_unique(),
+        withdrawals: vec![AssetAmount {
+            asset: Asset::Sol,
+            amount: 1_000,
+        }],
+        expiry: 1_700_000_000,
+        network_id: 101,
+        memo: None,
+        treasury: None,
+        not_before: None,
+        token_accounts: None,
+        source_ref: None,
+        balance_bps: None,
+    }
+}
+
+#[test]
+fn not_before_cant_pass_for_a_memo() {
+    let mut signed = withdrawal_ticket();
+    signed.memo = Some("memo".to_string());
+
+    // The memo's length prefix and bytes, read back as an activation time.
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&4u32.to_le_bytes());
+    bytes[4..].copy_from_slice(b"memo");
+    let mut forged = signed.clone();
+    forged.memo = None;
+    forged.not_before = Some(i64::from_le_bytes(bytes));
+
+    assert_ne!(signed.hash(""), forged.hash(""));
+}
+
+#[test]
+fn memo_cant_pass_for_a_source_ref() {
+    let source_ref = [0x61u8; 32];
+    let mut signed = withdrawal_ticket();
+    signed.memo = Some(String::from_utf8(source_ref.to_vec()).unwrap());
+
+    let mut forged = signed.clone();
+    forged.memo = None;
+    forged.source_ref = Some(source_ref);
+
+    assert_ne!(signed.hash(""), forged.hash(""));
+}
+
+#[test]
+fn unset_fields_cant_pass_for_set_ones() {
+    // Signed for 1,000 lamports with a memo.
+    let mut signed = withdrawal_ticket();
+    signed.memo = Some("abéa ".to_string());
+
+    // The memo's length prefix and bytes, read back as an activation time and a 82.89% share
+    // of the vault's balance.
+    let mut forged = signed.clone();
+    forged.memo = None;
+    forged.not_before = Some(-6214014892089802746);
+    forged.balance_bps = Some(8289);
+
+    assert_ne!(signed.hash(""), forged.hash(""));
+}
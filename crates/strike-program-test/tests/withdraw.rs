@@ -0,0 +1,1995 @@
+use anchor_lang::AnchorSerialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::account::{AccountSharedData, ReadableAccount};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use strike_example::instructions::constant::{
+    EXTRA_SIGNATURES, MAX_EXPIRY_GRACE, MAX_EXTRA_NETWORK_IDS, MAX_FEE_BPS, MAX_NONCE_TREE_DEPTH,
+    MIN_TICKET_VERSION, NONCE_GC_CRANKER_BPS, NONCE_GC_DELAY, RELAYER_TX_FEE, TICKET_VERSION,
+};
+use strike_example::instructions::errors::ErrorCode;
+use strike_example::instructions::util::next_processed_root;
+use strike_example::instructions::{
+    Asset, AuthScheme, GatewayTokenState, PriceOracle, Ticket, TicketValidation,
+};
+use strike_program_test::*;
+
+const LAMPORTS: u64 = 1_000_000_000;
+
+async fn funded_vault() -> (TestVault, Keypair) {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    (tv, user)
+}
+
+#[tokio::test]
+async fn withdraw_sol_with_quorum() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    let sigs = tv.sign(&ticket, &[0, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    assert!(tv.nonce_used(&nonce).await);
+}
+
+#[tokio::test]
+async fn validate_ticket_reports_what_withdraw_would_do() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let failure = |code: ErrorCode| Some(u32::from(code) as u64);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let hash = ticket.hash(&tv.vault_state().await.domain_suffix);
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.validate_ticket_ix(ticket.clone(), sigs, vec![]);
+    let validation: TicketValidation = tv.view(ix).await;
+    assert_eq!(validation.ticket_hash, hash);
+    assert_eq!(validation.error, failure(ErrorCode::InsufficientSignatures));
+
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.validate_ticket_ix(ticket.clone(), sigs.clone(), vec![]);
+    let validation: TicketValidation = tv.view(ix).await;
+    assert_eq!(validation.error, None);
+    assert_eq!(validation.valid_signers, 2);
+    assert!(!validation.recipient_blocked);
+    // Nothing moved and the nonce is still free.
+    assert_eq!(tv.lamports(&recipient).await, 0);
+    assert!(
+        !tv.nonce_used(&nonce_address(&tv.vault, ticket.request_id))
+            .await
+    );
+
+    let large = tv
+        .withdrawal_ticket(recipient, vec![sol(50 * LAMPORTS)])
+        .await;
+    let large_sigs = tv.sign(&large, &[0, 1]);
+    let ix = tv.validate_ticket_ix(large, large_sigs, vec![]);
+    let validation: TicketValidation = tv.view(ix).await;
+    assert_eq!(validation.error, failure(ErrorCode::InsufficientFunds));
+
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    let ix = tv.validate_ticket_ix(ticket, sigs, vec![]);
+    let validation: TicketValidation = tv.view(ix).await;
+    assert_eq!(validation.error, failure(ErrorCode::NonceAlreadyUsed));
+}
+
+#[tokio::test]
+async fn withdraw_quorum_sums_signer_weights() {
+    let mut tv = TestVault::new(VaultConfig {
+        weights: vec![3, 1, 1],
+        m_threshold: 3,
+        admin_threshold: 4,
+        ..VaultConfig::default()
+    })
+    .await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    // Two light signers carry 2 of the 3 needed.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    // The heavy signer meets the threshold alone.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn version_1_signers_count_once_after_migrate() {
+    let (mut tv, _) = funded_vault().await;
+    tv.rewrite_as_v1(vec![Asset::Sol]).await;
+    let ix = tv.migrate_ix(&tv.payer(), &[]);
+    tv.process(&[ix], &[]).await.unwrap();
+    let vault = tv.vault_state().await;
+    assert!(vault
+        .signers
+        .iter()
+        .all(|signer| signer.weight == 1 && signer.valid_until.is_none()));
+    let recipient = Keypair::new().pubkey();
+
+    // m_threshold still counts signatures, as it did in version 1.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn signature_budget_counts_signers_not_weight() {
+    let mut tv = TestVault::new(VaultConfig {
+        weights: vec![80, 80, 80],
+        m_threshold: 160,
+        admin_threshold: 240,
+        ..VaultConfig::default()
+    })
+    .await;
+    let user = tv.new_funded_keypair(10 * LAMPORTS).await;
+    tv.whitelist(Asset::Sol).await;
+    tv.deposit_sol(&user, 5 * LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    // Two signers reach 160, so at most 2 + EXTRA_SIGNATURES signatures are recovered.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let mut sigs = tv.sign_all(&ticket);
+    sigs.extend(tv.sign(&ticket, &[0, 1]));
+    assert_eq!(sigs.len(), 3 + EXTRA_SIGNATURES as usize);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TooManySignatures);
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_rejects_signatures_past_the_budget() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    // m_threshold 2 allows 2 + EXTRA_SIGNATURES signatures; the list is refused before any of
+    // them is recovered.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let mut sigs = tv.sign_all(&ticket);
+    sigs.extend(tv.sign(&ticket, &[0, 1, 2]));
+    assert!(sigs.len() > 2 + EXTRA_SIGNATURES as usize);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TooManySignatures);
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_with_secp256k1_precompile_signatures() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let verify_0 = tv.signers[0].secp256k1_ix(&ticket, &tv.domain_suffix, 0);
+
+    // One precompile signature and one recovered in the program make the quorum.
+    let sigs = tv.sign(&ticket, &[1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![instructions_sysvar()]);
+    tv.process(&[verify_0, ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // Both signatures verified by the precompile, none passed to the program.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let verify_0 = tv.signers[0].secp256k1_ix(&ticket, &tv.domain_suffix, 0);
+    let verify_2 = tv.signers[2].secp256k1_ix(&ticket, &tv.domain_suffix, 1);
+    let ix = tv.withdraw_ix(ticket, vec![], vec![instructions_sysvar()]);
+    tv.process(&[verify_0, verify_2, ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+
+    // Signatures over another ticket's preimage don't count.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let other = tv.withdrawal_ticket(recipient, vec![sol(1)]).await;
+    let verify_other = tv.signers[0].secp256k1_ix(&other, &tv.domain_suffix, 0);
+    let sigs = tv.sign(&ticket, &[1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![instructions_sysvar()]);
+    assert_error(
+        tv.process(&[verify_other, ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+}
+
+#[tokio::test]
+async fn withdraw_counts_ed25519_signers() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let custodian = Keypair::new();
+
+    let mut signers = tv.signer_set();
+    signers.push(ed25519_signer(&custodian));
+    let rotate = tv.rotate_ticket(signers, 2, 3).await;
+    let sigs = tv.sign_all(&rotate);
+    let ix = tv.rotate_validators_ix(rotate, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let approval = ed25519_ix(&custodian, &ticket, &tv.domain_suffix);
+    let sigs = tv.sign(&ticket, &[0]);
+
+    // The precompile signature is only read through the instructions sysvar.
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[approval.clone(), ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // A signature over another ticket doesn't count.
+    let other = tv.withdrawal_ticket(recipient, vec![sol(1)]).await;
+    let wrong = ed25519_ix(&custodian, &other, &tv.domain_suffix);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![instructions_sysvar()]);
+    assert_error(
+        tv.process(&[wrong, ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    let ix = tv.withdraw_ix(ticket, sigs, vec![instructions_sysvar()]);
+    tv.process(&[approval, ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_replay_is_rejected() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+
+    assert_failed(tv.process(&[ix], &[]).await);
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_nonce_is_bound_to_the_ticket() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.nonce_account(&nonce).await.unwrap().ticket_hash,
+        ticket.hash(&tv.domain_suffix)
+    );
+
+    // The same ticket under another quorum is a replay.
+    let sigs = tv.sign(&ticket, &[0, 2]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+
+    // Another ticket signed under the consumed request id conflicts with it.
+    let mut other = ticket.clone();
+    other.withdrawals = vec![sol(2 * LAMPORTS)];
+    let sigs = tv.sign(&other, &[0, 1]);
+    let ix = tv.withdraw_ix(other, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::RequestIdConflict);
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_quorum_edge_cases() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+
+    // Fewer signatures than the threshold.
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // The same signer twice is rejected.
+    let sigs = tv.sign(&ticket, &[1, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateSignature);
+
+    // Signatures must be sorted by signer address.
+    let mut sigs = tv.sign(&ticket, &[0, 1]);
+    sigs.reverse();
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UnsortedSignatures);
+
+    // Signatures from keys outside the signer set are ignored.
+    let mut sigs = tv.sign(&ticket, &[0]);
+    sigs.push(EthSigner::from_seed(99).sign(&ticket, &tv.domain_suffix));
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    // Signatures over a different domain suffix don't verify.
+    let sigs = vec![
+        tv.signers[0].sign(&ticket, "other-desk"),
+        tv.signers[1].sign(&ticket, "other-desk"),
+    ];
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    // A tampered amount invalidates every signature.
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let mut tampered = ticket.clone();
+    tampered.withdrawals[0].amount += 1;
+    let ix = tv.withdraw_ix(tampered, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    // Invalid recovery ids are skipped rather than aborting.
+    let mut sigs = tv.sign(&ticket, &[0, 1]);
+    sigs[1].recovery_id = 5;
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    // The high-s encoding of a valid signature doesn't count.
+    let mut sigs = tv.sign(&ticket, &[0, 1]);
+    sigs[1] = high_s(&sigs[1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+}
+
+#[tokio::test]
+async fn strict_signatures_reject_the_first_bad_signature() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv.set_strict_signatures_ticket(true).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_strict_signatures_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.vault_state().await.strict_signatures);
+
+    let has_log = |logs: &[String], expected: &str| logs.iter().any(|log| log.contains(expected));
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+
+    // A bad recovery id fails the ticket even though a quorum could be met without it.
+    let mut sigs = tv.sign_all(&ticket);
+    sigs[1].recovery_id = 5;
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    let logs = tv.simulate_logs(std::slice::from_ref(&ix)).await;
+    assert!(has_log(&logs, "Signature 0 recovered 0x"), "{:?}", logs);
+    assert!(has_log(&logs, "Signature 1 failed recovery"), "{:?}", logs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::SignatureRecoveryFailed,
+    );
+
+    // So does a signature from a key outside the signer set.
+    let outsider = EthSigner::from_seed(99);
+    let mut sigs = tv.sign(&ticket, &[0, 1]);
+    sigs.push(outsider.sign(&ticket, &tv.domain_suffix));
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    let logs = tv.simulate_logs(std::slice::from_ref(&ix)).await;
+    let address: String = outsider
+        .address
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let expected = format!("Signature 2 from 0x{} is not an active signer", address);
+    assert!(has_log(&logs, &expected), "no {:?} in {:?}", expected, logs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UnauthorizedSigner);
+
+    // Valid signatures past the threshold are still checked and logged.
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    let logs = tv.simulate_logs(std::slice::from_ref(&ix)).await;
+    assert!(has_log(&logs, "Signature 2 recovered 0x"), "{:?}", logs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_ticket_field_checks() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.network_id = NETWORK_ID + 1;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidNetwork);
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.vault = Keypair::new().pubkey();
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidVault);
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.version = TICKET_VERSION + 1;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::UnsupportedTicketVersion,
+    );
+
+    // Version 1 hashed optional fields without a presence byte.
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.version = MIN_TICKET_VERSION - 1;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::UnsupportedTicketVersion,
+    );
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let mut ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    ix.accounts[3].pubkey = Keypair::new().pubkey();
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidRecipient);
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), sol(LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::DuplicateAsset);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::NoWithdrawalsProvided,
+    );
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(100 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn withdrawals_leave_the_treasury_reserve() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv.set_treasury_reserve_ticket(4 * LAMPORTS).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_treasury_reserve_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.treasury_reserve, 4 * LAMPORTS);
+
+    // 5 SOL deposited: only what lies above the 4 SOL reserve can be withdrawn.
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(2 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+
+    // Removing the reserve frees the rest.
+    let set = tv.set_treasury_reserve_ticket(0).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_treasury_reserve_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+}
+
+#[tokio::test]
+async fn balance_bps_withdraws_a_share_of_the_balance() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(0)]).await;
+    ticket.balance_bps = Some(10_001);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidBalanceBps);
+
+    // Half of the 5 SOL deposited, so a 3 SOL minimum isn't met.
+    let mut ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(3 * LAMPORTS)])
+        .await;
+    ticket.balance_bps = Some(5_000);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+
+    let mut ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(2 * LAMPORTS)])
+        .await;
+    ticket.balance_bps = Some(5_000);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 5 * LAMPORTS / 2);
+
+    // Withdraw all: the treasury is left with its rent exemption only.
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(0)]).await;
+    ticket.balance_bps = Some(10_000);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 5 * LAMPORTS);
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(1)]).await;
+    ticket.balance_bps = Some(10_000);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn watermark_replay_withdraws_without_nonce_accounts() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv.set_replay_mode_ticket(true, 0).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_replay_mode_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let stale = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_without_nonce_ix(ticket.clone(), sigs.clone(), vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    assert_eq!(tv.lamports(&nonce).await, 0);
+    assert_eq!(
+        tv.vault_state().await.request_id_watermark,
+        ticket.request_id
+    );
+
+    // The same id, or any lower one, is now spent.
+    let ix = tv.withdraw_without_nonce_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RequestIdBelowWatermark,
+    );
+    let stale_sigs = tv.sign(&stale, &[0, 1]);
+    let ix = tv.withdraw_without_nonce_ix(stale.clone(), stale_sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RequestIdBelowWatermark,
+    );
+
+    // Back in nonce mode the watermark still holds, and nonces are required again.
+    let watermark = tv.vault_state().await.request_id_watermark;
+    let set = tv.set_replay_mode_ticket(false, watermark).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_replay_mode_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let ix = tv.withdraw_ix(stale, stale_sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RequestIdBelowWatermark,
+    );
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_without_nonce_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::NonceAccountRequired,
+    );
+}
+
+#[tokio::test]
+async fn withdraw_expired_ticket_is_rejected() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    tv.warp_to_timestamp(ticket.expiry + 1).await;
+
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TicketExpired);
+}
+
+#[tokio::test]
+async fn withdraw_waits_for_not_before() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let not_before = tv.now().await + 600;
+    ticket.not_before = Some(not_before);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TicketNotYetValid);
+
+    tv.warp_to_timestamp(not_before).await;
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_creates_a_receipt_when_passed_one() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let request_id = ticket.request_id;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.withdrawal_receipt(request_id).await.is_none());
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let request_id = ticket.request_id;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_with_receipt_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let receipt = tv.withdrawal_receipt(request_id).await.unwrap();
+    assert_eq!(receipt.vault, tv.vault);
+    assert_eq!(receipt.request_id, request_id);
+    assert_eq!(receipt.recipient, recipient);
+    assert_eq!(receipt.withdrawals.len(), 1);
+    assert!(receipt.withdrawals[0].asset == Asset::Sol);
+    assert_eq!(receipt.withdrawals[0].amount, LAMPORTS);
+}
+
+#[tokio::test]
+async fn set_nonce_tree_rejects_bad_ranges() {
+    let (mut tv, _) = funded_vault().await;
+    let nonce_tree = Keypair::new().pubkey();
+
+    for (base_request_id, max_depth) in [(1_000, 0), (1_000, MAX_NONCE_TREE_DEPTH + 1), (0, 10)] {
+        let ticket = tv
+            .set_nonce_tree_ticket(nonce_tree, base_request_id, max_depth)
+            .await;
+        let sigs = tv.sign_all(&ticket);
+        let ix = tv.set_nonce_tree_ix(ticket, sigs);
+        assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidNonceTree);
+    }
+    assert_eq!(tv.vault_state().await.nonce_tree, Pubkey::default());
+}
+
+#[tokio::test]
+async fn withdraw_records_its_request_id_in_the_nonce_tree() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let nonce_tree = tv.create_nonce_tree(3);
+
+    let ticket = tv.set_nonce_tree_ticket(nonce_tree, 1_000, 3).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_nonce_tree_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let vault = tv.vault_state().await;
+    assert_eq!(vault.nonce_tree, nonce_tree);
+    assert_eq!(vault.request_id_watermark, 1_007);
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.request_id = 1_002;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+
+    // The proof covers every level of the tree.
+    let accounts = tv.nonce_tree_accounts(nonce_tree, 2);
+    let ix = tv.withdraw_without_nonce_ix(ticket.clone(), sigs.clone(), accounts);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceTreeNotFound);
+
+    // Accounts after the proof aren't part of it.
+    let mut accounts = tv.nonce_tree_accounts(nonce_tree, 3);
+    accounts.push(readonly(Keypair::new().pubkey()));
+    let ix = tv.withdraw_without_nonce_ix(ticket, sigs, accounts.clone());
+    tv.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // The leaf is set now, so the request id can't be recorded again.
+    assert_failed(tv.process(&[ix], &[]).await);
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_runs_within_the_expiry_grace() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.set_expiry_grace_ticket(MAX_EXPIRY_GRACE + 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_expiry_grace_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::ExpiryGraceTooLong);
+
+    let ticket = tv.set_expiry_grace_ticket(30).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_expiry_grace_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.expiry_grace, 30);
+
+    let late = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let late_sigs = tv.sign(&late, &[0, 1]);
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    tv.warp_to_timestamp(ticket.expiry + 30).await;
+
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    tv.warp_to_timestamp(late.expiry + 31).await;
+    let ix = tv.withdraw_ix(late, late_sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::TicketExpired);
+}
+
+#[tokio::test]
+async fn withdraw_rejects_tickets_outliving_max_lifetime() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.set_max_ticket_lifetime_ticket(600).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_max_ticket_lifetime_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.max_ticket_lifetime, 600);
+
+    // The default expiry is an hour out, past the cap.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TicketLifetimeTooLong,
+    );
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.expiry = tv.now().await + 600;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_accepts_extra_network_ids() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    for extra_network_ids in [
+        vec![NETWORK_ID],
+        vec![NETWORK_ID + 1, NETWORK_ID + 1],
+        vec![1, 2, 3, 4, 5],
+    ] {
+        let expected = if extra_network_ids.len() > MAX_EXTRA_NETWORK_IDS {
+            ErrorCode::TooManyNetworkIds
+        } else {
+            ErrorCode::DuplicateNetworkId
+        };
+        let ticket = tv.set_extra_network_ids_ticket(extra_network_ids).await;
+        let sigs = tv.sign_all(&ticket);
+        let ix = tv.set_extra_network_ids_ix(ticket, sigs);
+        assert_error(tv.process(&[ix], &[]).await, expected);
+    }
+
+    let ticket = tv.set_extra_network_ids_ticket(vec![NETWORK_ID + 1]).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_extra_network_ids_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.vault_state().await.extra_network_ids,
+        vec![NETWORK_ID + 1]
+    );
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.network_id = NETWORK_ID + 2;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidNetwork);
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.network_id = NETWORK_ID + 1;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_is_allowed_after_asset_removal() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let remove = tv.remove_asset_ticket(Asset::Sol).await;
+    let sigs = tv.sign_all(&remove);
+    let ix = tv.remove_asset_ix(remove, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_spl_to_recipient_ata() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.funded_mint(6, 1_000).await;
+    let recipient = Keypair::new().pubkey();
+    let recipient_ata = tv.create_ata(&recipient, &mint).await;
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![spl(mint, 400)]).await;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let accounts = tv.spl_withdraw_accounts(&recipient, &[mint]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let vault_ata = get_associated_token_address(&tv.vault, &mint);
+    assert_eq!(tv.token_balance(&recipient_ata).await, 400);
+    assert_eq!(tv.token_balance(&vault_ata).await, 600);
+
+    // Redirecting to a non-canonical account of the recipient is rejected.
+    let ticket = tv.withdrawal_ticket(recipient, vec![spl(mint, 1)]).await;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(mint), writable(vault_ata)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![spl(mint, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let accounts = tv.spl_withdraw_accounts(&recipient, &[mint]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn withdraw_spl_to_committed_token_account() {
+    let mut tv = TestVault::new(VaultConfig::default()).await;
+    let mint = tv.funded_mint(6, 1_000).await;
+    let recipient = Keypair::new().pubkey();
+    let recipient_ata = tv.create_ata(&recipient, &mint).await;
+    let token_account = tv.create_token_account(&recipient, &mint).await;
+    let vault_ata = get_associated_token_address(&tv.vault, &mint);
+    let accounts = vec![
+        readonly(mint),
+        writable(vault_ata),
+        writable(recipient_ata),
+        writable(token_account),
+    ];
+
+    // The signers approve the exact destination, so the recipient's ATA can't stand in for it.
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![spl(mint, 400)]).await;
+    ticket.token_accounts = Some(vec![token_account]);
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts[..3].to_vec());
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TokenAccountNotFound,
+    );
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![spl(mint, 400)]).await;
+    ticket.token_accounts = Some(vec![token_account]);
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts.clone());
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.token_balance(&token_account).await, 400);
+    assert_eq!(tv.token_balance(&recipient_ata).await, 0);
+
+    // One committed account per withdrawal line.
+    let mut ticket = tv
+        .withdrawal_ticket(recipient, vec![spl(mint, 1), sol(1)])
+        .await;
+    ticket.token_accounts = Some(vec![token_account]);
+    let sigs = tv.sign(&ticket, &[1, 2]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InvalidTokenAccounts,
+    );
+}
+
+#[tokio::test]
+async fn withdraw_with_memo() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.memo = Some("deposit-ref-4821".to_string());
+    let sigs = tv.sign(&ticket, &[0, 1]);
+
+    // The memo program must be passed when the ticket carries a memo.
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::MemoProgramNotFound);
+
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![readonly(anchor_spl::memo::ID)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // The memo is part of the signed hash.
+    let mut tampered = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    tampered.memo = Some("original".to_string());
+    let sigs = tv.sign(&tampered, &[0, 1]);
+    tampered.memo = Some("tampered".to_string());
+    let ix = tv.withdraw_ix(tampered, sigs, vec![readonly(anchor_spl::memo::ID)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    let mut too_long = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    too_long.memo = Some("x".repeat(257));
+    let sigs = tv.sign(&too_long, &[0, 1]);
+    let ix = tv.withdraw_ix(too_long, sigs, vec![readonly(anchor_spl::memo::ID)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::MemoTooLong);
+}
+
+#[tokio::test]
+async fn withdraw_with_source_ref() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    // The source ref is part of the signed hash.
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let hash = ticket.hash(&tv.domain_suffix);
+    ticket.source_ref = Some([7; 32]);
+    assert_ne!(ticket.hash(&tv.domain_suffix), hash);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+
+    let mut tampered = ticket.clone();
+    tampered.source_ref = Some([8; 32]);
+    let ix = tv.withdraw_ix(tampered, sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientValidSignatures,
+    );
+
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_is_capped_by_recipient_allowance() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv
+        .set_allowance_ticket(recipient, Asset::Sol, 2 * LAMPORTS)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_allowance_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(3 * LAMPORTS / 2)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AllowanceExceeded);
+
+    let allowance = tv.allowance(&recipient, &Asset::Sol).await.unwrap();
+    assert_eq!(allowance.consumed, 3 * LAMPORTS / 2);
+
+    // Other recipients have no allowance and stay uncapped.
+    let other = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(other, vec![sol(3 * LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn withdraw_extends_processed_root() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let before = tv.vault_state().await;
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let request_id = ticket.request_id;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(std::slice::from_ref(&ix), &[]).await.unwrap();
+
+    let after = tv.vault_state().await;
+    assert_eq!(after.processed_count, before.processed_count + 1);
+    assert_eq!(
+        after.processed_root,
+        next_processed_root(&before.processed_root, b"nonce", request_id)
+    );
+
+    // A rejected replay leaves the chain untouched.
+    assert_failed(tv.process(&[ix], &[]).await);
+    let replayed = tv.vault_state().await;
+    assert_eq!(replayed.processed_root, after.processed_root);
+    assert_eq!(replayed.processed_count, after.processed_count);
+}
+
+#[tokio::test]
+async fn withdraw_routes_protocol_fee_to_fee_treasury() {
+    let (mut tv, _) = funded_vault().await;
+    let mint = tv.funded_mint(6, 1_000_000).await;
+    let recipient = Keypair::new().pubkey();
+    let recipient_ata = tv.create_ata(&recipient, &mint).await;
+    let fee_treasury = fee_treasury_address(&tv.vault);
+    let fee_ata = tv.create_ata(&fee_treasury, &mint).await;
+
+    let ticket = tv.set_fee_ticket(MAX_FEE_BPS + 1).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidFee);
+
+    // 1%
+    let ticket = tv.set_fee_ticket(100).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let fee_treasury_before = tv.lamports(&fee_treasury).await;
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), spl(mint, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let mut accounts = tv.spl_withdraw_accounts(&recipient, &[mint]);
+    accounts.push(writable(fee_ata));
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS - LAMPORTS / 100);
+    assert_eq!(
+        tv.lamports(&fee_treasury).await - fee_treasury_before,
+        LAMPORTS / 100
+    );
+    assert_eq!(tv.token_balance(&recipient_ata).await, 9_900);
+    assert_eq!(tv.token_balance(&fee_ata).await, 100);
+}
+
+#[tokio::test]
+async fn withdraw_refunds_relayer_up_to_max_rebate() {
+    let (mut tv, _) = funded_vault().await;
+    let treasury = tv.treasury;
+    let payer = tv.payer();
+
+    let ticket = tv.set_relayer_rebate_ticket(LAMPORTS).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_relayer_rebate_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // Nonce rent and the transaction fee come back in full.
+    let payer_before = tv.lamports(&payer).await;
+    let treasury_before = tv.lamports(&treasury).await;
+    let recipient = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(tv.lamports(&payer).await, payer_before);
+    let rebate = treasury_before - tv.lamports(&treasury).await - LAMPORTS;
+    assert!(rebate > RELAYER_TX_FEE);
+
+    // Capped below the relayer's cost.
+    let ticket = tv.set_relayer_rebate_ticket(1_000).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_relayer_rebate_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let treasury_before = tv.lamports(&treasury).await;
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(
+        treasury_before - tv.lamports(&treasury).await,
+        LAMPORTS + 1_000
+    );
+}
+
+#[tokio::test]
+async fn allowlist_mode_only_pays_listed_recipients() {
+    let (mut tv, _) = funded_vault().await;
+    let listed = Keypair::new().pubkey();
+    let unlisted = Keypair::new().pubkey();
+    let allowlist = recipient_allowlist_address(&tv.vault);
+
+    let ticket = tv.add_recipient_ticket(listed).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.add_recipient_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let ticket = tv.set_recipient_allowlist_ticket(true).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_recipient_allowlist_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.recipient_allowlist().await.unwrap().recipients,
+        vec![listed]
+    );
+
+    let ticket = tv.withdrawal_ticket(unlisted, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(allowlist)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RecipientNotAllowlisted,
+    );
+
+    let ticket = tv.withdrawal_ticket(listed, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RecipientAllowlistNotFound,
+    );
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(allowlist)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&listed).await, LAMPORTS);
+
+    // Removed recipients are refused again.
+    let ticket = tv.remove_recipient_ticket(listed).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.remove_recipient_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(listed, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(allowlist)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::RecipientNotAllowlisted,
+    );
+}
+
+#[tokio::test]
+async fn blocked_recipient_ticket_is_spent_without_paying_out() {
+    let (mut tv, _) = funded_vault().await;
+    let blocked = Keypair::new().pubkey();
+    let blocklist = blocklist_address(&tv.vault);
+    let treasury = tv.treasury;
+
+    let ticket = tv.block_recipient_ticket(blocked).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.block_recipient_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.vault_state().await.has_blocklist);
+    assert_eq!(tv.blocklist().await.unwrap().recipients, vec![blocked]);
+
+    // Once the blocklist exists, withdrawals must pass it.
+    let ticket = tv.withdrawal_ticket(blocked, vec![sol(LAMPORTS)]).await;
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::BlocklistNotFound);
+
+    let treasury_before = tv.lamports(&treasury).await;
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(blocklist)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&blocked).await, 0);
+    assert_eq!(tv.lamports(&treasury).await, treasury_before);
+    assert!(tv.nonce_used(&nonce).await);
+
+    // Unblocked recipients are paid again.
+    let ticket = tv.unblock_recipient_ticket(blocked).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.unblock_recipient_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(blocked, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(blocklist)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&blocked).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdrawals_below_the_asset_minimum_are_rejected() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv
+        .set_min_withdrawal_ticket(Asset::Sol, LAMPORTS / 10)
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_min_withdrawal_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        tv.vault_state().await.min_withdrawal(&Asset::Sol),
+        LAMPORTS / 10
+    );
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS / 10 - 1)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::BelowMinWithdrawal);
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS / 10)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS / 10);
+
+    // Only whitelisted assets take a minimum.
+    let ticket = tv
+        .set_min_withdrawal_ticket(
+            Asset::SplToken {
+                mint: Keypair::new().pubkey(),
+            },
+            1,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_min_withdrawal_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::AssetNotWhitelisted);
+}
+
+const SOL_USD_FEED: [u8; 32] = [0x50; 32];
+const USDC_USD_FEED: [u8; 32] = [0x55; 32];
+
+/// Cap SOL withdrawal lines at $300, priced at $150 from the Pyth feed.
+async fn usd_capped_vault() -> (TestVault, Keypair) {
+    let (mut tv, user) = funded_vault().await;
+    let ticket = tv
+        .set_usd_cap_ticket(
+            Asset::Sol,
+            PriceOracle::Pyth {
+                feed_id: SOL_USD_FEED,
+            },
+            300_000_000,
+            60,
+            100,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_usd_cap_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.vault_state().await.has_usd_caps);
+    assert_eq!(tv.usd_cap(&Asset::Sol).await.unwrap().decimals, 9);
+    (tv, user)
+}
+
+#[tokio::test]
+async fn usd_caps_bound_withdrawal_lines_by_oracle_value() {
+    let (mut tv, _) = usd_capped_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let usd_cap = readonly(usd_cap_address(&tv.vault, &Asset::Sol));
+    let now = tv.now().await;
+    let price = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 1_000_000, -8, now);
+
+    // Capped vaults need the cap of every withdrawn asset.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(price)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::UsdCapAccountNotFound,
+    );
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(3 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(price)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdCapExceeded);
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(2 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(price)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+
+    // Prices older than max_age or less certain than max_conf_bps aren't used.
+    let stale = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 1_000_000, -8, now - 61);
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(stale)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::StalePrice);
+
+    let uncertain = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 300_000_000, -8, now);
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap, readonly(uncertain)]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::PriceConfidenceTooWide,
+    );
+}
+
+#[tokio::test]
+async fn ticket_usd_cap_needs_every_asset_priced() {
+    let (mut tv, _) = usd_capped_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let usd_cap = readonly(usd_cap_address(&tv.vault, &Asset::Sol));
+    let now = tv.now().await;
+    let price = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 1_000_000, -8, now);
+
+    let ticket = tv.set_max_ticket_usd_ticket(100_000_000).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_max_ticket_usd_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.max_ticket_usd, 100_000_000);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(price)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdCapExceeded);
+
+    // An asset without a USD cap can't be counted against the ticket cap.
+    let mint = tv.create_mint(6).await;
+    tv.whitelist(Asset::SplToken { mint }).await;
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS / 2), spl(mint, 1)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let unpriced = readonly(usd_cap_address(&tv.vault, &Asset::SplToken { mint }));
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap, unpriced, readonly(price)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdPriceNotFound);
+}
+
+#[tokio::test]
+async fn withdraw_pays_protocol_fee_in_the_fee_mint() {
+    let (mut tv, _) = usd_capped_vault().await;
+    let usdc = tv.funded_mint(6, 1_000_000_000).await;
+    let usdc_asset = Asset::SplToken { mint: usdc };
+    let recipient = Keypair::new().pubkey();
+    let recipient_ata = tv.create_ata(&recipient, &usdc).await;
+    let fee_treasury = fee_treasury_address(&tv.vault);
+    let fee_ata = tv.create_ata(&fee_treasury, &usdc).await;
+    let payer = tv.payer();
+    let payer_ata = tv.create_ata(&payer, &usdc).await;
+    let vault_ata = get_associated_token_address(&tv.vault, &usdc);
+    let treasury = tv.treasury;
+
+    let ticket = tv
+        .set_usd_cap_ticket(
+            usdc_asset.clone(),
+            PriceOracle::Pyth {
+                feed_id: USDC_USD_FEED,
+            },
+            0,
+            60,
+            100,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_usd_cap_ix(ticket, sigs, vec![readonly(usdc)]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // Only a whitelisted mint can carry fees.
+    let mut ticket = tv.set_fee_ticket(100).await;
+    ticket.fee_mint = tv.create_mint(6).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidFeeMint);
+
+    // 1%, paid in USDC.
+    let mut ticket = tv.set_fee_ticket(100).await;
+    ticket.fee_mint = usdc;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_fee_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.fee_mint, usdc);
+
+    let now = tv.now().await;
+    let sol_price = tv.post_pyth_price(SOL_USD_FEED, 15_000_000_000, 1_000_000, -8, now);
+    let usdc_price = tv.post_pyth_price(USDC_USD_FEED, 100_000_000, 10_000, -8, now);
+    let mut accounts = tv.spl_withdraw_accounts(&recipient, &[usdc]);
+    accounts.extend([
+        writable(fee_ata),
+        writable(payer_ata),
+        readonly(usd_cap_address(&tv.vault, &Asset::Sol)),
+        readonly(usd_cap_address(&tv.vault, &usdc_asset)),
+        readonly(sol_price),
+    ]);
+
+    // The fee mint must be priced too.
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), spl(usdc, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts.clone());
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdPriceNotFound);
+
+    accounts.push(readonly(usdc_price));
+
+    // The payer covers the fee, not the vault.
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), spl(usdc, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts.clone());
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InsufficientFunds);
+
+    tv.mint_to(&usdc, &payer_ata, 2_000_000).await;
+    let treasury_before = tv.lamports(&treasury).await;
+    let fee_treasury_before = tv.lamports(&fee_treasury).await;
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(LAMPORTS), spl(usdc, 10_000)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // The SOL line pays out whole and the payer sends the fee treasury the $1.50 worth of USDC
+    // its 0.01 SOL fee is worth. The USDC line pays its fee in kind.
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    assert_eq!(treasury_before - tv.lamports(&treasury).await, LAMPORTS);
+    assert_eq!(tv.lamports(&fee_treasury).await, fee_treasury_before);
+    assert_eq!(tv.token_balance(&payer_ata).await, 500_000);
+    assert_eq!(tv.token_balance(&recipient_ata).await, 9_900);
+    assert_eq!(tv.token_balance(&fee_ata).await, 1_500_000 + 100);
+    assert_eq!(tv.token_balance(&vault_ata).await, 1_000_000_000 - 10_000);
+}
+
+#[tokio::test]
+async fn usd_caps_read_chainlink_feeds() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let usd_cap = readonly(usd_cap_address(&tv.vault, &Asset::Sol));
+    let now = tv.now().await;
+    // $150 with 8 decimals.
+    let feed = tv.post_chainlink_round(15_000_000_000, 8, now as u32);
+
+    let ticket = tv
+        .set_usd_cap_ticket(
+            Asset::Sol,
+            PriceOracle::Chainlink { feed },
+            300_000_000,
+            60,
+            0,
+        )
+        .await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_usd_cap_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(3 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(feed)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdCapExceeded);
+
+    let ticket = tv
+        .withdrawal_ticket(recipient, vec![sol(2 * LAMPORTS)])
+        .await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone(), readonly(feed)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+
+    // The feed must be passed and its latest round fresh.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap.clone()]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::UsdPriceNotFound);
+
+    tv.warp_to_timestamp(now + 61).await;
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![usd_cap, readonly(feed)]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::StalePrice);
+}
+
+#[tokio::test]
+async fn withdraw_from_named_treasury() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let cold = named_treasury_address(&tv.vault, "cold");
+
+    let ticket = tv.set_treasury_ticket("cold", 3).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.set_treasury_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    tv.fund(&cold, 2 * LAMPORTS).await;
+    let treasury = tv.treasury;
+    let treasury_before = tv.lamports(&treasury).await;
+
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.treasury = Some("cold".to_string());
+
+    // The vault's M isn't enough for the cold treasury.
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    assert_eq!(tv.lamports(&treasury).await, treasury_before);
+
+    // Named treasuries only pay out SOL.
+    let mint = tv.create_mint(6).await;
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![spl(mint, 1)]).await;
+    ticket.treasury = Some("cold".to_string());
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::TreasuryAssetNotSupported,
+    );
+
+    // Unknown treasuries aren't found.
+    let mut ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    ticket.treasury = Some("hot".to_string());
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::NamedTreasuryNotFound,
+    );
+}
+
+#[tokio::test]
+async fn guardian_veto_blocks_withdrawal() {
+    let (mut tv, _) = funded_vault().await;
+    let guardian = tv.new_funded_keypair(LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let withdraw = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+
+    // No guardian is set yet.
+    let veto = tv.veto_withdrawal_ix(&guardian.pubkey(), ticket.request_id);
+    assert_error(
+        tv.process(std::slice::from_ref(&veto), &[&guardian]).await,
+        ErrorCode::NotGuardian,
+    );
+
+    let set = tv.set_guardian_ticket(guardian.pubkey()).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_guardian_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.guardian, guardian.pubkey());
+
+    let other = tv.new_funded_keypair(LAMPORTS).await;
+    let ix = tv.veto_withdrawal_ix(&other.pubkey(), ticket.request_id);
+    assert_error(tv.process(&[ix], &[&other]).await, ErrorCode::NotGuardian);
+
+    tv.process(&[veto], &[&guardian]).await.unwrap();
+    assert!(tv.nonce_used(&nonce).await);
+
+    assert_failed(tv.process(&[withdraw], &[]).await);
+    assert_eq!(tv.lamports(&recipient).await, 0);
+}
+
+#[tokio::test]
+async fn guardian_cannot_veto_under_watermark_replay() {
+    let (mut tv, _) = funded_vault().await;
+    let guardian = tv.new_funded_keypair(LAMPORTS).await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv.set_guardian_ticket(guardian.pubkey()).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_guardian_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let set = tv.set_replay_mode_ticket(true, 0).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_replay_mode_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // A veto of the highest id would otherwise raise the watermark past every ticket.
+    let veto = tv.veto_withdrawal_ix(&guardian.pubkey(), u64::MAX);
+    assert_error(
+        tv.process(&[veto], &[&guardian]).await,
+        ErrorCode::VetoUnderWatermarkReplay,
+    );
+    assert_eq!(tv.vault_state().await.request_id_watermark, 0);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_without_nonce_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn expired_nonces_are_collected_into_the_treasury() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let request_id = ticket.request_id;
+    let expiry = ticket.expiry;
+    let nonce = nonce_address(&tv.vault, request_id);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let withdraw = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(std::slice::from_ref(&withdraw), &[])
+        .await
+        .unwrap();
+
+    let ix = tv.close_expired_nonce_ix(request_id, false);
+    tv.warp_to_timestamp(expiry + NONCE_GC_DELAY).await;
+    assert_error(
+        tv.process(std::slice::from_ref(&ix), &[]).await,
+        ErrorCode::NonceNotCollectible,
+    );
+
+    tv.warp_to_timestamp(expiry + NONCE_GC_DELAY + 1).await;
+    let treasury = tv.treasury;
+    let rent = tv.lamports(&nonce).await;
+    let balance = tv.lamports(&treasury).await;
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&nonce).await, 0);
+    assert_eq!(
+        tv.lamports(&treasury).await,
+        balance + rent - rent * NONCE_GC_CRANKER_BPS as u64 / 10_000
+    );
+
+    // The collected ticket stays expired.
+    assert_failed(tv.process(&[withdraw], &[]).await);
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn schedule_pays_each_period_once_due() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let ticket = tv
+        .create_schedule_ticket(recipient, Asset::Sol, LAMPORTS / 10, 3600, 2)
+        .await;
+    let request_id = ticket.request_id;
+    let first = ticket.first_execution;
+    let sigs = tv.sign(&ticket, &[0]);
+    let ix = tv.create_schedule_ix(ticket.clone(), sigs);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.create_schedule_ix(ticket, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert!(tv.nonce_used(&nonce_address(&tv.vault, request_id)).await);
+
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS / 10);
+
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::ScheduleNotDue);
+
+    tv.warp_to_timestamp(first + 3600).await;
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS / 5);
+    assert_eq!(tv.schedule(request_id).await.unwrap().remaining, 0);
+
+    tv.warp_to_timestamp(first + 7200).await;
+    let ix = tv.execute_schedule_ix(request_id, recipient, &Asset::Sol, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::ScheduleCompleted);
+}
+
+#[tokio::test]
+async fn withdraw_notifies_the_hook_program() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv.set_hook_program_ticket(TEST_HOOK_PROGRAM_ID).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_hook_program_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::HookProgramNotFound);
+
+    let accounts = tv.hook_accounts();
+    let ix = tv.withdraw_ix(ticket, sigs, accounts);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // Clearing the hook drops the extra accounts again.
+    let set = tv.set_hook_program_ticket(Pubkey::default()).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_hook_program_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, 2 * LAMPORTS);
+}
+
+#[tokio::test]
+async fn withdraw_with_vaa_from_the_trusted_emitter() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let payload = ticket.try_to_vec().unwrap();
+    let posted_vaa = tv.post_vaa(TEST_EMITTER_CHAIN, TEST_EMITTER_ADDRESS, payload.clone());
+
+    // No emitter is trusted yet.
+    let ix = tv.withdraw_with_vaa_ix(&ticket, posted_vaa, vec![]);
+    assert_failed(tv.process(&[ix], &[]).await);
+
+    let set = tv
+        .set_wormhole_emitter_ticket(TEST_EMITTER_CHAIN, TEST_EMITTER_ADDRESS)
+        .await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_wormhole_emitter_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let other_emitter = tv.post_vaa(TEST_EMITTER_CHAIN, [0xaa; 32], payload);
+    let ix = tv.withdraw_with_vaa_ix(&ticket, other_emitter, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidVaa);
+
+    // The same bytes outside the core bridge's ownership prove nothing.
+    let posted = tv
+        .context
+        .banks_client
+        .get_account(posted_vaa)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut forged =
+        AccountSharedData::new(posted.lamports, posted.data().len(), &Pubkey::new_unique());
+    forged.set_data_from_slice(posted.data());
+    let forged_vaa = Pubkey::new_unique();
+    tv.context.set_account(&forged_vaa, &forged);
+    let ix = tv.withdraw_with_vaa_ix(&ticket, forged_vaa, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidVaa);
+
+    let garbage = tv.post_vaa(TEST_EMITTER_CHAIN, TEST_EMITTER_ADDRESS, vec![1, 2, 3]);
+    let ix = tv.withdraw_with_vaa_ix(&ticket, garbage, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidVaaPayload);
+
+    let ix = tv.withdraw_with_vaa_ix(&ticket, posted_vaa, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+    let nonce = nonce_address(&tv.vault, ticket.request_id);
+    assert_eq!(
+        tv.nonce_account(&nonce).await.unwrap().ticket_hash,
+        ticket.hash(&tv.domain_suffix)
+    );
+
+    // The request id is spent for the VAA and the signature path alike.
+    let ix = tv.withdraw_with_vaa_ix(&ticket, posted_vaa, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::NonceAlreadyUsed);
+}
+
+#[tokio::test]
+async fn ed25519_scheme_ignores_secp256k1_signatures() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let custodians = [Keypair::new(), Keypair::new()];
+
+    let mut signers = tv.signer_set();
+    signers.extend(custodians.iter().map(ed25519_signer));
+    let rotate = tv.rotate_ticket(signers, 2, 3).await;
+    let sigs = tv.sign_all(&rotate);
+    let ix = tv.rotate_validators_ix(rotate, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    let set = tv.set_auth_scheme_ticket(AuthScheme::Ed25519).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_auth_scheme_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.auth_scheme, AuthScheme::Ed25519);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![instructions_sysvar()]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    // One custodian and two recovered signatures still fall short.
+    let approval = ed25519_ix(&custodians[0], &ticket, &tv.domain_suffix);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![instructions_sysvar()]);
+    assert_error(
+        tv.process(&[approval.clone(), ix], &[]).await,
+        ErrorCode::InsufficientSignatures,
+    );
+
+    let second = ed25519_ix(&custodians[1], &ticket, &tv.domain_suffix);
+    let ix = tv.withdraw_ix(ticket, vec![], vec![instructions_sysvar()]);
+    tv.process(&[approval, second, ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+}
+
+#[tokio::test]
+async fn attestor_scheme_replaces_the_signer_set() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+
+    let set = tv
+        .set_auth_scheme_ticket(AuthScheme::Attestor {
+            program: strike_example::ID,
+        })
+        .await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_auth_scheme_ix(set, sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::InvalidAttestor);
+
+    let set = tv
+        .set_auth_scheme_ticket(AuthScheme::Attestor {
+            program: TEST_ATTESTOR_ID,
+        })
+        .await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_auth_scheme_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+
+    // A full quorum of signatures no longer authorizes anything.
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign_all(&ticket);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs, vec![]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::MissingAttestation);
+
+    // Lamports sent to the attestation address aren't an attestation.
+    let attestation = tv.attest(&ticket);
+    tv.context.set_account(
+        &attestation.pubkey,
+        &AccountSharedData::new(LAMPORTS, 0, &solana_sdk::system_program::ID),
+    );
+    let ix = tv.withdraw_ix(ticket.clone(), vec![], vec![attestation.clone()]);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::MissingAttestation);
+
+    let attestation = tv.attest(&ticket);
+    let ix = tv.withdraw_ix(ticket, vec![], vec![attestation]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // Leaving the attestor takes an attestation too.
+    let set = tv.set_auth_scheme_ticket(AuthScheme::Secp256k1).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_auth_scheme_ix(set.clone(), sigs);
+    assert_error(tv.process(&[ix], &[]).await, ErrorCode::MissingAttestation);
+    let attestation = tv.attest(&set);
+    let mut ix = tv.set_auth_scheme_ix(set, vec![]);
+    ix.accounts.push(attestation);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.auth_scheme, AuthScheme::Secp256k1);
+}
+
+#[tokio::test]
+async fn withdraw_needs_an_identity_pass_once_a_network_is_set() {
+    let (mut tv, _) = funded_vault().await;
+    let recipient = Keypair::new().pubkey();
+    let network = Pubkey::new_unique();
+
+    let set = tv.set_identity_network_ticket(network).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_identity_network_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.vault_state().await.identity_network, network);
+
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![]);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::IdentityPassRequired,
+    );
+
+    // A pass for someone else or from another network doesn't count.
+    let other_wallet = tv.post_identity_pass(
+        Pubkey::new_unique(),
+        network,
+        GatewayTokenState::Active,
+        None,
+    );
+    let other_network = tv.post_identity_pass(
+        recipient,
+        Pubkey::new_unique(),
+        GatewayTokenState::Active,
+        None,
+    );
+    let accounts = vec![readonly(other_wallet), readonly(other_network)];
+    let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), accounts);
+    assert_error(
+        tv.process(&[ix], &[]).await,
+        ErrorCode::IdentityPassRequired,
+    );
+
+    let now = tv.now().await;
+    for (state, expire_time) in [
+        (GatewayTokenState::Frozen, None),
+        (GatewayTokenState::Revoked, None),
+        (GatewayTokenState::Active, Some(now)),
+    ] {
+        let pass = tv.post_identity_pass(recipient, network, state, expire_time);
+        let ix = tv.withdraw_ix(ticket.clone(), sigs.clone(), vec![readonly(pass)]);
+        assert_error(tv.process(&[ix], &[]).await, ErrorCode::IdentityPassInvalid);
+    }
+
+    let pass = tv.post_identity_pass(
+        recipient,
+        network,
+        GatewayTokenState::Active,
+        Some(now + 3600),
+    );
+    let ix = tv.withdraw_ix(ticket, sigs, vec![readonly(pass)]);
+    tv.process(&[ix], &[]).await.unwrap();
+    assert_eq!(tv.lamports(&recipient).await, LAMPORTS);
+
+    // The default network turns the check off.
+    let set = tv.set_identity_network_ticket(Pubkey::default()).await;
+    let sigs = tv.sign_all(&set);
+    let ix = tv.set_identity_network_ix(set, sigs);
+    tv.process(&[ix], &[]).await.unwrap();
+    let ticket = tv.withdrawal_ticket(recipient, vec![sol(LAMPORTS)]).await;
+    let sigs = tv.sign(&ticket, &[0, 1]);
+    let ix = tv.withdraw_ix(ticket, sigs, vec![]);
+    tv.process(&[ix], &[]).await.unwrap();
+}
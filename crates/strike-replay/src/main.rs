@@ -0,0 +1,1703 @@
+//! Replays every historical transaction of a vault through the program's own models and
+//! reports where the cluster's recorded effects diverge from what the program should have done.
+//!
+//! Usage: strike-replay <RPC_URL> <VAULT_ADDRESS>
+//!
+//! Only a vault's own instructions are replayed (the vault is their first account), so a
+//! sub-vault, whose history starts with its parent's `create_sub_vault`, can't be replayed yet.
+
+mod model;
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::sysvar;
+use solana_sdk::{ed25519_program, secp256k1_program};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiTransactionEncoding, UiTransactionStatusMeta};
+use strike_example::instruction as ix;
+use strike_example::instructions::constant::{SECP256R1_PROGRAM_ID, TREASURY_SEED};
+use strike_example::instructions::util::{
+    is_supported_ticket_version, parse_ed25519_instruction, parse_secp256k1_instruction,
+    parse_secp256r1_instruction,
+};
+use strike_example::instructions::{
+    AssetAmount, PostedVaaData, PrecompileApproval, Vault, WithdrawalTicket,
+};
+
+use model::{Expected, TicketFields, VaultModel};
+
+/// RPC page size for `getSignaturesForAddress`.
+const SIGNATURE_PAGE: usize = 1000;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: strike-replay <RPC_URL> <VAULT_ADDRESS>");
+        return ExitCode::from(2);
+    }
+
+    let vault = match Pubkey::from_str(&args[2]) {
+        Ok(vault) => vault,
+        Err(e) => {
+            eprintln!("invalid vault address {}: {}", args[2], e);
+            return ExitCode::from(2);
+        }
+    };
+    let client = RpcClient::new_with_commitment(args[1].clone(), CommitmentConfig::finalized());
+
+    match replay(&client, &vault) {
+        Ok(divergences) if divergences.is_empty() => {
+            println!("OK: no divergence found");
+            ExitCode::SUCCESS
+        }
+        Ok(divergences) => {
+            for divergence in divergences.iter() {
+                println!("DIVERGENCE {}", divergence);
+            }
+            println!("{} divergence(s) found", divergences.len());
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("replay failed: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn replay(client: &RpcClient, vault: &Pubkey) -> Result<Vec<String>, String> {
+    let (treasury, _) =
+        Pubkey::find_program_address(&[TREASURY_SEED, vault.as_ref()], &strike_example::ID);
+
+    let signatures = fetch_signatures(client, vault)?;
+    println!(
+        "replaying {} transaction(s) for vault {}",
+        signatures.len(),
+        vault
+    );
+
+    let mut model: Option<VaultModel> = None;
+    let mut divergences = Vec::new();
+
+    for signature in signatures.iter() {
+        let tx = client
+            .get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::finalized()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .map_err(|e| format!("get transaction {}: {}", signature, e))?;
+
+        let Some(meta) = tx.transaction.meta else {
+            divergences.push(format!("{}: transaction has no status meta", signature));
+            continue;
+        };
+        // Failed transactions have no effect, so they cannot diverge.
+        if meta.err.is_some() {
+            continue;
+        }
+        let Some(versioned) = tx.transaction.transaction.decode() else {
+            return Err(format!("{}: cannot decode transaction", signature));
+        };
+
+        let account_keys = full_account_keys(versioned.message.static_account_keys(), &meta)?;
+        let mut expected = Expected::default();
+
+        // Approvals paired with the precompile that made them.
+        let mut precompile_sigs: Vec<(PrecompileApproval, Pubkey)> = Vec::new();
+        for (index, instruction) in versioned.message.instructions().iter().enumerate() {
+            let program_id = account_keys[instruction.program_id_index as usize];
+            let approvals = if program_id == ed25519_program::ID {
+                parse_ed25519_instruction(&instruction.data)
+            } else if program_id == secp256k1_program::ID {
+                parse_secp256k1_instruction(&instruction.data, index as u8)
+            } else if program_id == SECP256R1_PROGRAM_ID {
+                parse_secp256r1_instruction(&instruction.data)
+            } else {
+                continue;
+            };
+            precompile_sigs.extend(approvals.into_iter().map(|approval| (approval, program_id)));
+        }
+
+        // Inner invocations of the program are only `emit_cpi!` event records, so just the
+        // top-level instructions are replayed.
+        for instruction in versioned.message.instructions() {
+            let program_id = account_keys[instruction.program_id_index as usize];
+            if program_id != strike_example::ID {
+                continue;
+            }
+            let accounts: Vec<Pubkey> = instruction
+                .accounts
+                .iter()
+                .map(|index| account_keys[*index as usize])
+                .collect();
+            if accounts.first() != Some(vault) {
+                continue;
+            }
+            // The program only reads precompile signatures through the instructions sysvar, and
+            // only from the precompiles the vault's scheme accepts.
+            if let Some(model) = model.as_mut() {
+                model.precompile_sigs = if accounts.contains(&sysvar::instructions::ID) {
+                    precompile_sigs
+                        .iter()
+                        .filter(|(_, program_id)| model.auth_scheme.accepts_precompile(program_id))
+                        .map(|(approval, _)| *approval)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+            }
+
+            if let Err(reason) = apply(
+                client,
+                &mut model,
+                vault,
+                &accounts,
+                &instruction.data,
+                tx.block_time,
+                &mut expected,
+            ) {
+                divergences.push(format!("{}: {}", signature, reason));
+            }
+        }
+
+        check_balances(
+            signature,
+            &treasury,
+            vault,
+            &account_keys,
+            &meta,
+            &expected,
+            &mut divergences,
+        );
+    }
+
+    let Some(model) = model else {
+        divergences.push("initialize not found in the vault history".to_string());
+        return Ok(divergences);
+    };
+
+    let account = client
+        .get_account(vault)
+        .map_err(|e| format!("get vault account: {}", e))?;
+    let on_chain = Vault::try_deserialize(&mut account.data.as_slice())
+        .map_err(|e| format!("deserialize vault account: {}", e))?;
+    for divergence in model.diff(&on_chain) {
+        divergences.push(format!("final state: {}", divergence));
+    }
+
+    Ok(divergences)
+}
+
+/// All signatures touching the vault, oldest first.
+fn fetch_signatures(client: &RpcClient, vault: &Pubkey) -> Result<Vec<Signature>, String> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = client
+            .get_signatures_for_address_with_config(
+                vault,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(CommitmentConfig::finalized()),
+                },
+            )
+            .map_err(|e| format!("get signatures: {}", e))?;
+
+        let page_len = page.len();
+        for status in page {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| format!("parse signature {}: {}", status.signature, e))?;
+            signatures.push(signature);
+        }
+        if page_len < SIGNATURE_PAGE {
+            break;
+        }
+        before = signatures.last().copied();
+    }
+
+    signatures.reverse();
+    Ok(signatures)
+}
+
+/// Static keys followed by the addresses loaded from lookup tables, in message index order.
+fn full_account_keys(
+    static_keys: &[Pubkey],
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<Pubkey>, String> {
+    let mut keys = static_keys.to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            keys.push(Pubkey::from_str(key).map_err(|e| format!("parse loaded address: {}", e))?);
+        }
+    }
+    Ok(keys)
+}
+
+/// Decode one program instruction and advance the model with it.
+fn apply(
+    client: &RpcClient,
+    model: &mut Option<VaultModel>,
+    vault: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+    block_time: Option<i64>,
+    expected: &mut Expected,
+) -> Result<(), String> {
+    if let Some(args) = decode::<ix::Initialize>(data)? {
+        if model.is_some() {
+            return Err("vault initialized twice".to_string());
+        }
+        let mut initialized = VaultModel::initialize(
+            *vault,
+            args.network_id,
+            args.m_threshold,
+            args.admin_threshold,
+            args.signers,
+            args.admin_signers,
+            args.domain_suffix,
+        );
+        initialized.auth_scheme = args.auth_scheme;
+        initialized.genesis_hash = args.genesis_hash.unwrap_or_default();
+        *model = Some(initialized);
+        return Ok(());
+    }
+    if let Some(args) = decode::<ix::InitializeFull>(data)? {
+        if model.is_some() {
+            return Err("vault initialized twice".to_string());
+        }
+        let mut initialized = VaultModel::initialize(
+            *vault,
+            args.network_id,
+            args.m_threshold,
+            args.admin_threshold,
+            args.signers,
+            args.admin_signers,
+            args.domain_suffix,
+        );
+        initialized.auth_scheme = args.auth_scheme;
+        initialized.genesis_hash = args.genesis_hash.unwrap_or_default();
+        initialized.whitelisted_assets = args.assets;
+        *model = Some(initialized);
+        return Ok(());
+    }
+
+    let Some(model) = model.as_mut() else {
+        return Err("instruction executed before initialize".to_string());
+    };
+    // The program bumps the sequence before anything can reject the instruction, so it counts
+    // even when the model diverges below.
+    // `migrate` leaves a vault that was initialized at the current version untouched.
+    if !is_view(data)?
+        && decode::<ix::CreateVaultTokenAccounts>(data)?.is_none()
+        && decode::<ix::Migrate>(data)?.is_none()
+    {
+        model.sequence += 1;
+    }
+    if decode::<ix::Migrate>(data)?.is_some() {
+        return Ok(());
+    }
+
+    if let Some(args) = decode::<ix::Deposit>(data)? {
+        return model.deposit(&args.deposits, expected);
+    }
+    if let Some(args) = decode::<ix::DepositWsol>(data)? {
+        return model.deposit_wsol(args.amount, expected);
+    }
+    if let Some(args) = decode::<ix::AdminDeposit>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.admin_deposit(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.deposits,
+            t.bypass_whitelist,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::Withdraw>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        // Balance shares are priced on-chain: only the ticket, its approval and nonce replay.
+        let withdrawals: &[AssetAmount] = if t.balance_bps.is_some() {
+            expected.payouts_unpriced = true;
+            &[]
+        } else {
+            &t.withdrawals
+        };
+        // An omitted optional nonce account is passed as the program id.
+        let nonces = (accounts.get(4) != Some(&strike_example::ID)) as u64;
+        let nonce_tree = model.in_nonce_tree(t.request_id);
+        if nonce_tree && nonces > 0 {
+            return Err(format!(
+                "request_id {} is in the nonce tree's range but passed a nonce account",
+                t.request_id
+            ));
+        }
+        model.withdraw(
+            t,
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            withdrawals,
+            t.treasury.as_deref(),
+            nonce_tree,
+            block_time,
+            expected,
+        )?;
+        model.pay_relayer_rebate(nonces, expected);
+        return Ok(());
+    }
+    if let Some(args) = decode::<ix::WithdrawWsol>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        if t.treasury.is_some() {
+            return Err("withdraw_wsol named a treasury".to_string());
+        }
+        // The recipient gets native SOL after the temporary wSOL account is closed.
+        return model.withdraw(
+            t,
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &t.withdrawals,
+            None,
+            false,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::WithdrawWithVaa>(data)? {
+        // The ticket isn't in the instruction data but in the payload of the posted VAA account.
+        let posted_vaa = accounts
+            .get(4)
+            .ok_or_else(|| "withdraw_with_vaa without a posted VAA account".to_string())?;
+        let account = client
+            .get_account(posted_vaa)
+            .map_err(|e| format!("get posted VAA {}: {}", posted_vaa, e))?;
+        let vaa = account
+            .data
+            .strip_prefix(b"vaa")
+            .and_then(|mut body| PostedVaaData::deserialize(&mut body).ok())
+            .ok_or_else(|| format!("{} is not a posted VAA", posted_vaa))?;
+        let ticket = WithdrawalTicket::try_from_slice(&vaa.payload)
+            .map_err(|e| format!("VAA payload is not a withdrawal ticket: {}", e))?;
+        if ticket.request_id != args.request_id {
+            return Err(format!(
+                "VAA ticket for request {} submitted as {}",
+                ticket.request_id, args.request_id
+            ));
+        }
+        model.withdraw_with_vaa(&ticket, &account.owner, &vaa, block_time, expected)?;
+        model.pay_relayer_rebate(1, expected);
+        return Ok(());
+    }
+    if let Some(args) = decode::<ix::BulkWithdraw>(data)? {
+        return replay_bulk_withdraw(
+            model,
+            &args.bulk_ticket,
+            &args.signers_with_sigs,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::PublishClaimRoot>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.publish_claim_root(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.root,
+            t.claim_expiry,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::Claim>(data)? {
+        return model.claim(&args.leaf, &args.proof, block_time, expected);
+    }
+    if let Some(args) = decode::<ix::ClaimCreditedDeposit>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.claim_credited_deposit(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.credits,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::CreateStream>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.create_stream(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &t.asset,
+            t.amount,
+            block_time,
+            expected,
+        );
+    }
+    // Claims pay out of the stream PDA; only a crank incentive leaves the treasury.
+    if decode::<ix::ClaimStream>(data)?.is_some() {
+        let Some(cranker) = accounts.get(4) else {
+            return Err("claim_stream without a cranker account".to_string());
+        };
+        return model.pay_crank_incentive(cranker, expected);
+    }
+    if let Some(args) = decode::<ix::CreateSchedule>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.create_schedule(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &AssetAmount {
+                asset: t.asset.clone(),
+                amount: t.amount,
+            },
+            t.interval,
+            t.first_execution,
+            t.payments,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::CreateVaultTokenAccounts>(data)? {
+        return model.create_vault_token_accounts(&args.mints);
+    }
+    if decode::<ix::ExecuteSchedule>(data)?.is_some() {
+        // Accounts: vault, treasury, schedule, recipient, cranker.
+        let (Some(schedule), Some(cranker)) = (accounts.get(2), accounts.get(4)) else {
+            return Err("execute_schedule without schedule and cranker accounts".to_string());
+        };
+        return model.execute_schedule(schedule, cranker, block_time, expected);
+    }
+    if let Some(args) = decode::<ix::AdminWithdraw>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.admin_withdraw(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &t.withdrawals,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::AddAsset>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.add_asset(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.asset,
+            &t.settings,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::UpdateAsset>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.update_asset(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.asset,
+            &t.settings,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetTreasury>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_treasury(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.name,
+            t.m_threshold,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::RemoveAsset>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.remove_asset(t, &args.signers_with_sigs, &fields, &t.asset, block_time);
+    }
+    if let Some(args) = decode::<ix::SetMinWithdrawal>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_min_withdrawal(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.asset,
+            t.min_amount,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetUsdCap>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_usd_cap(t, &args.signers_with_sigs, &fields, &t.asset, block_time);
+    }
+    if let Some(args) = decode::<ix::SetMaxTicketUsd>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_max_ticket_usd(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.max_ticket_usd,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::AddRecipient>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.add_recipient(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::RemoveRecipient>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.remove_recipient(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::AddStrategy>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.add_strategy(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.program_id,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::RemoveStrategy>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.remove_strategy(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.program_id,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::AddCranker>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.add_cranker(t, &args.signers_with_sigs, &fields, &t.cranker, block_time);
+    }
+    if let Some(args) = decode::<ix::RemoveCranker>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.remove_cranker(t, &args.signers_with_sigs, &fields, &t.cranker, block_time);
+    }
+    if let Some(args) = decode::<ix::SetCrankIncentive>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_crank_incentive(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.incentive,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::ExecuteStrategy>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.execute_strategy(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.program_id,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::SetRecipientAllowlist>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_recipient_allowlist(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.enabled,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::BlockRecipient>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.block_recipient(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::UnblockRecipient>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.unblock_recipient(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::RotateValidators>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.rotate_validators(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.signers,
+            t.m_threshold,
+            t.admin_threshold,
+            block_time,
+        );
+    }
+
+    if let Some(args) = decode::<ix::RotateAdminSigners>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.rotate_admin_signers(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.signers,
+            t.admin_threshold,
+            block_time,
+        );
+    }
+
+    if let Some(args) = decode::<ix::SetLogVerbosity>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_log_verbosity(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.log_verbosity,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetFee>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_fee(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.fee_bps,
+            t.fee_mint,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::DelegateStake>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.delegate_stake(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.amount,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::DeactivateStake>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.deactivate_stake(t, &args.signers_with_sigs, &fields, block_time);
+    }
+    if let Some(args) = decode::<ix::WithdrawStake>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.withdraw_stake(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.amount,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::LiquidStake>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.liquid_stake(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.pool_mint,
+            t.lamports,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::LiquidUnstake>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.liquid_unstake(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.min_lamports,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::Rebalance>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.rebalance(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.asset,
+            t.amount,
+            &t.source,
+            &t.destination,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::Swap>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.swap(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.input_mint,
+            &t.output_mint,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::SetGuardian>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_guardian(t, &args.signers_with_sigs, &fields, &t.guardian, block_time);
+    }
+    if let Some(args) = decode::<ix::SetAuthScheme>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_auth_scheme(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.auth_scheme,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetHookProgram>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_hook_program(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.hook_program,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetDepositRegistry>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_deposit_registry(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.registry,
+            &t.schema,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetIdentityNetwork>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_identity_network(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.network,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetStrictSignatures>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_strict_signatures(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.enabled,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetTreasuryReserve>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_treasury_reserve(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.reserve,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetReplayMode>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_replay_mode(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.watermark_replay,
+            t.watermark,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::ProposeSignerSet>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        // Accounts: vault, signer set, nonce, payer.
+        let Some(payer) = accounts.get(3) else {
+            return Err("propose_signer_set without a payer account".to_string());
+        };
+        return model.propose_signer_set(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.signers_hash,
+            t.signers_len,
+            t.m_threshold,
+            t.admin_threshold,
+            payer,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::UploadSigners>(data)? {
+        let Some(uploader) = accounts.get(2) else {
+            return Err("upload_signers without an uploader account".to_string());
+        };
+        return model.upload_signers(uploader, &args.signers);
+    }
+    if decode::<ix::ActivateSignerSet>(data)?.is_some() {
+        let Some(uploader) = accounts.get(2) else {
+            return Err("activate_signer_set without an uploader account".to_string());
+        };
+        return model.activate_signer_set(uploader);
+    }
+    if let Some(args) = decode::<ix::SetWormholeEmitter>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_wormhole_emitter(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.core_bridge,
+            t.emitter_chain,
+            t.emitter_address,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::VetoWithdrawal>(data)? {
+        return model.veto_withdrawal(args.request_id);
+    }
+    if let Some(args) = decode::<ix::CloseExpiredNonce>(data)? {
+        return model.close_expired_nonce(args.request_id, args.admin, block_time, expected);
+    }
+    if let Some(args) = decode::<ix::SetRelayerRebate>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_relayer_rebate(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.max_rebate,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetMaxTicketLifetime>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_max_ticket_lifetime(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.max_lifetime,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetExpiryGrace>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_expiry_grace(t, &args.signers_with_sigs, &fields, t.grace, block_time);
+    }
+    if let Some(args) = decode::<ix::SetNonceTree>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_nonce_tree(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            t.nonce_tree,
+            t.base_request_id,
+            t.max_depth,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SetExtraNetworkIds>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_extra_network_ids(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.extra_network_ids,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SweepDust>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.sweep_dust(t, &args.signers_with_sigs, &fields, &t.mints, block_time);
+    }
+    if let Some(args) = decode::<ix::CloseVaultTokenAccount>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.close_vault_token_account(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.mint,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::SweepAsset>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.sweep_asset(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.mint,
+            t.close_account,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::UpgradeProgram>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.upgrade_program(t, &args.signers_with_sigs, &fields, block_time);
+    }
+    if let Some(args) = decode::<ix::CollectFees>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.collect_fees(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &t.fees,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::SetAllowance>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.set_allowance(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.recipient,
+            &t.asset,
+            t.cap,
+            block_time,
+        );
+    }
+    if let Some(args) = decode::<ix::CreateSubVault>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.create_sub_vault(t, &args.signers_with_sigs, &fields, block_time);
+    }
+    if let Some(args) = decode::<ix::ReclaimSubVault>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.reclaim_sub_vault(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.withdrawals,
+            block_time,
+            expected,
+        );
+    }
+    if let Some(args) = decode::<ix::CreateReceiptMint>(data)? {
+        let t = &args.ticket;
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        return model.create_receipt_mint(
+            t,
+            &args.signers_with_sigs,
+            &fields,
+            &t.asset,
+            block_time,
+        );
+    }
+
+    if is_view(data)? {
+        return Ok(());
+    }
+
+    Err(format!(
+        "unrecognized instruction discriminator {:?}",
+        &data[..data.len().min(8)]
+    ))
+}
+
+/// Views only read state.
+fn is_view(data: &[u8]) -> Result<bool, String> {
+    Ok(decode::<ix::ComputeTicketHash>(data)?.is_some()
+        || decode::<ix::ValidateTicket>(data)?.is_some()
+        || decode::<ix::GetVaultInfo>(data)?.is_some()
+        || decode::<ix::GetNonceStatus>(data)?.is_some()
+        || decode::<ix::GetAssetConfig>(data)?.is_some())
+}
+
+fn replay_bulk_withdraw(
+    model: &mut VaultModel,
+    bulk_ticket: &strike_example::instructions::BulkWithdrawalTicket,
+    sigs: &[strike_example::instructions::SignerWithSignature],
+    block_time: Option<i64>,
+    expected: &mut Expected,
+) -> Result<(), String> {
+    if bulk_ticket.tickets.is_empty() {
+        return Err("bulk withdraw with no tickets".to_string());
+    }
+    if !is_supported_ticket_version(bulk_ticket.version) {
+        return Err(format!(
+            "unsupported bulk ticket version {}",
+            bulk_ticket.version
+        ));
+    }
+    // The quorum covers the bulk hash, so every inner ticket is checked against it.
+    for t in bulk_ticket.tickets.iter() {
+        if t.treasury.is_some() {
+            return Err("bulk_withdraw ticket named a treasury".to_string());
+        }
+        let fields = fields(
+            t.version,
+            t.request_id,
+            t.vault,
+            t.expiry,
+            t.network_id,
+            t.not_before,
+        );
+        model.withdraw(
+            t,
+            bulk_ticket,
+            sigs,
+            &fields,
+            &t.recipient,
+            &t.withdrawals,
+            None,
+            false,
+            block_time,
+            expected,
+        )?;
+    }
+    // The program only creates nonces for tickets it hasn't seen, and a seen ticket fails above.
+    // Under watermark replay it creates none.
+    let nonces = if model.watermark_replay {
+        0
+    } else {
+        bulk_ticket.tickets.len() as u64
+    };
+    model.pay_relayer_rebate(nonces, expected);
+    Ok(())
+}
+
+fn fields(
+    version: u8,
+    request_id: u64,
+    vault: Pubkey,
+    expiry: i64,
+    network_id: u64,
+    not_before: Option<i64>,
+) -> TicketFields {
+    TicketFields {
+        version,
+        request_id,
+        vault,
+        expiry,
+        network_id,
+        not_before,
+    }
+}
+
+/// Deserialize instruction args when the data carries `T`'s discriminator.
+fn decode<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Result<Option<T>, String> {
+    if !data.starts_with(T::DISCRIMINATOR) {
+        return Ok(None);
+    }
+    T::try_from_slice(&data[T::DISCRIMINATOR.len()..])
+        .map(Some)
+        .map_err(|e| format!("cannot decode instruction args: {}", e))
+}
+
+/// Compare the balance movements recorded in the transaction meta with the model's expectations.
+fn check_balances(
+    signature: &Signature,
+    treasury: &Pubkey,
+    vault: &Pubkey,
+    account_keys: &[Pubkey],
+    meta: &UiTransactionStatusMeta,
+    expected: &Expected,
+    divergences: &mut Vec<String>,
+) {
+    if expected.payouts_unpriced {
+        return;
+    }
+    let treasury_delta = account_keys
+        .iter()
+        .position(|key| key == treasury)
+        .map(|index| meta.post_balances[index] as i128 - meta.pre_balances[index] as i128)
+        .unwrap_or(0);
+    let mismatch = if expected.treasury_delta_is_floor {
+        treasury_delta < expected.treasury_delta
+    } else {
+        treasury_delta != expected.treasury_delta
+    };
+    if mismatch {
+        divergences.push(format!(
+            "{}: treasury moved {} lamports, replay expects {}",
+            signature, treasury_delta, expected.treasury_delta
+        ));
+    }
+
+    let token_deltas = token_deltas(meta);
+
+    for ((owner, mint), amount) in expected.token_credits.iter() {
+        let actual = token_deltas.get(&(*owner, *mint)).copied().unwrap_or(0);
+        if actual != *amount as i128 {
+            divergences.push(format!(
+                "{}: {} received {} of mint {}, replay expects {}",
+                signature, owner, actual, mint, amount
+            ));
+        }
+    }
+
+    for ((owner, mint), actual) in token_deltas.iter() {
+        if owner != vault || *actual <= 0 || expected.vault_tokens_unbounded {
+            continue;
+        }
+        let bound = expected
+            .vault_token_deposits
+            .get(mint)
+            .copied()
+            .unwrap_or(0);
+        if *actual > bound as i128 {
+            divergences.push(format!(
+                "{}: vault received {} of mint {}, replay allows at most {}",
+                signature, actual, mint, bound
+            ));
+        }
+    }
+}
+
+/// Token balance change per (owner, mint) recorded in the transaction meta.
+fn token_deltas(meta: &UiTransactionStatusMeta) -> HashMap<(Pubkey, Pubkey), i128> {
+    let mut deltas = HashMap::new();
+    let sides = [
+        (&meta.pre_token_balances, -1i128),
+        (&meta.post_token_balances, 1i128),
+    ];
+    for (balances, sign) in sides {
+        let OptionSerializer::Some(balances) = balances else {
+            continue;
+        };
+        for balance in balances.iter() {
+            let OptionSerializer::Some(owner) = &balance.owner else {
+                continue;
+            };
+            let (Ok(owner), Ok(mint)) = (Pubkey::from_str(owner), Pubkey::from_str(&balance.mint))
+            else {
+                continue;
+            };
+            let amount: i128 = balance.ui_token_amount.amount.parse().unwrap_or(0);
+            *deltas.entry((owner, mint)).or_insert(0) += sign * amount;
+        }
+    }
+    deltas
+}
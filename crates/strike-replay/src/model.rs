@@ -0,0 +1,2383 @@
+use std::collections::{HashMap, HashSet};
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::Space;
+use strike_example::instructions::constant::{
+    ADMIN_NONCE_SEED, MAX_ALLOWLISTED_RECIPIENTS, MAX_BLOCKED_RECIPIENTS, MAX_CRANKERS,
+    MAX_EXPIRY_GRACE, MAX_EXTRA_NETWORK_IDS, MAX_FEE_BPS, MAX_NONCE_TREE_DEPTH,
+    MAX_STRATEGY_PROGRAMS, NONCE_GC_CRANKER_BPS, NONCE_GC_DELAY, NONCE_SEED, TREASURY_SEED,
+};
+use strike_example::instructions::util::{
+    active_weight, check_auth_scheme, check_signature_budget, fee_for, is_supported_ticket_version,
+    next_processed_root, relayer_rebate, signed_weight, signers_needed, validate_sigs,
+    verify_claim_proof,
+};
+use strike_example::instructions::{
+    signer_set_hash, Asset, AssetAmount, AssetSettings, AuthScheme, ClaimLeaf, LogVerbosity,
+    NonceAccount, PostedVaaData, PrecompileApproval, SignerWithSignature, Ticket, Vault,
+    WeightedSigner, WithdrawalTicket,
+};
+
+/// Native mirror of the vault's on-chain state, advanced one instruction at a time using the
+/// program's own ticket hashing and signature validation.
+#[derive(Debug, Clone)]
+pub struct VaultModel {
+    pub vault: Pubkey,
+    pub network_id: u64,
+    /// Network ids tickets may carry besides `network_id`.
+    pub extra_network_ids: Vec<u64>,
+    /// Genesis hash `network_id` is derived from, zero when not bound.
+    pub genesis_hash: [u8; 32],
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub signers: Vec<WeightedSigner>,
+    /// Whether `signers` live in the signer set PDA, leaving the vault's list empty.
+    pub has_signer_set: bool,
+    /// Signer set proposal being uploaded, if any.
+    pub pending_signer_set: Option<PendingSignerSetModel>,
+    /// Admin op approvers; empty while `signers` approve admin ops too.
+    pub admin_signers: Vec<WeightedSigner>,
+    pub whitelisted_assets: Vec<Asset>,
+    /// Limits of whitelisted assets; an asset without an entry has none.
+    pub asset_settings: HashMap<Asset, AssetSettings>,
+    /// Smallest withdrawal line per asset, in the order they were set.
+    pub min_withdrawals: Vec<(Asset, u64)>,
+    pub domain_suffix: String,
+    pub log_verbosity: LogVerbosity,
+    pub fee_bps: u16,
+    /// SPL mint fees are paid in, default while they're paid in kind.
+    pub fee_mint: Pubkey,
+    pub max_relayer_rebate: u64,
+    /// Cap on a ticket's expiry past execution time, 0 while uncapped.
+    pub max_ticket_lifetime: u64,
+    /// Seconds past its expiry a ticket still runs.
+    pub expiry_grace: u64,
+    /// May veto withdrawals, default while none is set.
+    pub guardian: Pubkey,
+    /// Notified of deposits and withdrawals, default while none is set.
+    pub hook_program: Pubkey,
+    /// Registry and schema depositors need an attestation from, default while deposits are open.
+    pub deposit_registry: (Pubkey, Pubkey),
+    /// Gatekeeper network withdrawal recipients need an identity pass from, default if none.
+    pub identity_network: Pubkey,
+    /// Reject tickets carrying any signature that fails recovery or isn't from an active signer.
+    pub strict_signatures: bool,
+    /// Lamports above rent exemption withdrawals must leave in the treasury.
+    pub treasury_reserve: u64,
+    pub watermark_replay: bool,
+    pub request_id_watermark: u64,
+    /// Tree `withdraw` records request ids from `nonce_tree_base` on in, default while none is set.
+    pub nonce_tree: Pubkey,
+    pub nonce_tree_base: u64,
+    pub nonce_tree_depth: u8,
+    /// What approves tickets.
+    pub auth_scheme: AuthScheme,
+    /// Whether any asset has a USD cap, and the USD cap per withdrawal ticket, 0 for none.
+    pub has_usd_caps: bool,
+    pub max_ticket_usd: u64,
+    /// Core bridge, emitter chain and emitter address trusted for VAA withdrawals.
+    pub wormhole_emitter: Option<(Pubkey, u16, [u8; 32])>,
+    /// Allowlist mode and the recipients on the allowlist PDA, in insertion order.
+    pub recipient_allowlist: bool,
+    pub allowlisted_recipients: Vec<Pubkey>,
+    /// Whether the blocklist PDA exists and the recipients on it, in insertion order.
+    pub has_blocklist: bool,
+    pub blocked_recipients: Vec<Pubkey>,
+    /// Programs on the strategy allowlist PDA, in insertion order.
+    pub strategy_programs: Vec<Pubkey>,
+    /// Whether the crank registry PDA exists, its crankers and their per-crank incentive.
+    pub has_crank_registry: bool,
+    pub crankers: Vec<Pubkey>,
+    pub crank_incentive: u64,
+    /// Hash of the ticket that consumed each request id, zero for vetoes.
+    pub nonces: HashMap<u64, [u8; 32]>,
+    pub admin_nonces: HashMap<u64, [u8; 32]>,
+    /// Expiry of the ticket behind each live nonce PDA, keyed by (admin, request id); `i64::MAX`
+    /// for vetoes.
+    pub nonce_expiries: HashMap<(bool, u64), i64>,
+    /// Hash chain over consumed request ids, in the order the program consumed them.
+    pub processed_root: [u8; 32],
+    pub processed_count: u64,
+    /// State-changing instructions applied so far, initialize included.
+    pub sequence: u64,
+    /// (cap, consumed) per (recipient, asset) for recipients the validators capped.
+    pub allowances: HashMap<(Pubkey, Asset), (u64, u64)>,
+    /// Published claim roots, keyed by request id.
+    pub claim_roots: HashMap<u64, ClaimRootModel>,
+    /// Recurring payments, keyed by schedule PDA.
+    pub schedules: HashMap<Pubkey, ScheduleModel>,
+    /// Precompile signatures the instruction being replayed can see.
+    pub precompile_sigs: Vec<PrecompileApproval>,
+    /// Withdrawal threshold of each named treasury.
+    pub treasuries: HashMap<String, u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimRootModel {
+    pub root: [u8; 32],
+    pub claim_expiry: i64,
+    pub claimed: HashSet<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingSignerSetModel {
+    pub request_id: u64,
+    pub signers_hash: [u8; 32],
+    pub signers_len: u8,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub uploader: Pubkey,
+    pub uploaded: Vec<WeightedSigner>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleModel {
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub payment: AssetAmount,
+    pub interval: i64,
+    pub next_execution: i64,
+    pub remaining: u32,
+}
+
+/// Balance movements a transaction must show for the instructions it carried.
+#[derive(Debug, Default)]
+pub struct Expected {
+    /// Net lamport change of the treasury PDA.
+    pub treasury_delta: i128,
+    /// Exact token amounts each (owner, mint) must receive.
+    pub token_credits: HashMap<(Pubkey, Pubkey), u64>,
+    /// Upper bound of what the vault may receive per mint (transfer fees lower the real figure).
+    pub vault_token_deposits: HashMap<Pubkey, u64>,
+    /// Stake pool redemptions are priced on-chain; the treasury may receive more than
+    /// `treasury_delta`, never less.
+    pub treasury_delta_is_floor: bool,
+    /// A strategy CPI may pay any token into the vault, so vault deposits aren't bounded.
+    pub vault_tokens_unbounded: bool,
+    /// A `balance_bps` withdrawal pays out shares of balances the replay doesn't track, so no
+    /// movement is checked.
+    pub payouts_unpriced: bool,
+}
+
+impl Expected {
+    fn credit_sol(&mut self, amount: u64) {
+        self.treasury_delta += amount as i128;
+    }
+
+    fn debit_sol(&mut self, amount: u64) {
+        self.treasury_delta -= amount as i128;
+    }
+
+    fn deposit(&mut self, asset_amount: &AssetAmount) {
+        match &asset_amount.asset {
+            Asset::Sol => self.credit_sol(asset_amount.amount),
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                *self.vault_token_deposits.entry(*mint).or_default() += asset_amount.amount;
+            }
+        }
+    }
+
+    /// `fee` is the protocol fee kept back from the recipient and routed to the fee treasury.
+    fn withdraw(&mut self, recipient: &Pubkey, asset_amount: &AssetAmount, fee: u64) {
+        match &asset_amount.asset {
+            Asset::Sol => self.debit_sol(asset_amount.amount),
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                *self.token_credits.entry((*recipient, *mint)).or_default() +=
+                    asset_amount.amount - fee;
+            }
+        }
+    }
+}
+
+/// Why the model refused an instruction the cluster accepted.
+pub type Rejection = String;
+
+impl VaultModel {
+    pub fn initialize(
+        vault: Pubkey,
+        network_id: u64,
+        m_threshold: u8,
+        admin_threshold: u8,
+        signers: Vec<WeightedSigner>,
+        admin_signers: Vec<WeightedSigner>,
+        domain_suffix: String,
+    ) -> Self {
+        Self {
+            vault,
+            network_id,
+            extra_network_ids: Vec::new(),
+            genesis_hash: [0; 32],
+            m_threshold,
+            admin_threshold,
+            signers,
+            has_signer_set: false,
+            pending_signer_set: None,
+            admin_signers,
+            whitelisted_assets: Vec::new(),
+            asset_settings: HashMap::new(),
+            min_withdrawals: Vec::new(),
+            domain_suffix,
+            log_verbosity: LogVerbosity::Itemized,
+            fee_bps: 0,
+            fee_mint: Pubkey::default(),
+            max_relayer_rebate: 0,
+            max_ticket_lifetime: 0,
+            expiry_grace: 0,
+            guardian: Pubkey::default(),
+            hook_program: Pubkey::default(),
+            deposit_registry: (Pubkey::default(), Pubkey::default()),
+            identity_network: Pubkey::default(),
+            strict_signatures: false,
+            treasury_reserve: 0,
+            watermark_replay: false,
+            request_id_watermark: 0,
+            nonce_tree: Pubkey::default(),
+            nonce_tree_base: 0,
+            nonce_tree_depth: 0,
+            auth_scheme: AuthScheme::Secp256k1,
+            has_usd_caps: false,
+            max_ticket_usd: 0,
+            wormhole_emitter: None,
+            recipient_allowlist: false,
+            allowlisted_recipients: Vec::new(),
+            has_blocklist: false,
+            blocked_recipients: Vec::new(),
+            strategy_programs: Vec::new(),
+            has_crank_registry: false,
+            crankers: Vec::new(),
+            crank_incentive: 0,
+            nonces: HashMap::new(),
+            admin_nonces: HashMap::new(),
+            nonce_expiries: HashMap::new(),
+            processed_root: [0; 32],
+            processed_count: 0,
+            sequence: 1,
+            allowances: HashMap::new(),
+            claim_roots: HashMap::new(),
+            schedules: HashMap::new(),
+            precompile_sigs: Vec::new(),
+            treasuries: HashMap::new(),
+        }
+    }
+
+    pub fn deposit(
+        &self,
+        deposits: &[AssetAmount],
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        for deposit in deposits {
+            self.check_deposit(deposit)?;
+            expected.deposit(deposit);
+        }
+        Ok(())
+    }
+
+    pub fn deposit_wsol(&self, amount: u64, expected: &mut Expected) -> Result<(), Rejection> {
+        self.check_deposit(&AssetAmount {
+            asset: Asset::Sol,
+            amount,
+        })?;
+        expected.credit_sol(amount);
+        Ok(())
+    }
+
+    /// `approved` is what `sigs` sign: the ticket itself, or the bulk ticket carrying it.
+    /// `nonce_tree` is whether the request id is recorded as a leaf of the vault's nonce tree.
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw(
+        &mut self,
+        ticket: &dyn Ticket,
+        approved: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        withdrawals: &[AssetAmount],
+        treasury: Option<&str>,
+        nonce_tree: bool,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        let m_threshold = match treasury {
+            Some(name) => {
+                if withdrawals.iter().any(|w| w.asset != Asset::Sol) {
+                    return Err(format!("named treasury {} only holds SOL", name));
+                }
+                *self
+                    .treasuries
+                    .get(name)
+                    .ok_or_else(|| format!("unknown treasury {}", name))?
+            }
+            None => self.m_threshold,
+        };
+        self.check_quorum(
+            approved,
+            sigs,
+            &self.signers,
+            m_threshold as u16,
+            block_time,
+        )?;
+        self.pay_out(
+            ticket,
+            request,
+            recipient,
+            withdrawals,
+            treasury,
+            nonce_tree,
+            expected,
+        )
+    }
+
+    /// A `withdraw_with_vaa` of `ticket`, the payload of `vaa` read from an account `vaa_owner`
+    /// owns.
+    pub fn withdraw_with_vaa(
+        &mut self,
+        ticket: &WithdrawalTicket,
+        vaa_owner: &Pubkey,
+        vaa: &PostedVaaData,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        let Some((core_bridge, emitter_chain, emitter_address)) = self.wormhole_emitter else {
+            return Err("VAA withdrawal before a Wormhole emitter was set".to_string());
+        };
+        if *vaa_owner != core_bridge
+            || vaa.emitter_chain != emitter_chain
+            || vaa.emitter_address != emitter_address
+        {
+            return Err(format!(
+                "VAA from chain {} not posted by the trusted emitter",
+                vaa.emitter_chain
+            ));
+        }
+        if ticket.treasury.is_some() {
+            return Err("VAA withdrawal named a treasury".to_string());
+        }
+        let request = TicketFields {
+            version: ticket.version,
+            request_id: ticket.request_id,
+            vault: ticket.vault,
+            expiry: ticket.expiry,
+            network_id: ticket.network_id,
+            not_before: ticket.not_before,
+        };
+        self.check_ticket(&request, block_time)?;
+        self.pay_out(
+            ticket,
+            &request,
+            &ticket.recipient,
+            &ticket.withdrawals,
+            None,
+            false,
+            expected,
+        )
+    }
+
+    /// The part of a withdrawal after it was authorized: recipient and asset checks, allowances,
+    /// expected balance movements and the nonce.
+    #[allow(clippy::too_many_arguments)]
+    fn pay_out(
+        &mut self,
+        ticket: &dyn Ticket,
+        request: &TicketFields,
+        recipient: &Pubkey,
+        withdrawals: &[AssetAmount],
+        treasury: Option<&str>,
+        nonce_tree: bool,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_recipient_allowed(recipient)?;
+        self.check_withdrawal_limits(withdrawals)?;
+        // A blocked recipient's ticket is spent without paying out.
+        let withdrawals: &[AssetAmount] = if self.blocked_recipients.contains(recipient) {
+            &[]
+        } else {
+            withdrawals
+        };
+        for withdrawal in withdrawals {
+            self.check_whitelisted(&withdrawal.asset)?;
+            self.consume_allowance(recipient, withdrawal)?;
+            // Named treasuries are separate accounts, the default treasury doesn't move.
+            if treasury.is_none() {
+                // Paid in the fee mint, the fee comes from the payer and the line pays out whole.
+                let fee = if self.pays_fee_in_kind(&withdrawal.asset) {
+                    fee_for(withdrawal.amount, self.fee_bps)
+                } else {
+                    0
+                };
+                expected.withdraw(recipient, withdrawal, fee);
+            }
+        }
+        if nonce_tree {
+            return self.use_nonce_tree_leaf(request.request_id, ticket.hash(&self.domain_suffix));
+        }
+        self.use_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )
+    }
+
+    /// Treasury refund to the payer of a `withdraw` or `bulk_withdraw` that created `nonces`
+    /// nonce accounts.
+    pub fn pay_relayer_rebate(&self, nonces: u64, expected: &mut Expected) {
+        let nonce_rent = Rent::default().minimum_balance(8 + NonceAccount::INIT_SPACE);
+        expected.debit_sol(relayer_rebate(self.max_relayer_rebate, nonce_rent, nonces));
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn publish_claim_root(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        root: [u8; 32],
+        claim_expiry: i64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        self.check_quorum(
+            ticket,
+            sigs,
+            &self.signers,
+            self.m_threshold as u16,
+            block_time,
+        )?;
+        self.use_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )?;
+        self.claim_roots.insert(
+            request.request_id,
+            ClaimRootModel {
+                root,
+                claim_expiry,
+                claimed: HashSet::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Credits only touch the user's deposit record, which the model doesn't track.
+    pub fn claim_credited_deposit(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        credits: &[AssetAmount],
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        for credit in credits {
+            self.check_whitelisted(&credit.asset)?;
+        }
+        self.check_quorum(
+            ticket,
+            sigs,
+            &self.signers,
+            self.m_threshold as u16,
+            block_time,
+        )?;
+        self.use_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )
+    }
+
+    /// The instruction data doesn't name the root, so the leaf is matched to a published root
+    /// its proof resolves to, preferring one where the leaf is still unclaimed.
+    pub fn claim(
+        &mut self,
+        leaf: &ClaimLeaf,
+        proof: &[[u8; 32]],
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        let leaf_hash = leaf.hash();
+        let mut matching: Vec<_> = self
+            .claim_roots
+            .iter_mut()
+            .filter(|(_, state)| verify_claim_proof(leaf_hash, proof, &state.root))
+            .collect();
+        if matching.is_empty() {
+            return Err(format!(
+                "claim of leaf {} matches no published root",
+                leaf.index
+            ));
+        }
+        let Some((request_id, state)) = matching
+            .iter_mut()
+            .find(|(_, state)| !state.claimed.contains(&leaf.index))
+        else {
+            return Err(format!("leaf {} was already claimed", leaf.index));
+        };
+        if let Some(block_time) = block_time {
+            if block_time > state.claim_expiry {
+                return Err(format!(
+                    "leaf {} of claim root {} claimed at {} after claim expiry {}",
+                    leaf.index, request_id, block_time, state.claim_expiry
+                ));
+            }
+        }
+        state.claimed.insert(leaf.index);
+        self.check_recipient_allowed(&leaf.recipient)?;
+        self.check_withdrawal_limits(std::slice::from_ref(&leaf.asset_amount))?;
+        if self.blocked_recipients.contains(&leaf.recipient) {
+            return Ok(());
+        }
+        self.consume_allowance(&leaf.recipient, &leaf.asset_amount)?;
+        let fee = if self.pays_fee_in_kind(&leaf.asset_amount.asset) {
+            fee_for(leaf.asset_amount.amount, self.fee_bps)
+        } else {
+            0
+        };
+        expected.withdraw(&leaf.recipient, &leaf.asset_amount, fee);
+        Ok(())
+    }
+
+    /// The stream PDA takes the locked funds; later claims pay out of it, not the vault.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        asset: &Asset,
+        amount: u64,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        self.check_recipient_allowed(recipient)?;
+        self.check_quorum(
+            ticket,
+            sigs,
+            &self.signers,
+            self.m_threshold as u16,
+            block_time,
+        )?;
+        self.use_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )?;
+        if self.blocked_recipients.contains(recipient) {
+            return Ok(());
+        }
+        let (stream, _) = Pubkey::find_program_address(
+            &[
+                b"stream",
+                self.vault.as_ref(),
+                &request.request_id.to_le_bytes(),
+            ],
+            &strike_example::ID,
+        );
+        let asset_amount = AssetAmount {
+            asset: asset.clone(),
+            amount,
+        };
+        expected.withdraw(&stream, &asset_amount, 0);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_schedule(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        payment: &AssetAmount,
+        interval: i64,
+        first_execution: i64,
+        payments: u32,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        if payment.amount == 0 || interval <= 0 || payments == 0 {
+            return Err(format!(
+                "schedule {} pays nothing or has no interval",
+                request.request_id
+            ));
+        }
+        self.check_quorum(
+            ticket,
+            sigs,
+            &self.signers,
+            self.m_threshold as u16,
+            block_time,
+        )?;
+        self.use_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )?;
+        let (schedule, _) = Pubkey::find_program_address(
+            &[
+                b"schedule",
+                self.vault.as_ref(),
+                &request.request_id.to_le_bytes(),
+            ],
+            &strike_example::ID,
+        );
+        self.schedules.insert(
+            schedule,
+            ScheduleModel {
+                request_id: request.request_id,
+                recipient: *recipient,
+                payment: payment.clone(),
+                interval,
+                next_execution: first_execution,
+                remaining: payments,
+            },
+        );
+        Ok(())
+    }
+
+    /// Pays one period of the schedule at `schedule`, like a withdrawal of the scheduled amount.
+    pub fn execute_schedule(
+        &mut self,
+        schedule: &Pubkey,
+        cranker: &Pubkey,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        let Some(state) = self.schedules.get_mut(schedule) else {
+            return Err(format!("schedule {} was never created", schedule));
+        };
+        if state.remaining == 0 {
+            return Err(format!(
+                "schedule {} executed with no payments left",
+                state.request_id
+            ));
+        }
+        if let Some(block_time) = block_time {
+            if block_time < state.next_execution {
+                return Err(format!(
+                    "schedule {} executed at {} before it was due at {}",
+                    state.request_id, block_time, state.next_execution
+                ));
+            }
+        }
+        state.remaining -= 1;
+        state.next_execution += state.interval;
+        let recipient = state.recipient;
+        let payment = state.payment.clone();
+
+        self.check_recipient_allowed(&recipient)?;
+        self.pay_crank_incentive(cranker, expected)?;
+        if self.blocked_recipients.contains(&recipient) {
+            return Ok(());
+        }
+        self.consume_allowance(&recipient, &payment)?;
+        expected.withdraw(&recipient, &payment, 0);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn admin_deposit(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        deposits: &[AssetAmount],
+        bypass_whitelist: bool,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        self.check_quorum(ticket, sigs, self.admin_signer_set(), 1, block_time)?;
+        for deposit in deposits {
+            if !bypass_whitelist || self.whitelisted_assets.contains(&deposit.asset) {
+                self.check_deposit(deposit)?;
+            }
+            expected.deposit(deposit);
+        }
+        self.use_admin_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn admin_withdraw(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        withdrawals: &[AssetAmount],
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        // Every unexpired admin signer, whatever its weight.
+        let now = block_time.unwrap_or(i64::MIN);
+        let admin_signers = self.admin_signer_set();
+        self.check_quorum(
+            ticket,
+            sigs,
+            admin_signers,
+            active_weight(admin_signers, now).max(1),
+            block_time,
+        )?;
+        for withdrawal in withdrawals {
+            expected.withdraw(recipient, withdrawal, 0);
+        }
+        self.use_admin_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )
+    }
+
+    pub fn add_asset(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        settings: &AssetSettings,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if !self.whitelisted_assets.contains(asset) {
+            self.whitelisted_assets.push(asset.clone());
+            self.asset_settings.insert(asset.clone(), *settings);
+        }
+        Ok(())
+    }
+
+    pub fn update_asset(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        settings: &AssetSettings,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.check_whitelisted(asset)?;
+        self.asset_settings.insert(asset.clone(), *settings);
+        Ok(())
+    }
+
+    pub fn set_treasury(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        name: &str,
+        m_threshold: u8,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        let total_weight: u16 = self.signers.iter().map(|s| s.weight as u16).sum();
+        if m_threshold == 0 || m_threshold as u16 > total_weight {
+            return Err(format!("treasury threshold {} out of range", m_threshold));
+        }
+        self.treasuries.insert(name.to_string(), m_threshold);
+        Ok(())
+    }
+
+    pub fn remove_asset(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.whitelisted_assets.retain(|existing| existing != asset);
+        self.asset_settings.remove(asset);
+        self.min_withdrawals
+            .retain(|(existing, _)| existing != asset);
+        Ok(())
+    }
+
+    pub fn set_min_withdrawal(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        min_amount: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.check_whitelisted(asset)?;
+        self.min_withdrawals
+            .retain(|(existing, _)| existing != asset);
+        if min_amount > 0 {
+            self.min_withdrawals.push((asset.clone(), min_amount));
+        }
+        Ok(())
+    }
+
+    /// Withdrawals are priced against historical oracle prices the replay doesn't have, so only
+    /// the cap's effect on the vault account is modelled.
+    pub fn set_usd_cap(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.check_whitelisted(asset)?;
+        self.has_usd_caps = true;
+        Ok(())
+    }
+
+    pub fn set_max_ticket_usd(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        max_ticket_usd: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.max_ticket_usd = max_ticket_usd;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_recipient(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if !self.allowlisted_recipients.contains(recipient) {
+            if self.allowlisted_recipients.len() >= MAX_ALLOWLISTED_RECIPIENTS {
+                return Err(format!("recipient allowlist full, can't add {}", recipient));
+            }
+            self.allowlisted_recipients.push(*recipient);
+        }
+        Ok(())
+    }
+
+    pub fn remove_recipient(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.allowlisted_recipients.retain(|r| r != recipient);
+        Ok(())
+    }
+
+    pub fn set_recipient_allowlist(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        enabled: bool,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.recipient_allowlist = enabled;
+        Ok(())
+    }
+
+    pub fn block_recipient(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.has_blocklist = true;
+        if !self.blocked_recipients.contains(recipient) {
+            if self.blocked_recipients.len() >= MAX_BLOCKED_RECIPIENTS {
+                return Err(format!("blocklist full, can't add {}", recipient));
+            }
+            self.blocked_recipients.push(*recipient);
+        }
+        Ok(())
+    }
+
+    pub fn unblock_recipient(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.blocked_recipients.retain(|r| r != recipient);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn rotate_validators(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        signers: &[WeightedSigner],
+        m_threshold: u8,
+        admin_threshold: u8,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.signers = signers.to_vec();
+        self.has_signer_set = false;
+        self.m_threshold = m_threshold;
+        self.admin_threshold = admin_threshold;
+        Ok(())
+    }
+
+    /// A `propose_signer_set` paid by `uploader`, replacing any pending proposal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_signer_set(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        signers_hash: [u8; 32],
+        signers_len: u8,
+        m_threshold: u8,
+        admin_threshold: u8,
+        uploader: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.pending_signer_set = Some(PendingSignerSetModel {
+            request_id: request.request_id,
+            signers_hash,
+            signers_len,
+            m_threshold,
+            admin_threshold,
+            uploader: *uploader,
+            uploaded: Vec::new(),
+        });
+        Ok(())
+    }
+
+    pub fn upload_signers(
+        &mut self,
+        uploader: &Pubkey,
+        signers: &[WeightedSigner],
+    ) -> Result<(), Rejection> {
+        let pending = self.pending_signer_set_of(uploader)?;
+        if pending.uploaded.len() + signers.len() > pending.signers_len as usize {
+            return Err(format!(
+                "upload past the {} proposed signers",
+                pending.signers_len
+            ));
+        }
+        pending.uploaded.extend_from_slice(signers);
+        Ok(())
+    }
+
+    pub fn activate_signer_set(&mut self, uploader: &Pubkey) -> Result<(), Rejection> {
+        let pending = self.pending_signer_set_of(uploader)?;
+        if pending.uploaded.len() != pending.signers_len as usize
+            || signer_set_hash(&pending.uploaded) != pending.signers_hash
+        {
+            return Err(format!(
+                "uploaded signers don't match proposal {}",
+                pending.request_id
+            ));
+        }
+        let pending = self.pending_signer_set.take().expect("checked above");
+        self.signers = pending.uploaded;
+        self.has_signer_set = true;
+        self.m_threshold = pending.m_threshold;
+        self.admin_threshold = pending.admin_threshold;
+        Ok(())
+    }
+
+    fn pending_signer_set_of(
+        &mut self,
+        uploader: &Pubkey,
+    ) -> Result<&mut PendingSignerSetModel, Rejection> {
+        let pending = self
+            .pending_signer_set
+            .as_mut()
+            .ok_or_else(|| "no signer set proposal pending".to_string())?;
+        if pending.uploader != *uploader {
+            return Err(format!(
+                "{} isn't the signer set uploader {}",
+                uploader, pending.uploader
+            ));
+        }
+        Ok(pending)
+    }
+
+    pub fn rotate_admin_signers(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        signers: &[WeightedSigner],
+        admin_threshold: u8,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.admin_signers = signers.to_vec();
+        self.admin_threshold = admin_threshold;
+        Ok(())
+    }
+
+    pub fn set_log_verbosity(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        log_verbosity: LogVerbosity,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.log_verbosity = log_verbosity;
+        Ok(())
+    }
+
+    pub fn set_fee(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        fee_bps: u16,
+        fee_mint: Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(format!(
+                "fee {} bps above the {} bps maximum",
+                fee_bps, MAX_FEE_BPS
+            ));
+        }
+        if fee_mint != Pubkey::default() {
+            self.check_whitelisted(&Asset::SplToken { mint: fee_mint })?;
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.fee_bps = fee_bps;
+        self.fee_mint = fee_mint;
+        Ok(())
+    }
+
+    /// Whether a withdrawal line of `asset` pays its protocol fee in kind rather than in the fee
+    /// mint.
+    fn pays_fee_in_kind(&self, asset: &Asset) -> bool {
+        self.fee_mint == Pubkey::default()
+            || *asset
+                == (Asset::SplToken {
+                    mint: self.fee_mint,
+                })
+    }
+
+    pub fn set_relayer_rebate(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        max_rebate: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.max_relayer_rebate = max_rebate;
+        Ok(())
+    }
+
+    pub fn set_max_ticket_lifetime(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        max_lifetime: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.max_ticket_lifetime = max_lifetime;
+        Ok(())
+    }
+
+    pub fn set_expiry_grace(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        grace: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if grace > MAX_EXPIRY_GRACE {
+            return Err(format!(
+                "expiry grace {} seconds above the {} second maximum",
+                grace, MAX_EXPIRY_GRACE
+            ));
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.expiry_grace = grace;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_nonce_tree(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        nonce_tree: Pubkey,
+        base_request_id: u64,
+        max_depth: u32,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if max_depth == 0 || max_depth > MAX_NONCE_TREE_DEPTH {
+            return Err(format!(
+                "nonce tree depth {} outside 1 to {}",
+                max_depth, MAX_NONCE_TREE_DEPTH
+            ));
+        }
+        if base_request_id <= self.request_id_watermark {
+            return Err(format!(
+                "nonce tree base {} at or below the watermark {}",
+                base_request_id, self.request_id_watermark
+            ));
+        }
+        let last_request_id = base_request_id
+            .checked_add((1u64 << max_depth) - 1)
+            .ok_or_else(|| format!("nonce tree range from {} overflows", base_request_id))?;
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.nonce_tree = nonce_tree;
+        self.nonce_tree_base = base_request_id;
+        self.nonce_tree_depth = max_depth as u8;
+        self.request_id_watermark = last_request_id;
+        Ok(())
+    }
+
+    pub fn set_extra_network_ids(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        extra_network_ids: &[u64],
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if extra_network_ids.len() > MAX_EXTRA_NETWORK_IDS {
+            return Err(format!(
+                "{} extra network ids, at most {} allowed",
+                extra_network_ids.len(),
+                MAX_EXTRA_NETWORK_IDS
+            ));
+        }
+        let mut seen = HashSet::from([self.network_id]);
+        if let Some(duplicate) = extra_network_ids.iter().find(|id| !seen.insert(**id)) {
+            return Err(format!("network id {} listed twice", duplicate));
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.extra_network_ids = extra_network_ids.to_vec();
+        Ok(())
+    }
+
+    pub fn set_guardian(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        guardian: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.guardian = *guardian;
+        Ok(())
+    }
+
+    pub fn set_hook_program(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        hook_program: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if *hook_program == strike_example::ID {
+            return Err("hook program set to the vault program itself".to_string());
+        }
+        self.hook_program = *hook_program;
+        Ok(())
+    }
+
+    pub fn set_deposit_registry(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        registry: &Pubkey,
+        schema: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if *registry == Pubkey::default() {
+            self.deposit_registry = (Pubkey::default(), Pubkey::default());
+            return Ok(());
+        }
+        if *registry == strike_example::ID || *schema == Pubkey::default() {
+            return Err(format!(
+                "invalid deposit registry {} with schema {}",
+                registry, schema
+            ));
+        }
+        self.deposit_registry = (*registry, *schema);
+        Ok(())
+    }
+
+    pub fn set_identity_network(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        network: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.identity_network = *network;
+        Ok(())
+    }
+
+    pub fn set_strict_signatures(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        enabled: bool,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.strict_signatures = enabled;
+        Ok(())
+    }
+
+    pub fn set_treasury_reserve(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        reserve: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.treasury_reserve = reserve;
+        Ok(())
+    }
+
+    pub fn set_replay_mode(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        watermark_replay: bool,
+        watermark: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if watermark < self.request_id_watermark {
+            return Err(format!(
+                "watermark lowered from {} to {}",
+                self.request_id_watermark, watermark
+            ));
+        }
+        self.watermark_replay = watermark_replay;
+        self.request_id_watermark = watermark;
+        Ok(())
+    }
+
+    pub fn set_auth_scheme(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        auth_scheme: AuthScheme,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        check_auth_scheme(&auth_scheme).map_err(|e| e.to_string())?;
+        self.auth_scheme = auth_scheme;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_wormhole_emitter(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        core_bridge: &Pubkey,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.wormhole_emitter = Some((*core_bridge, emitter_chain, emitter_address));
+        Ok(())
+    }
+
+    pub fn delegate_stake(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        amount: u64,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        expected.debit_sol(amount);
+        Ok(())
+    }
+
+    pub fn deactivate_stake(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)
+    }
+
+    pub fn withdraw_stake(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        amount: u64,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        expected.credit_sol(amount);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn liquid_stake(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        pool_mint: &Pubkey,
+        lamports: u64,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        expected.debit_sol(lamports);
+        // The pool prices the deposit, so there is no upper bound on the tokens minted.
+        expected.vault_token_deposits.insert(*pool_mint, u64::MAX);
+        let asset = Asset::SplToken { mint: *pool_mint };
+        if !self.whitelisted_assets.contains(&asset) {
+            self.whitelisted_assets.push(asset);
+        }
+        Ok(())
+    }
+
+    pub fn liquid_unstake(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        min_lamports: u64,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        expected.credit_sol(min_lamports);
+        expected.treasury_delta_is_floor = true;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if input_mint == output_mint {
+            return Err(format!("swap of {} into itself", input_mint));
+        }
+        self.check_whitelisted(&Asset::SplToken { mint: *input_mint })?;
+        self.check_whitelisted(&Asset::SplToken { mint: *output_mint })?;
+        // The route prices the swap; the ticket only bounds it from below.
+        expected.vault_token_deposits.insert(*output_mint, u64::MAX);
+        Ok(())
+    }
+
+    /// Empty token accounts, so nothing to track beyond the whitelist gate.
+    pub fn create_vault_token_accounts(&self, mints: &[Pubkey]) -> Result<(), Rejection> {
+        for mint in mints {
+            let whitelisted = self.whitelisted_assets.iter().any(|asset| {
+                matches!(asset, Asset::SplToken { mint: m } | Asset::Nft { mint: m } if m == mint)
+            });
+            if !whitelisted {
+                return Err(format!("mint {} is not whitelisted", mint));
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn rebalance(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        amount: u64,
+        source: &Pubkey,
+        destination: &Pubkey,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if amount == 0 {
+            return Err("rebalance of zero".to_string());
+        }
+        if source == destination {
+            return Err(format!("rebalance of {} into itself", source));
+        }
+        // Funds stay in the vault; only a SOL move in or out of the treasury PDA shows up.
+        if *asset == Asset::Sol {
+            let (treasury, _) = Pubkey::find_program_address(
+                &[TREASURY_SEED, self.vault.as_ref()],
+                &strike_example::ID,
+            );
+            if *source == treasury {
+                expected.debit_sol(amount);
+            } else if *destination == treasury {
+                expected.credit_sol(amount);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_strategy(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        program_id: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if *program_id == strike_example::ID {
+            return Err("the vault program can't be a strategy".to_string());
+        }
+        if !self.strategy_programs.contains(program_id) {
+            if self.strategy_programs.len() >= MAX_STRATEGY_PROGRAMS {
+                return Err(format!("strategy allowlist full, can't add {}", program_id));
+            }
+            self.strategy_programs.push(*program_id);
+        }
+        Ok(())
+    }
+
+    pub fn remove_strategy(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        program_id: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.strategy_programs.retain(|p| p != program_id);
+        Ok(())
+    }
+
+    pub fn add_cranker(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        cranker: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.has_crank_registry = true;
+        if !self.crankers.contains(cranker) {
+            if self.crankers.len() >= MAX_CRANKERS {
+                return Err(format!("crank registry full, can't add {}", cranker));
+            }
+            self.crankers.push(*cranker);
+        }
+        Ok(())
+    }
+
+    pub fn remove_cranker(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        cranker: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if !self.has_crank_registry {
+            return Err(format!("{} removed without a crank registry", cranker));
+        }
+        self.crankers.retain(|c| c != cranker);
+        Ok(())
+    }
+
+    pub fn set_crank_incentive(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        incentive: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.has_crank_registry = true;
+        self.crank_incentive = incentive;
+        Ok(())
+    }
+
+    /// The CPI itself is opaque to the model; only its authorization is replayed.
+    pub fn execute_strategy(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        program_id: &Pubkey,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        if !self.strategy_programs.contains(program_id) {
+            return Err(format!("{} is not an approved strategy", program_id));
+        }
+        expected.vault_tokens_unbounded = true;
+        Ok(())
+    }
+
+    /// The guardian's signature is checked by the runtime; the model only checks one is set.
+    pub fn veto_withdrawal(&mut self, request_id: u64) -> Result<(), Rejection> {
+        if self.guardian == Pubkey::default() {
+            return Err(format!(
+                "request_id {} vetoed without a guardian",
+                request_id
+            ));
+        }
+        if self.watermark_replay {
+            return Err(format!(
+                "request_id {} vetoed under watermark replay",
+                request_id
+            ));
+        }
+        self.use_nonce(request_id, [0; 32], i64::MAX)
+    }
+
+    /// Anyone may close a nonce PDA `NONCE_GC_DELAY` after its ticket expired; the treasury keeps
+    /// what the cranker doesn't take. Its request id may then be signed again.
+    pub fn close_expired_nonce(
+        &mut self,
+        request_id: u64,
+        admin: bool,
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        let Some(&expiry) = self.nonce_expiries.get(&(admin, request_id)) else {
+            return Err(format!(
+                "closed the nonce of request_id {} which has none",
+                request_id
+            ));
+        };
+        if expiry == i64::MAX {
+            return Err(format!(
+                "closed the vetoed nonce of request_id {}",
+                request_id
+            ));
+        }
+        if let Some(now) = block_time {
+            if now <= expiry.saturating_add(NONCE_GC_DELAY) {
+                return Err(format!(
+                    "closed the nonce of request_id {} before {}",
+                    request_id,
+                    expiry.saturating_add(NONCE_GC_DELAY)
+                ));
+            }
+        }
+
+        self.nonce_expiries.remove(&(admin, request_id));
+        if admin {
+            self.admin_nonces.remove(&request_id);
+        } else {
+            self.nonces.remove(&request_id);
+        }
+        let nonce_rent = Rent::default().minimum_balance(8 + NonceAccount::INIT_SPACE);
+        expected.credit_sol(nonce_rent - fee_for(nonce_rent, NONCE_GC_CRANKER_BPS));
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn collect_fees(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        fees: &[AssetAmount],
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        for fee in fees {
+            // Fees come out of the fee treasury, so the main treasury doesn't move.
+            if let Asset::SplToken { mint } | Asset::Nft { mint } = &fee.asset {
+                *expected
+                    .token_credits
+                    .entry((*recipient, *mint))
+                    .or_default() += fee.amount;
+            }
+        }
+        Ok(())
+    }
+
+    /// The model doesn't track vault token balances, so what was swept isn't checked.
+    pub fn sweep_dust(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        mints: &[Pubkey],
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if mints.is_empty() {
+            return Err("sweep dust with no mints".to_string());
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)
+    }
+
+    pub fn close_vault_token_account(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        mint: &Pubkey,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        for asset in [Asset::SplToken { mint: *mint }, Asset::Nft { mint: *mint }] {
+            if self.whitelisted_assets.contains(&asset) {
+                return Err(format!("closing the token account of listed {:?}", asset));
+            }
+        }
+        Ok(())
+    }
+
+    /// The model doesn't track vault token balances, so what was swept isn't checked.
+    pub fn sweep_asset(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        mint: &Pubkey,
+        close_account: bool,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if close_account {
+            return self.close_vault_token_account(ticket, sigs, request, mint, block_time);
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)
+    }
+
+    /// The program's code isn't part of the vault state, only the admin nonce is used.
+    pub fn upgrade_program(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_allowance(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        recipient: &Pubkey,
+        asset: &Asset,
+        cap: u64,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        let entry = self
+            .allowances
+            .entry((*recipient, asset.clone()))
+            .or_insert((0, 0));
+        entry.0 = cap;
+        Ok(())
+    }
+
+    pub fn create_receipt_mint(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        asset: &Asset,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        self.check_whitelisted(asset)
+    }
+
+    /// The sub-vault's own state lives in its account and is replayed separately.
+    pub fn create_sub_vault(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_admin_update(ticket, sigs, request, block_time)
+    }
+
+    pub fn reclaim_sub_vault(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        withdrawals: &[AssetAmount],
+        block_time: Option<i64>,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        if withdrawals.is_empty() {
+            return Err("reclaim with no withdrawals".to_string());
+        }
+        self.check_admin_update(ticket, sigs, request, block_time)?;
+        for withdrawal in withdrawals {
+            // Reclaimed funds arrive like a deposit; transfer fees may lower SPL amounts.
+            expected.deposit(withdrawal);
+        }
+        Ok(())
+    }
+
+    /// Compare the replayed state with the vault account as it exists now.
+    pub fn diff(&self, on_chain: &Vault) -> Vec<String> {
+        let mut divergences = Vec::new();
+        if on_chain.network_id != self.network_id {
+            divergences.push(format!(
+                "network_id: replayed {}, on-chain {}",
+                self.network_id, on_chain.network_id
+            ));
+        }
+        if on_chain.extra_network_ids != self.extra_network_ids {
+            divergences.push(format!(
+                "extra_network_ids: replayed {:?}, on-chain {:?}",
+                self.extra_network_ids, on_chain.extra_network_ids
+            ));
+        }
+        if on_chain.genesis_hash != self.genesis_hash {
+            divergences.push(format!(
+                "genesis_hash: replayed {}, on-chain {}",
+                hex(self.genesis_hash),
+                hex(on_chain.genesis_hash)
+            ));
+        }
+        if on_chain.m_threshold != self.m_threshold {
+            divergences.push(format!(
+                "m_threshold: replayed {}, on-chain {}",
+                self.m_threshold, on_chain.m_threshold
+            ));
+        }
+        if on_chain.admin_threshold != self.admin_threshold {
+            divergences.push(format!(
+                "admin_threshold: replayed {}, on-chain {}",
+                self.admin_threshold, on_chain.admin_threshold
+            ));
+        }
+        if on_chain.has_signer_set != self.has_signer_set {
+            divergences.push(format!(
+                "has_signer_set: replayed {}, on-chain {}",
+                self.has_signer_set, on_chain.has_signer_set
+            ));
+        }
+        // A signer set PDA holds the signers in place of the vault's list.
+        let vault_signers: &[WeightedSigner] = if self.has_signer_set {
+            &[]
+        } else {
+            &self.signers
+        };
+        if on_chain.signers != vault_signers {
+            divergences.push(format!(
+                "signers: replayed {:?}, on-chain {:?}",
+                vault_signers.iter().map(weighted_hex).collect::<Vec<_>>(),
+                on_chain
+                    .signers
+                    .iter()
+                    .map(weighted_hex)
+                    .collect::<Vec<_>>()
+            ));
+        }
+        if on_chain.admin_signers != self.admin_signers {
+            divergences.push(format!(
+                "admin_signers: replayed {:?}, on-chain {:?}",
+                self.admin_signers
+                    .iter()
+                    .map(weighted_hex)
+                    .collect::<Vec<_>>(),
+                on_chain
+                    .admin_signers
+                    .iter()
+                    .map(weighted_hex)
+                    .collect::<Vec<_>>()
+            ));
+        }
+        let on_chain_assets: Vec<Asset> = on_chain
+            .whitelisted_assets
+            .iter()
+            .map(|config| config.asset.clone())
+            .collect();
+        if on_chain_assets != self.whitelisted_assets {
+            divergences.push(format!(
+                "whitelisted_assets: replayed {:?}, on-chain {:?}",
+                self.whitelisted_assets, on_chain_assets
+            ));
+        }
+        for config in &on_chain.whitelisted_assets {
+            let settings = self.settings(&config.asset);
+            let on_chain_settings = AssetSettings {
+                min_deposit: config.min_deposit,
+                deposit_cap: config.deposit_cap,
+                withdrawal_limit: config.withdrawal_limit,
+                tvl_cap: config.tvl_cap,
+                user_cap: config.user_cap,
+                paused: config.paused,
+            };
+            if on_chain_settings != settings {
+                divergences.push(format!(
+                    "settings of {:?}: replayed {:?}, on-chain {:?}",
+                    config.asset, settings, on_chain_settings
+                ));
+            }
+        }
+        let on_chain_mins: Vec<(Asset, u64)> = on_chain
+            .min_withdrawals
+            .iter()
+            .map(|min| (min.asset.clone(), min.amount))
+            .collect();
+        if on_chain_mins != self.min_withdrawals {
+            divergences.push(format!(
+                "min_withdrawals: replayed {:?}, on-chain {:?}",
+                self.min_withdrawals, on_chain_mins
+            ));
+        }
+        if on_chain.domain_suffix != self.domain_suffix {
+            divergences.push(format!(
+                "domain_suffix: replayed {:?}, on-chain {:?}",
+                self.domain_suffix, on_chain.domain_suffix
+            ));
+        }
+        if on_chain.log_verbosity != self.log_verbosity {
+            divergences.push(format!(
+                "log_verbosity: replayed {:?}, on-chain {:?}",
+                self.log_verbosity, on_chain.log_verbosity
+            ));
+        }
+        if on_chain.fee_bps != self.fee_bps {
+            divergences.push(format!(
+                "fee_bps: replayed {}, on-chain {}",
+                self.fee_bps, on_chain.fee_bps
+            ));
+        }
+        if on_chain.fee_mint != self.fee_mint {
+            divergences.push(format!(
+                "fee_mint: replayed {}, on-chain {}",
+                self.fee_mint, on_chain.fee_mint
+            ));
+        }
+        if on_chain.max_relayer_rebate != self.max_relayer_rebate {
+            divergences.push(format!(
+                "max_relayer_rebate: replayed {}, on-chain {}",
+                self.max_relayer_rebate, on_chain.max_relayer_rebate
+            ));
+        }
+        if on_chain.max_ticket_lifetime != self.max_ticket_lifetime {
+            divergences.push(format!(
+                "max_ticket_lifetime: replayed {}, on-chain {}",
+                self.max_ticket_lifetime, on_chain.max_ticket_lifetime
+            ));
+        }
+        if on_chain.expiry_grace != self.expiry_grace {
+            divergences.push(format!(
+                "expiry_grace: replayed {}, on-chain {}",
+                self.expiry_grace, on_chain.expiry_grace
+            ));
+        }
+        if on_chain.nonce_tree != self.nonce_tree
+            || on_chain.nonce_tree_base != self.nonce_tree_base
+            || on_chain.nonce_tree_depth != self.nonce_tree_depth
+        {
+            divergences.push(format!(
+                "nonce tree: replayed {} from {} at depth {}, on-chain {} from {} at depth {}",
+                self.nonce_tree,
+                self.nonce_tree_base,
+                self.nonce_tree_depth,
+                on_chain.nonce_tree,
+                on_chain.nonce_tree_base,
+                on_chain.nonce_tree_depth
+            ));
+        }
+        if on_chain.guardian != self.guardian {
+            divergences.push(format!(
+                "guardian: replayed {}, on-chain {}",
+                self.guardian, on_chain.guardian
+            ));
+        }
+        if on_chain.auth_scheme != self.auth_scheme {
+            divergences.push(format!(
+                "auth_scheme: replayed {:?}, on-chain {:?}",
+                self.auth_scheme, on_chain.auth_scheme
+            ));
+        }
+        if on_chain.has_usd_caps != self.has_usd_caps {
+            divergences.push(format!(
+                "has_usd_caps: replayed {}, on-chain {}",
+                self.has_usd_caps, on_chain.has_usd_caps
+            ));
+        }
+        if on_chain.max_ticket_usd != self.max_ticket_usd {
+            divergences.push(format!(
+                "max_ticket_usd: replayed {}, on-chain {}",
+                self.max_ticket_usd, on_chain.max_ticket_usd
+            ));
+        }
+        let on_chain_registry = (on_chain.deposit_registry, on_chain.deposit_schema);
+        if on_chain_registry != self.deposit_registry {
+            divergences.push(format!(
+                "deposit_registry: replayed {:?}, on-chain {:?}",
+                self.deposit_registry, on_chain_registry
+            ));
+        }
+        if on_chain.identity_network != self.identity_network {
+            divergences.push(format!(
+                "identity_network: replayed {}, on-chain {}",
+                self.identity_network, on_chain.identity_network
+            ));
+        }
+        if on_chain.strict_signatures != self.strict_signatures {
+            divergences.push(format!(
+                "strict_signatures: replayed {}, on-chain {}",
+                self.strict_signatures, on_chain.strict_signatures
+            ));
+        }
+        if on_chain.watermark_replay != self.watermark_replay
+            || on_chain.request_id_watermark != self.request_id_watermark
+        {
+            divergences.push(format!(
+                "replay mode: replayed watermark {} ({}), on-chain watermark {} ({})",
+                self.request_id_watermark,
+                self.watermark_replay,
+                on_chain.request_id_watermark,
+                on_chain.watermark_replay
+            ));
+        }
+        if on_chain.treasury_reserve != self.treasury_reserve {
+            divergences.push(format!(
+                "treasury_reserve: replayed {}, on-chain {}",
+                self.treasury_reserve, on_chain.treasury_reserve
+            ));
+        }
+        if on_chain.hook_program != self.hook_program {
+            divergences.push(format!(
+                "hook_program: replayed {}, on-chain {}",
+                self.hook_program, on_chain.hook_program
+            ));
+        }
+        if on_chain.recipient_allowlist != self.recipient_allowlist {
+            divergences.push(format!(
+                "recipient_allowlist: replayed {}, on-chain {}",
+                self.recipient_allowlist, on_chain.recipient_allowlist
+            ));
+        }
+        if on_chain.has_crank_registry != self.has_crank_registry {
+            divergences.push(format!(
+                "has_crank_registry: replayed {}, on-chain {}",
+                self.has_crank_registry, on_chain.has_crank_registry
+            ));
+        }
+        if on_chain.has_blocklist != self.has_blocklist {
+            divergences.push(format!(
+                "has_blocklist: replayed {}, on-chain {}",
+                self.has_blocklist, on_chain.has_blocklist
+            ));
+        }
+        if on_chain.processed_count != self.processed_count
+            || on_chain.processed_root != self.processed_root
+        {
+            divergences.push(format!(
+                "processed: replayed {} ids (root {}), on-chain {} ids (root {})",
+                self.processed_count,
+                hex(self.processed_root),
+                on_chain.processed_count,
+                hex(on_chain.processed_root)
+            ));
+        }
+        if on_chain.sequence != self.sequence {
+            divergences.push(format!(
+                "sequence: replayed {}, on-chain {}",
+                self.sequence, on_chain.sequence
+            ));
+        }
+        divergences
+    }
+
+    fn check_ticket(
+        &self,
+        request: &TicketFields,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        if request.vault != self.vault {
+            return Err(format!("ticket vault {} is not this vault", request.vault));
+        }
+        if request.network_id != self.network_id
+            && !self.extra_network_ids.contains(&request.network_id)
+        {
+            return Err(format!(
+                "ticket network_id {} is neither vault network_id {} nor one of {:?}",
+                request.network_id, self.network_id, self.extra_network_ids
+            ));
+        }
+        if !is_supported_ticket_version(request.version) {
+            return Err(format!("unsupported ticket version {}", request.version));
+        }
+        // Block time is the validators' estimate of the bank clock, close enough to flag misuse.
+        if let Some(block_time) = block_time {
+            if block_time > request.expiry.saturating_add_unsigned(self.expiry_grace) {
+                return Err(format!(
+                    "executed at {} after ticket expiry {} and {} seconds of grace",
+                    block_time, request.expiry, self.expiry_grace
+                ));
+            }
+            if self.max_ticket_lifetime != 0
+                && request.expiry > block_time.saturating_add_unsigned(self.max_ticket_lifetime)
+            {
+                return Err(format!(
+                    "ticket expiry {} is more than {} seconds past execution at {}",
+                    request.expiry, self.max_ticket_lifetime, block_time
+                ));
+            }
+            if let Some(not_before) = request.not_before {
+                if block_time < not_before {
+                    return Err(format!(
+                        "executed at {} before ticket not_before {}",
+                        block_time, not_before
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Signers approving admin ops, mirroring `Vault::admin_signer_set`.
+    fn admin_signer_set(&self) -> &[WeightedSigner] {
+        if self.admin_signers.is_empty() {
+            &self.signers
+        } else {
+            &self.admin_signers
+        }
+    }
+
+    fn check_quorum(
+        &self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        signers: &[WeightedSigner],
+        required: u16,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        let sigs: &[SignerWithSignature] = match self.auth_scheme {
+            AuthScheme::Secp256k1 => sigs,
+            AuthScheme::Ed25519 | AuthScheme::Secp256r1 => &[],
+            // Attestations are accounts the program checked when it ran, not instruction data.
+            AuthScheme::Attestor { .. } => return Ok(()),
+        };
+        // Without a block time no signer is treated as expired.
+        let now = block_time.unwrap_or(i64::MIN);
+        check_signature_budget(sigs.len(), signers_needed(signers, required, now))
+            .map_err(|e| e.to_string())?;
+        let valid = validate_sigs(
+            ticket,
+            sigs,
+            &self.precompile_sigs,
+            signers,
+            required,
+            &self.domain_suffix,
+            now,
+            self.strict_signatures,
+        )
+        .map_err(|e| e.to_string())?;
+        let weight = signed_weight(signers, &valid);
+        if weight < required {
+            return Err(format!(
+                "{} valid signatures weighing {}, {} required",
+                valid.len(),
+                weight,
+                required
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_admin_update(
+        &mut self,
+        ticket: &dyn Ticket,
+        sigs: &[SignerWithSignature],
+        request: &TicketFields,
+        block_time: Option<i64>,
+    ) -> Result<(), Rejection> {
+        self.check_ticket(request, block_time)?;
+        self.check_quorum(
+            ticket,
+            sigs,
+            self.admin_signer_set(),
+            self.admin_threshold as u16,
+            block_time,
+        )?;
+        self.use_admin_nonce(
+            request.request_id,
+            ticket.hash(&self.domain_suffix),
+            request.expiry,
+        )
+    }
+
+    fn consume_allowance(
+        &mut self,
+        recipient: &Pubkey,
+        withdrawal: &AssetAmount,
+    ) -> Result<(), Rejection> {
+        let Some((cap, consumed)) = self
+            .allowances
+            .get_mut(&(*recipient, withdrawal.asset.clone()))
+        else {
+            return Ok(());
+        };
+        let total = consumed.saturating_add(withdrawal.amount);
+        if total > *cap {
+            return Err(format!(
+                "{} withdrew {} of {:?} beyond its allowance ({} of {} used)",
+                recipient, withdrawal.amount, withdrawal.asset, consumed, cap
+            ));
+        }
+        *consumed = total;
+        Ok(())
+    }
+
+    fn use_nonce(
+        &mut self,
+        request_id: u64,
+        ticket_hash: [u8; 32],
+        expiry: i64,
+    ) -> Result<(), Rejection> {
+        if request_id <= self.request_id_watermark {
+            return Err(format!(
+                "request_id {} is at or below the watermark {}",
+                request_id, self.request_id_watermark
+            ));
+        }
+        if let Some(used_by) = self.nonces.get(&request_id) {
+            return Err(nonce_reuse("request_id", request_id, used_by, &ticket_hash));
+        }
+        self.nonces.insert(request_id, ticket_hash);
+        self.nonce_expiries.insert((false, request_id), expiry);
+        if self.watermark_replay {
+            self.request_id_watermark = request_id;
+        }
+        self.record_processed(NONCE_SEED, request_id);
+        Ok(())
+    }
+
+    /// Whether `withdraw` records `request_id` as a leaf of the nonce tree.
+    pub fn in_nonce_tree(&self, request_id: u64) -> bool {
+        self.nonce_tree != Pubkey::default()
+            && request_id
+                .checked_sub(self.nonce_tree_base)
+                .is_some_and(|index| index < 1u64 << self.nonce_tree_depth)
+    }
+
+    /// A leaf of the nonce tree is set once; it has no account to expire or close.
+    fn use_nonce_tree_leaf(
+        &mut self,
+        request_id: u64,
+        ticket_hash: [u8; 32],
+    ) -> Result<(), Rejection> {
+        if let Some(used_by) = self.nonces.get(&request_id) {
+            return Err(nonce_reuse("request_id", request_id, used_by, &ticket_hash));
+        }
+        self.nonces.insert(request_id, ticket_hash);
+        self.record_processed(NONCE_SEED, request_id);
+        Ok(())
+    }
+
+    fn use_admin_nonce(
+        &mut self,
+        request_id: u64,
+        ticket_hash: [u8; 32],
+        expiry: i64,
+    ) -> Result<(), Rejection> {
+        if let Some(used_by) = self.admin_nonces.get(&request_id) {
+            return Err(nonce_reuse(
+                "admin request_id",
+                request_id,
+                used_by,
+                &ticket_hash,
+            ));
+        }
+        self.admin_nonces.insert(request_id, ticket_hash);
+        self.nonce_expiries.insert((true, request_id), expiry);
+        self.record_processed(ADMIN_NONCE_SEED, request_id);
+        Ok(())
+    }
+
+    fn record_processed(&mut self, nonce_seed: &[u8], request_id: u64) {
+        self.processed_root = next_processed_root(&self.processed_root, nonce_seed, request_id);
+        self.processed_count += 1;
+    }
+
+    /// Stream claims and schedule executions pay registered crankers from the treasury.
+    pub fn pay_crank_incentive(
+        &self,
+        cranker: &Pubkey,
+        expected: &mut Expected,
+    ) -> Result<(), Rejection> {
+        if !self.has_crank_registry {
+            return Ok(());
+        }
+        if !self.crankers.contains(cranker) {
+            return Err(format!("{} cranked without being registered", cranker));
+        }
+        expected.debit_sol(self.crank_incentive);
+        Ok(())
+    }
+
+    fn check_recipient_allowed(&self, recipient: &Pubkey) -> Result<(), Rejection> {
+        if self.recipient_allowlist && !self.allowlisted_recipients.contains(recipient) {
+            return Err(format!("recipient {} not on the allowlist", recipient));
+        }
+        Ok(())
+    }
+
+    fn check_withdrawal_limits(&self, withdrawals: &[AssetAmount]) -> Result<(), Rejection> {
+        for withdrawal in withdrawals {
+            let limit = self.settings(&withdrawal.asset).withdrawal_limit;
+            if limit != 0 && withdrawal.amount > limit {
+                return Err(format!(
+                    "withdrawal of {} {:?} above the limit {}",
+                    withdrawal.amount, withdrawal.asset, limit
+                ));
+            }
+            if let Some((_, min)) = self
+                .min_withdrawals
+                .iter()
+                .find(|(asset, _)| *asset == withdrawal.asset)
+            {
+                if withdrawal.amount < *min {
+                    return Err(format!(
+                        "withdrawal of {} {:?} below the minimum {}",
+                        withdrawal.amount, withdrawal.asset, min
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn settings(&self, asset: &Asset) -> AssetSettings {
+        self.asset_settings.get(asset).copied().unwrap_or_default()
+    }
+
+    fn check_deposit(&self, deposit: &AssetAmount) -> Result<(), Rejection> {
+        self.check_whitelisted(&deposit.asset)?;
+        let settings = self.settings(&deposit.asset);
+        if settings.paused {
+            return Err(format!("deposits of {:?} are paused", deposit.asset));
+        }
+        if deposit.amount < settings.min_deposit {
+            return Err(format!(
+                "deposit of {} {:?} below the minimum {}",
+                deposit.amount, deposit.asset, settings.min_deposit
+            ));
+        }
+        if settings.deposit_cap != 0 && deposit.amount > settings.deposit_cap {
+            return Err(format!(
+                "deposit of {} {:?} above the cap {}",
+                deposit.amount, deposit.asset, settings.deposit_cap
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_whitelisted(&self, asset: &Asset) -> Result<(), Rejection> {
+        if !self.whitelisted_assets.contains(asset) {
+            return Err(format!("asset {:?} is not whitelisted", asset));
+        }
+        Ok(())
+    }
+}
+
+/// Fields shared by every ticket type, pulled out so checks can be written once.
+pub struct TicketFields {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+/// A consumed request id seen again: a replay of the same ticket, or a different ticket signed
+/// under it, which the program rejects with `RequestIdConflict`.
+fn nonce_reuse(kind: &str, request_id: u64, used_by: &[u8; 32], ticket_hash: &[u8; 32]) -> String {
+    if used_by == &[0; 32] || used_by == ticket_hash {
+        format!("{} {} was already used", kind, request_id)
+    } else {
+        format!(
+            "{} {} was already used by ticket {}",
+            kind,
+            request_id,
+            hex(*used_by)
+        )
+    }
+}
+
+fn weighted_hex(signer: &WeightedSigner) -> String {
+    format!("{}:{}", hex(signer.address), signer.weight)
+}
+
+fn hex(bytes: impl AsRef<[u8]>) -> String {
+    let mut out = String::from("0x");
+    for byte in bytes.as_ref() {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
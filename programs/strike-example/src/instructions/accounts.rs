@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 
 use super::constant::*;
-use super::models::Asset;
+use super::errors::ErrorCode;
+use super::models::{
+    Asset, AssetAmount, AssetConfig, AuthScheme, LogVerbosity, PriceOracle, WeightedSigner,
+};
+use super::util::{fee_for, next_processed_root};
 
 #[account]
 #[derive(InitSpace)]
@@ -14,16 +18,574 @@ pub struct Vault {
     pub admin_threshold: u8,
     pub network_id: u64,
     #[max_len(MAX_SIGNERS)]
-    pub signers: Vec<[u8; 20]>, // 4 + N*20 - Ethereum addresses of authorized signers
+    pub signers: Vec<WeightedSigner>, // 4 + N*30 - operational signers, approve withdrawals
     #[max_len(MAX_ASSETS)]
-    pub whitelisted_assets: Vec<Asset>,
+    pub whitelisted_assets: Vec<AssetConfig>, // per-asset limits, in whitelisting order
     pub bump: u8,          // 1 - PDA bump
     pub treasury_bump: u8, // 1 - Treasury PDA bump
     pub reserve: [u8; 64], // reserve 64 bits for this version. Update the limit according to your need.
+    // Version 2 fields. `migrate` converts version 1 vaults, which end at `reserve`.
+    #[max_len(MAX_SIGNERS)]
+    pub admin_signers: Vec<WeightedSigner>, // 4 + N*30 - admin op approvers, empty to share signers
+    #[max_len(MAX_ASSETS)]
+    pub min_withdrawals: Vec<AssetAmount>, // smallest ticket line per whitelisted asset
+    #[max_len(MAX_DOMAIN_SUFFIX_LEN)]
+    pub domain_suffix: String, // 4 + 32 - mixed into every ticket domain separator, immutable
+    pub log_verbosity: LogVerbosity, // 1 - itemized or compact instruction logs
+    pub processed_root: [u8; 32],    // 32 - hash chain over every consumed request id
+    pub processed_count: u64,        // 8 - number of request ids folded into processed_root
+    pub fee_bps: u16,                // 2 - protocol fee taken from each withdrawal line
+    pub fee_treasury_bump: u8,       // 1 - Fee treasury PDA bump, set by set_fee
+    pub fee_mint: Pubkey,            // 32 - SPL mint fees are paid in, default to pay in kind
+    pub max_relayer_rebate: u64,     // 8 - cap on lamports refunded to withdrawal payers
+    pub recipient_allowlist: bool,   // 1 - only pay recipients on the allowlist PDA
+    pub has_blocklist: bool,         // 1 - blocklist PDA exists, withdrawals must pass it
+    pub has_crank_registry: bool,    // 1 - crank registry PDA exists, only its crankers may crank
+    pub max_ticket_lifetime: u64,    // 8 - cap in seconds on a ticket's expiry - now, 0 for none
+    pub parent: Pubkey,              // 32 - vault that created this sub-vault, default if none
+    pub guardian: Pubkey,            // 32 - may veto withdrawals, default if none
+    pub hook_program: Pubkey,        // 32 - notified of deposits and withdrawals, default if none
+    pub auth_scheme: AuthScheme,     // 1 + 32 - what approves tickets, Secp256k1 by default
+    pub has_usd_caps: bool,          // 1 - some asset has a USD cap PDA, withdrawals must pass it
+    pub max_ticket_usd: u64,         // 8 - cap in micro-dollars on a withdrawal ticket, 0 for none
+    pub deposit_registry: Pubkey,    // 32 - program owning depositor attestations, default if none
+    pub deposit_schema: Pubkey,      // 32 - schema a depositor's attestation must carry
+    pub identity_network: Pubkey, // 32 - gatekeeper network recipients need a pass from, default if none
+    pub strict_signatures: bool, // 1 - reject tickets with any bad signature instead of skipping it
+    pub has_signer_set: bool,    // 1 - signers live in the signer set PDA, `signers` is empty
+    pub treasury_reserve: u64, // 8 - lamports above rent exemption withdrawals must leave in the treasury
+    pub sequence: u64, // 8 - bumped once by every state-changing instruction, carried by its events
+    pub watermark_replay: bool, // 1 - withdrawal request ids must exceed the watermark, nonce PDAs optional
+    pub request_id_watermark: u64, // 8 - withdrawal request ids at or below it are rejected, 0 for none
+    #[max_len(MAX_EXTRA_NETWORK_IDS)]
+    pub extra_network_ids: Vec<u64>, // 4 + 8*K - network ids tickets may carry besides network_id
+    pub genesis_hash: [u8; 32],    // 32 - cluster network_id is derived from, zero when not bound
+    pub expiry_grace: u64, // 8 - seconds past its expiry a ticket still runs, absorbing clock skew
+    pub nonce_tree: Pubkey, // 32 - concurrent Merkle tree `withdraw` records request ids in, default if none
+    pub nonce_tree_base: u64, // 8 - request id recorded at the nonce tree's leaf 0
+    pub nonce_tree_depth: u8, // 1 - nonce tree depth, it covers 2^depth request ids
+}
+
+impl Vault {
+    /// Protocol fee on a withdrawal line of `amount`, rounded down.
+    pub fn withdrawal_fee(&self, amount: u64) -> u64 {
+        fee_for(amount, self.fee_bps)
+    }
+
+    /// Whether a withdrawal line of `asset` pays its protocol fee in kind, rather than in
+    /// `fee_mint` through `pay_fee_in_fee_mint`.
+    pub fn pays_fee_in_kind(&self, asset: &Asset) -> bool {
+        self.fee_mint == Pubkey::default()
+            || *asset
+                == (Asset::SplToken {
+                    mint: self.fee_mint,
+                })
+    }
+
+    /// Advance `sequence` for the running instruction and return it. Called once per
+    /// state-changing instruction, so indexers can spot a missed event by a gap.
+    pub fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    /// First seed of this vault's PDA: `"vault"`, or `"sub_vault"` for a sub-vault.
+    pub fn seed_namespace(&self) -> &'static [u8] {
+        if self.parent == Pubkey::default() {
+            VAULT_SEED
+        } else {
+            SUB_VAULT_SEED
+        }
+    }
+
+    /// Seed between the namespace and `vault_seed`: a sub-vault's parent, empty otherwise. An
+    /// empty seed adds nothing to the derivation, so top-level vaults keep `["vault", vault_seed]`.
+    pub fn seed_parent(&self) -> &[u8] {
+        if self.parent == Pubkey::default() {
+            &[]
+        } else {
+            self.parent.as_ref()
+        }
+    }
+
+    /// Signers approving admin ops (`admin_threshold`): the admin set, or the operational set
+    /// while no separate admin set is configured.
+    pub fn admin_signer_set(&self) -> &[WeightedSigner] {
+        if self.admin_signers.is_empty() {
+            &self.signers
+        } else {
+            &self.admin_signers
+        }
+    }
+
+    /// Whether tickets signed for `network_id` are accepted: the vault's own network id or one of
+    /// its `extra_network_ids`.
+    pub fn accepts_network_id(&self, network_id: u64) -> bool {
+        self.network_id == network_id || self.extra_network_ids.contains(&network_id)
+    }
+
+    /// Whether a ticket expiring at `expiry` may run at `now` under `max_ticket_lifetime`.
+    pub fn is_ticket_lifetime_allowed(&self, expiry: i64, now: i64) -> bool {
+        self.max_ticket_lifetime == 0
+            || expiry <= now.saturating_add_unsigned(self.max_ticket_lifetime)
+    }
+
+    /// Whether a ticket expiring at `expiry` may still run at `now`, up to `expiry_grace` seconds
+    /// late.
+    pub fn is_ticket_unexpired(&self, expiry: i64, now: i64) -> bool {
+        now <= expiry.saturating_add_unsigned(self.expiry_grace)
+    }
+
+    /// Whether `withdraw` records `request_id` as a leaf of the nonce tree instead of in a nonce
+    /// account.
+    pub fn in_nonce_tree(&self, request_id: u64) -> bool {
+        self.nonce_tree != Pubkey::default()
+            && request_id
+                .checked_sub(self.nonce_tree_base)
+                .is_some_and(|index| index < 1u64 << self.nonce_tree_depth)
+    }
+
+    /// Whitelist entry of `asset`, `None` while it isn't whitelisted.
+    pub fn asset_config(&self, asset: &Asset) -> Option<&AssetConfig> {
+        self.whitelisted_assets
+            .iter()
+            .find(|config| config.asset == *asset)
+    }
+
+    pub fn is_whitelisted(&self, asset: &Asset) -> bool {
+        self.asset_config(asset).is_some()
+    }
+
+    /// Check a deposit line of `amount` against `asset`'s whitelist entry.
+    pub fn check_deposit(&self, asset: &Asset, amount: u64) -> Result<()> {
+        let config = self
+            .asset_config(asset)
+            .ok_or(ErrorCode::AssetNotWhitelisted)?;
+        require!(!config.paused, ErrorCode::AssetPaused);
+        require!(amount >= config.min_deposit, ErrorCode::BelowMinDeposit);
+        require!(
+            config.deposit_cap == 0 || amount <= config.deposit_cap,
+            ErrorCode::DepositCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Check the vault's `holdings` of `asset` after a deposit against its TVL cap.
+    pub fn check_tvl_cap(&self, asset: &Asset, holdings: u64) -> Result<()> {
+        if let Some(config) = self.asset_config(asset) {
+            require!(
+                config.tvl_cap == 0 || holdings <= config.tvl_cap,
+                ErrorCode::TvlCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Check a depositor's running total of `asset` against its per-user cap.
+    pub fn check_user_cap(&self, asset: &Asset, deposited: u64) -> Result<()> {
+        if let Some(config) = self.asset_config(asset) {
+            require!(
+                config.user_cap == 0 || deposited <= config.user_cap,
+                ErrorCode::UserCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Smallest withdrawal line accepted for `asset`, 0 when none is set.
+    pub fn min_withdrawal(&self, asset: &Asset) -> u64 {
+        self.min_withdrawals
+            .iter()
+            .find(|min| min.asset == *asset)
+            .map_or(0, |min| min.amount)
+    }
+
+    /// Replay check a withdrawal request id must pass on top of its nonce account, if it has one:
+    /// ids at or below `request_id_watermark` are rejected, and under watermark replay the id
+    /// becomes the new watermark.
+    pub fn consume_request_id(&mut self, request_id: u64) -> Result<()> {
+        require!(
+            request_id > self.request_id_watermark,
+            ErrorCode::RequestIdBelowWatermark
+        );
+        if self.watermark_replay {
+            self.request_id_watermark = request_id;
+        }
+        Ok(())
+    }
+
+    /// Fold a consumed request id into `processed_root`, see [`next_processed_root`].
+    pub fn record_processed(&mut self, nonce_seed: &[u8], request_id: u64) -> Result<()> {
+        self.processed_root = next_processed_root(&self.processed_root, nonce_seed, request_id);
+        self.processed_count = self
+            .processed_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+/// A `Vault` as version 1 of the program wrote it, read by `migrate`. Signers were bare
+/// addresses and the whitelist bare assets.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct VaultV1 {
+    pub version: u8,
+    pub authority: Pubkey,
+    #[max_len(32)]
+    pub vault_seed: String,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub network_id: u64,
+    #[max_len(MAX_SIGNERS)]
+    pub signers: Vec<[u8; 20]>,
+    #[max_len(MAX_ASSETS)]
+    pub whitelisted_assets: Vec<Asset>,
+    pub bump: u8,
+    pub treasury_bump: u8,
+    pub reserve: [u8; 64],
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct NonceAccount {
     pub used: bool,
+    pub ticket_hash: [u8; 32], // ticket that consumed the request id, zero when vetoed
+    pub expiry: i64,           // that ticket's expiry, i64::MAX when vetoed
+}
+
+impl NonceAccount {
+    /// Consume the request id for the ticket hashing to `ticket_hash` and expiring at `expiry`
+    /// (when `close_expired_nonce` may collect it). Replaying that ticket fails
+    /// with `NonceAlreadyUsed`, another ticket signed under the same request id with
+    /// `RequestIdConflict`.
+    pub fn consume(&mut self, ticket_hash: [u8; 32], expiry: i64) -> Result<()> {
+        require!(
+            !self.used || self.ticket_hash == [0; 32] || self.ticket_hash == ticket_hash,
+            ErrorCode::RequestIdConflict
+        );
+        require!(!self.used, ErrorCode::NonceAlreadyUsed);
+
+        self.used = true;
+        self.ticket_hash = ticket_hash;
+        self.expiry = expiry;
+        Ok(())
+    }
+}
+
+/// Cumulative deposits of one depositor into one vault, net of any transfer fee.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositRecord {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    #[max_len(MAX_ASSETS)]
+    pub deposits: Vec<AssetAmount>, // running total per asset, in first-deposit order
+    pub bump: u8,
+}
+
+impl DepositRecord {
+    /// Fill in the owning vault and user the first time the record is used.
+    pub fn open(&mut self, vault: Pubkey, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.vault = vault;
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    /// Add `amount` to the running total of `asset` and return the new total.
+    pub fn credit(&mut self, asset: &Asset, amount: u64) -> Result<u64> {
+        match self.deposits.iter_mut().find(|total| total.asset == *asset) {
+            Some(total) => {
+                total.amount = total
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::Overflow)?;
+                Ok(total.amount)
+            }
+            None => {
+                require!(
+                    self.deposits.len() < MAX_ASSETS as usize,
+                    ErrorCode::DepositRecordFull
+                );
+                self.deposits.push(AssetAmount {
+                    asset: asset.clone(),
+                    amount,
+                });
+                Ok(amount)
+            }
+        }
+    }
+}
+
+/// Per-recipient withdrawal limit for one asset, set by `admin_threshold` validators. Checked by
+/// `withdraw`, `withdraw_wsol`, `bulk_withdraw` and `claim`; a recipient without one is uncapped.
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub cap: u64,      // lifetime limit, in ticket (net) amounts
+    pub consumed: u64, // withdrawn so far
+    pub bump: u8,
+}
+
+/// Recipients withdrawals may pay while the vault is in allowlist mode (`[b"recipient_allowlist",
+/// vault]`), managed by `admin_threshold` validators.
+#[account]
+#[derive(InitSpace)]
+pub struct RecipientAllowlist {
+    pub vault: Pubkey,
+    #[max_len(MAX_ALLOWLISTED_RECIPIENTS)]
+    pub recipients: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Recipients withdrawals refuse to pay (`[b"blocklist", vault]`), managed by `admin_threshold`
+/// validators. Created by the first `block_recipient`.
+#[account]
+#[derive(InitSpace)]
+pub struct Blocklist {
+    pub vault: Pubkey,
+    #[max_len(MAX_BLOCKED_RECIPIENTS)]
+    pub recipients: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// External programs `execute_strategy` may invoke with the vault PDA as signer
+/// (`[b"strategy_allowlist", vault]`), managed by `admin_threshold` validators.
+#[account]
+#[derive(InitSpace)]
+pub struct StrategyAllowlist {
+    pub vault: Pubkey,
+    #[max_len(MAX_STRATEGY_PROGRAMS)]
+    pub programs: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Keepers allowed to run crank instructions (`execute_schedule`, `claim_stream`) once it
+/// exists (`[b"crank_registry", vault]`), and the treasury lamports each successful crank earns.
+/// Managed by `admin_threshold` validators.
+#[account]
+#[derive(InitSpace)]
+pub struct CrankRegistry {
+    pub vault: Pubkey,
+    #[max_len(MAX_CRANKERS)]
+    pub crankers: Vec<Pubkey>,
+    pub incentive: u64, // lamports paid to the cranker per successful crank
+    pub bump: u8,
+}
+
+/// A published payout batch (`[b"claim_root", vault, request_id]`), redeemed leaf by leaf.
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimRoot {
+    pub vault: Pubkey,
+    pub request_id: u64,
+    pub root: [u8; 32],
+    pub claim_expiry: i64,
+    pub claimed: u64, // leaves redeemed so far
+    pub bump: u8,
+}
+
+/// Marks one leaf of a claim root as redeemed (`[b"claimed", claim_root, index]`).
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimedLeaf {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// What one executed withdrawal ticket paid (`[b"withdrawal_receipt", vault, request_id]`),
+/// created when the relayer passes it to `withdraw` or `withdraw_wsol`.
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalReceipt {
+    pub vault: Pubkey,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    #[max_len(MAX_RECEIPT_WITHDRAWALS)]
+    pub withdrawals: Vec<AssetAmount>, // ticket amounts, empty when the recipient was blocked
+    pub slot: u64,
+}
+
+impl WithdrawalReceipt {
+    /// Fill in the receipt, failing with `TooManyReceiptWithdrawals` for more lines than it holds.
+    pub fn record(
+        &mut self,
+        vault: Pubkey,
+        request_id: u64,
+        recipient: Pubkey,
+        withdrawals: &[AssetAmount],
+        slot: u64,
+    ) -> Result<()> {
+        require!(
+            withdrawals.len() <= MAX_RECEIPT_WITHDRAWALS,
+            ErrorCode::TooManyReceiptWithdrawals
+        );
+        self.vault = vault;
+        self.request_id = request_id;
+        self.recipient = recipient;
+        self.withdrawals = withdrawals.to_vec();
+        self.slot = slot;
+        Ok(())
+    }
+}
+
+/// Funds locked for one recipient and released over time (`[b"stream", vault, request_id]`).
+/// SOL is held as the account's own lamports, SPL tokens in its associated token account.
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub vault: Pubkey,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub total: u64,   // locked at creation, net of any transfer fee
+    pub claimed: u64, // paid out so far
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub rate: u64, // base units released per second
+    pub bump: u8,
+}
+
+impl Stream {
+    /// Amount released by `now`: nothing before the cliff, then `rate` per second since the
+    /// start, up to `total`.
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff_time {
+            return 0;
+        }
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u64;
+        elapsed.saturating_mul(self.rate).min(self.total)
+    }
+}
+
+/// A recurring payment out of the vault (`[b"schedule", vault, request_id]`), paid by
+/// `execute_schedule` each time `next_execution` passes.
+#[account]
+#[derive(InitSpace)]
+pub struct Schedule {
+    pub vault: Pubkey,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,   // per payment, received by the recipient
+    pub interval: i64, // seconds between payments
+    pub next_execution: i64,
+    pub remaining: u32, // payments left
+    pub bump: u8,
+}
+
+/// A treasury PDA next to the vault's default one (`[b"treasury", vault, name]`), e.g. "hot" or
+/// "cold", holding SOL that withdrawals naming it pay out with their own threshold. Created and
+/// updated by `set_treasury`.
+#[account]
+#[derive(InitSpace)]
+pub struct NamedTreasury {
+    pub vault: Pubkey,
+    #[max_len(MAX_TREASURY_NAME_LEN)]
+    pub name: String,
+    pub m_threshold: u8, // signed weight withdrawals from this treasury need, instead of the vault's
+    pub bump: u8,
+}
+
+/// The Wormhole emitter whose VAAs `withdraw_with_vaa` accepts as withdrawal tickets
+/// (`[b"wormhole_emitter", vault]`), and the core bridge that must have posted them. Created and
+/// updated by `set_wormhole_emitter`.
+#[account]
+#[derive(InitSpace)]
+pub struct WormholeEmitter {
+    pub vault: Pubkey,
+    pub core_bridge: Pubkey, // Wormhole core bridge program owning posted VAAs
+    pub emitter_chain: u16,  // Wormhole chain id of the emitting contract
+    pub emitter_address: [u8; 32],
+    pub bump: u8,
+}
+
+/// USD pricing of one asset (`[b"usd_cap", vault, asset seed key]`), set by `admin_threshold`
+/// validators. Once any exists, withdrawals price every line and check it against `max_usd`
+/// and the vault's `max_ticket_usd`.
+#[account]
+#[derive(InitSpace)]
+pub struct UsdCap {
+    pub vault: Pubkey,
+    pub asset: Asset,
+    pub oracle: PriceOracle,
+    pub decimals: u8,      // of the asset, read from its mint
+    pub max_usd: u64,      // micro-dollars per withdrawal line, 0 for none
+    pub max_age: u64,      // seconds a price may be old
+    pub max_conf_bps: u16, // confidence interval as a share of the price
+    pub bump: u8,
+}
+
+/// One signer of a `SignerSet`, `WeightedSigner` laid out for zero-copy access.
+#[zero_copy]
+pub struct SignerSlot {
+    pub valid_until: i64, // read only while has_valid_until is 1
+    pub address: [u8; 20],
+    pub weight: u8,
+    pub has_valid_until: u8,
+    pub _padding: [u8; 2],
+}
+
+impl From<WeightedSigner> for SignerSlot {
+    fn from(signer: WeightedSigner) -> Self {
+        SignerSlot {
+            valid_until: signer.valid_until.unwrap_or_default(),
+            address: signer.address,
+            weight: signer.weight,
+            has_valid_until: signer.valid_until.is_some() as u8,
+            _padding: [0; 2],
+        }
+    }
+}
+
+impl From<SignerSlot> for WeightedSigner {
+    fn from(slot: SignerSlot) -> Self {
+        WeightedSigner {
+            address: slot.address,
+            weight: slot.weight,
+            valid_until: (slot.has_valid_until != 0).then_some(slot.valid_until),
+        }
+    }
+}
+
+/// Operational signers of a vault past `MAX_SIGNERS` (`[b"signer_set", vault]`), up to
+/// `MAX_SIGNER_SET_LEN`. Too large to travel in one ticket, a set is proposed by hash with
+/// `admin_threshold`, uploaded in chunks into the staged half and swapped in on activation, so
+/// the active half keeps approving tickets meanwhile.
+#[account(zero_copy)]
+pub struct SignerSet {
+    pub vault: Pubkey,
+    pub uploader: Pubkey, // payer of the proposal, the only one who may upload and activate
+    pub slots: [[SignerSlot; MAX_SIGNER_SET_LEN as usize]; 2], // active and staged halves
+    pub pending_hash: [u8; 32], // `signer_set_hash` of the proposed set
+    pub pending_request_id: u64,
+    pub lens: [u8; 2],   // signers in each half
+    pub active: u8,      // index of the active half
+    pub pending_len: u8, // 0 while no proposal is pending
+    pub pending_m_threshold: u8,
+    pub pending_admin_threshold: u8,
+    pub pending_valid_signers: u8,
+    pub bump: u8,
+}
+
+impl SignerSet {
+    pub fn active_signers(&self) -> Vec<WeightedSigner> {
+        let active = self.active as usize;
+        self.slots[active][..self.lens[active] as usize]
+            .iter()
+            .map(|slot| WeightedSigner::from(*slot))
+            .collect()
+    }
+
+    pub fn staged_signers(&self) -> Vec<WeightedSigner> {
+        let staged = 1 - self.active as usize;
+        self.slots[staged][..self.lens[staged] as usize]
+            .iter()
+            .map(|slot| WeightedSigner::from(*slot))
+            .collect()
+    }
 }
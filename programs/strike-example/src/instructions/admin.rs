@@ -1,49 +1,127 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 use super::accounts::*;
 use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
-use super::util::validate_sigs;
+use super::util::{
+    asset_decimals, authorize, check_auth_scheme, check_duplicate_assets, check_nft_amount,
+    check_signer_set, check_threshold, find_associated_token_account, find_mint,
+    gross_amount_for_net, is_supported_ticket_version, is_ticket_active, load_signer_set,
+    transfer_checked_with_hook, transfer_lamports, Quorum,
+};
 
-pub fn add_asset(
-    ctx: Context<AddAsset>,
+/// Whitelist `ticket.asset` with the ticket's limits. Remaining accounts: the mint of an SPL
+/// asset, for its decimals. Adding a whitelisted asset changes nothing; see `update_asset`.
+pub fn add_asset<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddAsset<'info>>,
     ticket: AddAssetTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
-    check_before_admin_update(
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
         &ctx.accounts.vault,
         &ticket,
         &signers_with_sigs,
+        ctx.remaining_accounts,
         &ticket.vault,
         ticket.expiry,
+        ticket.not_before,
         ticket.network_id,
+        ticket.version,
     )?;
 
     let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
-
-    nonce_account.used = true;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
     let vault = &mut ctx.accounts.vault;
 
-    for existing in &vault.whitelisted_assets {
-        if *existing == ticket.asset {
-            msg!(
-                "Admin request {:?}: asset exists in whitelist: {:?}",
-                ticket.request_id,
-                ticket.asset
-            );
-            return Ok(()); // early return since exist.
-        }
+    let added = !vault.is_whitelisted(&ticket.asset);
+    if added {
+        require!(
+            vault.whitelisted_assets.len() < MAX_ASSETS as usize,
+            ErrorCode::TooManyAssets
+        );
+        let decimals = asset_decimals(ctx.remaining_accounts, &ticket.asset)?;
+        vault.whitelisted_assets.push(AssetConfig::new(
+            ticket.asset.clone(),
+            decimals,
+            &ticket.settings,
+        ));
     }
 
-    vault.whitelisted_assets.push(ticket.asset.clone());
-    msg!(
-        "Admin request {:?}: asset added to whitelist: {:?}",
-        ticket.request_id,
-        ticket.asset
-    );
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::AddAsset {
+            asset: ticket.asset,
+            settings: ticket.settings,
+            added,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Replace the limits of a whitelisted asset.
+pub fn update_asset(
+    ctx: Context<UpdateAsset>,
+    ticket: UpdateAssetTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    let config = vault
+        .whitelisted_assets
+        .iter_mut()
+        .find(|config| config.asset == ticket.asset)
+        .ok_or(ErrorCode::AssetNotWhitelisted)?;
+    config.apply(&ticket.settings);
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::UpdateAsset {
+            asset: ticket.asset,
+            settings: ticket.settings,
+        },
+        valid_signers,
+    });
 
     Ok(())
 }
@@ -53,204 +131,3719 @@ pub fn remove_asset(
     ticket: RemoveAssetTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
-    check_before_admin_update(
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
         &ctx.accounts.vault,
         &ticket,
         &signers_with_sigs,
+        ctx.remaining_accounts,
         &ticket.vault,
         ticket.expiry,
+        ticket.not_before,
         ticket.network_id,
+        ticket.version,
     )?;
+    // Delisting must not strand tokens the vault still holds.
+    if let Asset::SplToken { mint } | Asset::Nft { mint } = &ticket.asset {
+        require!(
+            vault_token_balance(ctx.remaining_accounts, &ctx.accounts.vault.key(), mint)? == 0,
+            ErrorCode::TokenAccountNotEmpty
+        );
+    }
 
     let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
-
-    nonce_account.used = true;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
     let vault = &mut ctx.accounts.vault;
 
     let pos = vault
         .whitelisted_assets
         .iter()
-        .position(|a| *a == ticket.asset);
+        .position(|config| config.asset == ticket.asset);
 
     if let Some(pos) = pos {
         vault.whitelisted_assets.remove(pos);
-        msg!(
-            "Admin request {:?}: asset removed from whitelist: {:?}",
-            ticket.request_id,
-            ticket.asset
-        );
-    } else {
-        msg!(
-            "Admin request {:?}: asset not found: {:?}",
-            ticket.request_id,
-            ticket.asset
-        );
     }
+    vault
+        .min_withdrawals
+        .retain(|min| min.asset != ticket.asset);
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::RemoveAsset {
+            asset: ticket.asset,
+            removed: pos.is_some(),
+        },
+        valid_signers,
+    });
 
     Ok(())
 }
 
-pub fn rotate_validators(
-    ctx: Context<RotateValidator>,
-    ticket: RotateValidatorTicket,
+/// Balance of the vault's associated token account for `mint`, 0 while the account doesn't
+/// exist. The mint and the account are looked up in `accounts`.
+fn vault_token_balance(accounts: &[AccountInfo], vault: &Pubkey, mint: &Pubkey) -> Result<u64> {
+    let mint_info = accounts
+        .iter()
+        .find(|acc| acc.key == mint)
+        .ok_or(ErrorCode::MintAccountNotFound)?;
+    // The mint's owner picks the ATA, so it has to be a real token program.
+    require!(
+        *mint_info.owner == anchor_spl::token::ID || *mint_info.owner == anchor_spl::token_2022::ID,
+        ErrorCode::MintAccountNotFound
+    );
+    let expected = get_associated_token_address_with_program_id(vault, mint, mint_info.owner);
+    let account = accounts
+        .iter()
+        .find(|acc| *acc.key == expected)
+        .ok_or(ErrorCode::TokenAccountNotFound)?;
+    if account.data_is_empty() {
+        return Ok(0);
+    }
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(token_account.amount)
+}
+
+/// Set the smallest amount a withdrawal line of a whitelisted asset may carry. Tickets with a
+/// line below it are rejected with `BelowMinWithdrawal`.
+pub fn set_min_withdrawal(
+    ctx: Context<SetMinWithdrawal>,
+    ticket: SetMinWithdrawalTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
-    let signers_len = ticket.signers.len();
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
     require!(
-        signers_len > 0 && signers_len <= MAX_SIGNERS,
-        ErrorCode::InvalidSignersCount
-    );
-    require!(
-        ticket.m_threshold > 0 && (ticket.m_threshold as usize) <= signers_len,
-        ErrorCode::InvalidThreshold
-    );
-    require!(
-        ticket.admin_threshold > 0 && (ticket.admin_threshold as usize) <= signers_len,
-        ErrorCode::InvalidThreshold
+        vault.is_whitelisted(&ticket.asset),
+        ErrorCode::AssetNotWhitelisted
     );
 
-    // Check for duplicate signers
-    for i in 0..signers_len {
-        for j in (i + 1)..signers_len {
-            require!(
-                ticket.signers[i] != ticket.signers[j],
-                ErrorCode::DuplicateSigner
-            );
-        }
+    vault
+        .min_withdrawals
+        .retain(|min| min.asset != ticket.asset);
+    if ticket.min_amount > 0 {
+        vault.min_withdrawals.push(AssetAmount {
+            asset: ticket.asset.clone(),
+            amount: ticket.min_amount,
+        });
     }
 
-    check_before_admin_update(
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetMinWithdrawal {
+            asset: ticket.asset,
+            min_amount: ticket.min_amount,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Price a whitelisted asset in USD through `ticket.oracle`, capping each withdrawal line of it
+/// at `max_usd`. From then on every withdrawal must pass the USD cap PDAs of its assets.
+pub fn set_usd_cap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetUsdCap<'info>>,
+    ticket: SetUsdCapTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
         &ctx.accounts.vault,
         &ticket,
         &signers_with_sigs,
+        ctx.remaining_accounts,
         &ticket.vault,
         ticket.expiry,
+        ticket.not_before,
         ticket.network_id,
+        ticket.version,
     )?;
 
     let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    require!(
+        vault.is_whitelisted(&ticket.asset),
+        ErrorCode::AssetNotWhitelisted
+    );
+
+    let decimals = asset_decimals(ctx.remaining_accounts, &ticket.asset)?;
+    vault.has_usd_caps = true;
+
+    let usd_cap = &mut ctx.accounts.usd_cap;
+    usd_cap.vault = vault.key();
+    usd_cap.asset = ticket.asset.clone();
+    usd_cap.oracle = ticket.oracle;
+    usd_cap.decimals = decimals;
+    usd_cap.max_usd = ticket.max_usd;
+    usd_cap.max_age = ticket.max_age;
+    usd_cap.max_conf_bps = ticket.max_conf_bps;
+    usd_cap.bump = ctx.bumps.usd_cap;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetUsdCap {
+            asset: ticket.asset,
+            oracle: ticket.oracle,
+            max_usd: ticket.max_usd,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Cap the summed USD value of a withdrawal ticket. While set, every withdrawn asset must have
+/// a USD cap.
+pub fn set_max_ticket_usd(
+    ctx: Context<SetMaxTicketUsd>,
+    ticket: SetMaxTicketUsdTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
-    nonce_account.used = true;
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
     let vault = &mut ctx.accounts.vault;
-    vault.m_threshold = ticket.m_threshold;
-    vault.admin_threshold = ticket.admin_threshold;
-    vault.signers = ticket.signers;
+    vault.max_ticket_usd = ticket.max_ticket_usd;
 
-    msg!(
-        "Admin request {:?}: rotate validators: {:?}, m_threshold: {:?}, admin_threshold: {:?}",
-        ticket.request_id,
-        signers_len,
-        ticket.m_threshold,
-        ticket.admin_threshold,
-    );
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetMaxTicketUsd {
+            max_ticket_usd: ticket.max_ticket_usd,
+        },
+        valid_signers,
+    });
 
     Ok(())
 }
 
-fn check_before_admin_update(
-    vault: &Account<Vault>,
-    ticket: &dyn Ticket,
-    signers_with_sigs: &Vec<SignerWithSignature>,
-    ticket_vault: &Pubkey,
-    ticket_expire: i64,
-    ticket_network_id: u64,
+pub fn add_recipient(
+    ctx: Context<AddRecipient>,
+    ticket: AddRecipientTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
-    let clock = Clock::get()?;
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
-    require!(ticket_vault == &vault.key(), ErrorCode::InvalidVault);
-    require!(
-        clock.unix_timestamp <= ticket_expire,
-        ErrorCode::TicketExpired
-    );
-    require!(
-        vault.network_id == ticket_network_id,
-        ErrorCode::InvalidNetwork
-    );
-    require!(
-        signers_with_sigs.len() >= vault.admin_threshold as usize,
-        ErrorCode::InsufficientSignatures
-    );
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
-    // admin update required admin_threshold's signers approve.
-    let validated_sigs = validate_sigs(ticket, signers_with_sigs, &vault.signers);
-    require!(
-        validated_sigs.len() >= vault.admin_threshold as usize,
-        ErrorCode::InsufficientValidSignatures
-    );
+    let allowlist = &mut ctx.accounts.recipient_allowlist;
+    allowlist.vault = ctx.accounts.vault.key();
+    allowlist.bump = ctx.bumps.recipient_allowlist;
+
+    let added = !allowlist.recipients.contains(&ticket.recipient);
+    if added {
+        require!(
+            allowlist.recipients.len() < MAX_ALLOWLISTED_RECIPIENTS,
+            ErrorCode::RecipientAllowlistFull
+        );
+        allowlist.recipients.push(ticket.recipient);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::AddRecipient {
+            recipient: ticket.recipient,
+            added,
+        },
+        valid_signers,
+    });
 
     Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(ticket: AddAssetTicket)]
-pub struct AddAsset<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, Vault>,
+pub fn remove_recipient(
+    ctx: Context<RemoveRecipient>,
+    ticket: RemoveRecipientTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"admin_nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
-        bump
-    )]
-    pub nonce_account: Account<'info, NonceAccount>,
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    let allowlist = &mut ctx.accounts.recipient_allowlist;
+    let pos = allowlist
+        .recipients
+        .iter()
+        .position(|r| *r == ticket.recipient);
 
-    pub system_program: Program<'info, System>,
+    if let Some(pos) = pos {
+        allowlist.recipients.remove(pos);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::RemoveRecipient {
+            recipient: ticket.recipient,
+            removed: pos.is_some(),
+        },
+        valid_signers,
+    });
+
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(ticket: RemoveAssetTicket)]
-pub struct RemoveAsset<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, Vault>,
+/// Turn allowlist mode on or off. While on, withdrawals and claims only pay recipients on the
+/// recipient allowlist.
+pub fn set_recipient_allowlist(
+    ctx: Context<SetRecipientAllowlist>,
+    ticket: SetRecipientAllowlistTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"admin_nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
-        bump
-    )]
-    pub nonce_account: Account<'info, NonceAccount>,
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    let vault = &mut ctx.accounts.vault;
+    vault.recipient_allowlist = ticket.enabled;
 
-    pub system_program: Program<'info, System>,
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetRecipientAllowlist {
+            enabled: ticket.enabled,
+        },
+        valid_signers,
+    });
+
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(ticket: RotateValidatorTicket)]
-pub struct RotateValidator<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, Vault>,
+/// Put `ticket.recipient` on the blocklist. Withdrawals and claims paying a blocked recipient
+/// consume their ticket without paying out and emit a `BlockedRecipientEvent`.
+pub fn block_recipient(
+    ctx: Context<BlockRecipient>,
+    ticket: BlockRecipientTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
 
-    #[account(
-        init,
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+    ctx.accounts.vault.has_blocklist = true;
+
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.vault = ctx.accounts.vault.key();
+    blocklist.bump = ctx.bumps.blocklist;
+
+    let added = !blocklist.recipients.contains(&ticket.recipient);
+    if added {
+        require!(
+            blocklist.recipients.len() < MAX_BLOCKED_RECIPIENTS,
+            ErrorCode::BlocklistFull
+        );
+        blocklist.recipients.push(ticket.recipient);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::BlockRecipient {
+            recipient: ticket.recipient,
+            added,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn unblock_recipient(
+    ctx: Context<UnblockRecipient>,
+    ticket: UnblockRecipientTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let blocklist = &mut ctx.accounts.blocklist;
+    let pos = blocklist
+        .recipients
+        .iter()
+        .position(|r| *r == ticket.recipient);
+
+    if let Some(pos) = pos {
+        blocklist.recipients.remove(pos);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::UnblockRecipient {
+            recipient: ticket.recipient,
+            removed: pos.is_some(),
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Approve `program_id` for `execute_strategy`.
+pub fn add_strategy(
+    ctx: Context<AddStrategy>,
+    ticket: AddStrategyTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.program_id != crate::ID, ErrorCode::InvalidCpiProgram);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let allowlist = &mut ctx.accounts.strategy_allowlist;
+    allowlist.vault = ctx.accounts.vault.key();
+    allowlist.bump = ctx.bumps.strategy_allowlist;
+
+    let added = !allowlist.programs.contains(&ticket.program_id);
+    if added {
+        require!(
+            allowlist.programs.len() < MAX_STRATEGY_PROGRAMS,
+            ErrorCode::StrategyAllowlistFull
+        );
+        allowlist.programs.push(ticket.program_id);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::AddStrategy {
+            program_id: ticket.program_id,
+            added,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn remove_strategy(
+    ctx: Context<RemoveStrategy>,
+    ticket: RemoveStrategyTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let allowlist = &mut ctx.accounts.strategy_allowlist;
+    let pos = allowlist
+        .programs
+        .iter()
+        .position(|p| *p == ticket.program_id);
+
+    if let Some(pos) = pos {
+        allowlist.programs.remove(pos);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::RemoveStrategy {
+            program_id: ticket.program_id,
+            removed: pos.is_some(),
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Register `ticket.cranker` as a keeper. Creating the registry restricts crank instructions to
+/// its crankers from then on.
+pub fn add_cranker(
+    ctx: Context<AddCranker>,
+    ticket: AddCrankerTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+    ctx.accounts.vault.has_crank_registry = true;
+
+    let registry = &mut ctx.accounts.crank_registry;
+    registry.vault = ctx.accounts.vault.key();
+    registry.bump = ctx.bumps.crank_registry;
+
+    let added = !registry.crankers.contains(&ticket.cranker);
+    if added {
+        require!(
+            registry.crankers.len() < MAX_CRANKERS,
+            ErrorCode::CrankRegistryFull
+        );
+        registry.crankers.push(ticket.cranker);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::AddCranker {
+            cranker: ticket.cranker,
+            added,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn remove_cranker(
+    ctx: Context<RemoveCranker>,
+    ticket: RemoveCrankerTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let registry = &mut ctx.accounts.crank_registry;
+    let pos = registry.crankers.iter().position(|c| *c == ticket.cranker);
+
+    if let Some(pos) = pos {
+        registry.crankers.remove(pos);
+    }
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::RemoveCranker {
+            cranker: ticket.cranker,
+            removed: pos.is_some(),
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Pay registered crankers `incentive` lamports from the treasury per successful crank. Creates
+/// the registry if needed, which restricts cranks to its (possibly empty) cranker list.
+pub fn set_crank_incentive(
+    ctx: Context<SetCrankIncentive>,
+    ticket: SetCrankIncentiveTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+    ctx.accounts.vault.has_crank_registry = true;
+
+    let registry = &mut ctx.accounts.crank_registry;
+    registry.vault = ctx.accounts.vault.key();
+    registry.bump = ctx.bumps.crank_registry;
+    registry.incentive = ticket.incentive;
+
+    emit!(AdminOpEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetCrankIncentive {
+            incentive: ticket.incentive,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn rotate_validators(
+    ctx: Context<RotateValidator>,
+    ticket: RotateValidatorTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    check_signer_set(
+        &ticket.signers,
+        ticket.m_threshold,
+        Clock::get()?.unix_timestamp,
+    )?;
+    // admin_threshold applies to whichever set approves admin ops after the rotation.
+    if ctx.accounts.vault.admin_signers.is_empty() {
+        check_threshold(&ticket.signers, ticket.admin_threshold)?;
+    } else {
+        check_threshold(&ctx.accounts.vault.admin_signers, ticket.admin_threshold)?;
+    }
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.m_threshold = ticket.m_threshold;
+    vault.admin_threshold = ticket.admin_threshold;
+    vault.signers = ticket.signers.clone();
+    vault.has_signer_set = false;
+
+    emit!(RotateEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        signers: ticket.signers,
+        m_threshold: ticket.m_threshold,
+        admin_threshold: ticket.admin_threshold,
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Replace the admin signer set and `admin_threshold` without touching the operational set. An
+/// empty list hands admin ops back to the operational signers.
+pub fn rotate_admin_signers(
+    ctx: Context<RotateAdminSigners>,
+    ticket: RotateAdminSignersTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    if ticket.signers.is_empty() {
+        let signers = operational_signers(&ctx.accounts.vault, ctx.remaining_accounts)?;
+        check_threshold(&signers, ticket.admin_threshold)?;
+    } else {
+        check_signer_set(
+            &ticket.signers,
+            ticket.admin_threshold,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.admin_signers = ticket.signers.clone();
+    vault.admin_threshold = ticket.admin_threshold;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::RotateAdminSigners {
+            signers: ticket.signers,
+            admin_threshold: ticket.admin_threshold,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn set_log_verbosity(
+    ctx: Context<SetLogVerbosity>,
+    ticket: SetLogVerbosityTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.log_verbosity = ticket.log_verbosity;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetLogVerbosity {
+            log_verbosity: ticket.log_verbosity,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Set the protocol fee taken from withdrawal lines. With a `fee_mint`, lines in other assets
+/// pay their fee's oracle value in that mint instead, see `pay_fee_in_fee_mint`.
+pub fn set_fee(
+    ctx: Context<SetFee>,
+    ticket: SetFeeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.fee_bps <= MAX_FEE_BPS, ErrorCode::InvalidFee);
+    require!(
+        ticket.fee_mint == Pubkey::default()
+            || ctx
+                .accounts
+                .vault
+                .asset_config(&Asset::SplToken {
+                    mint: ticket.fee_mint,
+                })
+                .is_some(),
+        ErrorCode::InvalidFeeMint
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.fee_bps = ticket.fee_bps;
+    vault.fee_treasury_bump = ctx.bumps.fee_treasury;
+    vault.fee_mint = ticket.fee_mint;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetFee {
+            fee_bps: ticket.fee_bps,
+            fee_mint: ticket.fee_mint,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Refund withdrawal payers their nonce rent and base transaction fee from the treasury, up to
+/// `max_rebate` lamports per instruction.
+pub fn set_relayer_rebate(
+    ctx: Context<SetRelayerRebate>,
+    ticket: SetRelayerRebateTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.max_relayer_rebate = ticket.max_rebate;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetRelayerRebate {
+            max_rebate: ticket.max_rebate,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Cap how far past execution time a ticket's expiry may be, so tickets signed with an
+/// accidentally long expiry are rejected. 0 removes the cap.
+pub fn set_max_ticket_lifetime(
+    ctx: Context<SetMaxTicketLifetime>,
+    ticket: SetMaxTicketLifetimeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.max_ticket_lifetime = ticket.max_lifetime;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetMaxTicketLifetime {
+            max_lifetime: ticket.max_lifetime,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Let tickets run up to `ticket.grace` seconds past their expiry, absorbing clock skew between
+/// validators and the cluster. 0 restores the hard expiry.
+pub fn set_expiry_grace(
+    ctx: Context<SetExpiryGrace>,
+    ticket: SetExpiryGraceTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        ticket.grace <= MAX_EXPIRY_GRACE,
+        ErrorCode::ExpiryGraceTooLong
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.expiry_grace = ticket.grace;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetExpiryGrace {
+            grace: ticket.grace,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Replace the network ids tickets may carry besides the vault's own `network_id`, e.g. for
+/// signers shared between devnet and testnet deployments. An empty list accepts `network_id`
+/// only.
+pub fn set_extra_network_ids(
+    ctx: Context<SetExtraNetworkIds>,
+    ticket: SetExtraNetworkIdsTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        ticket.extra_network_ids.len() <= MAX_EXTRA_NETWORK_IDS,
+        ErrorCode::TooManyNetworkIds
+    );
+    let mut seen = std::collections::HashSet::from([ctx.accounts.vault.network_id]);
+    require!(
+        ticket.extra_network_ids.iter().all(|id| seen.insert(*id)),
+        ErrorCode::DuplicateNetworkId
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.extra_network_ids = ticket.extra_network_ids.clone();
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetExtraNetworkIds {
+            extra_network_ids: ticket.extra_network_ids,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Set the guardian allowed to veto withdrawals, or remove it with the default pubkey.
+pub fn set_guardian(
+    ctx: Context<SetGuardian>,
+    ticket: SetGuardianTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.guardian = ticket.guardian;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetGuardian {
+            guardian: ticket.guardian,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Switch what approves the vault's tickets. The change itself is approved under the scheme in
+/// force, so leaving an attestor takes an attestation.
+pub fn set_auth_scheme(
+    ctx: Context<SetAuthScheme>,
+    ticket: SetAuthSchemeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    check_auth_scheme(&ticket.auth_scheme)?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.auth_scheme = ticket.auth_scheme;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetAuthScheme {
+            auth_scheme: ticket.auth_scheme,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Set the program deposits and withdrawals notify, or remove it with the default pubkey.
+pub fn set_hook_program(
+    ctx: Context<SetHookProgram>,
+    ticket: SetHookProgramTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        ticket.hook_program != crate::ID,
+        ErrorCode::InvalidHookProgram
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.hook_program = ticket.hook_program;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetHookProgram {
+            hook_program: ticket.hook_program,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Gate `deposit` and `deposit_wsol` on an attestation of the depositor from `ticket.registry`
+/// under `ticket.schema`, or lift the gate with the default pubkey.
+pub fn set_deposit_registry(
+    ctx: Context<SetDepositRegistry>,
+    ticket: SetDepositRegistryTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    let disabled = ticket.registry == Pubkey::default();
+    require!(
+        ticket.registry != crate::ID && (disabled || ticket.schema != Pubkey::default()),
+        ErrorCode::InvalidDepositRegistry
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.deposit_registry = ticket.registry;
+    vault.deposit_schema = if disabled {
+        Pubkey::default()
+    } else {
+        ticket.schema
+    };
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetDepositRegistry {
+            registry: vault.deposit_registry,
+            schema: vault.deposit_schema,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Require withdrawal recipients to hold an identity pass (Civic gateway token) from
+/// `ticket.network`, or turn the check off with the default pubkey.
+pub fn set_identity_network(
+    ctx: Context<SetIdentityNetwork>,
+    ticket: SetIdentityNetworkTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.identity_network = ticket.network;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetIdentityNetwork {
+            network: ticket.network,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Turn strict signature verification on or off. In strict mode a ticket with any submitted
+/// signature that fails recovery or isn't from an active signer is rejected, and the recovered
+/// addresses are logged, instead of the bad signature being skipped.
+pub fn set_strict_signatures(
+    ctx: Context<SetStrictSignatures>,
+    ticket: SetStrictSignaturesTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.strict_signatures = ticket.enabled;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetStrictSignatures {
+            enabled: ticket.enabled,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Set the lamports withdrawals must leave in the treasury on top of its rent exemption, so
+/// relayer rebates, crank incentives and nonce rent stay payable. Admin ops aren't bound by it.
+pub fn set_treasury_reserve(
+    ctx: Context<SetTreasuryReserve>,
+    ticket: SetTreasuryReserveTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.treasury_reserve = ticket.reserve;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetTreasuryReserve {
+            reserve: ticket.reserve,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Switch withdrawal replay protection between nonce accounts and the request id watermark.
+/// Under watermark replay request ids must be strictly increasing, and `withdraw`,
+/// `withdraw_wsol` and `bulk_withdraw` need no nonce account. The watermark stays a floor after
+/// switching back, so ids consumed without a nonce account can't be replayed.
+pub fn set_replay_mode(
+    ctx: Context<SetReplayMode>,
+    ticket: SetReplayModeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &mut ctx.accounts.vault;
+    require!(
+        ticket.watermark >= vault.request_id_watermark,
+        ErrorCode::WatermarkDecreased
+    );
+    vault.watermark_replay = ticket.watermark_replay;
+    vault.request_id_watermark = ticket.watermark;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetReplayMode {
+            watermark_replay: ticket.watermark_replay,
+            watermark: ticket.watermark,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Sweep accumulated protocol fees from the fee treasury to `ticket.recipient`. Like withdrawals,
+/// SPL amounts are net to the recipient; the fee treasury covers any transfer fee.
+pub fn collect_fees<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CollectFees<'info>>,
+    ticket: CollectFeesTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    require!(!ticket.fees.is_empty(), ErrorCode::NoWithdrawalsProvided);
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        ticket.recipient == ctx.accounts.recipient.key(),
+        ErrorCode::InvalidRecipient
+    );
+    check_duplicate_assets(&ticket.fees)?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let fee_treasury = &ctx.accounts.fee_treasury;
+    let seeds = &[
+        b"fee_treasury",
+        vault_key.as_ref(),
+        &[vault.fee_treasury_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // SOL moves after the token transfers: the fee treasury signs those CPIs, and the runtime
+    // rejects a CPI whose accounts no longer balance against the rest of the instruction.
+    let mut total_sol = 0u64;
+    for fee in ticket.fees.iter() {
+        require!(fee.amount > 0, ErrorCode::InvalidAmount);
+
+        match &fee.asset {
+            Asset::Sol => {
+                total_sol = total_sol
+                    .checked_add(fee.amount)
+                    .ok_or(ErrorCode::Overflow)?;
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+                let fee_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &fee_treasury.key(),
+                    mint,
+                    &token_program,
+                )?;
+                let recipient_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &ticket.recipient,
+                    mint,
+                    &token_program,
+                )?;
+
+                let gross_amount =
+                    gross_amount_for_net(&mint_account.to_account_info(), fee.amount)?;
+
+                require!(
+                    fee_token.amount >= gross_amount,
+                    ErrorCode::InsufficientFunds
+                );
+
+                let cpi_accounts = TransferChecked {
+                    from: fee_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
+                    to: recipient_token.to_account_info(),
+                    authority: fee_treasury.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
+                    gross_amount,
+                    mint_account.decimals,
+                )?;
+            }
+        }
+    }
+
+    if total_sol > 0 {
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(fee_treasury.data_len());
+        let available = fee_treasury.lamports().saturating_sub(rent_exempt_minimum);
+
+        require!(available >= total_sol, ErrorCode::InsufficientFunds);
+
+        transfer_lamports(fee_treasury, &ctx.accounts.recipient, total_sol)?;
+    }
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::CollectFees {
+            recipient: ticket.recipient,
+            fees: ticket.fees,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Move the whole balance of each listed vault token account that holds less than its asset's
+/// minimum withdrawal to the destination's associated token accounts. Balances at or above the
+/// minimum, or of assets without one, are left alone.
+pub fn sweep_dust<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepDust<'info>>,
+    ticket: SweepDustTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    require!(!ticket.mints.is_empty(), ErrorCode::NoWithdrawalsProvided);
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    let mut seen = std::collections::HashSet::new();
+    for mint in ticket.mints.iter() {
+        require!(seen.insert(mint), ErrorCode::DuplicateAsset);
+    }
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let token_program = ctx.accounts.token_program.key();
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let mut swept = Vec::new();
+    for mint in ticket.mints.iter() {
+        let vault_token = find_associated_token_account(
+            ctx.remaining_accounts,
+            &vault_key,
+            mint,
+            &token_program,
+        )?;
+        let threshold = vault.min_withdrawal(&Asset::SplToken { mint: *mint });
+        if vault_token.amount == 0 || vault_token.amount >= threshold {
+            continue;
+        }
+
+        let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+        let destination_token = find_associated_token_account(
+            ctx.remaining_accounts,
+            &ticket.destination,
+            mint,
+            &token_program,
+        )?;
+
+        let cpi_accounts = TransferChecked {
+            from: vault_token.to_account_info(),
+            mint: mint_account.to_account_info(),
+            to: destination_token.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked_with_hook(
+            cpi_ctx,
+            ctx.remaining_accounts,
+            vault_token.amount,
+            mint_account.decimals,
+        )?;
+        swept.push(AssetAmount {
+            asset: Asset::SplToken { mint: *mint },
+            amount: vault_token.amount,
+        });
+    }
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SweepDust {
+            destination: ticket.destination,
+            swept,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Close the vault's associated token account for a delisted, drained mint and return its rent
+/// to the ticket's rent recipient.
+pub fn close_vault_token_account(
+    ctx: Context<CloseVaultTokenAccount>,
+    ticket: CloseVaultTokenAccountTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    // Deposits of a listed asset need the account.
+    let mint = ticket.mint;
+    let vault = &ctx.accounts.vault;
+    require!(
+        !vault.is_whitelisted(&Asset::SplToken { mint })
+            && !vault.is_whitelisted(&Asset::Nft { mint }),
+        ErrorCode::AssetStillWhitelisted
+    );
+    require!(
+        ctx.accounts.vault_token_account.amount == 0,
+        ErrorCode::TokenAccountNotEmpty
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let lamports = ctx
+        .accounts
+        .vault_token_account
+        .to_account_info()
+        .lamports();
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token_account.to_account_info(),
+            destination: ctx.accounts.rent_recipient.to_account_info(),
+            authority: vault.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::CloseVaultTokenAccount {
+            mint,
+            rent_recipient: ticket.rent_recipient,
+            lamports,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Move the vault's whole balance of a mint to the destination's associated token account and,
+/// when the ticket says so, close the emptied vault account and pay its rent to the destination,
+/// decommissioning the asset in one step. Closing needs the asset off the whitelist first.
+///
+/// Remaining accounts: those the mint's transfer hook needs, if any.
+pub fn sweep_asset<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepAsset<'info>>,
+    ticket: SweepAssetTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    let mint = ticket.mint;
+    let vault = &ctx.accounts.vault;
+    require!(
+        !ticket.close_account
+            || (!vault.is_whitelisted(&Asset::SplToken { mint })
+                && !vault.is_whitelisted(&Asset::Nft { mint })),
+        ErrorCode::AssetStillWhitelisted
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let amount = ctx.accounts.vault_token_account.amount;
+    if amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer_checked_with_hook(
+            cpi_ctx,
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    if ticket.close_account {
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault_token_account.to_account_info(),
+                destination: ctx.accounts.destination.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SweepAsset {
+            mint,
+            destination: ticket.destination,
+            amount,
+            closed: ticket.close_account,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn set_allowance(
+    ctx: Context<SetAllowance>,
+    ticket: SetAllowanceTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let allowance = &mut ctx.accounts.allowance;
+    allowance.vault = ticket.vault;
+    allowance.recipient = ticket.recipient;
+    allowance.asset = ticket.asset.clone();
+    allowance.cap = ticket.cap;
+    allowance.bump = ctx.bumps.allowance;
+
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetAllowance {
+            recipient: ticket.recipient,
+            asset: ticket.asset,
+            cap: ticket.cap,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Create the vault-owned receipt mint for a whitelisted asset. From then on `deposit` mints
+/// receipts 1:1 with the net deposited amount to depositors who pass the mint.
+pub fn create_receipt_mint<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateReceiptMint<'info>>,
+    ticket: CreateReceiptMintTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    require!(
+        vault.is_whitelisted(&ticket.asset),
+        ErrorCode::AssetNotWhitelisted
+    );
+
+    let asset_decimals = match &ticket.asset {
+        Asset::Sol => SOL_DECIMALS,
+        Asset::SplToken { mint } => find_mint(ctx.remaining_accounts, mint)?.decimals,
+        Asset::Nft { .. } => return err!(ErrorCode::UnsupportedAsset),
+    };
+    require!(
+        ticket.decimals == asset_decimals,
+        ErrorCode::InvalidReceiptDecimals
+    );
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::CreateReceiptMint {
+            asset: ticket.asset,
+            mint: ctx.accounts.receipt_mint.key(),
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Create the named treasury `[b"treasury", vault, name]`, or change the threshold withdrawals
+/// from it need.
+pub fn set_treasury(
+    ctx: Context<SetTreasury>,
+    ticket: SetTreasuryTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    require!(
+        !ticket.name.is_empty() && ticket.name.len() <= MAX_TREASURY_NAME_LEN,
+        ErrorCode::InvalidTreasuryName
+    );
+    let signers = operational_signers(&ctx.accounts.vault, ctx.remaining_accounts)?;
+    check_threshold(&signers, ticket.m_threshold)?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.vault = ticket.vault;
+    treasury.name = ticket.name.clone();
+    treasury.m_threshold = ticket.m_threshold;
+    treasury.bump = ctx.bumps.treasury;
+
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetTreasury {
+            name: ticket.name,
+            m_threshold: ticket.m_threshold,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Trust VAAs from a Wormhole emitter, posted through `core_bridge`, as withdrawal tickets for
+/// `withdraw_with_vaa`. Replaces the emitter trusted so far.
+pub fn set_wormhole_emitter(
+    ctx: Context<SetWormholeEmitter>,
+    ticket: SetWormholeEmitterTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let emitter = &mut ctx.accounts.wormhole_emitter;
+    emitter.vault = ticket.vault;
+    emitter.core_bridge = ticket.core_bridge;
+    emitter.emitter_chain = ticket.emitter_chain;
+    emitter.emitter_address = ticket.emitter_address;
+    emitter.bump = ctx.bumps.wormhole_emitter;
+
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetWormholeEmitter {
+            core_bridge: ticket.core_bridge,
+            emitter_chain: ticket.emitter_chain,
+            emitter_address: ticket.emitter_address,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Create a child vault `[b"sub_vault", vault, sub_vault_seed]` under this vault. It starts with a copy of
+/// this vault's signer sets, thresholds, network and domain suffix, and its own empty whitelist,
+/// limits and treasury; from then on it's configured independently.
+pub fn create_sub_vault(
+    ctx: Context<CreateSubVault>,
+    ticket: CreateSubVaultTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    // A signer set PDA belongs to one vault, so there's no set to copy.
+    require!(
+        !ctx.accounts.vault.has_signer_set,
+        ErrorCode::SignerSetNotSupported
+    );
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let parent = &ctx.accounts.vault;
+    let sub_vault = &mut ctx.accounts.sub_vault;
+    sub_vault.version = CURRENT_VERSION;
+    sub_vault.authority = parent.authority;
+    sub_vault.vault_seed = ticket.sub_vault_seed.clone();
+    sub_vault.network_id = parent.network_id;
+    sub_vault.extra_network_ids = parent.extra_network_ids.clone();
+    sub_vault.m_threshold = parent.m_threshold;
+    sub_vault.admin_threshold = parent.admin_threshold;
+    sub_vault.signers = parent.signers.clone();
+    sub_vault.admin_signers = parent.admin_signers.clone();
+    sub_vault.domain_suffix = parent.domain_suffix.clone();
+    sub_vault.log_verbosity = LogVerbosity::Itemized;
+    sub_vault.auth_scheme = parent.auth_scheme;
+    sub_vault.genesis_hash = parent.genesis_hash;
+    sub_vault.parent = parent.key();
+    sub_vault.bump = ctx.bumps.sub_vault;
+    sub_vault.treasury_bump = ctx.bumps.sub_treasury;
+    sub_vault.next_sequence();
+
+    emit!(VaultInitializedEvent {
+        vault: sub_vault.key(),
+        sequence: sub_vault.sequence,
+        authority: sub_vault.authority,
+        network_id: sub_vault.network_id,
+        m_threshold: sub_vault.m_threshold,
+        admin_threshold: sub_vault.admin_threshold,
+        signers: sub_vault.signers.clone(),
+        admin_signers: sub_vault.admin_signers.clone(),
+        domain_suffix: sub_vault.domain_suffix.clone(),
+        auth_scheme: sub_vault.auth_scheme,
+        genesis_hash: sub_vault.genesis_hash,
+        whitelisted_assets: Vec::new(),
+    });
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::CreateSubVault {
+            sub_vault: sub_vault.key(),
+            sub_vault_seed: ticket.sub_vault_seed,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Move funds from a sub-vault back to this vault: SOL from treasury to treasury, SPL and NFTs
+/// between the two vaults' associated token accounts. Needs this vault's admin threshold, not
+/// the sub-vault's. SPL amounts are what leaves the sub-vault, before any transfer fee.
+pub fn reclaim_sub_vault<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReclaimSubVault<'info>>,
+    ticket: ReclaimSubVaultTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    require!(
+        !ticket.withdrawals.is_empty(),
+        ErrorCode::NoWithdrawalsProvided
+    );
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    let sub_vault = &ctx.accounts.sub_vault;
+    require!(ticket.sub_vault == sub_vault.key(), ErrorCode::InvalidVault);
+    require!(sub_vault.parent == ticket.vault, ErrorCode::NotASubVault);
+    check_duplicate_assets(&ticket.withdrawals)?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let sub_vault = &ctx.accounts.sub_vault;
+    let sub_treasury = &ctx.accounts.sub_treasury;
+    let seeds = &[
+        sub_vault.seed_namespace(),
+        sub_vault.seed_parent(),
+        sub_vault.vault_seed.as_bytes(),
+        &[sub_vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    for withdrawal in ticket.withdrawals.iter() {
+        require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+
+        match &withdrawal.asset {
+            Asset::Sol => {
+                let rent_exempt_minimum = Rent::get()?.minimum_balance(sub_treasury.data_len());
+                let available = sub_treasury.lamports().saturating_sub(rent_exempt_minimum);
+
+                require!(available >= withdrawal.amount, ErrorCode::InsufficientFunds);
+
+                transfer_lamports(sub_treasury, &ctx.accounts.treasury, withdrawal.amount)?;
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+                check_nft_amount(&withdrawal.asset, withdrawal.amount, &mint_account)?;
+                let sub_vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &sub_vault.key(),
+                    mint,
+                    &token_program,
+                )?;
+                let vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &ticket.vault,
+                    mint,
+                    &token_program,
+                )?;
+
+                require!(
+                    sub_vault_token.amount >= withdrawal.amount,
+                    ErrorCode::InsufficientFunds
+                );
+
+                let cpi_accounts = TransferChecked {
+                    from: sub_vault_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
+                    to: vault_token.to_account_info(),
+                    authority: sub_vault.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
+                    withdrawal.amount,
+                    mint_account.decimals,
+                )?;
+            }
+        }
+    }
+
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::ReclaimSubVault {
+            sub_vault: ticket.sub_vault,
+            reclaimed: ticket.withdrawals,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// The vault's operational signers, read from the signer set PDA in `remaining_accounts` while
+/// the vault has one.
+fn operational_signers(
+    vault: &Account<Vault>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<WeightedSigner>> {
+    if vault.has_signer_set {
+        load_signer_set(remaining_accounts, &vault.key())
+    } else {
+        Ok(vault.signers.clone())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn check_before_admin_update(
+    vault: &Account<Vault>,
+    ticket: &dyn Ticket,
+    signers_with_sigs: &[SignerWithSignature],
+    remaining_accounts: &[AccountInfo],
+    ticket_vault: &Pubkey,
+    ticket_expire: i64,
+    ticket_not_before: Option<i64>,
+    ticket_network_id: u64,
+    ticket_version: u8,
+) -> Result<u8> {
+    let clock = Clock::get()?;
+
+    require!(ticket_vault == &vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.is_ticket_unexpired(ticket_expire, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket_not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket_expire, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+    require!(
+        vault.accepts_network_id(ticket_network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket_version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+
+    // admin update requires signers weighing admin_threshold to approve.
+    authorize(
+        vault,
+        ticket,
+        signers_with_sigs,
+        remaining_accounts,
+        vault.admin_signer_set(),
+        Quorum::Weight(vault.admin_threshold as u16),
+        clock.unix_timestamp,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: AddAssetTicket)]
+pub struct AddAsset<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: UpdateAssetTicket)]
+pub struct UpdateAsset<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RemoveAssetTicket)]
+pub struct RemoveAsset<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetMinWithdrawalTicket)]
+pub struct SetMinWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetUsdCapTicket)]
+pub struct SetUsdCap<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UsdCap::INIT_SPACE,
+        seeds = [b"usd_cap", vault.key().as_ref(), ticket.asset.seed_key().as_ref()],
+        bump
+    )]
+    pub usd_cap: Account<'info, UsdCap>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetMaxTicketUsdTicket)]
+pub struct SetMaxTicketUsd<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: AddRecipientTicket)]
+pub struct AddRecipient<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RecipientAllowlist::INIT_SPACE,
+        seeds = [b"recipient_allowlist", vault.key().as_ref()],
+        bump
+    )]
+    pub recipient_allowlist: Account<'info, RecipientAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RemoveRecipientTicket)]
+pub struct RemoveRecipient<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"recipient_allowlist", vault.key().as_ref()],
+        bump = recipient_allowlist.bump
+    )]
+    pub recipient_allowlist: Account<'info, RecipientAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetRecipientAllowlistTicket)]
+pub struct SetRecipientAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: BlockRecipientTicket)]
+pub struct BlockRecipient<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Blocklist::INIT_SPACE,
+        seeds = [b"blocklist", vault.key().as_ref()],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: UnblockRecipientTicket)]
+pub struct UnblockRecipient<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"blocklist", vault.key().as_ref()],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: AddStrategyTicket)]
+pub struct AddStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StrategyAllowlist::INIT_SPACE,
+        seeds = [b"strategy_allowlist", vault.key().as_ref()],
+        bump
+    )]
+    pub strategy_allowlist: Account<'info, StrategyAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: AddCrankerTicket)]
+pub struct AddCranker<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CrankRegistry::INIT_SPACE,
+        seeds = [b"crank_registry", vault.key().as_ref()],
+        bump
+    )]
+    pub crank_registry: Account<'info, CrankRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RemoveCrankerTicket)]
+pub struct RemoveCranker<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"crank_registry", vault.key().as_ref()],
+        bump = crank_registry.bump
+    )]
+    pub crank_registry: Account<'info, CrankRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetCrankIncentiveTicket)]
+pub struct SetCrankIncentive<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CrankRegistry::INIT_SPACE,
+        seeds = [b"crank_registry", vault.key().as_ref()],
+        bump
+    )]
+    pub crank_registry: Account<'info, CrankRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RemoveStrategyTicket)]
+pub struct RemoveStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_allowlist", vault.key().as_ref()],
+        bump = strategy_allowlist.bump
+    )]
+    pub strategy_allowlist: Account<'info, StrategyAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RotateValidatorTicket)]
+pub struct RotateValidator<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RotateAdminSignersTicket)]
+pub struct RotateAdminSigners<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetLogVerbosityTicket)]
+pub struct SetLogVerbosity<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetFeeTicket)]
+pub struct SetFee<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, created on the first fee update
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetRelayerRebateTicket)]
+pub struct SetRelayerRebate<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetMaxTicketLifetimeTicket)]
+pub struct SetMaxTicketLifetime<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetExpiryGraceTicket)]
+pub struct SetExpiryGrace<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetExtraNetworkIdsTicket)]
+pub struct SetExtraNetworkIds<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetGuardianTicket)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetAuthSchemeTicket)]
+pub struct SetAuthScheme<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetHookProgramTicket)]
+pub struct SetHookProgram<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetDepositRegistryTicket)]
+pub struct SetDepositRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetIdentityNetworkTicket)]
+pub struct SetIdentityNetwork<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetStrictSignaturesTicket)]
+pub struct SetStrictSignatures<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetTreasuryReserveTicket)]
+pub struct SetTreasuryReserve<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetReplayModeTicket)]
+pub struct SetReplayMode<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: CollectFeesTicket)]
+pub struct CollectFees<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump = vault.fee_treasury_bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient verified against ticket
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SweepDustTicket)]
+pub struct SweepDust<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: CloseVaultTokenAccountTicket)]
+pub struct CloseVaultTokenAccount<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(address = ticket.mint, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = ticket.rent_recipient)]
+    /// CHECK: Rent recipient verified against the ticket
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SweepAssetTicket)]
+pub struct SweepAsset<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(address = ticket.mint, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = ticket.destination)]
+    /// CHECK: Destination verified against the ticket
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = destination,
+        associated_token::token_program = token_program
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetAllowanceTicket)]
+pub struct SetAllowance<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [
+            b"allowance",
+            vault.key().as_ref(),
+            ticket.recipient.as_ref(),
+            ticket.asset.seed_key().as_ref()
+        ],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: CreateReceiptMintTicket)]
+pub struct CreateReceiptMint<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"receipt_mint", vault.key().as_ref(), ticket.asset.seed_key().as_ref()],
+        bump,
+        mint::decimals = ticket.decimals,
+        mint::authority = vault,
+        mint::token_program = token_program
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetTreasuryTicket)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NamedTreasury::INIT_SPACE,
+        seeds = [TREASURY_SEED, vault.key().as_ref(), ticket.name.as_bytes()],
+        bump
+    )]
+    pub treasury: Account<'info, NamedTreasury>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetWormholeEmitterTicket)]
+pub struct SetWormholeEmitter<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WormholeEmitter::INIT_SPACE,
+        seeds = [b"wormhole_emitter", vault.key().as_ref()],
+        bump
+    )]
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: CreateSubVaultTicket)]
+pub struct CreateSubVault<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [SUB_VAULT_SEED, vault.key().as_ref(), ticket.sub_vault_seed.as_bytes()],
+        bump
+    )]
+    pub sub_vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8,
+        seeds = [TREASURY_SEED, sub_vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Sub-vault treasury PDA initialized with no data
+    pub sub_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: ReclaimSubVaultTicket)]
+pub struct ReclaimSubVault<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [sub_vault.seed_namespace(), sub_vault.seed_parent(), sub_vault.vault_seed.as_bytes()],
+        bump = sub_vault.bump
+    )]
+    pub sub_vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, sub_vault.key().as_ref()],
+        bump = sub_vault.treasury_bump
+    )]
+    /// CHECK: Sub-vault treasury PDA verified by seeds
+    pub sub_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
         payer = payer,
         space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"admin_nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
         bump
     )]
     pub nonce_account: Account<'info, NonceAccount>,
@@ -259,4 +3852,5 @@ pub struct RotateValidator<'info> {
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -1,16 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
 
 use super::accounts::*;
+use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
-use super::util::validate_sigs;
+use super::util::{
+    authorize, batch_summary, check_nft_amount, find_associated_token_account, find_mint,
+    is_supported_ticket_version, is_ticket_active, notify_hook, transfer_checked_with_hook, Quorum,
+};
 
 pub fn admin_deposit<'info>(
     ctx: Context<'_, '_, 'info, 'info, AdminDeposit<'info>>,
     ticket: AdminDepositTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
     require!(!ticket.deposits.is_empty(), ErrorCode::NoDepositsProvided);
 
     let vault = &mut ctx.accounts.vault;
@@ -18,41 +24,67 @@ pub fn admin_deposit<'info>(
 
     require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
     require!(
-        vault.network_id == ticket.network_id,
+        vault.accepts_network_id(ticket.network_id),
         ErrorCode::InvalidNetwork
     );
     require!(
-        clock.unix_timestamp <= ticket.expiry,
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
         ErrorCode::TicketExpired
     );
-
-    // Validate the signatures. Check at lease one signer.
     require!(
-        signers_with_sigs.len() >= 1,
-        ErrorCode::InsufficientSignatures
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
     );
-    let validated_sigs = validate_sigs(&ticket, &signers_with_sigs, &vault.signers);
     require!(
-        validated_sigs.len() >= 1,
-        ErrorCode::InsufficientValidSignatures
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
     );
 
-    // Check nonce hasn't been used (replay protection)
+    // Validate the signatures. Check at lease one signer.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        vault.admin_signer_set(),
+        Quorum::One,
+        clock.unix_timestamp,
+    )?;
+
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy)
     let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
-
-    // Mark nonce as used BEFORE transfer (prevent reentrancy)
-    nonce_account.used = true;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
 
     let vault = &ctx.accounts.vault;
+    let deposit_record = &mut ctx.accounts.deposit_record;
+    deposit_record.open(
+        vault.key(),
+        ctx.accounts.payer.key(),
+        ctx.bumps.deposit_record,
+    );
+
+    if let Some(source_ref) = ticket.source_ref {
+        emit_cpi!(SourceRefEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            source_ref,
+        });
+    }
 
-    for deposit_item in ticket.deposits {
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
+    for deposit_item in ticket.deposits.iter() {
         require!(deposit_item.amount > 0, ErrorCode::InvalidAmount);
 
-        require!(
-            vault.whitelisted_assets.contains(&deposit_item.asset),
-            ErrorCode::AssetNotWhitelisted
-        );
+        // A bypassing ticket skips only the whitelist; whitelisted assets keep their limits.
+        if !ticket.bypass_whitelist || vault.is_whitelisted(&deposit_item.asset) {
+            vault.check_deposit(&deposit_item.asset, deposit_item.amount)?;
+        }
 
         match deposit_item.asset {
             Asset::Sol => {
@@ -71,88 +103,151 @@ pub fn admin_deposit<'info>(
                         ctx.accounts.system_program.to_account_info(),
                     ],
                 )?;
-
-                msg!(
-                    "Admin Deposit SOL: request_id={}, payer={}, amount={}, treasury_balance={}",
-                    ticket.request_id,
-                    ctx.accounts.payer.key(),
-                    deposit_item.amount,
-                    ctx.accounts.treasury.to_account_info().lamports(),
-                );
-            }
-            Asset::SplToken { mint } => {
-                let mut user_token_account: Option<Account<'info, TokenAccount>> = None;
-                let mut vault_token_account: Option<Account<'info, TokenAccount>> = None;
-
-                for acc in ctx.remaining_accounts.iter() {
-                    if let Ok(token_acc) = Account::<TokenAccount>::try_from(acc) {
-                        if token_acc.mint == mint {
-                            if token_acc.owner == ctx.accounts.payer.key() {
-                                user_token_account = Some(token_acc);
-                            } else if token_acc.owner == vault.key() {
-                                vault_token_account = Some(token_acc);
-                            }
-                        }
-                    }
+                vault.check_tvl_cap(&deposit_item.asset, ctx.accounts.treasury.lamports())?;
+                deposit_record.credit(&deposit_item.asset, deposit_item.amount)?;
+
+                if itemized {
+                    emit_cpi!(DepositEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        depositor: ctx.accounts.payer.key(),
+                        asset: deposit_item.asset.clone(),
+                        amount: deposit_item.amount,
+                        net_amount: deposit_item.amount,
+                        admin: true,
+                        valid_signers,
+                        metadata: None,
+                    });
                 }
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&deposit_item.asset, deposit_item.amount, &mint_account)?;
+                let user_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &ctx.accounts.payer.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let mut vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let balance_before = vault_token.amount;
 
-                let user_token = user_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-                let vault_token = vault_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: user_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
                     to: vault_token.to_account_info(),
                     authority: ctx.accounts.payer.to_account_info(),
                 };
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-                token::transfer(cpi_ctx, deposit_item.amount)?;
-
-                msg!(
-                    "Admin Deposit SPL: request_id={}, mint={}, user={}, amount={}, vault_token_balance={}",
-                    ticket.request_id,
-                    mint,
-                    user_token.key(),
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
                     deposit_item.amount,
-                    vault_token.amount,
-                );
+                    mint_account.decimals,
+                )?;
+
+                // Transfer-fee mints credit less than the amount sent.
+                vault_token.reload()?;
+                let net_amount = vault_token
+                    .amount
+                    .checked_sub(balance_before)
+                    .ok_or(ErrorCode::Overflow)?;
+                vault.check_tvl_cap(&deposit_item.asset, vault_token.amount)?;
+                deposit_record.credit(&deposit_item.asset, net_amount)?;
+
+                if itemized {
+                    emit_cpi!(DepositEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        depositor: ctx.accounts.payer.key(),
+                        asset: deposit_item.asset.clone(),
+                        amount: deposit_item.amount,
+                        net_amount,
+                        admin: true,
+                        valid_signers,
+                        metadata: None,
+                    });
+                }
             }
         }
     }
 
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::AdminDeposit,
+        ticket.request_id,
+        ctx.accounts.payer.key(),
+        &ticket.deposits,
+    )?;
+
+    if !itemized {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::AdminDeposit,
+            vec![ticket.request_id],
+            &ticket.deposits,
+            0,
+            valid_signers,
+            None,
+        )?;
+        emit_cpi!(summary);
+    }
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(ticket: AdminDepositTicket)]
 pub struct AdminDeposit<'info> {
     #[account(
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
         bump = vault.bump
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump = vault.treasury_bump
     )]
     /// CHECK: Treasury PDA verified by seeds
     pub treasury: UncheckedAccount<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"admin_nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
         bump
     )]
     pub nonce_account: Account<'info, NonceAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [b"deposit_record", vault.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -1,16 +1,23 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
 
 use super::accounts::*;
+use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
-use super::util::{check_duplicate_assets, validate_sigs};
+use super::util::{
+    authorize, batch_summary, check_duplicate_assets, check_nft_amount,
+    find_associated_token_account, find_mint, gross_amount_for_net, is_supported_ticket_version,
+    is_ticket_active, notify_hook, transfer_checked_with_hook, transfer_lamports, Quorum,
+};
 
 pub fn admin_withdraw<'info>(
     ctx: Context<'_, '_, 'info, 'info, AdminWithdraw<'info>>,
     ticket: AdminWithdrawalTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
 ) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
     require!(
         !ticket.withdrawals.is_empty(),
         ErrorCode::NoWithdrawalsProvided
@@ -21,41 +28,59 @@ pub fn admin_withdraw<'info>(
 
     require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
     require!(
-        vault.network_id == ticket.network_id,
+        vault.accepts_network_id(ticket.network_id),
         ErrorCode::InvalidNetwork
     );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
     require!(
         ticket.recipient == ctx.accounts.recipient.key(),
         ErrorCode::InvalidRecipient
     );
     require!(
-        clock.unix_timestamp <= ticket.expiry,
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
         ErrorCode::TicketExpired
     );
     require!(
-        signers_with_sigs.len() >= vault.m_threshold as usize,
-        ErrorCode::InsufficientSignatures
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
     );
-
-    check_duplicate_assets(&ticket.withdrawals)?;
-
-    // Validate the signatures.
-    let validated_sigs = validate_sigs(&ticket, &signers_with_sigs, &vault.signers);
-
-    // Admin. Check all signers.
     require!(
-        validated_sigs.len() == vault.signers.len(),
-        ErrorCode::InsufficientValidSignatures
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
     );
 
-    // Check nonce hasn't been used (replay protection)
-    let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
+    check_duplicate_assets(&ticket.withdrawals)?;
 
-    // Mark nonce as used BEFORE transfer (prevent reentrancy)
-    nonce_account.used = true;
+    // Admin. Check all unexpired signers.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        vault.admin_signer_set(),
+        Quorum::All,
+        clock.unix_timestamp,
+    )?;
+
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    if let Some(source_ref) = ticket.source_ref {
+        emit_cpi!(SourceRefEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            source_ref,
+        });
+    }
 
-    for withdrawal in ticket.withdrawals {
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
+    for withdrawal in ticket.withdrawals.iter() {
         require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
 
         // Don't check whitelist since withdraw is always allowed.
@@ -70,83 +95,137 @@ pub fn admin_withdraw<'info>(
                 require!(available >= withdrawal.amount, ErrorCode::InsufficientFunds);
 
                 // Execute transfer
-                **ctx.accounts.treasury.try_borrow_mut_lamports()? -= withdrawal.amount;
-                **ctx.accounts.recipient.try_borrow_mut_lamports()? += withdrawal.amount;
-
-                msg!(
-                    "Admin Withdrawal SOL: request_id={}, recipient={}, amount={}, valid_signers={}",
-                    ticket.request_id,
-                    ticket.recipient,
+                transfer_lamports(
+                    &ctx.accounts.treasury,
+                    &ctx.accounts.recipient,
                     withdrawal.amount,
-                    validated_sigs.len()
-                );
-            }
-            Asset::SplToken { mint } => {
-                let mut recipient_token_account: Option<Account<'info, TokenAccount>> = None;
-                let mut vault_token_account: Option<Account<'info, TokenAccount>> = None;
-
-                for acc in ctx.remaining_accounts.iter() {
-                    if let Ok(token_acc) = Account::<TokenAccount>::try_from(acc) {
-                        if token_acc.mint == mint {
-                            if token_acc.owner == ctx.accounts.recipient.key() {
-                                recipient_token_account = Some(token_acc);
-                            } else if token_acc.owner == vault.key() {
-                                vault_token_account = Some(token_acc);
-                            }
-                        }
-                    }
+                )?;
+
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: withdrawal.amount,
+                        gross_amount: withdrawal.amount,
+                        fee: 0,
+                        admin: true,
+                        valid_signers,
+                        metadata: None,
+                    });
                 }
-
-                let vault_token = vault_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-                let recipient_token =
-                    recipient_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&withdrawal.asset, withdrawal.amount, &mint_account)?;
+                let vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let recipient_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &ctx.accounts.recipient.key(),
+                    &mint,
+                    &token_program,
+                )?;
+
+                // Ticket amounts are net to the recipient; the vault covers any transfer fee.
+                let gross_amount =
+                    gross_amount_for_net(&mint_account.to_account_info(), withdrawal.amount)?;
 
                 require!(
-                    vault_token.amount >= withdrawal.amount,
+                    vault_token.amount >= gross_amount,
                     ErrorCode::InsufficientFunds
                 );
 
-                let seeds = &[b"vault", vault.vault_seed.as_bytes(), &[vault.bump]];
+                let seeds = &[
+                    vault.seed_namespace(),
+                    vault.seed_parent(),
+                    vault.vault_seed.as_bytes(),
+                    &[vault.bump],
+                ];
                 let signer_seeds = &[&seeds[..]];
 
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: vault_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
                     to: recipient_token.to_account_info(),
                     authority: vault.to_account_info(),
                 };
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-                token::transfer(cpi_ctx, withdrawal.amount)?;
-
-                msg!(
-                    "Admin Withdraw SPL Token: request_id={}, mint={}, recipient={}, amount={}, valid_signers={}",
-                    ticket.request_id,
-                    mint,
-                    ticket.recipient,
-                    withdrawal.amount,
-                    validated_sigs.len()
-                );
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
+                    gross_amount,
+                    mint_account.decimals,
+                )?;
+
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: withdrawal.amount,
+                        gross_amount,
+                        fee: 0,
+                        admin: true,
+                        valid_signers,
+                        metadata: None,
+                    });
+                }
             }
         }
     }
 
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::AdminWithdraw,
+        ticket.request_id,
+        ticket.recipient,
+        &ticket.withdrawals,
+    )?;
+
+    if !itemized {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::AdminWithdraw,
+            vec![ticket.request_id],
+            &ticket.withdrawals,
+            0,
+            valid_signers,
+            None,
+        )?;
+        emit_cpi!(summary);
+    }
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(ticket: AdminWithdrawalTicket)]
 pub struct AdminWithdraw<'info> {
     #[account(
         mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
         bump = vault.bump
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump = vault.treasury_bump
     )]
     /// CHECK: Treasury PDA verified by seeds
@@ -157,10 +236,10 @@ pub struct AdminWithdraw<'info> {
     pub recipient: AccountInfo<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"admin_nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
         bump
     )]
     pub nonce_account: Account<'info, NonceAccount>,
@@ -169,5 +248,5 @@ pub struct AdminWithdraw<'info> {
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
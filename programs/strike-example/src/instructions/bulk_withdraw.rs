@@ -1,20 +1,36 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use super::accounts::*;
+use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
-use super::constant::*;
-use super::util::{check_duplicate_assets, validate_sigs};
-
+use super::util::{
+    account_at, authorize, batch_summary, check_duplicate_assets, check_identity_pass, check_memo,
+    check_nft_amount, check_recipient_allowed, check_token_accounts, check_usd_caps,
+    check_withdrawal_limits, consume_allowance, emit_memo, gross_amount_for_net,
+    is_recipient_blocked, is_supported_ticket_version, is_ticket_active, notify_hook,
+    pay_fee_in_fee_mint, pay_relayer_rebate, recipient_token_address, sanitize_metadata,
+    token_account_of, transfer_checked_with_hook, transfer_lamports, transfer_spl_fee,
+    withdrawable_lamports, Quorum,
+};
+
+/// Recipients, mints and token accounts are read at the positions `account_indexes` gives for
+/// each ticket rather than searched for among the remaining accounts.
 pub fn bulk_withdraw<'info>(
     ctx: Context<'_, '_, 'info, 'info, BulkWithdraw<'info>>,
     bulk_ticket: BulkWithdrawalTicket,
     signers_with_sigs: Vec<SignerWithSignature>,
+    account_indexes: Vec<BulkAccountIndexes>,
     metadata: Option<String>,
 ) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
     require!(
         !bulk_ticket.tickets.is_empty(),
         ErrorCode::NoWithdrawalsProvided
@@ -23,15 +39,24 @@ pub fn bulk_withdraw<'info>(
         bulk_ticket.tickets.len() <= MAX_BULK_TICKETS,
         ErrorCode::TooManyTickets
     );
+    require!(
+        account_indexes.len() == bulk_ticket.tickets.len(),
+        ErrorCode::InvalidAccountIndexes
+    );
 
-    let vault = &ctx.accounts.vault;
+    let vault = &mut ctx.accounts.vault;
     let vault_key = vault.key();
     let clock = Clock::get()?;
     let num_tickets = bulk_ticket.tickets.len();
 
-    // Must provide nonce accounts in remaining_accounts.
+    // Must provide nonce accounts in remaining_accounts, unless under watermark replay.
+    let nonce_accounts = if vault.watermark_replay {
+        0
+    } else {
+        num_tickets
+    };
     require!(
-        ctx.remaining_accounts.len() >= num_tickets,
+        ctx.remaining_accounts.len() >= nonce_accounts,
         ErrorCode::InsufficientAccounts
     );
 
@@ -44,159 +69,250 @@ pub fn bulk_withdraw<'info>(
         );
     }
 
-    require!(
-        signers_with_sigs.len() >= vault.m_threshold as usize,
-        ErrorCode::InsufficientSignatures
-    );
-
     // Validate the signatures.
-    let validated_sigs = validate_sigs(&bulk_ticket, &signers_with_sigs, &vault.signers);
-    require!(
-        validated_sigs.len() >= vault.m_threshold as usize,
-        ErrorCode::InsufficientValidSignatures
-    );
+    let valid_signers = authorize(
+        vault,
+        &bulk_ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
 
     // Calculate facts.
     let rent = Rent::get()?;
     let nonce_space = 8 + NonceAccount::INIT_SPACE;
     let nonce_rent = rent.minimum_balance(nonce_space);
 
-    let treasury_balance = ctx.accounts.treasury.lamports();
-    let rent_exempt_minimum =
-        rent.minimum_balance(ctx.accounts.treasury.to_account_info().data_len());
-    let treasury_available = treasury_balance.saturating_sub(rent_exempt_minimum);
+    let treasury_available = withdrawable_lamports(
+        &ctx.accounts.treasury.to_account_info(),
+        vault.treasury_reserve,
+    )?;
 
     let mut total_sol_withdrawals = 0u64;
     let mut total_spl_withdrawals = HashMap::<Pubkey, u64>::new();
+    let token_program = ctx.accounts.token_program.key();
 
     // Sol recipient accounts from remaining_accounts. Index by ticket index.
     let mut recipient_accounts = Vec::<&AccountInfo<'info>>::new();
+    // Mint accounts from remaining_accounts. Key by mint.
+    let mut mint_accounts = HashMap::<Pubkey, InterfaceAccount<'info, Mint>>::new();
     // Vault token accounts from remaining_accounts. Key by mint.
-    let mut vault_token_accounts = HashMap::<Pubkey, Account<'info, TokenAccount>>::new();
+    let mut vault_token_accounts = HashMap::<Pubkey, InterfaceAccount<'info, TokenAccount>>::new();
     // Spl recipient accounts from remaining_accounts. Index by ticket index. Inner map key by mint.
-    let mut recipient_token_accounts = Vec::<HashMap<Pubkey, Account<'info, TokenAccount>>>::new();
+    let mut recipient_token_accounts =
+        Vec::<HashMap<Pubkey, InterfaceAccount<'info, TokenAccount>>>::new();
+    // Whether each ticket pays a blocked recipient. Index by ticket index.
+    let mut blocked = Vec::<bool>::new();
+    // Hash of each ticket, recorded in its nonce account. Index by ticket index.
+    let mut ticket_hashes = Vec::<[u8; 32]>::new();
+
+    require!(
+        is_supported_ticket_version(bulk_ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
 
     // Step 1: Validating all tickets and nonce accounts.
     for (idx, ticket) in bulk_ticket.tickets.iter().enumerate() {
-        require!(
-            !ticket.withdrawals.is_empty(),
-            ErrorCode::NoWithdrawalsProvided
-        );
-
-        require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
-        require!(
-            vault.network_id == ticket.network_id,
-            ErrorCode::InvalidNetwork
-        );
-        require!(
-            clock.unix_timestamp <= ticket.expiry,
-            ErrorCode::TicketExpired
-        );
-
-        check_duplicate_assets(&ticket.withdrawals)?;
-
-        // Get nonce account from remaining_accounts
-        let nonce_account_info = &ctx.remaining_accounts[idx];
-        let nonce_seeds = &[
-            b"nonce",
-            vault_key.as_ref(),
-            &ticket.request_id.to_le_bytes(),
-        ];
-        let (expected_nonce_pda, _) = Pubkey::find_program_address(nonce_seeds, ctx.program_id);
-
-        require!(
-            nonce_account_info.key() == expected_nonce_pda,
-            ErrorCode::InvalidNonceAccount
-        );
-
-        // Check if nonce already exists and whether used.
-        // Nonce_data length is fixed to be 9 bytes (8 + 1 bool).
-        // If account is empty, it will be created in Step 2.
-        if !nonce_account_info.data_is_empty() {
-            let nonce_data = nonce_account_info.try_borrow_data()?;
-            require!(nonce_data.len() == 9, ErrorCode::InvalidNonceAccount);
-            let nonce_used = nonce_data[8] != 0;
-            require!(!nonce_used, ErrorCode::NonceAlreadyUsed);
-        }
-
-        // Verify recipient account exactly one.
-        let recipient_infos: Vec<&AccountInfo<'info>> = ctx
-            .remaining_accounts
-            .iter()
-            .skip(num_tickets)
-            .filter(|acc| acc.key() == ticket.recipient)
-            .collect();
-        require!(recipient_infos.len() == 1, ErrorCode::InvalidRecipient);
-
-        let recipient_info = recipient_infos[0];
-        recipient_accounts.push(recipient_info);
-
-        let mut recipient_token_accounts_by_mint = HashMap::new();
-
-        // Validate withdrawals under the ticket.
-        for withdrawal in ticket.withdrawals.iter() {
-            require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
-
-            match withdrawal.asset {
-                Asset::Sol => {
-                    require!(
-                        treasury_available >= withdrawal.amount,
-                        ErrorCode::InsufficientFunds
-                    );
-
-                    total_sol_withdrawals = total_sol_withdrawals
-                        .checked_add(withdrawal.amount)
-                        .ok_or(ErrorCode::Overflow)?;
+        // Withdrawal under check, None while the ticket's own fields are checked.
+        let mut failing_withdrawal = None;
+        let mut check_ticket = || -> Result<()> {
+            require!(
+                !ticket.withdrawals.is_empty(),
+                ErrorCode::NoWithdrawalsProvided
+            );
+            require!(
+                ticket.balance_bps.is_none(),
+                ErrorCode::BalanceBpsNotSupported
+            );
+
+            require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+            require!(
+                vault.accepts_network_id(ticket.network_id),
+                ErrorCode::InvalidNetwork
+            );
+            require!(
+                is_supported_ticket_version(ticket.version),
+                ErrorCode::UnsupportedTicketVersion
+            );
+            require!(
+                ticket.treasury.is_none(),
+                ErrorCode::NamedTreasuryNotSupported
+            );
+            require!(
+                vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+                ErrorCode::TicketExpired
+            );
+            require!(
+                is_ticket_active(ticket.not_before, clock.unix_timestamp),
+                ErrorCode::TicketNotYetValid
+            );
+            require!(
+                vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+                ErrorCode::TicketLifetimeTooLong
+            );
+
+            check_duplicate_assets(&ticket.withdrawals)?;
+            check_withdrawal_limits(vault, &ticket.withdrawals)?;
+            check_usd_caps(
+                ctx.remaining_accounts,
+                vault,
+                &ticket.withdrawals,
+                clock.unix_timestamp,
+            )?;
+            check_memo(&ticket.memo)?;
+            check_token_accounts(ticket)?;
+            let indexes = &account_indexes[idx];
+            require!(
+                indexes.withdrawals.len() == ticket.withdrawals.len(),
+                ErrorCode::InvalidAccountIndexes
+            );
+            check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+            check_identity_pass(
+                ctx.remaining_accounts,
+                vault,
+                &ticket.recipient,
+                clock.unix_timestamp,
+            )?;
+
+            let ticket_hash = ticket.hash(&vault.domain_suffix);
+            if idx < nonce_accounts {
+                // Get nonce account from remaining_accounts
+                let nonce_account_info = &ctx.remaining_accounts[idx];
+                let nonce_seeds = &[
+                    NONCE_SEED,
+                    vault_key.as_ref(),
+                    &ticket.request_id.to_le_bytes(),
+                ];
+                let (expected_nonce_pda, _) =
+                    Pubkey::find_program_address(nonce_seeds, ctx.program_id);
+
+                require!(
+                    nonce_account_info.key() == expected_nonce_pda,
+                    ErrorCode::InvalidNonceAccount
+                );
+
+                // Check if nonce already exists and whether used, on a copy; Step 3 consumes it.
+                // If account is empty, it will be created in Step 2.
+                if !nonce_account_info.data_is_empty() {
+                    let nonce_data = nonce_account_info.try_borrow_data()?;
+                    NonceAccount::try_deserialize(&mut &nonce_data[..])
+                        .map_err(|_| ErrorCode::InvalidNonceAccount)?
+                        .consume(ticket_hash, ticket.expiry)?;
                 }
-                Asset::SplToken { mint } => {
-                    let mut recipient_token_account: Option<Account<'info, TokenAccount>> = None;
-                    let mut vault_token_account: Option<Account<'info, TokenAccount>> = None;
-
-                    for acc in ctx.remaining_accounts.iter() {
-                        if let Ok(token_acc) = Account::<TokenAccount>::try_from(acc) {
-                            if token_acc.mint == mint {
-                                // Ensure no duplicate in recipient_token_account and vault_token_account for a given mint.
-                                if token_acc.owner == ticket.recipient.key() {
-                                    require!(
-                                        recipient_token_account.is_none(),
-                                        ErrorCode::UnexpectedTokenAccounts
-                                    );
-                                    recipient_token_account = Some(token_acc);
-                                } else if token_acc.owner == vault.key() {
-                                    require!(
-                                        vault_token_account.is_none(),
-                                        ErrorCode::UnexpectedTokenAccounts
-                                    );
-                                    vault_token_account = Some(token_acc);
-                                }
-                            }
-                        }
-                    }
-
-                    let vault_token = vault_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-                    let recipient_token =
-                        recipient_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-
-                    require!(
-                        vault_token.amount >= withdrawal.amount,
-                        ErrorCode::InsufficientFunds
-                    );
-
-                    *total_spl_withdrawals.entry(mint).or_insert(0) = total_spl_withdrawals
-                        .get(&mint)
-                        .unwrap_or(&0)
-                        .checked_add(withdrawal.amount)
-                        .ok_or(ErrorCode::Overflow)?;
+            }
+            ticket_hashes.push(ticket_hash);
+
+            let recipient_info = account_at(
+                ctx.remaining_accounts,
+                indexes.recipient,
+                &ticket.recipient,
+                ErrorCode::InvalidRecipient,
+            )?;
+            recipient_accounts.push(recipient_info);
+
+            let mut recipient_token_accounts_by_mint = HashMap::new();
+
+            // A blocked recipient's ticket is spent without paying out, so its withdrawals aren't
+            // validated.
+            let is_blocked =
+                is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)?;
+            blocked.push(is_blocked);
+            if is_blocked {
+                recipient_token_accounts.push(recipient_token_accounts_by_mint);
+                return Ok(());
+            }
 
-                    recipient_token_accounts_by_mint.insert(mint, recipient_token);
-                    if !vault_token_accounts.contains_key(&mint) {
-                        vault_token_accounts.insert(mint, vault_token);
+            // Validate withdrawals under the ticket.
+            for (withdrawal_idx, withdrawal) in ticket.withdrawals.iter().enumerate() {
+                failing_withdrawal = Some(withdrawal_idx);
+                require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+
+                match withdrawal.asset {
+                    Asset::Sol => {
+                        require!(
+                            treasury_available >= withdrawal.amount,
+                            ErrorCode::InsufficientFunds
+                        );
+
+                        total_sol_withdrawals = total_sol_withdrawals
+                            .checked_add(withdrawal.amount)
+                            .ok_or(ErrorCode::Overflow)?;
+                    }
+                    Asset::SplToken { mint } | Asset::Nft { mint } => {
+                        // Mints and vault token accounts are loaded once per batch.
+                        let withdrawal_indexes = indexes.withdrawals[withdrawal_idx];
+                        if let Entry::Vacant(entry) = mint_accounts.entry(mint) {
+                            let mint_info = account_at(
+                                ctx.remaining_accounts,
+                                withdrawal_indexes.mint,
+                                &mint,
+                                ErrorCode::MintAccountNotFound,
+                            )?;
+                            entry.insert(InterfaceAccount::try_from(mint_info)?);
+                        }
+                        let mint_account = &mint_accounts[&mint];
+                        check_nft_amount(&withdrawal.asset, withdrawal.amount, mint_account)?;
+                        if let Entry::Vacant(entry) = vault_token_accounts.entry(mint) {
+                            let vault_token_info = account_at(
+                                ctx.remaining_accounts,
+                                withdrawal_indexes.vault_token,
+                                &get_associated_token_address_with_program_id(
+                                    &vault_key,
+                                    &mint,
+                                    &token_program,
+                                ),
+                                ErrorCode::TokenAccountNotFound,
+                            )?;
+                            entry.insert(token_account_of(vault_token_info, &vault_key, &mint)?);
+                        }
+                        let vault_token = &vault_token_accounts[&mint];
+                        let recipient_token_info = account_at(
+                            ctx.remaining_accounts,
+                            withdrawal_indexes.recipient_token,
+                            &recipient_token_address(ticket, withdrawal_idx, &mint, &token_program),
+                            ErrorCode::TokenAccountNotFound,
+                        )?;
+                        let recipient_token =
+                            token_account_of(recipient_token_info, &ticket.recipient, &mint)?;
+
+                        // What's left after the protocol fee is net to the recipient; the vault
+                        // covers any transfer fee on it and on the fee. A fee paid in the fee
+                        // mint isn't withheld.
+                        let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+                            vault.withdrawal_fee(withdrawal.amount)
+                        } else {
+                            0
+                        };
+                        let mint_info = mint_account.to_account_info();
+                        let gross_amount =
+                            gross_amount_for_net(&mint_info, withdrawal.amount - fee_in_kind)?
+                                .checked_add(gross_amount_for_net(&mint_info, fee_in_kind)?)
+                                .ok_or(ErrorCode::Overflow)?;
+
+                        require!(
+                            vault_token.amount >= gross_amount,
+                            ErrorCode::InsufficientFunds
+                        );
+
+                        *total_spl_withdrawals.entry(mint).or_insert(0) = total_spl_withdrawals
+                            .get(&mint)
+                            .unwrap_or(&0)
+                            .checked_add(gross_amount)
+                            .ok_or(ErrorCode::Overflow)?;
+
+                        recipient_token_accounts_by_mint.insert(mint, recipient_token);
                     }
                 }
             }
-        }
 
-        recipient_token_accounts.push(recipient_token_accounts_by_mint);
+            recipient_token_accounts.push(recipient_token_accounts_by_mint);
+            Ok(())
+        };
+        check_ticket().inspect_err(|err| {
+            log_ticket_failure(idx, ticket, failing_withdrawal, err);
+        })?;
     }
 
     // Check the total won't exceed the balance.
@@ -212,21 +328,22 @@ pub fn bulk_withdraw<'info>(
     }
 
     // Step 2: Creating and initializing nonce accounts
-    for (idx, ticket) in bulk_ticket.tickets.iter().enumerate() {
+    let mut created_nonces = 0u64;
+    for (idx, ticket) in bulk_ticket.tickets.iter().enumerate().take(nonce_accounts) {
         let nonce_account_info = &ctx.remaining_accounts[idx];
         if !nonce_account_info.data_is_empty() {
             continue;
         }
 
         let nonce_seeds = &[
-            b"nonce",
+            NONCE_SEED,
             vault_key.as_ref(),
             &ticket.request_id.to_le_bytes(),
         ];
         let (_, nonce_bump) = Pubkey::find_program_address(nonce_seeds, ctx.program_id);
 
         let nonce_signer_seeds: &[&[u8]] = &[
-            b"nonce",
+            NONCE_SEED,
             vault_key.as_ref(),
             &ticket.request_id.to_le_bytes(),
             &[nonce_bump],
@@ -249,21 +366,82 @@ pub fn bulk_withdraw<'info>(
 
         let mut nonce_data = nonce_account_info.try_borrow_mut_data()?;
         nonce_data[0..8].copy_from_slice(NonceAccount::DISCRIMINATOR);
-        nonce_data[8] = 0;
+        created_nonces += 1;
     }
 
     // Step 3: Executing transfer
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
     for (transfer_idx, ticket) in bulk_ticket.tickets.iter().enumerate() {
         let recipient_info = recipient_accounts
             .get(transfer_idx)
             .ok_or(ErrorCode::InvalidRecipient)?;
 
         // Mark nonce as used BEFORE transfer
-        let nonce_account_info = &ctx.remaining_accounts[transfer_idx];
-        let mut nonce_data = nonce_account_info.try_borrow_mut_data()?;
-        nonce_data[8] = 1;
+        if transfer_idx < nonce_accounts {
+            let nonce_account_info = &ctx.remaining_accounts[transfer_idx];
+            let mut nonce_data = nonce_account_info.try_borrow_mut_data()?;
+            let mut nonce = NonceAccount::try_deserialize(&mut &nonce_data[..])?;
+            nonce.consume(ticket_hashes[transfer_idx], ticket.expiry)?;
+            nonce.try_serialize(&mut &mut nonce_data[..])?;
+        }
+        vault.consume_request_id(ticket.request_id)?;
+        vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+        if blocked[transfer_idx] {
+            emit_cpi!(BlockedRecipientEvent {
+                vault: vault_key,
+                sequence: vault.sequence,
+                request_id: ticket.request_id,
+                recipient: ticket.recipient,
+            });
+            continue;
+        }
+
+        if let Some(source_ref) = ticket.source_ref {
+            emit_cpi!(SourceRefEvent {
+                vault: vault_key,
+                sequence: vault.sequence,
+                request_id: ticket.request_id,
+                source_ref,
+            });
+        }
+        emit_memo(ctx.remaining_accounts, &ticket.memo)?;
 
         for (withdrawal_idx, withdrawal) in ticket.withdrawals.iter().enumerate() {
+            consume_allowance(
+                ctx.remaining_accounts,
+                &vault_key,
+                &ticket.recipient,
+                &withdrawal.asset,
+                withdrawal.amount,
+            )
+            .inspect_err(|err| {
+                log_ticket_failure(transfer_idx, ticket, Some(withdrawal_idx), err);
+            })?;
+
+            // The protocol fee comes out of the ticket amount, unless the payer covers it in the
+            // fee mint.
+            let fee = vault.withdrawal_fee(withdrawal.amount);
+            let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+                fee
+            } else {
+                pay_fee_in_fee_mint(
+                    ctx.remaining_accounts,
+                    vault,
+                    &withdrawal.asset,
+                    fee,
+                    &ctx.accounts.payer,
+                    &ctx.accounts.fee_treasury.key(),
+                    &ctx.accounts.token_program,
+                    clock.unix_timestamp,
+                )
+                .inspect_err(|err| {
+                    log_ticket_failure(transfer_idx, ticket, Some(withdrawal_idx), err);
+                })?;
+                0
+            };
+            let received = withdrawal.amount - fee_in_kind;
+
             match &withdrawal.asset {
                 Asset::Sol => {
                     ctx.accounts
@@ -273,39 +451,61 @@ pub fn bulk_withdraw<'info>(
                         .ok_or(ErrorCode::Overflow)?;
                     recipient_info
                         .lamports()
-                        .checked_add(withdrawal.amount)
+                        .checked_add(received)
                         .ok_or(ErrorCode::Overflow)?;
 
-                    **ctx.accounts.treasury.try_borrow_mut_lamports()? -= withdrawal.amount;
-                    **recipient_info.try_borrow_mut_lamports()? += withdrawal.amount;
-
-                    msg!(
-                        "Bulk Withdrawal SOL [ticket {}, withdrawal {}]: request_id={}, recipient={}, amount={}, valid_signers={}, metadata={:?}",
-                        transfer_idx,
-                        withdrawal_idx,
-                        ticket.request_id,
-                        ticket.recipient,
-                        withdrawal.amount,
-                        validated_sigs.len(),
-                        metadata,
-                    );
+                    transfer_lamports(&ctx.accounts.treasury, recipient_info, received)?;
+                    transfer_lamports(
+                        &ctx.accounts.treasury,
+                        &ctx.accounts.fee_treasury,
+                        fee_in_kind,
+                    )?;
+
+                    if itemized {
+                        emit_cpi!(WithdrawEvent {
+                            vault: vault_key,
+                            sequence: vault.sequence,
+                            request_id: ticket.request_id,
+                            recipient: ticket.recipient,
+                            asset: withdrawal.asset.clone(),
+                            amount: received,
+                            gross_amount: received + fee_in_kind,
+                            fee,
+                            admin: false,
+                            valid_signers,
+                            metadata: metadata.clone(),
+                        });
+                    }
                 }
-                Asset::SplToken { mint } => {
+                Asset::SplToken { mint } | Asset::Nft { mint } => {
                     let recipient_token_accounts_by_mint = recipient_token_accounts
                         .get(transfer_idx)
                         .ok_or(ErrorCode::TokenAccountNotFound)?;
                     let recipient_token = recipient_token_accounts_by_mint
-                        .get(&mint)
+                        .get(mint)
                         .ok_or(ErrorCode::TokenAccountNotFound)?;
                     let vault_token = vault_token_accounts
-                        .get_mut(&mint)
+                        .get_mut(mint)
                         .ok_or(ErrorCode::TokenAccountNotFound)?;
-
-                    let seeds = &[b"vault", vault.vault_seed.as_bytes(), &[vault.bump]];
+                    let mint_account = mint_accounts
+                        .get(mint)
+                        .ok_or(ErrorCode::MintAccountNotFound)?;
+                    let gross_amount =
+                        gross_amount_for_net(&mint_account.to_account_info(), received)?;
+                    let gross_fee =
+                        gross_amount_for_net(&mint_account.to_account_info(), fee_in_kind)?;
+
+                    let seeds = &[
+                        vault.seed_namespace(),
+                        vault.seed_parent(),
+                        vault.vault_seed.as_bytes(),
+                        &[vault.bump],
+                    ];
                     let signer_seeds = &[&seeds[..]];
 
-                    let cpi_accounts = Transfer {
+                    let cpi_accounts = TransferChecked {
                         from: vault_token.to_account_info(),
+                        mint: mint_account.to_account_info(),
                         to: recipient_token.to_account_info(),
                         authority: vault.to_account_info(),
                     };
@@ -313,49 +513,155 @@ pub fn bulk_withdraw<'info>(
                     let cpi_ctx =
                         CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-                    token::transfer(cpi_ctx, withdrawal.amount)?;
+                    transfer_checked_with_hook(
+                        cpi_ctx,
+                        ctx.remaining_accounts,
+                        gross_amount,
+                        mint_account.decimals,
+                    )?;
+                    transfer_spl_fee(
+                        ctx.remaining_accounts,
+                        vault,
+                        vault_token,
+                        mint_account,
+                        &ctx.accounts.fee_treasury.key(),
+                        &ctx.accounts.token_program,
+                        gross_fee,
+                    )?;
                     vault_token.reload()?;
 
-                    msg!(
-                        "Bulk Withdraw SPL Token [ticket {}, withdrawal {}]: request_id={}, mint={}, recipient={}, amount={}, valid_signers={}, metadata={:?}",
-                        transfer_idx,
-                        withdrawal_idx,
-                        ticket.request_id,
-                        mint,
-                        ticket.recipient,
-                        withdrawal.amount,
-                        validated_sigs.len(),
-                        metadata,
-                    );
+                    if itemized {
+                        emit_cpi!(WithdrawEvent {
+                            vault: vault_key,
+                            sequence: vault.sequence,
+                            request_id: ticket.request_id,
+                            recipient: ticket.recipient,
+                            asset: withdrawal.asset.clone(),
+                            amount: received,
+                            gross_amount: gross_amount + gross_fee,
+                            fee,
+                            admin: false,
+                            valid_signers,
+                            metadata: metadata.clone(),
+                        });
+                    }
                 }
             }
         }
     }
 
+    for (ticket, is_blocked) in bulk_ticket.tickets.iter().zip(blocked.iter()) {
+        if !is_blocked {
+            notify_hook(
+                ctx.remaining_accounts,
+                vault,
+                BatchKind::BulkWithdraw,
+                ticket.request_id,
+                ticket.recipient,
+                &ticket.withdrawals,
+            )?;
+        }
+    }
+
+    if !itemized {
+        // Only the tickets that paid out.
+        let paid = || {
+            bulk_ticket
+                .tickets
+                .iter()
+                .zip(blocked.iter())
+                .filter(|(_, is_blocked)| !**is_blocked)
+                .map(|(t, _)| t)
+        };
+        let request_ids: Vec<u64> = paid().map(|t| t.request_id).collect();
+        let summary = batch_summary(
+            vault_key,
+            vault.sequence,
+            BatchKind::BulkWithdraw,
+            request_ids,
+            paid().flat_map(|t| t.withdrawals.iter()),
+            vault.fee_bps,
+            valid_signers,
+            metadata,
+        )?;
+        emit_cpi!(summary);
+    }
+
+    // The payer funded the nonce accounts created above.
+    let rebate = pay_relayer_rebate(
+        vault,
+        &ctx.accounts.treasury,
+        &ctx.accounts.payer,
+        created_nonces,
+    )?;
+    if rebate > 0 {
+        emit_cpi!(RelayerRebateEvent {
+            vault: vault_key,
+            sequence: vault.sequence,
+            payer: ctx.accounts.payer.key(),
+            request_ids: bulk_ticket.tickets.iter().map(|t| t.request_id).collect(),
+            amount: rebate,
+        });
+    }
+
     Ok(())
 }
 
+/// Log which ticket of the batch, and which of its withdrawals, `err` comes from: the error code
+/// alone doesn't tell.
+fn log_ticket_failure(
+    ticket_idx: usize,
+    ticket: &WithdrawalTicket,
+    withdrawal_idx: Option<usize>,
+    err: &Error,
+) {
+    match withdrawal_idx {
+        Some(withdrawal_idx) => msg!(
+            "Bulk ticket {} (request id {}) withdrawal {} failed: {}",
+            ticket_idx,
+            ticket.request_id,
+            withdrawal_idx,
+            err
+        ),
+        None => msg!(
+            "Bulk ticket {} (request id {}) failed: {}",
+            ticket_idx,
+            ticket.request_id,
+            err
+        ),
+    }
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(bulk_ticket: BulkWithdrawalTicket)]
 pub struct BulkWithdraw<'info> {
     #[account(
         mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
         bump = vault.bump
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump = vault.treasury_bump
     )]
     /// CHECK: Treasury PDA verified by seeds
     pub treasury: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, credited only while a fee is set
+    pub fee_treasury: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
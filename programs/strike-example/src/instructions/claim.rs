@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    authorize, check_identity_pass, check_nft_amount, check_recipient_allowed, check_usd_caps,
+    check_withdrawal_limits, consume_allowance, find_associated_token_account, find_mint,
+    gross_amount_for_net, is_recipient_blocked, is_supported_ticket_version, is_ticket_active,
+    pay_fee_in_fee_mint, transfer_checked_with_hook, transfer_lamports, transfer_spl_fee,
+    verify_claim_proof, withdrawable_lamports, Quorum,
+};
+
+pub fn publish_claim_root(
+    ctx: Context<PublishClaimRoot>,
+    ticket: ClaimRootTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.next_sequence();
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+    require!(
+        clock.unix_timestamp <= ticket.claim_expiry,
+        ErrorCode::ClaimWindowClosed
+    );
+
+    // The root pays out like a batch of withdrawals, so it needs the same M of N.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    // Check nonce hasn't been used (replay protection)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    let claim_root = &mut ctx.accounts.claim_root;
+    claim_root.vault = vault.key();
+    claim_root.request_id = ticket.request_id;
+    claim_root.root = ticket.root;
+    claim_root.claim_expiry = ticket.claim_expiry;
+    claim_root.claimed = 0;
+    claim_root.bump = ctx.bumps.claim_root;
+
+    emit_cpi!(ClaimRootEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        root: ticket.root,
+        claim_expiry: ticket.claim_expiry,
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+pub fn claim<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Claim<'info>>,
+    leaf: ClaimLeaf,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let vault = &ctx.accounts.vault;
+    let claim_root = &mut ctx.accounts.claim_root;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp <= claim_root.claim_expiry,
+        ErrorCode::ClaimWindowClosed
+    );
+    require!(
+        leaf.recipient == ctx.accounts.recipient.key(),
+        ErrorCode::InvalidRecipient
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &leaf.recipient)?;
+    check_identity_pass(
+        ctx.remaining_accounts,
+        vault,
+        &leaf.recipient,
+        clock.unix_timestamp,
+    )?;
+    require!(leaf.asset_amount.amount > 0, ErrorCode::InvalidAmount);
+    check_withdrawal_limits(vault, std::slice::from_ref(&leaf.asset_amount))?;
+    check_usd_caps(
+        ctx.remaining_accounts,
+        vault,
+        std::slice::from_ref(&leaf.asset_amount),
+        clock.unix_timestamp,
+    )?;
+    require!(
+        verify_claim_proof(leaf.hash(), &proof, &claim_root.root),
+        ErrorCode::InvalidClaimProof
+    );
+
+    // `claimed_leaf` is created by this instruction, so a leaf can only be redeemed once.
+    let claimed_leaf = &mut ctx.accounts.claimed_leaf;
+    claimed_leaf.recipient = leaf.recipient;
+    claimed_leaf.amount = leaf.asset_amount.amount;
+    claim_root.claimed = claim_root
+        .claimed
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // A blocked recipient's leaf is spent without paying out.
+    if is_recipient_blocked(ctx.remaining_accounts, vault, &leaf.recipient)? {
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: claim_root.request_id,
+            recipient: leaf.recipient,
+        });
+        return Ok(());
+    }
+
+    let asset = &leaf.asset_amount.asset;
+    let amount = leaf.asset_amount.amount;
+    consume_allowance(
+        ctx.remaining_accounts,
+        &vault.key(),
+        &leaf.recipient,
+        asset,
+        amount,
+    )?;
+
+    // The protocol fee comes out of the leaf amount, unless the payer covers it in the fee mint.
+    let fee = vault.withdrawal_fee(amount);
+    let fee_in_kind = if vault.pays_fee_in_kind(asset) {
+        fee
+    } else {
+        pay_fee_in_fee_mint(
+            ctx.remaining_accounts,
+            vault,
+            asset,
+            fee,
+            &ctx.accounts.payer,
+            &ctx.accounts.fee_treasury.key(),
+            &ctx.accounts.token_program,
+            clock.unix_timestamp,
+        )?;
+        0
+    };
+    let received = amount - fee_in_kind;
+
+    let gross_amount = match asset {
+        Asset::Sol => {
+            let available = withdrawable_lamports(
+                &ctx.accounts.treasury.to_account_info(),
+                vault.treasury_reserve,
+            )?;
+
+            require!(available >= amount, ErrorCode::InsufficientFunds);
+
+            transfer_lamports(&ctx.accounts.treasury, &ctx.accounts.recipient, received)?;
+            transfer_lamports(
+                &ctx.accounts.treasury,
+                &ctx.accounts.fee_treasury,
+                fee_in_kind,
+            )?;
+            amount
+        }
+        Asset::SplToken { mint } | Asset::Nft { mint } => {
+            let token_program = ctx.accounts.token_program.key();
+            let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+            check_nft_amount(asset, amount, &mint_account)?;
+            let vault_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &vault.key(),
+                mint,
+                &token_program,
+            )?;
+            let recipient_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &leaf.recipient,
+                mint,
+                &token_program,
+            )?;
+
+            // What's left of the leaf after the protocol fee is net to the recipient; the vault
+            // covers any transfer fee.
+            let gross_amount = gross_amount_for_net(&mint_account.to_account_info(), received)?;
+            let gross_fee = gross_amount_for_net(&mint_account.to_account_info(), fee_in_kind)?;
+
+            require!(
+                vault_token.amount
+                    >= gross_amount
+                        .checked_add(gross_fee)
+                        .ok_or(ErrorCode::Overflow)?,
+                ErrorCode::InsufficientFunds
+            );
+
+            let seeds = &[
+                vault.seed_namespace(),
+                vault.seed_parent(),
+                vault.vault_seed.as_bytes(),
+                &[vault.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = TransferChecked {
+                from: vault_token.to_account_info(),
+                mint: mint_account.to_account_info(),
+                to: recipient_token.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer_checked_with_hook(
+                cpi_ctx,
+                ctx.remaining_accounts,
+                gross_amount,
+                mint_account.decimals,
+            )?;
+            transfer_spl_fee(
+                ctx.remaining_accounts,
+                vault,
+                &vault_token,
+                &mint_account,
+                &ctx.accounts.fee_treasury.key(),
+                &ctx.accounts.token_program,
+                gross_fee,
+            )?;
+            gross_amount + gross_fee
+        }
+    };
+
+    emit_cpi!(ClaimEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: claim_root.request_id,
+        index: leaf.index,
+        recipient: leaf.recipient,
+        asset: asset.clone(),
+        amount: received,
+        gross_amount,
+        fee,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ticket: ClaimRootTicket)]
+pub struct PublishClaimRoot<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ClaimRoot::INIT_SPACE,
+        seeds = [b"claim_root", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub claim_root: Account<'info, ClaimRoot>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf: ClaimLeaf)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, credited only while a fee is set
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"claim_root", vault.key().as_ref(), &claim_root.request_id.to_le_bytes()],
+        bump = claim_root.bump
+    )]
+    pub claim_root: Account<'info, ClaimRoot>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ClaimedLeaf::INIT_SPACE,
+        seeds = [b"claimed", claim_root.key().as_ref(), &leaf.index.to_le_bytes()],
+        bump
+    )]
+    pub claimed_leaf: Account<'info, ClaimedLeaf>,
+
+    /// CHECK: Recipient verified against the claim leaf
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
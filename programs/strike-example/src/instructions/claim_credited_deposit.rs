@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    authorize, check_duplicate_assets, is_supported_ticket_version, is_ticket_active, Quorum,
+};
+
+/// Credit a user's `DepositRecord` with funds that arrived for them on another chain, once
+/// signers weighing `m_threshold` attest to it. The user presents the ticket themselves. Nothing
+/// moves into the vault; the ledger is the only thing updated.
+pub fn claim_credited_deposit(
+    ctx: Context<ClaimCreditedDeposit>,
+    ticket: CreditTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    require!(!ticket.credits.is_empty(), ErrorCode::NoDepositsProvided);
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        ticket.user == ctx.accounts.user.key(),
+        ErrorCode::InvalidRecipient
+    );
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+    check_duplicate_assets(&ticket.credits)?;
+    for credit in ticket.credits.iter() {
+        require!(credit.amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            vault.is_whitelisted(&credit.asset),
+            ErrorCode::AssetNotWhitelisted
+        );
+    }
+
+    // A credit is as good as a withdrawal to the user, so it needs the same M of N.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    // Check nonce hasn't been used (replay protection)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    let deposit_record = &mut ctx.accounts.deposit_record;
+    deposit_record.open(
+        vault.key(),
+        ctx.accounts.user.key(),
+        ctx.bumps.deposit_record,
+    );
+
+    emit_cpi!(SourceRefEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        source_ref: ticket.source_ref,
+    });
+    for credit in ticket.credits.iter() {
+        let total = deposit_record.credit(&credit.asset, credit.amount)?;
+        emit_cpi!(DepositCreditedEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            user: ticket.user,
+            asset: credit.asset.clone(),
+            amount: credit.amount,
+            total,
+            valid_signers,
+        });
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ticket: CreditTicket)]
+pub struct ClaimCreditedDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [b"deposit_record", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
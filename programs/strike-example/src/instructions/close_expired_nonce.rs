@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::NonceCollectedEvent;
+use super::util::{fee_for, transfer_lamports};
+
+/// Close the nonce PDA of `request_id` (an admin nonce when `admin`) once its ticket expired more
+/// than `NONCE_GC_DELAY` ago. Anyone can crank it: `NONCE_GC_CRANKER_BPS` of the reclaimed rent
+/// goes to the cranker, the rest to the treasury. The expired ticket can't run again, so the
+/// ticket stays spent, though a new ticket under the same request id no longer conflicts with it.
+/// Vetoed request ids never expire and are never collected.
+pub fn close_expired_nonce(
+    ctx: Context<CloseExpiredNonce>,
+    request_id: u64,
+    admin: bool,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let nonce_account = ctx.accounts.nonce_account.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.nonce_account.used
+            && clock.unix_timestamp
+                > ctx
+                    .accounts
+                    .nonce_account
+                    .expiry
+                    .saturating_add(NONCE_GC_DELAY),
+        ErrorCode::NonceNotCollectible
+    );
+
+    // `close = treasury` sweeps what the cranker doesn't take.
+    let rent = nonce_account.lamports();
+    let cranker_amount = fee_for(rent, NONCE_GC_CRANKER_BPS);
+    transfer_lamports(&nonce_account, &ctx.accounts.cranker, cranker_amount)?;
+
+    emit_cpi!(NonceCollectedEvent {
+        vault: ctx.accounts.vault.key(),
+        sequence: ctx.accounts.vault.sequence,
+        request_id,
+        admin,
+        cranker: ctx.accounts.cranker.key(),
+        cranker_amount,
+        treasury_amount: rent - cranker_amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(request_id: u64, admin: bool)]
+pub struct CloseExpiredNonce<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [
+            if admin { ADMIN_NONCE_SEED } else { NONCE_SEED },
+            vault.key().as_ref(),
+            &request_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
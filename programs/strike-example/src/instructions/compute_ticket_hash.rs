@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+
+use super::models::*;
+
+/// Any ticket the program accepts, for `compute_ticket_hash`.
+#[allow(clippy::large_enum_variant)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AnyTicket {
+    AddAsset {
+        ticket: AddAssetTicket,
+    },
+    UpdateAsset {
+        ticket: UpdateAssetTicket,
+    },
+    RemoveAsset {
+        ticket: RemoveAssetTicket,
+    },
+    SetMinWithdrawal {
+        ticket: SetMinWithdrawalTicket,
+    },
+    SetUsdCap {
+        ticket: SetUsdCapTicket,
+    },
+    SetMaxTicketUsd {
+        ticket: SetMaxTicketUsdTicket,
+    },
+    AddRecipient {
+        ticket: AddRecipientTicket,
+    },
+    RemoveRecipient {
+        ticket: RemoveRecipientTicket,
+    },
+    BlockRecipient {
+        ticket: BlockRecipientTicket,
+    },
+    UnblockRecipient {
+        ticket: UnblockRecipientTicket,
+    },
+    RotateValidator {
+        ticket: RotateValidatorTicket,
+    },
+    RotateAdminSigners {
+        ticket: RotateAdminSignersTicket,
+    },
+    SetLogVerbosity {
+        ticket: SetLogVerbosityTicket,
+    },
+    SetRecipientAllowlist {
+        ticket: SetRecipientAllowlistTicket,
+    },
+    SetFee {
+        ticket: SetFeeTicket,
+    },
+    SetRelayerRebate {
+        ticket: SetRelayerRebateTicket,
+    },
+    SetMaxTicketLifetime {
+        ticket: SetMaxTicketLifetimeTicket,
+    },
+    SetExtraNetworkIds {
+        ticket: SetExtraNetworkIdsTicket,
+    },
+    SetExpiryGrace {
+        ticket: SetExpiryGraceTicket,
+    },
+    SetNonceTree {
+        ticket: SetNonceTreeTicket,
+    },
+    SetAuthScheme {
+        ticket: SetAuthSchemeTicket,
+    },
+    SetHookProgram {
+        ticket: SetHookProgramTicket,
+    },
+    SetDepositRegistry {
+        ticket: SetDepositRegistryTicket,
+    },
+    SetIdentityNetwork {
+        ticket: SetIdentityNetworkTicket,
+    },
+    SetStrictSignatures {
+        ticket: SetStrictSignaturesTicket,
+    },
+    ProposeSignerSet {
+        ticket: ProposeSignerSetTicket,
+    },
+    SetTreasuryReserve {
+        ticket: SetTreasuryReserveTicket,
+    },
+    SetReplayMode {
+        ticket: SetReplayModeTicket,
+    },
+    SetGuardian {
+        ticket: SetGuardianTicket,
+    },
+    DelegateStake {
+        ticket: DelegateStakeTicket,
+    },
+    DeactivateStake {
+        ticket: DeactivateStakeTicket,
+    },
+    WithdrawStake {
+        ticket: WithdrawStakeTicket,
+    },
+    LiquidStake {
+        ticket: LiquidStakeTicket,
+    },
+    LiquidUnstake {
+        ticket: LiquidUnstakeTicket,
+    },
+    Rebalance {
+        ticket: RebalanceTicket,
+    },
+    Swap {
+        ticket: SwapTicket,
+    },
+    CollectFees {
+        ticket: CollectFeesTicket,
+    },
+    SweepDust {
+        ticket: SweepDustTicket,
+    },
+    CloseVaultTokenAccount {
+        ticket: CloseVaultTokenAccountTicket,
+    },
+    SweepAsset {
+        ticket: SweepAssetTicket,
+    },
+    UpgradeProgram {
+        ticket: UpgradeProgramTicket,
+    },
+    SetAllowance {
+        ticket: SetAllowanceTicket,
+    },
+    SetTreasury {
+        ticket: SetTreasuryTicket,
+    },
+    SetWormholeEmitter {
+        ticket: SetWormholeEmitterTicket,
+    },
+    CreateSubVault {
+        ticket: CreateSubVaultTicket,
+    },
+    ReclaimSubVault {
+        ticket: ReclaimSubVaultTicket,
+    },
+    CreateReceiptMint {
+        ticket: CreateReceiptMintTicket,
+    },
+    AdminDeposit {
+        ticket: AdminDepositTicket,
+    },
+    Withdrawal {
+        ticket: WithdrawalTicket,
+    },
+    AdminWithdrawal {
+        ticket: AdminWithdrawalTicket,
+    },
+    BulkWithdrawal {
+        ticket: BulkWithdrawalTicket,
+    },
+    AuthorizedCpi {
+        ticket: AuthorizedCpiTicket,
+    },
+    AddStrategy {
+        ticket: AddStrategyTicket,
+    },
+    RemoveStrategy {
+        ticket: RemoveStrategyTicket,
+    },
+    AddCranker {
+        ticket: AddCrankerTicket,
+    },
+    RemoveCranker {
+        ticket: RemoveCrankerTicket,
+    },
+    SetCrankIncentive {
+        ticket: SetCrankIncentiveTicket,
+    },
+    Strategy {
+        ticket: StrategyTicket,
+    },
+    Execute {
+        ticket: ExecuteTicket,
+    },
+    CreateStream {
+        ticket: CreateStreamTicket,
+    },
+    CreateSchedule {
+        ticket: CreateScheduleTicket,
+    },
+    ClaimRoot {
+        ticket: ClaimRootTicket,
+    },
+    Credit {
+        ticket: CreditTicket,
+    },
+}
+
+impl AnyTicket {
+    pub fn ticket(&self) -> &dyn Ticket {
+        match self {
+            AnyTicket::AddAsset { ticket } => ticket,
+            AnyTicket::UpdateAsset { ticket } => ticket,
+            AnyTicket::RemoveAsset { ticket } => ticket,
+            AnyTicket::SetMinWithdrawal { ticket } => ticket,
+            AnyTicket::SetUsdCap { ticket } => ticket,
+            AnyTicket::SetMaxTicketUsd { ticket } => ticket,
+            AnyTicket::AddRecipient { ticket } => ticket,
+            AnyTicket::RemoveRecipient { ticket } => ticket,
+            AnyTicket::BlockRecipient { ticket } => ticket,
+            AnyTicket::UnblockRecipient { ticket } => ticket,
+            AnyTicket::RotateValidator { ticket } => ticket,
+            AnyTicket::RotateAdminSigners { ticket } => ticket,
+            AnyTicket::SetLogVerbosity { ticket } => ticket,
+            AnyTicket::SetRecipientAllowlist { ticket } => ticket,
+            AnyTicket::SetFee { ticket } => ticket,
+            AnyTicket::SetRelayerRebate { ticket } => ticket,
+            AnyTicket::SetMaxTicketLifetime { ticket } => ticket,
+            AnyTicket::SetExtraNetworkIds { ticket } => ticket,
+            AnyTicket::SetExpiryGrace { ticket } => ticket,
+            AnyTicket::SetNonceTree { ticket } => ticket,
+            AnyTicket::SetAuthScheme { ticket } => ticket,
+            AnyTicket::SetHookProgram { ticket } => ticket,
+            AnyTicket::SetDepositRegistry { ticket } => ticket,
+            AnyTicket::SetIdentityNetwork { ticket } => ticket,
+            AnyTicket::SetStrictSignatures { ticket } => ticket,
+            AnyTicket::ProposeSignerSet { ticket } => ticket,
+            AnyTicket::SetTreasuryReserve { ticket } => ticket,
+            AnyTicket::SetReplayMode { ticket } => ticket,
+            AnyTicket::SetGuardian { ticket } => ticket,
+            AnyTicket::DelegateStake { ticket } => ticket,
+            AnyTicket::DeactivateStake { ticket } => ticket,
+            AnyTicket::WithdrawStake { ticket } => ticket,
+            AnyTicket::LiquidStake { ticket } => ticket,
+            AnyTicket::LiquidUnstake { ticket } => ticket,
+            AnyTicket::Rebalance { ticket } => ticket,
+            AnyTicket::Swap { ticket } => ticket,
+            AnyTicket::CollectFees { ticket } => ticket,
+            AnyTicket::SweepDust { ticket } => ticket,
+            AnyTicket::CloseVaultTokenAccount { ticket } => ticket,
+            AnyTicket::SweepAsset { ticket } => ticket,
+            AnyTicket::UpgradeProgram { ticket } => ticket,
+            AnyTicket::SetAllowance { ticket } => ticket,
+            AnyTicket::SetTreasury { ticket } => ticket,
+            AnyTicket::SetWormholeEmitter { ticket } => ticket,
+            AnyTicket::CreateSubVault { ticket } => ticket,
+            AnyTicket::ReclaimSubVault { ticket } => ticket,
+            AnyTicket::CreateReceiptMint { ticket } => ticket,
+            AnyTicket::AdminDeposit { ticket } => ticket,
+            AnyTicket::Withdrawal { ticket } => ticket,
+            AnyTicket::AdminWithdrawal { ticket } => ticket,
+            AnyTicket::BulkWithdrawal { ticket } => ticket,
+            AnyTicket::AuthorizedCpi { ticket } => ticket,
+            AnyTicket::AddStrategy { ticket } => ticket,
+            AnyTicket::RemoveStrategy { ticket } => ticket,
+            AnyTicket::AddCranker { ticket } => ticket,
+            AnyTicket::RemoveCranker { ticket } => ticket,
+            AnyTicket::SetCrankIncentive { ticket } => ticket,
+            AnyTicket::Strategy { ticket } => ticket,
+            AnyTicket::Execute { ticket } => ticket,
+            AnyTicket::CreateStream { ticket } => ticket,
+            AnyTicket::CreateSchedule { ticket } => ticket,
+            AnyTicket::ClaimRoot { ticket } => ticket,
+            AnyTicket::Credit { ticket } => ticket,
+        }
+    }
+}
+
+/// Return the hash validators sign for `ticket` under `domain_suffix`, computed exactly as the
+/// instructions consuming it do. Needs no accounts: simulate it to check an off-chain ticket
+/// encoder byte for byte.
+pub fn compute_ticket_hash(
+    _ctx: Context<ComputeTicketHash>,
+    ticket: AnyTicket,
+    domain_suffix: String,
+) -> Result<[u8; 32]> {
+    Ok(ticket.ticket().hash(&domain_suffix))
+}
+
+#[derive(Accounts)]
+pub struct ComputeTicketHash {}
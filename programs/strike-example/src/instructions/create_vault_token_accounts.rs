@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::TokenInterface;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::models::Asset;
+use super::util::create_vault_ata;
+
+/// Create the vault's associated token accounts for `mints` in one transaction, e.g. when
+/// onboarding an asset list. Only mints whitelisted as an SPL or NFT asset get an account, so
+/// callers can't grow the vault's footprint with arbitrary mints. Accounts that already exist
+/// are left as they are.
+///
+/// Remaining accounts: the mint and the vault's associated token account for every mint, all
+/// owned by `token_program`.
+pub fn create_vault_token_accounts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateVaultTokenAccounts<'info>>,
+    mints: Vec<Pubkey>,
+) -> Result<()> {
+    require!(mints.len() <= MAX_ASSETS as usize, ErrorCode::TooManyAssets);
+    let vault = &ctx.accounts.vault;
+    let mut seen = HashSet::new();
+    for mint in &mints {
+        require!(seen.insert(mint), ErrorCode::DuplicateAsset);
+        require!(
+            vault.is_whitelisted(&Asset::SplToken { mint: *mint })
+                || vault.is_whitelisted(&Asset::Nft { mint: *mint }),
+            ErrorCode::AssetNotWhitelisted
+        );
+    }
+
+    for mint in &mints {
+        create_vault_ata(
+            ctx.remaining_accounts,
+            &vault.to_account_info(),
+            mint,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateVaultTokenAccounts<'info> {
+    #[account(
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
 
 use super::accounts::*;
+use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
+use super::util::{
+    batch_summary, check_depositor_attestation, check_nft_amount, find_associated_token_account,
+    find_mint, mint_receipt, notify_hook, sanitize_metadata, transfer_checked_with_hook,
+};
 
 pub fn deposit<'info>(
     ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
@@ -11,17 +17,29 @@ pub fn deposit<'info>(
     request_id: u64,
     metadata: Option<String>,
 ) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
     require!(!deposits.is_empty(), ErrorCode::NoDepositsProvided);
 
     let vault = &ctx.accounts.vault;
-
-    for deposit_item in deposits {
+    check_depositor_attestation(
+        ctx.remaining_accounts,
+        vault,
+        &ctx.accounts.user.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    let deposit_record = &mut ctx.accounts.deposit_record;
+    deposit_record.open(
+        vault.key(),
+        ctx.accounts.user.key(),
+        ctx.bumps.deposit_record,
+    );
+
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
+    for deposit_item in deposits.iter() {
         require!(deposit_item.amount > 0, ErrorCode::InvalidAmount);
 
-        require!(
-            vault.whitelisted_assets.contains(&deposit_item.asset),
-            ErrorCode::AssetNotWhitelisted
-        );
+        vault.check_deposit(&deposit_item.asset, deposit_item.amount)?;
 
         match deposit_item.asset {
             Asset::Sol => {
@@ -40,80 +58,160 @@ pub fn deposit<'info>(
                         ctx.accounts.system_program.to_account_info(),
                     ],
                 )?;
-
-                msg!(
-                    "Deposit SOL: request_id={}, user={}, amount={}, treasury_balance={}, metadata={:?}",
-                    request_id,
-                    ctx.accounts.user.key(),
+                vault.check_tvl_cap(&deposit_item.asset, ctx.accounts.treasury.lamports())?;
+                let deposited = deposit_record.credit(&deposit_item.asset, deposit_item.amount)?;
+                vault.check_user_cap(&deposit_item.asset, deposited)?;
+                mint_receipt(
+                    ctx.remaining_accounts,
+                    vault,
+                    &ctx.accounts.user.key(),
+                    &deposit_item.asset,
+                    &ctx.accounts.token_program,
                     deposit_item.amount,
-                    ctx.accounts.treasury.to_account_info().lamports(),
-                    metadata,
-                );
-            }
-            Asset::SplToken { mint } => {
-                let mut user_token_account: Option<Account<'info, TokenAccount>> = None;
-                let mut vault_token_account: Option<Account<'info, TokenAccount>> = None;
-
-                for acc in ctx.remaining_accounts.iter() {
-                    if let Ok(token_acc) = Account::<TokenAccount>::try_from(acc) {
-                        if token_acc.mint == mint {
-                            if token_acc.owner == ctx.accounts.user.key() {
-                                user_token_account = Some(token_acc);
-                            } else if token_acc.owner == vault.key() {
-                                vault_token_account = Some(token_acc);
-                            }
-                        }
-                    }
-                }
+                )?;
 
-                let user_token = user_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-                let vault_token = vault_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
+                if itemized {
+                    emit_cpi!(DepositEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id,
+                        depositor: ctx.accounts.user.key(),
+                        asset: deposit_item.asset.clone(),
+                        amount: deposit_item.amount,
+                        net_amount: deposit_item.amount,
+                        admin: false,
+                        valid_signers: 0,
+                        metadata: metadata.clone(),
+                    });
+                }
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&deposit_item.asset, deposit_item.amount, &mint_account)?;
+                let user_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &ctx.accounts.user.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let mut vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let balance_before = vault_token.amount;
 
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: user_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
                     to: vault_token.to_account_info(),
                     authority: ctx.accounts.user.to_account_info(),
                 };
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-                token::transfer(cpi_ctx, deposit_item.amount)?;
-
-                msg!(
-                    "Deposit SPL: request_id={}, mint={}, user={}, amount={}, vault_token_balance={}, metadata={:?}",
-                    request_id,
-                    mint,
-                    user_token.key(),
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
                     deposit_item.amount,
-                    vault_token.amount,
-                    metadata,
-                );
+                    mint_account.decimals,
+                )?;
+
+                // Transfer-fee mints credit less than the amount sent.
+                vault_token.reload()?;
+                let net_amount = vault_token
+                    .amount
+                    .checked_sub(balance_before)
+                    .ok_or(ErrorCode::Overflow)?;
+                vault.check_tvl_cap(&deposit_item.asset, vault_token.amount)?;
+                let deposited = deposit_record.credit(&deposit_item.asset, net_amount)?;
+                vault.check_user_cap(&deposit_item.asset, deposited)?;
+                mint_receipt(
+                    ctx.remaining_accounts,
+                    vault,
+                    &ctx.accounts.user.key(),
+                    &deposit_item.asset,
+                    &ctx.accounts.token_program,
+                    net_amount,
+                )?;
+
+                if itemized {
+                    emit_cpi!(DepositEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id,
+                        depositor: ctx.accounts.user.key(),
+                        asset: deposit_item.asset.clone(),
+                        amount: deposit_item.amount,
+                        net_amount,
+                        admin: false,
+                        valid_signers: 0,
+                        metadata: metadata.clone(),
+                    });
+                }
             }
         }
     }
 
+    // Deposits are final once the hook, if any, accepts them.
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::Deposit,
+        request_id,
+        ctx.accounts.user.key(),
+        &deposits,
+    )?;
+
+    if !itemized {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::Deposit,
+            vec![request_id],
+            &deposits,
+            0,
+            0,
+            metadata,
+        )?;
+        emit_cpi!(summary);
+    }
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
         bump = vault.bump
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump = vault.treasury_bump
     )]
     /// CHECK: Treasury PDA verified by seeds
     pub treasury: UncheckedAccount<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [b"deposit_record", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
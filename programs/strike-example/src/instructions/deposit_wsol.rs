@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{BatchKind, DepositEvent};
+use super::models::*;
+use super::util::{check_depositor_attestation, notify_hook, sanitize_metadata, transfer_lamports};
+
+pub fn deposit_wsol(
+    ctx: Context<DepositWsol>,
+    amount: u64,
+    request_id: u64,
+    metadata: Option<String>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let vault = &ctx.accounts.vault;
+
+    // wSOL is credited to the SOL treasury, so SOL must be whitelisted.
+    vault.check_deposit(&Asset::Sol, amount)?;
+    check_depositor_attestation(
+        ctx.remaining_accounts,
+        vault,
+        &ctx.accounts.user.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Rent paid by the user to open the temporary wSOL account.
+    let temp_rent = ctx.accounts.temp_wsol.to_account_info().lamports();
+
+    // Move wSOL from user -> temporary vault-owned wSOL account.
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_wsol.to_account_info(),
+        to: ctx.accounts.temp_wsol.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    // Unwrap by closing the temporary account into the treasury.
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.temp_wsol.to_account_info(),
+        destination: ctx.accounts.treasury.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)?;
+
+    // Refund the temporary account rent so the treasury only gains `amount`.
+    transfer_lamports(
+        &ctx.accounts.treasury,
+        &ctx.accounts.user.to_account_info(),
+        temp_rent,
+    )?;
+    vault.check_tvl_cap(&Asset::Sol, ctx.accounts.treasury.lamports())?;
+
+    let deposit_record = &mut ctx.accounts.deposit_record;
+    deposit_record.open(
+        vault.key(),
+        ctx.accounts.user.key(),
+        ctx.bumps.deposit_record,
+    );
+    let deposited = deposit_record.credit(&Asset::Sol, amount)?;
+    vault.check_user_cap(&Asset::Sol, deposited)?;
+
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::Deposit,
+        request_id,
+        ctx.accounts.user.key(),
+        &[AssetAmount {
+            asset: Asset::Sol,
+            amount,
+        }],
+    )?;
+
+    emit_cpi!(DepositEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id,
+        depositor: ctx.accounts.user.key(),
+        asset: Asset::Sol,
+        amount,
+        net_amount: amount,
+        admin: false,
+        valid_signers: 0,
+        metadata,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositWsol<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = native_mint,
+        associated_token::authority = user
+    )]
+    pub user_wsol: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"wsol", vault.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = vault
+    )]
+    pub temp_wsol: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [b"deposit_record", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
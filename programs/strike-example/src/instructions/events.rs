@@ -0,0 +1,521 @@
+use anchor_lang::prelude::*;
+
+use super::models::{
+    Asset, AssetAmount, AssetSettings, AuthScheme, LogVerbosity, PriceOracle, WeightedSigner,
+};
+
+#[event]
+pub struct VaultInitializedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64, // `Vault::sequence` after the emitting instruction, shared by all its events
+    pub authority: Pubkey,
+    pub network_id: u64,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub signers: Vec<WeightedSigner>,
+    pub admin_signers: Vec<WeightedSigner>, // empty when `signers` also approve admin ops
+    pub domain_suffix: String,
+    pub auth_scheme: AuthScheme,
+    pub genesis_hash: [u8; 32], // zero when network_id isn't bound to a cluster
+    pub whitelisted_assets: Vec<Asset>, // set by initialize_full, empty for initialize
+}
+
+/// `migrate` converted a vault to the current layout.
+#[event]
+pub struct VaultMigratedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub authority: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// One per deposited asset line, for user and admin deposits.
+#[event]
+pub struct DepositEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub depositor: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,
+    pub net_amount: u64, // credited to the vault after any transfer fee
+    pub admin: bool,
+    pub valid_signers: u8, // 0 for user deposits
+    pub metadata: Option<String>,
+}
+
+/// One per withdrawn asset line, for user, bulk and admin withdrawals.
+#[event]
+pub struct WithdrawEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,       // received by the recipient
+    pub gross_amount: u64, // debited from the vault, including the fee and any transfer fee
+    pub fee: u64,          // protocol fee, paid by the payer in the fee mint unless taken in kind
+    pub admin: bool,
+    pub valid_signers: u8,
+    pub metadata: Option<String>,
+}
+
+/// Lamports the treasury refunded to the payer of a withdrawal, see `set_relayer_rebate`.
+#[event]
+pub struct RelayerRebateEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub payer: Pubkey,
+    pub request_ids: Vec<u64>,
+    pub amount: u64,
+}
+
+/// A ticket paying a blocked recipient was consumed without paying out, see `block_recipient`.
+#[event]
+pub struct BlockedRecipientEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64, // of the ticket, or of the claim root for a claim
+    pub recipient: Pubkey,
+}
+
+/// The remote event a ticket names as its origin, e.g. the origin-chain tx hash. Emitted once per
+/// ticket carrying a `source_ref`, whatever the log verbosity.
+#[event]
+pub struct SourceRefEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub source_ref: [u8; 32],
+}
+
+/// A withdrawal was authorized by a Wormhole VAA instead of validator signatures, see
+/// `withdraw_with_vaa`.
+#[event]
+pub struct VaaWithdrawalEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub vaa_sequence: u64,
+}
+
+/// The guardian consumed a withdrawal request id before it executed, see `veto_withdrawal`.
+#[event]
+pub struct WithdrawalVetoedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub guardian: Pubkey,
+}
+
+/// A signer-approved instruction was executed with the vault PDA as signer, see `execute`.
+#[event]
+pub struct ExecuteEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub program_id: Pubkey,
+    pub accounts: u8,
+    pub valid_signers: u8,
+}
+
+/// A payout batch was published; its leaves are paid out by `ClaimEvent`s.
+#[event]
+pub struct ClaimRootEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub root: [u8; 32],
+    pub claim_expiry: i64,
+    pub valid_signers: u8,
+}
+
+/// One per redeemed claim leaf.
+#[event]
+pub struct ClaimEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64, // of the claim root
+    pub index: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,       // received by the recipient
+    pub gross_amount: u64, // debited from the vault, including the fee and any transfer fee
+    pub fee: u64,          // protocol fee, paid by the payer in the fee mint unless taken in kind
+}
+
+/// A `claim_credited_deposit` line added to the user's `DepositRecord`.
+#[event]
+pub struct DepositCreditedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub user: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,
+    pub total: u64, // the record's running total of the asset after the credit
+    pub valid_signers: u8,
+}
+
+/// A stream locked funds for its recipient, see `create_stream`.
+#[event]
+pub struct StreamCreatedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub total: u64, // locked in the stream, net of any transfer fee
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub rate: u64,
+    pub valid_signers: u8,
+}
+
+/// One per `claim_stream` that paid out.
+#[event]
+pub struct StreamClaimEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64, // of the stream
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,  // released from the stream by this claim
+    pub claimed: u64, // released so far
+}
+
+/// A recurring payment was approved, see `create_schedule`.
+#[event]
+pub struct ScheduleCreatedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,
+    pub interval: i64,
+    pub first_execution: i64,
+    pub payments: u32,
+    pub valid_signers: u8,
+}
+
+/// One per payment cranked by `execute_schedule`.
+#[event]
+pub struct ScheduleExecutedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64, // of the schedule
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,       // received by the recipient
+    pub gross_amount: u64, // debited from the vault, including any transfer fee
+    pub remaining: u32,    // payments left after this one
+}
+
+/// An expired nonce PDA was closed and its rent split, see `close_expired_nonce`.
+#[event]
+pub struct NonceCollectedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub admin: bool,
+    pub cranker: Pubkey,
+    pub cranker_amount: u64,
+    pub treasury_amount: u64,
+}
+
+/// The program's upgrade authority moved to the vault's upgrade authority PDA, see
+/// `accept_upgrade_authority`.
+#[event]
+pub struct UpgradeAuthorityAcceptedEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub previous_authority: Pubkey,
+    pub upgrade_authority: Pubkey,
+}
+
+/// Treasury lamports paid to a registered cranker for one successful crank.
+#[event]
+pub struct CrankIncentiveEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub cranker: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BatchKind {
+    Deposit,
+    AdminDeposit,
+    Withdraw,
+    WithdrawWsol,
+    BulkWithdraw,
+    AdminWithdraw,
+    WithdrawWithVaa,
+}
+
+/// Emitted once per instruction in place of the per-line events when the vault is in
+/// `LogVerbosity::Compact`. Totals are per asset, as requested by the caller.
+#[event]
+pub struct BatchSummaryEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub kind: BatchKind,
+    pub request_ids: Vec<u64>,
+    pub lines: u32,
+    pub totals: Vec<AssetAmount>,
+    pub fees: Vec<AssetAmount>, // protocol fees per asset, empty when none were charged
+    pub valid_signers: u8,
+    pub metadata: Option<String>,
+}
+
+/// What `notify_hook` sends the vault's hook program after a deposit or withdrawal, following
+/// `HOOK_IX_DISCRIMINATOR` in the instruction data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HookPayload {
+    pub vault: Pubkey,
+    pub kind: BatchKind,
+    pub request_id: u64,
+    pub account: Pubkey,          // depositor or recipient
+    pub assets: Vec<AssetAmount>, // as requested, before any fee
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum AdminOp {
+    AddAsset {
+        asset: Asset,
+        settings: AssetSettings,
+        added: bool,
+    },
+    UpdateAsset {
+        asset: Asset,
+        settings: AssetSettings,
+    },
+    RemoveAsset {
+        asset: Asset,
+        removed: bool,
+    },
+    SetMinWithdrawal {
+        asset: Asset,
+        min_amount: u64,
+    },
+    SetUsdCap {
+        asset: Asset,
+        oracle: PriceOracle,
+        max_usd: u64,
+    },
+    SetMaxTicketUsd {
+        max_ticket_usd: u64,
+    },
+    SetLogVerbosity {
+        log_verbosity: LogVerbosity,
+    },
+    SetFee {
+        fee_bps: u16,
+        fee_mint: Pubkey,
+    },
+    AddRecipient {
+        recipient: Pubkey,
+        added: bool,
+    },
+    RemoveRecipient {
+        recipient: Pubkey,
+        removed: bool,
+    },
+    SetRecipientAllowlist {
+        enabled: bool,
+    },
+    BlockRecipient {
+        recipient: Pubkey,
+        added: bool,
+    },
+    UnblockRecipient {
+        recipient: Pubkey,
+        removed: bool,
+    },
+    SetRelayerRebate {
+        max_rebate: u64,
+    },
+    SetMaxTicketLifetime {
+        max_lifetime: u64,
+    },
+    SetExpiryGrace {
+        grace: u64,
+    },
+    SetNonceTree {
+        nonce_tree: Pubkey,
+        base_request_id: u64,
+        max_depth: u32,
+    },
+    SetExtraNetworkIds {
+        extra_network_ids: Vec<u64>,
+    },
+    SetGuardian {
+        guardian: Pubkey,
+    },
+    SetHookProgram {
+        hook_program: Pubkey,
+    },
+    SetDepositRegistry {
+        registry: Pubkey,
+        schema: Pubkey,
+    },
+    SetIdentityNetwork {
+        network: Pubkey,
+    },
+    SetStrictSignatures {
+        enabled: bool,
+    },
+    SetTreasuryReserve {
+        reserve: u64,
+    },
+    SetReplayMode {
+        watermark_replay: bool,
+        watermark: u64,
+    },
+    ProposeSignerSet {
+        signers_hash: [u8; 32],
+        signers_len: u8,
+        m_threshold: u8,
+        admin_threshold: u8,
+    },
+    SetAuthScheme {
+        auth_scheme: AuthScheme,
+    },
+    DelegateStake {
+        vote_account: Pubkey,
+        amount: u64,
+    },
+    DeactivateStake {
+        vote_account: Pubkey,
+    },
+    WithdrawStake {
+        vote_account: Pubkey,
+        amount: u64,
+    },
+    LiquidStake {
+        stake_pool: Pubkey,
+        lamports: u64,
+        pool_tokens: u64,
+    },
+    LiquidUnstake {
+        stake_pool: Pubkey,
+        pool_tokens: u64,
+        lamports: u64,
+    },
+    Rebalance {
+        asset: Asset,
+        source: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    },
+    Swap {
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+    },
+    CollectFees {
+        recipient: Pubkey,
+        fees: Vec<AssetAmount>,
+    },
+    SweepDust {
+        destination: Pubkey,
+        swept: Vec<AssetAmount>,
+    },
+    CloseVaultTokenAccount {
+        mint: Pubkey,
+        rent_recipient: Pubkey,
+        lamports: u64,
+    },
+    SweepAsset {
+        mint: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        closed: bool,
+    },
+    UpgradeProgram {
+        buffer: Pubkey,
+        spill: Pubkey,
+    },
+    RotateAdminSigners {
+        signers: Vec<WeightedSigner>,
+        admin_threshold: u8,
+    },
+    SetAllowance {
+        recipient: Pubkey,
+        asset: Asset,
+        cap: u64,
+    },
+    CreateReceiptMint {
+        asset: Asset,
+        mint: Pubkey,
+    },
+    AuthorizedCpi {
+        program_id: Pubkey,
+        accounts: u8,
+    },
+    AddStrategy {
+        program_id: Pubkey,
+        added: bool,
+    },
+    RemoveStrategy {
+        program_id: Pubkey,
+        removed: bool,
+    },
+    AddCranker {
+        cranker: Pubkey,
+        added: bool,
+    },
+    RemoveCranker {
+        cranker: Pubkey,
+        removed: bool,
+    },
+    SetCrankIncentive {
+        incentive: u64,
+    },
+    ExecuteStrategy {
+        program_id: Pubkey,
+        accounts: u8,
+    },
+    SetTreasury {
+        name: String,
+        m_threshold: u8,
+    },
+    SetWormholeEmitter {
+        core_bridge: Pubkey,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    },
+    CreateSubVault {
+        sub_vault: Pubkey,
+        sub_vault_seed: String,
+    },
+    ReclaimSubVault {
+        sub_vault: Pubkey,
+        reclaimed: Vec<AssetAmount>,
+    },
+}
+
+#[event]
+pub struct AdminOpEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub op: AdminOp,
+    pub valid_signers: u8,
+}
+
+#[event]
+pub struct RotateEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub request_id: u64,
+    pub signers: Vec<WeightedSigner>,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub valid_signers: u8,
+}
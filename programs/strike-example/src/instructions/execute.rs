@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use solana_program::keccak;
+use solana_program::program::invoke_signed;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::ExecuteEvent;
+use super::models::*;
+use super::util::{
+    authorize, is_supported_ticket_version, is_ticket_active, ticket_cpi_instruction, Quorum,
+};
+
+/// Execute the instruction serialized in the ticket with the vault PDA as signer, once signers
+/// weighing `m_threshold` approve it.
+///
+/// Remaining accounts: the target program, followed by the ticket's account metas in order,
+/// then the instructions sysvar when signers approve through precompile instructions.
+pub fn execute<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Execute<'info>>,
+    ticket: ExecuteTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.next_sequence();
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+
+    // Validate the signatures.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    let ticket_hash = ticket.hash(&vault.domain_suffix);
+    let data_hash = keccak::hash(&ticket.data).to_bytes();
+    let instruction = ticket_cpi_instruction(
+        &vault.key(),
+        &ticket.program_id,
+        &ticket.accounts,
+        ticket.data,
+        &data_hash,
+        ctx.remaining_accounts,
+    )?;
+
+    // Consume the nonce BEFORE the CPI (replay protection, prevents reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket_hash, ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+    // Persist before the CPI and reload after it, so a call back into this program neither
+    // misses the record nor gets overwritten when the vault is written at exit.
+    vault.exit(&crate::ID)?;
+
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+    vault.reload()?;
+
+    emit!(ExecuteEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        program_id: ticket.program_id,
+        accounts: ticket.accounts.len() as u8,
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: ExecuteTicket)]
+pub struct Execute<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
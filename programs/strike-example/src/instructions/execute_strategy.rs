@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use solana_program::program::invoke_signed;
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+use super::util::ticket_cpi_instruction;
+
+/// Perform the CPI committed to by an `admin_threshold` ticket into a program on the vault's
+/// strategy allowlist, with the vault PDA as signer. Lets lending and yield integrations run
+/// without an adapter per protocol.
+///
+/// Remaining accounts: the target program, followed by the ticket's account metas in order,
+/// then the instructions sysvar when signers approve through precompile instructions.
+pub fn execute_strategy<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteStrategy<'info>>,
+    ticket: StrategyTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+    data: Vec<u8>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        ctx.accounts
+            .strategy_allowlist
+            .programs
+            .contains(&ticket.program_id),
+        ErrorCode::StrategyNotApproved
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let instruction = ticket_cpi_instruction(
+        &vault.key(),
+        &ticket.program_id,
+        &ticket.accounts,
+        data,
+        &ticket.data_hash,
+        ctx.remaining_accounts,
+    )?;
+
+    // Mark nonce as used BEFORE the CPI (prevent reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+    // Persist before the CPI and reload after it, so a call back into this program neither
+    // misses the record nor gets overwritten when the vault is written at exit.
+    vault.exit(&crate::ID)?;
+
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+    vault.reload()?;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::ExecuteStrategy {
+            program_id: ticket.program_id,
+            accounts: ticket.accounts.len() as u8,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: StrategyTicket)]
+pub struct ExecuteStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [b"strategy_allowlist", vault.key().as_ref()],
+        bump = strategy_allowlist.bump
+    )]
+    pub strategy_allowlist: Account<'info, StrategyAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,57 +1,183 @@
+use std::collections::HashSet;
+
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::TokenInterface;
 
 use super::accounts::*;
 use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::VaultInitializedEvent;
+use super::models::{Asset, AssetConfig, AssetSettings, AuthScheme, LogVerbosity, WeightedSigner};
+use super::util::{
+    asset_decimals, check_auth_scheme, check_genesis_binding, check_signer_set, check_threshold,
+    create_vault_ata,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize(
     ctx: Context<Initialize>,
     vault_seed: String,
     network_id: u64,
     m_threshold: u8,
     admin_threshold: u8,
-    signers: Vec<[u8; 20]>, // Ethereum addresses (20 bytes)
+    signers: Vec<WeightedSigner>,
+    admin_signers: Vec<WeightedSigner>, // empty to let `signers` approve admin ops too
+    domain_suffix: String,
+    auth_scheme: AuthScheme,
+    genesis_hash: Option<[u8; 32]>, // binds network_id to this cluster, see `genesis_network_id`
+) -> Result<()> {
+    init_vault(
+        ctx.accounts,
+        &ctx.bumps,
+        vault_seed,
+        network_id,
+        m_threshold,
+        admin_threshold,
+        signers,
+        admin_signers,
+        domain_suffix,
+        auth_scheme,
+        genesis_hash,
+        Vec::new(),
+    )
+}
+
+/// `initialize` plus the vault token accounts and whitelist for `assets` in one transaction.
+/// Remaining accounts: the mint and the vault's associated token account for every SPL and NFT
+/// asset, all owned by `token_program`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_full<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeFull<'info>>,
+    vault_seed: String,
+    network_id: u64,
+    m_threshold: u8,
+    admin_threshold: u8,
+    signers: Vec<WeightedSigner>,
+    admin_signers: Vec<WeightedSigner>,
+    domain_suffix: String,
+    auth_scheme: AuthScheme,
+    genesis_hash: Option<[u8; 32]>,
+    assets: Vec<Asset>,
 ) -> Result<()> {
-    let signers_len = signers.len();
+    let whitelisted_assets = assets
+        .into_iter()
+        .map(|asset| {
+            let decimals = asset_decimals(ctx.remaining_accounts, &asset)?;
+            Ok(AssetConfig::new(asset, decimals, &AssetSettings::default()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    init_vault(
+        &mut ctx.accounts.base,
+        &ctx.bumps.base,
+        vault_seed,
+        network_id,
+        m_threshold,
+        admin_threshold,
+        signers,
+        admin_signers,
+        domain_suffix,
+        auth_scheme,
+        genesis_hash,
+        whitelisted_assets,
+    )?;
+
+    let base = &ctx.accounts.base;
+    for config in &base.vault.whitelisted_assets {
+        let (Asset::SplToken { mint } | Asset::Nft { mint }) = &config.asset else {
+            continue;
+        };
+        create_vault_ata(
+            ctx.remaining_accounts,
+            &base.vault.to_account_info(),
+            mint,
+            &base.authority.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &base.system_program,
+        )?;
+    }
 
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn init_vault(
+    accounts: &mut Initialize,
+    bumps: &InitializeBumps,
+    vault_seed: String,
+    network_id: u64,
+    m_threshold: u8,
+    admin_threshold: u8,
+    signers: Vec<WeightedSigner>,
+    admin_signers: Vec<WeightedSigner>,
+    domain_suffix: String,
+    auth_scheme: AuthScheme,
+    genesis_hash: Option<[u8; 32]>,
+    whitelisted_assets: Vec<AssetConfig>,
+) -> Result<()> {
     require!(
-        signers_len > 0 && signers_len <= MAX_SIGNERS,
-        ErrorCode::InvalidSignersCount
+        domain_suffix.len() <= MAX_DOMAIN_SUFFIX_LEN,
+        ErrorCode::DomainSuffixTooLong
     );
+    check_auth_scheme(&auth_scheme)?;
+    check_genesis_binding(network_id, &genesis_hash)?;
     require!(
-        m_threshold > 0 && (m_threshold as usize) <= signers_len,
-        ErrorCode::InvalidThreshold
+        whitelisted_assets.len() <= MAX_ASSETS as usize,
+        ErrorCode::TooManyAssets
     );
+    let mut seen = HashSet::new();
     require!(
-        admin_threshold > 0 && (admin_threshold as usize) <= signers_len,
-        ErrorCode::InvalidThreshold
+        whitelisted_assets
+            .iter()
+            .all(|config| seen.insert(&config.asset)),
+        ErrorCode::DuplicateAsset
     );
 
-    // Check for duplicate signers
-    for i in 0..signers_len {
-        for j in (i + 1)..signers_len {
-            require!(signers[i] != signers[j], ErrorCode::DuplicateSigner);
-        }
+    let now = Clock::get()?.unix_timestamp;
+    check_signer_set(&signers, m_threshold, now)?;
+    if admin_signers.is_empty() {
+        check_threshold(&signers, admin_threshold)?;
+    } else {
+        check_signer_set(&admin_signers, admin_threshold, now)?;
     }
 
-    let vault = &mut ctx.accounts.vault;
+    let vault = &mut accounts.vault;
     vault.version = CURRENT_VERSION;
-    vault.authority = ctx.accounts.authority.key();
+    vault.authority = accounts.authority.key();
     vault.vault_seed = vault_seed;
     vault.network_id = network_id;
     vault.m_threshold = m_threshold;
     vault.admin_threshold = admin_threshold;
     vault.signers = signers;
-    vault.bump = ctx.bumps.vault;
-    vault.treasury_bump = ctx.bumps.treasury;
+    vault.admin_signers = admin_signers;
+    vault.whitelisted_assets = whitelisted_assets;
+    vault.domain_suffix = domain_suffix;
+    vault.log_verbosity = LogVerbosity::Itemized;
+    vault.auth_scheme = auth_scheme;
+    vault.genesis_hash = genesis_hash.unwrap_or_default();
+    vault.bump = bumps.vault;
+    vault.treasury_bump = bumps.treasury;
+    vault.next_sequence();
 
-    msg!(
-        "Vault initialized: m_threshold={}, admin_threshold={}, N={}, authority={}",
+    emit!(VaultInitializedEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        authority: vault.authority,
+        network_id,
         m_threshold,
         admin_threshold,
-        signers_len,
-        vault.authority
-    );
+        signers: vault.signers.clone(),
+        admin_signers: vault.admin_signers.clone(),
+        domain_suffix: vault.domain_suffix.clone(),
+        auth_scheme,
+        genesis_hash: vault.genesis_hash,
+        whitelisted_assets: vault
+            .whitelisted_assets
+            .iter()
+            .map(|config| config.asset.clone())
+            .collect(),
+    });
 
     Ok(())
 }
@@ -63,7 +189,7 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = 8 + Vault::INIT_SPACE,
-        seeds = [b"vault", vault_seed.as_bytes()],
+        seeds = [VAULT_SEED, vault_seed.as_bytes()],
         bump
     )]
     pub vault: Account<'info, Vault>,
@@ -72,10 +198,10 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = 8,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump
     )]
-    /// CHECK: Treasury PDA initialized as system-owned account with no data
+    /// CHECK: Treasury PDA initialized as a program-owned account with no data
     pub treasury: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -83,3 +209,11 @@ pub struct Initialize<'info> {
 
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct InitializeFull<'info> {
+    pub base: Initialize<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
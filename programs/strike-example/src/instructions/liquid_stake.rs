@@ -0,0 +1,389 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
+use solana_program::{stake, sysvar};
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+use super::util::transfer_lamports;
+
+/// `StakePoolInstruction` variants of the SPL stake pool program.
+const DEPOSIT_SOL: u8 = 14;
+const WITHDRAW_SOL: u8 = 16;
+
+/// Deposit `lamports` of treasury SOL into an SPL stake pool and keep the pool tokens in the
+/// vault's token account. The pool token is whitelisted if it isn't yet, so it can be withdrawn
+/// like any other asset. The stake pool must be updated for the current epoch.
+pub fn liquid_stake(
+    ctx: Context<LiquidStake>,
+    ticket: LiquidStakeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.lamports > 0, ErrorCode::InvalidAmount);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let treasury = &ctx.accounts.treasury;
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury.data_len());
+    let available = treasury.lamports().saturating_sub(rent_exempt_minimum);
+    require!(available >= ticket.lamports, ErrorCode::InsufficientFunds);
+
+    // The stake pool pulls SOL with a system transfer, which the program-owned treasury can't
+    // sign. Route it through a system-owned PDA that is emptied again by the deposit.
+    let lst_funding = &ctx.accounts.lst_funding;
+    transfer_lamports(treasury, lst_funding, ticket.lamports)?;
+    // Deposit the whole balance, so lamports sent to the PDA by anyone else can't leave it
+    // below rent exemption.
+    let deposit_lamports = lst_funding.lamports();
+
+    let pool_token_account = &mut ctx.accounts.vault_pool_token_account;
+    let balance_before = pool_token_account.amount;
+
+    let vault_key = ctx.accounts.vault.key();
+    let funding_seeds = &[b"lst_funding", vault_key.as_ref(), &[ctx.bumps.lst_funding]];
+    let mut data = vec![DEPOSIT_SOL];
+    data.extend_from_slice(&deposit_lamports.to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_STAKE_POOL_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.stake_pool.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.pool_withdraw_authority.key(), false),
+                AccountMeta::new(ctx.accounts.reserve_stake.key(), false),
+                AccountMeta::new(lst_funding.key(), true),
+                AccountMeta::new(pool_token_account.key(), false),
+                AccountMeta::new(ctx.accounts.manager_fee_account.key(), false),
+                // The vault refers itself, so the referral share of the fee stays with it.
+                AccountMeta::new(pool_token_account.key(), false),
+                AccountMeta::new(ctx.accounts.pool_mint.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            data,
+        },
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.pool_withdraw_authority.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            lst_funding.to_account_info(),
+            pool_token_account.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+        ],
+        &[&funding_seeds[..]],
+    )?;
+
+    pool_token_account.reload()?;
+    let pool_tokens = pool_token_account
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        pool_tokens >= ticket.min_pool_tokens,
+        ErrorCode::LiquidStakeSlippage
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let asset = Asset::SplToken {
+        mint: ticket.pool_mint,
+    };
+    if !vault.is_whitelisted(&asset) {
+        require!(
+            vault.whitelisted_assets.len() < MAX_ASSETS as usize,
+            ErrorCode::TooManyAssets
+        );
+        let decimals = ctx.accounts.pool_mint.decimals;
+        vault
+            .whitelisted_assets
+            .push(AssetConfig::new(asset, decimals, &AssetSettings::default()));
+    }
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::LiquidStake {
+            stake_pool: ticket.stake_pool,
+            lamports: deposit_lamports,
+            pool_tokens,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Redeem `pool_tokens` of the vault's stake pool tokens for SOL from the pool's reserve, paid
+/// into the treasury. Fails when the reserve can't cover it; the pool's staker has to rebalance
+/// first.
+pub fn liquid_unstake(
+    ctx: Context<LiquidUnstake>,
+    ticket: LiquidUnstakeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.pool_tokens > 0, ErrorCode::InvalidAmount);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let treasury = &ctx.accounts.treasury;
+    let lamports_before = treasury.lamports();
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let mut data = vec![WITHDRAW_SOL];
+    data.extend_from_slice(&ticket.pool_tokens.to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_STAKE_POOL_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.stake_pool.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.pool_withdraw_authority.key(), false),
+                AccountMeta::new_readonly(vault.key(), true),
+                AccountMeta::new(ctx.accounts.vault_pool_token_account.key(), false),
+                AccountMeta::new(ctx.accounts.reserve_stake.key(), false),
+                AccountMeta::new(treasury.key(), false),
+                AccountMeta::new(ctx.accounts.manager_fee_account.key(), false),
+                AccountMeta::new(ctx.accounts.pool_mint.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.stake_history.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.stake_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            data,
+        },
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.pool_withdraw_authority.to_account_info(),
+            vault.to_account_info(),
+            ctx.accounts.vault_pool_token_account.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            treasury.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+        ],
+        &[&vault_seeds[..]],
+    )?;
+
+    let lamports = treasury
+        .lamports()
+        .checked_sub(lamports_before)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        lamports >= ticket.min_lamports,
+        ErrorCode::LiquidStakeSlippage
+    );
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::LiquidUnstake {
+            stake_pool: ticket.stake_pool,
+            pool_tokens: ticket.pool_tokens,
+            lamports,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: LiquidStakeTicket)]
+pub struct LiquidStake<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lst_funding", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: System-owned PDA verified by seeds, only holds lamports during the deposit
+    pub lst_funding: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.stake_pool, owner = SPL_STAKE_POOL_PROGRAM_ID)]
+    /// CHECK: Stake pool, checked by the stake pool program
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Pool withdraw authority, checked by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pool reserve stake, checked by the stake pool program
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pool manager fee account, checked by the stake pool program
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    /// CHECK: SPL stake pool program
+    pub stake_pool_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: LiquidUnstakeTicket)]
+pub struct LiquidUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.stake_pool, owner = SPL_STAKE_POOL_PROGRAM_ID)]
+    /// CHECK: Stake pool, checked by the stake pool program
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Pool withdraw authority, checked by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pool reserve stake, checked by the stake pool program
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pool manager fee account, checked by the stake pool program
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_pool_token_account: Account<'info, TokenAccount>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: Stake history sysvar
+    pub stake_history: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    /// CHECK: SPL stake pool program
+    pub stake_pool_program: UncheckedAccount<'info>,
+    #[account(address = stake::program::ID)]
+    /// CHECK: Native stake program
+    pub stake_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::VaultMigratedEvent;
+use super::models::{AssetConfig, AssetSettings, LogVerbosity, WeightedSigner};
+use super::util::asset_decimals;
+
+/// Convert a version 1 vault to the current layout. The vault's authority signs and pays for the
+/// extra space. Version 1 signers become weight 1 signers without expiry, so `m_threshold` and
+/// `admin_threshold` still count signatures, and they keep approving admin ops. Whitelisted
+/// assets get default settings; remaining accounts are the mints of the whitelisted SPL tokens,
+/// for their decimals. Every other field starts as `initialize` leaves it. A vault already at
+/// `CURRENT_VERSION` is left as it is.
+pub fn migrate<'info>(ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let legacy = {
+        let data = vault_info.try_borrow_data()?;
+        require!(
+            data.starts_with(Vault::DISCRIMINATOR),
+            ErrorCode::InvalidVault
+        );
+        // Every version starts with `version` and `authority`.
+        let (version, authority) = <(u8, Pubkey)>::deserialize(&mut &data[8..])?;
+        require!(
+            authority == ctx.accounts.authority.key(),
+            ErrorCode::NotVaultAuthority
+        );
+        if version >= CURRENT_VERSION {
+            return Ok(());
+        }
+        VaultV1::deserialize(&mut &data[8..])?
+    };
+
+    let whitelisted_assets = legacy
+        .whitelisted_assets
+        .iter()
+        .map(|asset| {
+            let decimals = asset_decimals(ctx.remaining_accounts, asset)?;
+            Ok(AssetConfig::new(
+                asset.clone(),
+                decimals,
+                &AssetSettings::default(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let space = 8 + Vault::INIT_SPACE;
+    let top_up = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(vault_info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: vault_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    vault_info.resize(space)?;
+
+    // Start from zeroed data, as `init` does, and carry the version 1 fields over.
+    let mut data = vault_info.try_borrow_mut_data()?;
+    data.fill(0);
+    let mut vault = Vault::try_deserialize_unchecked(&mut &data[..])?;
+    vault.version = CURRENT_VERSION;
+    vault.authority = legacy.authority;
+    vault.vault_seed = legacy.vault_seed;
+    vault.m_threshold = legacy.m_threshold;
+    vault.admin_threshold = legacy.admin_threshold;
+    vault.network_id = legacy.network_id;
+    vault.signers = legacy
+        .signers
+        .iter()
+        .map(|&address| WeightedSigner {
+            address,
+            weight: 1,
+            valid_until: None,
+        })
+        .collect();
+    vault.whitelisted_assets = whitelisted_assets;
+    vault.bump = legacy.bump;
+    vault.treasury_bump = legacy.treasury_bump;
+    vault.reserve = legacy.reserve;
+    vault.log_verbosity = LogVerbosity::Itemized;
+    vault.next_sequence();
+    vault.try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "migrated vault from v{} to v{}",
+        legacy.version,
+        CURRENT_VERSION
+    );
+    emit!(VaultMigratedEvent {
+        vault: vault_info.key(),
+        sequence: vault.sequence,
+        authority: vault.authority,
+        from_version: legacy.version,
+        to_version: CURRENT_VERSION,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// CHECK: A vault in any layout version, so it can't be loaded as `Vault`; `migrate` checks
+    /// the discriminator and the authority before parsing it
+    #[account(mut, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -3,10 +3,36 @@ pub mod admin;
 pub mod admin_deposit;
 pub mod admin_withdraw;
 pub mod bulk_withdraw;
+pub mod claim;
+pub mod claim_credited_deposit;
+pub mod close_expired_nonce;
+pub mod compute_ticket_hash;
+pub mod create_vault_token_accounts;
 pub mod deposit;
+pub mod deposit_wsol;
+pub mod execute;
+#[cfg(feature = "authorized-cpi")]
+pub mod execute_authorized_cpi;
+pub mod execute_strategy;
 pub mod initialize;
+pub mod liquid_stake;
+pub mod migrate;
+pub mod nonce_tree;
+pub mod rebalance;
+pub mod schedule;
+pub mod signer_set;
+pub mod stake;
+pub mod stream;
+pub mod swap;
+pub mod upgrade_program;
+pub mod validate_ticket;
+pub mod veto_withdrawal;
+pub mod views;
 pub mod withdraw;
+pub mod withdraw_with_vaa;
+pub mod withdraw_wsol;
 
+pub mod events;
 pub mod models;
 pub mod util;
 
@@ -15,16 +41,273 @@ pub use admin::*;
 pub use admin_deposit::*;
 pub use admin_withdraw::*;
 pub use bulk_withdraw::*;
+pub use claim::*;
+pub use claim_credited_deposit::*;
+pub use close_expired_nonce::*;
+pub use compute_ticket_hash::*;
+pub use create_vault_token_accounts::*;
 pub use deposit::*;
+pub use deposit_wsol::*;
+pub use events::*;
+pub use execute::*;
+#[cfg(feature = "authorized-cpi")]
+pub use execute_authorized_cpi::*;
+pub use execute_strategy::*;
 pub use initialize::*;
+pub use liquid_stake::*;
+pub use migrate::*;
 pub use models::*;
+pub use nonce_tree::*;
+pub use rebalance::*;
+pub use schedule::*;
+pub use signer_set::*;
+pub use stake::*;
+pub use stream::*;
+pub use swap::*;
+pub use upgrade_program::*;
+pub use validate_ticket::*;
+pub use veto_withdrawal::*;
+pub use views::*;
 pub use withdraw::*;
+pub use withdraw_with_vaa::*;
+pub use withdraw_wsol::*;
 
 pub mod constant {
-    pub const CURRENT_VERSION: u8 = 1;
-    pub const MAX_SIGNERS: usize = 10; // N
-    pub const MAX_ASSETS: usize = 20;
+    use anchor_lang::prelude::constant;
+
+    pub const CURRENT_VERSION: u8 = 2; // vaults below it need `migrate`
+    pub const TICKET_VERSION: u8 = 2; // version new tickets are signed with
+    /// Oldest ticket version still accepted. Version 1 hashed optional fields only when set,
+    /// which let a forged ticket reuse another's signatures, so it isn't.
+    pub const MIN_TICKET_VERSION: u8 = 2;
+    #[constant]
+    pub const MAX_SIGNERS: u8 = 10; // N
+    #[constant]
+    pub const MAX_ASSETS: u8 = 20;
+    /// Signers a `SignerSet` account holds, for federations past `MAX_SIGNERS`
+    #[constant]
+    pub const MAX_SIGNER_SET_LEN: u8 = 64;
+    pub const MAX_ALLOWLISTED_RECIPIENTS: usize = 32;
+    pub const MAX_BLOCKED_RECIPIENTS: usize = 32;
+    pub const MAX_STRATEGY_PROGRAMS: usize = 16;
+    pub const MAX_CRANKERS: usize = 16;
     pub const MAX_BULK_TICKETS: usize = 4;
+    pub const MAX_RECEIPT_WITHDRAWALS: usize = 4; // withdrawal lines a receipt holds
+    pub const MAX_DOMAIN_SUFFIX_LEN: usize = 32;
+    #[constant]
+    pub const MAX_EXTRA_NETWORK_IDS: usize = 4;
+    /// Longest `expiry_grace` in seconds, far below `NONCE_GC_DELAY` so a nonce is never
+    /// collected while its ticket may still run
+    #[constant]
+    pub const MAX_EXPIRY_GRACE: u64 = 300;
+    pub const MAX_MEMO_LEN: usize = 256;
+    #[constant]
+    pub const MAX_METADATA_LEN: usize = 256;
+    pub const MAX_TREASURY_NAME_LEN: usize = 16;
+    pub const SOL_DECIMALS: u8 = 9;
+    pub const MAX_FEE_BPS: u16 = 1_000; // 10%
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    pub const RELAYER_TX_FEE: u64 = 5_000; // base fee of a single-signature transaction
+    /// Signatures a ticket may carry past the signers it needs, see `check_signature_budget`
+    #[constant]
+    pub const EXTRA_SIGNATURES: u16 = 2;
+    /// Seconds past its ticket's expiry before `close_expired_nonce` may close a nonce PDA
+    #[constant]
+    pub const NONCE_GC_DELAY: i64 = 30 * 24 * 60 * 60;
+    #[constant]
+    pub const NONCE_GC_CRANKER_BPS: u16 = 5_000; // cranker's share of a collected nonce's rent
+    /// Secp256r1 (P-256) signature verification precompile
+    pub const SECP256R1_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("Secp256r1SigVerify1111111111111111111111111");
+    /// SPL stake pool program, used by jitoSOL and most other liquid staking tokens
+    pub const SPL_STAKE_POOL_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgN2AerxyrnTcA");
+    /// Pyth pull oracle receiver, owner of the `PriceUpdateV2` accounts USD caps read
+    pub const PYTH_RECEIVER_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+    /// Anchor discriminator of the Pyth receiver's `PriceUpdateV2` account
+    pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+    /// Chainlink OCR2 store, owner of the feed `Transmissions` accounts USD caps read
+    pub const CHAINLINK_STORE_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+    /// Anchor discriminator of the Chainlink store's `Transmissions` account
+    pub const CHAINLINK_TRANSMISSIONS_DISCRIMINATOR: [u8; 8] = [96, 179, 69, 66, 128, 129, 73, 117];
+    pub const CHAINLINK_HEADER_SIZE: usize = 192; // feed header, padded
+    pub const CHAINLINK_TRANSMISSION_SIZE: usize = 48;
+    pub const USD_DECIMALS: u8 = 6; // USD caps are in micro-dollars
+    /// Civic gateway program, owner of the gateway tokens (identity passes) withdrawals may require
+    pub const CIVIC_GATEWAY_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("gatem74V238djXdzWnJf94Wo1DcnuGkfijbf3AuBhfs");
+    /// Jupiter aggregator v6, the only program `swap` routes through
+    pub const JUPITER_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+    /// SPL account compression program, owner of the concurrent Merkle trees nonce trees are
+    pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+    /// SPL noop program the compression program logs tree changes through
+    pub const SPL_NOOP_PROGRAM_ID: solana_program::pubkey::Pubkey =
+        solana_program::pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+    /// Anchor discriminators of the compression program's `init_empty_merkle_tree` and
+    /// `replace_leaf`
+    pub const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+    pub const REPLACE_LEAF_DISCRIMINATOR: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+    #[constant]
+    pub const MAX_NONCE_TREE_DEPTH: u32 = 30; // the compression program's deepest tree
+    /// Anchor discriminator of `on_vault_event`, the instruction hook programs expose
+    pub const HOOK_IX_DISCRIMINATOR: [u8; 8] = [0xd0, 0x96, 0x93, 0xa8, 0xac, 0x17, 0x92, 0x64];
+    /// secp256k1 curve order n / 2, big-endian; signatures with a larger s are rejected
+    pub const SECP256K1_HALF_ORDER: [u8; 32] = [
+        0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b,
+        0x20, 0xa0,
+    ];
+
+    // PDA seeds
+    #[constant]
+    pub const VAULT_SEED: &[u8] = b"vault";
+    #[constant]
+    pub const SUB_VAULT_SEED: &[u8] = b"sub_vault";
+    #[constant]
+    pub const TREASURY_SEED: &[u8] = b"treasury";
+    #[constant]
+    pub const NONCE_SEED: &[u8] = b"nonce";
+    #[constant]
+    pub const ADMIN_NONCE_SEED: &[u8] = b"admin_nonce";
+
+    // Domain separators tickets are hashed under, see `Ticket::separator`
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADD_ASSET: &str = "strike-protocol-v1-AddAsset";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_UPDATE_ASSET: &str = "strike-protocol-v1-UpdateAsset";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_REMOVE_ASSET: &str = "strike-protocol-v1-RemoveAsset";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_MIN_WITHDRAWAL: &str = "strike-protocol-v1-SetMinWithdrawal";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_USD_CAP: &str = "strike-protocol-v1-SetUsdCap";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_MAX_TICKET_USD: &str = "strike-protocol-v1-SetMaxTicketUsd";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADD_RECIPIENT: &str = "strike-protocol-v1-AddRecipient";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_REMOVE_RECIPIENT: &str = "strike-protocol-v1-RemoveRecipient";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_BLOCK_RECIPIENT: &str = "strike-protocol-v1-BlockRecipient";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_UNBLOCK_RECIPIENT: &str = "strike-protocol-v1-UnblockRecipient";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ROTATE: &str = "strike-protocol-v1-rotate";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ROTATE_ADMIN_SIGNERS: &str = "strike-protocol-v1-RotateAdminSigners";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_LOG_VERBOSITY: &str = "strike-protocol-v1-SetLogVerbosity";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_RECIPIENT_ALLOWLIST: &str =
+        "strike-protocol-v1-SetRecipientAllowlist";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_FEE: &str = "strike-protocol-v1-SetFee";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_RELAYER_REBATE: &str = "strike-protocol-v1-SetRelayerRebate";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_MAX_TICKET_LIFETIME: &str =
+        "strike-protocol-v1-SetMaxTicketLifetime";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_AUTH_SCHEME: &str = "strike-protocol-v1-SetAuthScheme";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_HOOK_PROGRAM: &str = "strike-protocol-v1-SetHookProgram";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_DEPOSIT_REGISTRY: &str = "strike-protocol-v1-SetDepositRegistry";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_IDENTITY_NETWORK: &str = "strike-protocol-v1-SetIdentityNetwork";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_STRICT_SIGNATURES: &str =
+        "strike-protocol-v1-SetStrictSignatures";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_PROPOSE_SIGNER_SET: &str = "strike-protocol-v1-ProposeSignerSet";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_TREASURY_RESERVE: &str = "strike-protocol-v1-SetTreasuryReserve";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_REPLAY_MODE: &str = "strike-protocol-v1-SetReplayMode";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_GUARDIAN: &str = "strike-protocol-v1-SetGuardian";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_DELEGATE_STAKE: &str = "strike-protocol-v1-DelegateStake";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_DEACTIVATE_STAKE: &str = "strike-protocol-v1-DeactivateStake";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_WITHDRAW_STAKE: &str = "strike-protocol-v1-WithdrawStake";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_LIQUID_STAKE: &str = "strike-protocol-v1-LiquidStake";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_LIQUID_UNSTAKE: &str = "strike-protocol-v1-LiquidUnstake";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_REBALANCE: &str = "strike-protocol-v1-Rebalance";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SWAP: &str = "strike-protocol-v1-Swap";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_COLLECT_FEES: &str = "strike-protocol-v1-CollectFees";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SWEEP_DUST: &str = "strike-protocol-v1-SweepDust";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CLOSE_VAULT_TOKEN_ACCOUNT: &str =
+        "strike-protocol-v1-CloseVaultTokenAccount";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SWEEP_ASSET: &str = "strike-protocol-v1-SweepAsset";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_EXPIRY_GRACE: &str = "strike-protocol-v1-SetExpiryGrace";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_NONCE_TREE: &str = "strike-protocol-v1-SetNonceTree";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_EXTRA_NETWORK_IDS: &str =
+        "strike-protocol-v1-SetExtraNetworkIds";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_UPGRADE_PROGRAM: &str = "strike-protocol-v1-UpgradeProgram";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_ALLOWANCE: &str = "strike-protocol-v1-SetAllowance";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_TREASURY: &str = "strike-protocol-v1-SetTreasury";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_WORMHOLE_EMITTER: &str = "strike-protocol-v1-SetWormholeEmitter";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CREATE_SUB_VAULT: &str = "strike-protocol-v1-CreateSubVault";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_RECLAIM_SUB_VAULT: &str = "strike-protocol-v1-ReclaimSubVault";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CREATE_RECEIPT_MINT: &str = "strike-protocol-v1-CreateReceiptMint";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADMIN_DEPOSIT: &str = "strike-protocol-v1-AdminDeposit";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_TREASURY_WITHDRAWAL: &str = "strike-protocol-v1-TreasuryWithdrawal";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_WITHDRAWAL: &str = "strike-protocol-v1-Withdrawal";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL: &str = "strike-protocol-v1-AdminWithdrawal";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_BULK_WITHDRAWAL: &str = "strike-protocol-v1-BulkWithdrawal";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_AUTHORIZED_CPI: &str = "strike-protocol-v1-AuthorizedCpi";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADD_STRATEGY: &str = "strike-protocol-v1-AddStrategy";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_REMOVE_STRATEGY: &str = "strike-protocol-v1-RemoveStrategy";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_ADD_CRANKER: &str = "strike-protocol-v1-AddCranker";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_REMOVE_CRANKER: &str = "strike-protocol-v1-RemoveCranker";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_SET_CRANK_INCENTIVE: &str = "strike-protocol-v1-SetCrankIncentive";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_EXECUTE_STRATEGY: &str = "strike-protocol-v1-ExecuteStrategy";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_EXECUTE: &str = "strike-protocol-v1-Execute";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CREATE_STREAM: &str = "strike-protocol-v1-CreateStream";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CREATE_SCHEDULE: &str = "strike-protocol-v1-CreateSchedule";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CLAIM_ROOT: &str = "strike-protocol-v1-ClaimRoot";
+    #[constant]
+    pub const DOMAIN_SEPARATOR_CREDIT_DEPOSIT: &str = "strike-protocol-v1-CreditDeposit";
 }
 
 pub mod errors {
@@ -84,5 +367,227 @@ pub mod errors {
         DuplicateRequestId,
         #[msg("Too many tickets")]
         TooManyTickets,
+        #[msg("Domain suffix too long")]
+        DomainSuffixTooLong,
+        #[msg("Token account is not the expected associated token account")]
+        InvalidTokenAccount,
+        #[msg("Asset not supported by this instruction")]
+        UnsupportedAsset,
+        #[msg("Mint account not found")]
+        MintAccountNotFound,
+        #[msg("Invalid CPI target program")]
+        InvalidCpiProgram,
+        #[msg("CPI accounts do not match the ticket")]
+        CpiAccountMismatch,
+        #[msg("CPI instruction data does not match the ticket")]
+        CpiDataMismatch,
+        #[msg("NFT amount must be exactly 1")]
+        InvalidNftAmount,
+        #[msg("NFT mint must have 0 decimals and a supply of 1")]
+        InvalidNftMint,
+        #[msg("Memo exceeds maximum length")]
+        MemoTooLong,
+        #[msg("SPL Memo program not found in remaining accounts")]
+        MemoProgramNotFound,
+        #[msg("Deposit record has no room for another asset")]
+        DepositRecordFull,
+        #[msg("Allowance account not provided")]
+        AllowanceAccountNotFound,
+        #[msg("Withdrawal exceeds the recipient allowance")]
+        AllowanceExceeded,
+        #[msg("Receipt mint decimals must match the asset")]
+        InvalidReceiptDecimals,
+        #[msg("Merkle proof does not match the claim root")]
+        InvalidClaimProof,
+        #[msg("Claim window has closed")]
+        ClaimWindowClosed,
+        #[msg("Fee exceeds the maximum")]
+        InvalidFee,
+        #[msg("Recipient allowlist account not provided")]
+        RecipientAllowlistNotFound,
+        #[msg("Recipient is not on the allowlist")]
+        RecipientNotAllowlisted,
+        #[msg("Recipient allowlist is full")]
+        RecipientAllowlistFull,
+        #[msg("Blocklist account not provided")]
+        BlocklistNotFound,
+        #[msg("Blocklist is full")]
+        BlocklistFull,
+        #[msg("Withdrawal amount is below the asset's minimum")]
+        BelowMinWithdrawal,
+        #[msg("Signer weight must be > 0")]
+        InvalidSignerWeight,
+        #[msg("Signer expiry must be in the future")]
+        InvalidSignerExpiry,
+        #[msg("Signature s value is not in the lower half of the curve order")]
+        HighSSignature,
+        #[msg("Unsupported ticket version")]
+        UnsupportedTicketVersion,
+        #[msg("Signer approved the ticket more than once")]
+        DuplicateSignature,
+        #[msg("Signatures must be sorted by ascending signer address")]
+        UnsortedSignatures,
+        #[msg("Too many whitelisted assets")]
+        TooManyAssets,
+        #[msg("Treasury name is empty or too long")]
+        InvalidTreasuryName,
+        #[msg("Named treasury account not provided")]
+        NamedTreasuryNotFound,
+        #[msg("Named treasuries only hold SOL")]
+        TreasuryAssetNotSupported,
+        #[msg("Only withdraw can draw from a named treasury")]
+        NamedTreasuryNotSupported,
+        #[msg("Vault is not a sub-vault of this vault")]
+        NotASubVault,
+        #[msg("Signer is not the vault's guardian")]
+        NotGuardian,
+        #[msg("Stake account for this validator is already in use")]
+        StakeAccountInUse,
+        #[msg("Liquid staking returned less than the ticket's minimum")]
+        LiquidStakeSlippage,
+        #[msg("Program is not an approved strategy")]
+        StrategyNotApproved,
+        #[msg("Strategy allowlist is full")]
+        StrategyAllowlistFull,
+        #[msg("Invalid stream schedule")]
+        InvalidStreamSchedule,
+        #[msg("Nothing has vested since the last claim")]
+        NothingToClaim,
+        #[msg("Invalid schedule")]
+        InvalidSchedule,
+        #[msg("Schedule payment is not due yet")]
+        ScheduleNotDue,
+        #[msg("Schedule has no payments left")]
+        ScheduleCompleted,
+        #[msg("Crank registry account not provided")]
+        CrankRegistryNotFound,
+        #[msg("Cranker is not on the crank registry")]
+        CrankerNotRegistered,
+        #[msg("Crank registry is full")]
+        CrankRegistryFull,
+        #[msg("Ticket is not valid yet")]
+        TicketNotYetValid,
+        #[msg("Ticket expiry is further out than the vault's maximum ticket lifetime")]
+        TicketLifetimeTooLong,
+        #[msg("Request id was already consumed by a different ticket")]
+        RequestIdConflict,
+        #[msg("Ticket token accounts don't match its withdrawals")]
+        InvalidTokenAccounts,
+        #[msg("Hook program can't be this program")]
+        InvalidHookProgram,
+        #[msg("Hook program or hook authority not found")]
+        HookProgramNotFound,
+        #[msg("Not a VAA posted by the core bridge from the vault's Wormhole emitter")]
+        InvalidVaa,
+        #[msg("VAA payload is not a withdrawal ticket")]
+        InvalidVaaPayload,
+        #[msg("Attestor can't be this program or the default pubkey")]
+        InvalidAttestor,
+        #[msg("Ticket has no attestation from the vault's attestor")]
+        MissingAttestation,
+        #[msg("USD cap account not provided")]
+        UsdCapAccountNotFound,
+        #[msg("No oracle price for a USD-capped asset")]
+        UsdPriceNotFound,
+        #[msg("Oracle price is not fully verified or not positive")]
+        InvalidPrice,
+        #[msg("Oracle price is older than the USD cap allows")]
+        StalePrice,
+        #[msg("Oracle price confidence interval is too wide")]
+        PriceConfidenceTooWide,
+        #[msg("Withdrawal exceeds a USD cap")]
+        UsdCapExceeded,
+        #[msg("Swap must trade one whitelisted token for another")]
+        InvalidSwap,
+        #[msg("Swap spent more than the ticket allows")]
+        SwapInputExceeded,
+        #[msg("Swap returned less than the ticket's minimum out")]
+        SwapSlippage,
+        #[msg("Swap route writes to another vault token account")]
+        SwapAccountNotAllowed,
+        #[msg("Rebalance must move funds between two different vault accounts")]
+        InvalidRebalance,
+        #[msg("Rebalance account is not a treasury or token account of the vault")]
+        InvalidRebalanceAccount,
+        #[msg("Vault token account still holds tokens")]
+        TokenAccountNotEmpty,
+        #[msg("Asset must be removed from the whitelist first")]
+        AssetStillWhitelisted,
+        #[msg("Deposits of this asset are paused")]
+        AssetPaused,
+        #[msg("Deposit is below the asset's minimum")]
+        BelowMinDeposit,
+        #[msg("Deposit is above the asset's deposit cap")]
+        DepositCapExceeded,
+        #[msg("Withdrawal line is above the asset's withdrawal limit")]
+        AboveWithdrawalLimit,
+        #[msg("Deposit would take the vault's holdings of the asset above its TVL cap")]
+        TvlCapExceeded,
+        #[msg("Deposit would take the depositor's total above the asset's per-user cap")]
+        UserCapExceeded,
+        #[msg("Deposit registry and schema must both be set, and not the vault program")]
+        InvalidDepositRegistry,
+        #[msg("Depositor has no attestation from the vault's registry")]
+        DepositorNotAttested,
+        #[msg("Depositor's attestation is expired or revoked")]
+        DepositorAttestationInvalid,
+        #[msg("Recipient has no identity pass from the vault's gatekeeper network")]
+        IdentityPassRequired,
+        #[msg("Recipient's identity pass is expired, frozen or revoked")]
+        IdentityPassInvalid,
+        #[msg("Signature could not be recovered, the log names its index")]
+        SignatureRecoveryFailed,
+        #[msg("Signature recovered to an address that is not an active signer")]
+        UnauthorizedSigner,
+        #[msg("Account indexes must give one entry per ticket and per withdrawal line")]
+        InvalidAccountIndexes,
+        #[msg("Vault's signer set account not found in remaining accounts")]
+        SignerSetAccountNotFound,
+        #[msg("No signer set proposal is waiting to be uploaded or activated")]
+        NoPendingSignerSet,
+        #[msg("Uploaded signers don't hash to the proposed signer set")]
+        SignerSetHashMismatch,
+        #[msg("Not supported while the vault uses a signer set account")]
+        SignerSetNotSupported,
+        #[msg("Only the payer of the signer set proposal may upload or activate it")]
+        NotSignerSetUploader,
+        #[msg("Request id is at or below the vault's request id watermark")]
+        RequestIdBelowWatermark,
+        #[msg("A nonce account is required unless the vault uses watermark replay")]
+        NonceAccountRequired,
+        #[msg("The request id watermark can't move backwards")]
+        WatermarkDecreased,
+        #[msg("Nonce is vetoed or its ticket hasn't been expired for NONCE_GC_DELAY yet")]
+        NonceNotCollectible,
+        #[msg("balance_bps can't exceed 10_000")]
+        InvalidBalanceBps,
+        #[msg("Only withdraw and validate_ticket accept tickets with balance_bps")]
+        BalanceBpsNotSupported,
+        #[msg("Metadata exceeds MAX_METADATA_LEN bytes")]
+        MetadataTooLong,
+        #[msg("Metadata contains control characters")]
+        InvalidMetadata,
+        #[msg("More signatures submitted than the quorum needs plus EXTRA_SIGNATURES")]
+        TooManySignatures,
+        #[msg("Too many extra network ids")]
+        TooManyNetworkIds,
+        #[msg("Extra network ids must be distinct and differ from the vault's network_id")]
+        DuplicateNetworkId,
+        #[msg("network_id isn't the one derived from the genesis hash")]
+        GenesisNetworkMismatch,
+        #[msg("Expiry grace is longer than the maximum")]
+        ExpiryGraceTooLong,
+        #[msg("Nonce tree depth or request id range is invalid")]
+        InvalidNonceTree,
+        #[msg("Nonce tree accounts or proof not found in remaining accounts")]
+        NonceTreeNotFound,
+        #[msg("Ticket has more withdrawal lines than a receipt holds")]
+        TooManyReceiptWithdrawals,
+        #[msg("Withdrawals can't be vetoed while the vault uses watermark replay")]
+        VetoUnderWatermarkReplay,
+        #[msg("Fee mint isn't a whitelisted SPL token")]
+        InvalidFeeMint,
+        #[msg("Only the vault's authority can migrate it")]
+        NotVaultAuthority,
     }
 }
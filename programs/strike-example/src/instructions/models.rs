@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use solana_program::keccak;
+use solana_program::{ed25519_program, keccak, secp256k1_program};
+
+use super::constant::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Hash, Debug, InitSpace)]
 pub enum Asset {
     Sol,
     SplToken { mint: Pubkey },
+    Nft { mint: Pubkey }, // decimals 0, always moved one at a time
 }
 
 impl Asset {
@@ -17,11 +20,170 @@ impl Asset {
                 data.push(1u8);
                 data.extend_from_slice(&mint.to_bytes());
             }
+            Asset::Nft { mint } => {
+                data.push(2u8);
+                data.extend_from_slice(&mint.to_bytes());
+            }
+        }
+    }
+
+    /// Key used in PDA seeds for the asset: the mint, or the default pubkey for SOL.
+    pub fn seed_key(&self) -> Pubkey {
+        match self {
+            Asset::Sol => Pubkey::default(),
+            Asset::SplToken { mint } | Asset::Nft { mint } => *mint,
         }
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+/// Limits an add or update asset ticket sets on a whitelisted asset. Amounts are in the asset's
+/// base units, 0 for no limit.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct AssetSettings {
+    pub min_deposit: u64,      // smallest deposit line
+    pub deposit_cap: u64,      // largest deposit line
+    pub withdrawal_limit: u64, // largest withdrawal ticket line
+    pub tvl_cap: u64,          // largest vault balance a deposit may leave
+    pub user_cap: u64,         // largest running total of one depositor's deposits
+    pub paused: bool,          // deposits rejected while set, withdrawals still allowed
+}
+
+impl AssetSettings {
+    fn add_to_data(&self, data: &mut Vec<u8>) {
+        data.extend_from_slice(&self.min_deposit.to_le_bytes());
+        data.extend_from_slice(&self.deposit_cap.to_le_bytes());
+        data.extend_from_slice(&self.withdrawal_limit.to_le_bytes());
+        data.extend_from_slice(&self.tvl_cap.to_le_bytes());
+        data.extend_from_slice(&self.user_cap.to_le_bytes());
+        data.push(self.paused as u8);
+    }
+}
+
+/// A whitelisted asset and its limits.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct AssetConfig {
+    pub asset: Asset,
+    pub decimals: u8, // read from the mint when the asset is added
+    pub min_deposit: u64,
+    pub deposit_cap: u64,
+    pub withdrawal_limit: u64,
+    pub tvl_cap: u64,
+    pub user_cap: u64,
+    pub paused: bool,
+}
+
+impl AssetConfig {
+    pub fn new(asset: Asset, decimals: u8, settings: &AssetSettings) -> Self {
+        let mut config = AssetConfig {
+            asset,
+            decimals,
+            min_deposit: 0,
+            deposit_cap: 0,
+            withdrawal_limit: 0,
+            tvl_cap: 0,
+            user_cap: 0,
+            paused: false,
+        };
+        config.apply(settings);
+        config
+    }
+
+    pub fn apply(&mut self, settings: &AssetSettings) {
+        self.min_deposit = settings.min_deposit;
+        self.deposit_cap = settings.deposit_cap;
+        self.withdrawal_limit = settings.withdrawal_limit;
+        self.tvl_cap = settings.tvl_cap;
+        self.user_cap = settings.user_cap;
+        self.paused = settings.paused;
+    }
+}
+
+/// How much a vault logs per instruction. `Compact` replaces the per-line logs with one summary
+/// so large bulk runs don't get their logs truncated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LogVerbosity {
+    Itemized,
+    Compact,
+}
+
+/// What approves a vault's tickets. Handlers name the signer set and quorum a ticket needs;
+/// the scheme decides which approvals count toward it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AuthScheme {
+    /// The signer set, with secp256k1 signatures recovered from the instruction data or
+    /// verified by the secp256k1 precompile. Ed25519 precompile approvals count too, for signers
+    /// keyed by `ed25519_signer_address`.
+    Secp256k1,
+    /// The signer set, with Ed25519 precompile approvals only.
+    Ed25519,
+    /// An external program, approving a ticket by creating its
+    /// `["attestation", vault, ticket_hash]` PDA. The signer sets and thresholds don't apply.
+    Attestor { program: Pubkey },
+    /// The signer set, with secp256r1 (P-256) precompile approvals only, for signers keyed by
+    /// `secp256r1_signer_address`, e.g. HSMs and passkeys.
+    Secp256r1,
+}
+
+impl AuthScheme {
+    fn add_to_data(&self, data: &mut Vec<u8>) {
+        match &self {
+            AuthScheme::Secp256k1 => {
+                data.push(0u8);
+            }
+            AuthScheme::Ed25519 => {
+                data.push(1u8);
+            }
+            AuthScheme::Attestor { program } => {
+                data.push(2u8);
+                data.extend_from_slice(&program.to_bytes());
+            }
+            AuthScheme::Secp256r1 => {
+                data.push(3u8);
+            }
+        }
+    }
+
+    /// Whether approvals from the precompile `program_id` count under this scheme.
+    pub fn accepts_precompile(&self, program_id: &Pubkey) -> bool {
+        match self {
+            AuthScheme::Secp256k1 => {
+                *program_id == ed25519_program::ID || *program_id == secp256k1_program::ID
+            }
+            AuthScheme::Ed25519 => *program_id == ed25519_program::ID,
+            AuthScheme::Secp256r1 => *program_id == SECP256R1_PROGRAM_ID,
+            AuthScheme::Attestor { .. } => false,
+        }
+    }
+}
+
+/// Where a USD-capped asset's price comes from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PriceOracle {
+    /// A Pyth pull oracle feed, read from any fully verified `PriceUpdateV2` account for it.
+    Pyth { feed_id: [u8; 32] },
+    /// A Chainlink OCR2 feed, read from its transmissions account in the Chainlink store.
+    /// Chainlink reports no confidence interval, so `max_conf_bps` doesn't apply.
+    Chainlink { feed: Pubkey },
+}
+
+impl PriceOracle {
+    fn add_to_data(&self, data: &mut Vec<u8>) {
+        match &self {
+            PriceOracle::Pyth { feed_id } => {
+                data.push(0u8);
+                data.extend_from_slice(feed_id);
+            }
+            PriceOracle::Chainlink { feed } => {
+                data.push(1u8);
+                data.extend_from_slice(&feed.to_bytes());
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
 pub struct AssetAmount {
     pub asset: Asset,
     pub amount: u64,
@@ -41,240 +203,2516 @@ pub struct SignerWithSignature {
     pub recovery_id: u8,     // v component (0, 1, 27, or 28)
 }
 
+/// A signature an Ed25519 or secp256k1 precompile verified in the current transaction: the
+/// signer with signer-set `address` approved the 32-byte `message` hash.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PrecompileApproval {
+    pub address: [u8; 20],
+    pub message: [u8; 32],
+}
+
+/// An authorized signer and its voting weight. Thresholds are sums of weights. Ethereum signers
+/// use their address; Ed25519 and secp256r1 signers use `ed25519_signer_address` or
+/// `secp256r1_signer_address` of their pubkey.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct WeightedSigner {
+    pub address: [u8; 20],
+    pub weight: u8,
+    pub valid_until: Option<i64>, // signatures are ignored after this unix timestamp
+}
+
+impl WeightedSigner {
+    pub fn is_active(&self, now: i64) -> bool {
+        !matches!(self.valid_until, Some(valid_until) if now > valid_until)
+    }
+
+    fn add_to_data(&self, data: &mut Vec<u8>) {
+        data.push(55u8);
+        data.extend_from_slice(&self.address);
+        data.push(self.weight);
+        match self.valid_until {
+            Some(valid_until) => {
+                data.push(1u8);
+                data.extend_from_slice(&valid_until.to_le_bytes());
+            }
+            None => data.push(0u8),
+        }
+        data.push(56u8);
+    }
+}
+
+/// keccak256 over the ticket encoding of each signer in order, which a `ProposeSignerSetTicket`
+/// commits to in place of the signers themselves.
+pub fn signer_set_hash(signers: &[WeightedSigner]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(signers.len() * 32);
+    for signer in signers.iter() {
+        signer.add_to_data(&mut data);
+    }
+    keccak::hash(&data).to_bytes()
+}
+
 pub trait Ticket {
     fn separator(&self) -> &'static str;
-    fn hash(&self) -> [u8; 32];
+    /// The bytes validators sign the keccak256 of.
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8>;
+
+    fn hash(&self, domain_suffix: &str) -> [u8; 32] {
+        keccak::hash(&self.preimage(domain_suffix)).to_bytes()
+    }
+}
+
+/// Start the hash preimage with the domain separator, the vault's domain suffix, the
+/// program id and the ticket version, so signed tickets don't verify against another
+/// deployment or under another version of the hashing scheme. The suffix is length-prefixed:
+/// some separators extend others (`Execute` and `ExecuteStrategy`), so a bare suffix could
+/// complete one vault's separator into another's.
+fn domain_data(separator: &str, version: u8, domain_suffix: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(separator.as_bytes());
+    data.extend_from_slice(&(domain_suffix.len() as u32).to_le_bytes());
+    data.extend_from_slice(domain_suffix.as_bytes());
+    data.extend_from_slice(crate::ID.as_ref());
+    data.push(version);
+    data
+}
+
+/// Append the ticket's activation time behind a presence byte, so the fields after it can't be
+/// read as one.
+fn add_not_before(data: &mut Vec<u8>, not_before: Option<i64>) {
+    match not_before {
+        Some(not_before) => {
+            data.push(1u8);
+            data.extend_from_slice(&not_before.to_le_bytes());
+        }
+        None => data.push(0u8),
+    }
+}
+
+/// Append the ticket's cross-chain provenance behind a presence byte, so a byte field before it
+/// can't stand in for it.
+fn add_source_ref(data: &mut Vec<u8>, source_ref: &Option<[u8; 32]>) {
+    match source_ref {
+        Some(source_ref) => {
+            data.push(1u8);
+            data.extend_from_slice(source_ref);
+        }
+        None => data.push(0u8),
+    }
+}
+
+/// Append an optional byte field behind a presence byte, then length-prefixed.
+fn add_optional_bytes(data: &mut Vec<u8>, bytes: Option<&[u8]>) {
+    match bytes {
+        Some(bytes) => {
+            data.push(1u8);
+            data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            data.extend_from_slice(bytes);
+        }
+        None => data.push(0u8),
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AddAssetTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
     pub asset: Asset,
+    pub settings: AssetSettings,
     pub expiry: i64,
     pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
 impl Ticket for AddAssetTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-AddAsset"
+        DOMAIN_SEPARATOR_ADD_ASSET
     }
 
-    fn hash(&self) -> [u8; 32] {
-        hash_asset_ticket(
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        asset_settings_ticket_preimage(
             self.separator(),
+            self.version,
+            domain_suffix,
             self.request_id,
             &self.vault,
             &self.asset,
+            &self.settings,
             self.expiry,
             self.network_id,
+            self.not_before,
         )
     }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RemoveAssetTicket {
+pub struct UpdateAssetTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
     pub asset: Asset,
+    pub settings: AssetSettings,
     pub expiry: i64,
     pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for RemoveAssetTicket {
+impl Ticket for UpdateAssetTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-RemoveAsset"
+        DOMAIN_SEPARATOR_UPDATE_ASSET
     }
 
-    fn hash(&self) -> [u8; 32] {
-        hash_asset_ticket(
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        asset_settings_ticket_preimage(
             self.separator(),
+            self.version,
+            domain_suffix,
             self.request_id,
             &self.vault,
             &self.asset,
+            &self.settings,
             self.expiry,
             self.network_id,
+            self.not_before,
         )
     }
 }
 
-fn hash_asset_ticket(
-    separator: &str,
-    request_id: u64,
-    vault: &Pubkey,
-    asset: &Asset,
-    expiry: i64,
-    network_id: u64,
-) -> [u8; 32] {
-    let mut data = Vec::new();
-    data.extend_from_slice(separator.as_bytes());
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveAssetTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub asset: Asset,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
 
-    // Ticket fields
-    data.extend_from_slice(&request_id.to_le_bytes());
-    data.extend_from_slice(&vault.to_bytes());
-    data.extend_from_slice(&expiry.to_le_bytes());
-    data.extend_from_slice(&network_id.to_le_bytes());
-    asset.add_to_data(&mut data);
+impl Ticket for RemoveAssetTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_REMOVE_ASSET
+    }
 
-    let hash_result = keccak::hash(&data);
-    hash_result.to_bytes()
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        asset_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.asset,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RotateValidatorTicket {
+pub struct SetMinWithdrawalTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
-    pub signers: Vec<[u8; 20]>,
-    pub m_threshold: u8,
-    pub admin_threshold: u8,
+    pub asset: Asset,
+    pub min_amount: u64, // 0 removes the minimum
     pub expiry: i64,
     pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for RotateValidatorTicket {
+impl Ticket for SetMinWithdrawalTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-rotate"
+        DOMAIN_SEPARATOR_SET_MIN_WITHDRAWAL
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut data = Vec::new();
-        data.extend_from_slice(self.separator().as_bytes());
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
 
         // Ticket fields
         data.extend_from_slice(&self.request_id.to_le_bytes());
         data.extend_from_slice(&self.vault.to_bytes());
-        for signer in self.signers.iter() {
-            data.push(55u8);
-            data.extend_from_slice(signer);
-            data.push(56u8);
-        }
-        data.extend_from_slice(&self.m_threshold.to_le_bytes());
-        data.extend_from_slice(&self.admin_threshold.to_le_bytes());
+        self.asset.add_to_data(&mut data);
+        data.extend_from_slice(&self.min_amount.to_le_bytes());
         data.extend_from_slice(&self.expiry.to_le_bytes());
         data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
 
-        let hash_result = keccak::hash(&data);
-        hash_result.to_bytes()
+        data
     }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AdminDepositTicket {
+pub struct SetUsdCapTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
-    pub deposits: Vec<AssetAmount>,
-    pub expiry: i64,     // Unix timestamp
-    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub asset: Asset,
+    pub oracle: PriceOracle,
+    pub max_usd: u64, // micro-dollars per withdrawal line, 0 to only price the asset
+    pub max_age: u64, // seconds a price may be old
+    pub max_conf_bps: u16, // confidence interval as a share of the price
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for AdminDepositTicket {
+impl Ticket for SetUsdCapTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-AdminDeposit"
+        DOMAIN_SEPARATOR_SET_USD_CAP
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut data = Vec::new();
-        data.extend_from_slice(self.separator().as_bytes());
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
 
         // Ticket fields
         data.extend_from_slice(&self.request_id.to_le_bytes());
         data.extend_from_slice(&self.vault.to_bytes());
-        for asset_amount in self.deposits.iter() {
-            asset_amount.add_to_data(&mut data);
-        }
+        self.asset.add_to_data(&mut data);
+        self.oracle.add_to_data(&mut data);
+        data.extend_from_slice(&self.max_usd.to_le_bytes());
+        data.extend_from_slice(&self.max_age.to_le_bytes());
+        data.extend_from_slice(&self.max_conf_bps.to_le_bytes());
         data.extend_from_slice(&self.expiry.to_le_bytes());
         data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
 
-        let hash_result = keccak::hash(&data);
-        hash_result.to_bytes()
+        data
     }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct WithdrawalTicket {
+pub struct SetMaxTicketUsdTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
-    pub recipient: Pubkey,
-    pub withdrawals: Vec<AssetAmount>,
-    pub expiry: i64,     // Unix timestamp
-    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub max_ticket_usd: u64, // micro-dollars per withdrawal ticket, 0 for none
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for WithdrawalTicket {
+impl Ticket for SetMaxTicketUsdTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-Withdrawal"
+        DOMAIN_SEPARATOR_SET_MAX_TICKET_USD
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut data = Vec::new();
-        data.extend_from_slice(self.separator().as_bytes());
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
 
         // Ticket fields
         data.extend_from_slice(&self.request_id.to_le_bytes());
         data.extend_from_slice(&self.vault.to_bytes());
-        data.extend_from_slice(&self.recipient.to_bytes());
-        for asset_amount in self.withdrawals.iter() {
-            asset_amount.add_to_data(&mut data);
-        }
+        data.extend_from_slice(&self.max_ticket_usd.to_le_bytes());
         data.extend_from_slice(&self.expiry.to_le_bytes());
         data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
 
-        let hash_result = keccak::hash(&data);
-        hash_result.to_bytes()
+        data
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn asset_ticket_preimage(
+    separator: &str,
+    version: u8,
+    domain_suffix: &str,
+    request_id: u64,
+    vault: &Pubkey,
+    asset: &Asset,
+    expiry: i64,
+    network_id: u64,
+    not_before: Option<i64>,
+) -> Vec<u8> {
+    let mut data = domain_data(separator, version, domain_suffix);
+
+    // Ticket fields
+    data.extend_from_slice(&request_id.to_le_bytes());
+    data.extend_from_slice(&vault.to_bytes());
+    data.extend_from_slice(&expiry.to_le_bytes());
+    data.extend_from_slice(&network_id.to_le_bytes());
+    asset.add_to_data(&mut data);
+    add_not_before(&mut data, not_before);
+
+    data
+}
+
+#[allow(clippy::too_many_arguments)]
+fn asset_settings_ticket_preimage(
+    separator: &str,
+    version: u8,
+    domain_suffix: &str,
+    request_id: u64,
+    vault: &Pubkey,
+    asset: &Asset,
+    settings: &AssetSettings,
+    expiry: i64,
+    network_id: u64,
+    not_before: Option<i64>,
+) -> Vec<u8> {
+    let mut data = domain_data(separator, version, domain_suffix);
+
+    // Ticket fields
+    data.extend_from_slice(&request_id.to_le_bytes());
+    data.extend_from_slice(&vault.to_bytes());
+    data.extend_from_slice(&expiry.to_le_bytes());
+    data.extend_from_slice(&network_id.to_le_bytes());
+    asset.add_to_data(&mut data);
+    settings.add_to_data(&mut data);
+    add_not_before(&mut data, not_before);
+
+    data
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AdminWithdrawalTicket {
+pub struct AddRecipientTicket {
+    pub version: u8,
     pub request_id: u64,
     pub vault: Pubkey,
     pub recipient: Pubkey,
-    pub withdrawals: Vec<AssetAmount>,
-    pub expiry: i64,     // Unix timestamp
-    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for AdminWithdrawalTicket {
+impl Ticket for AddRecipientTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-AdminWithdrawal"
+        DOMAIN_SEPARATOR_ADD_RECIPIENT
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut data = Vec::new();
-        data.extend_from_slice(self.separator().as_bytes());
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.recipient,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
 
-        // Ticket fields
-        data.extend_from_slice(&self.request_id.to_le_bytes());
-        data.extend_from_slice(&self.vault.to_bytes());
-        data.extend_from_slice(&self.recipient.to_bytes());
-        for asset_amount in self.withdrawals.iter() {
-            asset_amount.add_to_data(&mut data);
-        }
-        data.extend_from_slice(&self.expiry.to_le_bytes());
-        data.extend_from_slice(&self.network_id.to_le_bytes());
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveRecipientTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
 
-        let hash_result = keccak::hash(&data);
-        hash_result.to_bytes()
+impl Ticket for RemoveRecipientTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_REMOVE_RECIPIENT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.recipient,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
     }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BulkWithdrawalTicket {
-    pub tickets: Vec<WithdrawalTicket>,
+pub struct BlockRecipientTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
 }
 
-impl Ticket for BulkWithdrawalTicket {
+impl Ticket for BlockRecipientTicket {
     fn separator(&self) -> &'static str {
-        "strike-protocol-v1-BulkWithdrawal"
+        DOMAIN_SEPARATOR_BLOCK_RECIPIENT
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut data = Vec::new();
-        data.extend_from_slice(self.separator().as_bytes());
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.recipient,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
 
-        for ticket in self.tickets.iter() {
-            data.extend_from_slice(&ticket.hash());
-        }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UnblockRecipientTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for UnblockRecipientTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_UNBLOCK_RECIPIENT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.recipient,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn recipient_ticket_preimage(
+    separator: &str,
+    version: u8,
+    domain_suffix: &str,
+    request_id: u64,
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    expiry: i64,
+    network_id: u64,
+    not_before: Option<i64>,
+) -> Vec<u8> {
+    let mut data = domain_data(separator, version, domain_suffix);
+
+    // Ticket fields
+    data.extend_from_slice(&request_id.to_le_bytes());
+    data.extend_from_slice(&vault.to_bytes());
+    data.extend_from_slice(&recipient.to_bytes());
+    data.extend_from_slice(&expiry.to_le_bytes());
+    data.extend_from_slice(&network_id.to_le_bytes());
+    add_not_before(&mut data, not_before);
+
+    data
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RotateValidatorTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub signers: Vec<WeightedSigner>,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for RotateValidatorTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ROTATE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        for signer in self.signers.iter() {
+            signer.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.m_threshold.to_le_bytes());
+        data.extend_from_slice(&self.admin_threshold.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Replace the admin signer set, or share the operational set again with an empty list.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RotateAdminSignersTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub signers: Vec<WeightedSigner>,
+    pub admin_threshold: u8,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for RotateAdminSignersTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ROTATE_ADMIN_SIGNERS
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        for signer in self.signers.iter() {
+            signer.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.admin_threshold.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Move the operational signers into the signer set PDA, for sets past `MAX_SIGNERS`. Commits
+/// to the signers by `signer_set_hash`; they are uploaded after and checked on activation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposeSignerSetTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub signers_hash: [u8; 32],
+    pub signers_len: u8,
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for ProposeSignerSetTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_PROPOSE_SIGNER_SET
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.signers_hash);
+        data.push(self.signers_len);
+        data.push(self.m_threshold);
+        data.push(self.admin_threshold);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetLogVerbosityTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub log_verbosity: LogVerbosity,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetLogVerbosityTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_LOG_VERBOSITY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.push(self.log_verbosity as u8);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRecipientAllowlistTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetRecipientAllowlistTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_RECIPIENT_ALLOWLIST
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.push(self.enabled as u8);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetFeeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub fee_bps: u16,     // protocol fee on withdrawals, in basis points
+    pub fee_mint: Pubkey, // whitelisted SPL mint fees are paid in, default to pay in kind
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetFeeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_FEE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.fee_bps.to_le_bytes());
+        data.extend_from_slice(&self.fee_mint.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRelayerRebateTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub max_rebate: u64, // lamports, 0 turns the rebate off
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetRelayerRebateTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_RELAYER_REBATE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.max_rebate.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetMaxTicketLifetimeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub max_lifetime: u64, // seconds, 0 removes the cap
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetMaxTicketLifetimeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_MAX_TICKET_LIFETIME
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.max_lifetime.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetNonceTreeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub nonce_tree: Pubkey,
+    pub base_request_id: u64, // recorded at leaf 0, must be above the request id watermark
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetNonceTreeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_NONCE_TREE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.nonce_tree.to_bytes());
+        data.extend_from_slice(&self.base_request_id.to_le_bytes());
+        data.extend_from_slice(&self.max_depth.to_le_bytes());
+        data.extend_from_slice(&self.max_buffer_size.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetExpiryGraceTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub grace: u64, // seconds, 0 for a hard expiry
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetExpiryGraceTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_EXPIRY_GRACE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.grace.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetExtraNetworkIdsTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub extra_network_ids: Vec<u64>, // replaces the current list, empty to accept network_id only
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetExtraNetworkIdsTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_EXTRA_NETWORK_IDS
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        for network_id in self.extra_network_ids.iter() {
+            data.extend_from_slice(&network_id.to_le_bytes());
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetAuthSchemeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub auth_scheme: AuthScheme,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetAuthSchemeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_AUTH_SCHEME
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        self.auth_scheme.add_to_data(&mut data);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetHookProgramTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub hook_program: Pubkey, // default pubkey removes the hook
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetHookProgramTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_HOOK_PROGRAM
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.hook_program.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetDepositRegistryTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub registry: Pubkey, // default pubkey lets anyone deposit again
+    pub schema: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetDepositRegistryTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_DEPOSIT_REGISTRY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.registry.to_bytes());
+        data.extend_from_slice(&self.schema.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetIdentityNetworkTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub network: Pubkey, // default pubkey turns the identity check off
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetIdentityNetworkTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_IDENTITY_NETWORK
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.network.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetStrictSignaturesTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub enabled: bool,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetStrictSignaturesTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_STRICT_SIGNATURES
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.push(self.enabled as u8);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Set the treasury reserve, in lamports above rent exemption. 0 removes it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetTreasuryReserveTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub reserve: u64,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetTreasuryReserveTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_TREASURY_RESERVE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.reserve.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Switch withdrawal replay protection between nonce accounts and the request id watermark, and
+/// raise the watermark to `watermark` (it never moves backwards).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetReplayModeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub watermark_replay: bool,
+    pub watermark: u64,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetReplayModeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_REPLAY_MODE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.push(self.watermark_replay as u8);
+        data.extend_from_slice(&self.watermark.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetGuardianTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub guardian: Pubkey, // default pubkey removes the guardian
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetGuardianTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_GUARDIAN
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.guardian.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DelegateStakeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub vote_account: Pubkey, // validator; the stake account is `[b"stake", vault, vote_account]`
+    pub amount: u64,          // lamports moved from the treasury into the stake account
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for DelegateStakeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_DELEGATE_STAKE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.vote_account.to_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DeactivateStakeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub vote_account: Pubkey, // validator; the stake account is `[b"stake", vault, vote_account]`
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for DeactivateStakeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_DEACTIVATE_STAKE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.vote_account.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawStakeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub vote_account: Pubkey, // validator; the stake account is `[b"stake", vault, vote_account]`
+    pub amount: u64,          // lamports moved from the stake account back to the treasury
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for WithdrawStakeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_WITHDRAW_STAKE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.vote_account.to_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Converts treasury SOL into the pool token of an SPL stake pool. The pool token is whitelisted
+/// on first use.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LiquidStakeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    pub lamports: u64,
+    pub min_pool_tokens: u64, // slippage floor
+    pub expiry: i64,          // Unix timestamp
+    pub network_id: u64,      // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for LiquidStakeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_LIQUID_STAKE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.stake_pool.to_bytes());
+        data.extend_from_slice(&self.pool_mint.to_bytes());
+        data.extend_from_slice(&self.lamports.to_le_bytes());
+        data.extend_from_slice(&self.min_pool_tokens.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Redeems vault-held pool tokens of an SPL stake pool for SOL paid into the treasury.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LiquidUnstakeTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    pub pool_tokens: u64,
+    pub min_lamports: u64, // slippage floor
+    pub expiry: i64,       // Unix timestamp
+    pub network_id: u64,   // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for LiquidUnstakeTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_LIQUID_UNSTAKE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.stake_pool.to_bytes());
+        data.extend_from_slice(&self.pool_mint.to_bytes());
+        data.extend_from_slice(&self.pool_tokens.to_le_bytes());
+        data.extend_from_slice(&self.min_lamports.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RebalanceTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,
+    pub source: Pubkey,      // treasury PDA, named treasury or vault token account
+    pub destination: Pubkey, // same kinds as `source`
+    pub expiry: i64,         // Unix timestamp
+    pub network_id: u64,     // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for RebalanceTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_REBALANCE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        self.asset.add_to_data(&mut data);
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.source.to_bytes());
+        data.extend_from_slice(&self.destination.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,      // most the swap may spend
+    pub min_amount_out: u64, // slippage floor
+    pub expiry: i64,         // Unix timestamp
+    pub network_id: u64,     // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SwapTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SWAP
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.input_mint.to_bytes());
+        data.extend_from_slice(&self.output_mint.to_bytes());
+        data.extend_from_slice(&self.amount_in.to_le_bytes());
+        data.extend_from_slice(&self.min_amount_out.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CollectFeesTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub fees: Vec<AssetAmount>,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CollectFeesTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_COLLECT_FEES
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        for asset_amount in self.fees.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SweepDustTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub mints: Vec<Pubkey>, // vault token accounts to sweep, when below the asset's minimum
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SweepDustTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SWEEP_DUST
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.destination.to_bytes());
+        for mint in self.mints.iter() {
+            data.extend_from_slice(&mint.to_bytes());
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SweepAssetTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey, // owner of the token account paid, receives the rent when closing
+    pub close_account: bool, // close the emptied vault token account
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SweepAssetTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SWEEP_ASSET
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.mint.to_bytes());
+        data.extend_from_slice(&self.destination.to_bytes());
+        data.push(self.close_account as u8);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpgradeProgramTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub buffer: Pubkey, // owned by the vault's upgrade authority PDA
+    pub spill: Pubkey,  // receives the buffer's lamports
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for UpgradeProgramTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_UPGRADE_PROGRAM
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.buffer.to_bytes());
+        data.extend_from_slice(&self.spill.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CloseVaultTokenAccountTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub rent_recipient: Pubkey, // receives the account's rent
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CloseVaultTokenAccountTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CLOSE_VAULT_TOKEN_ACCOUNT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.mint.to_bytes());
+        data.extend_from_slice(&self.rent_recipient.to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetAllowanceTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub cap: u64, // lifetime withdrawal limit; what was already consumed is kept
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetAllowanceTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_ALLOWANCE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        self.asset.add_to_data(&mut data);
+        data.extend_from_slice(&self.cap.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetTreasuryTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub name: String,
+    pub m_threshold: u8, // threshold of withdrawals naming this treasury
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetTreasuryTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_TREASURY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&(self.name.len() as u32).to_le_bytes());
+        data.extend_from_slice(self.name.as_bytes());
+        data.push(self.m_threshold);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetWormholeEmitterTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub core_bridge: Pubkey,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetWormholeEmitterTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_WORMHOLE_EMITTER
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.core_bridge.to_bytes());
+        data.extend_from_slice(&self.emitter_chain.to_le_bytes());
+        data.extend_from_slice(&self.emitter_address);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// How many Wormhole guardian signatures the Pyth receiver checked for a price update.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Body of a Pyth receiver `PriceUpdateV2` account, after its Anchor discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+/// Body of a registry's attestation account, after its 8-byte discriminator, which isn't checked.
+/// The registry program owning the account is what vouches for it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DepositAttestation {
+    pub schema: Pubkey,
+    pub subject: Pubkey, // the attested depositor
+    pub expiry: i64,     // unix timestamp, 0 for none
+    pub revoked: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GatewayTokenState {
+    Active,
+    Frozen,
+    Revoked,
+}
+
+/// A Civic gateway token account, Borsh from the first byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GatewayToken {
+    pub features: u8,
+    pub parent_gateway_token: Option<Pubkey>,
+    pub owner_wallet: Pubkey,
+    pub owner_identity: Option<Pubkey>,
+    pub gatekeeper_network: Pubkey,
+    pub issuing_gatekeeper: Pubkey,
+    pub state: GatewayTokenState,
+    pub expire_time: Option<i64>,
+}
+
+/// Header of a Chainlink store `Transmissions` account, after its Anchor discriminator. The
+/// store lays it out packed, which matches Borsh, and pads it to `CHAINLINK_HEADER_SIZE`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ChainlinkFeedHeader {
+    pub version: u8,
+    pub state: u8,
+    pub owner: Pubkey,
+    pub proposed_owner: Pubkey,
+    pub writer: Pubkey,
+    pub description: [u8; 32],
+    pub decimals: u8,
+    pub flagging_threshold: u32,
+    pub latest_round_id: u32,
+    pub granularity: u8,
+    pub live_length: u32,
+    pub live_cursor: u32, // ring buffer slot the next round is written to
+    pub historical_cursor: u32,
+}
+
+/// One round in a Chainlink feed's live ring buffer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ChainlinkTransmission {
+    pub slot: u64,
+    pub timestamp: u32,
+    pub padding0: u32,
+    pub answer: i128,
+    pub padding1: u64,
+    pub padding2: u64,
+}
+
+/// Body of a Wormhole core bridge `PostedVAA` account, after its `b"vaa"` magic.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PostedVaaData {
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateSubVaultTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,          // parent vault
+    pub sub_vault_seed: String, // seed of the child vault PDA
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CreateSubVaultTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CREATE_SUB_VAULT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&(self.sub_vault_seed.len() as u32).to_le_bytes());
+        data.extend_from_slice(self.sub_vault_seed.as_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReclaimSubVaultTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey, // parent vault, receives the funds
+    pub sub_vault: Pubkey,
+    pub withdrawals: Vec<AssetAmount>,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for ReclaimSubVaultTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_RECLAIM_SUB_VAULT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.sub_vault.to_bytes());
+        for asset_amount in self.withdrawals.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateReceiptMintTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub asset: Asset,
+    pub decimals: u8, // must match the asset, so receipts are 1:1 with deposited units
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CreateReceiptMintTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CREATE_RECEIPT_MINT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        self.asset.add_to_data(&mut data);
+        data.push(self.decimals);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AdminDepositTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub deposits: Vec<AssetAmount>,
+    pub expiry: i64,     // Unix timestamp
+    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+    pub source_ref: Option<[u8; 32]>, // e.g. the origin-chain tx hash, emitted as SourceRefEvent
+    pub bypass_whitelist: bool, // accept assets not (yet) whitelisted, e.g. seeding before add_asset
+}
+
+impl Ticket for AdminDepositTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ADMIN_DEPOSIT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        for asset_amount in self.deposits.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+        add_source_ref(&mut data, &self.source_ref);
+        data.push(self.bypass_whitelist as u8);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawalTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub withdrawals: Vec<AssetAmount>,
+    pub expiry: i64,                         // Unix timestamp
+    pub network_id: u64,                     // Solana mainnet=101, devnet=102, testnet=103
+    pub memo: Option<String>, // Emitted via SPL Memo for the recipient's reconciliation
+    pub treasury: Option<String>, // Named treasury SOL is paid from, the default one when None
+    pub not_before: Option<i64>, // Unix timestamp the ticket becomes valid, immediately when None
+    pub token_accounts: Option<Vec<Pubkey>>, // Account each withdrawal line pays into, in order
+    pub source_ref: Option<[u8; 32]>, // Remote event authorizing it, e.g. the origin-chain tx hash
+    pub balance_bps: Option<u16>, // Share of the vault's balance per line, 10_000 for all of it
+}
+
+impl WithdrawalTicket {
+    /// The token account the signers committed withdrawal line `idx` to, if any.
+    pub fn token_account(&self, idx: usize) -> Option<&Pubkey> {
+        self.token_accounts
+            .as_ref()
+            .and_then(|accounts| accounts.get(idx))
+    }
+}
+
+impl Ticket for WithdrawalTicket {
+    fn separator(&self) -> &'static str {
+        // A separate domain keeps tickets naming a treasury apart from default-treasury ones.
+        if self.treasury.is_some() {
+            DOMAIN_SEPARATOR_TREASURY_WITHDRAWAL
+        } else {
+            DOMAIN_SEPARATOR_WITHDRAWAL
+        }
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        // Counted, so the lines can't run on into the variable-length fields below.
+        data.extend_from_slice(&(self.withdrawals.len() as u32).to_le_bytes());
+        for asset_amount in self.withdrawals.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+        add_optional_bytes(&mut data, self.treasury.as_ref().map(String::as_bytes));
+        add_optional_bytes(&mut data, self.memo.as_ref().map(String::as_bytes));
+
+        match &self.token_accounts {
+            Some(token_accounts) => {
+                data.push(1u8);
+                data.extend_from_slice(&(token_accounts.len() as u32).to_le_bytes());
+                for token_account in token_accounts.iter() {
+                    data.extend_from_slice(&token_account.to_bytes());
+                }
+            }
+            None => data.push(0u8),
+        }
+        add_source_ref(&mut data, &self.source_ref);
+
+        match self.balance_bps {
+            Some(balance_bps) => {
+                data.push(1u8);
+                data.extend_from_slice(&balance_bps.to_le_bytes());
+            }
+            None => data.push(0u8),
+        }
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AdminWithdrawalTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub withdrawals: Vec<AssetAmount>,
+    pub expiry: i64,     // Unix timestamp
+    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+    pub source_ref: Option<[u8; 32]>, // e.g. the origin-chain tx hash, emitted as SourceRefEvent
+}
+
+impl Ticket for AdminWithdrawalTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        for asset_amount in self.withdrawals.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+        add_source_ref(&mut data, &self.source_ref);
+
+        data
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BulkWithdrawalTicket {
+    pub version: u8,
+    pub tickets: Vec<WithdrawalTicket>,
+}
+
+impl Ticket for BulkWithdrawalTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_BULK_WITHDRAWAL
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        for ticket in self.tickets.iter() {
+            data.extend_from_slice(&ticket.hash(domain_suffix));
+        }
+
+        data
+    }
+}
+
+/// Where `bulk_withdraw` finds one ticket's accounts, as indexes into its remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct BulkAccountIndexes {
+    pub recipient: u8,
+    pub withdrawals: Vec<WithdrawalAccountIndexes>, // one per withdrawal line, unread for SOL lines
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawalAccountIndexes {
+    pub mint: u8,
+    pub vault_token: u8,
+    pub recipient_token: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CpiAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AuthorizedCpiTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<CpiAccountMeta>,
+    pub data_hash: [u8; 32], // keccak256 of the instruction data
+    pub expiry: i64,         // Unix timestamp
+    pub network_id: u64,     // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for AuthorizedCpiTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_AUTHORIZED_CPI
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.program_id.to_bytes());
+        for meta in self.accounts.iter() {
+            data.extend_from_slice(&meta.pubkey.to_bytes());
+            data.push(meta.is_signer as u8);
+            data.push(meta.is_writable as u8);
+        }
+        data.extend_from_slice(&self.data_hash);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Approves an external program for `execute_strategy`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AddStrategyTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub program_id: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for AddStrategyTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ADD_STRATEGY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.program_id,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveStrategyTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub program_id: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for RemoveStrategyTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_REMOVE_STRATEGY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.program_id,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AddCrankerTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for AddCrankerTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_ADD_CRANKER
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.cranker,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveCrankerTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub cranker: Pubkey,
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for RemoveCrankerTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_REMOVE_CRANKER
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        recipient_ticket_preimage(
+            self.separator(),
+            self.version,
+            domain_suffix,
+            self.request_id,
+            &self.vault,
+            &self.cranker,
+            self.expiry,
+            self.network_id,
+            self.not_before,
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetCrankIncentiveTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub incentive: u64, // lamports per successful crank, 0 turns it off
+    pub expiry: i64,
+    pub network_id: u64,
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for SetCrankIncentiveTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_SET_CRANK_INCENTIVE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.incentive.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// One CPI into an approved strategy program, committed to the same way as
+/// `AuthorizedCpiTicket` but signed by `admin_threshold` instead of every admin signer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StrategyTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<CpiAccountMeta>,
+    pub data_hash: [u8; 32], // keccak256 of the instruction data
+    pub expiry: i64,         // Unix timestamp
+    pub network_id: u64,     // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for StrategyTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_EXECUTE_STRATEGY
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.program_id.to_bytes());
+        for meta in self.accounts.iter() {
+            data.extend_from_slice(&meta.pubkey.to_bytes());
+            data.push(meta.is_signer as u8);
+            data.push(meta.is_writable as u8);
+        }
+        data.extend_from_slice(&self.data_hash);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// A serialized instruction the vault executes once signers weighing `m_threshold` approve it,
+/// like a Safe transaction. Shares the withdrawal request id space, so the guardian can veto it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExecuteTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<CpiAccountMeta>,
+    pub data: Vec<u8>,
+    pub expiry: i64,     // Unix timestamp
+    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for ExecuteTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_EXECUTE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.program_id.to_bytes());
+        for meta in self.accounts.iter() {
+            data.extend_from_slice(&meta.pubkey.to_bytes());
+            data.push(meta.is_signer as u8);
+            data.push(meta.is_writable as u8);
+        }
+        data.extend_from_slice(&keccak::hash(&self.data).to_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Locks `amount` of `asset` for `recipient`, released at `rate` per second from `start_time`.
+/// Nothing can be claimed before `cliff_time`; what accrued by then becomes claimable at once.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateStreamTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,
+    pub start_time: i64, // Unix timestamp
+    pub cliff_time: i64, // Unix timestamp, at or after start_time
+    pub rate: u64,       // base units released per second
+    pub expiry: i64,     // Unix timestamp
+    pub network_id: u64, // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CreateStreamTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CREATE_STREAM
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        self.asset.add_to_data(&mut data);
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.start_time.to_le_bytes());
+        data.extend_from_slice(&self.cliff_time.to_le_bytes());
+        data.extend_from_slice(&self.rate.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Pays `amount` of `asset` to `recipient` every `interval` seconds from `first_execution`, for
+/// `payments` periods. Each payment is cranked by `execute_schedule` without fresh signatures.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateScheduleTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub asset: Asset,
+    pub amount: u64,          // per payment, received by the recipient
+    pub interval: i64,        // seconds between payments
+    pub first_execution: i64, // Unix timestamp the first payment becomes due
+    pub payments: u32,        // number of payments
+    pub expiry: i64,          // Unix timestamp
+    pub network_id: u64,      // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CreateScheduleTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CREATE_SCHEDULE
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        self.asset.add_to_data(&mut data);
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.interval.to_le_bytes());
+        data.extend_from_slice(&self.first_execution.to_le_bytes());
+        data.extend_from_slice(&self.payments.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Publishes the Merkle root of a payout batch. Each recipient later redeems its own leaf with
+/// `claim`, so one multisig ticket covers any number of recipients.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimRootTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub root: [u8; 32],    // Merkle root over `ClaimLeaf::hash` values
+    pub claim_expiry: i64, // Unix timestamp after which leaves can no longer be claimed
+    pub expiry: i64,       // Unix timestamp
+    pub network_id: u64,   // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for ClaimRootTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CLAIM_ROOT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.root);
+        data.extend_from_slice(&self.claim_expiry.to_le_bytes());
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// Attests that `credits` arrived for `user` on another chain, identified by `source_ref`. The user
+/// redeems it with `claim_credited_deposit` to credit their `DepositRecord`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreditTicket {
+    pub version: u8,
+    pub request_id: u64,
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub credits: Vec<AssetAmount>,
+    pub source_ref: [u8; 32], // e.g. the origin-chain tx hash
+    pub expiry: i64,          // Unix timestamp
+    pub network_id: u64,      // Solana mainnet=101, devnet=102, testnet=103
+    pub not_before: Option<i64>,
+}
+
+impl Ticket for CreditTicket {
+    fn separator(&self) -> &'static str {
+        DOMAIN_SEPARATOR_CREDIT_DEPOSIT
+    }
+
+    fn preimage(&self, domain_suffix: &str) -> Vec<u8> {
+        let mut data = domain_data(self.separator(), self.version, domain_suffix);
+
+        // Ticket fields
+        data.extend_from_slice(&self.request_id.to_le_bytes());
+        data.extend_from_slice(&self.vault.to_bytes());
+        data.extend_from_slice(&self.user.to_bytes());
+        data.extend_from_slice(&(self.credits.len() as u32).to_le_bytes());
+        for asset_amount in self.credits.iter() {
+            asset_amount.add_to_data(&mut data);
+        }
+        data.extend_from_slice(&self.source_ref);
+        data.extend_from_slice(&self.expiry.to_le_bytes());
+        data.extend_from_slice(&self.network_id.to_le_bytes());
+        add_not_before(&mut data, self.not_before);
+
+        data
+    }
+}
+
+/// One payout of a claim root. The index keeps identical payouts to the same recipient distinct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimLeaf {
+    pub index: u64,
+    pub recipient: Pubkey,
+    pub asset_amount: AssetAmount,
+}
+
+impl ClaimLeaf {
+    /// `keccak(0x00 || index || recipient || asset_amount)`. The 0x00 prefix (0x01 for inner
+    /// nodes) keeps a leaf from being passed off as an inner node.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut data = vec![0u8];
+        data.extend_from_slice(&self.index.to_le_bytes());
+        data.extend_from_slice(&self.recipient.to_bytes());
+        self.asset_amount.add_to_data(&mut data);
 
         let hash_result = keccak::hash(&data);
         hash_result.to_bytes()
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+
+/// Record withdrawal request ids `ticket.base_request_id` to `base_request_id + 2^max_depth - 1`
+/// as leaves of `ticket.nonce_tree` instead of nonce accounts. The tree must be allocated, owned
+/// by the SPL account compression program and sized for `max_depth` and `max_buffer_size`; it's
+/// initialized here with the vault's `["nonce_tree_authority", vault]` PDA as its authority.
+/// The request id watermark is raised past the range, so only `withdraw` can consume its ids,
+/// and a later tree must start above it.
+pub fn set_nonce_tree(
+    ctx: Context<SetNonceTree>,
+    ticket: SetNonceTreeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(
+        (1..=MAX_NONCE_TREE_DEPTH).contains(&ticket.max_depth)
+            && ticket.base_request_id > ctx.accounts.vault.request_id_watermark,
+        ErrorCode::InvalidNonceTree
+    );
+    let last_request_id = ticket
+        .base_request_id
+        .checked_add((1u64 << ticket.max_depth) - 1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&ticket.max_depth.to_le_bytes());
+    data.extend_from_slice(&ticket.max_buffer_size.to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ticket.nonce_tree, false),
+                AccountMeta::new_readonly(ctx.accounts.nonce_tree_authority.key(), true),
+                AccountMeta::new_readonly(SPL_NOOP_PROGRAM_ID, false),
+            ],
+            data,
+        },
+        &[
+            ctx.accounts.nonce_tree.to_account_info(),
+            ctx.accounts.nonce_tree_authority.to_account_info(),
+            ctx.accounts.noop_program.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+        ],
+        &[&[
+            b"nonce_tree_authority",
+            vault_key.as_ref(),
+            &[ctx.bumps.nonce_tree_authority],
+        ]],
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.nonce_tree = ticket.nonce_tree;
+    vault.nonce_tree_base = ticket.base_request_id;
+    vault.nonce_tree_depth = ticket.max_depth as u8;
+    vault.request_id_watermark = last_request_id;
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::SetNonceTree {
+            nonce_tree: ticket.nonce_tree,
+            base_request_id: ticket.base_request_id,
+            max_depth: ticket.max_depth,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SetNonceTreeTicket)]
+pub struct SetNonceTree<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, address = ticket.nonce_tree)]
+    /// CHECK: Verified against the ticket, the compression program checks its owner and size
+    pub nonce_tree: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"nonce_tree_authority", vault.key().as_ref()], bump)]
+    /// CHECK: PDA verified by seeds, only signs compression program instructions
+    pub nonce_tree_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    /// CHECK: SPL account compression program
+    pub compression_program: UncheckedAccount<'info>,
+    #[account(address = SPL_NOOP_PROGRAM_ID)]
+    /// CHECK: SPL noop program
+    pub noop_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, TransferChecked};
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+use super::util::{check_nft_amount, find_mint, transfer_checked_with_hook, transfer_lamports};
+
+/// Move `amount` of an asset between two of the vault's own accounts: SOL between the treasury
+/// and named treasuries, tokens between token accounts the vault owns. Funds never leave the
+/// vault, so there is no recipient, fee or allowance involved.
+///
+/// Remaining accounts: the mint for token moves (and transfer hook accounts), then the
+/// instructions sysvar when signers approve through precompile instructions.
+pub fn rebalance<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+    ticket: RebalanceTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ticket.source != ticket.destination,
+        ErrorCode::InvalidRebalance
+    );
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let source = &ctx.accounts.source;
+    let destination = &ctx.accounts.destination;
+    match &ticket.asset {
+        Asset::Sol => {
+            let treasury = ctx.accounts.treasury.key();
+            check_vault_treasury(source, &vault.key(), &treasury)?;
+            check_vault_treasury(destination, &vault.key(), &treasury)?;
+
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(source.data_len());
+            let available = source.lamports().saturating_sub(rent_exempt_minimum);
+            require!(available >= ticket.amount, ErrorCode::InsufficientFunds);
+
+            transfer_lamports(source, destination, ticket.amount)?;
+        }
+        Asset::SplToken { mint } | Asset::Nft { mint } => {
+            let token_program = ctx.accounts.token_program.key();
+            let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+            check_nft_amount(&ticket.asset, ticket.amount, &mint_account)?;
+            let source_balance =
+                check_vault_token_account(source, &vault.key(), mint, &token_program)?;
+            check_vault_token_account(destination, &vault.key(), mint, &token_program)?;
+            require!(
+                source_balance >= ticket.amount,
+                ErrorCode::InsufficientFunds
+            );
+
+            let seeds = &[
+                vault.seed_namespace(),
+                vault.seed_parent(),
+                vault.vault_seed.as_bytes(),
+                &[vault.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = TransferChecked {
+                from: source.to_account_info(),
+                mint: mint_account.to_account_info(),
+                to: destination.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            transfer_checked_with_hook(
+                cpi_ctx,
+                ctx.remaining_accounts,
+                ticket.amount,
+                mint_account.decimals,
+            )?;
+        }
+    }
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::Rebalance {
+            asset: ticket.asset,
+            source: ticket.source,
+            destination: ticket.destination,
+            amount: ticket.amount,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// SOL can only move between the vault's treasury and its named treasuries.
+fn check_vault_treasury(account: &AccountInfo, vault: &Pubkey, treasury: &Pubkey) -> Result<()> {
+    if account.key == treasury {
+        return Ok(());
+    }
+    require!(
+        *account.owner == crate::ID,
+        ErrorCode::InvalidRebalanceAccount
+    );
+    let named = NamedTreasury::try_deserialize(&mut &account.try_borrow_data()?[..])
+        .map_err(|_| error!(ErrorCode::InvalidRebalanceAccount))?;
+    require!(named.vault == *vault, ErrorCode::InvalidRebalanceAccount);
+    Ok(())
+}
+
+/// Tokens can only move between token accounts of `mint` the vault owns. Returns the balance.
+fn check_vault_token_account(
+    account: &AccountInfo,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<u64> {
+    require!(
+        account.owner == token_program,
+        ErrorCode::InvalidRebalanceAccount
+    );
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])
+        .map_err(|_| error!(ErrorCode::InvalidRebalanceAccount))?;
+    require!(
+        token_account.owner == *vault && token_account.mint == *mint,
+        ErrorCode::InvalidRebalanceAccount
+    );
+    Ok(token_account.amount)
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: RebalanceTicket)]
+pub struct Rebalance<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.source)]
+    /// CHECK: A treasury or token account of the vault, checked in the handler
+    pub source: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.destination)]
+    /// CHECK: A treasury or token account of the vault, checked in the handler
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,308 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    authorize, check_nft_amount, check_recipient_allowed, consume_allowance,
+    find_associated_token_account, find_mint, gross_amount_for_net, is_recipient_blocked,
+    is_supported_ticket_version, is_ticket_active, pay_crank_incentive, transfer_checked_with_hook,
+    transfer_lamports, withdrawable_lamports, Quorum,
+};
+
+/// Approve a recurring payment once; `execute_schedule` then pays each period without new
+/// signatures. No funds move here, every payment is checked against the vault when it runs.
+pub fn create_schedule(
+    ctx: Context<CreateSchedule>,
+    ticket: CreateScheduleTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.next_sequence();
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+    require!(ticket.amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ticket.interval > 0 && ticket.payments > 0,
+        ErrorCode::InvalidSchedule
+    );
+
+    // Every payment is a withdrawal, so the schedule needs the same M of N.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    // Check nonce hasn't been used (replay protection)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.vault = vault.key();
+    schedule.request_id = ticket.request_id;
+    schedule.recipient = ticket.recipient;
+    schedule.asset = ticket.asset.clone();
+    schedule.amount = ticket.amount;
+    schedule.interval = ticket.interval;
+    schedule.next_execution = ticket.first_execution;
+    schedule.remaining = ticket.payments;
+    schedule.bump = ctx.bumps.schedule;
+
+    emit_cpi!(ScheduleCreatedEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        recipient: ticket.recipient,
+        asset: ticket.asset,
+        amount: ticket.amount,
+        interval: ticket.interval,
+        first_execution: ticket.first_execution,
+        payments: ticket.payments,
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Pay the schedule's next due period. Anyone can crank it, or only registered crankers once the
+/// vault has a crank registry; a crank pays one period, so missed periods are caught up by
+/// cranking again.
+///
+/// Remaining accounts: the recipient's allowance PDA (writable), the recipient allowlist,
+/// blocklist and crank registry PDAs when enabled and, for SPL schedules, the mint, the vault's and the recipient's
+/// associated token accounts.
+pub fn execute_schedule<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSchedule<'info>>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let vault = &ctx.accounts.vault;
+    let schedule = &mut ctx.accounts.schedule;
+    let clock = Clock::get()?;
+
+    require!(schedule.remaining > 0, ErrorCode::ScheduleCompleted);
+    require!(
+        clock.unix_timestamp >= schedule.next_execution,
+        ErrorCode::ScheduleNotDue
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &schedule.recipient)?;
+
+    let incentive = pay_crank_incentive(
+        ctx.remaining_accounts,
+        vault,
+        &ctx.accounts.treasury,
+        &ctx.accounts.cranker,
+    )?;
+    if incentive > 0 {
+        emit_cpi!(CrankIncentiveEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            cranker: ctx.accounts.cranker.key(),
+            amount: incentive,
+        });
+    }
+
+    schedule.remaining -= 1;
+    schedule.next_execution = schedule
+        .next_execution
+        .checked_add(schedule.interval)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // A blocked recipient's period is spent without paying out.
+    if is_recipient_blocked(ctx.remaining_accounts, vault, &schedule.recipient)? {
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: schedule.request_id,
+            recipient: schedule.recipient,
+        });
+        return Ok(());
+    }
+
+    let asset = &schedule.asset;
+    let amount = schedule.amount;
+    consume_allowance(
+        ctx.remaining_accounts,
+        &vault.key(),
+        &schedule.recipient,
+        asset,
+        amount,
+    )?;
+
+    let gross_amount = match asset {
+        Asset::Sol => {
+            let available = withdrawable_lamports(
+                &ctx.accounts.treasury.to_account_info(),
+                vault.treasury_reserve,
+            )?;
+
+            require!(available >= amount, ErrorCode::InsufficientFunds);
+
+            transfer_lamports(&ctx.accounts.treasury, &ctx.accounts.recipient, amount)?;
+            amount
+        }
+        Asset::SplToken { mint } | Asset::Nft { mint } => {
+            let token_program = ctx.accounts.token_program.key();
+            let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+            check_nft_amount(asset, amount, &mint_account)?;
+            let vault_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &vault.key(),
+                mint,
+                &token_program,
+            )?;
+            let recipient_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &schedule.recipient,
+                mint,
+                &token_program,
+            )?;
+
+            // Payment amounts are net to the recipient; the vault covers any transfer fee.
+            let gross_amount = gross_amount_for_net(&mint_account.to_account_info(), amount)?;
+
+            require!(
+                vault_token.amount >= gross_amount,
+                ErrorCode::InsufficientFunds
+            );
+
+            let seeds = &[
+                vault.seed_namespace(),
+                vault.seed_parent(),
+                vault.vault_seed.as_bytes(),
+                &[vault.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = TransferChecked {
+                from: vault_token.to_account_info(),
+                mint: mint_account.to_account_info(),
+                to: recipient_token.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer_checked_with_hook(
+                cpi_ctx,
+                ctx.remaining_accounts,
+                gross_amount,
+                mint_account.decimals,
+            )?;
+            gross_amount
+        }
+    };
+
+    emit_cpi!(ScheduleExecutedEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: schedule.request_id,
+        recipient: schedule.recipient,
+        asset: asset.clone(),
+        amount,
+        gross_amount,
+        remaining: schedule.remaining,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ticket: CreateScheduleTicket)]
+pub struct CreateSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Schedule::INIT_SPACE,
+        seeds = [b"schedule", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub schedule: Account<'info, Schedule>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"schedule", vault.key().as_ref(), &schedule.request_id.to_le_bytes()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, Schedule>,
+
+    /// CHECK: Recipient verified against the schedule
+    #[account(mut, address = schedule.recipient @ ErrorCode::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{check_signer_set_within, check_threshold};
+
+/// Propose moving the operational signers into the signer set PDA, for federations of up to
+/// `MAX_SIGNER_SET_LEN`. The ticket commits to the signers by `signer_set_hash`; the payer then
+/// uploads them with `upload_signers` and swaps them in with `activate_signer_set`. A new
+/// proposal discards a pending one.
+pub fn propose_signer_set(
+    ctx: Context<ProposeSignerSet>,
+    ticket: ProposeSignerSetTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    require!(
+        ticket.signers_len > 0 && ticket.signers_len <= MAX_SIGNER_SET_LEN,
+        ErrorCode::InvalidSignersCount
+    );
+    require!(
+        ticket.m_threshold > 0 && ticket.admin_threshold > 0,
+        ErrorCode::InvalidThreshold
+    );
+
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let mut signer_set = match ctx.accounts.signer_set.load_mut() {
+        Ok(signer_set) => signer_set,
+        Err(_) => {
+            let mut signer_set = ctx.accounts.signer_set.load_init()?;
+            signer_set.vault = ticket.vault;
+            signer_set.bump = ctx.bumps.signer_set;
+            signer_set
+        }
+    };
+    let staged = 1 - signer_set.active as usize;
+    signer_set.uploader = ctx.accounts.payer.key();
+    signer_set.lens[staged] = 0;
+    signer_set.pending_hash = ticket.signers_hash;
+    signer_set.pending_request_id = ticket.request_id;
+    signer_set.pending_len = ticket.signers_len;
+    signer_set.pending_m_threshold = ticket.m_threshold;
+    signer_set.pending_admin_threshold = ticket.admin_threshold;
+    signer_set.pending_valid_signers = valid_signers;
+
+    emit!(AdminOpEvent {
+        vault: ticket.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::ProposeSignerSet {
+            signers_hash: ticket.signers_hash,
+            signers_len: ticket.signers_len,
+            m_threshold: ticket.m_threshold,
+            admin_threshold: ticket.admin_threshold,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Append `signers` to the pending signer set, in the order the proposal hashed them. A set
+/// rarely fits one transaction, so upload it over as many as it takes.
+pub fn upload_signers(ctx: Context<UploadSigners>, signers: Vec<WeightedSigner>) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let mut signer_set = ctx.accounts.signer_set.load_mut()?;
+    require!(signer_set.pending_len > 0, ErrorCode::NoPendingSignerSet);
+    require!(
+        signer_set.uploader == ctx.accounts.uploader.key(),
+        ErrorCode::NotSignerSetUploader
+    );
+
+    let staged = 1 - signer_set.active as usize;
+    let start = signer_set.lens[staged] as usize;
+    let end = start + signers.len();
+    require!(
+        end <= signer_set.pending_len as usize,
+        ErrorCode::InvalidSignersCount
+    );
+    for (slot, signer) in signer_set.slots[staged][start..end].iter_mut().zip(signers) {
+        *slot = SignerSlot::from(signer);
+    }
+    signer_set.lens[staged] = end as u8;
+
+    Ok(())
+}
+
+/// Make the fully uploaded pending set the vault's operational signers, with the proposed
+/// thresholds. The set must hash to the proposal and pass the same checks as a rotation.
+pub fn activate_signer_set(ctx: Context<ActivateSignerSet>) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let mut signer_set = ctx.accounts.signer_set.load_mut()?;
+    require!(signer_set.pending_len > 0, ErrorCode::NoPendingSignerSet);
+    require!(
+        signer_set.uploader == ctx.accounts.uploader.key(),
+        ErrorCode::NotSignerSetUploader
+    );
+
+    let staged = 1 - signer_set.active as usize;
+    require!(
+        signer_set.lens[staged] == signer_set.pending_len,
+        ErrorCode::InvalidSignersCount
+    );
+    let signers = signer_set.staged_signers();
+    require!(
+        signer_set_hash(&signers) == signer_set.pending_hash,
+        ErrorCode::SignerSetHashMismatch
+    );
+    check_signer_set_within(
+        &signers,
+        signer_set.pending_m_threshold,
+        Clock::get()?.unix_timestamp,
+        MAX_SIGNER_SET_LEN as usize,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    // admin_threshold applies to whichever set approves admin ops after the activation.
+    if vault.admin_signers.is_empty() {
+        check_threshold(&signers, signer_set.pending_admin_threshold)?;
+    } else {
+        check_threshold(&vault.admin_signers, signer_set.pending_admin_threshold)?;
+    }
+
+    vault.m_threshold = signer_set.pending_m_threshold;
+    vault.admin_threshold = signer_set.pending_admin_threshold;
+    vault.signers.clear();
+    vault.has_signer_set = true;
+
+    signer_set.active = staged as u8;
+    signer_set.pending_len = 0;
+    signer_set.pending_hash = [0; 32];
+
+    emit!(RotateEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: signer_set.pending_request_id,
+        signers,
+        m_threshold: vault.m_threshold,
+        admin_threshold: vault.admin_threshold,
+        valid_signers: signer_set.pending_valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: ProposeSignerSetTicket)]
+pub struct ProposeSignerSet<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SignerSet>(),
+        seeds = [b"signer_set", vault.key().as_ref()],
+        bump
+    )]
+    pub signer_set: AccountLoader<'info, SignerSet>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UploadSigners<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"signer_set", vault.key().as_ref()],
+        bump = signer_set.load()?.bump
+    )]
+    pub signer_set: AccountLoader<'info, SignerSet>,
+
+    pub uploader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateSignerSet<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"signer_set", vault.key().as_ref()],
+        bump = signer_set.load()?.bump
+    )]
+    pub signer_set: AccountLoader<'info, SignerSet>,
+
+    pub uploader: Signer<'info>,
+}
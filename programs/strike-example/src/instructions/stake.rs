@@ -0,0 +1,403 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::stake::{
+    self, instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+use solana_program::{system_instruction, sysvar};
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+use super::util::transfer_lamports;
+
+/// Move `amount` treasury lamports into the vault's stake account for `vote_account` and
+/// delegate it. The vault PDA is both staker and withdrawer. The stake account must be unused;
+/// once fully withdrawn it can be delegated again.
+pub fn delegate_stake(
+    ctx: Context<DelegateStake>,
+    ticket: DelegateStakeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.amount > 0, ErrorCode::InvalidAmount);
+    let stake_account = &ctx.accounts.stake_account;
+    require!(stake_account.data_is_empty(), ErrorCode::StakeAccountInUse);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let treasury = &ctx.accounts.treasury;
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury.data_len());
+    let available = treasury.lamports().saturating_sub(rent_exempt_minimum);
+    require!(available >= ticket.amount, ErrorCode::InsufficientFunds);
+
+    transfer_lamports(treasury, stake_account, ticket.amount)?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let stake_seeds = &[
+        b"stake",
+        vault_key.as_ref(),
+        ticket.vote_account.as_ref(),
+        &[ctx.bumps.stake_account],
+    ];
+    let system_accounts = [
+        stake_account.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    ];
+    invoke_signed(
+        &system_instruction::allocate(&stake_account.key(), StakeStateV2::size_of() as u64),
+        &system_accounts,
+        &[&stake_seeds[..]],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(&stake_account.key(), &stake::program::ID),
+        &system_accounts,
+        &[&stake_seeds[..]],
+    )?;
+
+    invoke(
+        &stake_instruction::initialize(
+            &stake_account.key(),
+            &Authorized {
+                staker: vault_key,
+                withdrawer: vault_key,
+            },
+            &Lockup::default(),
+        ),
+        &[
+            stake_account.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+    )?;
+
+    let vault_seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::delegate_stake(&stake_account.key(), &vault_key, &ticket.vote_account),
+        &[
+            stake_account.to_account_info(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            vault.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&vault_seeds[..]],
+    )?;
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::DelegateStake {
+            vote_account: ticket.vote_account,
+            amount: ticket.amount,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Start cooling down the vault's stake with `vote_account`; it can be withdrawn once inactive.
+pub fn deactivate_stake(
+    ctx: Context<DeactivateStake>,
+    ticket: DeactivateStakeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::deactivate_stake(&ctx.accounts.stake_account.key(), &vault.key()),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            vault.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&vault_seeds[..]],
+    )?;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::DeactivateStake {
+            vote_account: ticket.vote_account,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Move `amount` lamports of the vault's stake with `vote_account` back to the treasury. Only
+/// inactive stake (and any excess over the delegation) can be withdrawn.
+pub fn withdraw_stake(
+    ctx: Context<WithdrawStake>,
+    ticket: WithdrawStakeTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.amount > 0, ErrorCode::InvalidAmount);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::withdraw(
+            &ctx.accounts.stake_account.key(),
+            &vault.key(),
+            &ctx.accounts.treasury.key(),
+            ticket.amount,
+            None,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            vault.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&vault_seeds[..]],
+    )?;
+
+    emit!(AdminOpEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::WithdrawStake {
+            vote_account: ticket.vote_account,
+            amount: ticket.amount,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: DelegateStakeTicket)]
+pub struct DelegateStake<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", vault.key().as_ref(), ticket.vote_account.as_ref()],
+        bump
+    )]
+    /// CHECK: Stake account PDA verified by seeds, created here
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(address = ticket.vote_account)]
+    /// CHECK: Checked as a vote account by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: Stake config, passed through to the stake program
+    pub stake_config: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: Stake history sysvar
+    pub stake_history: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = stake::program::ID)]
+    /// CHECK: Native stake program
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: DeactivateStakeTicket)]
+pub struct DeactivateStake<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", vault.key().as_ref(), ticket.vote_account.as_ref()],
+        bump
+    )]
+    /// CHECK: Stake account PDA verified by seeds
+    pub stake_account: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = stake::program::ID)]
+    /// CHECK: Native stake program
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: WithdrawStakeTicket)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", vault.key().as_ref(), ticket.vote_account.as_ref()],
+        bump
+    )]
+    /// CHECK: Stake account PDA verified by seeds
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: Stake history sysvar
+    pub stake_history: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = stake::program::ID)]
+    /// CHECK: Native stake program
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
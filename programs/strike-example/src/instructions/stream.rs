@@ -0,0 +1,359 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    authorize, check_nft_amount, check_recipient_allowed, find_associated_token_account, find_mint,
+    is_recipient_blocked, is_supported_ticket_version, is_ticket_active, pay_crank_incentive,
+    transfer_checked_with_hook, transfer_lamports, withdrawable_lamports, Quorum,
+};
+
+/// Lock `amount` of treasury SOL or vault tokens in a stream PDA for the ticket's recipient,
+/// released over time by `claim_stream`. Pays out like a withdrawal, so it needs the same M of N
+/// and uses a withdrawal request id the guardian can veto.
+///
+/// Remaining accounts: the recipient allowlist and blocklist PDAs when enabled and, for SPL
+/// streams, the mint, the vault's and the stream's associated token accounts (the stream's must
+/// already exist).
+pub fn create_stream<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateStream<'info>>,
+    ticket: CreateStreamTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.next_sequence();
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+    require!(
+        ticket.recipient == ctx.accounts.recipient.key(),
+        ErrorCode::InvalidRecipient
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    require!(ticket.amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ticket.rate > 0 && ticket.cliff_time >= ticket.start_time,
+        ErrorCode::InvalidStreamSchedule
+    );
+
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.vault = vault.key();
+    stream.request_id = ticket.request_id;
+    stream.recipient = ticket.recipient;
+    stream.asset = ticket.asset.clone();
+    stream.total = 0;
+    stream.claimed = 0;
+    stream.start_time = ticket.start_time;
+    stream.cliff_time = ticket.cliff_time;
+    stream.rate = ticket.rate;
+    stream.bump = ctx.bumps.stream;
+
+    // A blocked recipient's ticket is spent and its stream left empty.
+    if is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)? {
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            recipient: ticket.recipient,
+        });
+        return Ok(());
+    }
+
+    let total = match &ticket.asset {
+        Asset::Sol => {
+            let treasury = &ctx.accounts.treasury;
+            let available = withdrawable_lamports(treasury, vault.treasury_reserve)?;
+            require!(available >= ticket.amount, ErrorCode::InsufficientFunds);
+
+            transfer_lamports(treasury, &stream.to_account_info(), ticket.amount)?;
+            ticket.amount
+        }
+        Asset::SplToken { mint } | Asset::Nft { mint } => {
+            let token_program = ctx.accounts.token_program.key();
+            let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+            check_nft_amount(&ticket.asset, ticket.amount, &mint_account)?;
+            let vault_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &vault.key(),
+                mint,
+                &token_program,
+            )?;
+            let mut stream_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &stream.key(),
+                mint,
+                &token_program,
+            )?;
+            require!(
+                vault_token.amount >= ticket.amount,
+                ErrorCode::InsufficientFunds
+            );
+            let balance_before = stream_token.amount;
+
+            let seeds = &[
+                vault.seed_namespace(),
+                vault.seed_parent(),
+                vault.vault_seed.as_bytes(),
+                &[vault.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = TransferChecked {
+                from: vault_token.to_account_info(),
+                mint: mint_account.to_account_info(),
+                to: stream_token.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer_checked_with_hook(
+                cpi_ctx,
+                ctx.remaining_accounts,
+                ticket.amount,
+                mint_account.decimals,
+            )?;
+
+            // The stream releases what reached it; a transfer fee comes out of the total.
+            stream_token.reload()?;
+            stream_token
+                .amount
+                .checked_sub(balance_before)
+                .ok_or(ErrorCode::Overflow)?
+        }
+    };
+
+    stream.total = total;
+
+    emit_cpi!(StreamCreatedEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        recipient: ticket.recipient,
+        asset: ticket.asset,
+        total,
+        start_time: ticket.start_time,
+        cliff_time: ticket.cliff_time,
+        rate: ticket.rate,
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// Pay the stream's recipient everything vested since the last claim. Anyone can crank it, or
+/// only registered crankers once the vault has a crank registry.
+///
+/// Remaining accounts: the crank registry PDA when it exists and, for SPL streams, the mint, the
+/// stream's and the recipient's associated token accounts.
+pub fn claim_stream<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimStream<'info>>) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let stream = &mut ctx.accounts.stream;
+    let clock = Clock::get()?;
+
+    let amount = stream
+        .vested(clock.unix_timestamp)
+        .saturating_sub(stream.claimed);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    let incentive = pay_crank_incentive(
+        ctx.remaining_accounts,
+        &ctx.accounts.vault,
+        &ctx.accounts.treasury,
+        &ctx.accounts.cranker,
+    )?;
+    if incentive > 0 {
+        emit_cpi!(CrankIncentiveEvent {
+            vault: ctx.accounts.vault.key(),
+            sequence: ctx.accounts.vault.sequence,
+            cranker: ctx.accounts.cranker.key(),
+            amount: incentive,
+        });
+    }
+    stream.claimed = stream
+        .claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    match &stream.asset {
+        Asset::Sol => {
+            transfer_lamports(&stream.to_account_info(), &ctx.accounts.recipient, amount)?;
+        }
+        Asset::SplToken { mint } | Asset::Nft { mint } => {
+            let token_program = ctx.accounts.token_program.key();
+            let mint_account = find_mint(ctx.remaining_accounts, mint)?;
+            let stream_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &stream.key(),
+                mint,
+                &token_program,
+            )?;
+            let recipient_token = find_associated_token_account(
+                ctx.remaining_accounts,
+                &stream.recipient,
+                mint,
+                &token_program,
+            )?;
+
+            let vault_key = ctx.accounts.vault.key();
+            let request_id = stream.request_id.to_le_bytes();
+            let seeds = &[
+                b"stream",
+                vault_key.as_ref(),
+                request_id.as_ref(),
+                &[stream.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = TransferChecked {
+                from: stream_token.to_account_info(),
+                mint: mint_account.to_account_info(),
+                to: recipient_token.to_account_info(),
+                authority: stream.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer_checked_with_hook(
+                cpi_ctx,
+                ctx.remaining_accounts,
+                amount,
+                mint_account.decimals,
+            )?;
+        }
+    }
+
+    emit_cpi!(StreamClaimEvent {
+        vault: stream.vault,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: stream.request_id,
+        recipient: stream.recipient,
+        asset: stream.asset.clone(),
+        amount,
+        claimed: stream.claimed,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ticket: CreateStreamTicket)]
+pub struct CreateStream<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [b"stream", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: Recipient verified against ticket
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds, pays the crank incentive
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", vault.key().as_ref(), &stream.request_id.to_le_bytes()],
+        bump = stream.bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: Recipient verified against the stream
+    #[account(mut, address = stream.recipient @ ErrorCode::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
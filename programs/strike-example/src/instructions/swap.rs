@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::{AdminOp, AdminOpEvent};
+use super::models::*;
+
+/// Swap at most `amount_in` of the vault's `input_mint` tokens for at least `min_amount_out` of
+/// `output_mint` through Jupiter, with the vault PDA as the swapping user. The ticket commits to
+/// the mints and amounts; the route (`data` and the remaining accounts) is quoted at submission
+/// time, so the vault only trusts what its own token accounts show after the swap.
+///
+/// Remaining accounts: the Jupiter route accounts in order, then the instructions sysvar when
+/// signers approve through precompile instructions.
+pub fn swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+    ticket: SwapTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+    data: Vec<u8>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+    require!(ticket.amount_in > 0, ErrorCode::InvalidAmount);
+    require!(
+        ticket.input_mint != ticket.output_mint,
+        ErrorCode::InvalidSwap
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    for mint in [ticket.input_mint, ticket.output_mint] {
+        require!(
+            vault.is_whitelisted(&Asset::SplToken { mint }),
+            ErrorCode::AssetNotWhitelisted
+        );
+    }
+
+    let vault_key = vault.key();
+    let input_account = &ctx.accounts.vault_input_token_account;
+    let output_account = &ctx.accounts.vault_output_token_account;
+    check_route_accounts(
+        &vault_key,
+        &[input_account.key(), output_account.key()],
+        ctx.remaining_accounts,
+    )?;
+    let input_before = input_account.amount;
+    let output_before = output_account.amount;
+
+    // Mark nonce as used BEFORE the CPI (prevent reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+    vault.exit(&crate::ID)?;
+
+    let accounts = ctx
+        .remaining_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: acc.key(),
+            is_signer: acc.is_signer || acc.key() == vault_key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.jupiter_program.to_account_info());
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    invoke_signed(
+        &Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &account_infos,
+        &[&seeds[..]],
+    )?;
+    vault.reload()?;
+
+    let input_account = &mut ctx.accounts.vault_input_token_account;
+    let output_account = &mut ctx.accounts.vault_output_token_account;
+    input_account.reload()?;
+    output_account.reload()?;
+    let amount_in = input_before
+        .checked_sub(input_account.amount)
+        .ok_or(ErrorCode::InvalidSwap)?;
+    let amount_out = output_account
+        .amount
+        .checked_sub(output_before)
+        .ok_or(ErrorCode::InvalidSwap)?;
+    require!(amount_in <= ticket.amount_in, ErrorCode::SwapInputExceeded);
+    require!(amount_out >= ticket.min_amount_out, ErrorCode::SwapSlippage);
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::Swap {
+            input_mint: ticket.input_mint,
+            output_mint: ticket.output_mint,
+            amount_in,
+            amount_out,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+/// The vault signs the route, so it must not hand Jupiter any other writable token account the
+/// vault owns.
+fn check_route_accounts(
+    vault: &Pubkey,
+    allowed: &[Pubkey],
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    for account_info in accounts.iter().filter(|acc| acc.is_writable) {
+        if allowed.contains(account_info.key)
+            || !(*account_info.owner == anchor_spl::token::ID
+                || *account_info.owner == anchor_spl::token_2022::ID)
+        {
+            continue;
+        }
+        let data = account_info.try_borrow_data()?;
+        if let Ok(token_account) = TokenAccount::try_deserialize_unchecked(&mut &data[..]) {
+            require!(
+                token_account.owner != *vault,
+                ErrorCode::SwapAccountNotAllowed
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: SwapTicket)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(address = ticket.input_mint, mint::token_program = input_token_program)]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = ticket.output_mint, mint::token_program = output_token_program)]
+    pub output_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = input_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = input_token_program
+    )]
+    pub vault_input_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = output_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = output_token_program
+    )]
+    pub vault_output_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = JUPITER_PROGRAM_ID)]
+    /// CHECK: Jupiter aggregator program
+    pub jupiter_program: UncheckedAccount<'info>,
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,202 @@
+#![allow(deprecated)] // loader instruction builders moved to `solana-loader-v3-interface`
+
+use anchor_lang::prelude::*;
+use solana_program::bpf_loader_upgradeable;
+use solana_program::program::invoke_signed;
+
+use super::accounts::*;
+use super::admin::check_before_admin_update;
+use super::constant::*;
+use super::events::{AdminOp, AdminOpEvent, UpgradeAuthorityAcceptedEvent};
+use super::models::*;
+
+/// Hand this program's upgrade authority to the vault's upgrade authority PDA. Signed by the
+/// current authority, usually the deploy key; afterwards only `upgrade_program` tickets signed
+/// by the admin threshold can upgrade the program.
+pub fn accept_upgrade_authority(ctx: Context<AcceptUpgradeAuthority>) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+
+    let vault_key = ctx.accounts.vault.key();
+    let authority_seeds = &[
+        b"upgrade_authority",
+        vault_key.as_ref(),
+        &[ctx.bumps.upgrade_authority],
+    ];
+    invoke_signed(
+        &bpf_loader_upgradeable::set_upgrade_authority_checked(
+            &crate::ID,
+            &ctx.accounts.current_authority.key(),
+            &ctx.accounts.upgrade_authority.key(),
+        ),
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.current_authority.to_account_info(),
+            ctx.accounts.upgrade_authority.to_account_info(),
+            ctx.accounts.bpf_loader_program.to_account_info(),
+        ],
+        &[&authority_seeds[..]],
+    )?;
+
+    emit!(UpgradeAuthorityAcceptedEvent {
+        vault: vault_key,
+        sequence: ctx.accounts.vault.sequence,
+        previous_authority: ctx.accounts.current_authority.key(),
+        upgrade_authority: ctx.accounts.upgrade_authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Upgrade this program from `ticket.buffer`, refunding the buffer's lamports to `ticket.spill`.
+/// The buffer's authority must already be the vault's upgrade authority PDA, which also freezes
+/// its contents; signers should verify the buffer only after that handover.
+pub fn upgrade_program(
+    ctx: Context<UpgradeProgram>,
+    ticket: UpgradeProgramTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let valid_signers = check_before_admin_update(
+        &ctx.accounts.vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &ticket.vault,
+        ticket.expiry,
+        ticket.not_before,
+        ticket.network_id,
+        ticket.version,
+    )?;
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(
+        ticket.hash(&ctx.accounts.vault.domain_suffix),
+        ticket.expiry,
+    )?;
+    ctx.accounts
+        .vault
+        .record_processed(ADMIN_NONCE_SEED, ticket.request_id)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let authority_seeds = &[
+        b"upgrade_authority",
+        vault_key.as_ref(),
+        &[ctx.bumps.upgrade_authority],
+    ];
+    invoke_signed(
+        &bpf_loader_upgradeable::upgrade(
+            &crate::ID,
+            &ticket.buffer,
+            &ctx.accounts.upgrade_authority.key(),
+            &ticket.spill,
+        ),
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.program.to_account_info(),
+            ctx.accounts.buffer.to_account_info(),
+            ctx.accounts.spill.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.upgrade_authority.to_account_info(),
+            ctx.accounts.bpf_loader_program.to_account_info(),
+        ],
+        &[&authority_seeds[..]],
+    )?;
+
+    emit!(AdminOpEvent {
+        vault: vault_key,
+        sequence: ctx.accounts.vault.sequence,
+        request_id: ticket.request_id,
+        op: AdminOp::UpgradeProgram {
+            buffer: ticket.buffer,
+            spill: ticket.spill,
+        },
+        valid_signers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptUpgradeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_program.key()
+    )]
+    /// CHECK: Program data PDA verified by seeds, the loader checks the current authority
+    pub program_data: UncheckedAccount<'info>,
+
+    pub current_authority: Signer<'info>,
+
+    #[account(seeds = [b"upgrade_authority", vault.key().as_ref()], bump)]
+    /// CHECK: PDA verified by seeds, only signs loader instructions
+    pub upgrade_authority: UncheckedAccount<'info>,
+
+    #[account(address = bpf_loader_upgradeable::ID)]
+    /// CHECK: Upgradeable BPF loader
+    pub bpf_loader_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: UpgradeProgramTicket)]
+pub struct UpgradeProgram<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, address = crate::ID)]
+    /// CHECK: This program
+    pub program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_program.key()
+    )]
+    /// CHECK: Program data PDA verified by seeds
+    pub program_data: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.buffer)]
+    /// CHECK: Buffer verified against the ticket, its authority is checked by the loader
+    pub buffer: UncheckedAccount<'info>,
+
+    #[account(mut, address = ticket.spill)]
+    /// CHECK: Receives the buffer's lamports, verified against the ticket
+    pub spill: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"upgrade_authority", vault.key().as_ref()], bump)]
+    /// CHECK: PDA verified by seeds, only signs loader instructions
+    pub upgrade_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [ADMIN_NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(address = bpf_loader_upgradeable::ID)]
+    /// CHECK: Upgradeable BPF loader
+    pub bpf_loader_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,10 +1,40 @@
 use std::collections::HashSet;
 
 use anchor_lang::prelude::*;
-use solana_program::keccak;
+use anchor_spl::associated_token::{
+    self, get_associated_token_address_with_program_id, AssociatedToken, Create,
+};
+use anchor_spl::memo::{self, BuildMemo};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
 use solana_program::secp256k1_recover::secp256k1_recover;
+use solana_program::sysvar;
+use solana_program::{ed25519_program, keccak, secp256k1_program};
 
+use super::accounts::{
+    Allowance, Blocklist, CrankRegistry, NamedTreasury, NonceAccount, RecipientAllowlist,
+    SignerSet, UsdCap, Vault, WormholeEmitter,
+};
+use super::constant::{
+    BPS_DENOMINATOR, CHAINLINK_HEADER_SIZE, CHAINLINK_STORE_PROGRAM_ID,
+    CHAINLINK_TRANSMISSIONS_DISCRIMINATOR, CHAINLINK_TRANSMISSION_SIZE, CIVIC_GATEWAY_PROGRAM_ID,
+    EXTRA_SIGNATURES, HOOK_IX_DISCRIMINATOR, MAX_MEMO_LEN, MAX_METADATA_LEN, MAX_SIGNERS,
+    MIN_TICKET_VERSION, PRICE_UPDATE_V2_DISCRIMINATOR, PYTH_RECEIVER_PROGRAM_ID, RELAYER_TX_FEE,
+    REPLACE_LEAF_DISCRIMINATOR, SECP256K1_HALF_ORDER, SECP256R1_PROGRAM_ID, SOL_DECIMALS,
+    SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID, TICKET_VERSION, TREASURY_SEED,
+    USD_DECIMALS,
+};
 use super::errors::ErrorCode;
+use super::events::{BatchKind, BatchSummaryEvent, HookPayload};
 use super::models::*;
 use super::models::{SignerWithSignature, Ticket};
 
@@ -16,26 +46,1833 @@ pub fn check_duplicate_assets(list: &[AssetAmount]) -> Result<()> {
     Ok(())
 }
 
+/// NFTs are whole, single-supply tokens and move one at a time.
+pub fn check_nft_amount(asset: &Asset, amount: u64, mint: &InterfaceAccount<Mint>) -> Result<()> {
+    if let Asset::Nft { .. } = asset {
+        require!(amount == 1, ErrorCode::InvalidNftAmount);
+        require!(
+            mint.decimals == 0 && mint.supply == 1,
+            ErrorCode::InvalidNftMint
+        );
+    }
+    Ok(())
+}
+
+/// Find the canonical associated token account of `owner` for `mint` in `accounts`.
+pub fn find_associated_token_account<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let expected = get_associated_token_address_with_program_id(owner, mint, token_program);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected)
+        .ok_or(ErrorCode::TokenAccountNotFound)?;
+
+    token_account_of(account_info, owner, mint)
+}
+
+/// Find the token account withdrawal line `idx` of `ticket` pays into: the one the signers
+/// committed it to, or else the recipient's canonical associated token account.
+pub fn find_recipient_token_account<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    ticket: &WithdrawalTicket,
+    idx: usize,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let expected = recipient_token_address(ticket, idx, mint, token_program);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected)
+        .ok_or(ErrorCode::TokenAccountNotFound)?;
+
+    token_account_of(account_info, &ticket.recipient, mint)
+}
+
+/// Address of the token account withdrawal line `idx` of `ticket` pays into.
+pub fn recipient_token_address(
+    ticket: &WithdrawalTicket,
+    idx: usize,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    match ticket.token_account(idx) {
+        Some(token_account) => *token_account,
+        None => {
+            get_associated_token_address_with_program_id(&ticket.recipient, mint, token_program)
+        }
+    }
+}
+
+/// `account_info` as a token account of `owner` for `mint`.
+pub fn token_account_of<'info>(
+    account_info: &'info AccountInfo<'info>,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let token_acc = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+    require!(
+        token_acc.mint == *mint && token_acc.owner == *owner,
+        ErrorCode::InvalidTokenAccount
+    );
+
+    Ok(token_acc)
+}
+
+/// The account at `index` in `accounts`, which must be `expected`. Failing with `missing`
+/// either way, an index lookup behaves like a search for `expected` that came up empty.
+pub fn account_at<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    index: u8,
+    expected: &Pubkey,
+    missing: ErrorCode,
+) -> Result<&'info AccountInfo<'info>> {
+    let account_info = accounts
+        .get(index as usize)
+        .filter(|acc| acc.key() == *expected)
+        .ok_or(missing)?;
+    Ok(account_info)
+}
+
+/// Find the mint account for `mint` in `accounts`.
+pub fn find_mint<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    mint: &Pubkey,
+) -> Result<InterfaceAccount<'info, Mint>> {
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == *mint)
+        .ok_or(ErrorCode::MintAccountNotFound)?;
+
+    InterfaceAccount::<Mint>::try_from(account_info)
+}
+
+/// Decimals of `asset`: SOL's, the mint's for SPL tokens (looked up in `accounts`), 0 for NFTs.
+pub fn asset_decimals<'info>(accounts: &'info [AccountInfo<'info>], asset: &Asset) -> Result<u8> {
+    Ok(match asset {
+        Asset::Sol => SOL_DECIMALS,
+        Asset::SplToken { mint } => find_mint(accounts, mint)?.decimals,
+        Asset::Nft { .. } => 0,
+    })
+}
+
+/// Create the vault's associated token account for `mint` unless it already exists. The mint
+/// and the account are looked up in `accounts`.
+pub fn create_vault_ata<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &AccountInfo<'info>,
+    mint: &Pubkey,
+    payer: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let mint = find_mint(accounts, mint)?;
+    let expected =
+        get_associated_token_address_with_program_id(vault.key, &mint.key(), &token_program.key());
+    let vault_token = accounts
+        .iter()
+        .find(|acc| acc.key() == expected)
+        .ok_or(ErrorCode::TokenAccountNotFound)?;
+
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        Create {
+            payer: payer.clone(),
+            associated_token: vault_token.clone(),
+            authority: vault.clone(),
+            mint: mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
+
+/// Find the vault's named treasury `name` in `accounts`. A treasury `set_treasury` never
+/// created is not found.
+pub fn find_named_treasury<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Pubkey,
+    name: &str,
+) -> Result<Account<'info, NamedTreasury>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[TREASURY_SEED, vault.as_ref(), name.as_bytes()],
+        &crate::ID,
+    );
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected && *acc.owner == crate::ID)
+        .ok_or(ErrorCode::NamedTreasuryNotFound)?;
+
+    Account::<NamedTreasury>::try_from(account_info)
+}
+
+/// Active signers of the vault's signer set PDA, which must be passed in `accounts`.
+pub fn load_signer_set(accounts: &[AccountInfo], vault: &Pubkey) -> Result<Vec<WeightedSigner>> {
+    let (expected, _) = Pubkey::find_program_address(&[b"signer_set", vault.as_ref()], &crate::ID);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected && *acc.owner == crate::ID)
+        .ok_or(ErrorCode::SignerSetAccountNotFound)?;
+
+    let data = account_info.try_borrow_data()?;
+    let end = SignerSet::DISCRIMINATOR.len() + std::mem::size_of::<SignerSet>();
+    require!(
+        data.len() >= end && data.starts_with(SignerSet::DISCRIMINATOR),
+        ErrorCode::SignerSetAccountNotFound
+    );
+    let signer_set: &SignerSet = bytemuck::from_bytes(&data[SignerSet::DISCRIMINATOR.len()..end]);
+    Ok(signer_set.active_signers())
+}
+
+/// Charge `amount` against the recipient's allowance for `asset`. The allowance PDA must always
+/// be passed in `accounts`; while the validators have never set it, the recipient is uncapped.
+pub fn consume_allowance<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    asset: &Asset,
+    amount: u64,
+) -> Result<()> {
+    if let Some((account_info, allowance)) =
+        check_allowance(accounts, vault, recipient, asset, amount)?
+    {
+        allowance.try_serialize(&mut &mut account_info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Check that the recipient's allowance for `asset` covers `amount` more, and return it with
+/// `amount` charged but not written back. `None` while the allowance was never set.
+pub fn check_allowance<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    asset: &Asset,
+    amount: u64,
+) -> Result<Option<(&'a AccountInfo<'info>, Allowance)>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[
+            b"allowance",
+            vault.as_ref(),
+            recipient.as_ref(),
+            asset.seed_key().as_ref(),
+        ],
+        &crate::ID,
+    );
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected)
+        .ok_or(ErrorCode::AllowanceAccountNotFound)?;
+    if *account_info.owner != crate::ID {
+        return Ok(None);
+    }
+
+    let mut allowance = Allowance::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    allowance.consumed = allowance
+        .consumed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        allowance.consumed <= allowance.cap,
+        ErrorCode::AllowanceExceeded
+    );
+    Ok(Some((account_info, allowance)))
+}
+
+/// While the vault is in allowlist mode, require `recipient` on the recipient allowlist PDA, which
+/// must then be passed in `accounts`.
+pub fn check_recipient_allowed<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Account<Vault>,
+    recipient: &Pubkey,
+) -> Result<()> {
+    if !vault.recipient_allowlist {
+        return Ok(());
+    }
+
+    let vault_key = vault.key();
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"recipient_allowlist", vault_key.as_ref()], &crate::ID);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected && *acc.owner == crate::ID)
+        .ok_or(ErrorCode::RecipientAllowlistNotFound)?;
+
+    let allowlist = RecipientAllowlist::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    require!(
+        allowlist.recipients.contains(recipient),
+        ErrorCode::RecipientNotAllowlisted
+    );
+    Ok(())
+}
+
+/// While the vault has an identity network, require a gateway token of `recipient` from that
+/// gatekeeper network in `accounts`, active and unexpired at `now`.
+pub fn check_identity_pass(
+    accounts: &[AccountInfo],
+    vault: &Vault,
+    recipient: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if vault.identity_network == Pubkey::default() {
+        return Ok(());
+    }
+
+    for account_info in accounts
+        .iter()
+        .filter(|acc| *acc.owner == CIVIC_GATEWAY_PROGRAM_ID)
+    {
+        let data = account_info.try_borrow_data()?;
+        let Ok(pass) = GatewayToken::deserialize(&mut &data[..]) else {
+            continue;
+        };
+        if pass.owner_wallet != *recipient || pass.gatekeeper_network != vault.identity_network {
+            continue;
+        }
+
+        require!(
+            pass.state == GatewayTokenState::Active
+                && pass.expire_time.is_none_or(|expiry| now < expiry),
+            ErrorCode::IdentityPassInvalid
+        );
+        return Ok(());
+    }
+
+    err!(ErrorCode::IdentityPassRequired)
+}
+
+/// Reject withdrawal lines below their asset's minimum, see `set_min_withdrawal`, or above its
+/// whitelist entry's withdrawal limit.
+pub fn check_withdrawal_limits(vault: &Vault, withdrawals: &[AssetAmount]) -> Result<()> {
+    for withdrawal in withdrawals {
+        require!(
+            withdrawal.amount >= vault.min_withdrawal(&withdrawal.asset),
+            ErrorCode::BelowMinWithdrawal
+        );
+        // A delisted asset keeps no limit.
+        if let Some(config) = vault.asset_config(&withdrawal.asset) {
+            require!(
+                config.withdrawal_limit == 0 || withdrawal.amount <= config.withdrawal_limit,
+                ErrorCode::AboveWithdrawalLimit
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Price each withdrawal line in USD and check it against its asset's `UsdCap`, and the lines'
+/// sum against the vault's `max_ticket_usd`. Once the vault has USD caps, every line's USD cap
+/// PDA must be passed in `accounts`, with the oracle accounts its price is read from. An asset
+/// without a USD cap is unpriced, which only a vault without a ticket cap accepts.
+pub fn check_usd_caps(
+    accounts: &[AccountInfo],
+    vault: &Account<Vault>,
+    withdrawals: &[AssetAmount],
+    now: i64,
+) -> Result<()> {
+    if !vault.has_usd_caps {
+        return Ok(());
+    }
+
+    let vault_key = vault.key();
+    let mut total: u64 = 0;
+    for withdrawal in withdrawals {
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                b"usd_cap",
+                vault_key.as_ref(),
+                withdrawal.asset.seed_key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        let account_info = accounts
+            .iter()
+            .find(|acc| acc.key() == expected)
+            .ok_or(ErrorCode::UsdCapAccountNotFound)?;
+        if *account_info.owner != crate::ID {
+            require!(vault.max_ticket_usd == 0, ErrorCode::UsdPriceNotFound);
+            continue;
+        }
+
+        let usd_cap = UsdCap::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+        let (price, exponent) = oracle_price(accounts, &usd_cap, now)?;
+        let value = usd_value(withdrawal.amount, price, exponent, usd_cap.decimals)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            usd_cap.max_usd == 0 || value <= usd_cap.max_usd,
+            ErrorCode::UsdCapExceeded
+        );
+        total = total.checked_add(value).ok_or(ErrorCode::Overflow)?;
+    }
+    require!(
+        vault.max_ticket_usd == 0 || total <= vault.max_ticket_usd,
+        ErrorCode::UsdCapExceeded
+    );
+    Ok(())
+}
+
+/// Price of one whole unit of `usd_cap.asset` in dollars, as (price, exponent), from its oracle.
+fn oracle_price(accounts: &[AccountInfo], usd_cap: &UsdCap, now: i64) -> Result<(i64, i32)> {
+    match &usd_cap.oracle {
+        PriceOracle::Pyth { feed_id } => pyth_price(
+            accounts,
+            feed_id,
+            usd_cap.max_age,
+            usd_cap.max_conf_bps,
+            now,
+        ),
+        PriceOracle::Chainlink { feed } => chainlink_price(accounts, feed, usd_cap.max_age, now),
+    }
+}
+
+/// Latest round of the Chainlink feed `feed`, passed in `accounts`. The round must be positive,
+/// fit an i64 and be at most `max_age` seconds old at `now`.
+pub fn chainlink_price(
+    accounts: &[AccountInfo],
+    feed: &Pubkey,
+    max_age: u64,
+    now: i64,
+) -> Result<(i64, i32)> {
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == *feed)
+        .ok_or(ErrorCode::UsdPriceNotFound)?;
+    require!(
+        *account_info.owner == CHAINLINK_STORE_PROGRAM_ID,
+        ErrorCode::InvalidPrice
+    );
+
+    let data = account_info.try_borrow_data()?;
+    let body = data
+        .strip_prefix(&CHAINLINK_TRANSMISSIONS_DISCRIMINATOR[..])
+        .ok_or(ErrorCode::InvalidPrice)?;
+    let header = ChainlinkFeedHeader::deserialize(&mut &body[..])
+        .map_err(|_| error!(ErrorCode::InvalidPrice))?;
+    require!(
+        header.live_length > 0 && header.latest_round_id > 0,
+        ErrorCode::UsdPriceNotFound
+    );
+
+    // The live cursor points past the latest round, wrapping around the ring buffer.
+    let latest = header
+        .live_cursor
+        .checked_sub(1)
+        .unwrap_or(header.live_length - 1) as usize;
+    let offset = CHAINLINK_HEADER_SIZE + latest * CHAINLINK_TRANSMISSION_SIZE;
+    let round = body
+        .get(offset..offset + CHAINLINK_TRANSMISSION_SIZE)
+        .and_then(|mut round| ChainlinkTransmission::deserialize(&mut round).ok())
+        .ok_or(ErrorCode::InvalidPrice)?;
+
+    let price = i64::try_from(round.answer).map_err(|_| error!(ErrorCode::InvalidPrice))?;
+    require!(price > 0, ErrorCode::InvalidPrice);
+    require!(
+        now.saturating_sub(round.timestamp as i64) <= i64::try_from(max_age).unwrap_or(i64::MAX),
+        ErrorCode::StalePrice
+    );
+    Ok((price, -(header.decimals as i32)))
+}
+
+/// Price of `feed_id` from a Pyth receiver `PriceUpdateV2` account in `accounts`. The update must
+/// be fully verified, positive, at most `max_age` seconds old at `now`, and its confidence
+/// interval within `max_conf_bps` of the price.
+pub fn pyth_price(
+    accounts: &[AccountInfo],
+    feed_id: &[u8; 32],
+    max_age: u64,
+    max_conf_bps: u16,
+    now: i64,
+) -> Result<(i64, i32)> {
+    for account_info in accounts
+        .iter()
+        .filter(|acc| *acc.owner == PYTH_RECEIVER_PROGRAM_ID)
+    {
+        let data = account_info.try_borrow_data()?;
+        let Some(mut body) = data.strip_prefix(&PRICE_UPDATE_V2_DISCRIMINATOR[..]) else {
+            continue;
+        };
+        let Ok(update) = PriceUpdateV2::deserialize(&mut body) else {
+            continue;
+        };
+        let message = update.price_message;
+        if message.feed_id != *feed_id {
+            continue;
+        }
+
+        require!(
+            update.verification_level == VerificationLevel::Full && message.price > 0,
+            ErrorCode::InvalidPrice
+        );
+        require!(
+            now.saturating_sub(message.publish_time) <= i64::try_from(max_age).unwrap_or(i64::MAX),
+            ErrorCode::StalePrice
+        );
+        require!(
+            message.conf as u128 * BPS_DENOMINATOR as u128
+                <= message.price as u128 * max_conf_bps as u128,
+            ErrorCode::PriceConfidenceTooWide
+        );
+        return Ok((message.price, message.exponent));
+    }
+    err!(ErrorCode::UsdPriceNotFound)
+}
+
+/// Value in micro-dollars of `amount` base units of an asset with `decimals`, at `price` x
+/// 10^`exponent` dollars per whole unit, rounded up. `None` if it doesn't fit a u64.
+pub fn usd_value(amount: u64, price: i64, exponent: i32, decimals: u8) -> Option<u64> {
+    let value = (amount as u128).checked_mul(u128::try_from(price).ok()?)?;
+    let scale = USD_DECIMALS as i32 + exponent - decimals as i32;
+    let value = if scale >= 0 {
+        value.checked_mul(10u128.checked_pow(scale as u32)?)?
+    } else {
+        match 10u128.checked_pow(scale.unsigned_abs()) {
+            Some(divisor) => value.div_ceil(divisor),
+            None => u128::from(value > 0),
+        }
+    };
+    u64::try_from(value).ok()
+}
+
+/// Base units of an asset with `decimals` worth `value` micro-dollars at `price` x 10^`exponent`
+/// dollars per whole unit, rounded down: the inverse of `usd_value`. `None` if it doesn't fit a
+/// u64.
+pub fn amount_for_usd(value: u64, price: i64, exponent: i32, decimals: u8) -> Option<u64> {
+    let price = u128::try_from(price).ok().filter(|price| *price > 0)?;
+    let scale = USD_DECIMALS as i32 + exponent - decimals as i32;
+    let amount = if scale >= 0 {
+        match 10u128
+            .checked_pow(scale as u32)
+            .and_then(|unit| price.checked_mul(unit))
+        {
+            Some(divisor) => value as u128 / divisor,
+            None => 0,
+        }
+    } else {
+        (value as u128).checked_mul(10u128.checked_pow(scale.unsigned_abs())?)? / price
+    };
+    u64::try_from(amount).ok()
+}
+
+/// Oracle price of `asset` through its USD cap PDA in `accounts`, as (price, exponent, decimals).
+fn usd_cap_price(
+    accounts: &[AccountInfo],
+    vault: &Pubkey,
+    asset: &Asset,
+    now: i64,
+) -> Result<(i64, i32, u8)> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"usd_cap", vault.as_ref(), asset.seed_key().as_ref()],
+        &crate::ID,
+    );
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected && *acc.owner == crate::ID)
+        .ok_or(ErrorCode::UsdPriceNotFound)?;
+    let usd_cap = UsdCap::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    let (price, exponent) = oracle_price(accounts, &usd_cap, now)?;
+    Ok((price, exponent, usd_cap.decimals))
+}
+
+/// Amount of the vault's fee mint worth a withdrawal line's protocol `fee`, in base units of
+/// `asset`: the fee's USD value at `asset`'s oracle price, converted at the fee mint's and
+/// rounded down. Both assets' USD cap PDAs and oracle accounts must be in `accounts`.
+pub fn fee_mint_amount(
+    accounts: &[AccountInfo],
+    vault: &Account<Vault>,
+    asset: &Asset,
+    fee: u64,
+    now: i64,
+) -> Result<u64> {
+    let vault_key = vault.key();
+    let (price, exponent, decimals) = usd_cap_price(accounts, &vault_key, asset, now)?;
+    let value = usd_value(fee, price, exponent, decimals).ok_or(ErrorCode::Overflow)?;
+    let fee_asset = Asset::SplToken {
+        mint: vault.fee_mint,
+    };
+    let (price, exponent, decimals) = usd_cap_price(accounts, &vault_key, &fee_asset, now)?;
+    Ok(amount_for_usd(value, price, exponent, decimals).ok_or(ErrorCode::Overflow)?)
+}
+
+/// Pay a withdrawal line's protocol `fee`, in base units of `asset`, in the vault's fee mint:
+/// `payer` sends `fee_mint_amount` of the fee mint, with any transfer fee on top, from its token
+/// account to the fee treasury's, and the line pays out without a fee withheld. Nothing leaves
+/// the vault for the fee. The fee mint and both token accounts, under `token_program`, must be in
+/// `accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_fee_in_fee_mint<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Account<'info, Vault>,
+    asset: &Asset,
+    fee: u64,
+    payer: &AccountInfo<'info>,
+    fee_treasury: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
+    let amount = fee_mint_amount(accounts, vault, asset, fee, now)?;
+    if amount == 0 {
+        return Ok(());
+    }
+    let mint = find_mint(accounts, &vault.fee_mint)?;
+    let payer_token =
+        find_associated_token_account(accounts, payer.key, &vault.fee_mint, token_program.key)?;
+    let fee_token =
+        find_associated_token_account(accounts, fee_treasury, &vault.fee_mint, token_program.key)?;
+    let gross_amount = gross_amount_for_net(&mint.to_account_info(), amount)?;
+    require!(
+        payer_token.amount >= gross_amount,
+        ErrorCode::InsufficientFunds
+    );
+
+    let cpi_accounts = TransferChecked {
+        from: payer_token.to_account_info(),
+        mint: mint.to_account_info(),
+        to: fee_token.to_account_info(),
+        authority: payer.clone(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+    transfer_checked_with_hook(cpi_ctx, accounts, gross_amount, mint.decimals)
+}
+
+/// Whether `recipient` is on the vault's blocklist. Once the blocklist PDA exists it must be
+/// passed in `accounts`.
+pub fn is_recipient_blocked<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Account<Vault>,
+    recipient: &Pubkey,
+) -> Result<bool> {
+    if !vault.has_blocklist {
+        return Ok(false);
+    }
+
+    let vault_key = vault.key();
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"blocklist", vault_key.as_ref()], &crate::ID);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected && *acc.owner == crate::ID)
+        .ok_or(ErrorCode::BlocklistNotFound)?;
+
+    let blocklist = Blocklist::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    Ok(blocklist.recipients.contains(recipient))
+}
+
+/// Mint `amount` receipts for `asset` to the depositor's associated token account when the
+/// vault issues receipts for the asset and the depositor passed its receipt mint (writable) in
+/// `accounts`.
+pub fn mint_receipt<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Account<'info, Vault>,
+    depositor: &Pubkey,
+    asset: &Asset,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let (receipt_mint, _) = Pubkey::find_program_address(
+        &[
+            b"receipt_mint",
+            vault.key().as_ref(),
+            asset.seed_key().as_ref(),
+        ],
+        &crate::ID,
+    );
+    let Some(mint_info) = accounts.iter().find(|acc| acc.key() == receipt_mint) else {
+        return Ok(());
+    };
+    let depositor_token =
+        find_associated_token_account(accounts, depositor, &receipt_mint, &token_program.key())?;
+
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = MintTo {
+        mint: mint_info.clone(),
+        to: depositor_token.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds),
+        amount,
+    )
+}
+
+/// Gross amount to send so that `net_amount` reaches the recipient after the mint's
+/// Token-2022 transfer fee (if any).
+pub fn gross_amount_for_net(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(net_amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(ErrorCode::Overflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(net_amount.checked_add(fee).ok_or(ErrorCode::Overflow)?)
+}
+
+/// `transfer_checked` that also forwards Token-2022 transfer hook accounts. The hook program,
+/// its validation account and the extra accounts it lists are resolved from `hook_accounts`.
+pub fn transfer_checked_with_hook<'info>(
+    cpi_ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    if *cpi_ctx.program.key != anchor_spl::token_2022::ID {
+        return token_interface::transfer_checked(cpi_ctx, amount, decimals);
+    }
+
+    invoke_transfer_checked(
+        cpi_ctx.program.key,
+        cpi_ctx.accounts.from,
+        cpi_ctx.accounts.mint,
+        cpi_ctx.accounts.to,
+        cpi_ctx.accounts.authority,
+        hook_accounts,
+        amount,
+        decimals,
+        cpi_ctx.signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Send the protocol fee of an SPL withdrawal line, `gross_fee` including any transfer fee, from
+/// the vault's token account to the fee treasury's, which must be in `accounts`.
+pub fn transfer_spl_fee<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Account<'info, Vault>,
+    vault_token: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    fee_treasury: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    gross_fee: u64,
+) -> Result<()> {
+    if gross_fee == 0 {
+        return Ok(());
+    }
+    let fee_token =
+        find_associated_token_account(accounts, fee_treasury, &mint.key(), &token_program.key())?;
+
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = TransferChecked {
+        from: vault_token.to_account_info(),
+        mint: mint.to_account_info(),
+        to: fee_token.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    transfer_checked_with_hook(cpi_ctx, accounts, gross_fee, mint.decimals)
+}
+
+/// Lamports refunded to the payer of a withdrawal that created `nonces` nonce accounts: their rent
+/// plus a base transaction fee, capped at `max_rebate` (0 turns the rebate off).
+pub fn relayer_rebate(max_rebate: u64, nonce_rent: u64, nonces: u64) -> u64 {
+    nonce_rent
+        .saturating_mul(nonces)
+        .saturating_add(RELAYER_TX_FEE)
+        .min(max_rebate)
+}
+
+/// Refund the relayer rebate from the treasury to `payer`, which fronted the nonce rent because the
+/// program-owned treasury can't pay for an account's creation itself. Returns the lamports paid.
+pub fn pay_relayer_rebate(
+    vault: &Vault,
+    treasury: &AccountInfo,
+    payer: &AccountInfo,
+    nonces: u64,
+) -> Result<u64> {
+    let rent = Rent::get()?;
+    let rebate = relayer_rebate(
+        vault.max_relayer_rebate,
+        rent.minimum_balance(8 + NonceAccount::INIT_SPACE),
+        nonces,
+    );
+    if rebate == 0 {
+        return Ok(0);
+    }
+
+    let available = treasury
+        .lamports()
+        .saturating_sub(rent.minimum_balance(treasury.data_len()));
+    require!(available >= rebate, ErrorCode::InsufficientFunds);
+
+    transfer_lamports(treasury, payer, rebate)?;
+    Ok(rebate)
+}
+
+/// Lamports `treasury` can pay withdrawals: its balance above rent exemption and `reserve`.
+pub fn withdrawable_lamports(treasury: &AccountInfo, reserve: u64) -> Result<u64> {
+    let floor = Rent::get()?
+        .minimum_balance(treasury.data_len())
+        .saturating_add(reserve);
+    Ok(treasury.lamports().saturating_sub(floor))
+}
+
+/// The lines `ticket` withdraws. With `balance_bps` set, each line takes that share of what the
+/// vault holds of its asset when the ticket runs: the source treasury's lamports above `reserve`
+/// for SOL, the vault's token account for SPL tokens and NFTs. The signed amount then is the least
+/// the line may come to.
+pub fn resolve_withdrawals<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Account<Vault>,
+    ticket: &WithdrawalTicket,
+    source_treasury: &AccountInfo,
+    reserve: u64,
+    token_program: &Pubkey,
+) -> Result<Vec<AssetAmount>> {
+    let Some(balance_bps) = ticket.balance_bps else {
+        return Ok(ticket.withdrawals.clone());
+    };
+    require!(
+        balance_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidBalanceBps
+    );
+
+    ticket
+        .withdrawals
+        .iter()
+        .map(|withdrawal| {
+            let balance = match withdrawal.asset {
+                Asset::Sol => withdrawable_lamports(source_treasury, reserve)?,
+                Asset::SplToken { mint } | Asset::Nft { mint } => {
+                    find_associated_token_account(accounts, &vault.key(), &mint, token_program)?
+                        .amount
+                }
+            };
+            let amount = fee_for(balance, balance_bps);
+            require!(amount >= withdrawal.amount, ErrorCode::InsufficientFunds);
+            Ok(AssetAmount {
+                asset: withdrawal.asset.clone(),
+                amount,
+            })
+        })
+        .collect()
+}
+
+/// Move `amount` lamports from `from` to `to`. Every SOL payout goes through here rather than a
+/// `system_instruction::transfer` signed with the treasury seeds: `initialize` creates the
+/// treasuries with this program as owner, the System program only debits accounts it owns, and
+/// the runtime lets the owning program debit its accounts' lamports directly.
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_balance = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    let to_balance = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+/// While the vault has a crank registry, require `cranker` on it and pay it the registry's
+/// incentive from the treasury. The registry PDA must then be passed in `accounts`. Returns the
+/// lamports paid.
+pub fn pay_crank_incentive<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    vault: &Account<Vault>,
+    treasury: &AccountInfo,
+    cranker: &AccountInfo,
+) -> Result<u64> {
+    if !vault.has_crank_registry {
+        return Ok(0);
+    }
+
+    let vault_key = vault.key();
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"crank_registry", vault_key.as_ref()], &crate::ID);
+    let account_info = accounts
+        .iter()
+        .find(|acc| acc.key() == expected)
+        .ok_or(ErrorCode::CrankRegistryNotFound)?;
+
+    let registry = Account::<CrankRegistry>::try_from(account_info)?;
+    require!(
+        registry.crankers.contains(cranker.key),
+        ErrorCode::CrankerNotRegistered
+    );
+    if registry.incentive == 0 {
+        return Ok(0);
+    }
+
+    let available = treasury
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(treasury.data_len()));
+    require!(
+        available >= registry.incentive,
+        ErrorCode::InsufficientFunds
+    );
+
+    transfer_lamports(treasury, cranker, registry.incentive)?;
+    Ok(registry.incentive)
+}
+
+/// A ticket committing to token accounts names one per withdrawal line, the recipient itself for
+/// SOL lines.
+pub fn check_token_accounts(ticket: &WithdrawalTicket) -> Result<()> {
+    if let Some(token_accounts) = &ticket.token_accounts {
+        require!(
+            token_accounts.len() == ticket.withdrawals.len(),
+            ErrorCode::InvalidTokenAccounts
+        );
+        for (withdrawal, token_account) in ticket.withdrawals.iter().zip(token_accounts.iter()) {
+            require!(
+                withdrawal.asset != Asset::Sol || *token_account == ticket.recipient,
+                ErrorCode::InvalidTokenAccounts
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn check_memo(memo: &Option<String>) -> Result<()> {
+    if let Some(memo) = memo {
+        require!(memo.len() <= MAX_MEMO_LEN, ErrorCode::MemoTooLong);
+    }
+    Ok(())
+}
+
+/// The caller-supplied `metadata` as events carry it: trimmed, `None` when blank, at most
+/// `MAX_METADATA_LEN` bytes and free of control characters, so indexers can't be fed newlines or
+/// escape sequences. Borsh already rejects strings that aren't UTF-8.
+pub fn sanitize_metadata(metadata: Option<String>) -> Result<Option<String>> {
+    let Some(metadata) = metadata else {
+        return Ok(None);
+    };
+    let metadata = metadata.trim();
+    require!(
+        metadata.len() <= MAX_METADATA_LEN,
+        ErrorCode::MetadataTooLong
+    );
+    require!(
+        !metadata.chars().any(char::is_control),
+        ErrorCode::InvalidMetadata
+    );
+    Ok((!metadata.is_empty()).then(|| metadata.to_string()))
+}
+
+/// Log `memo` through the SPL Memo program found in `accounts`, if the ticket carries one.
+pub fn emit_memo<'info>(accounts: &[AccountInfo<'info>], memo: &Option<String>) -> Result<()> {
+    let Some(memo) = memo else {
+        return Ok(());
+    };
+
+    let memo_program = accounts
+        .iter()
+        .find(|acc| acc.key() == memo::ID)
+        .ok_or(ErrorCode::MemoProgramNotFound)?;
+
+    memo::build_memo(
+        CpiContext::new(memo_program.clone(), BuildMemo {}),
+        memo.as_bytes(),
+    )
+}
+
+/// Build the instruction a CPI ticket committed to, checking it against the remaining accounts:
+/// the target program, followed by the ticket's account metas in order, then the instructions
+/// sysvar when signers approve through precompile instructions. Only `vault` may be a signer
+/// the transaction doesn't provide, since it's the only account the program signs for.
+pub fn ticket_cpi_instruction(
+    vault: &Pubkey,
+    program_id: &Pubkey,
+    accounts: &[CpiAccountMeta],
+    data: Vec<u8>,
+    data_hash: &[u8; 32],
+    remaining_accounts: &[AccountInfo],
+) -> Result<Instruction> {
+    // The instruction must be exactly the one the signers approved.
+    require!(
+        keccak::hash(&data).to_bytes() == *data_hash,
+        ErrorCode::CpiDataMismatch
+    );
+    require!(*program_id != crate::ID, ErrorCode::InvalidCpiProgram);
+
+    let (program_info, account_infos) = remaining_accounts
+        .split_first()
+        .ok_or(ErrorCode::InsufficientAccounts)?;
+    let account_infos = match account_infos.split_last() {
+        Some((last, rest))
+            if last.key() == sysvar::instructions::ID && rest.len() == accounts.len() =>
+        {
+            rest
+        }
+        _ => account_infos,
+    };
+    require!(
+        program_info.key() == *program_id && program_info.executable,
+        ErrorCode::InvalidCpiProgram
+    );
+    require!(
+        account_infos.len() == accounts.len(),
+        ErrorCode::CpiAccountMismatch
+    );
+
+    let mut metas = Vec::with_capacity(accounts.len());
+    for (meta, account_info) in accounts.iter().zip(account_infos.iter()) {
+        require!(
+            account_info.key() == meta.pubkey,
+            ErrorCode::CpiAccountMismatch
+        );
+        require!(
+            !meta.is_writable || account_info.is_writable,
+            ErrorCode::CpiAccountMismatch
+        );
+        require!(
+            !meta.is_signer || meta.pubkey == *vault || account_info.is_signer,
+            ErrorCode::CpiAccountMismatch
+        );
+
+        metas.push(AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        });
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: metas,
+        data,
+    })
+}
+
+/// Read a Wormhole `PostedVAA` account, which the core bridge only creates once the guardians'
+/// signatures over it verified. Returns the VAA and its payload, once it's known to come from
+/// `emitter`.
+pub fn posted_vaa_payload(
+    posted_vaa: &AccountInfo,
+    emitter: &WormholeEmitter,
+) -> Result<(PostedVaaData, Vec<u8>)> {
+    require!(
+        *posted_vaa.owner == emitter.core_bridge,
+        ErrorCode::InvalidVaa
+    );
+    let data = posted_vaa.try_borrow_data()?;
+    let body = data.strip_prefix(b"vaa").ok_or(ErrorCode::InvalidVaa)?;
+    let mut vaa = PostedVaaData::deserialize(&mut &body[..]).map_err(|_| ErrorCode::InvalidVaa)?;
+    require!(
+        vaa.emitter_chain == emitter.emitter_chain
+            && vaa.emitter_address == emitter.emitter_address,
+        ErrorCode::InvalidVaa
+    );
+
+    let payload = std::mem::take(&mut vaa.payload);
+    Ok((vaa, payload))
+}
+
+/// Tell the vault's hook program, if one is set, about a deposit or withdrawal so it can react in
+/// the same transaction. The call's only account is the vault's `["hook_authority", vault]` PDA,
+/// which holds nothing and signs to prove the caller; it and the hook program must be passed in
+/// `accounts`.
+pub fn notify_hook<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Account<'_, Vault>,
+    kind: BatchKind,
+    request_id: u64,
+    account: Pubkey,
+    assets: &[AssetAmount],
+) -> Result<()> {
+    if vault.hook_program == Pubkey::default() {
+        return Ok(());
+    }
+
+    let vault_key = vault.key();
+    let (hook_authority, bump) =
+        Pubkey::find_program_address(&[b"hook_authority", vault_key.as_ref()], &crate::ID);
+    let hook_program = accounts
+        .iter()
+        .find(|acc| acc.key() == vault.hook_program)
+        .ok_or(ErrorCode::HookProgramNotFound)?;
+    let hook_authority_info = accounts
+        .iter()
+        .find(|acc| acc.key() == hook_authority)
+        .ok_or(ErrorCode::HookProgramNotFound)?;
+
+    let mut data = HOOK_IX_DISCRIMINATOR.to_vec();
+    HookPayload {
+        vault: vault_key,
+        kind,
+        request_id,
+        account,
+        assets: assets.to_vec(),
+    }
+    .serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: vault.hook_program,
+        accounts: vec![AccountMeta::new_readonly(hook_authority, true)],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[hook_authority_info.clone(), hook_program.clone()],
+        &[&[b"hook_authority", vault_key.as_ref(), &[bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// Record `request_id` in the vault's nonce tree: replace the empty leaf at
+/// `request_id - nonce_tree_base` with `ticket_hash` through the SPL account compression program,
+/// which fails if the leaf was already set. `accounts` must hold the compression and noop
+/// programs, the vault's `["nonce_tree_authority", vault]` PDA and the tree, directly followed by
+/// the tree root the proof was built against (as a pubkey) and the full `nonce_tree_depth` proof
+/// nodes, leaf to root. Accounts after the proof are left to the other remaining-account checks.
+pub fn record_in_nonce_tree<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Account<'_, Vault>,
+    request_id: u64,
+    ticket_hash: [u8; 32],
+) -> Result<()> {
+    let vault_key = vault.key();
+    let (authority, bump) =
+        Pubkey::find_program_address(&[b"nonce_tree_authority", vault_key.as_ref()], &crate::ID);
+    let find = |address: Pubkey| {
+        accounts
+            .iter()
+            .find(|acc| acc.key() == address)
+            .ok_or(ErrorCode::NonceTreeNotFound)
+    };
+    let compression_program = find(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+    let noop_program = find(SPL_NOOP_PROGRAM_ID)?;
+    let authority_info = find(authority)?;
+    let tree_position = accounts
+        .iter()
+        .position(|acc| acc.key() == vault.nonce_tree)
+        .ok_or(ErrorCode::NonceTreeNotFound)?;
+    let (root, proof) = accounts[tree_position + 1..]
+        .split_first()
+        .ok_or(ErrorCode::NonceTreeNotFound)?;
+    let proof = proof
+        .get(..vault.nonce_tree_depth as usize)
+        .ok_or(ErrorCode::NonceTreeNotFound)?;
+
+    let mut data = REPLACE_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(root.key.as_ref());
+    data.extend_from_slice(&[0; 32]); // previous leaf: empty while the id is unused
+    data.extend_from_slice(&ticket_hash);
+    data.extend_from_slice(&((request_id - vault.nonce_tree_base) as u32).to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(vault.nonce_tree, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(SPL_NOOP_PROGRAM_ID, false),
+    ];
+    metas.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+    let mut infos = vec![
+        accounts[tree_position].clone(),
+        authority_info.clone(),
+        noop_program.clone(),
+        compression_program.clone(),
+    ];
+    infos.extend(proof.iter().cloned());
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[&[b"nonce_tree_authority", vault_key.as_ref(), &[bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// While the vault has a deposit registry, require an attestation of `depositor` under the
+/// vault's schema among `accounts`: owned by the registry, unrevoked and unexpired at `now`.
+pub fn check_depositor_attestation(
+    accounts: &[AccountInfo],
+    vault: &Vault,
+    depositor: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if vault.deposit_registry == Pubkey::default() {
+        return Ok(());
+    }
+
+    for account_info in accounts
+        .iter()
+        .filter(|acc| *acc.owner == vault.deposit_registry)
+    {
+        let data = account_info.try_borrow_data()?;
+        let Some(mut body) = data.get(8..) else {
+            continue;
+        };
+        let Ok(attestation) = DepositAttestation::deserialize(&mut body) else {
+            continue;
+        };
+        if attestation.schema != vault.deposit_schema || attestation.subject != *depositor {
+            continue;
+        }
+
+        require!(
+            !attestation.revoked && (attestation.expiry == 0 || now < attestation.expiry),
+            ErrorCode::DepositorAttestationInvalid
+        );
+        return Ok(());
+    }
+
+    err!(ErrorCode::DepositorNotAttested)
+}
+
+/// `amount * bps / 10_000`, rounded down.
+pub fn fee_for(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Build the `BatchSummaryEvent` for a whole instruction, emitted instead of the per-line events
+/// when the vault is in `LogVerbosity::Compact`. `fee_bps` is the protocol fee charged on each
+/// line; 0 for deposits and admin withdrawals.
+#[allow(clippy::too_many_arguments)]
+pub fn batch_summary<'a>(
+    vault: Pubkey,
+    sequence: u64,
+    kind: BatchKind,
+    request_ids: Vec<u64>,
+    items: impl IntoIterator<Item = &'a AssetAmount>,
+    fee_bps: u16,
+    valid_signers: u8,
+    metadata: Option<String>,
+) -> Result<BatchSummaryEvent> {
+    let mut lines = 0u32;
+    let mut totals: Vec<AssetAmount> = Vec::new();
+    let mut fees: Vec<AssetAmount> = Vec::new();
+    for item in items {
+        lines += 1;
+        add_to_totals(&mut totals, &item.asset, item.amount)?;
+        let fee = fee_for(item.amount, fee_bps);
+        if fee > 0 {
+            add_to_totals(&mut fees, &item.asset, fee)?;
+        }
+    }
+
+    Ok(BatchSummaryEvent {
+        vault,
+        sequence,
+        kind,
+        request_ids,
+        lines,
+        totals,
+        fees,
+        valid_signers,
+        metadata,
+    })
+}
+
+fn add_to_totals(totals: &mut Vec<AssetAmount>, asset: &Asset, amount: u64) -> Result<()> {
+    match totals.iter_mut().find(|total| total.asset == *asset) {
+        Some(total) => {
+            total.amount = total
+                .amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?
+        }
+        None => totals.push(AssetAmount {
+            asset: asset.clone(),
+            amount,
+        }),
+    }
+    Ok(())
+}
+
+/// Next link of the vault's processed-request hash chain:
+/// `keccak(root || nonce_seed || request_id_le)`, where `nonce_seed` is the seed of the consumed
+/// nonce PDA (`b"nonce"` or `b"admin_nonce"`).
+pub fn next_processed_root(root: &[u8; 32], nonce_seed: &[u8], request_id: u64) -> [u8; 32] {
+    keccak::hashv(&[root, nonce_seed, &request_id.to_le_bytes()]).to_bytes()
+}
+
+/// Inner node of a claim tree: `keccak(0x01 || min(a, b) || max(a, b))`. Sorting the pair means
+/// proofs carry no left/right flags.
+pub fn claim_node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[&[1u8], low, high]).to_bytes()
+}
+
+pub fn verify_claim_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| claim_node_hash(&node, sibling));
+    &computed == root
+}
+
+/// Whether tickets hashed under `version` are still accepted.
+pub fn is_supported_ticket_version(version: u8) -> bool {
+    (MIN_TICKET_VERSION..=TICKET_VERSION).contains(&version)
+}
+
+/// Whether a ticket activating at `not_before` may be executed at `now`.
+pub fn is_ticket_active(not_before: Option<i64>, now: i64) -> bool {
+    not_before.is_none_or(|not_before| now >= not_before)
+}
+
+/// How much of a signer set must approve a ticket.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quorum {
+    /// Signers weighing at least this much.
+    Weight(u16),
+    /// Every active signer.
+    All,
+    /// Any one active signer.
+    One,
+}
+
+/// Check that `ticket` is approved by `quorum` of `signers` under the vault's `auth_scheme`, and
+/// return how many signers approved it, for the events' `valid_signers`. An attestor's
+/// approval counts as no signer.
+#[allow(clippy::too_many_arguments)]
+pub fn authorize(
+    vault: &Account<'_, Vault>,
+    ticket: &dyn Ticket,
+    signers_with_sigs: &[SignerWithSignature],
+    remaining_accounts: &[AccountInfo],
+    signers: &[WeightedSigner],
+    quorum: Quorum,
+    now: i64,
+) -> Result<u8> {
+    let (signers_with_sigs, precompile_sigs) = match vault.auth_scheme {
+        AuthScheme::Secp256k1 => (
+            signers_with_sigs,
+            precompile_approvals(remaining_accounts, &vault.auth_scheme),
+        ),
+        AuthScheme::Ed25519 | AuthScheme::Secp256r1 => (
+            &[][..],
+            precompile_approvals(remaining_accounts, &vault.auth_scheme),
+        ),
+        AuthScheme::Attestor { program } => {
+            check_attestation(
+                remaining_accounts,
+                &vault.key(),
+                &program,
+                &ticket.hash(&vault.domain_suffix),
+            )?;
+            return Ok(0);
+        }
+    };
+
+    // The operational set, and the admin set while it is shared, live in the signer set PDA.
+    let signer_set;
+    let signers = if signers.is_empty() && vault.has_signer_set {
+        signer_set = load_signer_set(remaining_accounts, &vault.key())?;
+        &signer_set[..]
+    } else {
+        signers
+    };
+
+    // Reject a short signature list before recovering any of it.
+    let submitted = signers_with_sigs.len() + precompile_sigs.len();
+    let (enough_submitted, required) = match quorum {
+        Quorum::Weight(threshold) => (
+            max_signed_weight(signers, submitted) >= threshold,
+            threshold,
+        ),
+        Quorum::All => (
+            submitted >= active_signers(signers, now),
+            active_weight(signers, now),
+        ),
+        Quorum::One => (submitted >= 1, 1),
+    };
+    require!(enough_submitted, ErrorCode::InsufficientSignatures);
+    check_signature_budget(
+        signers_with_sigs.len(),
+        signers_needed(signers, required, now),
+    )?;
+
+    let validated_sigs = validate_sigs(
+        ticket,
+        signers_with_sigs,
+        &precompile_sigs,
+        signers,
+        required,
+        &vault.domain_suffix,
+        now,
+        vault.strict_signatures,
+    )?;
+    let approved = match quorum {
+        Quorum::Weight(threshold) => signed_weight(signers, &validated_sigs) >= threshold,
+        Quorum::All => {
+            !validated_sigs.is_empty() && validated_sigs.len() == active_signers(signers, now)
+        }
+        Quorum::One => !validated_sigs.is_empty(),
+    };
+    require!(approved, ErrorCode::InsufficientValidSignatures);
+
+    Ok(validated_sigs.len() as u8)
+}
+
+/// Bound the signatures recovered for one ticket to `needed` (see [`signers_needed`]) plus
+/// `EXTRA_SIGNATURES`, which leave room for a few that fail to verify. Precompile approvals
+/// aren't counted, the runtime verified them.
+pub fn check_signature_budget(submitted: usize, needed: usize) -> Result<()> {
+    require!(
+        submitted <= needed + EXTRA_SIGNATURES as usize,
+        ErrorCode::TooManySignatures
+    );
+    Ok(())
+}
+
+/// Most signatures it can take to reach `required` weight: the lightest signers active at `now`
+/// counted until their weight reaches it, or all active signers when it can't be reached.
+pub fn signers_needed(signers: &[WeightedSigner], required: u16, now: i64) -> usize {
+    let mut weights: Vec<u16> = signers
+        .iter()
+        .filter(|s| s.is_active(now))
+        .map(|s| s.weight as u16)
+        .collect();
+    weights.sort_unstable();
+    let mut total = 0u16;
+    for (count, weight) in weights.iter().enumerate() {
+        if total >= required {
+            return count;
+        }
+        total = total.saturating_add(*weight);
+    }
+    weights.len()
+}
+
+/// An attestor can't be this program or the default pubkey, neither of which creates
+/// attestations.
+pub fn check_auth_scheme(auth_scheme: &AuthScheme) -> Result<()> {
+    if let AuthScheme::Attestor { program } = auth_scheme {
+        require!(
+            *program != crate::ID && *program != Pubkey::default(),
+            ErrorCode::InvalidAttestor
+        );
+    }
+    Ok(())
+}
+
+/// Network id of a vault bound to the cluster with `genesis_hash`: the hash's first 8 bytes,
+/// little-endian. Validators derive it from the genesis hash of the cluster they sign for, so
+/// their tickets only carry an id a vault on that cluster accepts.
+pub fn genesis_network_id(genesis_hash: &[u8; 32]) -> u64 {
+    u64::from_le_bytes(genesis_hash[..8].try_into().unwrap())
+}
+
+/// With a genesis hash, `network_id` must be the one derived from it. The program can't read
+/// the cluster's genesis hash itself, so the caller passes it from the `getGenesisHash` RPC.
+pub fn check_genesis_binding(network_id: u64, genesis_hash: &Option<[u8; 32]>) -> Result<()> {
+    if let Some(genesis_hash) = genesis_hash {
+        require!(
+            *genesis_hash != [0; 32] && network_id == genesis_network_id(genesis_hash),
+            ErrorCode::GenesisNetworkMismatch
+        );
+    }
+    Ok(())
+}
+
+/// Address of the account `attestor` creates to approve the ticket hashing to `message_hash`
+/// for `vault`.
+pub fn attestation_address(vault: &Pubkey, attestor: &Pubkey, message_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"attestation", vault.as_ref(), message_hash], attestor).0
+}
+
+/// The attestation must be in `accounts`, owned by the attestor and initialized. Only the
+/// attestor can sign for its PDA, so anything else at that address is lamports sent to it.
+fn check_attestation(
+    accounts: &[AccountInfo],
+    vault: &Pubkey,
+    attestor: &Pubkey,
+    message_hash: &[u8; 32],
+) -> Result<()> {
+    let address = attestation_address(vault, attestor, message_hash);
+    let attestation = accounts
+        .iter()
+        .find(|acc| acc.key() == address)
+        .ok_or(ErrorCode::MissingAttestation)?;
+    require!(
+        attestation.owner == attestor && !attestation.data_is_empty(),
+        ErrorCode::MissingAttestation
+    );
+    Ok(())
+}
+
+/// Active signers in `real_signers` that approved `ticket`. Stops as soon as their summed
+/// weight reaches `quorum`, so signatures past the threshold aren't recovered. Signatures from
+/// the set must come in strictly increasing signer address order, and a signer approving
+/// twice, by either path, fails with `DuplicateSignature`.
+///
+/// With `strict`, every signature in `signers_with_sigs` is checked and its recovered address
+/// logged. The first one that fails recovery (`SignatureRecoveryFailed`) or isn't from an
+/// active signer (`UnauthorizedSigner`) rejects the ticket, with its index in the log.
+#[allow(clippy::too_many_arguments)]
 pub fn validate_sigs(
     ticket: &dyn Ticket,
-    signers_with_sigs: &Vec<SignerWithSignature>,
-    real_signers: &Vec<[u8; 20]>,
-) -> HashSet<[u8; 20]> {
-    let message_hash = ticket.hash();
+    signers_with_sigs: &[SignerWithSignature],
+    precompile_approvals: &[PrecompileApproval],
+    real_signers: &[WeightedSigner],
+    quorum: u16,
+    domain_suffix: &str,
+    now: i64,
+    strict: bool,
+) -> Result<HashSet<[u8; 20]>> {
+    let message_hash = ticket.hash(domain_suffix);
 
-    let mut valid_signers = HashSet::new();
-    for signer_sig in signers_with_sigs.iter() {
-        match recover_eth_address(&message_hash, &signer_sig.signature, signer_sig.recovery_id) {
-            Ok(recovered_address) => {
-                if real_signers.contains(&recovered_address) {
-                    valid_signers.insert(recovered_address);
+    // Precompile approvals were verified by the runtime, so they are counted before any
+    // signature is recovered here.
+    let approved = precompile_approvals
+        .iter()
+        .filter(|approval| approval.message == message_hash)
+        .map(|approval| Ok((approval.address, None)));
+    let recovered = signers_with_sigs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, signer_sig)| {
+            match recover_eth_address(&message_hash, &signer_sig.signature, signer_sig.recovery_id)
+            {
+                Ok(address) => {
+                    if strict {
+                        msg!("Signature {} recovered 0x{}", index, hex_address(&address));
+                    }
+                    Some(Ok((address, Some(index))))
+                }
+                Err(err) if strict => {
+                    msg!("Signature {} failed recovery: {}", index, err);
+                    Some(Err(error!(ErrorCode::SignatureRecoveryFailed)))
                 }
+                Err(_) => None,
+            }
+        });
+
+    let mut valid_signers = HashSet::new();
+    let mut weight: u16 = 0;
+    let mut last_recovered: Option<[u8; 20]> = None;
+    for approval in approved.chain(recovered) {
+        let (address, index) = approval?;
+        // Strict mode checks every signature, not just enough to reach the quorum.
+        if weight >= quorum && !strict {
+            break;
+        }
+        // Signatures from expired signers don't count.
+        let Some(signer) = real_signers
+            .iter()
+            .find(|s| s.address == address && s.is_active(now))
+        else {
+            if let (true, Some(index)) = (strict, index) {
+                msg!(
+                    "Signature {} from 0x{} is not an active signer",
+                    index,
+                    hex_address(&address)
+                );
+                return err!(ErrorCode::UnauthorizedSigner);
             }
-            Err(_) => continue,
+            continue;
+        };
+        if index.is_some() {
+            // Sorted submissions put a repeated signer right after its first signature.
+            require!(
+                last_recovered != Some(address),
+                ErrorCode::DuplicateSignature
+            );
+            require!(
+                last_recovered.is_none_or(|last| last < address),
+                ErrorCode::UnsortedSignatures
+            );
+            last_recovered = Some(address);
         }
+        require!(valid_signers.insert(address), ErrorCode::DuplicateSignature);
+        weight += signer.weight as u16;
     }
 
-    valid_signers
+    Ok(valid_signers)
+}
+
+fn hex_address(address: &[u8; 20]) -> String {
+    address.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Signer-set address of an Ed25519 key: the last 20 bytes of keccak256(pubkey), the same
+/// derivation Ethereum applies to secp256k1 keys.
+pub fn ed25519_signer_address(pubkey: &Pubkey) -> [u8; 20] {
+    let hash = keccak::hash(pubkey.as_ref()).to_bytes();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..32]);
+    address
+}
+
+/// Signer-set address of a secp256r1 key: the last 20 bytes of keccak256 of its 33-byte
+/// compressed encoding, as for Ed25519 keys.
+pub fn secp256r1_signer_address(pubkey: &[u8; 33]) -> [u8; 20] {
+    let hash = keccak::hash(pubkey).to_bytes();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..32]);
+    address
+}
+
+/// Signatures over 32-byte messages in one Ed25519 precompile instruction. Only entries whose
+/// signature, pubkey and message all live in that instruction are returned.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Vec<PrecompileApproval> {
+    parse_offsets_instruction(data, 32, |pubkey| {
+        ed25519_signer_address(&Pubkey::new_from_array(pubkey.try_into().unwrap()))
+    })
+}
+
+/// Signatures over 32-byte messages in one secp256r1 precompile instruction, which shares the
+/// Ed25519 layout with 33-byte compressed keys. The precompile rejects high-s signatures, so
+/// approvals can't be made malleable.
+pub fn parse_secp256r1_instruction(data: &[u8]) -> Vec<PrecompileApproval> {
+    parse_offsets_instruction(data, 33, |pubkey| {
+        secp256r1_signer_address(pubkey.try_into().unwrap())
+    })
+}
+
+/// Entries of an Ed25519-layout precompile instruction whose signature, `pubkey_len`-byte key and
+/// 32-byte message all live in that instruction, keyed by `address_of` their key.
+fn parse_offsets_instruction(
+    data: &[u8],
+    pubkey_len: usize,
+    address_of: impl Fn(&[u8]) -> [u8; 20],
+) -> Vec<PrecompileApproval> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    let read_u16 = |at: usize| -> Option<u16> {
+        data.get(at..at + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+
+    let count = data.first().copied().unwrap_or(0) as usize;
+    let mut approvals = Vec::new();
+    for i in 0..count {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        let offsets: Option<Vec<u16>> = (0..7).map(|field| read_u16(at + field * 2)).collect();
+        let Some(offsets) = offsets else {
+            break;
+        };
+        let (signature_ix, pubkey_offset, pubkey_ix) = (offsets[1], offsets[2], offsets[3]);
+        let (message_offset, message_size, message_ix) = (offsets[4], offsets[5], offsets[6]);
+        if signature_ix != THIS_INSTRUCTION
+            || pubkey_ix != THIS_INSTRUCTION
+            || message_ix != THIS_INSTRUCTION
+            || message_size != 32
+        {
+            continue;
+        }
+
+        let pubkey = data.get(pubkey_offset as usize..pubkey_offset as usize + pubkey_len);
+        let message = data.get(message_offset as usize..message_offset as usize + 32);
+        if let (Some(pubkey), Some(message)) = (pubkey, message) {
+            approvals.push(PrecompileApproval {
+                address: address_of(pubkey),
+                message: message.try_into().unwrap(),
+            });
+        }
+    }
+    approvals
+}
+
+/// Signatures in one secp256k1 precompile instruction at `index` in the transaction. The
+/// precompile checks that the signature over keccak256(message) recovers to the given Ethereum
+/// address, so the message is the ticket preimage and the approval is for its hash. Only
+/// low-s entries whose signature, address and message all live in that instruction are returned.
+pub fn parse_secp256k1_instruction(data: &[u8], index: u8) -> Vec<PrecompileApproval> {
+    const HEADER_LEN: usize = 1;
+    const OFFSETS_LEN: usize = 11;
+
+    let read_u16 = |at: usize| -> Option<usize> {
+        data.get(at..at + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let read_u8 = |at: usize| -> Option<u8> { data.get(at).copied() };
+
+    let count = data.first().copied().unwrap_or(0) as usize;
+    let mut approvals = Vec::new();
+    for i in 0..count {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        let (
+            Some(signature_offset),
+            Some(signature_ix),
+            Some(address_offset),
+            Some(address_ix),
+            Some(message_offset),
+            Some(message_size),
+            Some(message_ix),
+        ) = (
+            read_u16(at),
+            read_u8(at + 2),
+            read_u16(at + 3),
+            read_u8(at + 5),
+            read_u16(at + 6),
+            read_u16(at + 8),
+            read_u8(at + 10),
+        )
+        else {
+            break;
+        };
+        if signature_ix != index || address_ix != index || message_ix != index {
+            continue;
+        }
+
+        let signature = data.get(signature_offset..signature_offset + 64);
+        let address = data.get(address_offset..address_offset + 20);
+        let message = data.get(message_offset..message_offset + message_size);
+        if let (Some(signature), Some(address), Some(message)) = (signature, address, message) {
+            if !is_low_s(signature) {
+                continue;
+            }
+            approvals.push(PrecompileApproval {
+                address: address.try_into().unwrap(),
+                message: keccak::hash(message).to_bytes(),
+            });
+        }
+    }
+    approvals
+}
+
+/// Approvals the precompiles `auth_scheme` accepts verified in the current transaction, read
+/// through the instructions sysvar when it is passed in `remaining_accounts`. The runtime fails
+/// the transaction if any precompile signature doesn't verify, so every entry is valid.
+pub fn precompile_approvals(
+    remaining_accounts: &[AccountInfo],
+    auth_scheme: &AuthScheme,
+) -> Vec<PrecompileApproval> {
+    let Some(instructions_sysvar) = remaining_accounts
+        .iter()
+        .find(|a| a.key() == sysvar::instructions::ID)
+    else {
+        return vec![];
+    };
+
+    let mut approvals = Vec::new();
+    let mut index = 0;
+    while let Ok(instruction) =
+        sysvar::instructions::load_instruction_at_checked(index, instructions_sysvar)
+    {
+        let program_id = instruction.program_id;
+        if !auth_scheme.accepts_precompile(&program_id) {
+            index += 1;
+            continue;
+        }
+        if program_id == ed25519_program::ID {
+            approvals.extend(parse_ed25519_instruction(&instruction.data));
+        } else if program_id == secp256k1_program::ID {
+            approvals.extend(parse_secp256k1_instruction(&instruction.data, index as u8));
+        } else if program_id == SECP256R1_PROGRAM_ID {
+            approvals.extend(parse_secp256r1_instruction(&instruction.data));
+        }
+        index += 1;
+    }
+    approvals
+}
+
+/// Summed weight of the `signers` whose address is in `addresses`.
+pub fn signed_weight(signers: &[WeightedSigner], addresses: &HashSet<[u8; 20]>) -> u16 {
+    signers
+        .iter()
+        .filter(|s| addresses.contains(&s.address))
+        .map(|s| s.weight as u16)
+        .sum()
+}
+
+/// Number of `signers` whose signatures still count at `now`.
+pub fn active_signers(signers: &[WeightedSigner], now: i64) -> usize {
+    signers.iter().filter(|s| s.is_active(now)).count()
+}
+
+/// Summed weight of the `signers` whose signatures still count at `now`.
+pub fn active_weight(signers: &[WeightedSigner], now: i64) -> u16 {
+    signers
+        .iter()
+        .filter(|s| s.is_active(now))
+        .map(|s| s.weight as u16)
+        .sum()
+}
+
+/// Most weight `count` signatures could carry: the sum of the `count` heaviest `signers`. Lets
+/// instructions reject a short signature list before recovering any of it.
+pub fn max_signed_weight(signers: &[WeightedSigner], count: usize) -> u16 {
+    let mut weights: Vec<u16> = signers.iter().map(|s| s.weight as u16).collect();
+    weights.sort_unstable_by(|a, b| b.cmp(a));
+    weights.iter().take(count).sum()
+}
+
+/// A threshold must be non-zero and reachable by the total weight of `signers`.
+pub fn check_threshold(signers: &[WeightedSigner], threshold: u8) -> Result<()> {
+    let total_weight: u16 = signers.iter().map(|s| s.weight as u16).sum();
+    require!(
+        threshold > 0 && threshold as u16 <= total_weight,
+        ErrorCode::InvalidThreshold
+    );
+    Ok(())
+}
+
+/// Validate a new signer set for `initialize` and the rotations: 1..=MAX_SIGNERS distinct
+/// addresses with non-zero weights and no expiry before `now`, and `threshold` reachable by the
+/// total weight.
+pub fn check_signer_set(signers: &[WeightedSigner], threshold: u8, now: i64) -> Result<()> {
+    check_signer_set_within(signers, threshold, now, MAX_SIGNERS as usize)
+}
+
+/// [`check_signer_set`] with `max_len` in place of `MAX_SIGNERS`, for signer set accounts.
+pub fn check_signer_set_within(
+    signers: &[WeightedSigner],
+    threshold: u8,
+    now: i64,
+    max_len: usize,
+) -> Result<()> {
+    let signers_len = signers.len();
+    require!(
+        signers_len > 0 && signers_len <= max_len,
+        ErrorCode::InvalidSignersCount
+    );
+    require!(
+        signers.iter().all(|s| s.weight > 0),
+        ErrorCode::InvalidSignerWeight
+    );
+    require!(
+        !signers
+            .iter()
+            .any(|s| matches!(s.valid_until, Some(valid_until) if valid_until <= now)),
+        ErrorCode::InvalidSignerExpiry
+    );
+
+    check_threshold(signers, threshold)?;
+
+    // Check for duplicate signers
+    for i in 0..signers_len {
+        for j in (i + 1)..signers_len {
+            require!(
+                signers[i].address != signers[j].address,
+                ErrorCode::DuplicateSigner
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the s half of a 64-byte (r, s) signature is at most n / 2.
+fn is_low_s(signature: &[u8]) -> bool {
+    signature[32..64] <= SECP256K1_HALF_ORDER[..]
 }
 
 /// Recover Ethereum address from signature using secp256k1_recover syscall
@@ -51,6 +1888,11 @@ pub fn recover_eth_address(
         _ => return err!(ErrorCode::InvalidRecoveryId),
     };
 
+    // Only accept the low-s encoding so each approval has a single valid signature
+    if !is_low_s(signature) {
+        return err!(ErrorCode::HighSSignature);
+    }
+
     // Recover the 64-byte public key from the signature
     let recovered_pubkey = secp256k1_recover(message_hash, normalized_recovery_id, signature)
         .map_err(|_| ErrorCode::InvalidSignature)?;
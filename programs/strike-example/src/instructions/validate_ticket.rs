@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_spl::token_interface::TokenInterface;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::models::*;
+use super::util::{
+    authorize, check_allowance, check_duplicate_assets, check_identity_pass, check_memo,
+    check_nft_amount, check_recipient_allowed, check_token_accounts, check_usd_caps,
+    check_withdrawal_limits, fee_mint_amount, find_associated_token_account, find_mint,
+    find_named_treasury, find_recipient_token_account, gross_amount_for_net, is_recipient_blocked,
+    is_supported_ticket_version, is_ticket_active, resolve_withdrawals, withdrawable_lamports,
+    Quorum,
+};
+
+/// Outcome of `validate_ticket`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TicketValidation {
+    pub ticket_hash: [u8; 32],
+    pub error: Option<u64>, // program error `withdraw` would fail with, None when it would pass
+    pub valid_signers: u8,  // signers that approved the ticket, 0 until signatures are checked
+    pub recipient_blocked: bool, // the ticket would be spent without paying out
+}
+
+/// Run every check `withdraw` runs on `ticket` (vault, network, version, expiry, recipient
+/// gating, limits, signatures, nonce, allowances and balances) without consuming the nonce or
+/// moving funds, and return the outcome instead of failing. Meant to be simulated by relayers
+/// before a ticket goes out for approval or submission.
+///
+/// Remaining accounts: those `withdraw` takes for the same ticket.
+pub fn validate_ticket<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ValidateTicket<'info>>,
+    ticket: WithdrawalTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+) -> Result<TicketValidation> {
+    let mut validation = TicketValidation {
+        ticket_hash: ticket.hash(&ctx.accounts.vault.domain_suffix),
+        error: None,
+        valid_signers: 0,
+        recipient_blocked: false,
+    };
+
+    if let Err(err) = check_withdrawal(&ctx, &ticket, &signers_with_sigs, &mut validation) {
+        msg!("Ticket {} would fail: {}", ticket.request_id, err);
+        validation.error = Some(u64::from(ProgramError::from(err)));
+    }
+
+    Ok(validation)
+}
+
+fn check_withdrawal<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ValidateTicket<'info>>,
+    ticket: &WithdrawalTicket,
+    signers_with_sigs: &[SignerWithSignature],
+    validation: &mut TicketValidation,
+) -> Result<()> {
+    require!(
+        !ticket.withdrawals.is_empty(),
+        ErrorCode::NoWithdrawalsProvided
+    );
+
+    let vault = &ctx.accounts.vault;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    check_identity_pass(ctx.remaining_accounts, vault, &ticket.recipient, now)?;
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, now),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, now),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, now),
+        ErrorCode::TicketLifetimeTooLong
+    );
+
+    let named_treasury = match &ticket.treasury {
+        Some(name) => {
+            require!(
+                ticket
+                    .withdrawals
+                    .iter()
+                    .all(|withdrawal| withdrawal.asset == Asset::Sol),
+                ErrorCode::TreasuryAssetNotSupported
+            );
+            Some(find_named_treasury(
+                ctx.remaining_accounts,
+                &vault.key(),
+                name,
+            )?)
+        }
+        None => None,
+    };
+    let m_threshold = named_treasury
+        .as_ref()
+        .map_or(vault.m_threshold, |treasury| treasury.m_threshold);
+    // The treasury reserve only holds back the main treasury.
+    let (source_treasury, reserve) = match &named_treasury {
+        Some(treasury) => (treasury.to_account_info(), 0),
+        None => (
+            ctx.accounts.treasury.to_account_info(),
+            vault.treasury_reserve,
+        ),
+    };
+
+    check_duplicate_assets(&ticket.withdrawals)?;
+    let withdrawals = resolve_withdrawals(
+        ctx.remaining_accounts,
+        vault,
+        ticket,
+        &source_treasury,
+        reserve,
+        &ctx.accounts.token_program.key(),
+    )?;
+    check_withdrawal_limits(vault, &withdrawals)?;
+    check_usd_caps(ctx.remaining_accounts, vault, &withdrawals, now)?;
+    check_memo(&ticket.memo)?;
+    check_token_accounts(ticket)?;
+
+    validation.valid_signers = authorize(
+        vault,
+        ticket,
+        signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(m_threshold as u16),
+        now,
+    )?;
+
+    // Ids in the nonce tree's range sit below the watermark; their leaf isn't checked here.
+    require!(
+        ticket.request_id > vault.request_id_watermark || vault.in_nonce_tree(ticket.request_id),
+        ErrorCode::RequestIdBelowWatermark
+    );
+    // Consume a copy of the nonce to tell a replay or request id conflict from a fresh ticket.
+    let nonce_account = &ctx.accounts.nonce_account;
+    if *nonce_account.owner == crate::ID && !nonce_account.data_is_empty() {
+        let mut nonce = NonceAccount::try_deserialize(&mut &nonce_account.try_borrow_data()?[..])?;
+        nonce.consume(validation.ticket_hash, ticket.expiry)?;
+    }
+
+    validation.recipient_blocked =
+        is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    if validation.recipient_blocked {
+        return Ok(());
+    }
+
+    for (idx, withdrawal) in withdrawals.iter().enumerate() {
+        require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+        check_allowance(
+            ctx.remaining_accounts,
+            &vault.key(),
+            &ticket.recipient,
+            &withdrawal.asset,
+            withdrawal.amount,
+        )?;
+
+        // A fee paid in the fee mint only has to be priced here; the payer's balance is checked
+        // when the withdrawal lands.
+        let fee = vault.withdrawal_fee(withdrawal.amount);
+        let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+            fee
+        } else {
+            fee_mint_amount(ctx.remaining_accounts, vault, &withdrawal.asset, fee, now)?;
+            0
+        };
+        let received = withdrawal.amount - fee_in_kind;
+        match withdrawal.asset {
+            Asset::Sol => {
+                let available = withdrawable_lamports(&source_treasury, reserve)?;
+                require!(available >= withdrawal.amount, ErrorCode::InsufficientFunds);
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&withdrawal.asset, withdrawal.amount, &mint_account)?;
+                let vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                find_recipient_token_account(
+                    ctx.remaining_accounts,
+                    ticket,
+                    idx,
+                    &mint,
+                    &token_program,
+                )?;
+
+                let gross_amount = gross_amount_for_net(&mint_account.to_account_info(), received)?;
+                let gross_fee = gross_amount_for_net(&mint_account.to_account_info(), fee_in_kind)?;
+                require!(
+                    vault_token.amount
+                        >= gross_amount
+                            .checked_add(gross_fee)
+                            .ok_or(ErrorCode::Overflow)?,
+                    ErrorCode::InsufficientFunds
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ticket: WithdrawalTicket)]
+pub struct ValidateTicket<'info> {
+    #[account(
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Nonce PDA verified by seeds, may not exist yet
+    pub nonce_account: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::WithdrawalVetoedEvent;
+
+/// Consume the withdrawal nonce of `request_id` before the ticket executes, so `withdraw`,
+/// `withdraw_wsol`, `bulk_withdraw` or `publish_claim_root` can never use it. The guardian pays
+/// the nonce rent and can't move funds. Under watermark replay withdrawals skip nonce accounts
+/// and a consumed id would raise the watermark past every pending ticket, so vetoes are refused.
+pub fn veto_withdrawal(ctx: Context<VetoWithdrawal>, request_id: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.next_sequence();
+    require!(
+        vault.guardian != Pubkey::default() && vault.guardian == ctx.accounts.guardian.key(),
+        ErrorCode::NotGuardian
+    );
+    require!(!vault.watermark_replay, ErrorCode::VetoUnderWatermarkReplay);
+
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
+
+    nonce_account.used = true;
+    nonce_account.expiry = i64::MAX; // never collected: the vetoed ticket may not have expired
+    vault.consume_request_id(request_id)?;
+    vault.record_processed(NONCE_SEED, request_id)?;
+
+    emit!(WithdrawalVetoedEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id,
+        guardian: vault.guardian,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct VetoWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
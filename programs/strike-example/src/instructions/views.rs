@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use super::accounts::*;
+use super::constant::*;
+use super::models::*;
+
+/// Vault state returned by `get_vault_info`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultInfo {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub vault_seed: String,
+    pub network_id: u64,
+    pub extra_network_ids: Vec<u64>,
+    pub genesis_hash: [u8; 32],
+    pub m_threshold: u8,
+    pub admin_threshold: u8,
+    pub signers: Vec<WeightedSigner>,
+    pub admin_signers: Vec<WeightedSigner>, // empty while admin ops share `signers`
+    pub domain_suffix: String,
+    pub processed_root: [u8; 32],
+    pub processed_count: u64,
+    pub sequence: u64,
+    pub fee_bps: u16,
+    pub guardian: Pubkey,
+    pub parent: Pubkey,
+    pub asset_count: u8,
+    pub treasury: Pubkey,
+    pub treasury_lamports: u64,
+}
+
+/// Request id status returned by `get_nonce_status`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NonceStatus {
+    pub nonce: Pubkey,
+    pub used: bool,
+    pub vetoed: bool,          // consumed by the guardian rather than a ticket
+    pub ticket_hash: [u8; 32], // ticket that consumed the request id, zero when unused or vetoed
+}
+
+/// Return the vault's configuration and SOL treasury balance. Read-only: meant to be simulated,
+/// or called through CPI, with the result read from the transaction's return data.
+pub fn get_vault_info(ctx: Context<GetVaultInfo>) -> Result<VaultInfo> {
+    let vault = &ctx.accounts.vault;
+    let treasury = &ctx.accounts.treasury;
+
+    Ok(VaultInfo {
+        version: vault.version,
+        authority: vault.authority,
+        vault_seed: vault.vault_seed.clone(),
+        network_id: vault.network_id,
+        extra_network_ids: vault.extra_network_ids.clone(),
+        genesis_hash: vault.genesis_hash,
+        m_threshold: vault.m_threshold,
+        admin_threshold: vault.admin_threshold,
+        signers: vault.signers.clone(),
+        admin_signers: vault.admin_signers.clone(),
+        domain_suffix: vault.domain_suffix.clone(),
+        processed_root: vault.processed_root,
+        processed_count: vault.processed_count,
+        sequence: vault.sequence,
+        fee_bps: vault.fee_bps,
+        guardian: vault.guardian,
+        parent: vault.parent,
+        asset_count: vault.whitelisted_assets.len() as u8,
+        treasury: treasury.key(),
+        treasury_lamports: treasury.lamports(),
+    })
+}
+
+/// Return whether `request_id` is consumed, among withdrawal nonces or, with `admin`, admin op
+/// nonces. The nonce account doesn't have to exist: withdrawal ids at or below the request id
+/// watermark count as consumed.
+pub fn get_nonce_status(
+    ctx: Context<GetNonceStatus>,
+    request_id: u64,
+    admin: bool,
+) -> Result<NonceStatus> {
+    let account = &ctx.accounts.nonce_account;
+    let nonce = if account.owner == &crate::ID && !account.data_is_empty() {
+        Some(NonceAccount::try_deserialize(
+            &mut &account.try_borrow_data()?[..],
+        )?)
+    } else {
+        None
+    };
+
+    let used = nonce.as_ref().is_some_and(|nonce| nonce.used);
+    let ticket_hash = nonce.map_or([0; 32], |nonce| nonce.ticket_hash);
+    let below_watermark = !admin && request_id <= ctx.accounts.vault.request_id_watermark;
+    Ok(NonceStatus {
+        nonce: account.key(),
+        used: used || below_watermark,
+        vetoed: used && ticket_hash == [0; 32],
+        ticket_hash,
+    })
+}
+
+/// Return the whitelist entry of `asset`, `None` while it isn't whitelisted.
+pub fn get_asset_config(ctx: Context<GetAssetConfig>, asset: Asset) -> Result<Option<AssetConfig>> {
+    Ok(ctx.accounts.vault.asset_config(&asset).cloned())
+}
+
+#[derive(Accounts)]
+pub struct GetVaultInfo<'info> {
+    #[account(
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64, admin: bool)]
+pub struct GetNonceStatus<'info> {
+    #[account(
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [
+            if admin { ADMIN_NONCE_SEED } else { NONCE_SEED },
+            vault.key().as_ref(),
+            &request_id.to_le_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: Nonce PDA verified by seeds, may not exist yet
+    pub nonce_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetAssetConfig<'info> {
+    #[account(
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+}
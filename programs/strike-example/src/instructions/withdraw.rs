@@ -1,10 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
 
 use super::accounts::*;
+use super::constant::*;
 use super::errors::ErrorCode;
+use super::events::*;
 use super::models::*;
-use super::util::{check_duplicate_assets, validate_sigs};
+use super::util::{
+    authorize, batch_summary, check_duplicate_assets, check_identity_pass, check_memo,
+    check_nft_amount, check_recipient_allowed, check_token_accounts, check_usd_caps,
+    check_withdrawal_limits, consume_allowance, emit_memo, find_associated_token_account,
+    find_mint, find_named_treasury, find_recipient_token_account, gross_amount_for_net,
+    is_recipient_blocked, is_supported_ticket_version, is_ticket_active, notify_hook,
+    pay_fee_in_fee_mint, pay_relayer_rebate, record_in_nonce_tree, resolve_withdrawals,
+    sanitize_metadata, transfer_checked_with_hook, transfer_lamports, transfer_spl_fee,
+    withdrawable_lamports, Quorum,
+};
 
 pub fn withdraw<'info>(
     ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
@@ -12,6 +23,8 @@ pub fn withdraw<'info>(
     signers_with_sigs: Vec<SignerWithSignature>,
     metadata: Option<String>,
 ) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
     require!(
         !ticket.withdrawals.is_empty(),
         ErrorCode::NoWithdrawalsProvided
@@ -22,158 +35,386 @@ pub fn withdraw<'info>(
 
     require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
     require!(
-        vault.network_id == ticket.network_id,
+        vault.accepts_network_id(ticket.network_id),
         ErrorCode::InvalidNetwork
     );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
     require!(
         ticket.recipient == ctx.accounts.recipient.key(),
         ErrorCode::InvalidRecipient
     );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    check_identity_pass(
+        ctx.remaining_accounts,
+        vault,
+        &ticket.recipient,
+        clock.unix_timestamp,
+    )?;
     require!(
-        clock.unix_timestamp <= ticket.expiry,
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
         ErrorCode::TicketExpired
     );
     require!(
-        signers_with_sigs.len() >= vault.m_threshold as usize,
-        ErrorCode::InsufficientSignatures
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
     );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+
+    // A named treasury pays SOL lines and sets the threshold in place of the default one.
+    let named_treasury = match &ticket.treasury {
+        Some(name) => {
+            require!(
+                ticket
+                    .withdrawals
+                    .iter()
+                    .all(|withdrawal| withdrawal.asset == Asset::Sol),
+                ErrorCode::TreasuryAssetNotSupported
+            );
+            Some(find_named_treasury(
+                ctx.remaining_accounts,
+                &vault.key(),
+                name,
+            )?)
+        }
+        None => None,
+    };
+    let m_threshold = named_treasury
+        .as_ref()
+        .map_or(vault.m_threshold, |treasury| treasury.m_threshold);
+    // The treasury reserve only holds back the main treasury.
+    let (source_treasury, reserve) = match &named_treasury {
+        Some(treasury) => (treasury.to_account_info(), 0),
+        None => (
+            ctx.accounts.treasury.to_account_info(),
+            vault.treasury_reserve,
+        ),
+    };
 
     check_duplicate_assets(&ticket.withdrawals)?;
+    let withdrawals = resolve_withdrawals(
+        ctx.remaining_accounts,
+        vault,
+        &ticket,
+        &source_treasury,
+        reserve,
+        &ctx.accounts.token_program.key(),
+    )?;
+    check_withdrawal_limits(vault, &withdrawals)?;
+    check_usd_caps(
+        ctx.remaining_accounts,
+        vault,
+        &withdrawals,
+        clock.unix_timestamp,
+    )?;
+    check_memo(&ticket.memo)?;
+    check_token_accounts(&ticket)?;
 
-    // Validate the signatures.
-    let validated_sigs = validate_sigs(&ticket, &signers_with_sigs, &vault.signers);
+    // Validate the signatures against M, or the named treasury's threshold.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
 
-    // Normal recipient. Check M of N.
-    require!(
-        validated_sigs.len() >= vault.m_threshold as usize,
-        ErrorCode::InsufficientValidSignatures
-    );
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy). Under watermark
+    // replay the nonce account may be left out; ids in the nonce tree's range take none.
+    let ticket_hash = ticket.hash(&vault.domain_suffix);
+    if vault.in_nonce_tree(ticket.request_id) {
+        require!(
+            ctx.accounts.nonce_account.is_none(),
+            ErrorCode::InvalidNonceTree
+        );
+        record_in_nonce_tree(
+            ctx.remaining_accounts,
+            vault,
+            ticket.request_id,
+            ticket_hash,
+        )?;
+    } else {
+        match ctx.accounts.nonce_account.as_mut() {
+            Some(nonce_account) => nonce_account.consume(ticket_hash, ticket.expiry)?,
+            None => require!(vault.watermark_replay, ErrorCode::NonceAccountRequired),
+        }
+        vault.consume_request_id(ticket.request_id)?;
+    }
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
 
-    // Check nonce hasn't been used (replay protection)
-    let nonce_account = &mut ctx.accounts.nonce_account;
-    require!(!nonce_account.used, ErrorCode::NonceAlreadyUsed);
+    // The payer funded this ticket's nonce account, if it passed one.
+    let nonces = ctx.accounts.nonce_account.is_some() as u64;
+    let rebate = pay_relayer_rebate(vault, &ctx.accounts.treasury, &ctx.accounts.payer, nonces)?;
+    if rebate > 0 {
+        emit_cpi!(RelayerRebateEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            payer: ctx.accounts.payer.key(),
+            request_ids: vec![ticket.request_id],
+            amount: rebate,
+        });
+    }
 
-    // Mark nonce as used BEFORE transfer (prevent reentrancy)
-    nonce_account.used = true;
+    // A blocked recipient's ticket is spent without paying out.
+    let blocked = is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+        let paid: &[AssetAmount] = if blocked { &[] } else { &withdrawals };
+        receipt.record(
+            vault.key(),
+            ticket.request_id,
+            ticket.recipient,
+            paid,
+            clock.slot,
+        )?;
+    }
+    if blocked {
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            recipient: ticket.recipient,
+        });
+        return Ok(());
+    }
+
+    // Memo goes before the transfers so Token-2022 required-memo accounts accept them.
+    if let Some(source_ref) = ticket.source_ref {
+        emit_cpi!(SourceRefEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            source_ref,
+        });
+    }
+    emit_memo(ctx.remaining_accounts, &ticket.memo)?;
 
-    for withdrawal in ticket.withdrawals {
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
+    for (idx, withdrawal) in withdrawals.iter().enumerate() {
         require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+        consume_allowance(
+            ctx.remaining_accounts,
+            &vault.key(),
+            &ticket.recipient,
+            &withdrawal.asset,
+            withdrawal.amount,
+        )?;
+
+        // The protocol fee comes out of the ticket amount, unless the payer covers it in the fee
+        // mint.
+        let fee = vault.withdrawal_fee(withdrawal.amount);
+        let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+            fee
+        } else {
+            pay_fee_in_fee_mint(
+                ctx.remaining_accounts,
+                vault,
+                &withdrawal.asset,
+                fee,
+                &ctx.accounts.payer,
+                &ctx.accounts.fee_treasury.key(),
+                &ctx.accounts.token_program,
+                clock.unix_timestamp,
+            )?;
+            0
+        };
+        let received = withdrawal.amount - fee_in_kind;
 
         // Don't check whitelist since withdraw is always allowed.
         match withdrawal.asset {
             Asset::Sol => {
                 // Check sufficient balance
-                let treasury_balance = ctx.accounts.treasury.lamports();
-                let rent_exempt_minimum = Rent::get()?
-                    .minimum_balance(ctx.accounts.treasury.to_account_info().data_len());
-                let available = treasury_balance.saturating_sub(rent_exempt_minimum);
+                let available = withdrawable_lamports(&source_treasury, reserve)?;
 
                 require!(available >= withdrawal.amount, ErrorCode::InsufficientFunds);
 
                 // Execute transfer
-                **ctx.accounts.treasury.try_borrow_mut_lamports()? -= withdrawal.amount;
-                **ctx.accounts.recipient.try_borrow_mut_lamports()? += withdrawal.amount;
-
-                msg!(
-                    "Withdrawal SOL: request_id={}, recipient={}, amount={}, valid_signers={}, metadata={:?}",
-                    ticket.request_id,
-                    ticket.recipient,
-                    withdrawal.amount,
-                    validated_sigs.len(),
-                    metadata,
-                );
-            }
-            Asset::SplToken { mint } => {
-                let mut recipient_token_account: Option<Account<'info, TokenAccount>> = None;
-                let mut vault_token_account: Option<Account<'info, TokenAccount>> = None;
-
-                for acc in ctx.remaining_accounts.iter() {
-                    if let Ok(token_acc) = Account::<TokenAccount>::try_from(acc) {
-                        if token_acc.mint == mint {
-                            if token_acc.owner == ctx.accounts.recipient.key() {
-                                recipient_token_account = Some(token_acc);
-                            } else if token_acc.owner == vault.key() {
-                                vault_token_account = Some(token_acc);
-                            }
-                        }
-                        if recipient_token_account.is_some() && vault_token_account.is_some() {
-                            break;
-                        }
-                    }
+                transfer_lamports(&source_treasury, &ctx.accounts.recipient, received)?;
+                transfer_lamports(&source_treasury, &ctx.accounts.fee_treasury, fee_in_kind)?;
+
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: received,
+                        gross_amount: received + fee_in_kind,
+                        fee,
+                        admin: false,
+                        valid_signers,
+                        metadata: metadata.clone(),
+                    });
                 }
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&withdrawal.asset, withdrawal.amount, &mint_account)?;
+                let vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let recipient_token = find_recipient_token_account(
+                    ctx.remaining_accounts,
+                    &ticket,
+                    idx,
+                    &mint,
+                    &token_program,
+                )?;
 
-                let vault_token = vault_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
-                let recipient_token =
-                    recipient_token_account.ok_or(ErrorCode::TokenAccountNotFound)?;
+                // What's left after the protocol fee is net to the recipient; the vault covers
+                // any transfer fee.
+                let gross_amount = gross_amount_for_net(&mint_account.to_account_info(), received)?;
+                let gross_fee = gross_amount_for_net(&mint_account.to_account_info(), fee_in_kind)?;
 
                 require!(
-                    vault_token.amount >= withdrawal.amount,
+                    vault_token.amount
+                        >= gross_amount
+                            .checked_add(gross_fee)
+                            .ok_or(ErrorCode::Overflow)?,
                     ErrorCode::InsufficientFunds
                 );
 
-                let seeds = &[b"vault", vault.vault_seed.as_bytes(), &[vault.bump]];
+                let seeds = &[
+                    vault.seed_namespace(),
+                    vault.seed_parent(),
+                    vault.vault_seed.as_bytes(),
+                    &[vault.bump],
+                ];
                 let signer_seeds = &[&seeds[..]];
 
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: vault_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
                     to: recipient_token.to_account_info(),
                     authority: vault.to_account_info(),
                 };
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-                token::transfer(cpi_ctx, withdrawal.amount)?;
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
+                    gross_amount,
+                    mint_account.decimals,
+                )?;
+                transfer_spl_fee(
+                    ctx.remaining_accounts,
+                    vault,
+                    &vault_token,
+                    &mint_account,
+                    &ctx.accounts.fee_treasury.key(),
+                    &ctx.accounts.token_program,
+                    gross_fee,
+                )?;
 
-                msg!(
-                    "Withdraw SPL Token: request_id={}, mint={}, recipient={}, amount={}, valid_signers={}, metadata={:?}",
-                    ticket.request_id,
-                    mint,
-                    ticket.recipient,
-                    withdrawal.amount,
-                    validated_sigs.len(),
-                    metadata,
-                );
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: received,
+                        gross_amount: gross_amount + gross_fee,
+                        fee,
+                        admin: false,
+                        valid_signers,
+                        metadata: metadata.clone(),
+                    });
+                }
             }
         }
     }
 
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::Withdraw,
+        ticket.request_id,
+        ticket.recipient,
+        &withdrawals,
+    )?;
+
+    if !itemized {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::Withdraw,
+            vec![ticket.request_id],
+            &withdrawals,
+            vault.fee_bps,
+            valid_signers,
+            metadata,
+        )?;
+        emit_cpi!(summary);
+    }
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(ticket: WithdrawalTicket)]
 pub struct Withdraw<'info> {
     #[account(
         mut,
-        seeds = [b"vault", vault.vault_seed.as_bytes()],
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
         bump = vault.bump
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [b"treasury", vault.key().as_ref()],
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
         bump = vault.treasury_bump
     )]
     /// CHECK: Treasury PDA verified by seeds
     pub treasury: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, credited only while a fee is set
+    pub fee_treasury: UncheckedAccount<'info>,
+
     /// CHECK: Recipient verified against ticket
     #[account(mut)]
     pub recipient: AccountInfo<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = 8 + NonceAccount::INIT_SPACE,
-        seeds = [b"nonce", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Option<Account<'info, NonceAccount>>, // None under watermark replay
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WithdrawalReceipt::INIT_SPACE,
+        seeds = [b"withdrawal_receipt", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
         bump
     )]
-    pub nonce_account: Account<'info, NonceAccount>,
+    pub receipt: Option<Account<'info, WithdrawalReceipt>>, // None unless a receipt is wanted
 
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,370 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TransferChecked};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    batch_summary, check_duplicate_assets, check_identity_pass, check_memo, check_nft_amount,
+    check_recipient_allowed, check_token_accounts, check_usd_caps, check_withdrawal_limits,
+    consume_allowance, emit_memo, find_associated_token_account, find_mint,
+    find_recipient_token_account, gross_amount_for_net, is_recipient_blocked,
+    is_supported_ticket_version, is_ticket_active, notify_hook, pay_fee_in_fee_mint,
+    pay_relayer_rebate, posted_vaa_payload, sanitize_metadata, transfer_checked_with_hook,
+    transfer_lamports, transfer_spl_fee, withdrawable_lamports,
+};
+
+/// Withdraw against a Wormhole VAA instead of validator signatures. The VAA, posted (and so
+/// verified by the Wormhole guardians) through the core bridge, must come from the vault's
+/// `WormholeEmitter` and carry a Borsh `WithdrawalTicket` as its payload. The ticket then runs
+/// like one passed to `withdraw`, sharing its request id space, from the default treasury.
+pub fn withdraw_with_vaa<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawWithVaa<'info>>,
+    request_id: u64,
+    metadata: Option<String>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
+    let (vaa, ticket) =
+        posted_vaa_payload(&ctx.accounts.posted_vaa, &ctx.accounts.wormhole_emitter)?;
+    let ticket =
+        WithdrawalTicket::try_from_slice(&ticket).map_err(|_| ErrorCode::InvalidVaaPayload)?;
+    require!(
+        ticket.request_id == request_id,
+        ErrorCode::InvalidNonceAccount
+    );
+    require!(
+        !ticket.withdrawals.is_empty(),
+        ErrorCode::NoWithdrawalsProvided
+    );
+    require!(
+        ticket.balance_bps.is_none(),
+        ErrorCode::BalanceBpsNotSupported
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        ticket.treasury.is_none(),
+        ErrorCode::NamedTreasuryNotSupported
+    );
+    require!(
+        ticket.recipient == ctx.accounts.recipient.key(),
+        ErrorCode::InvalidRecipient
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    check_identity_pass(
+        ctx.remaining_accounts,
+        vault,
+        &ticket.recipient,
+        clock.unix_timestamp,
+    )?;
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+
+    check_duplicate_assets(&ticket.withdrawals)?;
+    check_withdrawal_limits(vault, &ticket.withdrawals)?;
+    check_usd_caps(
+        ctx.remaining_accounts,
+        vault,
+        &ticket.withdrawals,
+        clock.unix_timestamp,
+    )?;
+    check_memo(&ticket.memo)?;
+    check_token_accounts(&ticket)?;
+
+    emit_cpi!(VaaWithdrawalEvent {
+        vault: vault.key(),
+        sequence: vault.sequence,
+        request_id: ticket.request_id,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        vaa_sequence: vaa.sequence,
+    });
+
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy)
+    let nonce_account = &mut ctx.accounts.nonce_account;
+    nonce_account.consume(ticket.hash(&vault.domain_suffix), ticket.expiry)?;
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    // The payer funded this ticket's nonce account.
+    let rebate = pay_relayer_rebate(vault, &ctx.accounts.treasury, &ctx.accounts.payer, 1)?;
+    if rebate > 0 {
+        emit_cpi!(RelayerRebateEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            payer: ctx.accounts.payer.key(),
+            request_ids: vec![ticket.request_id],
+            amount: rebate,
+        });
+    }
+
+    // A blocked recipient's ticket is spent without paying out.
+    if is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)? {
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            recipient: ticket.recipient,
+        });
+        return Ok(());
+    }
+
+    // Memo goes before the transfers so Token-2022 required-memo accounts accept them.
+    if let Some(source_ref) = ticket.source_ref {
+        emit_cpi!(SourceRefEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            source_ref,
+        });
+    }
+    emit_memo(ctx.remaining_accounts, &ticket.memo)?;
+
+    let itemized = vault.log_verbosity == LogVerbosity::Itemized;
+    for (idx, withdrawal) in ticket.withdrawals.iter().enumerate() {
+        require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+        consume_allowance(
+            ctx.remaining_accounts,
+            &vault.key(),
+            &ticket.recipient,
+            &withdrawal.asset,
+            withdrawal.amount,
+        )?;
+
+        // The protocol fee comes out of the ticket amount, unless the payer covers it in the fee
+        // mint.
+        let fee = vault.withdrawal_fee(withdrawal.amount);
+        let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+            fee
+        } else {
+            pay_fee_in_fee_mint(
+                ctx.remaining_accounts,
+                vault,
+                &withdrawal.asset,
+                fee,
+                &ctx.accounts.payer,
+                &ctx.accounts.fee_treasury.key(),
+                &ctx.accounts.token_program,
+                clock.unix_timestamp,
+            )?;
+            0
+        };
+        let received = withdrawal.amount - fee_in_kind;
+
+        // Don't check whitelist since withdraw is always allowed.
+        match withdrawal.asset {
+            Asset::Sol => {
+                // Check sufficient balance
+                let source_treasury = ctx.accounts.treasury.to_account_info();
+                let available = withdrawable_lamports(&source_treasury, vault.treasury_reserve)?;
+
+                require!(available >= withdrawal.amount, ErrorCode::InsufficientFunds);
+
+                // Execute transfer
+                transfer_lamports(&source_treasury, &ctx.accounts.recipient, received)?;
+                transfer_lamports(&source_treasury, &ctx.accounts.fee_treasury, fee_in_kind)?;
+
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: received,
+                        gross_amount: received + fee_in_kind,
+                        fee,
+                        admin: false,
+                        valid_signers: 0,
+                        metadata: metadata.clone(),
+                    });
+                }
+            }
+            Asset::SplToken { mint } | Asset::Nft { mint } => {
+                let token_program = ctx.accounts.token_program.key();
+                let mint_account = find_mint(ctx.remaining_accounts, &mint)?;
+                check_nft_amount(&withdrawal.asset, withdrawal.amount, &mint_account)?;
+                let vault_token = find_associated_token_account(
+                    ctx.remaining_accounts,
+                    &vault.key(),
+                    &mint,
+                    &token_program,
+                )?;
+                let recipient_token = find_recipient_token_account(
+                    ctx.remaining_accounts,
+                    &ticket,
+                    idx,
+                    &mint,
+                    &token_program,
+                )?;
+
+                // What's left after the protocol fee is net to the recipient; the vault covers
+                // any transfer fee.
+                let gross_amount = gross_amount_for_net(&mint_account.to_account_info(), received)?;
+                let gross_fee = gross_amount_for_net(&mint_account.to_account_info(), fee_in_kind)?;
+
+                require!(
+                    vault_token.amount
+                        >= gross_amount
+                            .checked_add(gross_fee)
+                            .ok_or(ErrorCode::Overflow)?,
+                    ErrorCode::InsufficientFunds
+                );
+
+                let seeds = &[
+                    vault.seed_namespace(),
+                    vault.seed_parent(),
+                    vault.vault_seed.as_bytes(),
+                    &[vault.bump],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let cpi_accounts = TransferChecked {
+                    from: vault_token.to_account_info(),
+                    mint: mint_account.to_account_info(),
+                    to: recipient_token.to_account_info(),
+                    authority: vault.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+                transfer_checked_with_hook(
+                    cpi_ctx,
+                    ctx.remaining_accounts,
+                    gross_amount,
+                    mint_account.decimals,
+                )?;
+                transfer_spl_fee(
+                    ctx.remaining_accounts,
+                    vault,
+                    &vault_token,
+                    &mint_account,
+                    &ctx.accounts.fee_treasury.key(),
+                    &ctx.accounts.token_program,
+                    gross_fee,
+                )?;
+
+                if itemized {
+                    emit_cpi!(WithdrawEvent {
+                        vault: vault.key(),
+                        sequence: vault.sequence,
+                        request_id: ticket.request_id,
+                        recipient: ticket.recipient,
+                        asset: withdrawal.asset.clone(),
+                        amount: received,
+                        gross_amount: gross_amount + gross_fee,
+                        fee,
+                        admin: false,
+                        valid_signers: 0,
+                        metadata: metadata.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::WithdrawWithVaa,
+        ticket.request_id,
+        ticket.recipient,
+        &ticket.withdrawals,
+    )?;
+
+    if !itemized {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::WithdrawWithVaa,
+            vec![ticket.request_id],
+            &ticket.withdrawals,
+            vault.fee_bps,
+            0,
+            metadata,
+        )?;
+        emit_cpi!(summary);
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct WithdrawWithVaa<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, credited only while a fee is set
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"wormhole_emitter", vault.key().as_ref()],
+        bump = wormhole_emitter.bump
+    )]
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Owner, emitter and payload verified in `posted_vaa_payload`
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient verified against ticket
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
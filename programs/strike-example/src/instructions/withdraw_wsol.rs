@@ -0,0 +1,381 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{self, CloseAccount, Mint, SyncNative, Token, TokenAccount, Transfer};
+
+use super::accounts::*;
+use super::constant::*;
+use super::errors::ErrorCode;
+use super::events::*;
+use super::models::*;
+use super::util::{
+    authorize, batch_summary, check_duplicate_assets, check_identity_pass, check_memo,
+    check_recipient_allowed, check_usd_caps, check_withdrawal_limits, consume_allowance, emit_memo,
+    is_recipient_blocked, is_supported_ticket_version, is_ticket_active, notify_hook,
+    pay_fee_in_fee_mint, sanitize_metadata, transfer_lamports, withdrawable_lamports, Quorum,
+};
+
+/// Same as `withdraw`, but pays the SOL withdrawals out as wSOL to the recipient's
+/// native-mint ATA. Shares the `nonce` PDA with `withdraw`, so a ticket can be redeemed
+/// through only one of the two paths.
+pub fn withdraw_wsol<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawWsol<'info>>,
+    ticket: WithdrawalTicket,
+    signers_with_sigs: Vec<SignerWithSignature>,
+    metadata: Option<String>,
+) -> Result<()> {
+    ctx.accounts.vault.next_sequence();
+    let metadata = sanitize_metadata(metadata)?;
+    require!(
+        !ticket.withdrawals.is_empty(),
+        ErrorCode::NoWithdrawalsProvided
+    );
+    require!(
+        ticket.balance_bps.is_none(),
+        ErrorCode::BalanceBpsNotSupported
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(ticket.vault == vault.key(), ErrorCode::InvalidVault);
+    require!(
+        vault.accepts_network_id(ticket.network_id),
+        ErrorCode::InvalidNetwork
+    );
+    require!(
+        is_supported_ticket_version(ticket.version),
+        ErrorCode::UnsupportedTicketVersion
+    );
+    require!(
+        ticket.treasury.is_none(),
+        ErrorCode::NamedTreasuryNotSupported
+    );
+    // wSOL always lands in the recipient's canonical wSOL ATA.
+    require!(
+        ticket.token_accounts.is_none(),
+        ErrorCode::InvalidTokenAccounts
+    );
+    require!(
+        ticket.recipient == ctx.accounts.recipient.key(),
+        ErrorCode::InvalidRecipient
+    );
+    check_recipient_allowed(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    check_identity_pass(
+        ctx.remaining_accounts,
+        vault,
+        &ticket.recipient,
+        clock.unix_timestamp,
+    )?;
+    require!(
+        vault.is_ticket_unexpired(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketExpired
+    );
+    require!(
+        is_ticket_active(ticket.not_before, clock.unix_timestamp),
+        ErrorCode::TicketNotYetValid
+    );
+    require!(
+        vault.is_ticket_lifetime_allowed(ticket.expiry, clock.unix_timestamp),
+        ErrorCode::TicketLifetimeTooLong
+    );
+
+    check_duplicate_assets(&ticket.withdrawals)?;
+    check_withdrawal_limits(vault, &ticket.withdrawals)?;
+    check_usd_caps(
+        ctx.remaining_accounts,
+        vault,
+        &ticket.withdrawals,
+        clock.unix_timestamp,
+    )?;
+    check_memo(&ticket.memo)?;
+
+    // Validate the signatures.
+    let valid_signers = authorize(
+        vault,
+        &ticket,
+        &signers_with_sigs,
+        ctx.remaining_accounts,
+        &vault.signers,
+        Quorum::Weight(vault.m_threshold as u16),
+        clock.unix_timestamp,
+    )?;
+
+    // Consume the nonce BEFORE transfer (replay protection, prevents reentrancy). Under watermark
+    // replay the nonce account may be left out.
+    let ticket_hash = ticket.hash(&vault.domain_suffix);
+    match ctx.accounts.nonce_account.as_mut() {
+        Some(nonce_account) => nonce_account.consume(ticket_hash, ticket.expiry)?,
+        None => require!(vault.watermark_replay, ErrorCode::NonceAccountRequired),
+    }
+    vault.consume_request_id(ticket.request_id)?;
+    vault.record_processed(NONCE_SEED, ticket.request_id)?;
+
+    // A blocked recipient's ticket is spent without paying out. The temporary account is never
+    // funded, but it must still be closed for the next wSOL withdrawal.
+    let blocked = is_recipient_blocked(ctx.remaining_accounts, vault, &ticket.recipient)?;
+    if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+        let paid: &[AssetAmount] = if blocked { &[] } else { &ticket.withdrawals };
+        receipt.record(
+            vault.key(),
+            ticket.request_id,
+            ticket.recipient,
+            paid,
+            clock.slot,
+        )?;
+    }
+    if blocked {
+        close_temp_wsol(
+            &ctx.accounts.temp_wsol,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            vault,
+        )?;
+        emit_cpi!(BlockedRecipientEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            recipient: ticket.recipient,
+        });
+        return Ok(());
+    }
+
+    if let Some(source_ref) = ticket.source_ref {
+        emit_cpi!(SourceRefEvent {
+            vault: vault.key(),
+            sequence: vault.sequence,
+            request_id: ticket.request_id,
+            source_ref,
+        });
+    }
+    emit_memo(ctx.remaining_accounts, &ticket.memo)?;
+
+    // Only SOL can be paid out as wSOL. `total` leaves the treasury, `fees` of it go to the fee
+    // treasury; the payer covers any fee paid in the fee mint.
+    let mut total = 0u64;
+    let mut fees = 0u64;
+    for withdrawal in ticket.withdrawals.iter() {
+        require!(withdrawal.amount > 0, ErrorCode::InvalidAmount);
+        require!(withdrawal.asset == Asset::Sol, ErrorCode::UnsupportedAsset);
+        consume_allowance(
+            ctx.remaining_accounts,
+            &vault.key(),
+            &ticket.recipient,
+            &withdrawal.asset,
+            withdrawal.amount,
+        )?;
+
+        total = total
+            .checked_add(withdrawal.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        let fee = vault.withdrawal_fee(withdrawal.amount);
+        if vault.pays_fee_in_kind(&withdrawal.asset) {
+            fees += fee;
+        } else {
+            pay_fee_in_fee_mint(
+                ctx.remaining_accounts,
+                vault,
+                &withdrawal.asset,
+                fee,
+                &ctx.accounts.payer,
+                &ctx.accounts.fee_treasury.key(),
+                &ctx.accounts.token_program,
+                clock.unix_timestamp,
+            )?;
+        }
+    }
+    let wrapped = total - fees;
+
+    // Check sufficient balance
+    let available = withdrawable_lamports(
+        &ctx.accounts.treasury.to_account_info(),
+        vault.treasury_reserve,
+    )?;
+
+    require!(available >= total, ErrorCode::InsufficientFunds);
+
+    // Wrap: move lamports into the temporary wSOL account and sync its token amount.
+    let temp_wsol_info = ctx.accounts.temp_wsol.to_account_info();
+    transfer_lamports(&ctx.accounts.treasury, &temp_wsol_info, wrapped)?;
+    transfer_lamports(&ctx.accounts.treasury, &ctx.accounts.fee_treasury, fees)?;
+
+    let cpi_accounts = SyncNative {
+        account: temp_wsol_info.clone(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::sync_native(cpi_ctx)?;
+
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.temp_wsol.to_account_info(),
+        to: ctx.accounts.recipient_wsol.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, wrapped)?;
+
+    // Close the emptied temporary account and return its rent to the payer.
+    close_temp_wsol(
+        &ctx.accounts.temp_wsol,
+        &ctx.accounts.payer,
+        &ctx.accounts.token_program,
+        vault,
+    )?;
+
+    notify_hook(
+        ctx.remaining_accounts,
+        vault,
+        BatchKind::WithdrawWsol,
+        ticket.request_id,
+        ticket.recipient,
+        &ticket.withdrawals,
+    )?;
+
+    if vault.log_verbosity == LogVerbosity::Itemized {
+        for withdrawal in ticket.withdrawals.iter() {
+            let fee = vault.withdrawal_fee(withdrawal.amount);
+            let fee_in_kind = if vault.pays_fee_in_kind(&withdrawal.asset) {
+                fee
+            } else {
+                0
+            };
+            emit_cpi!(WithdrawEvent {
+                vault: vault.key(),
+                sequence: vault.sequence,
+                request_id: ticket.request_id,
+                recipient: ticket.recipient,
+                asset: withdrawal.asset.clone(),
+                amount: withdrawal.amount - fee_in_kind,
+                gross_amount: withdrawal.amount,
+                fee,
+                admin: false,
+                valid_signers,
+                metadata: metadata.clone(),
+            });
+        }
+    } else {
+        let summary = batch_summary(
+            vault.key(),
+            vault.sequence,
+            BatchKind::WithdrawWsol,
+            vec![ticket.request_id],
+            &ticket.withdrawals,
+            vault.fee_bps,
+            valid_signers,
+            metadata,
+        )?;
+        emit_cpi!(summary);
+    }
+
+    Ok(())
+}
+
+/// Close the temporary wSOL account and return its rent to the payer.
+fn close_temp_wsol<'info>(
+    temp_wsol: &Account<'info, TokenAccount>,
+    payer: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, Vault>,
+) -> Result<()> {
+    let seeds = &[
+        vault.seed_namespace(),
+        vault.seed_parent(),
+        vault.vault_seed.as_bytes(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = CloseAccount {
+        account: temp_wsol.to_account_info(),
+        destination: payer.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ticket: WithdrawalTicket)]
+pub struct WithdrawWsol<'info> {
+    #[account(
+        mut,
+        seeds = [vault.seed_namespace(), vault.seed_parent(), vault.vault_seed.as_bytes()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault.key().as_ref()],
+        bump = vault.treasury_bump
+    )]
+    /// CHECK: Treasury PDA verified by seeds
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_treasury", vault.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Fee treasury PDA verified by seeds, credited only while a fee is set
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient verified against ticket
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = native_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_wsol: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"wsol", vault.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = vault
+    )]
+    pub temp_wsol: Account<'info, TokenAccount>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NonceAccount::INIT_SPACE,
+        seeds = [NONCE_SEED, vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub nonce_account: Option<Account<'info, NonceAccount>>, // None under watermark replay
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WithdrawalReceipt::INIT_SPACE,
+        seeds = [b"withdrawal_receipt", vault.key().as_ref(), &ticket.request_id.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Option<Account<'info, WithdrawalReceipt>>, // None unless a receipt is wanted
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
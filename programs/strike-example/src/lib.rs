@@ -9,15 +9,71 @@ pub mod strike_example {
     use super::*;
     pub use instructions::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         ctx: Context<Initialize>,
         vault_seed: String,
         network_id: u64,
         m_threshold: u8,
         admin_threshold: u8,
-        signers: Vec<[u8; 20]>, // Ethereum addresses (20 bytes)
+        signers: Vec<WeightedSigner>,
+        admin_signers: Vec<WeightedSigner>,
+        domain_suffix: String,
+        auth_scheme: AuthScheme,
+        genesis_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        instructions::initialize(ctx, vault_seed, network_id, m_threshold, admin_threshold, signers)
+        instructions::initialize(
+            ctx,
+            vault_seed,
+            network_id,
+            m_threshold,
+            admin_threshold,
+            signers,
+            admin_signers,
+            domain_suffix,
+            auth_scheme,
+            genesis_hash,
+        )
+    }
+
+    pub fn migrate<'info>(ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>) -> Result<()> {
+        instructions::migrate(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_full<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeFull<'info>>,
+        vault_seed: String,
+        network_id: u64,
+        m_threshold: u8,
+        admin_threshold: u8,
+        signers: Vec<WeightedSigner>,
+        admin_signers: Vec<WeightedSigner>,
+        domain_suffix: String,
+        auth_scheme: AuthScheme,
+        genesis_hash: Option<[u8; 32]>,
+        assets: Vec<Asset>,
+    ) -> Result<()> {
+        instructions::initialize_full(
+            ctx,
+            vault_seed,
+            network_id,
+            m_threshold,
+            admin_threshold,
+            signers,
+            admin_signers,
+            domain_suffix,
+            auth_scheme,
+            genesis_hash,
+            assets,
+        )
+    }
+
+    pub fn create_vault_token_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateVaultTokenAccounts<'info>>,
+        mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::create_vault_token_accounts(ctx, mints)
     }
 
     pub fn deposit<'info>(
@@ -29,6 +85,15 @@ pub mod strike_example {
         instructions::deposit(ctx, deposits, request_id, metadata)
     }
 
+    pub fn deposit_wsol(
+        ctx: Context<DepositWsol>,
+        amount: u64,
+        request_id: u64,
+        metadata: Option<String>,
+    ) -> Result<()> {
+        instructions::deposit_wsol(ctx, amount, request_id, metadata)
+    }
+
     pub fn admin_deposit<'info>(
         ctx: Context<'_, '_, 'info, 'info, AdminDeposit<'info>>,
         ticket: AdminDepositTicket,
@@ -46,13 +111,97 @@ pub mod strike_example {
         instructions::withdraw(ctx, ticket, signers_with_sigs, metadata)
     }
 
+    pub fn withdraw_with_vaa<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawWithVaa<'info>>,
+        request_id: u64,
+        metadata: Option<String>,
+    ) -> Result<()> {
+        instructions::withdraw_with_vaa(ctx, request_id, metadata)
+    }
+
+    pub fn withdraw_wsol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawWsol<'info>>,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        metadata: Option<String>,
+    ) -> Result<()> {
+        instructions::withdraw_wsol(ctx, ticket, signers_with_sigs, metadata)
+    }
+
     pub fn bulk_withdraw<'info>(
         ctx: Context<'_, '_, 'info, 'info, BulkWithdraw<'info>>,
         bulk_ticket: BulkWithdrawalTicket,
         signers_with_sigs: Vec<SignerWithSignature>,
+        account_indexes: Vec<BulkAccountIndexes>,
         metadata: Option<String>,
     ) -> Result<()> {
-        instructions::bulk_withdraw(ctx, bulk_ticket, signers_with_sigs, metadata)
+        instructions::bulk_withdraw(
+            ctx,
+            bulk_ticket,
+            signers_with_sigs,
+            account_indexes,
+            metadata,
+        )
+    }
+
+    pub fn publish_claim_root(
+        ctx: Context<PublishClaimRoot>,
+        ticket: ClaimRootTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::publish_claim_root(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Claim<'info>>,
+        leaf: ClaimLeaf,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim(ctx, leaf, proof)
+    }
+
+    pub fn claim_credited_deposit(
+        ctx: Context<ClaimCreditedDeposit>,
+        ticket: CreditTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::claim_credited_deposit(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn create_stream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateStream<'info>>,
+        ticket: CreateStreamTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::create_stream(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn claim_stream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimStream<'info>>,
+    ) -> Result<()> {
+        instructions::claim_stream(ctx)
+    }
+
+    pub fn create_schedule(
+        ctx: Context<CreateSchedule>,
+        ticket: CreateScheduleTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::create_schedule(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn execute_schedule<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSchedule<'info>>,
+    ) -> Result<()> {
+        instructions::execute_schedule(ctx)
+    }
+
+    pub fn close_expired_nonce(
+        ctx: Context<CloseExpiredNonce>,
+        request_id: u64,
+        admin: bool,
+    ) -> Result<()> {
+        instructions::close_expired_nonce(ctx, request_id, admin)
     }
 
     pub fn admin_withdraw<'info>(
@@ -63,14 +212,89 @@ pub mod strike_example {
         instructions::admin_withdraw(ctx, ticket, signers_with_sigs)
     }
 
-    pub fn add_asset(
-        ctx: Context<AddAsset>,
+    #[cfg(feature = "authorized-cpi")]
+    pub fn execute_authorized_cpi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteAuthorizedCpi<'info>>,
+        ticket: AuthorizedCpiTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_authorized_cpi(ctx, ticket, signers_with_sigs, data)
+    }
+
+    pub fn execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Execute<'info>>,
+        ticket: ExecuteTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::execute(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn add_strategy(
+        ctx: Context<AddStrategy>,
+        ticket: AddStrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::add_strategy(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn remove_strategy(
+        ctx: Context<RemoveStrategy>,
+        ticket: RemoveStrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::remove_strategy(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn add_cranker(
+        ctx: Context<AddCranker>,
+        ticket: AddCrankerTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::add_cranker(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn remove_cranker(
+        ctx: Context<RemoveCranker>,
+        ticket: RemoveCrankerTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::remove_cranker(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_crank_incentive(
+        ctx: Context<SetCrankIncentive>,
+        ticket: SetCrankIncentiveTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_crank_incentive(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn execute_strategy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteStrategy<'info>>,
+        ticket: StrategyTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_strategy(ctx, ticket, signers_with_sigs, data)
+    }
+
+    pub fn add_asset<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddAsset<'info>>,
         ticket: AddAssetTicket,
         signers_with_sigs: Vec<SignerWithSignature>,
     ) -> Result<()> {
         instructions::add_asset(ctx, ticket, signers_with_sigs)
     }
 
+    pub fn update_asset(
+        ctx: Context<UpdateAsset>,
+        ticket: UpdateAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::update_asset(ctx, ticket, signers_with_sigs)
+    }
+
     pub fn remove_asset(
         ctx: Context<RemoveAsset>,
         ticket: RemoveAssetTicket,
@@ -79,6 +303,70 @@ pub mod strike_example {
         instructions::remove_asset(ctx, ticket, signers_with_sigs)
     }
 
+    pub fn set_min_withdrawal(
+        ctx: Context<SetMinWithdrawal>,
+        ticket: SetMinWithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_min_withdrawal(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_usd_cap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetUsdCap<'info>>,
+        ticket: SetUsdCapTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_usd_cap(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_max_ticket_usd(
+        ctx: Context<SetMaxTicketUsd>,
+        ticket: SetMaxTicketUsdTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_max_ticket_usd(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn add_recipient(
+        ctx: Context<AddRecipient>,
+        ticket: AddRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::add_recipient(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn remove_recipient(
+        ctx: Context<RemoveRecipient>,
+        ticket: RemoveRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::remove_recipient(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_recipient_allowlist(
+        ctx: Context<SetRecipientAllowlist>,
+        ticket: SetRecipientAllowlistTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_recipient_allowlist(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn block_recipient(
+        ctx: Context<BlockRecipient>,
+        ticket: BlockRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::block_recipient(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn unblock_recipient(
+        ctx: Context<UnblockRecipient>,
+        ticket: UnblockRecipientTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::unblock_recipient(ctx, ticket, signers_with_sigs)
+    }
+
     pub fn rotate_validators(
         ctx: Context<RotateValidator>,
         ticket: RotateValidatorTicket,
@@ -86,4 +374,336 @@ pub mod strike_example {
     ) -> Result<()> {
         instructions::rotate_validators(ctx, ticket, signers_with_sigs)
     }
+
+    pub fn rotate_admin_signers(
+        ctx: Context<RotateAdminSigners>,
+        ticket: RotateAdminSignersTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::rotate_admin_signers(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_log_verbosity(
+        ctx: Context<SetLogVerbosity>,
+        ticket: SetLogVerbosityTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_log_verbosity(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_fee(
+        ctx: Context<SetFee>,
+        ticket: SetFeeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_fee(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_relayer_rebate(
+        ctx: Context<SetRelayerRebate>,
+        ticket: SetRelayerRebateTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_relayer_rebate(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_max_ticket_lifetime(
+        ctx: Context<SetMaxTicketLifetime>,
+        ticket: SetMaxTicketLifetimeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_max_ticket_lifetime(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_nonce_tree(
+        ctx: Context<SetNonceTree>,
+        ticket: SetNonceTreeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_nonce_tree(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_expiry_grace(
+        ctx: Context<SetExpiryGrace>,
+        ticket: SetExpiryGraceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_expiry_grace(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_extra_network_ids(
+        ctx: Context<SetExtraNetworkIds>,
+        ticket: SetExtraNetworkIdsTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_extra_network_ids(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        ticket: SetGuardianTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_guardian(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_auth_scheme(
+        ctx: Context<SetAuthScheme>,
+        ticket: SetAuthSchemeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_auth_scheme(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_hook_program(
+        ctx: Context<SetHookProgram>,
+        ticket: SetHookProgramTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_hook_program(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_deposit_registry(
+        ctx: Context<SetDepositRegistry>,
+        ticket: SetDepositRegistryTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_deposit_registry(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_identity_network(
+        ctx: Context<SetIdentityNetwork>,
+        ticket: SetIdentityNetworkTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_identity_network(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_strict_signatures(
+        ctx: Context<SetStrictSignatures>,
+        ticket: SetStrictSignaturesTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_strict_signatures(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_treasury_reserve(
+        ctx: Context<SetTreasuryReserve>,
+        ticket: SetTreasuryReserveTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_treasury_reserve(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_replay_mode(
+        ctx: Context<SetReplayMode>,
+        ticket: SetReplayModeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_replay_mode(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn propose_signer_set(
+        ctx: Context<ProposeSignerSet>,
+        ticket: ProposeSignerSetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::propose_signer_set(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn upload_signers(ctx: Context<UploadSigners>, signers: Vec<WeightedSigner>) -> Result<()> {
+        instructions::upload_signers(ctx, signers)
+    }
+
+    pub fn activate_signer_set(ctx: Context<ActivateSignerSet>) -> Result<()> {
+        instructions::activate_signer_set(ctx)
+    }
+
+    pub fn delegate_stake(
+        ctx: Context<DelegateStake>,
+        ticket: DelegateStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::delegate_stake(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn deactivate_stake(
+        ctx: Context<DeactivateStake>,
+        ticket: DeactivateStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::deactivate_stake(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn withdraw_stake(
+        ctx: Context<WithdrawStake>,
+        ticket: WithdrawStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::withdraw_stake(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn liquid_stake(
+        ctx: Context<LiquidStake>,
+        ticket: LiquidStakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::liquid_stake(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn liquid_unstake(
+        ctx: Context<LiquidUnstake>,
+        ticket: LiquidUnstakeTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::liquid_unstake(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+        ticket: RebalanceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::rebalance(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+        ticket: SwapTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::swap(ctx, ticket, signers_with_sigs, data)
+    }
+
+    pub fn veto_withdrawal(ctx: Context<VetoWithdrawal>, request_id: u64) -> Result<()> {
+        instructions::veto_withdrawal(ctx, request_id)
+    }
+
+    pub fn validate_ticket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ValidateTicket<'info>>,
+        ticket: WithdrawalTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<TicketValidation> {
+        instructions::validate_ticket(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn compute_ticket_hash(
+        ctx: Context<ComputeTicketHash>,
+        ticket: AnyTicket,
+        domain_suffix: String,
+    ) -> Result<[u8; 32]> {
+        instructions::compute_ticket_hash(ctx, ticket, domain_suffix)
+    }
+
+    pub fn get_vault_info(ctx: Context<GetVaultInfo>) -> Result<VaultInfo> {
+        instructions::get_vault_info(ctx)
+    }
+
+    pub fn get_nonce_status(
+        ctx: Context<GetNonceStatus>,
+        request_id: u64,
+        admin: bool,
+    ) -> Result<NonceStatus> {
+        instructions::get_nonce_status(ctx, request_id, admin)
+    }
+
+    pub fn get_asset_config(
+        ctx: Context<GetAssetConfig>,
+        asset: Asset,
+    ) -> Result<Option<AssetConfig>> {
+        instructions::get_asset_config(ctx, asset)
+    }
+
+    pub fn collect_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectFees<'info>>,
+        ticket: CollectFeesTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::collect_fees(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn sweep_dust<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepDust<'info>>,
+        ticket: SweepDustTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::sweep_dust(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn close_vault_token_account(
+        ctx: Context<CloseVaultTokenAccount>,
+        ticket: CloseVaultTokenAccountTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::close_vault_token_account(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn sweep_asset<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepAsset<'info>>,
+        ticket: SweepAssetTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::sweep_asset(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn accept_upgrade_authority(ctx: Context<AcceptUpgradeAuthority>) -> Result<()> {
+        instructions::accept_upgrade_authority(ctx)
+    }
+
+    pub fn upgrade_program(
+        ctx: Context<UpgradeProgram>,
+        ticket: UpgradeProgramTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::upgrade_program(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_allowance(
+        ctx: Context<SetAllowance>,
+        ticket: SetAllowanceTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_allowance(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn create_receipt_mint<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateReceiptMint<'info>>,
+        ticket: CreateReceiptMintTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::create_receipt_mint(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        ticket: SetTreasuryTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_treasury(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn set_wormhole_emitter(
+        ctx: Context<SetWormholeEmitter>,
+        ticket: SetWormholeEmitterTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::set_wormhole_emitter(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn create_sub_vault(
+        ctx: Context<CreateSubVault>,
+        ticket: CreateSubVaultTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::create_sub_vault(ctx, ticket, signers_with_sigs)
+    }
+
+    pub fn reclaim_sub_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReclaimSubVault<'info>>,
+        ticket: ReclaimSubVaultTicket,
+        signers_with_sigs: Vec<SignerWithSignature>,
+    ) -> Result<()> {
+        instructions::reclaim_sub_vault(ctx, ticket, signers_with_sigs)
+    }
 }
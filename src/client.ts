@@ -14,12 +14,25 @@ import {
   Transaction,
   LAMPORTS_PER_SOL,
   SystemProgram,
+  Ed25519Program,
+  Secp256k1Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_STAKE_HISTORY_PUBKEY,
+  StakeProgram,
+  TransactionInstruction,
 } from "@solana/web3.js";
 import {
   getOrCreateAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
   mintTo,
   getAccount,
+  createTransferCheckedWithTransferHookInstruction,
+  NATIVE_MINT,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { keccak256 } from "ethereum-cryptography/keccak";
 import { secp256k1 } from "ethereum-cryptography/secp256k1";
@@ -33,13 +46,111 @@ export const ANCHOR_WALLET = ENV.ANCHOR_WALLET || "~/.config/solana/id.json";
 export const ANCHOR_PROVIDER_URL = ENV.ANCHOR_PROVIDER_URL || "https://api.devnet.solana.com";
 export const PROGRAM_ID = ENV.PROGRAM_ID;
 
-const DOMAIN_SEPARATOR_WITHDRAWAL = "strike-protocol-v1-Withdrawal";
-const DOMAIN_SEPARATOR_BULK_WITHDRAWAL = "strike-protocol-v1-BulkWithdrawal"
-const DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL = "strike-protocol-v1-AdminWithdrawal";
-const DOMAIN_SEPARATOR_ADMIN_DEPOSIT = "strike-protocol-v1-AdminDeposit";
-const DOMAIN_SEPARATOR_ADD_ASSET = "strike-protocol-v1-AddAsset";
-const DOMAIN_SEPARATOR_REMOVE_ASSET = "strike-protocol-v1-RemoveAsset";
-const DOMAIN_SEPARATOR_ROTATE = "strike-protocol-v1-rotate";
+// Ticket hashing scheme version; must be within the program's supported range
+export const TICKET_VERSION = 2;
+
+// Stake program config account passed to delegate_stake
+const STAKE_CONFIG_ID = new PublicKey("StakeConfig11111111111111111111111111111111");
+// SPL stake pool program used by liquid_stake / liquid_unstake
+export const SPL_STAKE_POOL_PROGRAM_ID = new PublicKey("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgN2AerxyrnTcA");
+// Jupiter aggregator v6, the only program swap routes through
+export const JUPITER_PROGRAM_ID = new PublicKey("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+export const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+// SPL account compression and noop programs, owner and log wrapper of the vault's nonce tree
+export const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+export const SPL_NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+// Upgradeable BPF loader, owner of the program data account upgrade_program rewrites
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/**
+ * Value of a `#[constant]` the program exports in its IDL: a string for `&str` constants, an
+ * array for `&[u8]` ones and a number for integers.
+ */
+function idlConstant(name: string): any {
+  const constant = idl.constants.find((c) => c.name === name);
+  if (!constant) {
+    throw new Error(`Constant ${name} is missing from the program IDL`);
+  }
+  return JSON.parse(constant.value);
+}
+
+// Seeds, limits and ticket domain separators the program exports
+const VAULT_SEED = Buffer.from(idlConstant("VAULT_SEED"));
+const SUB_VAULT_SEED = Buffer.from(idlConstant("SUB_VAULT_SEED"));
+const TREASURY_SEED = Buffer.from(idlConstant("TREASURY_SEED"));
+const NONCE_SEED = Buffer.from(idlConstant("NONCE_SEED"));
+const ADMIN_NONCE_SEED = Buffer.from(idlConstant("ADMIN_NONCE_SEED"));
+export const MAX_SIGNERS: number = idlConstant("MAX_SIGNERS");
+export const MAX_ASSETS: number = idlConstant("MAX_ASSETS");
+export const MAX_SIGNER_SET_LEN: number = idlConstant("MAX_SIGNER_SET_LEN");
+
+const DOMAIN_SEPARATOR_WITHDRAWAL: string = idlConstant("DOMAIN_SEPARATOR_WITHDRAWAL");
+const DOMAIN_SEPARATOR_TREASURY_WITHDRAWAL: string = idlConstant("DOMAIN_SEPARATOR_TREASURY_WITHDRAWAL");
+const DOMAIN_SEPARATOR_BULK_WITHDRAWAL: string = idlConstant("DOMAIN_SEPARATOR_BULK_WITHDRAWAL");
+const DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL: string = idlConstant("DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL");
+const DOMAIN_SEPARATOR_ADMIN_DEPOSIT: string = idlConstant("DOMAIN_SEPARATOR_ADMIN_DEPOSIT");
+const DOMAIN_SEPARATOR_ADD_ASSET: string = idlConstant("DOMAIN_SEPARATOR_ADD_ASSET");
+const DOMAIN_SEPARATOR_UPDATE_ASSET: string = idlConstant("DOMAIN_SEPARATOR_UPDATE_ASSET");
+const DOMAIN_SEPARATOR_REMOVE_ASSET: string = idlConstant("DOMAIN_SEPARATOR_REMOVE_ASSET");
+const DOMAIN_SEPARATOR_ROTATE: string = idlConstant("DOMAIN_SEPARATOR_ROTATE");
+const DOMAIN_SEPARATOR_ROTATE_ADMIN_SIGNERS: string = idlConstant("DOMAIN_SEPARATOR_ROTATE_ADMIN_SIGNERS");
+const DOMAIN_SEPARATOR_SET_LOG_VERBOSITY: string = idlConstant("DOMAIN_SEPARATOR_SET_LOG_VERBOSITY");
+const DOMAIN_SEPARATOR_SET_ALLOWANCE: string = idlConstant("DOMAIN_SEPARATOR_SET_ALLOWANCE");
+const DOMAIN_SEPARATOR_SET_MIN_WITHDRAWAL: string = idlConstant("DOMAIN_SEPARATOR_SET_MIN_WITHDRAWAL");
+const DOMAIN_SEPARATOR_SET_USD_CAP: string = idlConstant("DOMAIN_SEPARATOR_SET_USD_CAP");
+const DOMAIN_SEPARATOR_SET_MAX_TICKET_USD: string = idlConstant("DOMAIN_SEPARATOR_SET_MAX_TICKET_USD");
+const DOMAIN_SEPARATOR_SET_TREASURY: string = idlConstant("DOMAIN_SEPARATOR_SET_TREASURY");
+const DOMAIN_SEPARATOR_SET_WORMHOLE_EMITTER: string = idlConstant("DOMAIN_SEPARATOR_SET_WORMHOLE_EMITTER");
+const DOMAIN_SEPARATOR_CREATE_SUB_VAULT: string = idlConstant("DOMAIN_SEPARATOR_CREATE_SUB_VAULT");
+const DOMAIN_SEPARATOR_RECLAIM_SUB_VAULT: string = idlConstant("DOMAIN_SEPARATOR_RECLAIM_SUB_VAULT");
+const DOMAIN_SEPARATOR_CREATE_RECEIPT_MINT: string = idlConstant("DOMAIN_SEPARATOR_CREATE_RECEIPT_MINT");
+const DOMAIN_SEPARATOR_CLAIM_ROOT: string = idlConstant("DOMAIN_SEPARATOR_CLAIM_ROOT");
+const DOMAIN_SEPARATOR_CREDIT_DEPOSIT: string = idlConstant("DOMAIN_SEPARATOR_CREDIT_DEPOSIT");
+const DOMAIN_SEPARATOR_CREATE_STREAM: string = idlConstant("DOMAIN_SEPARATOR_CREATE_STREAM");
+const DOMAIN_SEPARATOR_CREATE_SCHEDULE: string = idlConstant("DOMAIN_SEPARATOR_CREATE_SCHEDULE");
+const DOMAIN_SEPARATOR_ADD_RECIPIENT: string = idlConstant("DOMAIN_SEPARATOR_ADD_RECIPIENT");
+const DOMAIN_SEPARATOR_REMOVE_RECIPIENT: string = idlConstant("DOMAIN_SEPARATOR_REMOVE_RECIPIENT");
+const DOMAIN_SEPARATOR_SET_RECIPIENT_ALLOWLIST: string = idlConstant("DOMAIN_SEPARATOR_SET_RECIPIENT_ALLOWLIST");
+const DOMAIN_SEPARATOR_BLOCK_RECIPIENT: string = idlConstant("DOMAIN_SEPARATOR_BLOCK_RECIPIENT");
+const DOMAIN_SEPARATOR_UNBLOCK_RECIPIENT: string = idlConstant("DOMAIN_SEPARATOR_UNBLOCK_RECIPIENT");
+const DOMAIN_SEPARATOR_SET_FEE: string = idlConstant("DOMAIN_SEPARATOR_SET_FEE");
+const DOMAIN_SEPARATOR_SET_RELAYER_REBATE: string = idlConstant("DOMAIN_SEPARATOR_SET_RELAYER_REBATE");
+const DOMAIN_SEPARATOR_SET_MAX_TICKET_LIFETIME: string = idlConstant("DOMAIN_SEPARATOR_SET_MAX_TICKET_LIFETIME");
+const DOMAIN_SEPARATOR_SET_EXPIRY_GRACE: string = idlConstant("DOMAIN_SEPARATOR_SET_EXPIRY_GRACE");
+const DOMAIN_SEPARATOR_SET_NONCE_TREE: string = idlConstant("DOMAIN_SEPARATOR_SET_NONCE_TREE");
+const DOMAIN_SEPARATOR_SET_EXTRA_NETWORK_IDS: string = idlConstant("DOMAIN_SEPARATOR_SET_EXTRA_NETWORK_IDS");
+const DOMAIN_SEPARATOR_SET_AUTH_SCHEME: string = idlConstant("DOMAIN_SEPARATOR_SET_AUTH_SCHEME");
+const DOMAIN_SEPARATOR_SET_HOOK_PROGRAM: string = idlConstant("DOMAIN_SEPARATOR_SET_HOOK_PROGRAM");
+const DOMAIN_SEPARATOR_SET_DEPOSIT_REGISTRY: string = idlConstant("DOMAIN_SEPARATOR_SET_DEPOSIT_REGISTRY");
+const DOMAIN_SEPARATOR_SET_IDENTITY_NETWORK: string = idlConstant("DOMAIN_SEPARATOR_SET_IDENTITY_NETWORK");
+const DOMAIN_SEPARATOR_SET_STRICT_SIGNATURES: string = idlConstant("DOMAIN_SEPARATOR_SET_STRICT_SIGNATURES");
+const DOMAIN_SEPARATOR_PROPOSE_SIGNER_SET: string = idlConstant("DOMAIN_SEPARATOR_PROPOSE_SIGNER_SET");
+const DOMAIN_SEPARATOR_SET_TREASURY_RESERVE: string = idlConstant("DOMAIN_SEPARATOR_SET_TREASURY_RESERVE");
+const DOMAIN_SEPARATOR_SET_REPLAY_MODE: string = idlConstant("DOMAIN_SEPARATOR_SET_REPLAY_MODE");
+const DOMAIN_SEPARATOR_SET_GUARDIAN: string = idlConstant("DOMAIN_SEPARATOR_SET_GUARDIAN");
+const DOMAIN_SEPARATOR_DELEGATE_STAKE: string = idlConstant("DOMAIN_SEPARATOR_DELEGATE_STAKE");
+const DOMAIN_SEPARATOR_DEACTIVATE_STAKE: string = idlConstant("DOMAIN_SEPARATOR_DEACTIVATE_STAKE");
+const DOMAIN_SEPARATOR_WITHDRAW_STAKE: string = idlConstant("DOMAIN_SEPARATOR_WITHDRAW_STAKE");
+const DOMAIN_SEPARATOR_LIQUID_STAKE: string = idlConstant("DOMAIN_SEPARATOR_LIQUID_STAKE");
+const DOMAIN_SEPARATOR_LIQUID_UNSTAKE: string = idlConstant("DOMAIN_SEPARATOR_LIQUID_UNSTAKE");
+const DOMAIN_SEPARATOR_REBALANCE: string = idlConstant("DOMAIN_SEPARATOR_REBALANCE");
+const DOMAIN_SEPARATOR_SWAP: string = idlConstant("DOMAIN_SEPARATOR_SWAP");
+const DOMAIN_SEPARATOR_COLLECT_FEES: string = idlConstant("DOMAIN_SEPARATOR_COLLECT_FEES");
+const DOMAIN_SEPARATOR_SWEEP_DUST: string = idlConstant("DOMAIN_SEPARATOR_SWEEP_DUST");
+const DOMAIN_SEPARATOR_SWEEP_ASSET: string = idlConstant("DOMAIN_SEPARATOR_SWEEP_ASSET");
+const DOMAIN_SEPARATOR_UPGRADE_PROGRAM: string = idlConstant("DOMAIN_SEPARATOR_UPGRADE_PROGRAM");
+const DOMAIN_SEPARATOR_CLOSE_VAULT_TOKEN_ACCOUNT: string = idlConstant("DOMAIN_SEPARATOR_CLOSE_VAULT_TOKEN_ACCOUNT");
+const DOMAIN_SEPARATOR_AUTHORIZED_CPI: string = idlConstant("DOMAIN_SEPARATOR_AUTHORIZED_CPI");
+const DOMAIN_SEPARATOR_EXECUTE: string = idlConstant("DOMAIN_SEPARATOR_EXECUTE");
+const DOMAIN_SEPARATOR_ADD_STRATEGY: string = idlConstant("DOMAIN_SEPARATOR_ADD_STRATEGY");
+const DOMAIN_SEPARATOR_REMOVE_STRATEGY: string = idlConstant("DOMAIN_SEPARATOR_REMOVE_STRATEGY");
+const DOMAIN_SEPARATOR_EXECUTE_STRATEGY: string = idlConstant("DOMAIN_SEPARATOR_EXECUTE_STRATEGY");
+const DOMAIN_SEPARATOR_ADD_CRANKER: string = idlConstant("DOMAIN_SEPARATOR_ADD_CRANKER");
+const DOMAIN_SEPARATOR_REMOVE_CRANKER: string = idlConstant("DOMAIN_SEPARATOR_REMOVE_CRANKER");
+const DOMAIN_SEPARATOR_SET_CRANK_INCENTIVE: string = idlConstant("DOMAIN_SEPARATOR_SET_CRANK_INCENTIVE");
+
+// Instruction tag Anchor prefixes to `emit_cpi!` self-invocations (EVENT_IX_TAG_LE).
+const EVENT_IX_TAG = Buffer.from([0x1d, 0x9a, 0xcb, 0x51, 0x2e, 0xa5, 0x45, 0xe4]);
 
 // Network IDs matching the contract
 export enum NetworkId {
@@ -51,7 +162,19 @@ export enum NetworkId {
 // Asset types
 export type Asset = 
   | { sol: {} }
-  | { splToken: { mint: PublicKey } };
+  | { splToken: { mint: PublicKey } }
+  | { nft: { mint: PublicKey } };
+
+// What approves a vault's tickets
+export type AuthScheme =
+  | { secp256k1: {} }
+  | { ed25519: {} }
+  | { attestor: { program: PublicKey } }
+  | { secp256r1: {} };
+
+export type PriceOracle =
+  | { pyth: { feedId: number[] } }
+  | { chainlink: { feed: PublicKey } };
 
 export interface AssetAmount {
   asset: Asset;
@@ -65,1144 +188,9558 @@ export function printEnv() {
 }
 
 export interface WithdrawalTicket {
+  version: number;
   requestId: BN;
   vault: PublicKey;
   recipient: PublicKey;
   withdrawals: AssetAmount[];
   expiry: BN;
   networkId: BN;
+  notBefore?: BN | null; // not executable before this unix timestamp
+  memo?: string | null; // emitted via SPL Memo; not part of admin withdrawals
+  treasury?: string | null; // named treasury to draw SOL from; `withdraw` only
+  tokenAccounts?: PublicKey[] | null; // account each withdrawal line pays into; not `withdrawWsol`
+  sourceRef?: Uint8Array | null; // 32 bytes, e.g. the origin-chain tx hash; emitted as SourceRefEvent
+  balanceBps?: number | null; // share of the vault's balance per line, 10000 for all; `withdraw` only
 }
 
 export interface BulkWithdrawalTicket {
+  version: number;
   tickets: WithdrawalTicket[];
 }
 
 export interface AdminDepositTicket {
+  version: number;
   requestId: BN;
   vault: PublicKey;
   deposits: AssetAmount[];
   expiry: BN;
   networkId: BN;
+  notBefore?: BN | null;
+  sourceRef?: Uint8Array | null; // 32 bytes, e.g. the origin-chain tx hash; emitted as SourceRefEvent
+  bypassWhitelist?: boolean; // accept assets not (yet) whitelisted, e.g. seeding before addAsset
 }
 
+// Matches the on-chain `AssetSettings`; zero means no limit for each amount
+export interface AssetSettings {
+  minDeposit: BN; // smallest deposit line
+  depositCap: BN; // largest deposit line
+  withdrawalLimit: BN; // largest withdrawal ticket line
+  tvlCap: BN; // largest vault balance a deposit may leave
+  userCap: BN; // largest running total of one depositor's deposits
+  paused: boolean; // deposits rejected while set, withdrawals still allowed
+}
+
+// Matches the on-chain `AssetConfig`, an entry of the vault's whitelist
+export interface AssetConfig {
+  asset: Asset;
+  decimals: number;
+  minDeposit: BN;
+  depositCap: BN;
+  withdrawalLimit: BN;
+  tvlCap: BN;
+  userCap: BN;
+  paused: boolean;
+}
+
+export const NO_ASSET_LIMITS: AssetSettings = {
+  minDeposit: new BN(0),
+  depositCap: new BN(0),
+  withdrawalLimit: new BN(0),
+  tvlCap: new BN(0),
+  userCap: new BN(0),
+  paused: false,
+};
+
 export interface AddAssetTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  asset: Asset;
+  settings: AssetSettings;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface UpdateAssetTicket {
+  version: number;
   requestId: BN;
   vault: PublicKey;
   asset: Asset;
+  settings: AssetSettings;
   expiry: BN;
   networkId: BN;
+  notBefore?: BN | null;
 }
 
 export interface RemoveAssetTicket {
+  version: number;
   requestId: BN;
   vault: PublicKey;
   asset: Asset;
   expiry: BN;
   networkId: BN;
+  notBefore?: BN | null;
+}
+
+// Matches the on-chain `WeightedSigner`; thresholds are sums of weights
+export interface WeightedSigner {
+  address: Uint8Array;      // 20-byte Ethereum address
+  weight: number;           // u8, at least 1
+  validUntil: BN | null;    // signatures are ignored after this unix timestamp
 }
 
 export interface RotateValidatorTicket {
+  version: number;
   requestId: BN;
   vault: PublicKey;
-  signers: Uint8Array[]; // Array of 20-byte Ethereum addresses
+  signers: WeightedSigner[];
   mThreshold: number;
   adminThreshold: number;
   expiry: BN;
   networkId: BN;
+  notBefore?: BN | null;
 }
 
-export interface EthereumKeypair {
-  privateKey: Uint8Array; // 32 bytes
-  publicKey: Uint8Array;  // 64 bytes (uncompressed, without prefix)
-  address: Uint8Array;    // 20 bytes (Ethereum address)
+export interface RotateAdminSignersTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  signers: WeightedSigner[]; // Empty to hand admin ops back to the operational signers
+  adminThreshold: number;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
 }
 
-export interface SignerWithSignature {
-  ethAddress: Uint8Array;  // 20 bytes
-  signature: Uint8Array;   // 64 bytes (r + s)
-  recoveryId: number;      // 0, 1, 27, or 28
+// Commits to a signer set past MAX_SIGNERS by `createSignerSetHash`; the signers are uploaded after
+export interface ProposeSignerSetTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  signersHash: Uint8Array; // 32 bytes
+  signersLen: number;
+  mThreshold: number;
+  adminThreshold: number;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
 }
 
-export class MultisigVaultClient {
-  program: Program<StrikeExample>;
-  provider: anchor.AnchorProvider;
-  vaultSeed: string;
-  networkId: number;
+// Matches the on-chain `LogVerbosity` enum
+export type LogVerbosity = { itemized: {} } | { compact: {} };
 
-  constructor(
-    program: Program<StrikeExample>,
-    provider: anchor.AnchorProvider,
-    vaultSeed: string,
-    networkId: number,
-  ) {
-    this.program = program;
-    this.provider = provider;
-    this.vaultSeed = vaultSeed;
-    this.networkId = networkId;
-  }
+export interface SetLogVerbosityTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  logVerbosity: LogVerbosity;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Generate an Ethereum-compatible keypair
-   */
-  static generateEthereumKeypair(): EthereumKeypair {
-    const privateKey = secp256k1.utils.randomPrivateKey();
-    const publicKeyFull = secp256k1.getPublicKey(privateKey, false); // uncompressed (65 bytes with 0x04 prefix)
-    const publicKey = publicKeyFull.slice(1); // Remove 0x04 prefix (64 bytes)
-    
-    // Ethereum address: keccak256(publicKey)[12:32]
-    const hash = keccak256(publicKey);
-    const address = hash.slice(-20);
-    
-    return {
-      privateKey,
-      publicKey,
-      address,
-    };
-  }
+export interface SetAllowanceTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  asset: Asset;
+  cap: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Load Ethereum keypair from hex string (private key)
-   */
-  static loadEthereumKeypair(privateKeyHex: string): EthereumKeypair {
-    // Remove 0x prefix if present
-    const hex = privateKeyHex.startsWith("0x") ? privateKeyHex.slice(2) : privateKeyHex;
-    const privateKey = new Uint8Array(Buffer.from(hex, "hex"));
-    
-    const publicKeyFull = secp256k1.getPublicKey(privateKey, false);
-    const publicKey = publicKeyFull.slice(1);
-    
-    const hash = keccak256(publicKey);
-    const address = hash.slice(-20);
-    
-    return {
-      privateKey,
-      publicKey,
-      address,
-    };
-  }
+export interface SetMinWithdrawalTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  asset: Asset;
+  minAmount: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Derive the vault PDA address
-   */
-  getVaultAddress(vaultSeed: string): [PublicKey, number] {
-    if (!vaultSeed || vaultSeed.length === 0 || vaultSeed.length > 32) {
-      throw new Error('Vault seed must be between 1 and 32 characters');
-    }
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), Buffer.from(vaultSeed, 'utf-8')],
-      this.program.programId
-    );
-  }
+export interface SetUsdCapTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  asset: Asset;
+  oracle: PriceOracle;
+  maxUsd: BN; // micro-dollars per withdrawal line, 0 to only price the asset
+  maxAge: BN; // seconds a price may be old
+  maxConfBps: number; // confidence interval as a share of the price
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Derive the treasury PDA address
-   */
-  getTreasuryAddress(vaultPda: PublicKey): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury"), vaultPda.toBuffer()],
-      this.program.programId
-    );
-  }
+export interface SetMaxTicketUsdTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  maxTicketUsd: BN; // micro-dollars per withdrawal ticket, 0 for none
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Derive the nonce account PDA address
-   */
-  getNonceAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
-    const requestIdBuffer = Buffer.alloc(8);
-    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
-    
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("nonce"), vaultPda.toBuffer(), requestIdBuffer],
-      this.program.programId
-    );
-  }
+export interface SetTreasuryTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  name: string;
+  mThreshold: number;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Derive the nonce account PDA address for admin
-   */
-  getAdminNonceAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
-    const requestIdBuffer = Buffer.alloc(8);
-    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
-    
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("admin_nonce"), vaultPda.toBuffer(), requestIdBuffer],
-      this.program.programId
-    );
-  }
+export interface SetWormholeEmitterTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  coreBridge: PublicKey; // owner of the posted VAA accounts
+  emitterChain: number; // Wormhole chain id
+  emitterAddress: number[]; // 32 bytes
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Serialize asset for hashing
-   */
-  private serializeAsset(asset: Asset): Buffer {
-    const buffers: Buffer[] = [];
-    
-    if ('sol' in asset) {
-      buffers.push(Buffer.from([0])); // Sol variant = 0
-    } else if ('splToken' in asset) {
-      buffers.push(Buffer.from([1])); // SplToken variant = 1
-      buffers.push(asset.splToken.mint.toBuffer());
-    }
-    
-    return Buffer.concat(buffers);
-  }
+export interface CreateSubVaultTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey; // parent vault
+  subVaultSeed: string;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Serialize AssetAmount for hashing
-   */
-  private serializeAssetAmount(assetAmount: AssetAmount): Buffer {
-    const buffers: Buffer[] = [];
-    
-    buffers.push(this.serializeAsset(assetAmount.asset));
-    buffers.push(Buffer.from([64])); // Separator byte
-    
-    const amountBuf = Buffer.alloc(8);
-    amountBuf.writeBigUInt64LE(BigInt(assetAmount.amount.toString()));
-    buffers.push(amountBuf);
-    
-    return Buffer.concat(buffers);
-  }
+export interface ReclaimSubVaultTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey; // parent vault, receives the funds
+  subVault: PublicKey;
+  withdrawals: AssetAmount[];
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
 
-  /**
-   * Create a withdrawal ticket hash for signing (keccak256)
-   */
-  createWithdrawalTicketHash(ticket: WithdrawalTicket, admin?: boolean): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    if (admin) {
-      data.push(Buffer.from(DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL, "utf8"));
-    } else {
-      data.push(Buffer.from(DOMAIN_SEPARATOR_WITHDRAWAL, "utf8"));
-    }
-    
-    // Request ID (u64, little-endian)
-    const requestIdBuf = Buffer.alloc(8);
-    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
-    data.push(requestIdBuf);
-    
-    // Vault pubkey (32 bytes)
-    data.push(ticket.vault.toBuffer());
-    
-    // Recipient pubkey (32 bytes)
-    data.push(ticket.recipient.toBuffer());
-    
-    // Withdrawals
-    for (const withdrawal of ticket.withdrawals) {
-      data.push(this.serializeAssetAmount(withdrawal));
-    }
-    
-    // Expiry (i64, little-endian)
-    const expiryBuf = Buffer.alloc(8);
-    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
-    data.push(expiryBuf);
-    
-    // Network ID (u64, little-endian)
-    const networkIdBuf = Buffer.alloc(8);
-    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
-    data.push(networkIdBuf);
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
-  }
+export interface CreateReceiptMintTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  asset: Asset;
+  decimals: number;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface AddRecipientTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface RemoveRecipientTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface BlockRecipientTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface UnblockRecipientTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetRecipientAllowlistTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  enabled: boolean;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetFeeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  feeBps: number;
+  feeMint: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetRelayerRebateTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  maxRebate: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetMaxTicketLifetimeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  maxLifetime: BN; // seconds, 0 removes the cap
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetExpiryGraceTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  grace: BN; // seconds, 0 for a hard expiry
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetNonceTreeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  nonceTree: PublicKey;
+  baseRequestId: BN; // first withdrawal request id recorded in the tree
+  maxDepth: number; // the tree holds 2^maxDepth request ids
+  maxBufferSize: number;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetExtraNetworkIdsTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  extraNetworkIds: BN[]; // replaces the current list, empty to accept networkId only
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetAuthSchemeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  authScheme: AuthScheme;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetHookProgramTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  hookProgram: PublicKey; // PublicKey.default removes the hook
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetDepositRegistryTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  registry: PublicKey; // PublicKey.default lets anyone deposit again
+  schema: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetIdentityNetworkTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  network: PublicKey; // PublicKey.default turns the identity check off
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetStrictSignaturesTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  enabled: boolean;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetTreasuryReserveTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  reserve: BN; // lamports above rent exemption, 0 removes the reserve
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetReplayModeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  watermarkReplay: boolean; // request ids must be strictly increasing, nonce accounts optional
+  watermark: BN; // withdrawal request ids at or below it are rejected, never lowered
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetGuardianTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  guardian: PublicKey; // PublicKey.default removes the guardian
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface DelegateStakeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  voteAccount: PublicKey;
+  amount: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface DeactivateStakeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  voteAccount: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface LiquidStakeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  stakePool: PublicKey;
+  poolMint: PublicKey;
+  lamports: BN;
+  minPoolTokens: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface LiquidUnstakeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  stakePool: PublicKey;
+  poolMint: PublicKey;
+  poolTokens: BN;
+  minLamports: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface RebalanceTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  asset: Asset;
+  amount: BN;
+  source: PublicKey; // treasury PDA, named treasury or vault-owned token account
+  destination: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SwapTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  inputMint: PublicKey;
+  outputMint: PublicKey;
+  amountIn: BN; // most the swap may spend
+  minAmountOut: BN; // slippage floor
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+/**
+ * Accounts of the SPL stake pool the vault deposits into, as read from the pool's state
+ */
+export interface StakePoolAccounts {
+  stakePool: PublicKey;
+  poolMint: PublicKey;
+  reserveStake: PublicKey;
+  managerFeeAccount: PublicKey;
+}
+
+export interface WithdrawStakeTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  voteAccount: PublicKey;
+  amount: BN;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface CollectFeesTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  fees: AssetAmount[];
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SweepDustTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  destination: PublicKey;
+  mints: PublicKey[];
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SweepAssetTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  mint: PublicKey;
+  destination: PublicKey; // owner of the token account paid, receives the rent when closing
+  closeAccount: boolean;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface UpgradeProgramTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  buffer: PublicKey; // owned by the vault's upgrade authority PDA
+  spill: PublicKey; // receives the buffer's lamports
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface CloseVaultTokenAccountTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  mint: PublicKey;
+  rentRecipient: PublicKey; // receives the account's rent
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface ClaimRootTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  root: number[]; // 32 bytes, Merkle root over claim leaf hashes
+  claimExpiry: BN; // leaves can't be claimed after this timestamp
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface CreditTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  user: PublicKey;
+  credits: AssetAmount[];
+  sourceRef: number[]; // 32 bytes, e.g. the origin-chain tx hash
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface CreateStreamTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  asset: Asset;
+  amount: BN;
+  startTime: BN;
+  cliffTime: BN; // nothing vests before this timestamp
+  rate: BN; // amount vested per second since startTime
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface CreateScheduleTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  recipient: PublicKey;
+  asset: Asset;
+  amount: BN; // per payment
+  interval: BN; // seconds between payments
+  firstExecution: BN; // timestamp the first payment becomes due
+  payments: number; // u32
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface ClaimLeaf {
+  index: BN;
+  recipient: PublicKey;
+  assetAmount: AssetAmount;
+}
+
+export interface CpiAccountMeta {
+  pubkey: PublicKey;
+  isSigner: boolean;
+  isWritable: boolean;
+}
+
+export interface AuthorizedCpiTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  programId: PublicKey;
+  accounts: CpiAccountMeta[];
+  dataHash: Uint8Array; // 32 bytes, keccak256 of the instruction data
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface ExecuteTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  programId: PublicKey;
+  accounts: CpiAccountMeta[];
+  data: Buffer;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface AddStrategyTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  programId: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface RemoveStrategyTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  programId: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface AddCrankerTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  cranker: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface RemoveCrankerTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  cranker: PublicKey;
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface SetCrankIncentiveTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  incentive: BN; // lamports per successful crank, 0 turns it off
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface StrategyTicket {
+  version: number;
+  requestId: BN;
+  vault: PublicKey;
+  programId: PublicKey;
+  accounts: CpiAccountMeta[];
+  dataHash: Uint8Array; // 32 bytes, keccak256 of the instruction data
+  expiry: BN;
+  networkId: BN;
+  notBefore?: BN | null;
+}
+
+export interface EthereumKeypair {
+  privateKey: Uint8Array; // 32 bytes
+  publicKey: Uint8Array;  // 64 bytes (uncompressed, without prefix)
+  address: Uint8Array;    // 20 bytes (Ethereum address)
+}
+
+export interface SignerWithSignature {
+  ethAddress: Uint8Array;  // 20 bytes
+  signature: Uint8Array;   // 64 bytes (r + s)
+  recoveryId: number;      // 0, 1, 27, or 28
+}
+
+export class MultisigVaultClient {
+  program: Program<StrikeExample>;
+  provider: anchor.AnchorProvider;
+  vaultSeed: string;
+  networkId: number | BN; // a BN once bound to the cluster's genesis hash
+  domainSuffix: string;
+  parentVault: PublicKey | null; // set when the vault is a sub-vault of this parent
+
+  constructor(
+    program: Program<StrikeExample>,
+    provider: anchor.AnchorProvider,
+    vaultSeed: string,
+    networkId: number,
+    domainSuffix: string = "",
+    parentVault: PublicKey | null = null,
+  ) {
+    this.program = program;
+    this.provider = provider;
+    this.vaultSeed = vaultSeed;
+    this.networkId = networkId;
+    this.domainSuffix = domainSuffix;
+    this.parentVault = parentVault;
+  }
+
+  /**
+   * Generate an Ethereum-compatible keypair
+   */
+  static generateEthereumKeypair(): EthereumKeypair {
+    const privateKey = secp256k1.utils.randomPrivateKey();
+    const publicKeyFull = secp256k1.getPublicKey(privateKey, false); // uncompressed (65 bytes with 0x04 prefix)
+    const publicKey = publicKeyFull.slice(1); // Remove 0x04 prefix (64 bytes)
+    
+    // Ethereum address: keccak256(publicKey)[12:32]
+    const hash = keccak256(publicKey);
+    const address = hash.slice(-20);
+    
+    return {
+      privateKey,
+      publicKey,
+      address,
+    };
+  }
+
+  /**
+   * Signer-set address of an Ed25519 (Solana) key: keccak256(pubkey)[12:32]
+   */
+  static ed25519SignerAddress(pubkey: PublicKey): Uint8Array {
+    return keccak256(pubkey.toBytes()).slice(-20);
+  }
+
+  /**
+   * Signers ordered by ascending Ethereum address, the order the program requires their
+   * signatures in
+   */
+  static sortBySignerAddress(keypairs: EthereumKeypair[]): EthereumKeypair[] {
+    return [...keypairs].sort((a, b) => Buffer.compare(Buffer.from(a.address), Buffer.from(b.address)));
+  }
+
+  /**
+   * Network id of a vault bound to the cluster with `genesisHash`: its first 8 bytes,
+   * little-endian
+   */
+  static genesisNetworkId(genesisHash: Uint8Array): BN {
+    return new BN(Buffer.from(genesisHash.slice(0, 8)), "le");
+  }
+
+  /**
+   * The connected cluster's genesis hash, and switch this client to the network id derived
+   * from it
+   */
+  async bindToGenesis(): Promise<Uint8Array> {
+    const genesisHash = new PublicKey(await this.provider.connection.getGenesisHash()).toBytes();
+    this.networkId = MultisigVaultClient.genesisNetworkId(genesisHash);
+    return genesisHash;
+  }
+
+  /**
+   * Load Ethereum keypair from hex string (private key)
+   */
+  static loadEthereumKeypair(privateKeyHex: string): EthereumKeypair {
+    // Remove 0x prefix if present
+    const hex = privateKeyHex.startsWith("0x") ? privateKeyHex.slice(2) : privateKeyHex;
+    const privateKey = new Uint8Array(Buffer.from(hex, "hex"));
+    
+    const publicKeyFull = secp256k1.getPublicKey(privateKey, false);
+    const publicKey = publicKeyFull.slice(1);
+    
+    const hash = keccak256(publicKey);
+    const address = hash.slice(-20);
+    
+    return {
+      privateKey,
+      publicKey,
+      address,
+    };
+  }
+
+  /**
+   * Derive the vault PDA address, `["vault", vaultSeed]`, or `["sub_vault", parentVault, vaultSeed]`
+   * for a sub-vault
+   */
+  getVaultAddress(vaultSeed: string, parentVault: PublicKey | null = null): [PublicKey, number] {
+    if (!vaultSeed || vaultSeed.length === 0 || vaultSeed.length > 32) {
+      throw new Error('Vault seed must be between 1 and 32 characters');
+    }
+    const seeds = parentVault
+      ? [SUB_VAULT_SEED, parentVault.toBuffer(), Buffer.from(vaultSeed, 'utf-8')]
+      : [VAULT_SEED, Buffer.from(vaultSeed, 'utf-8')];
+    return PublicKey.findProgramAddressSync(seeds, this.program.programId);
+  }
+
+  /**
+   * Derive the treasury PDA address
+   */
+  getTreasuryAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [TREASURY_SEED, vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the hook authority PDA address, which signs the vault's hook program calls
+   */
+  getHookAuthorityAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("hook_authority"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the nonce tree authority PDA address, which signs the vault's nonce tree updates
+   */
+  getNonceTreeAuthorityAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("nonce_tree_authority"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the upgrade authority PDA address, which holds the program's upgrade authority once
+   * accepted
+   */
+  getUpgradeAuthorityAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("upgrade_authority"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the program's program data account, owned by the upgradeable loader
+   */
+  getProgramDataAddress(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [this.program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    );
+  }
+
+  /**
+   * Derive the strategy allowlist PDA address
+   */
+  getStrategyAllowlistAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_allowlist"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the signer set PDA address
+   */
+  getSignerSetAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("signer_set"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the crank registry PDA address
+   */
+  getCrankRegistryAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("crank_registry"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the recipient allowlist PDA address
+   */
+  getRecipientAllowlistAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient_allowlist"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the blocklist PDA address
+   */
+  getBlocklistAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the fee treasury PDA address
+   */
+  getFeeTreasuryAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_treasury"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive a named treasury PDA address
+   */
+  getNamedTreasuryAddress(vaultPda: PublicKey, name: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [TREASURY_SEED, vaultPda.toBuffer(), Buffer.from(name, "utf8")],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the nonce account PDA address
+   */
+  getNonceAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+    
+    return PublicKey.findProgramAddressSync(
+      [NONCE_SEED, vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the nonce account PDA address for admin
+   */
+  getAdminNonceAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+    
+    return PublicKey.findProgramAddressSync(
+      [ADMIN_NONCE_SEED, vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the stake account PDA the vault uses for `voteAccount`
+   */
+  getStakeAddress(vaultPda: PublicKey, voteAccount: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("stake"), vaultPda.toBuffer(), voteAccount.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the system-owned PDA that carries treasury SOL into a stake pool deposit
+   */
+  getLstFundingAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lst_funding"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive a stake pool's withdraw authority
+   */
+  getStakePoolWithdrawAuthority(stakePool: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [stakePool.toBuffer(), Buffer.from("withdraw")],
+      SPL_STAKE_POOL_PROGRAM_ID
+    );
+  }
+
+  /**
+   * Derive the per-user deposit record PDA address
+   */
+  getDepositRecordAddress(vaultPda: PublicKey, user: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_record"), vaultPda.toBuffer(), user.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the allowance PDA address of a recipient for an asset
+   */
+  getAllowanceAddress(vaultPda: PublicKey, recipient: PublicKey, asset: Asset): [PublicKey, number] {
+    let assetKey = PublicKey.default;
+    if ('splToken' in asset) {
+      assetKey = asset.splToken.mint;
+    } else if ('nft' in asset) {
+      assetKey = asset.nft.mint;
+    }
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("allowance"), vaultPda.toBuffer(), recipient.toBuffer(), assetKey.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the receipt mint PDA address of an asset
+   */
+  getReceiptMintAddress(vaultPda: PublicKey, asset: Asset): [PublicKey, number] {
+    let assetKey = PublicKey.default;
+    if ('splToken' in asset) {
+      assetKey = asset.splToken.mint;
+    } else if ('nft' in asset) {
+      assetKey = asset.nft.mint;
+    }
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), vaultPda.toBuffer(), assetKey.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the withdrawal receipt PDA address of a request id
+   */
+  getWithdrawalReceiptAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("withdrawal_receipt"), vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the claim root PDA address published under a request id
+   */
+  getClaimRootAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_root"), vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the stream PDA created under a request id
+   */
+  getStreamAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("stream"), vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the schedule PDA created under a request id
+   */
+  getScheduleAddress(vaultPda: PublicKey, requestId: BN): [PublicKey, number] {
+    const requestIdBuffer = Buffer.alloc(8);
+    requestIdBuffer.writeBigUInt64LE(BigInt(requestId.toString()));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("schedule"), vaultPda.toBuffer(), requestIdBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the PDA marking a claim leaf as redeemed
+   */
+  getClaimedLeafAddress(claimRootPda: PublicKey, index: BN): [PublicKey, number] {
+    const indexBuffer = Buffer.alloc(8);
+    indexBuffer.writeBigUInt64LE(BigInt(index.toString()));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("claimed"), claimRootPda.toBuffer(), indexBuffer],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Derive the temporary wSOL account PDA used to wrap/unwrap SOL
+   */
+  getWsolTempAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("wsol"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Mint accounts needed by `transfer_checked` for the SPL assets in the list (deduplicated)
+   */
+  getMintAccounts(assetAmounts: AssetAmount[]): any[] {
+    const mints = new Map<string, PublicKey>();
+    for (const assetAmount of assetAmounts) {
+      if ('splToken' in assetAmount.asset) {
+        const mint = assetAmount.asset.splToken.mint;
+        mints.set(mint.toBase58(), mint);
+      } else if ('nft' in assetAmount.asset) {
+        const mint = assetAmount.asset.nft.mint;
+        mints.set(mint.toBase58(), mint);
+      }
+    }
+    return Array.from(mints.values()).map(mint => ({
+      pubkey: mint,
+      isWritable: false,
+      isSigner: false,
+    }));
+  }
+
+  /**
+   * Fee treasury token accounts credited with the protocol fee on SPL withdrawals (deduplicated)
+   */
+  getFeeTokenAccounts(
+    vaultPda: PublicKey,
+    assetAmounts: AssetAmount[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): any[] {
+    const [feeTreasuryPda] = this.getFeeTreasuryAddress(vaultPda);
+    return this.getMintAccounts(assetAmounts).map(({ pubkey }) => ({
+      pubkey: getAssociatedTokenAddressSync(pubkey, feeTreasuryPda, true, tokenProgram),
+      isWritable: true,
+      isSigner: false,
+    }));
+  }
+
+  /**
+   * SPL Memo program account, needed in remaining accounts when any ticket carries a memo.
+   */
+  getMemoAccounts(tickets: WithdrawalTicket[]): any[] {
+    if (!tickets.some(t => t.memo)) {
+      return [];
+    }
+    return [{
+      pubkey: MEMO_PROGRAM_ID,
+      isWritable: false,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * Where `bulk_withdraw` finds each ticket's recipient, mints and token accounts in
+   * `remainingAccounts`. Missing accounts get index 255, which the program rejects like any
+   * account it can't find.
+   */
+  getBulkAccountIndexes(
+    vaultPda: PublicKey,
+    tickets: WithdrawalTicket[],
+    remainingAccounts: any[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): any[] {
+    const indexOf = (address: PublicKey): number => {
+      const index = remainingAccounts.findIndex(meta => meta.pubkey.equals(address));
+      return index >= 0 && index < 255 ? index : 255;
+    };
+
+    return tickets.map(ticket => ({
+      recipient: indexOf(ticket.recipient),
+      withdrawals: ticket.withdrawals.map((withdrawal, i) => {
+        const mint = 'splToken' in withdrawal.asset ? withdrawal.asset.splToken.mint
+          : 'nft' in withdrawal.asset ? withdrawal.asset.nft.mint
+          : null;
+        if (!mint) {
+          return { mint: 0, vaultToken: 0, recipientToken: 0 };
+        }
+        const recipientToken = ticket.tokenAccounts?.[i]
+          ?? getAssociatedTokenAddressSync(mint, ticket.recipient, true, tokenProgram);
+        return {
+          mint: indexOf(mint),
+          vaultToken: indexOf(getAssociatedTokenAddressSync(mint, vaultPda, true, tokenProgram)),
+          recipientToken: indexOf(recipientToken),
+        };
+      }),
+    }));
+  }
+
+  /**
+   * Named treasury account debited instead of the default treasury when the ticket names one.
+   */
+  getNamedTreasuryAccounts(ticket: WithdrawalTicket): any[] {
+    if (!ticket.treasury) {
+      return [];
+    }
+    return [{
+      pubkey: this.getNamedTreasuryAddress(ticket.vault, ticket.treasury)[0],
+      isWritable: true,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * Allowance accounts charged by withdrawals, one per (recipient, asset) line (deduplicated).
+   * They must be passed whether or not an allowance was ever set.
+   */
+  getAllowanceAccounts(tickets: WithdrawalTicket[]): any[] {
+    const allowances = new Map<string, PublicKey>();
+    for (const ticket of tickets) {
+      for (const withdrawal of ticket.withdrawals) {
+        const [allowancePda] = this.getAllowanceAddress(ticket.vault, ticket.recipient, withdrawal.asset);
+        allowances.set(allowancePda.toBase58(), allowancePda);
+      }
+    }
+    return Array.from(allowances.values()).map(allowance => ({
+      pubkey: allowance,
+      isWritable: true,
+      isSigner: false,
+    }));
+  }
+
+  /**
+   * Recipient allowlist account, read by withdrawals and claims while the vault is in
+   * allowlist mode. Passed unconditionally; the program ignores it otherwise.
+   */
+  getRecipientAllowlistAccounts(vaultPda: PublicKey): any[] {
+    const [allowlistPda] = this.getRecipientAllowlistAddress(vaultPda);
+    return [{
+      pubkey: allowlistPda,
+      isWritable: false,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * Blocklist account, read by withdrawals and claims once the vault has a blocklist. Passed
+   * unconditionally; the program ignores it otherwise.
+   */
+  getBlocklistAccounts(vaultPda: PublicKey): any[] {
+    const [blocklistPda] = this.getBlocklistAddress(vaultPda);
+    return [{
+      pubkey: blocklistPda,
+      isWritable: false,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * The signer set PDA, which every signed instruction needs in its remaining accounts once the
+   * vault's signers live there
+   */
+  getSignerSetAccounts(vaultPda: PublicKey): any[] {
+    const [signerSetPda] = this.getSignerSetAddress(vaultPda);
+    return [{
+      pubkey: signerSetPda,
+      isWritable: false,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * The crank registry PDA, which crank instructions need once the vault has one
+   */
+  getCrankRegistryAccounts(vaultPda: PublicKey): any[] {
+    const [crankRegistryPda] = this.getCrankRegistryAddress(vaultPda);
+    return [{
+      pubkey: crankRegistryPda,
+      isWritable: false,
+      isSigner: false,
+    }];
+  }
+
+  /**
+   * Receipt mint and the owner's receipt token account for each deposited asset the vault
+   * issues receipts for. Pass them in `remainingAccounts` of deposit to receive receipts; the
+   * owner's associated token account must already exist.
+   */
+  async getReceiptAccounts(
+    deposits: AssetAmount[],
+    owner: PublicKey = this.provider.wallet.publicKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<any[]> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const accounts: any[] = [];
+    for (const deposit of deposits) {
+      const [receiptMint] = this.getReceiptMintAddress(vaultPda, deposit.asset);
+      if (accounts.some(a => a.pubkey.equals(receiptMint))) continue;
+      if (!(await this.provider.connection.getAccountInfo(receiptMint))) continue;
+      accounts.push(
+        { pubkey: receiptMint, isWritable: true, isSigner: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(receiptMint, owner, false, tokenProgram),
+          isWritable: true,
+          isSigner: false,
+        },
+      );
+    }
+    return accounts;
+  }
+
+  /**
+   * Extra accounts required by a Token-2022 transfer hook mint (hook program, validation
+   * account and the accounts it lists). Pass them in `remainingAccounts` of deposit/withdraw.
+   */
+  async getTransferHookAccounts(
+    mint: PublicKey,
+    source: PublicKey,
+    destination: PublicKey,
+    owner: PublicKey,
+    amount: BN,
+    decimals: number,
+  ): Promise<any[]> {
+    const ix = await createTransferCheckedWithTransferHookInstruction(
+      this.provider.connection,
+      source,
+      mint,
+      destination,
+      owner,
+      BigInt(amount.toString()),
+      decimals,
+      [],
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    // The first four keys are source, mint, destination and owner.
+    return ix.keys.slice(4).map(k => ({
+      pubkey: k.pubkey,
+      isWritable: k.isWritable,
+      isSigner: false,
+    }));
+  }
+
+  /**
+   * Serialize auth scheme for hashing
+   */
+  private serializeAuthScheme(authScheme: AuthScheme): Buffer {
+    if ('secp256k1' in authScheme) {
+      return Buffer.from([0]); // Secp256k1 variant = 0
+    } else if ('ed25519' in authScheme) {
+      return Buffer.from([1]); // Ed25519 variant = 1
+    } else if ('secp256r1' in authScheme) {
+      return Buffer.from([3]); // Secp256r1 variant = 3
+    }
+    // Attestor variant = 2
+    return Buffer.concat([Buffer.from([2]), authScheme.attestor.program.toBuffer()]);
+  }
+
+  /**
+   * Serialize price oracle for hashing
+   */
+  private serializePriceOracle(oracle: PriceOracle): Buffer {
+    if ('pyth' in oracle) {
+      // Pyth variant = 0
+      return Buffer.concat([Buffer.from([0]), Buffer.from(oracle.pyth.feedId)]);
+    }
+    // Chainlink variant = 1
+    return Buffer.concat([Buffer.from([1]), oracle.chainlink.feed.toBuffer()]);
+  }
+
+  /**
+   * Serialize asset for hashing
+   */
+  private serializeAsset(asset: Asset): Buffer {
+    const buffers: Buffer[] = [];
+    
+    if ('sol' in asset) {
+      buffers.push(Buffer.from([0])); // Sol variant = 0
+    } else if ('splToken' in asset) {
+      buffers.push(Buffer.from([1])); // SplToken variant = 1
+      buffers.push(asset.splToken.mint.toBuffer());
+    } else if ('nft' in asset) {
+      buffers.push(Buffer.from([2])); // Nft variant = 2
+      buffers.push(asset.nft.mint.toBuffer());
+    }
+    
+    return Buffer.concat(buffers);
+  }
+
+  /**
+   * Serialize AssetSettings for hashing
+   */
+  private serializeAssetSettings(settings: AssetSettings): Buffer {
+    const buffers: Buffer[] = [];
+    
+    for (const amount of [
+      settings.minDeposit,
+      settings.depositCap,
+      settings.withdrawalLimit,
+      settings.tvlCap,
+      settings.userCap,
+    ]) {
+      const amountBuf = Buffer.alloc(8);
+      amountBuf.writeBigUInt64LE(BigInt(amount.toString()));
+      buffers.push(amountBuf);
+    }
+    buffers.push(Buffer.from([settings.paused ? 1 : 0]));
+    
+    return Buffer.concat(buffers);
+  }
+
+  /**
+   * Serialize WeightedSigner for hashing
+   */
+  private serializeWeightedSigner(signer: WeightedSigner): Buffer {
+    const buffers: Buffer[] = [];
+    
+    buffers.push(Buffer.from([55])); // Separator byte before signer
+    buffers.push(Buffer.from(signer.address)); // 20-byte Ethereum address
+    buffers.push(Buffer.from([signer.weight])); // Weight (u8)
+    // Optional expiry: 1 + i64 little-endian when set, 0 otherwise
+    if (signer.validUntil) {
+      const validUntilBuf = Buffer.alloc(8);
+      validUntilBuf.writeBigInt64LE(BigInt(signer.validUntil.toString()));
+      buffers.push(Buffer.from([1]), validUntilBuf);
+    } else {
+      buffers.push(Buffer.from([0]));
+    }
+    buffers.push(Buffer.from([56])); // Separator byte after signer
+    
+    return Buffer.concat(buffers);
+  }
+
+  /**
+   * Serialize AssetAmount for hashing
+   */
+  private serializeAssetAmount(assetAmount: AssetAmount): Buffer {
+    const buffers: Buffer[] = [];
+    
+    buffers.push(this.serializeAsset(assetAmount.asset));
+    buffers.push(Buffer.from([64])); // Separator byte
+    
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(assetAmount.amount.toString()));
+    buffers.push(amountBuf);
+    
+    return Buffer.concat(buffers);
+  }
+
+  /**
+   * Ed25519 precompile instructions approving a ticket hash, to run before the program
+   * instruction. The program reads them through the instructions sysvar.
+   */
+  createEd25519Approvals(messageHash: Uint8Array, keypairs: Keypair[]): TransactionInstruction[] {
+    return keypairs.map(kp => Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: kp.secretKey,
+      message: messageHash,
+    }));
+  }
+
+  /**
+   * Secp256k1 precompile instructions verifying signatures over a ticket preimage, placed
+   * in the transaction starting at instruction `firstIndex`. The program reads them through
+   * the instructions sysvar instead of recovering the signatures itself.
+   */
+  createSecp256k1Approvals(preimage: Uint8Array, ethKeypairs: EthereumKeypair[], firstIndex = 0): TransactionInstruction[] {
+    return ethKeypairs.map((kp, i) => Secp256k1Program.createInstructionWithPrivateKey({
+      privateKey: kp.privateKey,
+      message: preimage,
+      instructionIndex: firstIndex + i,
+    }));
+  }
+
+  /**
+   * The instructions sysvar as a remaining account, when signers approve through precompiles
+   */
+  getInstructionsSysvarAccounts(precompileApprovals: number): any[] {
+    if (precompileApprovals === 0) {
+      return [];
+    }
+    return [{ pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isWritable: false, isSigner: false }];
+  }
+
+  /**
+   * Account an attestor program creates to approve the ticket hashing to `ticketHash`; pass it
+   * in remaining accounts under `AuthScheme.attestor`
+   */
+  getAttestationAddress(
+    vaultPda: PublicKey,
+    attestor: PublicKey,
+    ticketHash: Uint8Array,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("attestation"), vaultPda.toBuffer(), Buffer.from(ticketHash)],
+      attestor
+    );
+  }
+
+  getUsdCapAddress(vaultPda: PublicKey, asset: Asset): [PublicKey, number] {
+    let assetKey = PublicKey.default;
+    if ('splToken' in asset) {
+      assetKey = asset.splToken.mint;
+    } else if ('nft' in asset) {
+      assetKey = asset.nft.mint;
+    }
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("usd_cap"), vaultPda.toBuffer(), assetKey.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * USD cap PDA of each withdrawn asset, followed by the oracle price accounts they are priced
+   * from (Pyth price updates or Chainlink feeds). Withdrawals from a vault with USD caps need them in `remainingAccounts`.
+   */
+  getUsdCapAccounts(vaultPda: PublicKey, withdrawals: AssetAmount[], priceAccounts: PublicKey[]): any[] {
+    const accounts: any[] = [];
+    for (const withdrawal of withdrawals) {
+      const [usdCapPda] = this.getUsdCapAddress(vaultPda, withdrawal.asset);
+      if (accounts.some(a => a.pubkey.equals(usdCapPda))) continue;
+      accounts.push({ pubkey: usdCapPda, isWritable: false, isSigner: false });
+    }
+    for (const priceAccount of priceAccounts) {
+      accounts.push({ pubkey: priceAccount, isWritable: false, isSigner: false });
+    }
+    return accounts;
+  }
+
+  getWormholeEmitterAddress(vaultPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("wormhole_emitter"), vaultPda.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  /**
+   * Hook program and hook authority as remaining accounts, needed by deposits and withdrawals
+   * while the vault has a hook program set
+   */
+  getHookAccounts(vaultPda: PublicKey, hookProgram: PublicKey): any[] {
+    if (hookProgram.equals(PublicKey.default)) {
+      return [];
+    }
+    const [hookAuthority] = this.getHookAuthorityAddress(vaultPda);
+    return [
+      { pubkey: hookProgram, isWritable: false, isSigner: false },
+      { pubkey: hookAuthority, isWritable: false, isSigner: false },
+    ];
+  }
+
+  /**
+   * Hash preimage prefix: domain separator, vault domain suffix (u32 length-prefixed), program id
+   * and ticket version
+   */
+  domainData(separator: string, version: number): Buffer {
+    const suffix = Buffer.from(this.domainSuffix, "utf8");
+    const suffixLenBuf = Buffer.alloc(4);
+    suffixLenBuf.writeUInt32LE(suffix.length);
+    return Buffer.concat([
+      Buffer.from(separator, "utf8"),
+      suffixLenBuf,
+      suffix,
+      this.program.programId.toBuffer(),
+      Buffer.from([version]),
+    ]);
+  }
+
+  /**
+   * Hash suffix for a ticket's not-before timestamp: a presence byte, then the i64 (little-endian)
+   * when set
+   */
+  notBeforeData(notBefore?: BN | null): Buffer {
+    if (!notBefore) {
+      return Buffer.from([0]);
+    }
+    const notBeforeBuf = Buffer.alloc(8);
+    notBeforeBuf.writeBigInt64LE(BigInt(notBefore.toString()));
+    return Buffer.concat([Buffer.from([1]), notBeforeBuf]);
+  }
+
+  /**
+   * Hash suffix for a ticket's source ref: a presence byte, then the 32 bytes when set
+   */
+  sourceRefData(sourceRef?: Uint8Array | null): Buffer {
+    if (!sourceRef) {
+      return Buffer.from([0]);
+    }
+    if (sourceRef.length !== 32) {
+      throw new Error('Source ref must be 32 bytes');
+    }
+    return Buffer.concat([Buffer.from([1]), Buffer.from(sourceRef)]);
+  }
+
+  /**
+   * Hash data for an optional string field: a presence byte, then u32 length + utf8 bytes when set
+   */
+  optionalBytesData(value?: string | null): Buffer {
+    if (value == null) {
+      return Buffer.from([0]);
+    }
+    const valueBuf = Buffer.from(value, "utf8");
+    const lenBuf = Buffer.alloc(4);
+    lenBuf.writeUInt32LE(valueBuf.length);
+    return Buffer.concat([Buffer.from([1]), lenBuf, valueBuf]);
+  }
+
+  /**
+   * Create a withdrawal ticket hash for signing (keccak256)
+   */
+  createWithdrawalTicketHash(ticket: WithdrawalTicket, admin?: boolean): Uint8Array {
+    return keccak256(this.createWithdrawalTicketPreimage(ticket, admin));
+  }
+
+  /**
+   * The bytes a withdrawal ticket hash is taken over
+   */
+  createWithdrawalTicketPreimage(ticket: WithdrawalTicket, admin?: boolean): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    if (admin) {
+      data.push(this.domainData(DOMAIN_SEPARATOR_ADMIN_WITHDRAWAL, ticket.version));
+    } else if (ticket.treasury) {
+      data.push(this.domainData(DOMAIN_SEPARATOR_TREASURY_WITHDRAWAL, ticket.version));
+    } else {
+      data.push(this.domainData(DOMAIN_SEPARATOR_WITHDRAWAL, ticket.version));
+    }
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Withdrawals (u32 count, then each line; admin tickets aren't counted)
+    if (!admin) {
+      const countBuf = Buffer.alloc(4);
+      countBuf.writeUInt32LE(ticket.withdrawals.length);
+      data.push(countBuf);
+    }
+    for (const withdrawal of ticket.withdrawals) {
+      data.push(this.serializeAssetAmount(withdrawal));
+    }
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    if (!admin) {
+      // Treasury name, then memo: presence byte, then u32 length + utf8 bytes
+      data.push(this.optionalBytesData(ticket.treasury));
+      data.push(this.optionalBytesData(ticket.memo));
+
+      // Destination token accounts: presence byte, then u32 count + 32 bytes each
+      if (ticket.tokenAccounts != null) {
+        const countBuf = Buffer.alloc(4);
+        countBuf.writeUInt32LE(ticket.tokenAccounts.length);
+        data.push(Buffer.from([1]), countBuf, ...ticket.tokenAccounts.map(account => account.toBuffer()));
+      } else {
+        data.push(Buffer.from([0]));
+      }
+    }
+
+    // Source ref: presence byte, then 32 bytes
+    data.push(this.sourceRefData(ticket.sourceRef));
+
+    // Balance share: presence byte, then u16 (little-endian)
+    if (!admin) {
+      if (ticket.balanceBps != null) {
+        const balanceBpsBuf = Buffer.alloc(2);
+        balanceBpsBuf.writeUInt16LE(ticket.balanceBps);
+        data.push(Buffer.from([1]), balanceBpsBuf);
+      } else {
+        data.push(Buffer.from([0]));
+      }
+    }
+    
+    return Buffer.concat(data);
+  }
+
+  createBulkWithdrawalTicketHash(bulkTicket: BulkWithdrawalTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_BULK_WITHDRAWAL, bulkTicket.version));
+    
+    // Hash each individual ticket and concatenate
+    for (const ticket of bulkTicket.tickets) {
+      const ticketHash = this.createWithdrawalTicketHash(ticket);
+      data.push(Buffer.from(ticketHash));
+    }
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a admin deposit ticket hash for signing (keccak256)
+   */
+  createAdminDepositTicketHash(ticket: AdminDepositTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ADMIN_DEPOSIT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Deposits
+    for (const deposit of ticket.deposits) {
+      data.push(this.serializeAssetAmount(deposit));
+    }
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    // Source ref: presence byte, then 32 bytes
+    data.push(this.sourceRefData(ticket.sourceRef));
+
+    // Whitelist bypass (bool as u8)
+    data.push(Buffer.from([ticket.bypassWhitelist ? 1 : 0]));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create an add asset ticket hash for signing (keccak256)
+   */
+  createAddAssetTicketHash(ticket: AddAssetTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ADD_ASSET, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Settings
+    data.push(this.serializeAssetSettings(ticket.settings));
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create an update asset ticket hash for signing (keccak256)
+   */
+  createUpdateAssetTicketHash(ticket: UpdateAssetTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_UPDATE_ASSET, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Settings
+    data.push(this.serializeAssetSettings(ticket.settings));
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a remove asset ticket hash for signing (keccak256)
+   */
+  createRemoveAssetTicketHash(ticket: RemoveAssetTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_REMOVE_ASSET, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a rotate validator ticket hash for signing (keccak256)
+   */
+  createRotateValidatorTicketHash(ticket: RotateValidatorTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ROTATE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Signers array with separators (matching Rust implementation)
+    for (const signer of ticket.signers) {
+      data.push(this.serializeWeightedSigner(signer));
+    }
+    
+    // M threshold (u8, single byte)
+    data.push(Buffer.from([ticket.mThreshold]));
+    // Admin threshold (u8, single byte)
+    data.push(Buffer.from([ticket.adminThreshold]));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a rotate admin signers ticket hash for signing (keccak256)
+   */
+  createRotateAdminSignersTicketHash(ticket: RotateAdminSignersTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ROTATE_ADMIN_SIGNERS, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    for (const signer of ticket.signers) {
+      data.push(this.serializeWeightedSigner(signer));
+    }
+    
+    // Admin threshold (u8, single byte)
+    data.push(Buffer.from([ticket.adminThreshold]));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Hash a signer set proposal commits to: keccak256 over each signer's ticket encoding, in order
+   */
+  createSignerSetHash(signers: WeightedSigner[]): Uint8Array {
+    return keccak256(Buffer.concat(signers.map(s => this.serializeWeightedSigner(s))));
+  }
+
+  /**
+   * Create a propose signer set ticket hash for signing (keccak256)
+   */
+  createProposeSignerSetTicketHash(ticket: ProposeSignerSetTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_PROPOSE_SIGNER_SET, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Signers hash (32 bytes), signer count and thresholds (u8 each)
+    data.push(Buffer.from(ticket.signersHash));
+    data.push(Buffer.from([ticket.signersLen, ticket.mThreshold, ticket.adminThreshold]));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+
+  /**
+   * Create a set log verbosity ticket hash for signing (keccak256)
+   */
+  createSetLogVerbosityTicketHash(ticket: SetLogVerbosityTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_LOG_VERBOSITY, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Log verbosity (u8 variant index)
+    data.push(Buffer.from(["itemized" in ticket.logVerbosity ? 0 : 1]));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a add recipient ticket hash for signing (keccak256)
+   */
+  createAddRecipientTicketHash(ticket: AddRecipientTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ADD_RECIPIENT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a remove recipient ticket hash for signing (keccak256)
+   */
+  createRemoveRecipientTicketHash(ticket: RemoveRecipientTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_REMOVE_RECIPIENT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a block recipient ticket hash for signing (keccak256)
+   */
+  createBlockRecipientTicketHash(ticket: BlockRecipientTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_BLOCK_RECIPIENT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create an unblock recipient ticket hash for signing (keccak256)
+   */
+  createUnblockRecipientTicketHash(ticket: UnblockRecipientTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_UNBLOCK_RECIPIENT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set recipient allowlist ticket hash for signing (keccak256)
+   */
+  createSetRecipientAllowlistTicketHash(ticket: SetRecipientAllowlistTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_RECIPIENT_ALLOWLIST, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Enabled (bool as u8)
+    data.push(Buffer.from([ticket.enabled ? 1 : 0]));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set fee ticket hash for signing (keccak256)
+   */
+  createSetFeeTicketHash(ticket: SetFeeTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_FEE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Fee in basis points (u16, little-endian)
+    const feeBpsBuf = Buffer.alloc(2);
+    feeBpsBuf.writeUInt16LE(ticket.feeBps);
+    data.push(feeBpsBuf);
+    
+    // Fee mint pubkey (32 bytes, default to pay in kind)
+    data.push(ticket.feeMint.toBuffer());
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set auth scheme ticket hash for signing (keccak256)
+   */
+  createSetAuthSchemeTicketHash(ticket: SetAuthSchemeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_AUTH_SCHEME, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes) and auth scheme
+    data.push(ticket.vault.toBuffer());
+    data.push(this.serializeAuthScheme(ticket.authScheme));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set hook program ticket hash for signing (keccak256)
+   */
+  createSetHookProgramTicketHash(ticket: SetHookProgramTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_HOOK_PROGRAM, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and hook program pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.hookProgram.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set deposit registry ticket hash for signing (keccak256)
+   */
+  createSetDepositRegistryTicketHash(ticket: SetDepositRegistryTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_DEPOSIT_REGISTRY, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, registry and schema pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.registry.toBuffer());
+    data.push(ticket.schema.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set identity network ticket hash for signing (keccak256)
+   */
+  createSetIdentityNetworkTicketHash(ticket: SetIdentityNetworkTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_IDENTITY_NETWORK, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and gatekeeper network pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.network.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set strict signatures ticket hash for signing (keccak256)
+   */
+  createSetStrictSignaturesTicketHash(ticket: SetStrictSignaturesTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_STRICT_SIGNATURES, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Enabled (bool as u8)
+    data.push(Buffer.from([ticket.enabled ? 1 : 0]));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set treasury reserve ticket hash for signing (keccak256)
+   */
+  createSetTreasuryReserveTicketHash(ticket: SetTreasuryReserveTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_TREASURY_RESERVE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Reserve (u64, little-endian)
+    const reserveBuf = Buffer.alloc(8);
+    reserveBuf.writeBigUInt64LE(BigInt(ticket.reserve.toString()));
+    data.push(reserveBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set replay mode ticket hash for signing (keccak256)
+   */
+  createSetReplayModeTicketHash(ticket: SetReplayModeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_REPLAY_MODE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Watermark replay (bool, 1 byte)
+    data.push(Buffer.from([ticket.watermarkReplay ? 1 : 0]));
+
+    // Watermark (u64, little-endian)
+    const watermarkBuf = Buffer.alloc(8);
+    watermarkBuf.writeBigUInt64LE(BigInt(ticket.watermark.toString()));
+    data.push(watermarkBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set guardian ticket hash for signing (keccak256)
+   */
+  createSetGuardianTicketHash(ticket: SetGuardianTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_GUARDIAN, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and guardian pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.guardian.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a delegate stake ticket hash for signing (keccak256)
+   */
+  createDelegateStakeTicketHash(ticket: DelegateStakeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_DELEGATE_STAKE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and vote account pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.voteAccount.toBuffer());
+
+    // Amount (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.amount.toString()));
+    data.push(amountBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a deactivate stake ticket hash for signing (keccak256)
+   */
+  createDeactivateStakeTicketHash(ticket: DeactivateStakeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_DEACTIVATE_STAKE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and vote account pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.voteAccount.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a withdraw stake ticket hash for signing (keccak256)
+   */
+  createWithdrawStakeTicketHash(ticket: WithdrawStakeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_WITHDRAW_STAKE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and vote account pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.voteAccount.toBuffer());
+
+    // Amount (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.amount.toString()));
+    data.push(amountBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a liquid stake ticket hash for signing (keccak256)
+   */
+  createLiquidStakeTicketHash(ticket: LiquidStakeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_LIQUID_STAKE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, stake pool and pool mint pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.stakePool.toBuffer());
+    data.push(ticket.poolMint.toBuffer());
+
+    // Lamports (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.lamports.toString()));
+    data.push(amountBuf);
+
+    // Minimum pool tokens (u64, little-endian)
+    const minBuf = Buffer.alloc(8);
+    minBuf.writeBigUInt64LE(BigInt(ticket.minPoolTokens.toString()));
+    data.push(minBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a liquid unstake ticket hash for signing (keccak256)
+   */
+  createLiquidUnstakeTicketHash(ticket: LiquidUnstakeTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_LIQUID_UNSTAKE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, stake pool and pool mint pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.stakePool.toBuffer());
+    data.push(ticket.poolMint.toBuffer());
+
+    // Pool tokens (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.poolTokens.toString()));
+    data.push(amountBuf);
+
+    // Minimum lamports (u64, little-endian)
+    const minBuf = Buffer.alloc(8);
+    minBuf.writeBigUInt64LE(BigInt(ticket.minLamports.toString()));
+    data.push(minBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a rebalance ticket hash for signing (keccak256)
+   */
+  createRebalanceTicketHash(ticket: RebalanceTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_REBALANCE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+
+    // Amount (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.amount.toString()));
+    data.push(amountBuf);
+
+    // Source and destination pubkeys (32 bytes each)
+    data.push(ticket.source.toBuffer());
+    data.push(ticket.destination.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a swap ticket hash for signing (keccak256)
+   */
+  createSwapTicketHash(ticket: SwapTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SWAP, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, input mint and output mint pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.inputMint.toBuffer());
+    data.push(ticket.outputMint.toBuffer());
+
+    // Amount in (u64, little-endian)
+    const amountInBuf = Buffer.alloc(8);
+    amountInBuf.writeBigUInt64LE(BigInt(ticket.amountIn.toString()));
+    data.push(amountInBuf);
+
+    // Minimum amount out (u64, little-endian)
+    const minBuf = Buffer.alloc(8);
+    minBuf.writeBigUInt64LE(BigInt(ticket.minAmountOut.toString()));
+    data.push(minBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  createSetRelayerRebateTicketHash(ticket: SetRelayerRebateTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_RELAYER_REBATE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Max rebate in lamports (u64, little-endian)
+    const maxRebateBuf = Buffer.alloc(8);
+    maxRebateBuf.writeBigUInt64LE(BigInt(ticket.maxRebate.toString()));
+    data.push(maxRebateBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set max ticket lifetime ticket hash for signing (keccak256)
+   */
+  createSetMaxTicketLifetimeTicketHash(ticket: SetMaxTicketLifetimeTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_MAX_TICKET_LIFETIME, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Max lifetime in seconds (u64, little-endian)
+    const maxLifetimeBuf = Buffer.alloc(8);
+    maxLifetimeBuf.writeBigUInt64LE(BigInt(ticket.maxLifetime.toString()));
+    data.push(maxLifetimeBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set extra network ids ticket hash for signing (keccak256)
+   */
+  createSetExtraNetworkIdsTicketHash(ticket: SetExtraNetworkIdsTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_EXTRA_NETWORK_IDS, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Extra network ids (u64 each, little-endian)
+    for (const extraNetworkId of ticket.extraNetworkIds) {
+      const extraNetworkIdBuf = Buffer.alloc(8);
+      extraNetworkIdBuf.writeBigUInt64LE(BigInt(extraNetworkId.toString()));
+      data.push(extraNetworkIdBuf);
+    }
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set expiry grace ticket hash for signing (keccak256)
+   */
+  createSetExpiryGraceTicketHash(ticket: SetExpiryGraceTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_EXPIRY_GRACE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Grace in seconds (u64, little-endian)
+    const graceBuf = Buffer.alloc(8);
+    graceBuf.writeBigUInt64LE(BigInt(ticket.grace.toString()));
+    data.push(graceBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set nonce tree ticket hash for signing (keccak256)
+   */
+  createSetNonceTreeTicketHash(ticket: SetNonceTreeTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_NONCE_TREE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Nonce tree pubkey (32 bytes)
+    data.push(ticket.nonceTree.toBuffer());
+    
+    // Base request ID (u64, little-endian)
+    const baseBuf = Buffer.alloc(8);
+    baseBuf.writeBigUInt64LE(BigInt(ticket.baseRequestId.toString()));
+    data.push(baseBuf);
+    
+    // Max depth and max buffer size (u32 each, little-endian)
+    const sizeBuf = Buffer.alloc(8);
+    sizeBuf.writeUInt32LE(ticket.maxDepth, 0);
+    sizeBuf.writeUInt32LE(ticket.maxBufferSize, 4);
+    data.push(sizeBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a collect fees ticket hash for signing (keccak256)
+   */
+  createCollectFeesTicketHash(ticket: CollectFeesTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_COLLECT_FEES, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Recipient pubkey (32 bytes)
+    data.push(ticket.recipient.toBuffer());
+    
+    // Fees
+    for (const fee of ticket.fees) {
+      data.push(this.serializeAssetAmount(fee));
+    }
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a sweep dust ticket hash for signing (keccak256)
+   */
+  createSweepDustTicketHash(ticket: SweepDustTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SWEEP_DUST, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault and destination pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.destination.toBuffer());
+    
+    // Mints (32 bytes each)
+    for (const mint of ticket.mints) {
+      data.push(mint.toBuffer());
+    }
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a close vault token account ticket hash for signing (keccak256)
+   */
+  createCloseVaultTokenAccountTicketHash(ticket: CloseVaultTokenAccountTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CLOSE_VAULT_TOKEN_ACCOUNT, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, mint and rent recipient pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.mint.toBuffer());
+    data.push(ticket.rentRecipient.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a add strategy ticket hash for signing (keccak256)
+   */
+  createAddStrategyTicketHash(ticket: AddStrategyTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_ADD_STRATEGY, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and strategy program pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.programId.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a remove strategy ticket hash for signing (keccak256)
+   */
+  createRemoveStrategyTicketHash(ticket: RemoveStrategyTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_REMOVE_STRATEGY, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and strategy program pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.programId.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create an add cranker ticket hash for signing (keccak256)
+   */
+  createAddCrankerTicketHash(ticket: AddCrankerTicket): Uint8Array {
+    return this.createCrankerTicketHash(DOMAIN_SEPARATOR_ADD_CRANKER, ticket);
+  }
+
+  /**
+   * Create a remove cranker ticket hash for signing (keccak256)
+   */
+  createRemoveCrankerTicketHash(ticket: RemoveCrankerTicket): Uint8Array {
+    return this.createCrankerTicketHash(DOMAIN_SEPARATOR_REMOVE_CRANKER, ticket);
+  }
+
+  private createCrankerTicketHash(separator: string, ticket: AddCrankerTicket | RemoveCrankerTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(separator, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and cranker pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.cranker.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create a set crank incentive ticket hash for signing (keccak256)
+   */
+  createSetCrankIncentiveTicketHash(ticket: SetCrankIncentiveTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_CRANK_INCENTIVE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Incentive in lamports (u64, little-endian)
+    const incentiveBuf = Buffer.alloc(8);
+    incentiveBuf.writeBigUInt64LE(BigInt(ticket.incentive.toString()));
+    data.push(incentiveBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create an execute strategy ticket hash for signing (keccak256)
+   */
+  createStrategyTicketHash(ticket: StrategyTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_EXECUTE_STRATEGY, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and target program pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.programId.toBuffer());
+
+    // Account metas: pubkey, is_signer, is_writable
+    for (const meta of ticket.accounts) {
+      data.push(meta.pubkey.toBuffer());
+      data.push(Buffer.from([meta.isSigner ? 1 : 0, meta.isWritable ? 1 : 0]));
+    }
+
+    // Instruction data hash (32 bytes)
+    data.push(Buffer.from(ticket.dataHash));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create an execute ticket hash for signing (keccak256)
+   */
+  createExecuteTicketHash(ticket: ExecuteTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_EXECUTE, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and target program pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.programId.toBuffer());
+
+    // Account metas: pubkey, is_signer, is_writable
+    for (const meta of ticket.accounts) {
+      data.push(meta.pubkey.toBuffer());
+      data.push(Buffer.from([meta.isSigner ? 1 : 0, meta.isWritable ? 1 : 0]));
+    }
+
+    // Instruction data hash (32 bytes)
+    data.push(Buffer.from(keccak256(ticket.data)));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Create an authorized CPI ticket hash for signing (keccak256)
+   */
+  createAuthorizedCpiTicketHash(ticket: AuthorizedCpiTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_AUTHORIZED_CPI, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Target program id (32 bytes)
+    data.push(ticket.programId.toBuffer());
+    
+    // Account metas: pubkey, is_signer, is_writable
+    for (const meta of ticket.accounts) {
+      data.push(meta.pubkey.toBuffer());
+      data.push(Buffer.from([meta.isSigner ? 1 : 0, meta.isWritable ? 1 : 0]));
+    }
+    
+    // Instruction data hash (32 bytes)
+    data.push(Buffer.from(ticket.dataHash));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Sign a withdrawal ticket with an Ethereum keypair
+   */
+  signWithdrawalTicket(ticket: WithdrawalTicket, ethKeypair: EthereumKeypair, admin?: boolean): SignerWithSignature {
+    const messageHash = this.createWithdrawalTicketHash(ticket, admin);
+    
+    // Sign with secp256k1
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    
+    // Extract r, s, and recovery ID
+    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
+    const recoveryId = sig.recovery!; // 0 or 1
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  signBulkWithdrawalTicket(
+    bulkTicket: BulkWithdrawalTicket, 
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createBulkWithdrawalTicketHash(bulkTicket);
+    
+    // Sign with secp256k1
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    
+    // Extract r, s, and recovery ID
+    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
+    const recoveryId = sig.recovery!; // 0 or 1
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a deposit ticket with an Ethereum keypair
+   */
+  signAdminDepositTicket(ticket: AdminDepositTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAdminDepositTicketHash(ticket);
+    
+    // Sign with secp256k1
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    
+    // Extract r, s, and recovery ID
+    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
+    const recoveryId = sig.recovery!; // 0 or 1
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign an add asset ticket with an Ethereum keypair
+   */
+  signAddAssetTicket(ticket: AddAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAddAssetTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign an update asset ticket with an Ethereum keypair
+   */
+  signUpdateAssetTicket(ticket: UpdateAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createUpdateAssetTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a remove asset ticket with an Ethereum keypair
+   */
+  signRemoveAssetTicket(ticket: RemoveAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createRemoveAssetTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a rotate validator ticket with an Ethereum keypair
+   */
+  signRotateValidatorTicket(
+    ticket: RotateValidatorTicket, 
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createRotateValidatorTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+  /**
+   * Sign a rotate admin signers ticket with an Ethereum keypair
+   */
+  signRotateAdminSignersTicket(
+    ticket: RotateAdminSignersTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createRotateAdminSignersTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a propose signer set ticket with an Ethereum keypair
+   */
+  signProposeSignerSetTicket(ticket: ProposeSignerSetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createProposeSignerSetTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+
+  /**
+   * Create a set allowance ticket hash for signing (keccak256)
+   */
+  createSetAllowanceTicketHash(ticket: SetAllowanceTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_ALLOWANCE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault and recipient pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.recipient.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Cap (u64, little-endian)
+    const capBuf = Buffer.alloc(8);
+    capBuf.writeBigUInt64LE(BigInt(ticket.cap.toString()));
+    data.push(capBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+  /**
+   * Create a set wormhole emitter ticket hash for signing (keccak256)
+   */
+  createSetWormholeEmitterTicketHash(ticket: SetWormholeEmitterTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_WORMHOLE_EMITTER, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault and core bridge pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.coreBridge.toBuffer());
+
+    // Emitter chain (u16, little-endian) and address (32 bytes)
+    const emitterChainBuf = Buffer.alloc(2);
+    emitterChainBuf.writeUInt16LE(ticket.emitterChain);
+    data.push(emitterChainBuf);
+    data.push(Buffer.from(ticket.emitterAddress));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  createSetTreasuryTicketHash(ticket: SetTreasuryTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_TREASURY, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Name (u32 length + utf8 bytes)
+    const nameBuf = Buffer.from(ticket.name, "utf8");
+    const nameLenBuf = Buffer.alloc(4);
+    nameLenBuf.writeUInt32LE(nameBuf.length);
+    data.push(nameLenBuf, nameBuf);
+
+    // Threshold (u8)
+    data.push(Buffer.from([ticket.mThreshold]));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+  createCreateSubVaultTicketHash(ticket: CreateSubVaultTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CREATE_SUB_VAULT, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Parent vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+
+    // Sub-vault seed (u32 length + utf8 bytes)
+    const seedBuf = Buffer.from(ticket.subVaultSeed, "utf8");
+    const seedLenBuf = Buffer.alloc(4);
+    seedLenBuf.writeUInt32LE(seedBuf.length);
+    data.push(seedLenBuf, seedBuf);
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  createReclaimSubVaultTicketHash(ticket: ReclaimSubVaultTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_RECLAIM_SUB_VAULT, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Parent and sub-vault pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.subVault.toBuffer());
+
+    // Withdrawals
+    for (const withdrawal of ticket.withdrawals) {
+      data.push(this.serializeAssetAmount(withdrawal));
+    }
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+
+
+  /**
+   * Create a set min withdrawal ticket hash for signing (keccak256)
+   */
+  createSetMinWithdrawalTicketHash(ticket: SetMinWithdrawalTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_MIN_WITHDRAWAL, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Min amount (u64, little-endian)
+    const minAmountBuf = Buffer.alloc(8);
+    minAmountBuf.writeBigUInt64LE(BigInt(ticket.minAmount.toString()));
+    data.push(minAmountBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set USD cap ticket hash for signing (keccak256)
+   */
+  createSetUsdCapTicketHash(ticket: SetUsdCapTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_USD_CAP, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Oracle
+    data.push(this.serializePriceOracle(ticket.oracle));
+    
+    // Max USD (u64, little-endian)
+    const maxUsdBuf = Buffer.alloc(8);
+    maxUsdBuf.writeBigUInt64LE(BigInt(ticket.maxUsd.toString()));
+    data.push(maxUsdBuf);
+    
+    // Max age (u64, little-endian)
+    const maxAgeBuf = Buffer.alloc(8);
+    maxAgeBuf.writeBigUInt64LE(BigInt(ticket.maxAge.toString()));
+    data.push(maxAgeBuf);
+    
+    // Max confidence bps (u16, little-endian)
+    const maxConfBpsBuf = Buffer.alloc(2);
+    maxConfBpsBuf.writeUInt16LE(ticket.maxConfBps);
+    data.push(maxConfBpsBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a set max ticket USD ticket hash for signing (keccak256)
+   */
+  createSetMaxTicketUsdTicketHash(ticket: SetMaxTicketUsdTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SET_MAX_TICKET_USD, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Max ticket USD (u64, little-endian)
+    const maxTicketUsdBuf = Buffer.alloc(8);
+    maxTicketUsdBuf.writeBigUInt64LE(BigInt(ticket.maxTicketUsd.toString()));
+    data.push(maxTicketUsdBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a create receipt mint ticket hash for signing (keccak256)
+   */
+  createCreateReceiptMintTicketHash(ticket: CreateReceiptMintTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CREATE_RECEIPT_MINT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Decimals (u8, single byte)
+    data.push(Buffer.from([ticket.decimals]));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a claim root ticket hash for signing (keccak256)
+   */
+  createClaimRootTicketHash(ticket: ClaimRootTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CLAIM_ROOT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // Merkle root (32 bytes)
+    data.push(Buffer.from(ticket.root));
+    
+    // Claim expiry (i64, little-endian)
+    const claimExpiryBuf = Buffer.alloc(8);
+    claimExpiryBuf.writeBigInt64LE(BigInt(ticket.claimExpiry.toString()));
+    data.push(claimExpiryBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Hash of a claim leaf: keccak256(0x00 || index LE || recipient || asset amount)
+   */
+  createClaimLeafHash(leaf: ClaimLeaf): Uint8Array {
+    const indexBuf = Buffer.alloc(8);
+    indexBuf.writeBigUInt64LE(BigInt(leaf.index.toString()));
+    return keccak256(
+      Buffer.concat([
+        Buffer.from([0]),
+        indexBuf,
+        leaf.recipient.toBuffer(),
+        this.serializeAssetAmount(leaf.assetAmount),
+      ])
+    );
+  }
+
+  /**
+   * Build the Merkle tree `claim` verifies: inner nodes are keccak256(0x01 || min || max) of
+   * their sorted children, an odd node is carried up unchanged. Returns the root and the proof
+   * of every leaf, in leaf order.
+   */
+  buildClaimTree(leaves: ClaimLeaf[]): { root: Uint8Array; proofs: Uint8Array[][] } {
+    let level = leaves.map(leaf => this.createClaimLeafHash(leaf));
+    const positions = leaves.map((_, i) => i);
+    const proofs: Uint8Array[][] = leaves.map(() => []);
+
+    while (level.length > 1) {
+      positions.forEach((position, i) => {
+        const sibling = level[position ^ 1];
+        if (sibling) {
+          proofs[i].push(sibling);
+        }
+        positions[i] = Math.floor(position / 2);
+      });
+
+      const next: Uint8Array[] = [];
+      for (let i = 0; i < level.length; i += 2) {
+        if (i + 1 === level.length) {
+          next.push(level[i]);
+          continue;
+        }
+        const [low, high] = Buffer.compare(Buffer.from(level[i]), Buffer.from(level[i + 1])) <= 0
+          ? [level[i], level[i + 1]]
+          : [level[i + 1], level[i]];
+        next.push(keccak256(Buffer.concat([Buffer.from([1]), Buffer.from(low), Buffer.from(high)])));
+      }
+      level = next;
+    }
+
+    return { root: level[0], proofs };
+  }
+
+  /**
+   * Create a stream ticket hash for signing (keccak256)
+   */
+  createStreamTicketHash(ticket: CreateStreamTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CREATE_STREAM, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault and recipient pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.recipient.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Amount (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.amount.toString()));
+    data.push(amountBuf);
+    
+    // Start time and cliff time (i64, little-endian)
+    const startTimeBuf = Buffer.alloc(8);
+    startTimeBuf.writeBigInt64LE(BigInt(ticket.startTime.toString()));
+    data.push(startTimeBuf);
+    const cliffTimeBuf = Buffer.alloc(8);
+    cliffTimeBuf.writeBigInt64LE(BigInt(ticket.cliffTime.toString()));
+    data.push(cliffTimeBuf);
+    
+    // Rate (u64, little-endian)
+    const rateBuf = Buffer.alloc(8);
+    rateBuf.writeBigUInt64LE(BigInt(ticket.rate.toString()));
+    data.push(rateBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Create a schedule ticket hash for signing (keccak256)
+   */
+  createScheduleTicketHash(ticket: CreateScheduleTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CREATE_SCHEDULE, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault and recipient pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.recipient.toBuffer());
+    
+    // Asset
+    data.push(this.serializeAsset(ticket.asset));
+    
+    // Amount (u64, little-endian)
+    const amountBuf = Buffer.alloc(8);
+    amountBuf.writeBigUInt64LE(BigInt(ticket.amount.toString()));
+    data.push(amountBuf);
+    
+    // Interval and first execution (i64, little-endian)
+    const intervalBuf = Buffer.alloc(8);
+    intervalBuf.writeBigInt64LE(BigInt(ticket.interval.toString()));
+    data.push(intervalBuf);
+    const firstExecutionBuf = Buffer.alloc(8);
+    firstExecutionBuf.writeBigInt64LE(BigInt(ticket.firstExecution.toString()));
+    data.push(firstExecutionBuf);
+    
+    // Payments (u32, little-endian)
+    const paymentsBuf = Buffer.alloc(4);
+    paymentsBuf.writeUInt32LE(ticket.payments);
+    data.push(paymentsBuf);
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Sign a schedule ticket with an Ethereum keypair
+   */
+  signCreateScheduleTicket(ticket: CreateScheduleTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createScheduleTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a stream ticket with an Ethereum keypair
+   */
+  signCreateStreamTicket(ticket: CreateStreamTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createStreamTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Create a credit ticket hash for signing (keccak256)
+   */
+  createCreditTicketHash(ticket: CreditTicket): Uint8Array {
+    const data: Buffer[] = [];
+    
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_CREDIT_DEPOSIT, ticket.version));
+    
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+    
+    // Vault pubkey (32 bytes)
+    data.push(ticket.vault.toBuffer());
+    
+    // User pubkey (32 bytes)
+    data.push(ticket.user.toBuffer());
+    
+    // Credits (u32 count, then each line)
+    const countBuf = Buffer.alloc(4);
+    countBuf.writeUInt32LE(ticket.credits.length);
+    data.push(countBuf);
+    for (const credit of ticket.credits) {
+      data.push(this.serializeAssetAmount(credit));
+    }
+    
+    // Source ref (32 bytes)
+    data.push(Buffer.from(ticket.sourceRef));
+    
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+    
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+    
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+    
+    // Concatenate all data
+    const combined = Buffer.concat(data);
+    
+    // Hash using keccak256 (Ethereum compatible)
+    return keccak256(combined);
+  }
+
+  /**
+   * Sign a claim root ticket with an Ethereum keypair
+   */
+  signClaimRootTicket(ticket: ClaimRootTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createClaimRootTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a credit ticket with an Ethereum keypair
+   */
+  signCreditTicket(ticket: CreditTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createCreditTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a create receipt mint ticket with an Ethereum keypair
+   */
+  signCreateReceiptMintTicket(
+    ticket: CreateReceiptMintTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createCreateReceiptMintTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a add recipient ticket with an Ethereum keypair
+   */
+  signAddRecipientTicket(ticket: AddRecipientTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAddRecipientTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a remove recipient ticket with an Ethereum keypair
+   */
+  signRemoveRecipientTicket(ticket: RemoveRecipientTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createRemoveRecipientTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a block recipient ticket with an Ethereum keypair
+   */
+  signBlockRecipientTicket(ticket: BlockRecipientTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createBlockRecipientTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign an unblock recipient ticket with an Ethereum keypair
+   */
+  signUnblockRecipientTicket(ticket: UnblockRecipientTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createUnblockRecipientTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set recipient allowlist ticket with an Ethereum keypair
+   */
+  signSetRecipientAllowlistTicket(ticket: SetRecipientAllowlistTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetRecipientAllowlistTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set fee ticket with an Ethereum keypair
+   */
+  signSetFeeTicket(ticket: SetFeeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetFeeTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set relayer rebate ticket with an Ethereum keypair
+   */
+  signSetRelayerRebateTicket(
+    ticket: SetRelayerRebateTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetRelayerRebateTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set max ticket lifetime ticket with an Ethereum keypair
+   */
+  signSetMaxTicketLifetimeTicket(
+    ticket: SetMaxTicketLifetimeTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetMaxTicketLifetimeTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set nonce tree ticket with an Ethereum keypair
+   */
+  signSetNonceTreeTicket(
+    ticket: SetNonceTreeTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetNonceTreeTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set expiry grace ticket with an Ethereum keypair
+   */
+  signSetExpiryGraceTicket(
+    ticket: SetExpiryGraceTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetExpiryGraceTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set extra network ids ticket with an Ethereum keypair
+   */
+  signSetExtraNetworkIdsTicket(
+    ticket: SetExtraNetworkIdsTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetExtraNetworkIdsTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Create a sweep asset ticket hash for signing (keccak256)
+   */
+  createSweepAssetTicketHash(ticket: SweepAssetTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_SWEEP_ASSET, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, mint and destination pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.mint.toBuffer());
+    data.push(ticket.destination.toBuffer());
+
+    // Close account (bool as u8)
+    data.push(Buffer.from([ticket.closeAccount ? 1 : 0]));
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Sign a sweep asset ticket with an Ethereum keypair
+   */
+  signSweepAssetTicket(ticket: SweepAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSweepAssetTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Create an upgrade program ticket hash for signing (keccak256)
+   */
+  createUpgradeProgramTicketHash(ticket: UpgradeProgramTicket): Uint8Array {
+    const data: Buffer[] = [];
+
+    // Domain separator
+    data.push(this.domainData(DOMAIN_SEPARATOR_UPGRADE_PROGRAM, ticket.version));
+
+    // Request ID (u64, little-endian)
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
+    data.push(requestIdBuf);
+
+    // Vault, buffer and spill pubkeys (32 bytes each)
+    data.push(ticket.vault.toBuffer());
+    data.push(ticket.buffer.toBuffer());
+    data.push(ticket.spill.toBuffer());
+
+    // Expiry (i64, little-endian)
+    const expiryBuf = Buffer.alloc(8);
+    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
+    data.push(expiryBuf);
+
+    // Network ID (u64, little-endian)
+    const networkIdBuf = Buffer.alloc(8);
+    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
+    data.push(networkIdBuf);
+
+    // Not-before: presence byte, then i64 (little-endian)
+    data.push(this.notBeforeData(ticket.notBefore));
+
+    return keccak256(Buffer.concat(data));
+  }
+
+  /**
+   * Sign an upgrade program ticket with an Ethereum keypair
+   */
+  signUpgradeProgramTicket(ticket: UpgradeProgramTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createUpgradeProgramTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a sweep dust ticket with an Ethereum keypair
+   */
+  signSweepDustTicket(ticket: SweepDustTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSweepDustTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a close vault token account ticket with an Ethereum keypair
+   */
+  signCloseVaultTokenAccountTicket(
+    ticket: CloseVaultTokenAccountTicket,
+    ethKeypair: EthereumKeypair,
+  ): SignerWithSignature {
+    const messageHash = this.createCloseVaultTokenAccountTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a collect fees ticket with an Ethereum keypair
+   */
+  signCollectFeesTicket(ticket: CollectFeesTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createCollectFeesTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set allowance ticket with an Ethereum keypair
+   */
+  signSetAllowanceTicket(
+    ticket: SetAllowanceTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetAllowanceTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set wormhole emitter ticket with an Ethereum keypair
+   */
+  signSetWormholeEmitterTicket(ticket: SetWormholeEmitterTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetWormholeEmitterTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set treasury ticket with an Ethereum keypair
+   */
+  signSetTreasuryTicket(ticket: SetTreasuryTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetTreasuryTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a create sub-vault ticket with an Ethereum keypair
+   */
+  signCreateSubVaultTicket(ticket: CreateSubVaultTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createCreateSubVaultTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a reclaim sub-vault ticket with an Ethereum keypair
+   */
+  signReclaimSubVaultTicket(ticket: ReclaimSubVaultTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createReclaimSubVaultTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set auth scheme ticket with an Ethereum keypair
+   */
+  signSetAuthSchemeTicket(ticket: SetAuthSchemeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetAuthSchemeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set hook program ticket with an Ethereum keypair
+   */
+  signSetHookProgramTicket(ticket: SetHookProgramTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetHookProgramTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set deposit registry ticket with an Ethereum keypair
+   */
+  signSetDepositRegistryTicket(ticket: SetDepositRegistryTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetDepositRegistryTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set identity network ticket with an Ethereum keypair
+   */
+  signSetIdentityNetworkTicket(ticket: SetIdentityNetworkTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetIdentityNetworkTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set strict signatures ticket with an Ethereum keypair
+   */
+  signSetStrictSignaturesTicket(ticket: SetStrictSignaturesTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetStrictSignaturesTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set replay mode ticket with an Ethereum keypair
+   */
+  signSetReplayModeTicket(ticket: SetReplayModeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetReplayModeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set treasury reserve ticket with an Ethereum keypair
+   */
+  signSetTreasuryReserveTicket(ticket: SetTreasuryReserveTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetTreasuryReserveTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set guardian ticket with an Ethereum keypair
+   */
+  signSetGuardianTicket(ticket: SetGuardianTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetGuardianTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a delegate stake ticket with an Ethereum keypair
+   */
+  signDelegateStakeTicket(ticket: DelegateStakeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createDelegateStakeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a deactivate stake ticket with an Ethereum keypair
+   */
+  signDeactivateStakeTicket(ticket: DeactivateStakeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createDeactivateStakeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a withdraw stake ticket with an Ethereum keypair
+   */
+  signWithdrawStakeTicket(ticket: WithdrawStakeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createWithdrawStakeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a liquid stake ticket with an Ethereum keypair
+   */
+  signLiquidStakeTicket(ticket: LiquidStakeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createLiquidStakeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a liquid unstake ticket with an Ethereum keypair
+   */
+  signLiquidUnstakeTicket(ticket: LiquidUnstakeTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createLiquidUnstakeTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a rebalance ticket with an Ethereum keypair
+   */
+  signRebalanceTicket(ticket: RebalanceTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createRebalanceTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a swap ticket with an Ethereum keypair
+   */
+  signSwapTicket(ticket: SwapTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSwapTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set min withdrawal ticket with an Ethereum keypair
+   */
+  signSetMinWithdrawalTicket(ticket: SetMinWithdrawalTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetMinWithdrawalTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set USD cap ticket with an Ethereum keypair
+   */
+  signSetUsdCapTicket(ticket: SetUsdCapTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetUsdCapTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set max ticket USD ticket with an Ethereum keypair
+   */
+  signSetMaxTicketUsdTicket(ticket: SetMaxTicketUsdTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetMaxTicketUsdTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set log verbosity ticket with an Ethereum keypair
+   */
+  signSetLogVerbosityTicket(
+    ticket: SetLogVerbosityTicket,
+    ethKeypair: EthereumKeypair
+  ): SignerWithSignature {
+    const messageHash = this.createSetLogVerbosityTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a add strategy ticket with an Ethereum keypair
+   */
+  signAddStrategyTicket(ticket: AddStrategyTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAddStrategyTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a remove strategy ticket with an Ethereum keypair
+   */
+  signRemoveStrategyTicket(ticket: RemoveStrategyTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createRemoveStrategyTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a add cranker ticket with an Ethereum keypair
+   */
+  signAddCrankerTicket(ticket: AddCrankerTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAddCrankerTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a remove cranker ticket with an Ethereum keypair
+   */
+  signRemoveCrankerTicket(ticket: RemoveCrankerTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createRemoveCrankerTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a set crank incentive ticket with an Ethereum keypair
+   */
+  signSetCrankIncentiveTicket(ticket: SetCrankIncentiveTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createSetCrankIncentiveTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign a strategy ticket with an Ethereum keypair
+   */
+  signStrategyTicket(ticket: StrategyTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createStrategyTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign an execute ticket with an Ethereum keypair
+   */
+  signExecuteTicket(ticket: ExecuteTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createExecuteTicketHash(ticket);
+
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Sign an authorized CPI ticket with an Ethereum keypair
+   */
+  signAuthorizedCpiTicket(ticket: AuthorizedCpiTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
+    const messageHash = this.createAuthorizedCpiTicketHash(ticket);
+    
+    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
+    const signature = sig.toCompactRawBytes();
+    const recoveryId = sig.recovery!;
+    
+    return {
+      ethAddress: ethKeypair.address,
+      signature,
+      recoveryId,
+    };
+  }
+
+  /**
+   * Create a withdrawal ticket
+   */
+  createWithdrawalTicket(
+    recipient: PublicKey,
+    withdrawals: AssetAmount[],
+    requestId: number,
+    expiryTimestamp: number,
+    memo?: string,
+  ): WithdrawalTicket {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+
+    return {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      withdrawals,
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+      memo: memo ?? null,
+    };
+  }
+
+  /**
+   * Deposit assets into the vault
+   */
+  async deposit(
+    deposits: AssetAmount[],
+    requestId: number,
+    remainingAccounts: any[] = [],
+    metadata?: string,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const user = this.provider.wallet.publicKey;
+
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+
+    const depositsArg = deposits.map(d => ({
+      asset: d.asset,
+      amount: d.amount,
+    }));
+
+    const tx = await this.program.methods
+      .deposit(depositsArg, new BN(requestId), metadata || null)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        depositRecord: this.getDepositRecordAddress(vaultPda, user)[0],
+        user: user,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([...remainingAccounts, ...this.getMintAccounts(deposits)])
+      .rpc();
+
+    console.log(`✅ Deposited assets to treasury`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Deposit SOL into the vault (convenience method)
+   */
+  async depositSol(
+    amountSol: number,
+    requestId?: number
+  ): Promise<string> {
+    const amountLamports = amountSol * LAMPORTS_PER_SOL;
+    const deposits: AssetAmount[] = [{
+      asset: { sol: {} },
+      amount: new BN(amountLamports),
+    }];
+
+    const reqId = requestId || Date.now();
+    return this.deposit(deposits, reqId);
+  }
+
+  /**
+   * Deposit wSOL into the vault. The wSOL is unwrapped and credited to the SOL treasury.
+   */
+  async depositWsol(
+    amount: BN,
+    requestId: number,
+    metadata?: string,
+    remainingAccounts: any[] = [],
+  ): Promise<string> {
+    const user = this.provider.wallet.publicKey;
+
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [tempWsolPda] = this.getWsolTempAddress(vaultPda);
+
+    const tx = await this.program.methods
+      .depositWsol(amount, new BN(requestId), metadata || null)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        userWsol: getAssociatedTokenAddressSync(NATIVE_MINT, user),
+        tempWsol: tempWsolPda,
+        depositRecord: this.getDepositRecordAddress(vaultPda, user)[0],
+        nativeMint: NATIVE_MINT,
+        user: user,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Deposited wSOL to treasury`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Withdraw SOL from the vault as wSOL to the recipient's native-mint ATA
+   */
+  async withdrawWsol(
+    ticket: WithdrawalTicket,
+    ethKeypairs: EthereumKeypair[],
+    metadata?: string,
+    skipNonce: boolean = false, // only while the vault uses watermark replay
+    withReceipt: boolean = false, // create the ticket's WithdrawalReceipt, paid by the wallet
+  ): Promise<string> {
+    const [treasuryPda] = this.getTreasuryAddress(ticket.vault);
+    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+    const [receiptPda] = this.getWithdrawalReceiptAddress(ticket.vault, ticket.requestId);
+    const [tempWsolPda] = this.getWsolTempAddress(ticket.vault);
+
+    const actualPayer = this.provider.wallet.publicKey;
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signWithdrawalTicket(ticket, kp));
+
+    const ticketArg = {
+      version: ticket.version,
+      requestId: ticket.requestId,
+      vault: ticket.vault,
+      recipient: ticket.recipient,
+      withdrawals: ticket.withdrawals,
+      expiry: ticket.expiry,
+      networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+      memo: ticket.memo ?? null,
+      treasury: ticket.treasury ?? null,
+      tokenAccounts: ticket.tokenAccounts ?? null,
+      sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
+      balanceBps: ticket.balanceBps ?? null,
+    };
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const tx = await this.program.methods
+      .withdrawWsol(ticketArg, sigsArg, metadata || null)
+      .accounts({
+        vault: ticket.vault,
+        treasury: treasuryPda,
+        feeTreasury: this.getFeeTreasuryAddress(ticket.vault)[0],
+        recipient: ticket.recipient,
+        recipientWsol: getAssociatedTokenAddressSync(NATIVE_MINT, ticket.recipient),
+        tempWsol: tempWsolPda,
+        nativeMint: NATIVE_MINT,
+        nonceAccount: skipNonce ? null : noncePda,
+        receipt: withReceipt ? receiptPda : null,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts([
+        ...this.getAllowanceAccounts([ticket]),
+        ...this.getRecipientAllowlistAccounts(ticket.vault),
+        ...this.getBlocklistAccounts(ticket.vault),
+        ...this.getMemoAccounts([ticket]),
+      ])
+      .rpc();
+
+    console.log(`✅ Withdrew SOL from vault as wSOL`);
+    console.log(`   Recipient: ${ticket.recipient.toBase58()}`);
+    console.log(`   Request ID: ${ticket.requestId.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Withdraw assets from the vault with multisig approval using tickets
+   */
+  async withdraw(
+    ticket: WithdrawalTicket,
+    ethKeypairs: EthereumKeypair[],
+    remainingAccounts: any[] = [],
+    metadata?: string,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    ed25519Keypairs: Keypair[] = [],
+    precompileEthKeypairs: EthereumKeypair[] = [],
+    skipNonce: boolean = false, // only while the vault uses watermark replay
+    withReceipt: boolean = false, // create the ticket's WithdrawalReceipt, paid by the wallet
+  ): Promise<string> {
+    const [treasuryPda] = this.getTreasuryAddress(ticket.vault);
+    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+    const [receiptPda] = this.getWithdrawalReceiptAddress(ticket.vault, ticket.requestId);
+    
+    const actualPayer = this.provider.wallet.publicKey;
+
+    // Sign the ticket with all provided signers
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signWithdrawalTicket(ticket, kp));
+
+    // Convert ticket to program format
+    const ticketArg = {
+      version: ticket.version,
+      requestId: ticket.requestId,
+      vault: ticket.vault,
+      recipient: ticket.recipient,
+      withdrawals: ticket.withdrawals,
+      expiry: ticket.expiry,
+      networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+      memo: ticket.memo ?? null,
+      treasury: ticket.treasury ?? null,
+      tokenAccounts: ticket.tokenAccounts ?? null,
+      sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
+      balanceBps: ticket.balanceBps ?? null,
+    };
+
+    // Convert signatures to program format
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const tx = await this.program.methods
+      .withdraw(ticketArg, sigsArg, metadata || null)
+      .accounts({
+        vault: ticket.vault,
+        treasury: treasuryPda,
+        feeTreasury: this.getFeeTreasuryAddress(ticket.vault)[0],
+        recipient: ticket.recipient,
+        nonceAccount: skipNonce ? null : noncePda,
+        receipt: withReceipt ? receiptPda : null,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts(ticket.withdrawals),
+        ...this.getFeeTokenAccounts(ticket.vault, ticket.withdrawals, tokenProgram),
+        ...this.getAllowanceAccounts([ticket]),
+        ...this.getRecipientAllowlistAccounts(ticket.vault),
+        ...this.getBlocklistAccounts(ticket.vault),
+        ...this.getMemoAccounts([ticket]),
+        ...this.getNamedTreasuryAccounts(ticket),
+        ...this.getInstructionsSysvarAccounts(ed25519Keypairs.length + precompileEthKeypairs.length),
+      ])
+      .preInstructions([
+        ...this.createEd25519Approvals(this.createWithdrawalTicketHash(ticket), ed25519Keypairs),
+        ...this.createSecp256k1Approvals(
+          this.createWithdrawalTicketPreimage(ticket),
+          precompileEthKeypairs,
+          ed25519Keypairs.length,
+        ),
+      ])
+      .rpc();
+
+    console.log(`✅ Withdrew assets from vault`);
+    console.log(`   Recipient: ${ticket.recipient.toBase58()}`);
+    console.log(`   Request ID: ${ticket.requestId.toString()}`);
+    console.log(`   Valid Signers: ${signersWithSigs.length + ed25519Keypairs.length}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Simulate `validate_ticket`: run every check `withdraw` would run on the ticket without
+   * spending it. Resolves to `{ ticketHash, error, validSigners, recipientBlocked }`, where
+   * `error` is the program error code the withdrawal would fail with, null when it would pass
+   */
+  async validateTicket(
+    ticket: WithdrawalTicket,
+    ethKeypairs: EthereumKeypair[],
+    remainingAccounts: any[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<any> {
+    const [treasuryPda] = this.getTreasuryAddress(ticket.vault);
+    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signWithdrawalTicket(ticket, kp));
+
+    const ticketArg = {
+      version: ticket.version,
+      requestId: ticket.requestId,
+      vault: ticket.vault,
+      recipient: ticket.recipient,
+      withdrawals: ticket.withdrawals,
+      expiry: ticket.expiry,
+      networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+      memo: ticket.memo ?? null,
+      treasury: ticket.treasury ?? null,
+      tokenAccounts: ticket.tokenAccounts ?? null,
+      sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
+      balanceBps: ticket.balanceBps ?? null,
+    };
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    return await this.program.methods
+      .validateTicket(ticketArg, sigsArg)
+      .accounts({
+        vault: ticket.vault,
+        treasury: treasuryPda,
+        nonceAccount: noncePda,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts(ticket.withdrawals),
+        ...this.getAllowanceAccounts([ticket]),
+        ...this.getRecipientAllowlistAccounts(ticket.vault),
+        ...this.getBlocklistAccounts(ticket.vault),
+        ...this.getNamedTreasuryAccounts(ticket),
+      ])
+      .view();
+  }
+
+  /**
+   * Withdraw with a ticket carried as the payload of a VAA from the vault's trusted Wormhole
+   * emitter, already posted to the core bridge as `postedVaa`, instead of validator signatures.
+   */
+  async withdrawWithVaa(
+    ticket: WithdrawalTicket,
+    postedVaa: PublicKey,
+    remainingAccounts: any[] = [],
+    metadata?: string,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [treasuryPda] = this.getTreasuryAddress(ticket.vault);
+    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+
+    const tx = await this.program.methods
+      .withdrawWithVaa(ticket.requestId, metadata || null)
+      .accounts({
+        vault: ticket.vault,
+        treasury: treasuryPda,
+        feeTreasury: this.getFeeTreasuryAddress(ticket.vault)[0],
+        wormholeEmitter: this.getWormholeEmitterAddress(ticket.vault)[0],
+        postedVaa,
+        recipient: ticket.recipient,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts(ticket.withdrawals),
+        ...this.getFeeTokenAccounts(ticket.vault, ticket.withdrawals, tokenProgram),
+        ...this.getAllowanceAccounts([ticket]),
+        ...this.getRecipientAllowlistAccounts(ticket.vault),
+        ...this.getBlocklistAccounts(ticket.vault),
+        ...this.getMemoAccounts([ticket]),
+      ])
+      .rpc();
+
+    console.log(`✅ Withdrew assets from vault with a VAA`);
+    console.log(`   Recipient: ${ticket.recipient.toBase58()}`);
+    console.log(`   Request ID: ${ticket.requestId.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Convenience method: Withdraw SOL with current timestamp + duration
+   */
+  async createAndExecuteWithdrawal(
+    recipient: PublicKey,
+    amountSol: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600, // 1 hour default
+  ): Promise<string> {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const withdrawals: AssetAmount[] = [{
+      asset: { sol: {} },
+      amount: new BN(amountSol * LAMPORTS_PER_SOL),
+    }];
+
+    const ticket = this.createWithdrawalTicket(
+      recipient,
+      withdrawals,
+      requestId,
+      expiryTimestamp,
+    );
+
+    return this.withdraw(ticket, ethKeypairs);
+  }
+
+  createBulkWithdrawalTicket(
+    withdrawals: Array<{
+      recipient: PublicKey;
+      withdrawals: AssetAmount[];
+      requestId: number;
+      expiryTimestamp: number;
+      memo?: string;
+    }>
+  ): BulkWithdrawalTicket {
+    const tickets = withdrawals.map(w => 
+      this.createWithdrawalTicket(
+        w.recipient,
+        w.withdrawals,
+        w.requestId,
+        w.expiryTimestamp,
+        w.memo,
+      )
+    );
+
+    return { version: TICKET_VERSION, tickets };
+  }
+
+  /**
+   * Execute bulk withdrawal with multisig approval
+   */
+  async bulkWithdraw(
+    bulkTicket: BulkWithdrawalTicket,
+    ethKeypairs: EthereumKeypair[],
+    remainingAccounts: any[] = [],
+    metadata?: string,
+  ): Promise<string> {
+    if (bulkTicket.tickets.length === 0) {
+      throw new Error("No withdrawal tickets provided");
+    }
+
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const actualPayer = this.provider.wallet.publicKey;
+
+    // Sign the bulk ticket with all provided signers
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => 
+      this.signBulkWithdrawalTicket(bulkTicket, kp)
+    );
+
+    // Build remaining accounts array:
+    // 1. First N accounts are nonce accounts (one per ticket)
+    // 2. Remaining accounts are recipients and token accounts
+    const nonceAccounts: any[] = [];
+    
+    for (const ticket of bulkTicket.tickets) {
+      const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+      nonceAccounts.push({
+        pubkey: noncePda,
+        isWritable: true,
+        isSigner: false,
+      });
+    }
+
+    // Combine nonce accounts with other remaining accounts
+    const mintAccounts = this.getMintAccounts(bulkTicket.tickets.flatMap(t => t.withdrawals));
+    const feeTokenAccounts = this.getFeeTokenAccounts(
+      vaultPda,
+      bulkTicket.tickets.flatMap(t => t.withdrawals),
+    );
+    const allowanceAccounts = this.getAllowanceAccounts(bulkTicket.tickets);
+    const memoAccounts = this.getMemoAccounts(bulkTicket.tickets);
+    const allRemainingAccounts = [
+      ...nonceAccounts,
+      ...remainingAccounts,
+      ...mintAccounts,
+      ...feeTokenAccounts,
+      ...allowanceAccounts,
+      ...this.getRecipientAllowlistAccounts(vaultPda),
+      ...this.getBlocklistAccounts(vaultPda),
+      ...memoAccounts,
+    ];
+    const accountIndexes = this.getBulkAccountIndexes(
+      vaultPda,
+      bulkTicket.tickets,
+      allRemainingAccounts,
+    );
+
+    // Convert bulk ticket to program format
+    const bulkTicketArg = {
+      version: bulkTicket.version,
+      tickets: bulkTicket.tickets.map(ticket => ({
+        version: ticket.version,
+        requestId: ticket.requestId,
+        vault: ticket.vault,
+        recipient: ticket.recipient,
+        withdrawals: ticket.withdrawals,
+        expiry: ticket.expiry,
+        networkId: ticket.networkId,
+        notBefore: ticket.notBefore ?? null,
+        memo: ticket.memo ?? null,
+        treasury: ticket.treasury ?? null,
+        tokenAccounts: ticket.tokenAccounts ?? null,
+        sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
+        balanceBps: ticket.balanceBps ?? null,
+      }))
+    };
+
+    // Convert signatures to program format
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const tx = await this.program.methods
+      .bulkWithdraw(bulkTicketArg, sigsArg, accountIndexes, metadata || null)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        feeTreasury: this.getFeeTreasuryAddress(vaultPda)[0],
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(allRemainingAccounts)
+      .rpc();
+
+    console.log(`✅ Bulk withdrawal completed`);
+    console.log(`   Number of tickets: ${bulkTicket.tickets.length}`);
+    console.log(`   Valid Signers: ${signersWithSigs.length}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Convenience method: Bulk withdraw SOL with current timestamp + duration
+   */
+  async createAndExecuteBulkWithdrawal(
+    withdrawalRequests: Array<{
+      recipient: PublicKey;
+      amountSol: number;
+      requestId: number;
+    }>,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    // Convert to withdrawal ticket format
+    const withdrawals = withdrawalRequests.map(req => ({
+      recipient: req.recipient,
+      withdrawals: [{
+        asset: { sol: {} } as const,
+        amount: new BN(req.amountSol * anchor.web3.LAMPORTS_PER_SOL),
+      }],
+      requestId: req.requestId,
+      expiryTimestamp,
+    }));
+
+    const bulkTicket = this.createBulkWithdrawalTicket(withdrawals);
+
+    // Build remaining accounts for recipients with dedup.
+    const recipientAccounts = Array.from(
+      new Map(
+        withdrawalRequests.map(req => [req.recipient.toString(), {
+          pubkey: req.recipient,
+          isWritable: true,
+          isSigner: false,
+        }])
+      ).values()
+    );
+
+    return this.bulkWithdraw(bulkTicket, ethKeypairs, recipientAccounts);
+  }
+
+  /**
+   * Publish the Merkle root of a payout batch (M-of-N, like withdrawals). Recipients redeem
+   * their leaves with `claim` until `claimWindowSeconds` have passed.
+   */
+  async publishClaimRoot(
+    root: Uint8Array,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    claimWindowSeconds: number = 7 * 24 * 3600,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getNonceAddress(vaultPda, new BN(requestId));
+    const [claimRootPda] = this.getClaimRootAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: ClaimRootTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      root: Array.from(root),
+      claimExpiry: new BN(currentTimestamp + claimWindowSeconds),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signClaimRootTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .publishClaimRoot(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        claimRoot: claimRootPda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Published claim root`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Root: 0x${Buffer.from(root).toString("hex")}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Credit the wallet's deposit record with funds that arrived for it on another chain, using a
+   * credit ticket signed by M-of-N validators (`signCreditTicket`), sorted by signer address. The wallet must be the
+   * ticket's user and pays the nonce and record rent; no funds move into the vault.
+   */
+  async claimCreditedDeposit(
+    ticket: CreditTicket,
+    signersWithSigs: SignerWithSignature[],
+  ): Promise<string> {
+    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
+    const [depositRecordPda] = this.getDepositRecordAddress(ticket.vault, ticket.user);
+
+    const ticketArg = {
+      ...ticket,
+      notBefore: ticket.notBefore ?? null,
+    };
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const tx = await this.program.methods
+      .claimCreditedDeposit(ticketArg, sigsArg)
+      .accounts({
+        vault: ticket.vault,
+        nonceAccount: noncePda,
+        depositRecord: depositRecordPda,
+        user: ticket.user,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Claimed credited deposit`);
+    console.log(`   User: ${ticket.user.toBase58()}`);
+    console.log(`   Request ID: ${ticket.requestId.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Redeem one leaf of a published claim root. Anyone may submit it (the wallet pays the rent of
+   * the claimed-leaf marker); funds always go to the leaf's recipient. SPL leaves need the
+   * vault and recipient token accounts in `remainingAccounts`, and a vault with USD caps the
+   * leaf's `getUsdCapAccounts`.
+   */
+  async claim(
+    claimRequestId: BN,
+    leaf: ClaimLeaf,
+    proof: Uint8Array[],
+    remainingAccounts: any[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [claimRootPda] = this.getClaimRootAddress(vaultPda, claimRequestId);
+    const [claimedLeafPda] = this.getClaimedLeafAddress(claimRootPda, leaf.index);
+    const [allowancePda] = this.getAllowanceAddress(vaultPda, leaf.recipient, leaf.assetAmount.asset);
+
+    const tx = await this.program.methods
+      .claim(leaf, proof.map(node => Array.from(node)))
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        feeTreasury: this.getFeeTreasuryAddress(vaultPda)[0],
+        claimRoot: claimRootPda,
+        claimedLeaf: claimedLeafPda,
+        recipient: leaf.recipient,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts([leaf.assetAmount]),
+        ...this.getFeeTokenAccounts(vaultPda, [leaf.assetAmount], tokenProgram),
+        { pubkey: allowancePda, isWritable: true, isSigner: false },
+        ...this.getRecipientAllowlistAccounts(vaultPda),
+        ...this.getBlocklistAccounts(vaultPda),
+      ])
+      .rpc();
+
+    console.log(`✅ Claimed leaf ${leaf.index.toString()} of claim root ${claimRequestId.toString()}`);
+    console.log(`   Recipient: ${leaf.recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Lock `amount` of `asset` in a stream for `recipient` (M-of-N, like withdrawals). Nothing
+   * vests for `cliffSeconds`; then `rate` per second since creation vests. SPL streams need the
+   * vault and stream token accounts in `remainingAccounts`.
+   */
+  async createStream(
+    recipient: PublicKey,
+    asset: Asset,
+    amount: BN,
+    cliffSeconds: number,
+    rate: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    remainingAccounts: any[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [noncePda] = this.getNonceAddress(vaultPda, new BN(requestId));
+    const [streamPda] = this.getStreamAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: CreateStreamTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      asset,
+      amount,
+      startTime: new BN(currentTimestamp),
+      cliffTime: new BN(currentTimestamp + cliffSeconds),
+      rate,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCreateStreamTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .createStream(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        stream: streamPda,
+        recipient,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts([{ asset, amount }]),
+        ...this.getRecipientAllowlistAccounts(vaultPda),
+        ...this.getBlocklistAccounts(vaultPda),
+      ])
+      .rpc();
+
+    console.log(`✅ Created stream`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Recipient: ${recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Pay a stream's recipient everything vested since the last claim. Anyone may submit it, or
+   * only registered crankers once the vault has a crank registry; the wallet cranks. SPL streams
+   * need the stream and recipient token accounts in `remainingAccounts`.
+   */
+  async claimStream(
+    streamRequestId: BN,
+    remainingAccounts: any[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [streamPda] = this.getStreamAddress(vaultPda, streamRequestId);
+    const stream = await this.program.account.stream.fetch(streamPda);
+
+    const tx = await this.program.methods
+      .claimStream()
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        stream: streamPda,
+        recipient: stream.recipient,
+        cranker: this.provider.wallet.publicKey,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts([{ asset: stream.asset as Asset, amount: stream.total }]),
+        ...this.getCrankRegistryAccounts(vaultPda),
+      ])
+      .rpc();
+
+    console.log(`✅ Claimed stream ${streamRequestId.toString()}`);
+    console.log(`   Recipient: ${stream.recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Approve `payments` recurring payments of `amount` to `recipient`, one every
+   * `intervalSeconds` starting now (M-of-N, like withdrawals). Each is paid by `executeSchedule`.
+   */
+  async createSchedule(
+    recipient: PublicKey,
+    asset: Asset,
+    amount: BN,
+    intervalSeconds: number,
+    payments: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getNonceAddress(vaultPda, new BN(requestId));
+    const [schedulePda] = this.getScheduleAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: CreateScheduleTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      asset,
+      amount,
+      interval: new BN(intervalSeconds),
+      firstExecution: new BN(currentTimestamp),
+      payments,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCreateScheduleTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .createSchedule(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        schedule: schedulePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Created schedule`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Recipient: ${recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Pay the next due period of a schedule. Anyone may submit it, or only registered crankers
+   * once the vault has a crank registry; the wallet cranks. SPL schedules need the vault and
+   * recipient token accounts in `remainingAccounts`.
+   */
+  async executeSchedule(
+    scheduleRequestId: BN,
+    remainingAccounts: any[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [schedulePda] = this.getScheduleAddress(vaultPda, scheduleRequestId);
+    const schedule = await this.program.account.schedule.fetch(schedulePda);
+    const payment: AssetAmount = { asset: schedule.asset as Asset, amount: schedule.amount };
+    const [allowancePda] = this.getAllowanceAddress(vaultPda, schedule.recipient, payment.asset);
+
+    const tx = await this.program.methods
+      .executeSchedule()
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        schedule: schedulePda,
+        recipient: schedule.recipient,
+        cranker: this.provider.wallet.publicKey,
+        tokenProgram: tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts([payment]),
+        { pubkey: allowancePda, isWritable: true, isSigner: false },
+        ...this.getRecipientAllowlistAccounts(vaultPda),
+        ...this.getBlocklistAccounts(vaultPda),
+        ...this.getCrankRegistryAccounts(vaultPda),
+      ])
+      .rpc();
+
+    console.log(`✅ Executed schedule ${scheduleRequestId.toString()}`);
+    console.log(`   Recipient: ${schedule.recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Fetch vault account data
+   */
+  async getVaultData() {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const vaultAccount = await this.program.account.vault.fetch(vaultPda);
+
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const balance = await this.provider.connection.getBalance(treasuryPda);
+
+    return {
+      address: vaultPda,
+      authority: vaultAccount.authority,
+      mThreshold: vaultAccount.mThreshold,
+      adminThreshold: vaultAccount.adminThreshold,
+      signers: vaultAccount.signers,
+      adminSigners: vaultAccount.adminSigners,
+      whitelistedAssets: vaultAccount.whitelistedAssets.map((config: AssetConfig) => config.asset),
+      assetConfigs: vaultAccount.whitelistedAssets as AssetConfig[],
+      minWithdrawals: vaultAccount.minWithdrawals,
+      domainSuffix: vaultAccount.domainSuffix,
+      logVerbosity: vaultAccount.logVerbosity,
+      processedRoot: Buffer.from(vaultAccount.processedRoot),
+      processedCount: vaultAccount.processedCount,
+      sequence: vaultAccount.sequence,
+      watermarkReplay: vaultAccount.watermarkReplay,
+      requestIdWatermark: vaultAccount.requestIdWatermark,
+      feeBps: vaultAccount.feeBps,
+      feeMint: vaultAccount.feeMint,
+      maxRelayerRebate: vaultAccount.maxRelayerRebate,
+      maxTicketLifetime: vaultAccount.maxTicketLifetime,
+      hookProgram: vaultAccount.hookProgram,
+      depositRegistry: vaultAccount.depositRegistry,
+      depositSchema: vaultAccount.depositSchema,
+      identityNetwork: vaultAccount.identityNetwork,
+      authScheme: vaultAccount.authScheme,
+      hasUsdCaps: vaultAccount.hasUsdCaps,
+      maxTicketUsd: vaultAccount.maxTicketUsd,
+      recipientAllowlist: vaultAccount.recipientAllowlist,
+      hasBlocklist: vaultAccount.hasBlocklist,
+      hasCrankRegistry: vaultAccount.hasCrankRegistry,
+      bump: vaultAccount.bump,
+      balanceSol: balance / LAMPORTS_PER_SOL,
+      balanceLamports: balance,
+    };
+  }
+
+  /**
+   * Extend the vault's processed-request hash chain by one consumed request id:
+   * keccak256(root || nonce seed || request_id LE), where the nonce seed is "admin_nonce" for
+   * admin tickets and "nonce" for withdrawals
+   */
+  nextProcessedRoot(root: Uint8Array, requestId: BN, admin: boolean = false): Uint8Array {
+    const requestIdBuf = Buffer.alloc(8);
+    requestIdBuf.writeBigUInt64LE(BigInt(requestId.toString()));
+    return keccak256(
+      Buffer.concat([
+        Buffer.from(root),
+        Buffer.from(admin ? "admin_nonce" : "nonce", "utf8"),
+        requestIdBuf,
+      ])
+    );
+  }
+
+  /**
+   * Fetch the cumulative deposits of a user (defaults to the wallet), or null before their
+   * first deposit
+   */
+  async getDepositRecord(user: PublicKey = this.provider.wallet.publicKey) {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [depositRecordPda] = this.getDepositRecordAddress(vaultPda, user);
+    return await this.program.account.depositRecord.fetchNullable(depositRecordPda);
+  }
+
+  /**
+   * Fetch the allowance of a recipient for an asset, or null when it is uncapped
+   */
+  async getAllowance(recipient: PublicKey, asset: Asset) {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [allowancePda] = this.getAllowanceAddress(vaultPda, recipient, asset);
+    return await this.program.account.allowance.fetchNullable(allowancePda);
+  }
+
+  /**
+   * Fetch the recipient allowlist, or null if no recipient was ever added
+   */
+  async getRecipientAllowlist() {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [allowlistPda] = this.getRecipientAllowlistAddress(vaultPda);
+    return await this.program.account.recipientAllowlist.fetchNullable(allowlistPda);
+  }
+
+  /**
+   * Fetch the blocklist, or null if no recipient was ever blocked
+   */
+  async getBlocklist() {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [blocklistPda] = this.getBlocklistAddress(vaultPda);
+    return await this.program.account.blocklist.fetchNullable(blocklistPda);
+  }
+
+  /**
+   * Fetch the receipt of an executed withdrawal ticket, or null if it wasn't executed or was
+   * executed without a receipt
+   */
+  async getWithdrawalReceipt(requestId: BN) {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [receiptPda] = this.getWithdrawalReceiptAddress(vaultPda, requestId);
+    return await this.program.account.withdrawalReceipt.fetchNullable(receiptPda);
+  }
+
+  /**
+   * Fetch a published claim root, or null if none was published under the request id
+   */
+  async getClaimRoot(requestId: BN) {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [claimRootPda] = this.getClaimRootAddress(vaultPda, requestId);
+    return await this.program.account.claimRoot.fetchNullable(claimRootPda);
+  }
+
+  /**
+   * Decode the program events (DepositEvent, WithdrawEvent, BatchSummaryEvent, ...) of a
+   * confirmed transaction: `emit_cpi!` events carried by the program's self-invocations,
+   * followed by the admin events still emitted to the logs
+   */
+  async getTransactionEvents(signature: string): Promise<anchor.Event[]> {
+    const tx = await this.provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (!tx) {
+      return [];
+    }
+
+    const events: anchor.Event[] = [];
+    const accountKeys = tx.transaction.message.getAccountKeys({
+      accountKeysFromLookups: tx.meta?.loadedAddresses,
+    });
+    for (const inner of tx.meta?.innerInstructions ?? []) {
+      for (const ix of inner.instructions) {
+        if (!accountKeys.get(ix.programIdIndex)?.equals(this.program.programId)) continue;
+        const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+        if (!data.subarray(0, 8).equals(EVENT_IX_TAG)) continue;
+        const event = this.program.coder.events.decode(data.subarray(8).toString("base64"));
+        if (event) events.push(event);
+      }
+    }
+
+    const parser = new anchor.EventParser(this.program.programId, new anchor.BorshCoder(this.program.idl));
+    events.push(...parser.parseLogs(tx.meta?.logMessages ?? []));
+    return events;
+  }
+
+  /**
+   * Get treasury balance in SOL
+   */
+  async getTreasuryBalance(): Promise<number> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const balance = await this.provider.connection.getBalance(treasuryPda);
+    return balance / LAMPORTS_PER_SOL;
+  }
+
+  /**
+   * Check if an Ethereum address is a valid signer for the vault
+   */
+  async isValidSigner(
+    ethAddress: Uint8Array
+  ): Promise<boolean> {
+    const vaultData = await this.getVaultData();
+    return vaultData.signers.some((s: { address: number[] }) => 
+      s.address.every((byte, idx) => byte === ethAddress[idx])
+    );
+  }
+
+  /**
+   * Execute `instruction` with the vault PDA as signer once signers weighing `m_threshold`
+   * approve it. Uses a withdrawal request id, so the guardian can veto it. Requires the program
+   * to be built with the `authorized-cpi` feature.
+   */
+  async execute(
+    instruction: anchor.web3.TransactionInstruction,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: ExecuteTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      programId: instruction.programId,
+      accounts: instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isSigner: k.isSigner,
+        isWritable: k.isWritable,
+      })),
+      data: Buffer.from(instruction.data),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signExecuteTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    // The vault PDA signs inside the program, so it is never a transaction signer here.
+    const remainingAccounts = [
+      { pubkey: instruction.programId, isWritable: false, isSigner: false },
+      ...instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isWritable: k.isWritable,
+        isSigner: k.isSigner && !k.pubkey.equals(vaultPda),
+      })),
+    ];
+
+    // Not in the default IDL: the instruction only exists behind the `authorized-cpi` feature.
+    const tx = await (this.program.methods as any)
+      .execute(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Executed instruction`);
+    console.log(`   Program: ${instruction.programId.toBase58()}`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Check if a nonce has been used
+   */
+  async isNonceUsed(vaultPda: PublicKey, requestId: BN): Promise<boolean> {
+    // Ids at or below the watermark are consumed whether or not they have a nonce account.
+    const vaultAccount = await this.program.account.vault.fetch(vaultPda);
+    if (requestId.lte(vaultAccount.requestIdWatermark)) {
+      return true;
+    }
+    try {
+      const [noncePda] = this.getNonceAddress(vaultPda, requestId);
+      const nonceAccount = await this.program.account.nonceAccount.fetch(noncePda);
+      return nonceAccount.used;
+    } catch (error) {
+      // Nonce account doesn't exist yet
+      return false;
+    }
+  }
+
+  /**
+   * Hash of the withdrawal-side ticket that consumed `requestId`, all zeros when the guardian
+   * vetoed it, null while it is unused. Compare it with a ticket's hash to tell a replay from a
+   * different ticket signed under the same request id.
+   */
+  async getNonceTicketHash(vaultPda: PublicKey, requestId: BN): Promise<Uint8Array | null> {
+    try {
+      const [noncePda] = this.getNonceAddress(vaultPda, requestId);
+      const nonceAccount = await this.program.account.nonceAccount.fetch(noncePda);
+      return nonceAccount.used ? Uint8Array.from(nonceAccount.ticketHash) : null;
+    } catch (error) {
+      // Nonce account doesn't exist yet
+      return null;
+    }
+  }
+
+  /**
+   * Check if an admin nonce has been used
+   */
+  async isAdminNonceUsed(vaultPda: PublicKey, requestId: BN): Promise<boolean> {
+    try {
+      const [noncePda] = this.getAdminNonceAddress(vaultPda, requestId);
+      const nonceAccount = await this.program.account.nonceAccount.fetch(noncePda);
+      return nonceAccount.used;
+    } catch (error) {
+      // Nonce account doesn't exist yet
+      return false;
+    }
+  }
+
+  /**
+   * Vault configuration and treasury lamports from the `get_vault_info` view, read from the
+   * return data of a simulated transaction
+   */
+  async viewVaultInfo(): Promise<any> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+
+    return await this.program.methods
+      .getVaultInfo()
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+      } as any)
+      .view();
+  }
+
+  /**
+   * Status of `requestId` from the `get_nonce_status` view: whether it is used, vetoed, and the
+   * hash of the ticket that consumed it. `admin` reads the admin op nonce instead
+   */
+  async viewNonceStatus(requestId: BN, admin: boolean = false): Promise<any> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = admin
+      ? this.getAdminNonceAddress(vaultPda, requestId)
+      : this.getNonceAddress(vaultPda, requestId);
+
+    return await this.program.methods
+      .getNonceStatus(requestId, admin)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+      } as any)
+      .view();
+  }
+
+  /**
+   * Hash of a ticket as the program computes it, from the `compute_ticket_hash` view. `kind` is
+   * the `AnyTicket` variant in camelCase (e.g. "withdrawal", "addAsset") and `ticketArg` the
+   * ticket in program format. Compare it with the local `create*TicketHash` result to check the
+   * client's hashing byte for byte
+   */
+  async computeTicketHashOnChain(kind: string, ticketArg: any): Promise<Uint8Array> {
+    const hash = await this.program.methods
+      .computeTicketHash({ [kind]: { ticket: ticketArg } } as any, this.domainSuffix)
+      .accounts({} as any)
+      .view();
+    return Uint8Array.from(hash);
+  }
+
+  /**
+   * Whitelist entry of `asset` from the `get_asset_config` view, null while it isn't whitelisted
+   */
+  async viewAssetConfig(asset: any): Promise<AssetConfig | null> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+
+    return await this.program.methods
+      .getAssetConfig(asset)
+      .accounts({
+        vault: vaultPda,
+      } as any)
+      .view();
+  }
+}
+
+export class MultisigAdminClient extends MultisigVaultClient {
+  constructor(
+    program: Program<StrikeExample>,
+    provider: anchor.AnchorProvider,
+    vaultSeed: string,
+    networkId: number,
+    domainSuffix: string = "",
+    parentVault: PublicKey | null = null,
+  ) {
+    super(program, provider, vaultSeed, networkId, domainSuffix, parentVault);
+  }
+
+  /**
+   * Initialize a new multisig vault with Ethereum addresses. Thresholds are compared against the
+   * summed weights of the signers that sign; `weights` defaults to 1 per signer. `validUntil`
+   * optionally sets a unix timestamp after which a signer's signatures stop counting.
+   * `adminSigners`, when non-empty, is a separate set that alone approves admin ops with
+   * `adminThreshold`; otherwise the operational signers approve them too. `authScheme` picks
+   * what approves tickets, secp256k1 signatures by default. `bindGenesis` replaces the network
+   * id with the one derived from the cluster's genesis hash and binds the vault to it.
+   */
+  async initialize(
+    mThreshold: number,
+    adminThreshold: number,
+    ethAddresses: Uint8Array[], // Array of 20-byte Ethereum addresses
+    weights: number[] = ethAddresses.map(() => 1),
+    validUntil: (number | null)[] = ethAddresses.map(() => null),
+    adminSigners: WeightedSigner[] = [],
+    authScheme: AuthScheme = { secp256k1: {} },
+    bindGenesis: boolean = false,
+  ): Promise<{ signature: string; vaultAddress: PublicKey }> {
+    const authority = this.provider.wallet.publicKey;
+    const genesisHash = bindGenesis ? Array.from(await this.bindToGenesis()) : null;
+
+    const [vaultPda, bump] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+
+    // Convert to arrays for Anchor
+    const signersArray = ethAddresses.map((addr, i) => ({
+      address: Array.from(addr),
+      weight: weights[i],
+      validUntil: validUntil[i] === null ? null : new BN(validUntil[i]!),
+    }));
+
+    const tx = await this.program.methods
+      .initialize(
+        this.vaultSeed,
+        new BN(this.networkId),
+        mThreshold,
+        adminThreshold,
+        signersArray,
+        adminSigners.map(s => ({
+          address: Array.from(s.address),
+          weight: s.weight,
+          validUntil: s.validUntil,
+        })),
+        this.domainSuffix,
+        authScheme,
+        genesisHash,
+      )
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Vault initialized: ${vaultPda.toBase58()}`);
+    console.log(`   Treasury: ${treasuryPda.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+    console.log(`   M-of-N: ${mThreshold} of ${weights.reduce((a, b) => a + b, 0)} weight`);
+
+    return {
+      signature: tx,
+      vaultAddress: vaultPda,
+    };
+  }
+
+  /**
+   * Initialize a vault, create its token accounts for `assets` and whitelist them in one
+   * transaction. Every SPL/NFT mint in `assets` must belong to `tokenProgram`.
+   */
+  async initializeFull(
+    mThreshold: number,
+    adminThreshold: number,
+    signers: WeightedSigner[],
+    assets: Asset[],
+    adminSigners: WeightedSigner[] = [],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    authScheme: AuthScheme = { secp256k1: {} },
+    bindGenesis: boolean = false,
+  ): Promise<{ signature: string; vaultAddress: PublicKey }> {
+    const authority = this.provider.wallet.publicKey;
+    const genesisHash = bindGenesis ? Array.from(await this.bindToGenesis()) : null;
+
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+
+    const toArg = (s: WeightedSigner) => ({
+      address: Array.from(s.address),
+      weight: s.weight,
+      validUntil: s.validUntil,
+    });
+
+    // Mint and vault token account for every token asset
+    const tokenAccounts = this.getMintAccounts(assets.map(asset => ({ asset, amount: new BN(0) })))
+      .flatMap(({ pubkey }) => [
+        { pubkey, isWritable: false, isSigner: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(pubkey, vaultPda, true, tokenProgram),
+          isWritable: true,
+          isSigner: false,
+        },
+      ]);
+
+    const tx = await this.program.methods
+      .initializeFull(
+        this.vaultSeed,
+        new BN(this.networkId),
+        mThreshold,
+        adminThreshold,
+        signers.map(toArg),
+        adminSigners.map(toArg),
+        this.domainSuffix,
+        authScheme,
+        genesisHash,
+        assets,
+      )
+      .accounts({
+        base: {
+          vault: vaultPda,
+          treasury: treasuryPda,
+          authority: authority,
+          systemProgram: SystemProgram.programId,
+        },
+        tokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(tokenAccounts)
+      .rpc();
+
+    console.log(`✅ Vault initialized with ${assets.length} assets: ${vaultPda.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return {
+      signature: tx,
+      vaultAddress: vaultPda,
+    };
+  }
+
+  /**
+   * Create the vault's associated token accounts for `mints` in one transaction. Accounts that
+   * already exist are left as they are. Every mint must be whitelisted and belong to
+   * `tokenProgram`.
+   */
+  async createVaultTokenAccounts(
+    mints: PublicKey[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+
+    // Mint and vault token account for every mint
+    const tokenAccounts = mints.flatMap(mint => [
+      { pubkey: mint, isWritable: false, isSigner: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, vaultPda, true, tokenProgram),
+        isWritable: true,
+        isSigner: false,
+      },
+    ]);
+
+    const tx = await this.program.methods
+      .createVaultTokenAccounts(mints)
+      .accounts({
+        vault: vaultPda,
+        payer: this.provider.wallet.publicKey,
+        tokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(tokenAccounts)
+      .rpc();
+
+    console.log(`✅ Created vault token accounts for ${mints.length} mints`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Add an asset to the vault whitelist
+   */
+  async addAsset(
+    asset: Asset,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    settings: AssetSettings = NO_ASSET_LIMITS,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    
+    const actualPayer = this.provider.wallet.publicKey;
+    
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const ticket: AddAssetTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      asset,
+      settings,
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+    };
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signAddAssetTicket(ticket, kp));
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    // The program reads a token asset's decimals from its mint.
+    const tx = await this.program.methods
+      .addAsset(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(this.getMintAccounts([{ asset, amount: new BN(0) }]))
+      .rpc();
+
+    console.log(`✅ Added asset to whitelist`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Change the deposit and withdrawal limits of a whitelisted asset
+   */
+  async updateAsset(
+    asset: Asset,
+    settings: AssetSettings,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    
+    const actualPayer = this.provider.wallet.publicKey;
+    
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const ticket: UpdateAssetTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      asset,
+      settings,
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+    };
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signUpdateAssetTicket(ticket, kp));
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const tx = await this.program.methods
+      .updateAsset(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Updated asset settings`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Remove an asset from the vault whitelist
+   */
+  async removeAsset(
+    asset: Asset,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    
+    const actualPayer = this.provider.wallet.publicKey;
+    
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const ticket: RemoveAssetTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      asset,
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+    };
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signRemoveAssetTicket(ticket, kp));
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    // Token assets must be drained first; the program checks the vault's token account.
+    const remainingAccounts = this.getMintAccounts([{ asset, amount: new BN(0) }])
+      .flatMap(({ pubkey }) => [
+        { pubkey, isWritable: false, isSigner: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(pubkey, vaultPda, true, tokenProgram),
+          isWritable: false,
+          isSigner: false,
+        },
+      ]);
+
+    const tx = await this.program.methods
+      .removeAsset(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Removed asset from whitelist`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  async rotateValidators(
+    newSigners: Uint8Array[], // Array of 20-byte Ethereum addresses
+    newMThreshold: number,
+    newAdminThreshold: number,
+    requestId: number,
+    currentEthKeypairs: EthereumKeypair[], // Current validators signing the change
+    expiryDurationSeconds: number = 3600,
+    newWeights: number[] = newSigners.map(() => 1), // Voting weight per new signer
+    newValidUntil: (number | null)[] = newSigners.map(() => null), // Optional expiry per new signer
+  ): Promise<string> {
+    // Validation
+    if (newSigners.length === 0 || newSigners.length > MAX_SIGNERS) {
+      throw new Error(`Invalid signers count: ${newSigners.length} (must be 1-${MAX_SIGNERS})`);
+    }
+    if (
+      newWeights.length !== newSigners.length ||
+      newWeights.some(w => !Number.isInteger(w) || w < 1 || w > 255)
+    ) {
+      throw new Error("Invalid signer weight (need one weight of 1-255 per signer)");
+    }
+    if (newValidUntil.length !== newSigners.length) {
+      throw new Error("Invalid signer expiry (need one entry per signer)");
+    }
+    
+    const totalWeight = newWeights.reduce((a, b) => a + b, 0);
+    if (newMThreshold <= 0 || newMThreshold > totalWeight) {
+      throw new Error(
+        `Invalid threshold: ${newMThreshold} (must be 1-${totalWeight})`
+      );
+    }
+    if (newAdminThreshold <= 0 || newAdminThreshold > totalWeight) {
+      throw new Error(
+        `Invalid admin threshold: ${newAdminThreshold} (must be 1-${totalWeight})`
+      );
+    }
+    
+    // Check for duplicate signers
+    for (let i = 0; i < newSigners.length; i++) {
+      for (let j = i + 1; j < newSigners.length; j++) {
+        if (Buffer.from(newSigners[i]).equals(Buffer.from(newSigners[j]))) {
+          throw new Error("Duplicate signer detected");
+        }
+      }
+    }
+    
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    
+    const actualPayer = this.provider.wallet.publicKey;
+    
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const ticket: RotateValidatorTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      signers: newSigners.map((address, i) => ({
+        address,
+        weight: newWeights[i],
+        validUntil: newValidUntil[i] === null ? null : new BN(newValidUntil[i]!),
+      })),
+      mThreshold: newMThreshold,
+      adminThreshold: newAdminThreshold,
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+    };
+
+    // Sign with CURRENT validators (must meet current threshold)
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(currentEthKeypairs).map(kp => 
+      this.signRotateValidatorTicket(ticket, kp)
+    );
+
+    // Convert to program format
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+    
+    // Convert new signers to arrays for Anchor
+    const signersArray = ticket.signers.map(s => ({
+      address: Array.from(s.address),
+      weight: s.weight,
+      validUntil: s.validUntil,
+    }));
+
+    const ticketArg = {
+      version: ticket.version,
+      requestId: ticket.requestId,
+      vault: ticket.vault,
+      signers: signersArray,
+      mThreshold: ticket.mThreshold,
+      adminThreshold: ticket.adminThreshold,
+      expiry: ticket.expiry,
+      networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+    };
+
+    const tx = await this.program.methods
+      .rotateValidators(ticketArg, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Validators rotated successfully`);
+    console.log(`   New validator count: ${newSigners.length}`);
+    console.log(`   New M-of-N threshold: ${newMThreshold} of ${totalWeight} weight`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Replace the admin signer set and admin threshold, leaving the operational signers untouched.
+   * An empty `signers` list hands admin ops back to the operational signers. Signed by the
+   * current admin set.
+   */
+  async rotateAdminSigners(
+    signers: WeightedSigner[],
+    adminThreshold: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: RotateAdminSignersTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      signers,
+      adminThreshold,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signRotateAdminSignersTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const ticketArg = {
+      ...ticket,
+      signers: signers.map(s => ({
+        address: Array.from(s.address),
+        weight: s.weight,
+        validUntil: s.validUntil,
+      })),
+    };
+
+    const tx = await this.program.methods
+      .rotateAdminSigners(ticketArg, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Admin signers rotated`);
+    console.log(`   Admin signer count: ${signers.length}`);
+    console.log(`   Admin threshold: ${adminThreshold}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Move the operational signers into the signer set PDA, for federations of up to
+   * MAX_SIGNER_SET_LEN (admin threshold). Proposes the set by hash, uploads it in chunks of
+   * `chunkSize` signers and activates it, one transaction each. Afterwards every signed
+   * instruction needs `getSignerSetAccounts` in its remaining accounts; large quorums should
+   * verify through the secp256k1 precompile to fit the compute and transaction size limits.
+   */
+  async rotateToSignerSet(
+    signers: WeightedSigner[],
+    mThreshold: number,
+    adminThreshold: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    chunkSize: number = 12,
+  ): Promise<string> {
+    if (signers.length === 0 || signers.length > MAX_SIGNER_SET_LEN) {
+      throw new Error(`Invalid signers count: ${signers.length} (must be 1-${MAX_SIGNER_SET_LEN})`);
+    }
+
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [signerSetPda] = this.getSignerSetAddress(vaultPda);
+    const payer = this.provider.wallet.publicKey;
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: ProposeSignerSetTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      signersHash: this.createSignerSetHash(signers),
+      signersLen: signers.length,
+      mThreshold,
+      adminThreshold,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signProposeSignerSetTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    await this.program.methods
+      .proposeSignerSet({ ...ticket, signersHash: Array.from(ticket.signersHash) }, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        signerSet: signerSetPda,
+        nonceAccount: noncePda,
+        payer,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    for (let start = 0; start < signers.length; start += chunkSize) {
+      const chunk = signers.slice(start, start + chunkSize).map(s => ({
+        address: Array.from(s.address),
+        weight: s.weight,
+        validUntil: s.validUntil,
+      }));
+      await this.program.methods
+        .uploadSigners(chunk)
+        .accounts({
+          vault: vaultPda,
+          signerSet: signerSetPda,
+          uploader: payer,
+        } as any)
+        .rpc();
+    }
+
+    const tx = await this.program.methods
+      .activateSignerSet()
+      .accounts({
+        vault: vaultPda,
+        signerSet: signerSetPda,
+        uploader: payer,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Signer set activated`);
+    console.log(`   Signer count: ${signers.length}`);
+    console.log(`   M threshold: ${mThreshold}, admin threshold: ${adminThreshold}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Switch the vault between itemized and compact (one summary per instruction) logs
+   */
+  async setLogVerbosity(
+    logVerbosity: LogVerbosity,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetLogVerbosityTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      logVerbosity,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetLogVerbosityTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setLogVerbosity(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Log verbosity set to ${Object.keys(logVerbosity)[0]}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Add a recipient to the recipient allowlist (admin threshold), creating the allowlist on first use
+   */
+  async addRecipient(
+    recipient: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: AddRecipientTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signAddRecipientTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .addRecipient(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        recipientAllowlist: this.getRecipientAllowlistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Added recipient to allowlist`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Remove a recipient from the recipient allowlist (admin threshold)
+   */
+  async removeRecipient(
+    recipient: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: RemoveRecipientTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signRemoveRecipientTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .removeRecipient(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        recipientAllowlist: this.getRecipientAllowlistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Removed recipient from allowlist`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Turn allowlist mode on or off (admin threshold). While on, withdrawals and claims only pay
+   * recipients on the recipient allowlist.
+   */
+  async setRecipientAllowlist(
+    enabled: boolean,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetRecipientAllowlistTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      enabled,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetRecipientAllowlistTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setRecipientAllowlist(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Recipient allowlist mode ${enabled ? "enabled" : "disabled"}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Block a recipient (admin threshold), creating the blocklist on first use. Withdrawals and
+   * claims paying a blocked recipient consume their ticket without paying out
+   */
+  async blockRecipient(
+    recipient: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: BlockRecipientTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signBlockRecipientTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .blockRecipient(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        blocklist: this.getBlocklistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Blocked recipient`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Remove a recipient from the blocklist (admin threshold)
+   */
+  async unblockRecipient(
+    recipient: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: UnblockRecipientTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signUnblockRecipientTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .unblockRecipient(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        blocklist: this.getBlocklistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Unblocked recipient`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Set the protocol fee (in basis points) taken from each withdrawal line into the fee
+   * treasury (admin threshold). The fee treasury PDA is created on first use. With a `feeMint`
+   * (a whitelisted SPL token with a USD cap) fees are instead converted by the USD cap oracles
+   * and paid by the withdrawal's payer from its `feeMint` account, so lines pay out whole; leave
+   * it default to pay fees in kind.
+   */
+  async setFee(
+    feeBps: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    feeMint: PublicKey = PublicKey.default,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [feeTreasuryPda] = this.getFeeTreasuryAddress(vaultPda);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetFeeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      feeBps,
+      feeMint,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetFeeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setFee(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        feeTreasury: feeTreasuryPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Protocol fee set to ${feeBps} bps`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Refund the payer of each `withdraw` / `bulkWithdraw` its nonce rent and base transaction fee
+   * from the treasury, up to `maxRebate` lamports per instruction (admin threshold). 0 turns the
+   * rebate off.
+   */
+  async setRelayerRebate(
+    maxRebate: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetRelayerRebateTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      maxRebate,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetRelayerRebateTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setRelayerRebate(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Relayer rebate capped at ${maxRebate.toString()} lamports`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Cap how many seconds past execution a ticket's expiry may be (admin threshold); tickets
+   * signed with a longer expiry are rejected with `TicketLifetimeTooLong`. 0 removes the cap.
+   */
+  async setMaxTicketLifetime(
+    maxLifetime: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetMaxTicketLifetimeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      maxLifetime,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetMaxTicketLifetimeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setMaxTicketLifetime(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Ticket lifetime capped at ${maxLifetime.toString()} seconds`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Let tickets run up to `grace` seconds past their expiry (admin threshold), absorbing clock
+   * skew between validators and the cluster. At most `MAX_EXPIRY_GRACE`; 0 restores the hard
+   * expiry.
+   */
+  async setExpiryGrace(
+    grace: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetExpiryGraceTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      grace,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetExpiryGraceTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setExpiryGrace(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Expiry grace set to ${grace.toString()} seconds`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Record withdrawal request ids `baseRequestId` to `baseRequestId + 2^maxDepth - 1` as leaves of
+   * `nonceTree` instead of nonce accounts (admin threshold). The tree account must already be
+   * allocated for `maxDepth` and `maxBufferSize` and owned by the SPL account compression
+   * program; `baseRequestId` must lie above the vault's request id watermark.
+   */
+  async setNonceTree(
+    nonceTree: PublicKey,
+    baseRequestId: BN,
+    maxDepth: number,
+    maxBufferSize: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [nonceTreeAuthority] = this.getNonceTreeAuthorityAddress(vaultPda);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetNonceTreeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      nonceTree,
+      baseRequestId,
+      maxDepth,
+      maxBufferSize,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetNonceTreeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setNonceTree(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceTree,
+        nonceTreeAuthority,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        noopProgram: SPL_NOOP_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Nonce tree ${nonceTree.toBase58()} set for request ids from ${baseRequestId.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Replace the network ids tickets may carry besides the vault's own (admin threshold), up to
+   * `MAX_EXTRA_NETWORK_IDS`. An empty list accepts the vault's network id only.
+   */
+  async setExtraNetworkIds(
+    extraNetworkIds: BN[],
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetExtraNetworkIdsTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      extraNetworkIds,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetExtraNetworkIdsTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setExtraNetworkIds(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Extra network ids set to [${extraNetworkIds.map(id => id.toString()).join(", ")}]`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Switch what approves the vault's tickets (admin threshold, approved under the current
+   * scheme). Under an attestor, pass the attestation from `getAttestationAddress` in
+   * `remainingAccounts` instead of keypairs.
+   */
+  async setAuthScheme(
+    authScheme: AuthScheme,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    remainingAccounts: any[] = [],
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetAuthSchemeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      authScheme,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetAuthSchemeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setAuthScheme(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Auth scheme set to ${Object.keys(authScheme)[0]}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Set the program deposits and withdrawals notify, or remove it with `PublicKey.default`
+   * (admin threshold).
+   */
+  async setHookProgram(
+    hookProgram: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetHookProgramTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      hookProgram,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetHookProgramTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setHookProgram(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Hook program set to ${hookProgram.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Require depositors to present an attestation from `registry` under `schema`, or open
+   * deposits again with `PublicKey.default` (admin threshold). Depositors then pass their
+   * attestation account in `deposit`'s and `depositWsol`'s remaining accounts.
+   */
+  async setDepositRegistry(
+    registry: PublicKey,
+    schema: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetDepositRegistryTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      registry,
+      schema,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetDepositRegistryTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setDepositRegistry(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Deposit registry set to ${registry.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Require withdrawal recipients to hold an identity pass (Civic gateway token) from the
+   * gatekeeper `network`, or turn the check off with `PublicKey.default` (admin threshold).
+   * Withdrawals then pass each recipient's gateway token in their remaining accounts.
+   */
+  async setIdentityNetwork(
+    network: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetIdentityNetworkTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      network,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetIdentityNetworkTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setIdentityNetwork(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Identity network set to ${network.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Turn strict signature verification on or off (admin threshold). While on, a ticket with any
+   * signature that fails recovery or isn't from an active signer is rejected with its index in
+   * the program log, along with every recovered address.
+   */
+  async setStrictSignatures(
+    enabled: boolean,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetStrictSignaturesTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      enabled,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetStrictSignaturesTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setStrictSignatures(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Strict signature mode ${enabled ? "enabled" : "disabled"}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Set the lamports withdrawals must leave in the treasury on top of its rent exemption, or
+   * remove the reserve with 0 (admin threshold)
+   */
+  async setTreasuryReserve(
+    reserveLamports: number | BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetTreasuryReserveTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      reserve: new BN(reserveLamports),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetTreasuryReserveTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setTreasuryReserve(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Treasury reserve set to ${ticket.reserve.toString()} lamports`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Switch withdrawal replay protection to the request id watermark, or back to nonce accounts,
+   * and raise the watermark to `watermark` (admin threshold). Under watermark replay request ids
+   * must be strictly increasing and `withdraw` / `withdrawWsol` can skip the nonce account.
+   */
+  async setReplayMode(
+    watermarkReplay: boolean,
+    watermark: number | BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetReplayModeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      watermarkReplay,
+      watermark: new BN(watermark),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetReplayModeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setReplayMode(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Replay mode set to ${watermarkReplay ? "watermark" : "nonce accounts"}, watermark ${ticket.watermark.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
 
-  createBulkWithdrawalTicketHash(bulkTicket: BulkWithdrawalTicket): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    data.push(Buffer.from(DOMAIN_SEPARATOR_BULK_WITHDRAWAL, "utf8"));
-    
-    // Hash each individual ticket and concatenate
-    for (const ticket of bulkTicket.tickets) {
-      const ticketHash = this.createWithdrawalTicketHash(ticket);
-      data.push(Buffer.from(ticketHash));
-    }
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
+  /**
+   * Set the guardian that may veto withdrawals, or remove it with `PublicKey.default` (admin
+   * threshold).
+   */
+  async setGuardian(
+    guardian: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetGuardianTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      guardian,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetGuardianTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setGuardian(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Guardian set to ${guardian.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Create a admin deposit ticket hash for signing (keccak256)
+   * Convert a version 1 vault to the current layout (`CURRENT_VERSION`). The provider wallet must
+   * be the vault's authority and pays for the extra space; `splMints` are the mints of the
+   * whitelisted SPL tokens, whose decimals the new whitelist records. A no-op once migrated.
    */
-  createAdminDepositTicketHash(ticket: AdminDepositTicket): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    data.push(Buffer.from(DOMAIN_SEPARATOR_ADMIN_DEPOSIT, "utf8"));
-    
-    // Request ID (u64, little-endian)
-    const requestIdBuf = Buffer.alloc(8);
-    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
-    data.push(requestIdBuf);
-    
-    // Vault pubkey (32 bytes)
-    data.push(ticket.vault.toBuffer());
-    
-    // Deposits
-    for (const deposit of ticket.deposits) {
-      data.push(this.serializeAssetAmount(deposit));
-    }
-    
-    // Expiry (i64, little-endian)
-    const expiryBuf = Buffer.alloc(8);
-    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
-    data.push(expiryBuf);
-    
-    // Network ID (u64, little-endian)
-    const networkIdBuf = Buffer.alloc(8);
-    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
-    data.push(networkIdBuf);
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
+  async migrate(splMints: PublicKey[] = []): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+
+    const tx = await this.program.methods
+      .migrate()
+      .accounts({
+        vault: vaultPda,
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(
+        splMints.map(mint => ({ pubkey: mint, isSigner: false, isWritable: false }))
+      )
+      .rpc();
+
+    console.log(`✅ Migrated vault`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Create an add asset ticket hash for signing (keccak256)
+   * Veto the withdrawal with `requestId` before it executes. The provider wallet must be the
+   * vault's guardian.
    */
-  createAddAssetTicketHash(ticket: AddAssetTicket): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    data.push(Buffer.from(DOMAIN_SEPARATOR_ADD_ASSET, "utf8"));
-    
-    // Request ID (u64, little-endian)
-    const requestIdBuf = Buffer.alloc(8);
-    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
-    data.push(requestIdBuf);
-    
-    // Vault pubkey (32 bytes)
-    data.push(ticket.vault.toBuffer());
-    
-    // Expiry (i64, little-endian)
-    const expiryBuf = Buffer.alloc(8);
-    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
-    data.push(expiryBuf);
-    
-    // Network ID (u64, little-endian)
-    const networkIdBuf = Buffer.alloc(8);
-    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
-    data.push(networkIdBuf);
-    
-    // Asset
-    data.push(this.serializeAsset(ticket.asset));
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
+  async vetoWithdrawal(requestId: number): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getNonceAddress(vaultPda, new BN(requestId));
+
+    const tx = await this.program.methods
+      .vetoWithdrawal(new BN(requestId))
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        guardian: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Vetoed withdrawal ${requestId}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Create a remove asset ticket hash for signing (keccak256)
+   * Close the nonce PDA of `requestId` (an admin nonce when `admin`) once its ticket expired
+   * `NONCE_GC_DELAY` seconds ago. Anyone may submit it; the wallet receives the cranker's share
+   * of the rent and the treasury the rest.
    */
-  createRemoveAssetTicketHash(ticket: RemoveAssetTicket): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    data.push(Buffer.from(DOMAIN_SEPARATOR_REMOVE_ASSET, "utf8"));
-    
-    // Request ID (u64, little-endian)
-    const requestIdBuf = Buffer.alloc(8);
-    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
-    data.push(requestIdBuf);
-    
-    // Vault pubkey (32 bytes)
-    data.push(ticket.vault.toBuffer());
-    
-    // Expiry (i64, little-endian)
-    const expiryBuf = Buffer.alloc(8);
-    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
-    data.push(expiryBuf);
-    
-    // Network ID (u64, little-endian)
-    const networkIdBuf = Buffer.alloc(8);
-    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
-    data.push(networkIdBuf);
-    
-    // Asset
-    data.push(this.serializeAsset(ticket.asset));
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
+  async closeExpiredNonce(requestId: BN, admin: boolean = false): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [noncePda] = admin
+      ? this.getAdminNonceAddress(vaultPda, requestId)
+      : this.getNonceAddress(vaultPda, requestId);
+
+    const tx = await this.program.methods
+      .closeExpiredNonce(requestId, admin)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        nonceAccount: noncePda,
+        cranker: this.provider.wallet.publicKey,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Closed expired nonce ${requestId.toString()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Create a rotate validator ticket hash for signing (keccak256)
+   * Move `amount` lamports from the treasury into the vault's stake account for `voteAccount`
+   * and delegate it (admin threshold). The stake account must not be in use.
    */
-  createRotateValidatorTicketHash(ticket: RotateValidatorTicket): Uint8Array {
-    const data: Buffer[] = [];
-    
-    // Domain separator
-    data.push(Buffer.from(DOMAIN_SEPARATOR_ROTATE, "utf8"));
-    
-    // Request ID (u64, little-endian)
-    const requestIdBuf = Buffer.alloc(8);
-    requestIdBuf.writeBigUInt64LE(BigInt(ticket.requestId.toString()));
-    data.push(requestIdBuf);
-    
-    // Vault pubkey (32 bytes)
-    data.push(ticket.vault.toBuffer());
-    
-    // Signers array with separators (matching Rust implementation)
-    for (const signer of ticket.signers) {
-      data.push(Buffer.from([55])); // Separator byte before signer
-      data.push(Buffer.from(signer)); // 20-byte Ethereum address
-      data.push(Buffer.from([56])); // Separator byte after signer
-    }
-    
-    // M threshold (u8, single byte)
-    data.push(Buffer.from([ticket.mThreshold]));
-    // Admin threshold (u8, single byte)
-    data.push(Buffer.from([ticket.adminThreshold]));
-    
-    // Expiry (i64, little-endian)
-    const expiryBuf = Buffer.alloc(8);
-    expiryBuf.writeBigInt64LE(BigInt(ticket.expiry.toString()));
-    data.push(expiryBuf);
-    
-    // Network ID (u64, little-endian)
-    const networkIdBuf = Buffer.alloc(8);
-    networkIdBuf.writeBigUInt64LE(BigInt(ticket.networkId.toString()));
-    data.push(networkIdBuf);
-    
-    // Concatenate all data
-    const combined = Buffer.concat(data);
-    
-    // Hash using keccak256 (Ethereum compatible)
-    return keccak256(combined);
+  async delegateStake(
+    voteAccount: PublicKey,
+    amount: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [stakePda] = this.getStakeAddress(vaultPda, voteAccount);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: DelegateStakeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      voteAccount,
+      amount: new BN(amount),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signDelegateStakeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .delegateStake(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        stakeAccount: stakePda,
+        voteAccount,
+        stakeConfig: STAKE_CONFIG_ID,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        rent: SYSVAR_RENT_PUBKEY,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        stakeProgram: StakeProgram.programId,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Delegated ${amount} lamports to ${voteAccount.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Deactivate the vault's stake delegated to `voteAccount` (admin threshold). The lamports
+   * become withdrawable once the cooldown epoch has passed.
+   */
+  async deactivateStake(
+    voteAccount: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [stakePda] = this.getStakeAddress(vaultPda, voteAccount);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: DeactivateStakeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      voteAccount,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signDeactivateStakeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .deactivateStake(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        stakeAccount: stakePda,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        stakeProgram: StakeProgram.programId,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Deactivated stake delegated to ${voteAccount.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Sign a withdrawal ticket with an Ethereum keypair
+   * Withdraw `amount` inactive lamports from the vault's stake account for `voteAccount` back
+   * into the treasury (admin threshold).
    */
-  signWithdrawalTicket(ticket: WithdrawalTicket, ethKeypair: EthereumKeypair, admin?: boolean): SignerWithSignature {
-    const messageHash = this.createWithdrawalTicketHash(ticket, admin);
-    
-    // Sign with secp256k1
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    
-    // Extract r, s, and recovery ID
-    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
-    const recoveryId = sig.recovery!; // 0 or 1
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  async withdrawStake(
+    voteAccount: PublicKey,
+    amount: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [stakePda] = this.getStakeAddress(vaultPda, voteAccount);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: WithdrawStakeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      voteAccount,
+      amount: new BN(amount),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signWithdrawStakeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .withdrawStake(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        stakeAccount: stakePda,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        stakeProgram: StakeProgram.programId,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Withdrew ${amount} staked lamports to the treasury`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
-  signBulkWithdrawalTicket(
-    bulkTicket: BulkWithdrawalTicket, 
-    ethKeypair: EthereumKeypair
-  ): SignerWithSignature {
-    const messageHash = this.createBulkWithdrawalTicketHash(bulkTicket);
-    
-    // Sign with secp256k1
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    
-    // Extract r, s, and recovery ID
-    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
-    const recoveryId = sig.recovery!; // 0 or 1
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  /**
+   * Deposit `lamports` of treasury SOL into an SPL stake pool (admin threshold). The pool tokens
+   * land in the vault's token account and the pool mint is whitelisted on first use. Fails if
+   * fewer than `minPoolTokens` are minted.
+   */
+  async liquidStake(
+    pool: StakePoolAccounts,
+    lamports: number,
+    minPoolTokens: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [lstFundingPda] = this.getLstFundingAddress(vaultPda);
+    const [withdrawAuthority] = this.getStakePoolWithdrawAuthority(pool.stakePool);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: LiquidStakeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      stakePool: pool.stakePool,
+      poolMint: pool.poolMint,
+      lamports: new BN(lamports),
+      minPoolTokens: new BN(minPoolTokens),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signLiquidStakeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .liquidStake(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        lstFunding: lstFundingPda,
+        stakePool: pool.stakePool,
+        poolWithdrawAuthority: withdrawAuthority,
+        reserveStake: pool.reserveStake,
+        managerFeeAccount: pool.managerFeeAccount,
+        poolMint: pool.poolMint,
+        vaultPoolTokenAccount: getAssociatedTokenAddressSync(pool.poolMint, vaultPda, true),
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        stakePoolProgram: SPL_STAKE_POOL_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Liquid staked ${lamports} lamports into ${pool.stakePool.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Sign a deposit ticket with an Ethereum keypair
+   * Redeem `poolTokens` of the vault's stake pool tokens for SOL paid into the treasury (admin
+   * threshold). Fails if the pool's reserve pays out fewer than `minLamports`.
    */
-  signAdminDepositTicket(ticket: AdminDepositTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
-    const messageHash = this.createAdminDepositTicketHash(ticket);
-    
-    // Sign with secp256k1
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    
-    // Extract r, s, and recovery ID
-    const signature = sig.toCompactRawBytes(); // 64 bytes (r + s)
-    const recoveryId = sig.recovery!; // 0 or 1
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  async liquidUnstake(
+    pool: StakePoolAccounts,
+    poolTokens: number,
+    minLamports: number,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [withdrawAuthority] = this.getStakePoolWithdrawAuthority(pool.stakePool);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: LiquidUnstakeTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      stakePool: pool.stakePool,
+      poolMint: pool.poolMint,
+      poolTokens: new BN(poolTokens),
+      minLamports: new BN(minLamports),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signLiquidUnstakeTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .liquidUnstake(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        stakePool: pool.stakePool,
+        poolWithdrawAuthority: withdrawAuthority,
+        reserveStake: pool.reserveStake,
+        managerFeeAccount: pool.managerFeeAccount,
+        poolMint: pool.poolMint,
+        vaultPoolTokenAccount: getAssociatedTokenAddressSync(pool.poolMint, vaultPda, true),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        stakePoolProgram: SPL_STAKE_POOL_PROGRAM_ID,
+        stakeProgram: StakeProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Liquid unstaked ${poolTokens} pool tokens to the treasury`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Sign an add asset ticket with an Ethereum keypair
+   * Move `amount` of an asset between two of the vault's own accounts (admin threshold): SOL
+   * between the treasury PDA and named treasuries, tokens between token accounts the vault owns.
    */
-  signAddAssetTicket(ticket: AddAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
-    const messageHash = this.createAddAssetTicketHash(ticket);
-    
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    const signature = sig.toCompactRawBytes();
-    const recoveryId = sig.recovery!;
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  async rebalance(
+    asset: Asset,
+    amount: BN,
+    source: PublicKey,
+    destination: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: RebalanceTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      asset,
+      amount,
+      source,
+      destination,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signRebalanceTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .rebalance(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        source,
+        destination,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(this.getMintAccounts([{ asset, amount }]))
+      .rpc();
+
+    console.log(`✅ Rebalanced ${amount.toString()} from ${source.toBase58()} to ${destination.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Sign a remove asset ticket with an Ethereum keypair
+   * Swap at most `amountIn` of the vault's `inputMint` tokens for at least `minAmountOut` of
+   * `outputMint` through Jupiter (admin threshold). `swapInstruction` is a Jupiter swap
+   * instruction quoted with the vault PDA as user and its associated token accounts as source
+   * and destination; only its data and accounts are used, the ticket bounds the outcome.
    */
-  signRemoveAssetTicket(ticket: RemoveAssetTicket, ethKeypair: EthereumKeypair): SignerWithSignature {
-    const messageHash = this.createRemoveAssetTicketHash(ticket);
-    
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    const signature = sig.toCompactRawBytes();
-    const recoveryId = sig.recovery!;
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  async swap(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amountIn: BN,
+    minAmountOut: BN,
+    swapInstruction: anchor.web3.TransactionInstruction,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    inputTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    outputTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SwapTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      inputMint,
+      outputMint,
+      amountIn,
+      minAmountOut,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSwapTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    // The vault PDA signs inside the program, so it is never a transaction signer here.
+    const remainingAccounts = swapInstruction.keys.map(k => ({
+      pubkey: k.pubkey,
+      isWritable: k.isWritable,
+      isSigner: k.isSigner && !k.pubkey.equals(vaultPda),
+    }));
+
+    const tx = await this.program.methods
+      .swap(ticket, sigsArg, Buffer.from(swapInstruction.data))
+      .accounts({
+        vault: vaultPda,
+        inputMint,
+        outputMint,
+        vaultInputTokenAccount: getAssociatedTokenAddressSync(inputMint, vaultPda, true, inputTokenProgram),
+        vaultOutputTokenAccount: getAssociatedTokenAddressSync(outputMint, vaultPda, true, outputTokenProgram),
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        jupiterProgram: JUPITER_PROGRAM_ID,
+        inputTokenProgram,
+        outputTokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Swapped up to ${amountIn.toString()} of ${inputMint.toBase58()} for ${outputMint.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Sign a rotate validator ticket with an Ethereum keypair
+   * Sweep accumulated protocol fees from the fee treasury to `recipient` (admin threshold).
+   * For SPL fees pass the recipient's token accounts in `remainingAccounts`; the mints and the
+   * fee treasury's token accounts are added here.
    */
-  signRotateValidatorTicket(
-    ticket: RotateValidatorTicket, 
-    ethKeypair: EthereumKeypair
-  ): SignerWithSignature {
-    const messageHash = this.createRotateValidatorTicketHash(ticket);
-    
-    const sig = secp256k1.sign(messageHash, ethKeypair.privateKey);
-    const signature = sig.toCompactRawBytes();
-    const recoveryId = sig.recovery!;
-    
-    return {
-      ethAddress: ethKeypair.address,
-      signature,
-      recoveryId,
+  async collectFees(
+    recipient: PublicKey,
+    fees: AssetAmount[],
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    remainingAccounts: any[] = [],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [feeTreasuryPda] = this.getFeeTreasuryAddress(vaultPda);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: CollectFeesTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      fees,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCollectFeesTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .collectFees(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        feeTreasury: feeTreasuryPda,
+        recipient,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts([
+        ...remainingAccounts,
+        ...this.getMintAccounts(fees),
+        ...this.getFeeTokenAccounts(vaultPda, fees),
+      ])
+      .rpc();
+
+    console.log(`✅ Collected protocol fees`);
+    console.log(`   Recipient: ${recipient.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Create a withdrawal ticket
+   * Move every listed vault token account holding less than its asset's minimum withdrawal to
+   * `destination`'s associated token accounts, which must exist (admin threshold). All mints must
+   * belong to `tokenProgram`.
    */
-  createWithdrawalTicket(
-    recipient: PublicKey,
-    withdrawals: AssetAmount[],
+  async sweepDust(
+    destination: PublicKey,
+    mints: PublicKey[],
     requestId: number,
-    expiryTimestamp: number,
-  ): WithdrawalTicket {
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
+    ethKeypairs: EthereumKeypair[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
 
-    return {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SweepDustTicket = {
+      version: TICKET_VERSION,
       requestId: new BN(requestId),
       vault: vaultPda,
-      recipient,
-      withdrawals,
-      expiry: new BN(expiryTimestamp),
+      destination,
+      mints,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
       networkId: new BN(this.networkId),
     };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSweepDustTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .sweepDust(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram,
+      } as any)
+      .remainingAccounts(mints.flatMap(mint => [
+        { pubkey: mint, isWritable: false, isSigner: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(mint, vaultPda, true, tokenProgram),
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: getAssociatedTokenAddressSync(mint, destination, true, tokenProgram),
+          isWritable: true,
+          isSigner: false,
+        },
+      ]))
+      .rpc();
+
+    console.log(`✅ Swept dust`);
+    console.log(`   Destination: ${destination.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Deposit assets into the vault
+   * Move the vault's whole balance of `mint` to `destination`'s associated token account (admin
+   * threshold) and, with `closeAccount`, close the vault's account and send its rent to
+   * `destination`. Closing needs the asset removed from the whitelist.
    */
-  async deposit(
-    deposits: AssetAmount[],
+  async sweepAsset(
+    mint: PublicKey,
+    destination: PublicKey,
+    closeAccount: boolean,
     requestId: number,
-    remainingAccounts: any[] = [],
-    metadata?: string,
+    ethKeypairs: EthereumKeypair[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
   ): Promise<string> {
-    const user = this.provider.wallet.publicKey;
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
 
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
-    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SweepAssetTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      mint,
+      destination,
+      closeAccount,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
 
-    const depositsArg = deposits.map(d => ({
-      asset: d.asset,
-      amount: d.amount,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSweepAssetTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .deposit(depositsArg, new BN(requestId), metadata || null)
+      .sweepAsset(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
-        treasury: treasuryPda,
-        user: user,
+        mint,
+        vaultTokenAccount: getAssociatedTokenAddressSync(mint, vaultPda, true, tokenProgram),
+        destination,
+        destinationTokenAccount: getAssociatedTokenAddressSync(mint, destination, true, tokenProgram),
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        tokenProgram,
       } as any)
-      .remainingAccounts(remainingAccounts)
       .rpc();
 
-    console.log(`✅ Deposited assets to treasury`);
+    console.log(`✅ Swept ${mint.toBase58()}${closeAccount ? " and closed its account" : ""}`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
   }
 
   /**
-   * Deposit SOL into the vault (convenience method)
+   * Hand the program's upgrade authority to the vault's upgrade authority PDA. The provider
+   * wallet must be the current upgrade authority.
    */
-  async depositSol(
-    amountSol: number,
-    requestId?: number
-  ): Promise<string> {
-    const amountLamports = amountSol * LAMPORTS_PER_SOL;
-    const deposits: AssetAmount[] = [{
-      asset: { sol: {} },
-      amount: new BN(amountLamports),
-    }];
+  async acceptUpgradeAuthority(): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [upgradeAuthority] = this.getUpgradeAuthorityAddress(vaultPda);
+    const [programData] = this.getProgramDataAddress();
 
-    const reqId = requestId || Date.now();
-    return this.deposit(deposits, reqId);
+    const tx = await this.program.methods
+      .acceptUpgradeAuthority()
+      .accounts({
+        vault: vaultPda,
+        programData,
+        currentAuthority: this.provider.wallet.publicKey,
+        upgradeAuthority,
+        bpfLoaderProgram: BPF_LOADER_UPGRADEABLE_ID,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Upgrade authority moved to ${upgradeAuthority.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
   /**
-   * Withdraw assets from the vault with multisig approval using tickets
+   * Upgrade the program from `buffer` (admin threshold), refunding the buffer's lamports to
+   * `spill`. The buffer's authority must already be the vault's upgrade authority PDA.
    */
-  async withdraw(
-    ticket: WithdrawalTicket,
+  async upgradeProgram(
+    buffer: PublicKey,
+    spill: PublicKey,
+    requestId: number,
     ethKeypairs: EthereumKeypair[],
-    remainingAccounts: any[] = [],
-    metadata?: string,
+    expiryDurationSeconds: number = 3600,
   ): Promise<string> {
-    const [treasuryPda] = this.getTreasuryAddress(ticket.vault);
-    const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
-    
-    const actualPayer = this.provider.wallet.publicKey;
-
-    // Sign the ticket with all provided signers
-    const signersWithSigs = ethKeypairs.map(kp => this.signWithdrawalTicket(ticket, kp));
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [upgradeAuthority] = this.getUpgradeAuthorityAddress(vaultPda);
+    const [programData] = this.getProgramDataAddress();
 
-    // Convert ticket to program format
-    const ticketArg = {
-      requestId: ticket.requestId,
-      vault: ticket.vault,
-      recipient: ticket.recipient,
-      withdrawals: ticket.withdrawals,
-      expiry: ticket.expiry,
-      networkId: ticket.networkId,
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: UpgradeProgramTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      buffer,
+      spill,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
 
-    // Convert signatures to program format
-    const sigsArg = signersWithSigs.map(s => ({
-      signature: Array.from(s.signature),
-      recoveryId: s.recoveryId,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signUpgradeProgramTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .withdraw(ticketArg, sigsArg, metadata || null)
+      .upgradeProgram(ticket, sigsArg)
       .accounts({
-        vault: ticket.vault,
-        treasury: treasuryPda,
-        recipient: ticket.recipient,
+        vault: vaultPda,
+        program: this.program.programId,
+        programData,
+        buffer,
+        spill,
+        upgradeAuthority,
         nonceAccount: noncePda,
-        payer: actualPayer,
+        payer: this.provider.wallet.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        bpfLoaderProgram: BPF_LOADER_UPGRADEABLE_ID,
         systemProgram: SystemProgram.programId,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       } as any)
-      .remainingAccounts(remainingAccounts)
       .rpc();
 
-    console.log(`✅ Withdrew assets from vault`);
-    console.log(`   Recipient: ${ticket.recipient.toBase58()}`);
-    console.log(`   Request ID: ${ticket.requestId.toString()}`);
-    console.log(`   Valid Signers: ${signersWithSigs.length}`);
+    console.log(`✅ Program upgraded from ${buffer.toBase58()}`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
   }
 
   /**
-   * Convenience method: Withdraw SOL with current timestamp + duration
+   * Close the vault's associated token account for `mint` and send its rent to `rentRecipient`
+   * (admin threshold). The asset must be removed from the whitelist and the account empty.
    */
-  async createAndExecuteWithdrawal(
-    recipient: PublicKey,
-    amountSol: number,
+  async closeVaultTokenAccount(
+    mint: PublicKey,
+    rentRecipient: PublicKey,
     requestId: number,
     ethKeypairs: EthereumKeypair[],
-    expiryDurationSeconds: number = 3600, // 1 hour default
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
   ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
     const currentTimestamp = Math.floor(Date.now() / 1000);
-    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+    const ticket: CloseVaultTokenAccountTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      mint,
+      rentRecipient,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
 
-    const withdrawals: AssetAmount[] = [{
-      asset: { sol: {} },
-      amount: new BN(amountSol * LAMPORTS_PER_SOL),
-    }];
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCloseVaultTokenAccountTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
-    const ticket = this.createWithdrawalTicket(
-      recipient,
-      withdrawals,
-      requestId,
-      expiryTimestamp,
-    );
+    const tx = await this.program.methods
+      .closeVaultTokenAccount(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        mint,
+        vaultTokenAccount: getAssociatedTokenAddressSync(mint, vaultPda, true, tokenProgram),
+        rentRecipient,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram,
+      } as any)
+      .rpc();
 
-    return this.withdraw(ticket, ethKeypairs);
+    console.log(`✅ Closed vault token account for ${mint.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
   }
 
-  createBulkWithdrawalTicket(
-    withdrawals: Array<{
-      recipient: PublicKey;
-      withdrawals: AssetAmount[];
-      requestId: number;
-      expiryTimestamp: number;
-    }>
-  ): BulkWithdrawalTicket {
-    const tickets = withdrawals.map(w => 
-      this.createWithdrawalTicket(
-        w.recipient,
-        w.withdrawals,
-        w.requestId,
-        w.expiryTimestamp
-      )
-    );
+  /**
+   * Cap the total a recipient can withdraw of an asset (admin threshold). What the recipient
+   * already withdrew under an existing allowance keeps counting against the new cap.
+   */
+  async setAllowance(
+    recipient: PublicKey,
+    asset: Asset,
+    cap: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [allowancePda] = this.getAllowanceAddress(vaultPda, recipient, asset);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetAllowanceTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      recipient,
+      asset,
+      cap,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetAllowanceTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setAllowance(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        allowance: allowancePda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Allowance of ${recipient.toBase58()} set to ${cap.toString()}`);
+    console.log(`   Transaction: ${tx}`);
 
-    return { tickets };
+    return tx;
   }
 
   /**
-   * Execute bulk withdrawal with multisig approval
+   * Create or update a named treasury and the signer threshold its withdrawals need (admin threshold).
+   * Fund it with a plain SOL transfer to `getNamedTreasuryAddress`.
    */
-  async bulkWithdraw(
-    bulkTicket: BulkWithdrawalTicket,
+  async setTreasury(
+    name: string,
+    mThreshold: number,
+    requestId: number,
     ethKeypairs: EthereumKeypair[],
-    remainingAccounts: any[] = [],
-    metadata?: string,
+    expiryDurationSeconds: number = 3600,
   ): Promise<string> {
-    if (bulkTicket.tickets.length === 0) {
-      throw new Error("No withdrawal tickets provided");
-    }
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [namedTreasuryPda] = this.getNamedTreasuryAddress(vaultPda, name);
 
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
-    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
-    const actualPayer = this.provider.wallet.publicKey;
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetTreasuryTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      name,
+      mThreshold,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
 
-    // Sign the bulk ticket with all provided signers
-    const signersWithSigs = ethKeypairs.map(kp => 
-      this.signBulkWithdrawalTicket(bulkTicket, kp)
-    );
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetTreasuryTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
-    // Build remaining accounts array:
-    // 1. First N accounts are nonce accounts (one per ticket)
-    // 2. Remaining accounts are recipients and token accounts
-    const nonceAccounts: any[] = [];
-    
-    for (const ticket of bulkTicket.tickets) {
-      const [noncePda] = this.getNonceAddress(ticket.vault, ticket.requestId);
-      nonceAccounts.push({
-        pubkey: noncePda,
-        isWritable: true,
-        isSigner: false,
-      });
-    }
+    const tx = await this.program.methods
+      .setTreasury(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: namedTreasuryPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
 
-    // Combine nonce accounts with other remaining accounts
-    const allRemainingAccounts = [...nonceAccounts, ...remainingAccounts];
+    console.log(`✅ Treasury "${name}" set to threshold ${mThreshold}`);
+    console.log(`   Transaction: ${tx}`);
 
-    // Convert bulk ticket to program format
-    const bulkTicketArg = {
-      tickets: bulkTicket.tickets.map(ticket => ({
-        requestId: ticket.requestId,
-        vault: ticket.vault,
-        recipient: ticket.recipient,
-        withdrawals: ticket.withdrawals,
-        expiry: ticket.expiry,
-        networkId: ticket.networkId,
-      }))
+    return tx;
+  }
+
+  /**
+   * Trust a Wormhole emitter to authorize withdrawals through VAAs posted on `coreBridge`
+   * (admin threshold).
+   */
+  async setWormholeEmitter(
+    coreBridge: PublicKey,
+    emitterChain: number,
+    emitterAddress: number[],
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [wormholeEmitterPda] = this.getWormholeEmitterAddress(vaultPda);
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetWormholeEmitterTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      coreBridge,
+      emitterChain,
+      emitterAddress,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
 
-    // Convert signatures to program format
-    const sigsArg = signersWithSigs.map(s => ({
-      signature: Array.from(s.signature),
-      recoveryId: s.recoveryId,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetWormholeEmitterTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .bulkWithdraw(bulkTicketArg, sigsArg, metadata || null)
+      .setWormholeEmitter(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
-        treasury: treasuryPda,
-        payer: actualPayer,
+        wormholeEmitter: wormholeEmitterPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       } as any)
-      .remainingAccounts(allRemainingAccounts)
       .rpc();
 
-    console.log(`✅ Bulk withdrawal completed`);
-    console.log(`   Number of tickets: ${bulkTicket.tickets.length}`);
-    console.log(`   Valid Signers: ${signersWithSigs.length}`);
+    console.log(`✅ Wormhole emitter set to chain ${emitterChain}`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
   }
 
   /**
-   * Convenience method: Bulk withdraw SOL with current timestamp + duration
+   * Create a sub-vault of this vault (admin threshold). It starts with this vault's signers and
+   * thresholds and its own empty whitelist; use a client with `subVaultSeed` and this vault as
+   * `parentVault` to manage it.
    */
-  async createAndExecuteBulkWithdrawal(
-    withdrawalRequests: Array<{
-      recipient: PublicKey;
-      amountSol: number;
-      requestId: number;
-    }>,
+  async createSubVault(
+    subVaultSeed: string,
+    requestId: number,
     ethKeypairs: EthereumKeypair[],
     expiryDurationSeconds: number = 3600,
   ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [subVaultPda] = this.getVaultAddress(subVaultSeed, vaultPda);
+    const [subTreasuryPda] = this.getTreasuryAddress(subVaultPda);
+
     const currentTimestamp = Math.floor(Date.now() / 1000);
-    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+    const ticket: CreateSubVaultTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      subVaultSeed,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
 
-    // Convert to withdrawal ticket format
-    const withdrawals = withdrawalRequests.map(req => ({
-      recipient: req.recipient,
-      withdrawals: [{
-        asset: { sol: {} } as const,
-        amount: new BN(req.amountSol * anchor.web3.LAMPORTS_PER_SOL),
-      }],
-      requestId: req.requestId,
-      expiryTimestamp,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCreateSubVaultTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
-    const bulkTicket = this.createBulkWithdrawalTicket(withdrawals);
+    const tx = await this.program.methods
+      .createSubVault(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        subVault: subVaultPda,
+        subTreasury: subTreasuryPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
 
-    // Build remaining accounts for recipients with dedup.
-    const recipientAccounts = Array.from(
-      new Map(
-        withdrawalRequests.map(req => [req.recipient.toString(), {
-          pubkey: req.recipient,
-          isWritable: true,
-          isSigner: false,
-        }])
-      ).values()
-    );
+    console.log(`✅ Sub-vault created: ${subVaultPda.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
 
-    return this.bulkWithdraw(bulkTicket, ethKeypairs, recipientAccounts);
+    return tx;
   }
 
   /**
-   * Fetch vault account data
+   * Move funds from a sub-vault back into this vault (admin threshold of this vault). The
+   * vault's token accounts for SPL withdrawals must exist.
    */
-  async getVaultData() {
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
-    const vaultAccount = await this.program.account.vault.fetch(vaultPda);
-
+  async reclaimSubVault(
+    subVaultSeed: string,
+    withdrawals: AssetAmount[],
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
     const [treasuryPda] = this.getTreasuryAddress(vaultPda);
-    const balance = await this.provider.connection.getBalance(treasuryPda);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+    const [subVaultPda] = this.getVaultAddress(subVaultSeed, vaultPda);
+    const [subTreasuryPda] = this.getTreasuryAddress(subVaultPda);
 
-    return {
-      address: vaultPda,
-      authority: vaultAccount.authority,
-      mThreshold: vaultAccount.mThreshold,
-      signers: vaultAccount.signers,
-      whitelistedAssets: vaultAccount.whitelistedAssets,
-      bump: vaultAccount.bump,
-      balanceSol: balance / LAMPORTS_PER_SOL,
-      balanceLamports: balance,
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: ReclaimSubVaultTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      subVault: subVaultPda,
+      withdrawals,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
     };
-  }
-
-  /**
-   * Get treasury balance in SOL
-   */
-  async getTreasuryBalance(): Promise<number> {
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
-    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
-    const balance = await this.provider.connection.getBalance(treasuryPda);
-    return balance / LAMPORTS_PER_SOL;
-  }
 
-  /**
-   * Check if an Ethereum address is a valid signer for the vault
-   */
-  async isValidSigner(
-    ethAddress: Uint8Array
-  ): Promise<boolean> {
-    const vaultData = await this.getVaultData();
-    return vaultData.signers.some((s: number[]) => 
-      s.every((byte, idx) => byte === ethAddress[idx])
-    );
-  }
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signReclaimSubVaultTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tokenAccounts = this.getMintAccounts(withdrawals).flatMap(({ pubkey }) => [
+      subVaultPda,
+      vaultPda,
+    ].map(owner => ({
+      pubkey: getAssociatedTokenAddressSync(pubkey, owner, true, tokenProgram),
+      isWritable: true,
+      isSigner: false,
+    })));
 
-  /**
-   * Check if a nonce has been used
-   */
-  async isNonceUsed(vaultPda: PublicKey, requestId: BN): Promise<boolean> {
-    try {
-      const [noncePda] = this.getNonceAddress(vaultPda, requestId);
-      const nonceAccount = await this.program.account.nonceAccount.fetch(noncePda);
-      return nonceAccount.used;
-    } catch (error) {
-      // Nonce account doesn't exist yet
-      return false;
-    }
-  }
+    const tx = await this.program.methods
+      .reclaimSubVault(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        treasury: treasuryPda,
+        subVault: subVaultPda,
+        subTreasury: subTreasuryPda,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram,
+      } as any)
+      .remainingAccounts([
+        ...this.getMintAccounts(withdrawals),
+        ...tokenAccounts,
+      ])
+      .rpc();
 
-  /**
-   * Check if an admin nonce has been used
-   */
-  async isAdminNonceUsed(vaultPda: PublicKey, requestId: BN): Promise<boolean> {
-    try {
-      const [noncePda] = this.getAdminNonceAddress(vaultPda, requestId);
-      const nonceAccount = await this.program.account.nonceAccount.fetch(noncePda);
-      return nonceAccount.used;
-    } catch (error) {
-      // Nonce account doesn't exist yet
-      return false;
-    }
-  }
-}
+    console.log(`✅ Reclaimed funds from sub-vault ${subVaultPda.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
 
-export class MultisigAdminClient extends MultisigVaultClient {
-  constructor(
-    program: Program<StrikeExample>,
-    provider: anchor.AnchorProvider,
-    vaultSeed: string,
-    networkId: number,
-  ) {
-    super(program, provider, vaultSeed, networkId);
+    return tx;
   }
 
   /**
-   * Initialize a new multisig vault with Ethereum addresses
+   * Set the smallest amount a withdrawal line of a whitelisted asset may carry (admin threshold).
+   * A `minAmount` of 0 removes the minimum.
    */
-  async initialize(
-    mThreshold: number,
-    adminThreshold: number,
-    ethAddresses: Uint8Array[] // Array of 20-byte Ethereum addresses
-  ): Promise<{ signature: string; vaultAddress: PublicKey }> {
-    const authority = this.provider.wallet.publicKey;
+  async setMinWithdrawal(
+    asset: Asset,
+    minAmount: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
 
-    const [vaultPda, bump] = this.getVaultAddress(this.vaultSeed);
-    const [treasuryPda] = this.getTreasuryAddress(vaultPda);
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetMinWithdrawalTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      asset,
+      minAmount,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
 
-    // Convert to arrays for Anchor
-    const signersArray = ethAddresses.map(addr => Array.from(addr));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetMinWithdrawalTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .initialize(this.vaultSeed, new BN(this.networkId), mThreshold, adminThreshold, signersArray)
+      .setMinWithdrawal(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
-        treasury: treasuryPda,
-        authority: authority,
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
 
-    console.log(`✅ Vault initialized: ${vaultPda.toBase58()}`);
-    console.log(`   Treasury: ${treasuryPda.toBase58()}`);
+    console.log(`✅ Minimum withdrawal set to ${minAmount.toString()}`);
     console.log(`   Transaction: ${tx}`);
-    console.log(`   M-of-N: ${mThreshold} of ${ethAddresses.length}`);
 
-    return {
-      signature: tx,
-      vaultAddress: vaultPda,
-    };
+    return tx;
   }
 
   /**
-   * Add an asset to the vault whitelist
+   * Price a whitelisted asset through `oracle` and cap each withdrawal line of it at `maxUsd`
+   * micro-dollars (admin threshold). SPL assets need their mint in remaining accounts.
    */
-  async addAsset(
+  async setUsdCap(
     asset: Asset,
+    oracle: PriceOracle,
+    maxUsd: BN,
+    maxAge: BN,
+    maxConfBps: number,
     requestId: number,
     ethKeypairs: EthereumKeypair[],
     expiryDurationSeconds: number = 3600,
   ): Promise<string> {
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [usdCapPda] = this.getUsdCapAddress(vaultPda, asset);
     const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
-    
-    const actualPayer = this.provider.wallet.publicKey;
-    
-    const currentTimestamp = Math.floor(Date.now() / 1000);
-    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
 
-    const ticket: AddAssetTicket = {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetUsdCapTicket = {
+      version: TICKET_VERSION,
       requestId: new BN(requestId),
       vault: vaultPda,
       asset,
-      expiry: new BN(expiryTimestamp),
+      oracle,
+      maxUsd,
+      maxAge,
+      maxConfBps,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
       networkId: new BN(this.networkId),
     };
 
-    const signersWithSigs = ethKeypairs.map(kp => this.signAddAssetTicket(ticket, kp));
-
-    const sigsArg = signersWithSigs.map(s => ({
-      signature: Array.from(s.signature),
-      recoveryId: s.recoveryId,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetUsdCapTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .addAsset(ticket, sigsArg)
+      .setUsdCap(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
+        usdCap: usdCapPda,
         nonceAccount: noncePda,
-        payer: actualPayer,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       } as any)
+      .remainingAccounts(this.getMintAccounts([{ asset, amount: new BN(0) }]))
       .rpc();
 
-    console.log(`✅ Added asset to whitelist`);
+    console.log(`✅ USD cap set to ${maxUsd.toString()} micro-dollars`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
   }
 
   /**
-   * Remove an asset from the vault whitelist
+   * Cap the summed USD value of a withdrawal ticket at `maxTicketUsd` micro-dollars (admin
+   * threshold). A `maxTicketUsd` of 0 removes the cap.
    */
-  async removeAsset(
-    asset: Asset,
+  async setMaxTicketUsd(
+    maxTicketUsd: BN,
     requestId: number,
     ethKeypairs: EthereumKeypair[],
     expiryDurationSeconds: number = 3600,
   ): Promise<string> {
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
     const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
-    
-    const actualPayer = this.provider.wallet.publicKey;
-    
-    const currentTimestamp = Math.floor(Date.now() / 1000);
-    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
 
-    const ticket: RemoveAssetTicket = {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetMaxTicketUsdTicket = {
+      version: TICKET_VERSION,
       requestId: new BN(requestId),
       vault: vaultPda,
-      asset,
-      expiry: new BN(expiryTimestamp),
+      maxTicketUsd,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
       networkId: new BN(this.networkId),
     };
 
-    const signersWithSigs = ethKeypairs.map(kp => this.signRemoveAssetTicket(ticket, kp));
-
-    const sigsArg = signersWithSigs.map(s => ({
-      signature: Array.from(s.signature),
-      recoveryId: s.recoveryId,
-    }));
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetMaxTicketUsdTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .removeAsset(ticket, sigsArg)
+      .setMaxTicketUsd(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
         nonceAccount: noncePda,
-        payer: actualPayer,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
 
-    console.log(`✅ Removed asset from whitelist`);
+    console.log(`✅ Max ticket USD set to ${maxTicketUsd.toString()} micro-dollars`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
   }
 
-  async rotateValidators(
-    newSigners: Uint8Array[], // Array of 20-byte Ethereum addresses
-    newMThreshold: number,
-    newAdminThreshold: number,
+  /**
+   * Create the vault-owned receipt mint of a whitelisted asset (admin threshold). `decimals`
+   * must match the asset (9 for SOL); SPL assets need their mint in remaining accounts.
+   */
+  async createReceiptMint(
+    asset: Asset,
+    decimals: number,
     requestId: number,
-    currentEthKeypairs: EthereumKeypair[], // Current validators signing the change
-    expiryDurationSeconds: number = 3600,
-  ): Promise<string> {
-    // Validation
-    if (newSigners.length === 0 || newSigners.length > 10) { // Assuming MAX_SIGNERS = 10
-      throw new Error(`Invalid signers count: ${newSigners.length} (must be 1-10)`);
-    }
-    
-    if (newMThreshold <= 0 || newMThreshold > newSigners.length) {
-      throw new Error(
-        `Invalid threshold: ${newMThreshold} (must be 1-${newSigners.length})`
-      );
-    }
-    if (newAdminThreshold <= 0 || newAdminThreshold > newSigners.length) {
-      throw new Error(
-        `Invalid admin threshold: ${newAdminThreshold} (must be 1-${newSigners.length})`
-      );
-    }
-    
-    // Check for duplicate signers
-    for (let i = 0; i < newSigners.length; i++) {
-      for (let j = i + 1; j < newSigners.length; j++) {
-        if (Buffer.from(newSigners[i]).equals(Buffer.from(newSigners[j]))) {
-          throw new Error("Duplicate signer detected");
-        }
-      }
-    }
-    
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
     const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
-    
-    const actualPayer = this.provider.wallet.publicKey;
-    
-    const currentTimestamp = Math.floor(Date.now() / 1000);
-    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+    const [receiptMintPda] = this.getReceiptMintAddress(vaultPda, asset);
 
-    const ticket: RotateValidatorTicket = {
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: CreateReceiptMintTicket = {
+      version: TICKET_VERSION,
       requestId: new BN(requestId),
       vault: vaultPda,
-      signers: newSigners,
-      mThreshold: newMThreshold,
-      adminThreshold: newAdminThreshold,
-      expiry: new BN(expiryTimestamp),
+      asset,
+      decimals,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
       networkId: new BN(this.networkId),
     };
 
-    // Sign with CURRENT validators (must meet current threshold)
-    const signersWithSigs = currentEthKeypairs.map(kp => 
-      this.signRotateValidatorTicket(ticket, kp)
-    );
-
-    // Convert to program format
-    const sigsArg = signersWithSigs.map(s => ({
-      signature: Array.from(s.signature),
-      recoveryId: s.recoveryId,
-    }));
-    
-    // Convert new signers to arrays for Anchor
-    const signersArray = newSigners.map(addr => Array.from(addr));
-
-    const ticketArg = {
-      requestId: ticket.requestId,
-      vault: ticket.vault,
-      signers: signersArray,
-      mThreshold: ticket.mThreshold,
-      adminThreshold: ticket.adminThreshold,
-      expiry: ticket.expiry,
-      networkId: ticket.networkId,
-    };
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signCreateReceiptMintTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
 
     const tx = await this.program.methods
-      .rotateValidators(ticketArg, sigsArg)
+      .createReceiptMint(ticket, sigsArg)
       .accounts({
         vault: vaultPda,
+        receiptMint: receiptMintPda,
         nonceAccount: noncePda,
-        payer: actualPayer,
+        payer: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
+        tokenProgram: tokenProgram,
       } as any)
+      .remainingAccounts(this.getMintAccounts([{ asset, amount: new BN(0) }]))
       .rpc();
 
-    console.log(`✅ Validators rotated successfully`);
-    console.log(`   New validator count: ${newSigners.length}`);
-    console.log(`   New M-of-N threshold: ${newMThreshold} of ${newSigners.length}`);
-    console.log(`   Request ID: ${requestId}`);
+    console.log(`✅ Receipt mint created: ${receiptMintPda.toBase58()}`);
     console.log(`   Transaction: ${tx}`);
 
     return tx;
@@ -1222,15 +9759,19 @@ export class MultisigAdminClient extends MultisigVaultClient {
     const actualPayer = this.provider.wallet.publicKey;
 
     // Sign the ticket with all provided signers
-    const signersWithSigs = ethKeypairs.map(kp => this.signAdminDepositTicket(ticket, kp));
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signAdminDepositTicket(ticket, kp));
 
     // Convert ticket to program format
     const ticketArg = {
+      version: ticket.version,
       requestId: ticket.requestId,
       vault: ticket.vault,
       deposits: ticket.deposits,
       expiry: ticket.expiry,
       networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+      sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
+      bypassWhitelist: ticket.bypassWhitelist ?? false,
     };
 
     // Convert signatures to program format
@@ -1245,11 +9786,12 @@ export class MultisigAdminClient extends MultisigVaultClient {
         vault: ticket.vault,
         treasury: treasuryPda,
         nonceAccount: noncePda,
+        depositRecord: this.getDepositRecordAddress(ticket.vault, actualPayer)[0],
         payer: actualPayer,
         systemProgram: SystemProgram.programId,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       } as any)
-      .remainingAccounts(remainingAccounts)
+      .remainingAccounts([...remainingAccounts, ...this.getMintAccounts(ticket.deposits)])
       .rpc();
 
     console.log(`✅ Withdrew assets from vault`);
@@ -1264,18 +9806,21 @@ export class MultisigAdminClient extends MultisigVaultClient {
     deposits: AssetAmount[],
     requestId: number,
     expiryDurationSeconds: number = 3600,
+    bypassWhitelist: boolean = false,
   ): AdminDepositTicket {
     const currentTimestamp = Math.floor(Date.now() / 1000);
     const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
 
-    const [vaultPda] = this.getVaultAddress(this.vaultSeed);
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
 
     return {
+      version: TICKET_VERSION,
       requestId: new BN(requestId),
       vault: vaultPda,
       deposits,
       expiry: new BN(expiryTimestamp),
       networkId: new BN(this.networkId),
+      bypassWhitelist,
     };
   } 
 
@@ -1308,16 +9853,19 @@ export class MultisigAdminClient extends MultisigVaultClient {
     const actualPayer = this.provider.wallet.publicKey;
 
     // Sign the ticket with all provided signers
-    const signersWithSigs = ethKeypairs.map(kp => this.signWithdrawalTicket(ticket, kp, true));
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signWithdrawalTicket(ticket, kp, true));
 
     // Convert ticket to program format
     const ticketArg = {
+      version: ticket.version,
       requestId: ticket.requestId,
       vault: ticket.vault,
       recipient: ticket.recipient,
       withdrawals: ticket.withdrawals,
       expiry: ticket.expiry,
       networkId: ticket.networkId,
+      notBefore: ticket.notBefore ?? null,
+      sourceRef: ticket.sourceRef ? Array.from(ticket.sourceRef) : null,
     };
 
     // Convert signatures to program format
@@ -1337,7 +9885,7 @@ export class MultisigAdminClient extends MultisigVaultClient {
         systemProgram: SystemProgram.programId,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       } as any)
-      .remainingAccounts(remainingAccounts)
+      .remainingAccounts([...remainingAccounts, ...this.getMintAccounts(ticket.withdrawals)])
       .rpc();
 
     console.log(`✅ Admin Withdrew assets from vault`);
@@ -1376,6 +9924,381 @@ export class MultisigAdminClient extends MultisigVaultClient {
 
     return this.adminWithdraw(ticket, ethKeypairs);
   }
+
+  /**
+   * Approve `programId` for `executeStrategy` (admin threshold), creating the strategy allowlist on first use
+   */
+  async addStrategy(
+    programId: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: AddStrategyTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      programId,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signAddStrategyTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .addStrategy(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        strategyAllowlist: this.getStrategyAllowlistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Approved strategy ${programId.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Remove `programId` from the strategy allowlist (admin threshold)
+   */
+  async removeStrategy(
+    programId: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: RemoveStrategyTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      programId,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signRemoveStrategyTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .removeStrategy(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        strategyAllowlist: this.getStrategyAllowlistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Removed strategy ${programId.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Register `cranker` for crank instructions (admin threshold), creating the crank registry on first use. From then on only registered crankers may crank
+   */
+  async addCranker(
+    cranker: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: AddCrankerTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      cranker,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signAddCrankerTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .addCranker(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        crankRegistry: this.getCrankRegistryAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Registered cranker ${cranker.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Remove `cranker` from the crank registry (admin threshold)
+   */
+  async removeCranker(
+    cranker: PublicKey,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: RemoveCrankerTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      cranker,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signRemoveCrankerTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .removeCranker(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        crankRegistry: this.getCrankRegistryAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Removed cranker ${cranker.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Pay registered crankers `incentiveLamports` from the treasury per successful crank (admin threshold)
+   */
+  async setCrankIncentive(
+    incentiveLamports: BN,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: SetCrankIncentiveTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      incentive: incentiveLamports,
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signSetCrankIncentiveTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const tx = await this.program.methods
+      .setCrankIncentive(ticket, sigsArg)
+      .accounts({
+        vault: vaultPda,
+        crankRegistry: this.getCrankRegistryAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log(`✅ Set crank incentive to ${incentiveLamports.toString()} lamports`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Execute `instruction` against an approved strategy program with the vault PDA as signer
+   * (admin threshold).
+   */
+  async executeStrategy(
+    instruction: anchor.web3.TransactionInstruction,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const ticket: StrategyTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      programId: instruction.programId,
+      accounts: instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isSigner: k.isSigner,
+        isWritable: k.isWritable,
+      })),
+      dataHash: keccak256(instruction.data),
+      expiry: new BN(currentTimestamp + expiryDurationSeconds),
+      networkId: new BN(this.networkId),
+    };
+
+    const sigsArg = MultisigVaultClient.sortBySignerAddress(ethKeypairs)
+      .map(kp => this.signStrategyTicket(ticket, kp))
+      .map(s => ({
+        signature: Array.from(s.signature),
+        recoveryId: s.recoveryId,
+      }));
+
+    const ticketArg = {
+      ...ticket,
+      dataHash: Array.from(ticket.dataHash),
+    };
+
+    // The vault PDA signs inside the program, so it is never a transaction signer here.
+    const remainingAccounts = [
+      { pubkey: instruction.programId, isWritable: false, isSigner: false },
+      ...instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isWritable: k.isWritable,
+        isSigner: k.isSigner && !k.pubkey.equals(vaultPda),
+      })),
+    ];
+
+    const tx = await this.program.methods
+      .executeStrategy(ticketArg, sigsArg, Buffer.from(instruction.data))
+      .accounts({
+        vault: vaultPda,
+        strategyAllowlist: this.getStrategyAllowlistAddress(vaultPda)[0],
+        nonceAccount: noncePda,
+        payer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Strategy executed`);
+    console.log(`   Program: ${instruction.programId.toBase58()}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
+
+  /**
+   * Execute an arbitrary CPI approved by all signers, with the vault PDA as signer.
+   * Requires the program to be built with the `authorized-cpi` feature.
+   */
+  async executeAuthorizedCpi(
+    instruction: anchor.web3.TransactionInstruction,
+    requestId: number,
+    ethKeypairs: EthereumKeypair[],
+    expiryDurationSeconds: number = 3600,
+  ): Promise<string> {
+    const [vaultPda] = this.getVaultAddress(this.vaultSeed, this.parentVault);
+    const [noncePda] = this.getAdminNonceAddress(vaultPda, new BN(requestId));
+
+    const actualPayer = this.provider.wallet.publicKey;
+
+    const currentTimestamp = Math.floor(Date.now() / 1000);
+    const expiryTimestamp = currentTimestamp + expiryDurationSeconds;
+
+    const ticket: AuthorizedCpiTicket = {
+      version: TICKET_VERSION,
+      requestId: new BN(requestId),
+      vault: vaultPda,
+      programId: instruction.programId,
+      accounts: instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isSigner: k.isSigner,
+        isWritable: k.isWritable,
+      })),
+      dataHash: keccak256(instruction.data),
+      expiry: new BN(expiryTimestamp),
+      networkId: new BN(this.networkId),
+    };
+
+    const signersWithSigs = MultisigVaultClient.sortBySignerAddress(ethKeypairs).map(kp => this.signAuthorizedCpiTicket(ticket, kp));
+
+    const sigsArg = signersWithSigs.map(s => ({
+      signature: Array.from(s.signature),
+      recoveryId: s.recoveryId,
+    }));
+
+    const ticketArg = {
+      ...ticket,
+      dataHash: Array.from(ticket.dataHash),
+    };
+
+    // The vault PDA signs inside the program, so it is never a transaction signer here.
+    const remainingAccounts = [
+      { pubkey: instruction.programId, isWritable: false, isSigner: false },
+      ...instruction.keys.map(k => ({
+        pubkey: k.pubkey,
+        isWritable: k.isWritable,
+        isSigner: k.isSigner && !k.pubkey.equals(vaultPda),
+      })),
+    ];
+
+    // Not in the default IDL: the instruction only exists behind the `authorized-cpi` feature.
+    const tx = await (this.program.methods as any)
+      .executeAuthorizedCpi(ticketArg, sigsArg, Buffer.from(instruction.data))
+      .accounts({
+        vault: vaultPda,
+        nonceAccount: noncePda,
+        payer: actualPayer,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    console.log(`✅ Authorized CPI executed`);
+    console.log(`   Program: ${instruction.programId.toBase58()}`);
+    console.log(`   Request ID: ${requestId}`);
+    console.log(`   Transaction: ${tx}`);
+
+    return tx;
+  }
 }
 
 function expandHome(p: string): string {
@@ -1488,22 +10411,24 @@ export function setupUserClient(
   authorityOrWallet: Keypair | Wallet | PublicKey, 
   providerUrl: string,
   vaultSeed: string,
+  domainSuffix: string = "",
 ): MultisigVaultClient {
   const provider = setupProvider(authorityOrWallet, providerUrl);
   const program = new Program(idl, provider) as Program<StrikeExample>;
   const networkId = getNetworkId(providerUrl);
 
-  return new MultisigVaultClient(program, provider, vaultSeed, networkId);
+  return new MultisigVaultClient(program, provider, vaultSeed, networkId, domainSuffix);
 }
 
 export function setupAdminClient(
   authorityOrWallet: Keypair | Wallet | PublicKey, 
   providerUrl: string,
   vaultSeed: string,
+  domainSuffix: string = "",
 ): MultisigAdminClient {
   const provider = setupProvider(authorityOrWallet, providerUrl);
   const program = new Program(idl, provider) as Program<StrikeExample>;
   const networkId = getNetworkId(providerUrl);
 
-  return new MultisigAdminClient(program, provider, vaultSeed, networkId);
+  return new MultisigAdminClient(program, provider, vaultSeed, networkId, domainSuffix);
 }
@@ -565,6 +565,51 @@ describe("Admin Functions Tests", () => {
     });
   });
 
+  describe("Log Verbosity", () => {
+    it("should default to itemized logs", async function() {
+      this.timeout(30000);
+
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.logVerbosity).to.have.property('itemized');
+    });
+
+    it("should switch to compact logs with one summary per instruction", async function() {
+      this.timeout(60000);
+
+      await adminClient.addAsset({ sol: {} }, Date.now(), [ethKeypair1, ethKeypair2, ethKeypair3]);
+      await adminClient.setLogVerbosity(
+        { compact: {} },
+        Date.now() + 1,
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+      );
+
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.logVerbosity).to.have.property('compact');
+
+      const deposits: AssetAmount[] = [
+        { asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) },
+        { asset: { sol: {} }, amount: new BN(0.2 * LAMPORTS_PER_SOL) },
+      ];
+      const tx = await userClient.deposit(deposits, Date.now());
+
+      const events = await userClient.getTransactionEvents(tx);
+      expect(events.map(e => e.name)).to.deep.equal(["batchSummaryEvent"]);
+      expect(events[0].data.lines).to.equal(2);
+      expect(events[0].data.totals[0].amount.toString()).to.equal((0.3 * LAMPORTS_PER_SOL).toString());
+    });
+
+    it("should fail with insufficient signatures", async function() {
+      this.timeout(30000);
+
+      try {
+        await adminClient.setLogVerbosity({ compact: {} }, Date.now(), [ethKeypair1]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("Insufficient signatures provided");
+      }
+    });
+  });
+
   describe("Create Vault Token Account", () => {
     it("should successfully create vault token account", async function() {
       this.timeout(30000);
@@ -716,6 +761,110 @@ describe("Admin Functions Tests", () => {
       expect(vaultData.whitelistedAssets).to.have.lengthOf(1);
     });
 
+    it("should count signer weights toward the admin threshold", async function() {
+      this.timeout(60000);
+
+      const heavy = MultisigAdminClient.generateEthereumKeypair();
+      const light1 = MultisigAdminClient.generateEthereumKeypair();
+      const light2 = MultisigAdminClient.generateEthereumKeypair();
+
+      await adminClient.rotateValidators(
+        [heavy.address, light1.address, light2.address],
+        3,
+        3,
+        Date.now(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+        3600,
+        [3, 1, 1],
+      );
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.signers.map((s: any) => s.weight)).to.deep.equal([3, 1, 1]);
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      // The two light signers weigh 2 of the 3 needed
+      const solAsset: Asset = { sol: {} };
+      try {
+        await adminClient.addAsset(solAsset, Date.now(), [light1, light2]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        const errorStr = error.toString() + (error.logs ? error.logs.join(' ') : '');
+        expect(errorStr).to.match(/Not enough valid signatures|InsufficientValidSignatures/);
+      }
+
+      // The heavy signer meets it alone
+      await adminClient.addAsset(solAsset, Date.now(), [heavy]);
+      expect((await adminClient.getVaultData()).whitelistedAssets).to.have.lengthOf(1);
+    });
+
+    it("should ignore signatures from expired validators", async function() {
+      this.timeout(60000);
+
+      const expiring = MultisigAdminClient.generateEthereumKeypair();
+      const newEthKeypair2 = MultisigAdminClient.generateEthereumKeypair();
+      const newEthKeypair3 = MultisigAdminClient.generateEthereumKeypair();
+
+      await adminClient.rotateValidators(
+        [expiring.address, newEthKeypair2.address, newEthKeypair3.address],
+        2,
+        2,
+        Date.now(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+        3600,
+        [1, 1, 1],
+        [Math.floor(Date.now() / 1000) + 5, null, null],
+      );
+
+      await new Promise(resolve => setTimeout(resolve, 10000));
+
+      const solAsset: Asset = { sol: {} };
+      try {
+        await adminClient.addAsset(solAsset, Date.now(), [expiring, newEthKeypair2]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        const errorStr = error.toString() + (error.logs ? error.logs.join(' ') : '');
+        expect(errorStr).to.match(/Not enough valid signatures|InsufficientValidSignatures/);
+      }
+
+      await adminClient.addAsset(solAsset, Date.now(), [newEthKeypair2, newEthKeypair3]);
+      expect((await adminClient.getVaultData()).whitelistedAssets).to.have.lengthOf(1);
+    });
+
+    it("should let a separate admin set approve admin ops", async function() {
+      this.timeout(60000);
+
+      const cold1 = MultisigAdminClient.generateEthereumKeypair();
+      const cold2 = MultisigAdminClient.generateEthereumKeypair();
+
+      await adminClient.rotateAdminSigners(
+        [
+          { address: cold1.address, weight: 1, validUntil: null },
+          { address: cold2.address, weight: 1, validUntil: null },
+        ],
+        2,
+        Date.now(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+      );
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.adminSigners).to.have.lengthOf(2);
+      expect(vaultData.signers).to.have.lengthOf(3);
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      // The operational set no longer approves admin ops
+      const solAsset: Asset = { sol: {} };
+      try {
+        await adminClient.addAsset(solAsset, Date.now(), [ethKeypair1, ethKeypair2, ethKeypair3]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        const errorStr = error.toString() + (error.logs ? error.logs.join(' ') : '');
+        expect(errorStr).to.match(/Not enough valid signatures|InsufficientValidSignatures/);
+      }
+
+      await adminClient.addAsset(solAsset, Date.now(), [cold1, cold2]);
+      expect((await adminClient.getVaultData()).whitelistedAssets).to.have.lengthOf(1);
+    });
+
     it("should fail to use old validators after rotation", async function() {
       this.timeout(60000);
       
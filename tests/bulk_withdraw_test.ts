@@ -17,6 +17,7 @@ import {
   computeVaultSeed,
   Asset,
   EthereumKeypair,
+  TICKET_VERSION,
 } from "../src/client";
 
 describe("Bulk Withdraw Tests", () => {
@@ -351,7 +352,7 @@ describe("Bulk Withdraw Tests", () => {
     it("should reject bulk withdrawal with empty tickets array", async function() {
       this.timeout(30000);
       
-      const bulkTicket = { tickets: [] };
+      const bulkTicket = { version: TICKET_VERSION, tickets: [] };
       
       try {
         await userClient.bulkWithdraw(
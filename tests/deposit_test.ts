@@ -4,6 +4,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
 import {
+  createAccount,
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
@@ -235,6 +236,45 @@ describe("Deposit Tests", () => {
       
       expect(deposited).to.be.closeTo(0.3, 0.001);
     });
+
+    it("should accumulate SOL deposits in the user's deposit record", async function() {
+      this.timeout(30000);
+
+      const solTotal = async () => {
+        const record = await userClient.getDepositRecord();
+        const entry = record?.deposits.find((d: any) => 'sol' in d.asset);
+        return entry ? entry.amount.toNumber() : 0;
+      };
+
+      const before = await solTotal();
+      await userClient.depositSol(0.2, Date.now());
+      await userClient.depositSol(0.3, Date.now() + 1);
+
+      const record = await userClient.getDepositRecord();
+      expect(record!.user.toBase58()).to.equal(userClient.provider.wallet.publicKey.toBase58());
+      expect(await solTotal() - before).to.equal(0.5 * LAMPORTS_PER_SOL);
+    });
+
+    it("should mint SOL receipts once the vault issues them", async function() {
+      this.timeout(60000);
+
+      await adminClient.createReceiptMint({ sol: {} }, 9, Date.now(), [ethKeypair1, ethKeypair2, ethKeypair3]);
+      const [receiptMint] = userClient.getReceiptMintAddress(vaultPda, { sol: {} });
+      const userReceipts = await getOrCreateAssociatedTokenAccount(
+        connection,
+        user,
+        receiptMint,
+        user.publicKey,
+      );
+
+      const deposits: AssetAmount[] = [
+        { asset: { sol: {} }, amount: new BN(0.25 * LAMPORTS_PER_SOL) },
+      ];
+      await userClient.deposit(deposits, Date.now(), await userClient.getReceiptAccounts(deposits));
+
+      const account = await getAccount(connection, userReceipts.address);
+      expect(account.amount.toString()).to.equal((0.25 * LAMPORTS_PER_SOL).toString());
+    });
   });
 
   describe("SPL Token Deposits", () => {
@@ -384,6 +424,51 @@ describe("Deposit Tests", () => {
       }
     });
 
+    it("should fail when user token account is not the associated token account", async function() {
+      this.timeout(30000);
+
+      // Auxiliary (non-ATA) token account owned by the same user.
+      const auxTokenAccount = await createAccount(
+        connection,
+        user,
+        testMint,
+        user.publicKey,
+        Keypair.generate()
+      );
+      await mintTo(
+        connection,
+        authority,
+        testMint,
+        auxTokenAccount,
+        authority.publicKey,
+        100000000
+      );
+
+      const deposits: AssetAmount[] = [
+        { asset: { splToken: { mint: testMint } }, amount: new BN(100000000) },
+      ];
+
+      const remainingAccounts = [
+        {
+          pubkey: auxTokenAccount,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: vaultTokenAccount.address,
+          isWritable: true,
+          isSigner: false,
+        },
+      ];
+
+      try {
+        await userClient.deposit(deposits, Date.now(), remainingAccounts);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("TokenAccountNotFound");
+      }
+    });
+
     it("should fail when depositing zero SPL tokens", async function() {
       this.timeout(30000);
       
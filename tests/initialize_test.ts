@@ -400,6 +400,72 @@ describe("Initialize Vault Tests", () => {
     });
   });
 
+  describe("Domain Suffix", () => {
+    it("should store the domain suffix set at initialization", async function() {
+      this.timeout(30000);
+
+      const kp1 = MultisigAdminClient.generateEthereumKeypair();
+      const kp2 = MultisigAdminClient.generateEthereumKeypair();
+
+      const mThreshold = 2;
+      const ethAddresses = [kp1.address, kp2.address];
+
+      const vaultSeed = computeVaultSeed(ethAddresses, mThreshold);
+      adminClient = setupAdminClient(authority, ANCHOR_PROVIDER_URL, vaultSeed, "fleet-a");
+
+      await adminClient.initialize(mThreshold, ethAddresses.length, ethAddresses);
+
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.domainSuffix).to.equal("fleet-a");
+    });
+
+    it("should reject tickets signed under a different domain suffix", async function() {
+      this.timeout(60000);
+
+      const kp1 = MultisigAdminClient.generateEthereumKeypair();
+      const kp2 = MultisigAdminClient.generateEthereumKeypair();
+
+      const mThreshold = 2;
+      const ethAddresses = [kp1.address, kp2.address];
+
+      const vaultSeed = computeVaultSeed(ethAddresses, mThreshold);
+      adminClient = setupAdminClient(authority, ANCHOR_PROVIDER_URL, vaultSeed, "fleet-a");
+      await adminClient.initialize(mThreshold, ethAddresses.length, ethAddresses);
+
+      const otherFleetClient = setupAdminClient(authority, ANCHOR_PROVIDER_URL, vaultSeed, "fleet-b");
+
+      try {
+        await otherFleetClient.addAsset({ sol: {} }, Date.now(), [kp1, kp2]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.match(/Not enough valid signatures|InsufficientValidSignatures/i);
+      }
+
+      await adminClient.addAsset({ sol: {} }, Date.now(), [kp1, kp2]);
+      const vaultData = await adminClient.getVaultData();
+      expect(vaultData.whitelistedAssets).to.have.lengthOf(1);
+    });
+
+    it("should fail with domain suffix longer than 32 bytes", async function() {
+      this.timeout(30000);
+
+      const kp1 = MultisigAdminClient.generateEthereumKeypair();
+
+      const mThreshold = 1;
+      const ethAddresses = [kp1.address];
+
+      const vaultSeed = computeVaultSeed(ethAddresses, mThreshold);
+      adminClient = setupAdminClient(authority, ANCHOR_PROVIDER_URL, vaultSeed, "s".repeat(33));
+
+      try {
+        await adminClient.initialize(mThreshold, ethAddresses.length, ethAddresses);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.match(/Domain suffix too long|DomainSuffixTooLong/i);
+      }
+    });
+  });
+
   describe("Edge Cases", () => {
     it("should handle vault with all signers having same first byte", async function() {
       this.timeout(30000);
@@ -1,14 +1,26 @@
 import { describe, it, before, beforeEach } from "mocha";
 import { expect } from "chai";
 import * as anchor from "@coral-xyz/anchor";
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
 import BN from "bn.js";
 import {
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   getAccount,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  ExtensionType,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   MultisigAdminClient,
@@ -20,6 +32,7 @@ import {
   AssetAmount,
   WithdrawalTicket,
   EthereumKeypair,
+  TICKET_VERSION,
 } from "../src/client";
 
 describe("Withdraw Tests", () => {
@@ -205,6 +218,48 @@ describe("Withdraw Tests", () => {
       expect(withdrawn).to.be.closeTo(withdrawAmount, 0.001);
     });
 
+    it("should emit a WithdrawEvent per withdrawal line", async function() {
+      this.timeout(30000);
+
+      const requestId = getUniqueRequestId();
+      const ticket = userClient.createWithdrawalTicket(
+        recipient.publicKey,
+        [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+        requestId,
+        Math.floor(Date.now() / 1000) + 3600,
+      );
+      const tx = await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2], [], "evt-test");
+
+      const events = await userClient.getTransactionEvents(tx);
+      expect(events).to.have.lengthOf(1);
+      expect(events[0].name).to.equal("withdrawEvent");
+      expect(events[0].data.requestId.toString()).to.equal(requestId.toString());
+      expect(events[0].data.recipient.toBase58()).to.equal(recipient.publicKey.toBase58());
+      expect(events[0].data.asset).to.have.property('sol');
+      expect(events[0].data.amount.toString()).to.equal((0.1 * LAMPORTS_PER_SOL).toString());
+      expect(events[0].data.validSigners).to.equal(2);
+      expect(events[0].data.metadata).to.equal("evt-test");
+    });
+
+    it("should fold the request id into the vault's processed root", async function() {
+      this.timeout(30000);
+
+      const before = await userClient.getVaultData();
+      const requestId = getUniqueRequestId();
+      await userClient.createAndExecuteWithdrawal(
+        recipient.publicKey,
+        0.1,
+        requestId,
+        [ethKeypair1, ethKeypair2],
+        3600
+      );
+      const after = await userClient.getVaultData();
+
+      expect(after.processedCount.toString()).to.equal(before.processedCount.addn(1).toString());
+      const expected = userClient.nextProcessedRoot(before.processedRoot, new BN(requestId));
+      expect(after.processedRoot.equals(Buffer.from(expected))).to.be.true;
+    });
+
     it("should succeed with more than M signatures", async function() {
       this.timeout(30000);
       
@@ -240,6 +295,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(getUniqueRequestId()),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -338,6 +394,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -402,6 +459,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: fakeVault, // Wrong vault
         recipient: recipient.publicKey,
@@ -433,6 +491,7 @@ describe("Withdraw Tests", () => {
       
       // Create ticket with fake recipient
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: fakeRecipient,
@@ -445,7 +504,7 @@ describe("Withdraw Tests", () => {
       // or the signature won't match. Let's use the low-level withdraw method
       try {
         // Sign with fake recipient in ticket
-        const signersWithSigs = [ethKeypair1, ethKeypair2].map(kp => 
+        const signersWithSigs = MultisigVaultClient.sortBySignerAddress([ethKeypair1, ethKeypair2]).map(kp => 
           userClient.signWithdrawalTicket(ticket, kp)
         );
         
@@ -490,6 +549,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -519,6 +579,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -542,6 +603,7 @@ describe("Withdraw Tests", () => {
       const expiryTimestamp = Math.floor(Date.now() / 1000) + 3600;
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -682,6 +744,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -727,6 +790,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -775,6 +839,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -806,6 +871,7 @@ describe("Withdraw Tests", () => {
       ];
       
       const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
         requestId: new BN(requestId),
         vault: vaultPda,
         recipient: recipient.publicKey,
@@ -836,6 +902,587 @@ describe("Withdraw Tests", () => {
     });
   });
 
+  describe("Token-2022 Transfer Fee Withdrawals", () => {
+    const FEE_BASIS_POINTS = 100; // 1%
+    let feeMint: PublicKey;
+    let userFeeTokenAccount: any;
+    let recipientFeeTokenAccount: any;
+    let vaultFeeTokenAccount: any;
+
+    before(async function() {
+      this.timeout(60000);
+
+      const mintKeypair = Keypair.generate();
+      feeMint = mintKeypair.publicKey;
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority.publicKey,
+          newAccountPubkey: feeMint,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint,
+          authority.publicKey,
+          authority.publicKey,
+          FEE_BASIS_POINTS,
+          BigInt(1000000000000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMint, 9, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(connection, tx, [authority, mintKeypair]);
+
+      userFeeTokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection, user, feeMint, user.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      );
+      recipientFeeTokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection, recipient, feeMint, recipient.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      );
+      vaultFeeTokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection, authority, feeMint, vaultPda, true, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      );
+
+      await mintTo(
+        connection,
+        authority,
+        feeMint,
+        userFeeTokenAccount.address,
+        authority.publicKey,
+        1000000000000,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await adminClient.addAsset(
+        { splToken: { mint: feeMint } },
+        getUniqueRequestId(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+        3600
+      );
+    });
+
+    it("should credit the vault with the net amount on deposit", async function() {
+      this.timeout(30000);
+
+      const depositAmount = new BN(100000000000); // 100 tokens
+      const vaultBefore = await getAccount(connection, vaultFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+
+      await userClient.deposit(
+        [{ asset: { splToken: { mint: feeMint } }, amount: depositAmount }],
+        getUniqueRequestId(),
+        [
+          { pubkey: userFeeTokenAccount.address, isWritable: true, isSigner: false },
+          { pubkey: vaultFeeTokenAccount.address, isWritable: true, isSigner: false },
+        ],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const vaultAfter = await getAccount(connection, vaultFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+      const expectedNet = 100000000000n - (100000000000n * BigInt(FEE_BASIS_POINTS)) / 10000n;
+      expect((vaultAfter.amount - vaultBefore.amount).toString()).to.equal(expectedNet.toString());
+    });
+
+    it("should deliver the ticket amount net of the transfer fee", async function() {
+      this.timeout(30000);
+
+      const netAmount = new BN(10000000000); // 10 tokens
+      const ticket: WithdrawalTicket = {
+        version: TICKET_VERSION,
+        requestId: new BN(getUniqueRequestId()),
+        vault: vaultPda,
+        recipient: recipient.publicKey,
+        withdrawals: [{ asset: { splToken: { mint: feeMint } }, amount: netAmount }],
+        expiry: new BN(Math.floor(Date.now() / 1000) + 3600),
+        networkId: new BN(102),
+      };
+
+      const recipientBefore = await getAccount(connection, recipientFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+      const vaultBefore = await getAccount(connection, vaultFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+
+      await userClient.withdraw(
+        ticket,
+        [ethKeypair1, ethKeypair2],
+        [
+          { pubkey: recipientFeeTokenAccount.address, isWritable: true, isSigner: false },
+          { pubkey: vaultFeeTokenAccount.address, isWritable: true, isSigner: false },
+        ],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const recipientAfter = await getAccount(connection, recipientFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+      const vaultAfter = await getAccount(connection, vaultFeeTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+
+      expect((recipientAfter.amount - recipientBefore.amount).toString()).to.equal(netAmount.toString());
+      expect(vaultBefore.amount - vaultAfter.amount > BigInt(netAmount.toString())).to.be.true;
+    });
+  });
+
+  describe("NFT Withdrawals", () => {
+    let nftMint: PublicKey;
+    let userNftAccount: any;
+    let recipientNftAccount: any;
+    let vaultNftAccount: any;
+
+    before(async function() {
+      this.timeout(60000);
+
+      nftMint = await createMint(connection, authority, authority.publicKey, null, 0);
+      userNftAccount = await getOrCreateAssociatedTokenAccount(
+        connection, user, nftMint, user.publicKey
+      );
+      recipientNftAccount = await getOrCreateAssociatedTokenAccount(
+        connection, recipient, nftMint, recipient.publicKey
+      );
+      vaultNftAccount = await getOrCreateAssociatedTokenAccount(
+        connection, authority, nftMint, vaultPda, true
+      );
+      await mintTo(connection, authority, nftMint, userNftAccount.address, authority.publicKey, 1);
+
+      await adminClient.addAsset(
+        { nft: { mint: nftMint } },
+        getUniqueRequestId(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+        3600
+      );
+
+      await userClient.deposit(
+        [{ asset: { nft: { mint: nftMint } }, amount: new BN(1) }],
+        getUniqueRequestId(),
+        [
+          { pubkey: userNftAccount.address, isWritable: true, isSigner: false },
+          { pubkey: vaultNftAccount.address, isWritable: true, isSigner: false },
+        ]
+      );
+    });
+
+    it("should reject NFT withdrawals of more than one token", async function() {
+      this.timeout(30000);
+
+      const ticket = adminClient.createWithdrawalTicket(
+        recipient.publicKey,
+        [{ asset: { nft: { mint: nftMint } }, amount: new BN(2) }],
+        getUniqueRequestId(),
+        Math.floor(Date.now() / 1000) + 3600,
+      );
+
+      try {
+        await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2], [
+          { pubkey: recipientNftAccount.address, isWritable: true, isSigner: false },
+          { pubkey: vaultNftAccount.address, isWritable: true, isSigner: false },
+        ]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expectErrorMessage(error, 'InvalidNftAmount');
+      }
+    });
+
+    it("should withdraw the NFT to the recipient", async function() {
+      this.timeout(30000);
+
+      const ticket = adminClient.createWithdrawalTicket(
+        recipient.publicKey,
+        [{ asset: { nft: { mint: nftMint } }, amount: new BN(1) }],
+        getUniqueRequestId(),
+        Math.floor(Date.now() / 1000) + 3600,
+      );
+
+      await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2], [
+        { pubkey: recipientNftAccount.address, isWritable: true, isSigner: false },
+        { pubkey: vaultNftAccount.address, isWritable: true, isSigner: false },
+      ]);
+
+      const recipientAfter = await getAccount(connection, recipientNftAccount.address);
+      const vaultAfter = await getAccount(connection, vaultNftAccount.address);
+      expect(recipientAfter.amount.toString()).to.equal("1");
+      expect(vaultAfter.amount.toString()).to.equal("0");
+    });
+  });
+
+  describe("Memo Withdrawals", () => {
+    it("should withdraw with a memo logged through the SPL Memo program", async function() {
+      this.timeout(30000);
+
+      const memo = `exchange-ref-${getUniqueRequestId()}`;
+      const ticket = userClient.createWithdrawalTicket(
+        recipient.publicKey,
+        [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+        getUniqueRequestId(),
+        Math.floor(Date.now() / 1000) + 3600,
+        memo,
+      );
+
+      const tx = await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2]);
+
+      const confirmed = await connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const logs = confirmed?.meta?.logMessages ?? [];
+      expect(logs.some(log => log.includes(memo))).to.be.true;
+    });
+
+    it("should commit the memo into the ticket hash", async function() {
+      const ticket = userClient.createWithdrawalTicket(
+        recipient.publicKey,
+        [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+        getUniqueRequestId(),
+        Math.floor(Date.now() / 1000) + 3600,
+        "signed-memo",
+      );
+      const hashSigned = Buffer.from(userClient.createWithdrawalTicketHash(ticket));
+      const hashTampered = Buffer.from(
+        userClient.createWithdrawalTicketHash({ ...ticket, memo: "tampered-memo" })
+      );
+
+      expect(hashSigned.equals(hashTampered)).to.be.false;
+      expect(
+        Buffer.from(userClient.createWithdrawalTicketHash({ ...ticket, memo: null }))
+          .equals(hashSigned)
+      ).to.be.false;
+    });
+  });
+
+  describe("Recipient Allowances", () => {
+    it("should stop withdrawals beyond the recipient allowance", async function() {
+      this.timeout(60000);
+
+      const cappedRecipient = Keypair.generate().publicKey;
+      await adminClient.setAllowance(
+        cappedRecipient,
+        { sol: {} },
+        new BN(0.2 * LAMPORTS_PER_SOL),
+        getUniqueRequestId(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+      );
+
+      const expiry = Math.floor(Date.now() / 1000) + 3600;
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          cappedRecipient,
+          [{ asset: { sol: {} }, amount: new BN(0.15 * LAMPORTS_PER_SOL) }],
+          getUniqueRequestId(),
+          expiry,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+
+      try {
+        await userClient.withdraw(
+          userClient.createWithdrawalTicket(
+            cappedRecipient,
+            [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+            getUniqueRequestId(),
+            expiry,
+          ),
+          [ethKeypair1, ethKeypair2],
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("AllowanceExceeded");
+      }
+
+      const allowance = await userClient.getAllowance(cappedRecipient, { sol: {} });
+      expect(allowance!.consumed.toString()).to.equal((0.15 * LAMPORTS_PER_SOL).toString());
+    });
+  });
+
+  describe("Recipient Allowlist", () => {
+    it("should only pay allowlisted recipients while allowlist mode is on", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      const listed = Keypair.generate().publicKey;
+      await adminClient.addRecipient(listed, getUniqueRequestId(), admins);
+      await adminClient.setRecipientAllowlist(true, getUniqueRequestId(), admins);
+      expect((await userClient.getVaultData()).recipientAllowlist).to.be.true;
+
+      const expiry = Math.floor(Date.now() / 1000) + 3600;
+      try {
+        await userClient.withdraw(
+          userClient.createWithdrawalTicket(
+            Keypair.generate().publicKey,
+            [{ asset: { sol: {} }, amount: new BN(0.01 * LAMPORTS_PER_SOL) }],
+            getUniqueRequestId(),
+            expiry,
+          ),
+          [ethKeypair1, ethKeypair2],
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("RecipientNotAllowlisted");
+      }
+
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          listed,
+          [{ asset: { sol: {} }, amount: new BN(0.01 * LAMPORTS_PER_SOL) }],
+          getUniqueRequestId(),
+          expiry,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+      expect(await connection.getBalance(listed)).to.equal(0.01 * LAMPORTS_PER_SOL);
+
+      // Later tests pay arbitrary recipients
+      await adminClient.setRecipientAllowlist(false, getUniqueRequestId(), admins);
+    });
+  });
+
+  describe("Minimum Withdrawal", () => {
+    it("should reject withdrawal lines below the asset minimum", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      const min = new BN(0.005 * LAMPORTS_PER_SOL);
+      await adminClient.setMinWithdrawal({ sol: {} }, min, getUniqueRequestId(), admins);
+      const vaultData = await userClient.getVaultData();
+      expect(vaultData.minWithdrawals[0].amount.toString()).to.equal(min.toString());
+
+      const recipient = Keypair.generate().publicKey;
+      const expiry = Math.floor(Date.now() / 1000) + 3600;
+      try {
+        await userClient.withdraw(
+          userClient.createWithdrawalTicket(
+            recipient,
+            [{ asset: { sol: {} }, amount: min.subn(1) }],
+            getUniqueRequestId(),
+            expiry,
+          ),
+          [ethKeypair1, ethKeypair2],
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("BelowMinWithdrawal");
+      }
+
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          recipient,
+          [{ asset: { sol: {} }, amount: min }],
+          getUniqueRequestId(),
+          expiry,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+      expect(await connection.getBalance(recipient)).to.equal(min.toNumber());
+
+      // Later tests withdraw small amounts
+      await adminClient.setMinWithdrawal({ sol: {} }, new BN(0), getUniqueRequestId(), admins);
+    });
+
+    it("should sweep token balances below the minimum to a destination", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      const dustMint = await createMint(connection, authority, authority.publicKey, null, 6);
+      await adminClient.addAsset({ splToken: { mint: dustMint } }, getUniqueRequestId(), admins, 3600);
+      await adminClient.setMinWithdrawal(
+        { splToken: { mint: dustMint } },
+        new BN(1000),
+        getUniqueRequestId(),
+        admins,
+      );
+
+      const vaultDust = await getOrCreateAssociatedTokenAccount(
+        connection, authority, dustMint, vaultPda, true,
+      );
+      await mintTo(connection, authority, dustMint, vaultDust.address, authority.publicKey, 999);
+
+      const destination = Keypair.generate().publicKey;
+      const destinationDust = await getOrCreateAssociatedTokenAccount(
+        connection, authority, dustMint, destination,
+      );
+
+      await adminClient.sweepDust(destination, [dustMint], getUniqueRequestId(), admins);
+
+      expect(Number((await getAccount(connection, vaultDust.address)).amount)).to.equal(0);
+      expect(Number((await getAccount(connection, destinationDust.address)).amount)).to.equal(999);
+    });
+  });
+
+  describe("Recipient Blocklist", () => {
+    it("should consume tickets paying a blocked recipient without paying out", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      const blocked = Keypair.generate().publicKey;
+      await adminClient.blockRecipient(blocked, getUniqueRequestId(), admins);
+      expect((await userClient.getVaultData()).hasBlocklist).to.be.true;
+      const blocklist = await userClient.getBlocklist();
+      expect(blocklist!.recipients.map((r: PublicKey) => r.toBase58())).to.include(blocked.toBase58());
+
+      const expiry = Math.floor(Date.now() / 1000) + 3600;
+      const ticket = userClient.createWithdrawalTicket(
+        blocked,
+        [{ asset: { sol: {} }, amount: new BN(0.01 * LAMPORTS_PER_SOL) }],
+        getUniqueRequestId(),
+        expiry,
+      );
+      await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2]);
+      expect(await connection.getBalance(blocked)).to.equal(0);
+
+      // The ticket is spent.
+      try {
+        await userClient.withdraw(ticket, [ethKeypair1, ethKeypair2]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("already in use");
+      }
+
+      await adminClient.unblockRecipient(blocked, getUniqueRequestId(), admins);
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          blocked,
+          [{ asset: { sol: {} }, amount: new BN(0.01 * LAMPORTS_PER_SOL) }],
+          getUniqueRequestId(),
+          expiry,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+      expect(await connection.getBalance(blocked)).to.equal(0.01 * LAMPORTS_PER_SOL);
+    });
+  });
+
+  describe("Merkle Claims", () => {
+    it("should pay each leaf of a published root exactly once", async function() {
+      this.timeout(60000);
+
+      const leaves = [0, 1, 2].map(i => ({
+        index: new BN(i),
+        recipient: Keypair.generate().publicKey,
+        assetAmount: { asset: { sol: {} } as Asset, amount: new BN((i + 1) * 0.01 * LAMPORTS_PER_SOL) },
+      }));
+      const { root, proofs } = userClient.buildClaimTree(leaves);
+
+      const requestId = getUniqueRequestId();
+      await userClient.publishClaimRoot(root, requestId, [ethKeypair1, ethKeypair2]);
+
+      for (let i = 0; i < leaves.length; i++) {
+        await userClient.claim(new BN(requestId), leaves[i], proofs[i]);
+        const balance = await connection.getBalance(leaves[i].recipient);
+        expect(balance.toString()).to.equal(leaves[i].assetAmount.amount.toString());
+      }
+
+      try {
+        await userClient.claim(new BN(requestId), leaves[0], proofs[0]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("already in use");
+      }
+
+      const forged = { ...leaves[1], assetAmount: { ...leaves[1].assetAmount, amount: new BN(LAMPORTS_PER_SOL) } };
+      try {
+        await userClient.claim(new BN(requestId), forged, proofs[1]);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidClaimProof");
+      }
+
+      const claimRoot = await userClient.getClaimRoot(new BN(requestId));
+      expect(claimRoot!.claimed.toNumber()).to.equal(3);
+    });
+  });
+
+  describe("Protocol Fee", () => {
+    it("should route the fee on each withdrawal line to the fee treasury", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      try {
+        await adminClient.setFee(1001, getUniqueRequestId(), admins);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidFee");
+      }
+
+      await adminClient.setFee(100, getUniqueRequestId(), admins);
+      expect((await userClient.getVaultData()).feeBps).to.equal(100);
+
+      const [feeTreasuryPda] = userClient.getFeeTreasuryAddress(vaultPda);
+      const feeTreasuryBefore = await connection.getBalance(feeTreasuryPda);
+      const feeRecipient = Keypair.generate().publicKey;
+
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          feeRecipient,
+          [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+          getUniqueRequestId(),
+          Math.floor(Date.now() / 1000) + 3600,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+
+      // 1% of 0.1 SOL stays with the protocol
+      expect(await connection.getBalance(feeRecipient)).to.equal(0.099 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(feeTreasuryPda)).to.equal(
+        feeTreasuryBefore + 0.001 * LAMPORTS_PER_SOL,
+      );
+
+      // Later tests assume fee-free withdrawals
+      await adminClient.setFee(0, getUniqueRequestId(), admins);
+    });
+
+    it("should sweep collected fees to a designated address", async function() {
+      this.timeout(60000);
+
+      const [feeTreasuryPda] = userClient.getFeeTreasuryAddress(vaultPda);
+      const rentExempt = await connection.getMinimumBalanceForRentExemption(8);
+      const collected = (await connection.getBalance(feeTreasuryPda)) - rentExempt;
+      expect(collected).to.be.gt(0);
+
+      const collector = Keypair.generate().publicKey;
+      await adminClient.collectFees(
+        collector,
+        [{ asset: { sol: {} }, amount: new BN(collected) }],
+        getUniqueRequestId(),
+        [ethKeypair1, ethKeypair2, ethKeypair3],
+      );
+
+      expect(await connection.getBalance(collector)).to.equal(collected);
+      expect(await connection.getBalance(feeTreasuryPda)).to.equal(rentExempt);
+    });
+  });
+
+  describe("Relayer Rebate", () => {
+    it("should refund the payer's nonce rent and transaction fee from the treasury", async function() {
+      this.timeout(60000);
+
+      const admins = [ethKeypair1, ethKeypair2, ethKeypair3];
+      await adminClient.setRelayerRebate(new BN(LAMPORTS_PER_SOL), getUniqueRequestId(), admins);
+      expect((await userClient.getVaultData()).maxRelayerRebate.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+      const payer = userClient.provider.wallet.publicKey;
+      const payerBefore = await connection.getBalance(payer);
+      const treasuryBefore = await connection.getBalance(treasuryPda);
+
+      await userClient.withdraw(
+        userClient.createWithdrawalTicket(
+          Keypair.generate().publicKey,
+          [{ asset: { sol: {} }, amount: new BN(0.1 * LAMPORTS_PER_SOL) }],
+          getUniqueRequestId(),
+          Math.floor(Date.now() / 1000) + 3600,
+        ),
+        [ethKeypair1, ethKeypair2],
+      );
+
+      const nonceRent = await connection.getMinimumBalanceForRentExemption(9);
+      expect(await connection.getBalance(payer)).to.equal(payerBefore);
+      expect(treasuryBefore - (await connection.getBalance(treasuryPda))).to.equal(
+        0.1 * LAMPORTS_PER_SOL + nonceRent + 5000,
+      );
+
+      await adminClient.setRelayerRebate(new BN(0), getUniqueRequestId(), admins);
+    });
+  });
+
   describe("Edge Cases", () => {
     it("should not allow different recipients to withdraw with same request_id", async function() {
       this.timeout(60000);
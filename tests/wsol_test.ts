@@ -0,0 +1,149 @@
+import { describe, it, before } from "mocha";
+import { expect } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  createWrappedNativeAccount,
+  createAssociatedTokenAccount,
+  getAccount,
+  NATIVE_MINT,
+} from "@solana/spl-token";
+import {
+  MultisigAdminClient,
+  MultisigVaultClient,
+  setupAdminClient,
+  setupUserClient,
+  computeVaultSeed,
+  EthereumKeypair,
+} from "../src/client";
+
+describe("Wrapped SOL Tests", () => {
+  const ANCHOR_PROVIDER_URL = "http://127.0.0.1:8899";
+
+  let adminClient: MultisigAdminClient;
+  let userClient: MultisigVaultClient;
+  let authority: Keypair;
+  let user: Keypair;
+  let recipient: Keypair;
+  let connection: anchor.web3.Connection;
+
+  let ethKeypair1: EthereumKeypair;
+  let ethKeypair2: EthereumKeypair;
+
+  let vaultPda: PublicKey;
+  let treasuryPda: PublicKey;
+  let userWsol: PublicKey;
+  let recipientWsol: PublicKey;
+
+  let requestIdCounter = Math.floor(Date.now() / 1000);
+
+  function getUniqueRequestId(): number {
+    requestIdCounter += 1;
+    return requestIdCounter;
+  }
+
+  before(async function() {
+    this.timeout(120000);
+
+    connection = new anchor.web3.Connection(ANCHOR_PROVIDER_URL, "confirmed");
+
+    authority = Keypair.generate();
+    user = Keypair.generate();
+    recipient = Keypair.generate();
+
+    for (const kp of [authority, user, recipient]) {
+      const sig = await connection.requestAirdrop(kp.publicKey, 10 * LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig);
+    }
+
+    ethKeypair1 = MultisigAdminClient.generateEthereumKeypair();
+    ethKeypair2 = MultisigAdminClient.generateEthereumKeypair();
+
+    const mThreshold = 2;
+    const ethAddresses = [ethKeypair1.address, ethKeypair2.address];
+
+    const vaultSeed = computeVaultSeed(ethAddresses, mThreshold);
+    adminClient = setupAdminClient(authority, ANCHOR_PROVIDER_URL, vaultSeed);
+    userClient = setupUserClient(user, ANCHOR_PROVIDER_URL, vaultSeed);
+
+    const result = await adminClient.initialize(mThreshold, ethAddresses.length, ethAddresses);
+    vaultPda = result.vaultAddress;
+    [treasuryPda] = adminClient.getTreasuryAddress(vaultPda);
+
+    userWsol = await createWrappedNativeAccount(
+      connection,
+      user,
+      user.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    recipientWsol = await createAssociatedTokenAccount(
+      connection,
+      recipient,
+      NATIVE_MINT,
+      recipient.publicKey
+    );
+  });
+
+  it("should fail to deposit wSOL when SOL is not whitelisted", async function() {
+    this.timeout(30000);
+
+    try {
+      await userClient.depositWsol(new BN(LAMPORTS_PER_SOL / 10), getUniqueRequestId());
+      expect.fail("Should have thrown an error");
+    } catch (error: any) {
+      expect(error.message).to.include("AssetNotWhitelisted");
+    }
+  });
+
+  it("should deposit wSOL and credit the SOL treasury", async function() {
+    this.timeout(60000);
+
+    await adminClient.addAsset({ sol: {} }, getUniqueRequestId(), [ethKeypair1, ethKeypair2]);
+
+    const amount = new BN(LAMPORTS_PER_SOL);
+    const treasuryBefore = await connection.getBalance(treasuryPda);
+    const userWsolBefore = await getAccount(connection, userWsol);
+
+    await userClient.depositWsol(amount, getUniqueRequestId(), "wsol-deposit");
+
+    const treasuryAfter = await connection.getBalance(treasuryPda);
+    const userWsolAfter = await getAccount(connection, userWsol);
+
+    expect(treasuryAfter - treasuryBefore).to.equal(amount.toNumber());
+    expect((userWsolBefore.amount - userWsolAfter.amount).toString()).to.equal(amount.toString());
+  });
+
+  it("should withdraw SOL as wSOL to the recipient", async function() {
+    this.timeout(60000);
+
+    const amount = new BN(LAMPORTS_PER_SOL / 2);
+    const ticket = adminClient.createWithdrawalTicket(
+      recipient.publicKey,
+      [{ asset: { sol: {} }, amount }],
+      getUniqueRequestId(),
+      Math.floor(Date.now() / 1000) + 3600,
+    );
+
+    const treasuryBefore = await connection.getBalance(treasuryPda);
+    const recipientWsolBefore = await getAccount(connection, recipientWsol);
+
+    await adminClient.withdrawWsol(ticket, [ethKeypair1, ethKeypair2]);
+
+    const treasuryAfter = await connection.getBalance(treasuryPda);
+    const recipientWsolAfter = await getAccount(connection, recipientWsol);
+
+    expect(treasuryBefore - treasuryAfter).to.equal(amount.toNumber());
+    expect((recipientWsolAfter.amount - recipientWsolBefore.amount).toString()).to.equal(
+      amount.toString()
+    );
+
+    // The same ticket cannot be redeemed again through the native SOL path.
+    try {
+      await adminClient.withdraw(ticket, [ethKeypair1, ethKeypair2]);
+      expect.fail("Should have thrown an error");
+    } catch (error: any) {
+      expect(error.message).to.match(/already in use|NonceAlreadyUsed/i);
+    }
+  });
+});